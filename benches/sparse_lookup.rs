@@ -0,0 +1,93 @@
+//! Lookups in default and inline storages.
+//!
+//! Default storages have to compile to the same lookup as before inline storages were added,
+//! run `cargo bench --bench sparse_lookup` on both sides of a change to the sparse array to compare.
+
+use core::hint::black_box;
+use shipyard::*;
+use std::time::{Duration, Instant};
+
+struct Position(f32);
+impl Component for Position {
+    type Tracking = track::Untracked;
+}
+
+struct Velocity(f32);
+impl Component for Velocity {
+    type Tracking = track::Untracked;
+}
+
+struct Camera(f32);
+impl Component for Camera {
+    type Tracking = track::Untracked;
+    const INLINE: bool = true;
+}
+
+const ENTITIES: usize = 10_000;
+const ROUNDS: u32 = 200;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    for _ in 0..ROUNDS / 10 {
+        f();
+    }
+
+    let mut best = Duration::MAX;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        f();
+        best = best.min(start.elapsed());
+    }
+
+    println!("{name:<32} {:>10} ns", best.as_nanos());
+}
+
+fn main() {
+    let mut world = World::new();
+
+    let entities: Vec<EntityId> = (0..ENTITIES)
+        .map(|i| {
+            if i % 2 == 0 {
+                world.add_entity((Position(i as f32), Velocity(1.0)))
+            } else {
+                world.add_entity((Position(i as f32),))
+            }
+        })
+        .collect();
+    for &entity in entities.iter().step_by(ENTITIES / 4) {
+        world.add_component(entity, (Camera(0.0),));
+    }
+
+    world.run(
+        |positions: View<Position>, velocities: View<Velocity>, cameras: View<Camera>| {
+            bench("default get", || {
+                for &entity in &entities {
+                    black_box(positions.get(black_box(entity)).ok());
+                }
+            });
+
+            bench("default contains (miss)", || {
+                for &entity in &entities {
+                    black_box(velocities.contains(black_box(entity)));
+                }
+            });
+
+            bench("default iter (2 storages)", || {
+                for (position, velocity) in (&positions, &velocities).iter() {
+                    black_box(position.0 + velocity.0);
+                }
+            });
+
+            bench("inline get", || {
+                for &entity in &entities {
+                    black_box(cameras.get(black_box(entity)).ok());
+                }
+            });
+
+            bench("inline iter (2 storages)", || {
+                for (camera, position) in (&cameras, &positions).iter() {
+                    black_box(camera.0 + position.0);
+                }
+            });
+        },
+    );
+}
@@ -1,57 +1,147 @@
 use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::quote;
+use syn::spanned::Spanned;
 use syn::{Error, Result};
 
 pub(crate) fn expand_component(
     name: syn::Ident,
     generics: syn::Generics,
-    attribute_input: Option<&syn::Attribute>,
+    attrs: &[syn::Attribute],
 ) -> Result<TokenStream> {
-    let tracking = if let Some(tracking_attr) = attribute_input {
-        let mut track_insertion = false;
-        let mut track_modification = false;
-        let mut track_deletion = false;
-        let mut track_removal = false;
+    let mut tracking = Tracks::default();
+    let mut component_name = None;
+    let mut serialize = false;
+    let mut clone = false;
+    let mut inline = false;
 
-        match &tracking_attr.meta {
+    for attr in attrs {
+        if attr.path().is_ident("track") {
+            tracking.parse_list(&attr.meta)?;
+        } else if attr.path().is_ident("shipyard") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("track") {
+                    meta.parse_nested_meta(|meta| tracking.parse_ident(&meta.path))
+                } else if meta.path.is_ident("name") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    component_name = Some(value);
+
+                    Ok(())
+                } else if meta.path.is_ident("serialize") {
+                    serialize = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("clone") {
+                    clone = true;
+
+                    Ok(())
+                } else if meta.path.is_ident("inline") {
+                    inline = true;
+
+                    Ok(())
+                } else {
+                    Err(Error::new(
+                        meta.path.span(),
+                        "Unknown attribute. Possible attributes: track, name, serialize, clone or inline",
+                    ))
+                }
+            })?;
+        }
+    }
+
+    let tracking = syn::Ident::new(tracking.name(), Span::call_site());
+
+    let component_name = component_name.map(|component_name| {
+        quote!(const NAME: ::core::option::Option<&'static str> = ::core::option::Option::Some(#component_name);)
+    });
+
+    let inline = inline.then(|| {
+        quote!(
+            const INLINE: bool = true;
+        )
+    });
+
+    let register = if serialize || clone {
+        let serialize = serialize.then(|| quote!(registrar.serialize::<Self>();));
+        let clone = clone.then(|| quote!(registrar.cloneable::<Self>();));
+
+        Some(quote!(
+            fn register(registrar: &mut ::shipyard::ComponentRegistrar<'_>) {
+                #serialize
+                #clone
+            }
+        ))
+    } else {
+        None
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote!(
+        impl #impl_generics ::shipyard::Component for #name #ty_generics #where_clause {
+            type Tracking = ::shipyard::track::#tracking;
+            #component_name
+            #inline
+            #register
+        }
+    ))
+}
+
+#[derive(Default)]
+struct Tracks {
+    insertion: bool,
+    modification: bool,
+    deletion: bool,
+    removal: bool,
+}
+
+impl Tracks {
+    /// Parses `#[track(...)]`.
+    fn parse_list(&mut self, meta: &syn::Meta) -> Result<()> {
+        match meta {
             syn::Meta::List(list) => {
                 for token in list.tokens.clone() {
                     if let TokenTree::Ident(ident) = token {
-                        if ident == "Insertion" {
-                            track_insertion = true;
-                        } else if ident == "Modification" {
-                            track_modification = true;
-                        } else if ident == "Deletion" {
-                            track_deletion = true;
-                        } else if ident == "Removal" {
-                            track_removal = true;
-                        } else if ident == "All" {
-                            track_insertion = true;
-                            track_modification = true;
-                            track_deletion = true;
-                            track_removal = true;
-                        } else {
-                            return Err(Error::new_spanned(
-                                &ident,
-                                "Track should be either: Insertion, Modification, Deletion, Removal or All.",
-                            ))
-                        }
+                        self.parse_ident(&syn::Path::from(ident))?;
                     }
                 }
-            },
-            _ => {
-                return Err(Error::new_spanned(
-                    &tracking_attr.meta,
-                    "Track should be a list of either: Insertion, Modification, Deletion, Removal or All.",
-                ))
+
+                Ok(())
             }
-        };
+            _ => Err(Error::new_spanned(
+                meta,
+                "Track should be a list of either: Insertion, Modification, Deletion, Removal or All.",
+            )),
+        }
+    }
+    fn parse_ident(&mut self, path: &syn::Path) -> Result<()> {
+        if path.is_ident("Insertion") {
+            self.insertion = true;
+        } else if path.is_ident("Modification") {
+            self.modification = true;
+        } else if path.is_ident("Deletion") {
+            self.deletion = true;
+        } else if path.is_ident("Removal") {
+            self.removal = true;
+        } else if path.is_ident("All") {
+            self.insertion = true;
+            self.modification = true;
+            self.deletion = true;
+            self.removal = true;
+        } else {
+            return Err(Error::new_spanned(
+                path,
+                "Track should be either: Insertion, Modification, Deletion, Removal or All.",
+            ));
+        }
 
-        let tracking = match (
-            track_insertion,
-            track_modification,
-            track_deletion,
-            track_removal,
+        Ok(())
+    }
+    fn name(&self) -> &'static str {
+        match (
+            self.insertion,
+            self.modification,
+            self.deletion,
+            self.removal,
         ) {
             (true, true, true, true) => "All",
             (true, true, true, false) => "InsertionAndModificationAndDeletion",
@@ -69,20 +159,8 @@ pub(crate) fn expand_component(
             (false, false, true, false) => "Deletion",
             (false, false, false, true) => "Removal",
             (false, false, false, false) => "Untracked",
-        };
-
-        syn::Ident::new(tracking, Span::call_site())
-    } else {
-        syn::Ident::new("Untracked", Span::call_site())
-    };
-
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
-
-    Ok(quote!(
-        impl #impl_generics ::shipyard::Component for #name #ty_generics #where_clause {
-            type Tracking = ::shipyard::track::#tracking;
         }
-    ))
+    }
 }
 
 pub(crate) fn expand_unique(name: syn::Ident, generics: syn::Generics) -> TokenStream {
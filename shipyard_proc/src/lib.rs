@@ -5,6 +5,7 @@ mod borrow_info_expand;
 mod component_expand;
 mod into_iter_expand;
 mod label_expand;
+mod system_expand;
 mod world_borrow_expand;
 
 use borrow_expand::expand_borrow;
@@ -12,30 +13,26 @@ use borrow_info_expand::expand_borrow_info;
 use component_expand::{expand_component, expand_unique};
 use into_iter_expand::expand_into_iter;
 use label_expand::expand_label;
+use system_expand::expand_system;
 use world_borrow_expand::expand_world_borrow;
 
-#[proc_macro_derive(Component, attributes(track))]
+#[proc_macro_derive(Component, attributes(track, shipyard))]
 pub fn component(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
 
     let name = input.ident;
     let generics = input.generics;
 
-    let attribute_input: Option<&syn::Attribute> = input
+    let attrs: Vec<syn::Attribute> = input
         .attrs
-        .iter()
+        .into_iter()
         .filter(|attr| match attr.style {
             syn::AttrStyle::Outer => true,
             syn::AttrStyle::Inner(_) => false,
         })
-        .find(|attr| {
-            attr.path()
-                .get_ident()
-                .map(|ident| ident == "track")
-                .unwrap_or(false)
-        });
-
-    expand_component(name, generics, attribute_input)
+        .collect();
+
+    expand_component(name, generics, &attrs)
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
@@ -113,3 +110,29 @@ pub fn into_iter(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
+
+/// Turns a function taking `&T`, `&mut T`, `&Unique<T>`, `&mut Unique<T>` or `EntityId` parameters into a system.
+///
+/// Component parameters become `View`/`ViewMut` and the function runs for each entity that has all of them.\
+/// Unique parameters become `UniqueView`/`UniqueViewMut`, a function with only uniques runs once.\
+/// The original function is kept as an inner function called by the generated system.
+#[proc_macro_attribute]
+pub fn system(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "system doesn't take any argument.",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let function = syn::parse_macro_input!(item as syn::ItemFn);
+
+    expand_system(function)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
@@ -0,0 +1,213 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{Error, Result};
+
+enum Param {
+    Component { ty: syn::Type, mutable: bool },
+    Unique { ty: syn::Type, mutable: bool },
+    EntityId,
+}
+
+pub(crate) fn expand_system(mut function: syn::ItemFn) -> Result<TokenStream> {
+    let mut params = Vec::with_capacity(function.sig.inputs.len());
+    let mut names = Vec::with_capacity(function.sig.inputs.len());
+
+    for input in function.sig.inputs.iter_mut() {
+        let input = match input {
+            syn::FnArg::Typed(input) => input,
+            syn::FnArg::Receiver(receiver) => {
+                return Err(Error::new(
+                    receiver.span(),
+                    "Systems can't take self as parameter.",
+                ))
+            }
+        };
+
+        let name = match &*input.pat {
+            syn::Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                pat.ident.clone()
+            }
+            pat => {
+                return Err(Error::new(
+                    pat.span(),
+                    "System parameters have to be identifiers.",
+                ))
+            }
+        };
+
+        let param = parse_param(&input.ty)?;
+
+        // the inner function receives the unique itself
+        if let Param::Unique { ty, mutable } = &param {
+            *input.ty = if *mutable {
+                syn::parse_quote!(&mut #ty)
+            } else {
+                syn::parse_quote!(&#ty)
+            };
+        }
+
+        params.push(param);
+        names.push(name);
+    }
+
+    let has_components = params
+        .iter()
+        .any(|param| matches!(param, Param::Component { .. }));
+    let has_id = params.iter().any(|param| matches!(param, Param::EntityId));
+
+    if has_id && !has_components {
+        return Err(Error::new(
+            function.sig.span(),
+            "EntityId parameters require at least one component parameter.",
+        ));
+    }
+    if has_components && !matches!(function.sig.output, syn::ReturnType::Default) {
+        return Err(Error::new(
+            function.sig.output.span(),
+            "Systems iterating components can't return a value.",
+        ));
+    }
+
+    let mut view_params = Vec::new();
+    let mut iterated = Vec::new();
+    let mut items = Vec::new();
+    let mut args = Vec::new();
+
+    for (param, name) in params.iter().zip(&names) {
+        let view = format_ident!("__{}_view", name);
+        let item = format_ident!("__{}", name);
+
+        match param {
+            Param::Component { ty, mutable: true } => {
+                view_params.push(quote!(mut #view: ::shipyard::ViewMut<'_, #ty>));
+                iterated.push(quote!(&mut #view));
+                // `Mut` has to be mutable to be dereferenced
+                items.push(quote!(mut #item));
+                args.push(quote!(&mut *#item));
+            }
+            Param::Component { ty, mutable: false } => {
+                view_params.push(quote!(#view: ::shipyard::View<'_, #ty>));
+                iterated.push(quote!(&#view));
+                items.push(quote!(#item));
+                args.push(quote!(#item));
+            }
+            Param::Unique { ty, mutable: true } => {
+                view_params.push(quote!(mut #view: ::shipyard::UniqueViewMut<'_, #ty>));
+                args.push(quote!(&mut *#view));
+            }
+            Param::Unique { ty, mutable: false } => {
+                view_params.push(quote!(#view: ::shipyard::UniqueView<'_, #ty>));
+                args.push(quote!(&*#view));
+            }
+            Param::EntityId => args.push(quote!(__id)),
+        }
+    }
+
+    let inner_name = &function.sig.ident;
+
+    let body = if has_components {
+        let (iterated, items) = if iterated.len() == 1 {
+            (quote!(#(#iterated)*), quote!(#(#items)*))
+        } else {
+            (quote!((#(#iterated,)*)), quote!((#(#items,)*)))
+        };
+
+        if has_id {
+            quote!(
+                #[allow(unused_mut)]
+                for (__id, #items) in ::shipyard::IntoWithId::with_id(::shipyard::IntoIter::iter(#iterated)) {
+                    #inner_name(#(#args),*);
+                }
+            )
+        } else {
+            quote!(
+                #[allow(unused_mut)]
+                for #items in ::shipyard::IntoIter::iter(#iterated) {
+                    #inner_name(#(#args),*);
+                }
+            )
+        }
+    } else {
+        quote!(#inner_name(#(#args),*))
+    };
+
+    let attrs = function.attrs.clone();
+    let vis = function.vis.clone();
+    let sig = syn::Signature {
+        inputs: syn::parse_quote!(#(#view_params),*),
+        ..function.sig.clone()
+    };
+
+    function.attrs.retain(|attr| !attr.path().is_ident("doc"));
+    function.vis = syn::Visibility::Inherited;
+
+    Ok(quote!(
+        #(#attrs)*
+        #vis #sig {
+            #function
+
+            #body
+        }
+    ))
+}
+
+/// Parses `&T`, `&mut T`, `&Unique<T>`, `&mut Unique<T>` or `EntityId`.
+fn parse_param(ty: &syn::Type) -> Result<Param> {
+    let reference =
+        match ty {
+            syn::Type::Reference(reference) => reference,
+            syn::Type::Path(path)
+                if path.qself.is_none() && is_last_segment(&path.path, "EntityId") =>
+            {
+                return Ok(Param::EntityId)
+            }
+            _ => return Err(Error::new(
+                ty.span(),
+                "System parameters have to be &T, &mut T, &Unique<T>, &mut Unique<T> or EntityId.",
+            )),
+        };
+
+    if let Some(lifetime) = &reference.lifetime {
+        return Err(Error::new(
+            lifetime.span(),
+            "System parameters can't have explicit lifetimes.",
+        ));
+    }
+
+    let mutable = reference.mutability.is_some();
+
+    if let syn::Type::Path(path) = &*reference.elem {
+        if path.qself.is_none() && is_last_segment(&path.path, "Unique") {
+            let segment = path.path.segments.last().unwrap();
+
+            if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                if let (1, Some(syn::GenericArgument::Type(ty))) =
+                    (arguments.args.len(), arguments.args.first())
+                {
+                    return Ok(Param::Unique {
+                        ty: ty.clone(),
+                        mutable,
+                    });
+                }
+            }
+
+            return Err(Error::new(
+                segment.span(),
+                "Unique has to be used as Unique<T>.",
+            ));
+        }
+    }
+
+    Ok(Param::Component {
+        ty: (*reference.elem).clone(),
+        mutable,
+    })
+}
+
+fn is_last_segment(path: &syn::Path, name: &str) -> bool {
+    path.segments
+        .last()
+        .map(|segment| segment.ident == name)
+        .unwrap_or(false)
+}
@@ -5,9 +5,9 @@ use macroquad::{
     ui::{root_ui, widgets::Button},
 };
 use shipyard::{
-    AddComponent, AllStorages, AllStoragesViewMut, Component, EntitiesViewMut, IntoIter,
-    IntoWithId, IntoWorkload, IntoWorkloadTrySystem, SparseSet, Unique, UniqueView, UniqueViewMut,
-    View, ViewMut, Workload, World,
+    update_spatial_grid, AddComponent, AllStorages, AllStoragesViewMut, Component, EntitiesViewMut,
+    IntoIter, IntoWithId, IntoWorkload, IntoWorkloadTrySystem, SparseSet, SpatialGrid,
+    SpatialPosition, Unique, UniqueView, UniqueViewMut, View, ViewMut, Workload, World,
 };
 
 const WIDTH: f32 = 640.0;
@@ -21,6 +21,7 @@ const BASE_SQUARE_SPAWN_RATE: u32 = 25;
 const BASE_SQUAGUM_SPAWN_RATE: u32 = 150;
 
 #[derive(Component)]
+#[track(Insertion, Modification)]
 struct Square {
     x: f32,
     y: f32,
@@ -40,6 +41,12 @@ impl Square {
     }
 }
 
+impl SpatialPosition for Square {
+    fn position(&self) -> [f32; 2] {
+        self.pos().into()
+    }
+}
+
 #[derive(Unique)]
 struct Player {
     is_invincible: bool,
@@ -253,6 +260,7 @@ fn floor_loop() -> Workload {
     (
         counters,
         move_player,
+        update_spatial_grid::<Square>,
         move_square,
         grow_square,
         spawn,
@@ -324,6 +332,7 @@ async fn main() {
     world.add_unique(PowerUps::new());
     world.add_unique(MaxFloor(1));
     world.add_unique(Screen::Start);
+    world.add_unique(SpatialGrid::<Square>::new(MAX_SIZE));
     world.run(init_floor);
 
     // seed the random number generator with a random value
@@ -355,11 +364,8 @@ async fn main() {
             Screen::Floor => {
                 clear_background(WHITE);
 
-                if let Err(Some(err)) = world
-                    .run_workload(floor_loop)
-                    .map_err(shipyard::error::RunWorkload::custom_error)
-                {
-                    match err.downcast_ref::<FloorResult>().unwrap() {
+                if let Err(err) = world.run_workload(floor_loop) {
+                    match err.downcast::<FloorResult>().unwrap() {
                         FloorResult::Loose => {
                             debug!("Loose");
                             world.run(|mut floor_counter: UniqueViewMut<FloorCounter>| {
@@ -465,6 +471,7 @@ fn move_square(
     mut accelerations: ViewMut<Acceleration>,
     power_ups: UniqueView<PowerUps>,
     mut squares: ViewMut<Square>,
+    grid: UniqueView<SpatialGrid<Square>>,
 ) {
     for acceleration in (&mut accelerations).iter() {
         acceleration.0 += ACCELERATION_RATE;
@@ -484,11 +491,12 @@ fn move_square(
 
             let mut neighbourg_dir = Vec2::ZERO;
 
-            for neighbourg in squares.iter() {
-                if square.pos().distance_squared(neighbourg.pos()) < square.size * square.size / 1.5
-                {
-                    neighbourg_dir += Vec2::new(square.x - neighbourg.x, square.y - neighbourg.y);
-                }
+            let radius = square.size / 1.5f32.sqrt();
+
+            for (neighbourg, _) in grid.within_radius(square.position(), radius, &squares) {
+                let neighbourg = &squares[neighbourg];
+
+                neighbourg_dir += Vec2::new(square.x - neighbourg.x, square.y - neighbourg.y);
             }
 
             if square.size == MAX_SIZE {
@@ -81,6 +81,16 @@ macro_rules! impl_add_component {
                 )+
             }
         }
+
+        impl<$($component: Component,)+ $($storage: AddComponent<$component>,)+> AddComponent<($($component,)+)> for &mut ($($storage,)+) {
+            #[inline]
+            #[track_caller]
+            fn add_component_unchecked(&mut self, entity: EntityId, component: ($($component,)+)) {
+                $(
+                    let _ = self.$index.add_component_unchecked(entity, component.$index);
+                )+
+            }
+        }
     }
 }
 
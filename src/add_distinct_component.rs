@@ -46,7 +46,7 @@ impl AddDistinctComponent for () {
     }
 }
 
-impl<T: Component + PartialEq> AddDistinctComponent for ViewMut<'_, T> {
+impl<T: Component + PartialEq, TRACK> AddDistinctComponent for ViewMut<'_, T, TRACK> {
     type Component = T;
 
     #[inline]
@@ -68,7 +68,7 @@ impl<T: Component + PartialEq> AddDistinctComponent for ViewMut<'_, T> {
     }
 }
 
-impl<T: Component + PartialEq> AddDistinctComponent for &mut ViewMut<'_, T> {
+impl<T: Component + PartialEq, TRACK> AddDistinctComponent for &mut ViewMut<'_, T, TRACK> {
     type Component = T;
 
     #[inline]
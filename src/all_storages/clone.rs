@@ -0,0 +1,22 @@
+use super::AllStorages;
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::sparse_set::SparseSet;
+
+pub(super) type CloneFn = fn(&mut AllStorages, EntityId, EntityId);
+
+/// Adds a clone of `entity`'s `T` to `new_entity`.
+pub(super) fn clone_component<T: Component + Clone + Send + Sync>(
+    all_storages: &mut AllStorages,
+    entity: EntityId,
+    new_entity: EntityId,
+) {
+    let component = match all_storages.exclusive_storage_mut::<SparseSet<T>>() {
+        Ok(sparse_set) => sparse_set.private_get(entity).cloned(),
+        Err(_) => None,
+    };
+
+    if let Some(component) = component {
+        all_storages.add_component(new_entity, component);
+    }
+}
@@ -206,9 +206,12 @@ pub trait CustomStorageAccess {
 impl CustomStorageAccess for AllStorages {
     #[inline]
     fn custom_storage<S: 'static>(&self) -> Result<ARef<'_, &'_ S>, error::GetStorage> {
-        let storages = self.storages.read();
-        let storage = storages.get(&StorageId::of::<S>());
+        let storage_id = StorageId::of::<S>();
+        let storages = self.storages.shard(&storage_id).read();
+        let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow();
             drop(storages);
             match storage {
@@ -232,7 +235,7 @@ impl CustomStorageAccess for AllStorages {
         &self,
         storage_id: StorageId,
     ) -> Result<ARef<'_, &'_ dyn Storage>, error::GetStorage> {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
             let storage = unsafe { &*storage.0 }.borrow();
@@ -251,9 +254,12 @@ impl CustomStorageAccess for AllStorages {
     }
     #[inline]
     fn custom_storage_mut<S: 'static>(&self) -> Result<ARefMut<'_, &'_ mut S>, error::GetStorage> {
-        let storages = self.storages.read();
-        let storage = storages.get(&StorageId::of::<S>());
+        let storage_id = StorageId::of::<S>();
+        let storages = self.storages.shard(&storage_id).read();
+        let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow_mut();
             drop(storages);
             match storage {
@@ -277,7 +283,7 @@ impl CustomStorageAccess for AllStorages {
         &self,
         storage_id: StorageId,
     ) -> Result<ARefMut<'_, &'_ mut (dyn Storage + 'static)>, error::GetStorage> {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
             let storage = unsafe { &*storage.0 }.borrow_mut();
@@ -311,9 +317,11 @@ impl CustomStorageAccess for AllStorages {
         S: 'static + Storage + Send + Sync,
         F: FnOnce() -> S,
     {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow();
             drop(storages);
             match storage {
@@ -328,20 +336,19 @@ impl CustomStorageAccess for AllStorages {
             }
         } else {
             drop(storages);
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
-            let storage = unsafe {
-                &*storages
-                    .entry(storage_id)
-                    .or_insert_with(|| SBox::new(f()))
-                    .0
-            }
-            .borrow()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
-            });
+            let storage = storages.entry(storage_id).or_insert_with(|| SBox::new(f()));
+            storage.check::<S>(storage_id)?;
+
+            let storage =
+                unsafe { &*storage.0 }
+                    .borrow()
+                    .map_err(|err| error::GetStorage::StorageBorrow {
+                        name: Some(type_name::<S>()),
+                        id: StorageId::of::<S>(),
+                        borrow: err,
+                    });
 
             Ok(ARef::map(storage?, |storage| {
                 storage.as_any().downcast_ref::<S>().unwrap()
@@ -370,9 +377,11 @@ impl CustomStorageAccess for AllStorages {
         S: 'static + Storage + Sync,
         F: FnOnce() -> S,
     {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow();
 
             match storage {
@@ -395,20 +404,21 @@ impl CustomStorageAccess for AllStorages {
             }
 
             drop(storages);
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
-            let storage = unsafe {
-                &*storages
-                    .entry(storage_id)
-                    .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()))
-                    .0
-            }
-            .borrow()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
-            });
+            let storage = storages
+                .entry(storage_id)
+                .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()));
+            storage.check::<S>(storage_id)?;
+
+            let storage =
+                unsafe { &*storage.0 }
+                    .borrow()
+                    .map_err(|err| error::GetStorage::StorageBorrow {
+                        name: Some(type_name::<S>()),
+                        id: StorageId::of::<S>(),
+                        borrow: err,
+                    });
 
             Ok(ARef::map(storage?, |storage| {
                 storage.as_any().downcast_ref::<S>().unwrap()
@@ -437,9 +447,11 @@ impl CustomStorageAccess for AllStorages {
         S: 'static + Storage + Send,
         F: FnOnce() -> S,
     {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow();
 
             match storage {
@@ -454,20 +466,21 @@ impl CustomStorageAccess for AllStorages {
             }
         } else {
             drop(storages);
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
-            let storage = unsafe {
-                &*storages
-                    .entry(storage_id)
-                    .or_insert_with(|| SBox::new_non_sync(f()))
-                    .0
-            }
-            .borrow()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
-            });
+            let storage = storages
+                .entry(storage_id)
+                .or_insert_with(|| SBox::new_non_sync(f()));
+            storage.check::<S>(storage_id)?;
+
+            let storage =
+                unsafe { &*storage.0 }
+                    .borrow()
+                    .map_err(|err| error::GetStorage::StorageBorrow {
+                        name: Some(type_name::<S>()),
+                        id: StorageId::of::<S>(),
+                        borrow: err,
+                    });
 
             Ok(ARef::map(storage?, |storage| {
                 storage.as_any().downcast_ref::<S>().unwrap()
@@ -496,9 +509,11 @@ impl CustomStorageAccess for AllStorages {
         S: 'static + Storage,
         F: FnOnce() -> S,
     {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow();
 
             match storage {
@@ -521,22 +536,21 @@ impl CustomStorageAccess for AllStorages {
             }
 
             drop(storages);
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
-            let storage = unsafe {
-                &*storages
-                    .entry(storage_id)
-                    .or_insert_with(|| {
-                        SBox::new_non_send_sync(f(), self.thread_id_generator.clone())
-                    })
-                    .0
-            }
-            .borrow()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
-            });
+            let storage = storages
+                .entry(storage_id)
+                .or_insert_with(|| SBox::new_non_send_sync(f(), self.thread_id_generator.clone()));
+            storage.check::<S>(storage_id)?;
+
+            let storage =
+                unsafe { &*storage.0 }
+                    .borrow()
+                    .map_err(|err| error::GetStorage::StorageBorrow {
+                        name: Some(type_name::<S>()),
+                        id: StorageId::of::<S>(),
+                        borrow: err,
+                    });
 
             Ok(ARef::map(storage?, |storage| {
                 storage.as_any().downcast_ref::<S>().unwrap()
@@ -563,9 +577,11 @@ impl CustomStorageAccess for AllStorages {
         S: 'static + Storage + Send + Sync,
         F: FnOnce() -> S,
     {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow_mut();
             drop(storages);
             match storage {
@@ -580,19 +596,17 @@ impl CustomStorageAccess for AllStorages {
             }
         } else {
             drop(storages);
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
-            let storage = unsafe {
-                &*storages
-                    .entry(storage_id)
-                    .or_insert_with(|| SBox::new(f()))
-                    .0
-            }
-            .borrow_mut()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            let storage = storages.entry(storage_id).or_insert_with(|| SBox::new(f()));
+            storage.check::<S>(storage_id)?;
+
+            let storage = unsafe { &*storage.0 }.borrow_mut().map_err(|err| {
+                error::GetStorage::StorageBorrow {
+                    name: Some(type_name::<S>()),
+                    id: StorageId::of::<S>(),
+                    borrow: err,
+                }
             });
 
             Ok(ARefMut::map(storage?, |storage| {
@@ -622,9 +636,11 @@ impl CustomStorageAccess for AllStorages {
         S: 'static + Storage + Sync,
         F: FnOnce() -> S,
     {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow_mut();
 
             match storage {
@@ -647,19 +663,19 @@ impl CustomStorageAccess for AllStorages {
             }
 
             drop(storages);
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
-            let storage = unsafe {
-                &*storages
-                    .entry(storage_id)
-                    .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()))
-                    .0
-            }
-            .borrow_mut()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            let storage = storages
+                .entry(storage_id)
+                .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()));
+            storage.check::<S>(storage_id)?;
+
+            let storage = unsafe { &*storage.0 }.borrow_mut().map_err(|err| {
+                error::GetStorage::StorageBorrow {
+                    name: Some(type_name::<S>()),
+                    id: StorageId::of::<S>(),
+                    borrow: err,
+                }
             });
 
             Ok(ARefMut::map(storage?, |storage| {
@@ -689,9 +705,11 @@ impl CustomStorageAccess for AllStorages {
         S: 'static + Storage + Send,
         F: FnOnce() -> S,
     {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow_mut();
 
             match storage {
@@ -706,19 +724,19 @@ impl CustomStorageAccess for AllStorages {
             }
         } else {
             drop(storages);
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
-            let storage = unsafe {
-                &*storages
-                    .entry(storage_id)
-                    .or_insert_with(|| SBox::new_non_sync(f()))
-                    .0
-            }
-            .borrow_mut()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            let storage = storages
+                .entry(storage_id)
+                .or_insert_with(|| SBox::new_non_sync(f()));
+            storage.check::<S>(storage_id)?;
+
+            let storage = unsafe { &*storage.0 }.borrow_mut().map_err(|err| {
+                error::GetStorage::StorageBorrow {
+                    name: Some(type_name::<S>()),
+                    id: StorageId::of::<S>(),
+                    borrow: err,
+                }
             });
 
             Ok(ARefMut::map(storage?, |storage| {
@@ -748,9 +766,11 @@ impl CustomStorageAccess for AllStorages {
         S: 'static + Storage,
         F: FnOnce() -> S,
     {
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id);
         if let Some(storage) = storage {
+            storage.check::<S>(storage_id)?;
+
             let storage = unsafe { &*storage.0 }.borrow_mut();
 
             match storage {
@@ -773,21 +793,19 @@ impl CustomStorageAccess for AllStorages {
             }
 
             drop(storages);
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
-            let storage = unsafe {
-                &*storages
-                    .entry(storage_id)
-                    .or_insert_with(|| {
-                        SBox::new_non_send_sync(f(), self.thread_id_generator.clone())
-                    })
-                    .0
-            }
-            .borrow_mut()
-            .map_err(|err| error::GetStorage::StorageBorrow {
-                name: Some(type_name::<S>()),
-                id: StorageId::of::<S>(),
-                borrow: err,
+            let storage = storages
+                .entry(storage_id)
+                .or_insert_with(|| SBox::new_non_send_sync(f(), self.thread_id_generator.clone()));
+            storage.check::<S>(storage_id)?;
+
+            let storage = unsafe { &*storage.0 }.borrow_mut().map_err(|err| {
+                error::GetStorage::StorageBorrow {
+                    name: Some(type_name::<S>()),
+                    id: StorageId::of::<S>(),
+                    borrow: err,
+                }
             });
 
             Ok(ARefMut::map(storage?, |storage| {
@@ -797,8 +815,9 @@ impl CustomStorageAccess for AllStorages {
     }
     fn iter_storages(&self) -> Vec<ARef<'_, &dyn Storage>> {
         self.storages
-            .read()
+            .read_all()
             .iter()
+            .flat_map(|shard| shard.iter())
             .flat_map(|(storage_id, storage)| unsafe {
                 (*storage.0)
                     .borrow()
@@ -0,0 +1,13 @@
+use super::AllStorages;
+use crate::component::Component;
+use crate::entity_id::EntityId;
+
+pub(super) type DefaultFn = fn(&mut AllStorages, EntityId);
+
+/// Adds `T::default()` to `entity`.
+pub(super) fn default_component<T: Component + Default + Send + Sync>(
+    all_storages: &mut AllStorages,
+    entity: EntityId,
+) {
+    all_storages.add_component(entity, T::default());
+}
@@ -21,9 +21,13 @@ impl<T: 'static + Storage + CustomDeleteAny> TupleDeleteAny for T {
         let mut ids = ShipHashSet::with_hasher(BuildHasherDefault::default());
 
         let current = all_storages.get_current();
-        let storages = all_storages.storages.get_mut();
+        let storage_id = StorageId::of::<T>();
 
-        if let Some(storage) = storages.get_mut(&StorageId::of::<T>()) {
+        if let Some(storage) = all_storages
+            .storages
+            .shard_mut(&storage_id)
+            .get_mut(&storage_id)
+        {
             unsafe { &mut *storage.0 }
                 .get_mut()
                 .as_any_mut()
@@ -45,10 +49,11 @@ macro_rules! impl_delete_any {
                 let mut ids = ShipHashSet::with_hasher(BuildHasherDefault::default());
 
                 let current = all_storages.get_current();
-                let storages = all_storages.storages.get_mut();
+                let storages = &mut all_storages.storages;
 
                 $(
-                    if let Some(storage) = storages.get_mut(&StorageId::of::<$storage>()) {
+                    let storage_id = StorageId::of::<$storage>();
+                    if let Some(storage) = storages.shard_mut(&storage_id).get_mut(&storage_id) {
                         unsafe { &mut *storage.0 }.get_mut().as_any_mut().downcast_mut::<$storage>().unwrap().delete_any(&mut ids, current);
                     }
                 )+
@@ -1,37 +1,66 @@
+mod clone;
 mod custom_storage;
+mod default;
 mod delete_any;
+mod relation;
 mod retain;
+mod storage_map;
+mod transfer;
 
 pub use custom_storage::CustomStorageAccess;
 pub use delete_any::{CustomDeleteAny, TupleDeleteAny};
+pub use relation::{DeletionPolicy, Relation};
 pub use retain::TupleRetainStorage;
 
+pub(crate) use storage_map::StorageMap;
+
+use clone::{clone_component, CloneFn};
+use default::{default_component, DefaultFn};
+use relation::{orphan, related, OrphanFn, RelatedFn};
+use transfer::{transfer_component, TransferFn};
+
 use crate::atomic_refcell::{ARef, ARefMut, AtomicRefCell};
 use crate::borrow::Borrow;
-use crate::component::{Component, Unique};
-use crate::entities::Entities;
+use crate::component::{component_name, Component, Unique};
+use crate::component_mask::{ComponentMask, TupleSignature};
+use crate::component_registrar::{ComponentRegistrar, TupleRegisterComponent};
+use crate::digest::{storage_digest, Pod};
+use crate::entities::{Entities, EntityRemap};
 use crate::entity_id::EntityId;
+use crate::external_storage::{External, ExternalStorage};
 use crate::get_component::GetComponent;
 use crate::get_unique::GetUnique;
+use crate::hierarchy::{Children, Hierarchy, HierarchyIter, Parent};
 use crate::iter_component::{IntoIterRef, IterComponent};
 use crate::memory_usage::AllStoragesMemoryUsage;
 use crate::public_transport::RwLock;
 use crate::public_transport::ShipyardRwLock;
 use crate::r#mut::Mut;
+use crate::replay::{Recording, ReplayCommand, ReplayRegistry, ReplayStream};
+#[cfg(feature = "replication")]
+use crate::replication::{ReplicationClient, ReplicationDelta, ReplicationRegistry};
 use crate::reserve::BulkEntityIter;
-use crate::sparse_set::{BulkAddEntity, SparseSet, TupleAddComponent, TupleDelete, TupleRemove};
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{EntityIdMap, SnapshotFilter, SnapshotRegistry, WorldDiff};
+use crate::sparse_set::{
+    BulkAddEntity, SparseSet, StorageAllocator, TupleAddComponent, TupleContains, TupleDelete,
+    TupleRemove,
+};
 #[cfg(feature = "std")]
 use crate::std_thread_id_generator;
-use crate::storage::{SBox, Storage, StorageId};
+use crate::storage::{cast_mut, cast_ref, SBox, Storage, StorageHandle, StorageId};
 use crate::system::AllSystem;
 use crate::tracking::{TrackingTimestamp, TupleTrack};
+use crate::undo::{UndoJournal, UndoRegistry};
 use crate::unique::UniqueStorage;
-use crate::views::EntitiesViewMut;
-use crate::{error, ShipHashMap};
+use crate::views::{EntitiesViewMut, ViewMut};
+use crate::world::World;
+use crate::{error, ShipHashMap, ShipHashSet};
+use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::any::type_name;
-use core::hash::BuildHasherDefault;
 use core::marker::PhantomData;
 use core::sync::atomic::AtomicU64;
 use hashbrown::hash_map::Entry;
@@ -46,7 +75,7 @@ pub struct MissingThreadId;
 pub struct ThreadIdPresent;
 
 pub(crate) struct AllStoragesBuilder<Lock, ThreadId> {
-    custom_lock: Option<Box<dyn ShipyardRwLock + Send + Sync>>,
+    custom_lock: Option<fn() -> Box<dyn ShipyardRwLock + Send + Sync>>,
     custom_thread_id: Option<Arc<dyn Fn() -> u64 + Send + Sync>>,
     _phantom: PhantomData<(Lock, ThreadId)>,
 }
@@ -83,7 +112,7 @@ impl<Lock, ThreadId> AllStoragesBuilder<Lock, ThreadId> {
         self,
     ) -> AllStoragesBuilder<LockPresent, ThreadId> {
         AllStoragesBuilder {
-            custom_lock: Some(L::new()),
+            custom_lock: Some(L::new),
             custom_thread_id: self.custom_thread_id,
             _phantom: PhantomData,
         }
@@ -104,16 +133,12 @@ impl<Lock, ThreadId> AllStoragesBuilder<Lock, ThreadId> {
 
 impl AllStoragesBuilder<LockPresent, ThreadIdPresent> {
     pub(crate) fn build(self, counter: Arc<AtomicU64>) -> AtomicRefCell<AllStorages> {
-        let mut storages = ShipHashMap::with_hasher(BuildHasherDefault::default());
-
-        storages.insert(StorageId::of::<Entities>(), SBox::new(Entities::new()));
-
-        let storages = if let Some(custom_lock) = self.custom_lock {
-            RwLock::new_custom(custom_lock, storages)
+        let mut storages = if let Some(custom_lock) = self.custom_lock {
+            StorageMap::new(|shard| RwLock::new_custom(custom_lock(), shard))
         } else {
             #[cfg(feature = "std")]
             {
-                RwLock::new_std(storages)
+                StorageMap::new(RwLock::new_std)
             }
             #[cfg(not(feature = "std"))]
             {
@@ -121,6 +146,10 @@ impl AllStoragesBuilder<LockPresent, ThreadIdPresent> {
             }
         };
 
+        storages
+            .shard_mut(&StorageId::of::<Entities>())
+            .insert(StorageId::of::<Entities>(), SBox::new(Entities::new()));
+
         #[cfg(feature = "thread_local")]
         let thread_id_generator = self.custom_thread_id.unwrap();
         #[cfg(feature = "thread_local")]
@@ -131,22 +160,51 @@ impl AllStoragesBuilder<LockPresent, ThreadIdPresent> {
             AtomicRefCell::new_non_send(
                 AllStorages {
                     storages,
+                    id: next_all_storages_id(),
                     main_thread_id,
                     thread_id_generator: thread_id_generator.clone(),
                     counter,
+                    recording: None,
+                    undo: None,
+                    digests: ShipHashMap::default(),
+                    transfers: ShipHashMap::default(),
+                    clones: ShipHashMap::default(),
+                    defaults: ShipHashMap::default(),
+                    deletion_policies: ShipHashMap::default(),
+                    component_bits: ShipHashMap::default(),
+                    strict: false,
+                    registered: ShipHashSet::default(),
+                    #[cfg(feature = "replication")]
+                    replication: None,
                 },
                 thread_id_generator,
             )
         }
         #[cfg(not(feature = "thread_local"))]
         {
-            AtomicRefCell::new(AllStorages { storages, counter })
+            AtomicRefCell::new(AllStorages {
+                storages,
+                id: next_all_storages_id(),
+                counter,
+                recording: None,
+                undo: None,
+                digests: ShipHashMap::default(),
+                transfers: ShipHashMap::default(),
+                clones: ShipHashMap::default(),
+                defaults: ShipHashMap::default(),
+                deletion_policies: ShipHashMap::default(),
+                component_bits: ShipHashMap::default(),
+                strict: false,
+                registered: ShipHashSet::default(),
+                #[cfg(feature = "replication")]
+                replication: None,
+            })
         }
     }
 }
 
 /// Contains all storages present in the `World`.
-// The lock is held very briefly:
+// The storages are split in shards, each shard lock is held very briefly:
 // - shared: when trying to find a storage
 // - unique: when adding a storage
 // once the storage is found or created the lock is released
@@ -154,12 +212,34 @@ impl AllStoragesBuilder<LockPresent, ThreadIdPresent> {
 // so any access to storages are valid as long as the World exists
 // we use a HashMap, it can reallocate, but even in this case the storages won't move since they are boxed
 pub struct AllStorages {
-    pub(crate) storages: RwLock<ShipHashMap<StorageId, SBox>>,
+    pub(crate) storages: StorageMap,
+    /// Unique among all `AllStorages`, used to check `StorageHandle`s.
+    pub(crate) id: u64,
     #[cfg(feature = "thread_local")]
     main_thread_id: u64,
     #[cfg(feature = "thread_local")]
     thread_id_generator: Arc<dyn Fn() -> u64 + Send + Sync>,
     counter: Arc<AtomicU64>,
+    pub(crate) recording: Option<Box<Recording>>,
+    pub(crate) undo: Option<Box<UndoJournal>>,
+    digests: ShipHashMap<core::any::TypeId, fn(&mut AllStorages) -> u64>,
+    transfers: ShipHashMap<core::any::TypeId, TransferFn>,
+    clones: ShipHashMap<core::any::TypeId, CloneFn>,
+    defaults: ShipHashMap<core::any::TypeId, (&'static str, DefaultFn)>,
+    deletion_policies: ShipHashMap<core::any::TypeId, (DeletionPolicy, RelatedFn, OrphanFn)>,
+    component_bits: ShipHashMap<StorageId, usize>,
+    /// Borrowing a view of a component without storage errors instead of creating it.
+    strict: bool,
+    /// Components registered with `register_components`.
+    pub(crate) registered: ShipHashSet<StorageId>,
+    #[cfg(feature = "replication")]
+    pub(crate) replication: Option<Box<ReplicationRegistry>>,
+}
+
+pub(crate) fn next_all_storages_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    NEXT_ID.fetch_add(1, core::sync::atomic::Ordering::Relaxed)
 }
 
 #[cfg(not(feature = "thread_local"))]
@@ -170,17 +250,32 @@ unsafe impl Sync for AllStorages {}
 impl AllStorages {
     #[cfg(feature = "std")]
     pub(crate) fn new(counter: Arc<AtomicU64>) -> Self {
-        let mut storages = ShipHashMap::with_hasher(BuildHasherDefault::default());
+        let mut storages = StorageMap::new(RwLock::new_std);
 
-        storages.insert(StorageId::of::<Entities>(), SBox::new(Entities::new()));
+        storages
+            .shard_mut(&StorageId::of::<Entities>())
+            .insert(StorageId::of::<Entities>(), SBox::new(Entities::new()));
 
         AllStorages {
-            storages: RwLock::new_std(storages),
+            storages,
+            id: next_all_storages_id(),
             #[cfg(feature = "thread_local")]
             main_thread_id: (std_thread_id_generator)(),
             #[cfg(feature = "thread_local")]
             thread_id_generator: Arc::new(std_thread_id_generator),
             counter,
+            recording: None,
+            undo: None,
+            digests: ShipHashMap::default(),
+            transfers: ShipHashMap::default(),
+            clones: ShipHashMap::default(),
+            defaults: ShipHashMap::default(),
+            deletion_policies: ShipHashMap::default(),
+            component_bits: ShipHashMap::default(),
+            strict: false,
+            registered: ShipHashSet::default(),
+            #[cfg(feature = "replication")]
+            replication: None,
         }
     }
     /// Adds a new unique storage, unique storages store exactly one `T` at any time.  
@@ -206,6 +301,7 @@ impl AllStorages {
         let storage_id = StorageId::of::<UniqueStorage<T>>();
 
         self.storages
+            .shard(&storage_id)
             .write()
             .entry(storage_id)
             .insert(SBox::new(UniqueStorage::new(
@@ -213,6 +309,165 @@ impl AllStorages {
                 self.get_tracking_timestamp(),
             )));
     }
+    /// Inserts a custom storage to `AllStorages`.\
+    /// Does nothing if a storage of the same type already exists at `storage_id`.
+    ///
+    /// See [`Storage`] to implement custom storages.\
+    /// Storages shared with other dynamic libraries should use [`StorageId::from_name`], their `TypeId` can differ.
+    ///
+    /// ### Errors
+    ///
+    /// - A storage of a different type already exists at `storage_id`.
+    pub fn add_custom_storage<S: 'static + Storage + Send + Sync>(
+        &self,
+        storage_id: StorageId,
+        storage: S,
+    ) -> Result<(), error::GetStorage> {
+        self.storages
+            .shard(&storage_id)
+            .write()
+            .entry(storage_id)
+            .or_insert_with(|| SBox::new(storage))
+            .check::<S>(storage_id)
+    }
+    /// Returns the storage at `storage_id`, matching its type by name and layout when its `TypeId` differs.
+    ///
+    /// A type can have a different `TypeId` in each dynamic library,
+    /// this gives access to a storage added by another library, usually at a [`StorageId::from_name`].
+    ///
+    /// ### Safety
+    ///
+    /// The storage at `storage_id` has to be a `S`, built from the same definition by the same compiler.\
+    /// Two distinct types with the same name and layout, like a type from two versions of the same crate, are not told apart.
+    ///
+    /// ### Errors
+    ///
+    /// - No storage exists at `storage_id`.
+    /// - The storage's name or layout doesn't match `S`.
+    /// - The storage is already borrowed exclusively.
+    pub unsafe fn foreign_storage<S: 'static + Storage>(
+        &self,
+        storage_id: StorageId,
+    ) -> Result<ARef<'_, &'_ S>, error::GetStorage> {
+        let storages = self.storages.shard(&storage_id).read();
+        let storage = match storages.get(&storage_id) {
+            Some(storage) => storage,
+            None => {
+                return Err(error::GetStorage::MissingStorage {
+                    name: Some(type_name::<S>()),
+                    id: storage_id,
+                })
+            }
+        };
+        storage.check_like::<S>(storage_id)?;
+
+        let storage = (*storage.0)
+            .borrow()
+            .map_err(|err| error::GetStorage::StorageBorrow {
+                name: Some(type_name::<S>()),
+                id: storage_id,
+                borrow: err,
+            })?;
+        drop(storages);
+
+        Ok(ARef::map(storage, |storage| cast_ref(storage)))
+    }
+    /// Mutable version of [`AllStorages::foreign_storage`].
+    ///
+    /// ### Safety
+    ///
+    /// Same as [`AllStorages::foreign_storage`].
+    ///
+    /// ### Errors
+    ///
+    /// - No storage exists at `storage_id`.
+    /// - The storage's name or layout doesn't match `S`.
+    /// - The storage is already borrowed.
+    pub unsafe fn foreign_storage_mut<S: 'static + Storage>(
+        &self,
+        storage_id: StorageId,
+    ) -> Result<ARefMut<'_, &'_ mut S>, error::GetStorage> {
+        let storages = self.storages.shard(&storage_id).read();
+        let storage = match storages.get(&storage_id) {
+            Some(storage) => storage,
+            None => {
+                return Err(error::GetStorage::MissingStorage {
+                    name: Some(type_name::<S>()),
+                    id: storage_id,
+                })
+            }
+        };
+        storage.check_like::<S>(storage_id)?;
+
+        let storage =
+            (*storage.0)
+                .borrow_mut()
+                .map_err(|err| error::GetStorage::StorageBorrow {
+                    name: Some(type_name::<S>()),
+                    id: storage_id,
+                    borrow: err,
+                })?;
+        drop(storages);
+
+        Ok(ARefMut::map(storage, |storage| cast_mut(storage)))
+    }
+    /// Replaces `T`'s storage with an empty one allocating its components with `allocator`.\
+    /// The components of the previous storage are dropped without being tracked as deleted.
+    ///
+    /// Dropping the storage, by calling this method again with [`StorageAllocator::global`] for example,
+    /// gives all its memory back to `allocator`.
+    pub fn add_storage_in<T: Component + Send + Sync>(&mut self, allocator: StorageAllocator) {
+        self.replace_sparse_set(SparseSet::<T>::new_in(allocator));
+    }
+    /// Replaces `T`'s storage without moving it, [`StorageHandle`]s to it stay valid.
+    #[track_caller]
+    fn replace_sparse_set<T: Component + Send + Sync>(&mut self, sparse_set: SparseSet<T>) {
+        let mut sparse_set = Some(sparse_set);
+
+        let storage = self.exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<T>>(), || {
+            sparse_set.take().unwrap()
+        });
+
+        if let Some(sparse_set) = sparse_set {
+            *storage = sparse_set;
+        }
+    }
+    /// Replaces `T`'s storage with an empty one keeping up to `capacity` deleted components in a pool.\
+    /// The components of the previous storage are dropped without being tracked as deleted.
+    pub fn add_pooled_storage<T: Component + Send + Sync>(&mut self, capacity: usize) {
+        self.replace_sparse_set(SparseSet::<T>::new_pooled(capacity));
+    }
+    /// Replaces `T`'s storage with an empty one keeping a bitset of the entities it contains.\
+    /// The components of the previous storage are dropped without being tracked as deleted.
+    pub fn add_bitset_storage<T: Component + Send + Sync>(&mut self) {
+        self.replace_sparse_set(SparseSet::<T>::new_with_bitset());
+    }
+    /// Adds an [`ExternalStorage`], it can then be borrowed with [`ExternalView`] and [`ExternalViewMut`].\
+    /// Replaces the previous `S` if there was one.
+    ///
+    /// ### Borrows
+    ///
+    /// - `S` storage (exclusive)
+    ///
+    /// ### Panics
+    ///
+    /// - `S` storage borrow failed.
+    ///
+    /// [`ExternalView`]: crate::ExternalView
+    /// [`ExternalViewMut`]: crate::ExternalViewMut
+    #[track_caller]
+    pub fn add_external_storage<S: ExternalStorage>(&self, storage: S) {
+        let mut storage = Some(storage);
+
+        let mut external = self
+            .custom_storage_or_insert_mut(|| External(storage.take().unwrap()))
+            .unwrap_or_else(|err| panic!("{:?}", err));
+
+        // the previous storage can't be dropped while borrowed, it's replaced in place
+        if let Some(storage) = storage {
+            external.0 = storage;
+        }
+    }
     /// Adds a new unique storage, unique storages store exactly one `T` at any time.  
     /// To access a unique storage value, use [NonSend] and [UniqueViewMut] or [UniqueViewMut].  
     /// Does nothing if the storage already exists.
@@ -225,12 +480,16 @@ impl AllStorages {
         if (self.thread_id_generator)() == self.main_thread_id {
             let storage_id = StorageId::of::<UniqueStorage<T>>();
 
-            self.storages.write().entry(storage_id).or_insert_with(|| {
-                SBox::new_non_send(
-                    UniqueStorage::new(component, self.get_tracking_timestamp()),
-                    self.thread_id_generator.clone(),
-                )
-            });
+            self.storages
+                .shard(&storage_id)
+                .write()
+                .entry(storage_id)
+                .or_insert_with(|| {
+                    SBox::new_non_send(
+                        UniqueStorage::new(component, self.get_tracking_timestamp()),
+                        self.thread_id_generator.clone(),
+                    )
+                });
         }
     }
     /// Adds a new unique storage, unique storages store exactly one `T` at any time.  
@@ -244,9 +503,13 @@ impl AllStorages {
     pub fn add_unique_non_sync<T: Send + Unique>(&self, component: T) {
         let storage_id = StorageId::of::<UniqueStorage<T>>();
 
-        self.storages.write().entry(storage_id).or_insert_with(|| {
-            SBox::new_non_sync(UniqueStorage::new(component, self.get_tracking_timestamp()))
-        });
+        self.storages
+            .shard(&storage_id)
+            .write()
+            .entry(storage_id)
+            .or_insert_with(|| {
+                SBox::new_non_sync(UniqueStorage::new(component, self.get_tracking_timestamp()))
+            });
     }
     /// Adds a new unique storage, unique storages store exactly one `T` at any time.  
     /// To access a unique storage value, use [NonSync] and [UniqueViewMut] or [UniqueViewMut].  
@@ -260,12 +523,16 @@ impl AllStorages {
         if (self.thread_id_generator)() == self.main_thread_id {
             let storage_id = StorageId::of::<UniqueStorage<T>>();
 
-            self.storages.write().entry(storage_id).or_insert_with(|| {
-                SBox::new_non_send_sync(
-                    UniqueStorage::new(component, self.get_tracking_timestamp()),
-                    self.thread_id_generator.clone(),
-                )
-            });
+            self.storages
+                .shard(&storage_id)
+                .write()
+                .entry(storage_id)
+                .or_insert_with(|| {
+                    SBox::new_non_send_sync(
+                        UniqueStorage::new(component, self.get_tracking_timestamp()),
+                        self.thread_id_generator.clone(),
+                    )
+                });
         }
     }
     /// Removes a unique storage.
@@ -297,7 +564,7 @@ impl AllStorages {
         let storage_id = StorageId::of::<UniqueStorage<T>>();
 
         {
-            let mut storages = self.storages.write();
+            let mut storages = self.storages.shard(&storage_id).write();
 
             let storage = if let Entry::Occupied(entry) = storages.entry(storage_id) {
                 // `.err()` to avoid borrowing `entry` in the `Ok` case
@@ -350,13 +617,50 @@ impl AllStorages {
     /// });
     /// ```
     pub fn delete_entity(&mut self, entity: EntityId) -> bool {
+        let mut cascade = Vec::new();
+        let mut orphans = Vec::new();
+
+        for (policy, related_fn, orphan_fn) in self.deletion_policies.values() {
+            let related = related_fn(self, entity);
+
+            if related.is_empty() {
+                continue;
+            }
+
+            match policy {
+                DeletionPolicy::Cascade => cascade.extend(related),
+                DeletionPolicy::Orphan => orphans.push((*orphan_fn, related)),
+                DeletionPolicy::Forbid => return false,
+            }
+        }
+
         // no need to lock here since we have a unique access
         let mut entities = self.entities_mut().unwrap();
 
         if entities.delete_unchecked(entity) {
             drop(entities);
 
-            self.strip(entity);
+            if let Some(recording) = &mut self.recording {
+                recording.push(ReplayCommand::DeleteEntity(entity));
+            }
+
+            let components = crate::undo::entity_components(self, entity);
+
+            self.strip_storages(entity);
+
+            if let Some(components) = components {
+                crate::undo::record_entity_deletion(self, entity, components);
+            }
+
+            for (orphan_fn, related) in orphans {
+                for related in related {
+                    orphan_fn(self, related);
+                }
+            }
+
+            for related in cascade {
+                self.delete_entity(related);
+            }
 
             true
         } else {
@@ -385,9 +689,23 @@ impl AllStorages {
     /// ```
     #[track_caller]
     pub fn strip(&mut self, entity: EntityId) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(ReplayCommand::Strip(entity));
+        }
+
+        let components = crate::undo::entity_components(self, entity);
+
+        self.strip_storages(entity);
+
+        if let Some(components) = components {
+            crate::undo::record_strip(self, entity, components);
+        }
+    }
+    #[track_caller]
+    fn strip_storages(&mut self, entity: EntityId) {
         let current = self.get_current();
 
-        for storage in self.storages.get_mut().values_mut() {
+        for storage in self.storages.values_mut() {
             unsafe { &mut *storage.0 }.get_mut().delete(entity, current);
         }
     }
@@ -423,7 +741,7 @@ impl AllStorages {
     pub fn retain_storage_by_id(&mut self, entity: EntityId, excluded_storage: &[StorageId]) {
         let current = self.get_current();
 
-        for (storage_id, storage) in self.storages.get_mut().iter_mut() {
+        for (storage_id, storage) in self.storages.iter_mut() {
             if !excluded_storage.contains(storage_id) {
                 unsafe { &mut *storage.0 }.get_mut().delete(entity, current);
             }
@@ -443,16 +761,54 @@ impl AllStorages {
     /// ```
     #[track_caller]
     pub fn clear(&mut self) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(ReplayCommand::Clear);
+        }
+
+        if let Some(undo) = &mut self.undo {
+            undo.clear();
+        }
+
         let current = self.get_current();
 
-        for storage in self.storages.get_mut().values_mut() {
+        for storage in self.storages.values_mut() {
             unsafe { &mut *storage.0 }.get_mut().clear(current);
         }
     }
+    /// Frees the unused memory of all storages and sorts their components by entity index.\
+    /// See [`World::compact`].
+    ///
+    /// [`World::compact`]: crate::World::compact
+    pub fn compact(&mut self) {
+        for storage in self.storages.values_mut() {
+            unsafe { &mut *storage.0 }.get_mut().compact();
+        }
+    }
+    /// Moves alive entities to the lowest free indices then compacts all storages.\
+    /// See [`World::compact_entities`].
+    ///
+    /// [`World::compact_entities`]: crate::World::compact_entities
+    pub fn compact_entities(&mut self) -> EntityRemap {
+        let remap = self.entities_mut().unwrap().densify();
+
+        if !remap.is_empty() {
+            if let Some(undo) = &mut self.undo {
+                undo.clear();
+            }
+
+            for storage in self.storages.values_mut() {
+                unsafe { &mut *storage.0 }.get_mut().remap_entities(&remap);
+            }
+        }
+
+        self.compact();
+
+        remap
+    }
     /// Clear all deletion and removal tracking data.
     #[track_caller]
     pub fn clear_all_removed_and_deleted(&mut self) {
-        for storage in self.storages.get_mut().values_mut() {
+        for storage in self.storages.values_mut() {
             unsafe { &mut *storage.0 }
                 .get_mut()
                 .clear_all_removed_and_deleted();
@@ -464,7 +820,7 @@ impl AllStorages {
         &mut self,
         timestamp: TrackingTimestamp,
     ) {
-        for storage in self.storages.get_mut().values_mut() {
+        for storage in self.storages.values_mut() {
             unsafe { &mut *storage.0 }
                 .get_mut()
                 .clear_all_removed_and_deleted_older_than_timestamp(timestamp);
@@ -527,6 +883,15 @@ impl AllStorages {
         let current = self.get_current();
 
         let entity = self.exclusive_storage_mut::<Entities>().unwrap().generate();
+
+        if let Some(recording) = &mut self.recording {
+            recording.push(ReplayCommand::AddEntity(entity));
+        }
+
+        if let Some(undo) = &mut self.undo {
+            undo.push_add_entity(entity);
+        }
+
         component.add_component(self, entity, current);
 
         entity
@@ -552,7 +917,69 @@ impl AllStorages {
     /// ```
     #[inline]
     pub fn bulk_add_entity<T: BulkAddEntity>(&mut self, source: T) -> BulkEntityIter<'_> {
-        source.bulk_add_entity(self)
+        if self.recording.is_some() || self.undo.is_some() {
+            let entities_len = self.exclusive_storage_mut::<Entities>().unwrap().data.len();
+            let new_entities: Vec<EntityId> = source.bulk_add_entity(self).collect();
+
+            if let Some(mut recording) = self.recording.take() {
+                for &entity in &new_entities {
+                    recording.record_entity(self, entity);
+                }
+
+                self.recording = Some(recording);
+            }
+
+            if let Some(undo) = &mut self.undo {
+                for &entity in &new_entities {
+                    undo.push_add_entity(entity);
+                }
+            }
+
+            let entities = self.exclusive_storage_mut::<Entities>().unwrap();
+
+            BulkEntityIter {
+                iter: entities.data[entities_len..].iter().copied(),
+                slice: &entities.data[entities_len..],
+            }
+        } else {
+            source.bulk_add_entity(self)
+        }
+    }
+    /// Creates `count` new entities, their components are created in parallel by calling `f` with each entity's position in the batch.\
+    /// `f` must always return a tuple, even for a single component.
+    ///
+    /// Only the creation of the components is parallel, they are then added to the storages in bulk.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct USIZE(usize);
+    ///
+    /// let mut world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// let new_entities = all_storages.par_bulk_add_entity(10, |i| (U32(i as u32), USIZE(i)));
+    /// assert_eq!(new_entities.len(), 10);
+    /// ```
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_bulk_add_entity<B, F>(&mut self, count: usize, f: F) -> BulkEntityIter<'_>
+    where
+        B: Send,
+        F: Fn(usize) -> B + Send + Sync,
+        Vec<B>: BulkAddEntity,
+    {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let components: Vec<B> = (0..count).into_par_iter().map(f).collect();
+
+        self.bulk_add_entity(components)
     }
     /// Adds components to an existing entity.  
     /// If the entity already owned a component it will be replaced.  
@@ -621,7 +1048,46 @@ impl AllStorages {
     /// ```
     #[inline]
     pub fn delete_component<C: TupleDelete>(&mut self, entity: EntityId) {
-        C::delete(self, entity);
+        if let Some(mut recording) = self.recording.take() {
+            let before = recording.presence(self, entity);
+            C::delete(self, entity);
+            recording.record_deletions(self, entity, &before);
+
+            self.recording = Some(recording);
+        } else {
+            C::delete(self, entity);
+        }
+    }
+    /// Returns `true` if `entity` has all components of `C`.\
+    /// Missing storages are considered empty.
+    ///
+    /// ### Panics
+    ///
+    /// - Storage borrow failed.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct USIZE(usize);
+    ///
+    /// let mut world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// let entity = all_storages.add_entity(U32(0));
+    ///
+    /// assert!(all_storages.has::<U32>(entity));
+    /// assert!(!all_storages.has::<(U32, USIZE)>(entity));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn has<C: TupleContains>(&self, entity: EntityId) -> bool {
+        C::contains(self, entity)
     }
     /// Removes components from an entity.  
     /// `C` must always be a tuple, even for a single component.
@@ -647,7 +1113,17 @@ impl AllStorages {
     /// ```
     #[inline]
     pub fn remove<C: TupleRemove>(&mut self, entity: EntityId) -> C::Out {
-        C::remove(self, entity)
+        if let Some(mut recording) = self.recording.take() {
+            let before = recording.presence(self, entity);
+            let removed = C::remove(self, entity);
+            recording.record_deletions(self, entity, &before);
+
+            self.recording = Some(recording);
+
+            removed
+        } else {
+            C::remove(self, entity)
+        }
     }
     #[doc = "Borrows the requested storage(s), if it doesn't exist it'll get created.  
 You can use a tuple to get multiple storages at once.
@@ -1011,7 +1487,7 @@ let i = all_storages.run(sys1);
     pub(crate) fn entities(&self) -> Result<ARef<'_, &'_ Entities>, error::GetStorage> {
         let storage_id = StorageId::of::<Entities>();
 
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id).unwrap();
         let storage = unsafe { &*storage.0 }.borrow();
         drop(storages);
@@ -1025,7 +1501,7 @@ let i = all_storages.run(sys1);
     pub(crate) fn entities_mut(&self) -> Result<ARefMut<'_, &'_ mut Entities>, error::GetStorage> {
         let storage_id = StorageId::of::<Entities>();
 
-        let storages = self.storages.read();
+        let storages = self.storages.shard(&storage_id).read();
         let storage = storages.get(&storage_id).unwrap();
         let storage = unsafe { &*storage.0 }.borrow_mut();
         drop(storages);
@@ -1046,13 +1522,14 @@ let i = all_storages.run(sys1);
         &mut self,
         storage_id: StorageId,
     ) -> Result<&mut T, error::GetStorage> {
-        if let Some(storage) = self.storages.get_mut().get_mut(&storage_id) {
-            let storage = unsafe { &mut *storage.0 }
+        if let Some(storage) = self.storages.shard_mut(&storage_id).get_mut(&storage_id) {
+            storage.check::<T>(storage_id)?;
+
+            Ok(unsafe { &mut *storage.0 }
                 .get_mut()
                 .as_any_mut()
                 .downcast_mut()
-                .unwrap();
-            Ok(storage)
+                .unwrap())
         } else {
             Err(error::GetStorage::MissingStorage {
                 name: Some(type_name::<T>()),
@@ -1069,18 +1546,18 @@ let i = all_storages.run(sys1);
         T: 'static + Storage + Send + Sync,
         F: FnOnce() -> T,
     {
-        let storages = self.storages.get_mut();
+        let storages = self.storages.shard_mut(&storage_id);
 
-        unsafe {
-            &mut *storages
-                .entry(storage_id)
-                .or_insert_with(|| SBox::new(f()))
-                .0
+        let storage = storages.entry(storage_id).or_insert_with(|| SBox::new(f()));
+        if let Err(err) = storage.check::<T>(storage_id) {
+            panic!("{:?}", err);
         }
-        .get_mut()
-        .as_any_mut()
-        .downcast_mut()
-        .unwrap()
+
+        unsafe { &mut *storage.0 }
+            .get_mut()
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
     }
     #[cfg(feature = "thread_local")]
     #[track_caller]
@@ -1093,18 +1570,20 @@ let i = all_storages.run(sys1);
         T: 'static + Storage + Sync,
         F: FnOnce() -> T,
     {
-        let storages = self.storages.get_mut();
+        let storages = self.storages.shard_mut(&storage_id);
 
-        unsafe {
-            &mut *storages
-                .entry(storage_id)
-                .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()))
-                .0
+        let storage = storages
+            .entry(storage_id)
+            .or_insert_with(|| SBox::new_non_send(f(), self.thread_id_generator.clone()));
+        if let Err(err) = storage.check::<T>(storage_id) {
+            panic!("{:?}", err);
         }
-        .get_mut()
-        .as_any_mut()
-        .downcast_mut()
-        .unwrap()
+
+        unsafe { &mut *storage.0 }
+            .get_mut()
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
     }
     #[cfg(feature = "thread_local")]
     pub(crate) fn exclusive_storage_or_insert_non_sync_mut<T, F>(
@@ -1116,18 +1595,20 @@ let i = all_storages.run(sys1);
         T: 'static + Storage + Send,
         F: FnOnce() -> T,
     {
-        let storages = self.storages.get_mut();
+        let storages = self.storages.shard_mut(&storage_id);
 
-        unsafe {
-            &mut *storages
-                .entry(storage_id)
-                .or_insert_with(|| SBox::new_non_sync(f()))
-                .0
+        let storage = storages
+            .entry(storage_id)
+            .or_insert_with(|| SBox::new_non_sync(f()));
+        if let Err(err) = storage.check::<T>(storage_id) {
+            panic!("{:?}", err);
         }
-        .get_mut()
-        .as_any_mut()
-        .downcast_mut()
-        .unwrap()
+
+        unsafe { &mut *storage.0 }
+            .get_mut()
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
     }
     #[cfg(feature = "thread_local")]
     #[track_caller]
@@ -1140,27 +1621,42 @@ let i = all_storages.run(sys1);
         T: 'static + Storage,
         F: FnOnce() -> T,
     {
-        let storages = self.storages.get_mut();
+        let storages = self.storages.shard_mut(&storage_id);
 
-        unsafe {
-            &mut *storages
-                .entry(storage_id)
-                .or_insert_with(|| SBox::new_non_send_sync(f(), self.thread_id_generator.clone()))
-                .0
+        let storage = storages
+            .entry(storage_id)
+            .or_insert_with(|| SBox::new_non_send_sync(f(), self.thread_id_generator.clone()));
+        if let Err(err) = storage.check::<T>(storage_id) {
+            panic!("{:?}", err);
         }
-        .get_mut()
-        .as_any_mut()
-        .downcast_mut()
-        .unwrap()
+
+        unsafe { &mut *storage.0 }
+            .get_mut()
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
     }
     /// Make the given entity alive.  
     /// Does nothing if an entity with a greater generation is already at this index.  
     /// Returns `true` if the entity is successfully spawned.
     #[inline]
     pub fn spawn(&mut self, entity: EntityId) -> bool {
-        self.exclusive_storage_mut::<Entities>()
+        let spawned = self
+            .exclusive_storage_mut::<Entities>()
             .unwrap()
-            .spawn(entity)
+            .spawn(entity);
+
+        if spawned {
+            if let Some(recording) = &mut self.recording {
+                recording.push(ReplayCommand::AddEntity(entity));
+            }
+
+            if let Some(undo) = &mut self.undo {
+                undo.push_add_entity(entity);
+            }
+        }
+
+        spawned
     }
     /// Displays storages memory information.
     pub fn memory_usage(&self) -> AllStoragesMemoryUsage<'_> {
@@ -1245,6 +1741,7 @@ You can use:
 - [AllStorages] borrow failed.
 - Storage borrow failed.
 - Entity does not have the component.
+- Entity is dead, like [`EntityId::dead`](crate::EntityId::dead).
 
 ### Example
 ```
@@ -1277,6 +1774,10 @@ assert!(*j == &U32(1));
         &self,
         entity: EntityId,
     ) -> Result<T::Out<'_>, error::GetComponent> {
+        if entity.is_dead() {
+            return Err(error::GetComponent::DeadEntity(entity));
+        }
+
         let current = self.get_current();
 
         T::get(self, None, current, entity)
@@ -1491,7 +1992,7 @@ for (i, j) in &mut iter {
             entity
         );
 
-        for storage in self.storages.get_mut().values_mut() {
+        for storage in self.storages.values_mut() {
             unsafe { &mut *storage.0 }.get_mut().move_component_from(
                 other,
                 entity,
@@ -1555,7 +2056,7 @@ for (i, j) in &mut iter {
             );
         };
 
-        for storage in self.storages.get_mut().values_mut() {
+        for storage in self.storages.values_mut() {
             unsafe { &mut *storage.0 }.get_mut().move_component_from(
                 other,
                 from,
@@ -1565,38 +2066,1044 @@ for (i, j) in &mut iter {
             );
         }
     }
-}
-
-impl core::fmt::Debug for AllStorages {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut debug_struct = f.debug_struct("AllStorages");
-
-        let storages = self.storages.read();
-
-        debug_struct.field("storage_count", &storages.len());
-        debug_struct.field("storages", &storages.values());
-
-        debug_struct.finish()
+    /// Starts recording `AllStorages` and `World` operations and the value of the components in `registry`.  
+    /// Only operations going through `AllStorages` or `World` are recorded: adding and deleting entities,
+    /// adding, deleting and removing components, `strip`, `clear` and `spawn`.  
+    /// Components not part of `registry` are ignored.
+    ///
+    /// Changes made through views, adding entities or components, deleting or removing components
+    /// and modifying components, are not recorded. See [`World::start_recording`].
+    ///
+    /// If a recording was in progress, it's discarded.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, ReplayRegistry, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// let mut registry = ReplayRegistry::new();
+    /// registry.register::<U32>(
+    ///     |u32| u32.0.to_le_bytes().to_vec(),
+    ///     |bytes| Some(U32(u32::from_le_bytes(bytes.try_into().ok()?))),
+    /// );
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// all_storages.start_recording(registry);
+    /// all_storages.add_entity((U32(0),));
+    /// let stream = all_storages.stop_recording().unwrap();
+    ///
+    /// assert_eq!(stream.len(), 2);
+    /// ```
+    pub fn start_recording(&mut self, registry: ReplayRegistry) {
+        self.recording = Some(Box::new(Recording::new(registry)));
     }
-}
-
-impl core::fmt::Debug for AllStoragesMemoryUsage<'_> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut borrowed_storages = 0;
-
-        let mut debug_struct = f.debug_list();
-
-        let storages = self.0.storages.read();
-
-        debug_struct.entries(storages.values().filter_map(|storage| {
-            match unsafe { &*(storage.0) }.borrow() {
-                Ok(storage) => storage.memory_usage(),
-                Err(_) => {
-                    borrowed_storages += 1;
-                    None
-                }
-            }
-        }));
+    /// Stops the recording and returns everything recorded since [`AllStorages::start_recording`].  
+    /// Returns `None` if no recording was in progress.
+    pub fn stop_recording(&mut self) -> Option<ReplayStream> {
+        self.recording
+            .take()
+            .map(|recording| recording.into_stream())
+    }
+    /// Returns `true` if a recording is in progress.
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+    /// Starts recording the history [`AllStorages::undo`] and [`AllStorages::redo`] walk through.\
+    /// If undo was already enabled, its history is discarded.
+    ///
+    /// Entity creations and deletions are always recorded, component additions, replacements, deletions and removals
+    /// are recorded for the components in `registry`.\
+    /// Only operations going through `AllStorages` or `World` are recorded, modifications made with views are not.
+    /// A component modified in place can be recorded by adding it again with [`AllStorages::add_component`].
+    ///
+    /// Operations are grouped in steps with [`AllStorages::transaction`],
+    /// operations done outside of a transaction are grouped in a single unnamed step.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, UndoRegistry, World};
+    ///
+    /// #[derive(Component, Clone, Debug, PartialEq)]
+    /// struct Position(f32, f32);
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// all_storages.enable_undo(UndoRegistry::new().register::<Position>().clone());
+    ///
+    /// let entity = all_storages.transaction("spawn", |all_storages| {
+    ///     all_storages.add_entity(Position(0.0, 0.0))
+    /// });
+    ///
+    /// all_storages.transaction("move", |all_storages| {
+    ///     all_storages.add_component(entity, Position(1.0, 0.0));
+    /// });
+    ///
+    /// assert!(all_storages.undo());
+    /// assert_eq!(
+    ///     all_storages.get::<&Position>(entity).as_deref(),
+    ///     Ok(&&Position(0.0, 0.0))
+    /// );
+    ///
+    /// assert!(all_storages.undo());
+    /// assert!(!all_storages.is_entity_alive(entity));
+    ///
+    /// assert!(all_storages.redo());
+    /// assert_eq!(
+    ///     all_storages.get::<&Position>(entity).as_deref(),
+    ///     Ok(&&Position(0.0, 0.0))
+    /// );
+    /// ```
+    pub fn enable_undo(&mut self, registry: UndoRegistry) {
+        self.undo = Some(Box::new(UndoJournal::new(registry)));
+    }
+    /// Stops recording undo history and discards it.
+    pub fn disable_undo(&mut self) {
+        self.undo = None;
+    }
+    /// Returns `true` if undo history is recorded.
+    pub fn is_undo_enabled(&self) -> bool {
+        self.undo.is_some()
+    }
+    /// Groups all operations `f` does in a single undo step named `name`.\
+    /// Nested transactions are part of the outermost one.
+    ///
+    /// Does nothing more than calling `f` if undo isn't enabled.
+    pub fn transaction<R>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        f: impl FnOnce(&mut AllStorages) -> R,
+    ) -> R {
+        self.begin_transaction(name);
+        let result = f(self);
+        self.end_transaction();
+
+        result
+    }
+    /// Starts a transaction lasting until [`AllStorages::end_transaction`].\
+    /// Useful when a step spans multiple frames, like dragging an object.
+    ///
+    /// See [`AllStorages::transaction`].
+    pub fn begin_transaction(&mut self, name: impl Into<Cow<'static, str>>) {
+        if let Some(undo) = &mut self.undo {
+            undo.begin_transaction(name.into());
+        }
+    }
+    /// Ends a transaction started with [`AllStorages::begin_transaction`].
+    pub fn end_transaction(&mut self) {
+        if let Some(undo) = &mut self.undo {
+            undo.end_transaction();
+        }
+    }
+    /// Reverts the last undo step.\
+    /// Returns `false` if there is nothing to undo, undo isn't enabled or a transaction is in progress.
+    ///
+    /// Any new operation discards the steps that could be redone.
+    pub fn undo(&mut self) -> bool {
+        crate::undo::undo(self)
+    }
+    /// Reapplies the last step reverted by [`AllStorages::undo`].\
+    /// Returns `false` if there is nothing to redo, undo isn't enabled or a transaction is in progress.
+    pub fn redo(&mut self) -> bool {
+        crate::undo::redo(self)
+    }
+    /// Returns `true` if [`AllStorages::undo`] would revert a step.
+    pub fn can_undo(&self) -> bool {
+        self.undo.as_ref().is_some_and(|undo| undo.can_undo())
+    }
+    /// Returns `true` if [`AllStorages::redo`] would reapply a step.
+    pub fn can_redo(&self) -> bool {
+        self.undo.as_ref().is_some_and(|undo| undo.can_redo())
+    }
+    /// Returns the name of the steps that can be undone, the most recent first.\
+    /// Steps made of operations done outside of a transaction have no name.
+    pub fn undo_names(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.undo.iter().flat_map(|undo| undo.undo_names())
+    }
+    /// Returns the name of the steps that can be redone, the next one first.
+    pub fn redo_names(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.undo.iter().flat_map(|undo| undo.redo_names())
+    }
+    /// Makes `T` part of the types [`AllStorages::tick_digest`] can hash.
+    pub fn register_digest<T: Component + Pod>(&mut self) {
+        self.digests
+            .insert(core::any::TypeId::of::<T>(), storage_digest::<T>);
+    }
+    /// Returns a hash of the entity ids and components of `types`.\
+    /// The hash doesn't depend on the order entities are stored in and only depends on the order of `types`.
+    /// It doesn't allocate and is cheap enough to be computed every simulation tick,
+    /// peers of a lockstep simulation can compare it to detect desyncs.
+    ///
+    /// ### Panics
+    ///
+    /// - A type wasn't registered with [`AllStorages::register_digest`].
+    ///
+    /// ### Example
+    /// ```
+    /// use core::any::TypeId;
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component, Clone, Copy)]
+    /// struct Health(u32);
+    ///
+    /// unsafe impl shipyard::Pod for Health {}
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// all_storages.register_digest::<Health>();
+    /// all_storages.add_entity((Health(10),));
+    ///
+    /// let digest = all_storages.tick_digest(&[TypeId::of::<Health>()]);
+    /// ```
+    #[track_caller]
+    pub fn tick_digest(&mut self, types: &[core::any::TypeId]) -> u64 {
+        let mut hash = crate::digest::seed();
+
+        for type_id in types {
+            let storage_digest = match self.digests.get(type_id) {
+                Some(&storage_digest) => storage_digest,
+                None => panic!("TypeId {:?} is not registered for digests.", type_id),
+            };
+
+            hash = crate::digest::combine(hash, storage_digest(self));
+        }
+
+        hash
+    }
+    /// Makes `T` part of the components [`AllStorages::transfer`] moves to the other `World`.
+    pub fn register_transfer<T: Component + Send + Sync>(&mut self) {
+        self.transfers
+            .insert(core::any::TypeId::of::<T>(), transfer_component::<T>);
+    }
+    /// Moves `entity` to `other`, returns its id in `other`.\
+    /// Components registered with [`AllStorages::register_transfer`] are moved, the others are dropped with the entity.\
+    /// `EntityId`s stored inside components are not updated.\
+    /// Unlike [`AllStorages::move_entity`], `entity` gets a new id in `other` so it can't collide with its entities.
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Mesh(u32);
+    ///
+    /// let loading = World::new();
+    /// let mut live = World::new();
+    ///
+    /// let mut all_storages = loading.borrow::<AllStoragesViewMut>().unwrap();
+    /// all_storages.register_transfer::<Mesh>();
+    ///
+    /// let entity = all_storages.add_entity((Mesh(0),));
+    /// let new_entity = all_storages.transfer(entity, &mut live);
+    ///
+    /// assert!(!all_storages.is_entity_alive(entity));
+    /// assert_eq!(live.get::<&Mesh>(new_entity).as_deref(), Ok(&&Mesh(0)));
+    /// ```
+    #[track_caller]
+    pub fn transfer(&mut self, entity: EntityId, other: &mut World) -> EntityId {
+        if !self
+            .exclusive_storage_mut::<Entities>()
+            .unwrap()
+            .is_alive(entity)
+        {
+            panic!(
+                "Entity {:?} has to be alive to move it to another World.",
+                entity
+            );
+        }
+
+        let other = other.all_storages.get_mut();
+        let new_entity = other.add_entity(());
+
+        let transfers = core::mem::take(&mut self.transfers);
+        for transfer in transfers.values() {
+            transfer(self, entity, other, new_entity);
+        }
+        self.transfers = transfers;
+
+        self.delete_entity(entity);
+
+        new_entity
+    }
+    /// Sets what [`AllStorages::delete_entity`] does to the entities related through `R`.\
+    /// Without policy, deleting an entity doesn't affect the entities it's related to.
+    ///
+    /// A cascaded entity with a [`DeletionPolicy::Forbid`] relation is kept alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{
+    ///     AllStoragesViewMut, Children, DeletionPolicy, EntitiesViewMut, Hierarchy, Parent,
+    ///     ViewMut, World,
+    /// };
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// let (root, child) = all_storages.run(
+    ///     |mut hierarchy: (EntitiesViewMut, ViewMut<Parent>, ViewMut<Children>)| {
+    ///         let root = hierarchy.0.add_entity((), ());
+    ///         let child = hierarchy.attach_new(root);
+    ///
+    ///         (root, child)
+    ///     },
+    /// );
+    ///
+    /// all_storages.set_deletion_policy::<Children>(DeletionPolicy::Cascade);
+    ///
+    /// assert!(all_storages.delete_entity(root));
+    /// assert!(!all_storages.is_entity_alive(child));
+    /// ```
+    pub fn set_deletion_policy<R: Relation>(&mut self, policy: DeletionPolicy) {
+        self.deletion_policies.insert(
+            core::any::TypeId::of::<R>(),
+            (policy, related::<R>, orphan::<R>),
+        );
+    }
+    /// Removes `R`'s deletion policy, deleting an entity won't affect the entities related through `R` anymore.\
+    /// Returns the previous policy.
+    pub fn remove_deletion_policy<R: Relation>(&mut self) -> Option<DeletionPolicy> {
+        self.deletion_policies
+            .remove(&core::any::TypeId::of::<R>())
+            .map(|(policy, _, _)| policy)
+    }
+    /// Makes `T` part of the components [`AllStorages::clone_entity`] copies.
+    pub fn register_clone<T: Component + Clone + Send + Sync>(&mut self) {
+        self.clones
+            .insert(core::any::TypeId::of::<T>(), clone_component::<T>);
+    }
+    /// Adds a new entity with a clone of `entity`'s components, returns the new entity.\
+    /// Only components registered with [`AllStorages::register_clone`] are cloned.\
+    /// `EntityId`s stored inside components are not updated.
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component, Clone, Debug, PartialEq)]
+    /// struct Mesh(u32);
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// all_storages.register_clone::<Mesh>();
+    ///
+    /// let entity = all_storages.add_entity((Mesh(0),));
+    /// let clone = all_storages.clone_entity(entity);
+    ///
+    /// assert_ne!(entity, clone);
+    /// assert_eq!(all_storages.get::<&Mesh>(clone).as_deref(), Ok(&&Mesh(0)));
+    /// ```
+    #[track_caller]
+    pub fn clone_entity(&mut self, entity: EntityId) -> EntityId {
+        if !self
+            .exclusive_storage_mut::<Entities>()
+            .unwrap()
+            .is_alive(entity)
+        {
+            panic!("Entity {:?} has to be alive to clone it.", entity);
+        }
+
+        let new_entity = self.add_entity(());
+
+        let clones = core::mem::take(&mut self.clones);
+        for clone in clones.values() {
+            clone(self, entity, new_entity);
+        }
+        self.clones = clones;
+
+        new_entity
+    }
+    /// Makes `T` addable by [`AllStorages::add_default_component`], using `T::default()`.
+    pub fn register_default<T: Component + Default + Send + Sync>(&mut self) {
+        self.defaults.insert(
+            core::any::TypeId::of::<T>(),
+            (component_name::<T>(), default_component::<T>),
+        );
+    }
+    /// Adds the default value of the component identified by `type_id` to `entity`.\
+    /// Returns `false` if no default was registered for this type with [`AllStorages::register_default`].
+    ///
+    /// Meant for editors and scripts that only know components by id, see [`AllStorages::default_components`].
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component, Default, Debug, PartialEq)]
+    /// struct Health(u32);
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// all_storages.register_default::<Health>();
+    ///
+    /// let entity = all_storages.add_entity(());
+    /// let (type_id, _name) = all_storages.default_components().next().unwrap();
+    ///
+    /// assert!(all_storages.add_default_component(entity, type_id));
+    /// assert_eq!(all_storages.get::<&Health>(entity).as_deref(), Ok(&&Health(0)));
+    /// ```
+    #[track_caller]
+    pub fn add_default_component(&mut self, entity: EntityId, type_id: core::any::TypeId) -> bool {
+        let add_default = self
+            .defaults
+            .get(&type_id)
+            .map(|&(_, add_default)| add_default);
+
+        self.add_default_with(entity, add_default)
+    }
+    /// Adds the default value of the component named `name` to `entity`.\
+    /// The name is [`Component::NAME`] or the type name.\
+    /// Returns `false` if no default was registered for this name with [`AllStorages::register_default`].
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    #[track_caller]
+    pub fn add_default_component_by_name(&mut self, entity: EntityId, name: &str) -> bool {
+        let add_default = self
+            .defaults
+            .values()
+            .find(|&&(component_name, _)| component_name == name)
+            .map(|&(_, add_default)| add_default);
+
+        self.add_default_with(entity, add_default)
+    }
+    #[track_caller]
+    fn add_default_with(&mut self, entity: EntityId, add_default: Option<DefaultFn>) -> bool {
+        if !self
+            .exclusive_storage_mut::<Entities>()
+            .unwrap()
+            .is_alive(entity)
+        {
+            panic!("{:?}", error::AddComponent::EntityIsNotAlive);
+        }
+
+        match add_default {
+            Some(add_default) => {
+                add_default(self, entity);
+
+                true
+            }
+            None => false,
+        }
+    }
+    /// Returns the id and name of all components registered with [`AllStorages::register_default`].
+    pub fn default_components(
+        &self,
+    ) -> impl Iterator<Item = (core::any::TypeId, &'static str)> + '_ {
+        self.defaults
+            .iter()
+            .map(|(&type_id, &(name, _))| (type_id, name))
+    }
+    /// Replaces the storage of the component named like `New` by a storage of `New`, converting each component with `convert`.\
+    /// Returns the number of components migrated.
+    ///
+    /// The storage is looked up by [`Component::NAME`], `Old` and `New` are expected to share it.\
+    /// This allows to keep a `World` alive while the definition of a component changes, like when reloading a dynamic library.
+    /// Migrated components are considered inserted.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component)]
+    /// #[shipyard(name = "position")]
+    /// struct OldPosition(f32, f32);
+    ///
+    /// #[derive(Component, Debug, PartialEq)]
+    /// #[shipyard(name = "position")]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    ///     z: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// let entity = all_storages.add_entity(OldPosition(1.0, 2.0));
+    ///
+    /// all_storages
+    ///     .migrate_storage(|OldPosition(x, y)| Position { x, y, z: 0.0 })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     all_storages.get::<&Position>(entity).as_deref(),
+    ///     Ok(&&Position { x: 1.0, y: 2.0, z: 0.0 })
+    /// );
+    /// ```
+    pub fn migrate_storage<Old, New>(
+        &mut self,
+        mut convert: impl FnMut(Old) -> New,
+    ) -> Result<usize, error::MigrateStorage>
+    where
+        Old: Component + Send + Sync,
+        New: Component + Send + Sync,
+    {
+        let name = component_name::<New>();
+
+        let storage_id = self
+            .storages
+            .iter_mut()
+            .find(|(_, storage)| {
+                unsafe { &mut *storage.0 }.get_mut().component_name() == Some(name)
+            })
+            .map(|(&storage_id, _)| storage_id)
+            .ok_or(error::MigrateStorage::MissingStorage(name))?;
+
+        let shard = self.storages.shard_mut(&storage_id);
+        let storage = unsafe { &mut *shard[&storage_id].0 }.get_mut();
+        if !storage.any().is::<SparseSet<Old>>() {
+            return Err(error::MigrateStorage::WrongType {
+                component: name,
+                found: storage.name(),
+            });
+        }
+
+        let storage = shard.remove(&storage_id).unwrap();
+
+        // SAFE the storage was checked to be a `SparseSet<Old>`
+        let old: Box<AtomicRefCell<SparseSet<Old>>> =
+            unsafe { Box::from_raw(storage.0 as *mut AtomicRefCell<SparseSet<Old>>) };
+
+        core::mem::forget(storage);
+        // `StorageHandle`s to the removed storage fall back to a regular lookup
+        self.id = next_all_storages_id();
+
+        let old = old.into_inner();
+        let count = old.len();
+        let current = self.get_current();
+        let new = self.exclusive_storage_or_insert_mut(
+            StorageId::of::<SparseSet<New>>(),
+            SparseSet::<New>::new,
+        );
+
+        for (entity, component) in old.dense.into_iter().zip(old.data) {
+            let _ = new.insert(entity, convert(component), current);
+        }
+
+        Ok(count)
+    }
+    /// Applies the options `T` declared with `#[derive(Component)]` that concern the `AllStorages`.\
+    /// See [`ComponentRegistrar`].
+    ///
+    /// Registered components can be borrowed from strict `AllStorages`, see [`AllStorages::set_strict`].
+    pub fn register_components<T: TupleRegisterComponent>(&mut self) {
+        T::register_components(&mut ComponentRegistrar::all_storages(self));
+    }
+    /// When `strict`, borrowing a view of a component without storage errors with [`error::GetStorage::UnregisteredStorage`]
+    /// instead of creating the storage.\
+    /// Components registered with [`AllStorages::register_components`] or with a storage are accepted.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{error, AllStoragesViewMut, Component, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Position;
+    ///
+    /// #[derive(Component)]
+    /// struct Pos;
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// all_storages.set_strict(true);
+    /// all_storages.register_components::<Position>();
+    ///
+    /// assert!(all_storages.borrow::<View<Position>>().is_ok());
+    /// assert!(matches!(
+    ///     all_storages.borrow::<View<Pos>>(),
+    ///     Err(error::GetStorage::UnregisteredStorage { .. })
+    /// ));
+    /// ```
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+    /// Returns `true` if borrowing a view of an unregistered component errors, see [`AllStorages::set_strict`].
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+    /// Returns an error if `self` is strict and `T` has neither a storage nor was registered.
+    pub(crate) fn check_registered<T: Component>(
+        &self,
+        storage_id: StorageId,
+    ) -> Result<(), error::GetStorage> {
+        if !self.strict
+            || self.storages.contains(&storage_id)
+            || self.registered.contains(&StorageId::of::<SparseSet<T>>())
+        {
+            Ok(())
+        } else {
+            Err(error::GetStorage::UnregisteredStorage {
+                name: component_name::<T>(),
+                id: storage_id,
+            })
+        }
+    }
+    /// Returns the bit [`ComponentMask`]s use for `storage_id`, it's assigned the first time a storage is seen.
+    pub fn component_bit(&mut self, storage_id: StorageId) -> usize {
+        let next = self.component_bits.len();
+
+        *self.component_bits.entry(storage_id).or_insert(next)
+    }
+    /// Returns the mask of the storages `entity` has a component in.\
+    /// It's computed on demand by looking at all storages with a [`SparseArray`].
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Position;
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity;
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// let entity = all_storages.add_entity((Position, Velocity));
+    ///
+    /// let signature = all_storages.signature(entity);
+    /// assert_eq!(signature, all_storages.mask_of::<(Position, Velocity)>());
+    /// assert!(signature.is_superset(&all_storages.mask_of::<Position>()));
+    /// ```
+    ///
+    /// [`SparseArray`]: crate::SparseArray
+    pub fn signature(&mut self, entity: EntityId) -> ComponentMask {
+        let mut mask = ComponentMask::new();
+
+        for (storage_id, storage) in self.storages.iter_mut() {
+            let has_component = unsafe { &mut *storage.0 }
+                .get_mut()
+                .sparse_array()
+                .is_some_and(|sparse_array| sparse_array.contains(entity));
+
+            if has_component {
+                let next = self.component_bits.len();
+                mask.insert(*self.component_bits.entry(*storage_id).or_insert(next));
+            }
+        }
+
+        mask
+    }
+    /// Returns the mask of `T`'s storages.
+    pub fn mask_of<T: TupleSignature>(&mut self) -> ComponentMask {
+        let mut storage_ids = Vec::new();
+        T::storage_ids(&mut storage_ids);
+
+        let mut mask = ComponentMask::new();
+        for storage_id in storage_ids {
+            mask.insert(self.component_bit(storage_id));
+        }
+
+        mask
+    }
+    /// Returns the entities with exactly the components in `T`, no more, no less.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Position;
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity;
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// let entity = all_storages.add_entity((Position,));
+    /// all_storages.add_entity((Position, Velocity));
+    ///
+    /// assert_eq!(all_storages.query_exact::<Position>(), [entity]);
+    /// ```
+    pub fn query_exact<T: TupleSignature>(&mut self) -> Vec<EntityId> {
+        let mask = self.mask_of::<T>();
+
+        self.query_signature(|signature| *signature == mask)
+    }
+    /// Returns the entities with at least the components in `T`.
+    pub fn query_at_least<T: TupleSignature>(&mut self) -> Vec<EntityId> {
+        let mask = self.mask_of::<T>();
+
+        self.query_signature(|signature| signature.is_superset(&mask))
+    }
+    fn query_signature<F: Fn(&ComponentMask) -> bool>(&mut self, f: F) -> Vec<EntityId> {
+        let entities: Vec<EntityId> = self
+            .exclusive_storage_mut::<Entities>()
+            .unwrap()
+            .iter()
+            .collect();
+
+        entities
+            .into_iter()
+            .filter(|&entity| f(&self.signature(entity)))
+            .collect()
+    }
+    /// Deletes `entity` and all its descendants, `entity` is detached from its parent.\
+    /// Returns `false` if `entity` wasn't alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Children, EntitiesViewMut, Hierarchy, Parent, ViewMut, World};
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// let (root, child, grand_child) = all_storages.run(
+    ///     |mut hierarchy: (EntitiesViewMut, ViewMut<Parent>, ViewMut<Children>)| {
+    ///         let root = hierarchy.0.add_entity((), ());
+    ///         let child = hierarchy.attach_new(root);
+    ///         let grand_child = hierarchy.attach_new(child);
+    ///
+    ///         (root, child, grand_child)
+    ///     },
+    /// );
+    ///
+    /// assert!(all_storages.despawn_recursive(child));
+    /// assert!(all_storages.is_entity_alive(root));
+    /// assert!(!all_storages.is_entity_alive(grand_child));
+    /// ```
+    pub fn despawn_recursive(&mut self, entity: EntityId) -> bool {
+        let mut hierarchy = self
+            .borrow::<(
+                EntitiesViewMut<'_>,
+                ViewMut<'_, Parent>,
+                ViewMut<'_, Children>,
+            )>()
+            .unwrap();
+
+        if !hierarchy.0.is_alive(entity) {
+            return false;
+        }
+
+        hierarchy.detach(entity);
+
+        let descendants: Vec<EntityId> = (&hierarchy.1, &hierarchy.2).depth_first(entity).collect();
+
+        drop(hierarchy);
+
+        self.delete_entity(entity);
+        for descendant in descendants {
+            self.delete_entity(descendant);
+        }
+
+        true
+    }
+    /// Returns a [`StorageHandle`] to `T`'s storage, creating the storage if it doesn't exist.
+    ///
+    /// ### Panics
+    ///
+    /// - A storage of a different type has the same [`StorageId`].
+    #[track_caller]
+    pub fn storage_handle<T: Component + Send + Sync>(&self) -> StorageHandle<T> {
+        let storage_id = StorageId::of::<SparseSet<T>>();
+
+        let mut storages = self.storages.shard(&storage_id).write();
+        let storage = storages
+            .entry(storage_id)
+            .or_insert_with(|| SBox::new(SparseSet::<T>::new()));
+        if let Err(err) = storage.check::<SparseSet<T>>(storage_id) {
+            panic!("{:?}", err);
+        }
+
+        let storage = storage.0 as *const AtomicRefCell<dyn Storage>;
+        drop(storages);
+
+        StorageHandle::new(self.id, storage)
+    }
+    /// Plays back `stream` in this `AllStorages`.  
+    /// `registry` has to know all components present in the stream.  
+    /// The stream should be played in an empty `World` to get the same `EntityId`s as when it was recorded.
+    ///
+    /// ### Errors
+    ///
+    /// - A component isn't part of `registry`.
+    /// - A component's data couldn't be decoded.
+    /// - An entity couldn't be spawned with its recorded id.
+    /// - A component is added to an entity that isn't alive.
+    pub fn replay(
+        &mut self,
+        stream: &ReplayStream,
+        registry: &ReplayRegistry,
+    ) -> Result<(), error::Replay> {
+        stream.play(self, registry)
+    }
+    /// Saves all alive entities and the components in `registry` in a compact binary format.\
+    /// The snapshot starts with the name and layout hash of each registered component,
+    /// it can only be loaded with a compatible registry.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use shipyard::{AllStoragesViewMut, Component, SnapshotRegistry, World};
+    ///
+    /// #[derive(Component, Serialize, Deserialize)]
+    /// struct U32(u32);
+    ///
+    /// let mut registry = SnapshotRegistry::new();
+    /// registry.register::<U32>();
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// let entity = all_storages.add_entity((U32(0),));
+    /// let snapshot = all_storages.snapshot(&registry);
+    ///
+    /// let mut loaded = World::new();
+    /// loaded.load_snapshot(&snapshot, &registry).unwrap();
+    /// assert_eq!(loaded.get::<&U32>(entity).unwrap().0, 0);
+    /// ```
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn snapshot(&mut self, registry: &SnapshotRegistry) -> Vec<u8> {
+        crate::snapshot::save(self, registry, &SnapshotFilter::new())
+    }
+    /// Saves the alive entities and the components in `registry` selected by `filter`.\
+    /// Components of entities that aren't saved are skipped.
+    /// See [`AllStorages::snapshot`].
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn snapshot_filtered(
+        &mut self,
+        registry: &SnapshotRegistry,
+        filter: &SnapshotFilter<'_>,
+    ) -> Vec<u8> {
+        crate::snapshot::save(self, registry, filter)
+    }
+    /// Loads a snapshot created by [`AllStorages::snapshot`].\
+    /// It should be loaded in an empty `World` to get the same `EntityId`s as when it was saved.
+    ///
+    /// Components saved with a different layout hash than the one in `registry` are converted
+    /// with the migration registered for their old layout hash.\
+    /// Nothing is modified if the snapshot can't be decoded.
+    ///
+    /// ### Errors
+    ///
+    /// - The bytes aren't a valid snapshot.
+    /// - A component isn't part of `registry`.
+    /// - A component's layout hash doesn't match and there is no migration for it.
+    /// - A component couldn't be deserialized.
+    /// - An entity couldn't be spawned with its saved id.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn load_snapshot(
+        &mut self,
+        bytes: &[u8],
+        registry: &SnapshotRegistry,
+    ) -> Result<(), error::Snapshot> {
+        crate::snapshot::load(self, bytes, registry)
+    }
+    /// Loads a snapshot created by [`AllStorages::snapshot`], giving a new id to all its entities.\
+    /// Unlike [`AllStorages::load_snapshot`], the `World` doesn't have to be empty.
+    /// Returns the new id of each saved entity.
+    ///
+    /// `EntityId`s inside components registered with [`SnapshotRegistry::register_entity_references`] are updated.\
+    /// Components of entities that weren't alive when the snapshot was saved are not loaded.
+    ///
+    /// ### Errors
+    ///
+    /// - The bytes aren't a valid snapshot.
+    /// - A component isn't part of `registry`.
+    /// - A component's layout hash doesn't match and there is no migration for it.
+    /// - A component couldn't be deserialized.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn load_snapshot_remapped(
+        &mut self,
+        bytes: &[u8],
+        registry: &SnapshotRegistry,
+    ) -> Result<EntityIdMap, error::Snapshot> {
+        crate::snapshot::load_remapped(self, bytes, registry)
+    }
+    /// Applies the differences between two snapshots computed by [`WorldDiff::between`].\
+    /// The `AllStorages` is expected to be in the state of the older snapshot.
+    ///
+    /// ### Errors
+    ///
+    /// - The newer snapshot was saved by a newer version of the registry.
+    /// - A component isn't part of `registry`.
+    /// - A component's layout hash doesn't match and there is no migration for it.
+    /// - A component couldn't be deserialized.
+    /// - An added entity couldn't be spawned with its id.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn apply_diff(
+        &mut self,
+        diff: &WorldDiff,
+        registry: &SnapshotRegistry,
+    ) -> Result<(), error::Snapshot> {
+        crate::snapshot::apply_diff(self, diff, registry)
+    }
+    /// Makes the components in `registry` replicated.\
+    /// Their storages track insertion, modification, deletion and removal from now on.
+    /// If replication was already enabled, the previous registry is replaced.
+    ///
+    /// Components already present count as inserted when replication is enabled.\
+    /// Deleted components are kept until [`AllStorages::clear_replication_older_than`] is called.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    pub fn enable_replication(&mut self, registry: ReplicationRegistry) {
+        crate::replication::enable_tracking(self, &registry);
+
+        self.replication = Some(Box::new(registry));
+    }
+    /// Returns the changes of the replicated components since `since_tick`.\
+    /// Use [`TrackingTimestamp::origin`] to get everything or the tick of the last delta sent to a client.
+    ///
+    /// Entities are listed as spawned when all their replicated components were inserted after `since_tick`
+    /// and as despawned when they are no longer alive and had a replicated component deleted after `since_tick`.
+    ///
+    /// ### Panics
+    ///
+    /// - Replication isn't enabled.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    #[track_caller]
+    pub fn collect_replication_delta(&mut self, since_tick: TrackingTimestamp) -> ReplicationDelta {
+        let registry = self
+            .replication
+            .take()
+            .expect("Replication isn't enabled, call enable_replication first.");
+
+        let delta = crate::replication::collect(self, &registry, since_tick);
+
+        self.replication = Some(registry);
+
+        delta
+    }
+    /// Returns the changes of the replicated components `client` should see since the last delta collected for it.\
+    /// Entities entering the client's interest are sent with all their replicated components,
+    /// entities leaving it or deleted are listed as despawned.
+    ///
+    /// ### Panics
+    ///
+    /// - Replication isn't enabled.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    #[track_caller]
+    pub fn collect_replication_delta_for(
+        &mut self,
+        client: &mut ReplicationClient,
+    ) -> ReplicationDelta {
+        let registry = self
+            .replication
+            .take()
+            .expect("Replication isn't enabled, call enable_replication first.");
+
+        let delta = crate::replication::collect_for(self, &registry, client);
+
+        self.replication = Some(registry);
+
+        delta
+    }
+    /// Removes the deletion and removal tracking of the replicated components older than `tick`.\
+    /// Call it with the oldest tick a client might still ask a delta for.
+    ///
+    /// ### Panics
+    ///
+    /// - Replication isn't enabled.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    #[track_caller]
+    pub fn clear_replication_older_than(&mut self, tick: TrackingTimestamp) {
+        let registry = self
+            .replication
+            .take()
+            .expect("Replication isn't enabled, call enable_replication first.");
+
+        crate::replication::clear_older_than(self, &registry, tick);
+
+        self.replication = Some(registry);
+    }
+    /// Applies a delta created by [`AllStorages::collect_replication_delta`].\
+    /// `map` links server entities to the entities of this `World`, unknown server entities get a new entity.
+    /// It has to be kept between deltas.
+    ///
+    /// `EntityId`s inside components registered with [`ReplicationRegistry::register_entity_references`] are updated.\
+    /// Replication doesn't have to be enabled.
+    ///
+    /// ### Errors
+    ///
+    /// - A component isn't part of `registry`.
+    /// - A component couldn't be deserialized.
+    ///
+    /// Nothing is modified if the delta can't be decoded.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    pub fn apply_replication_delta(
+        &mut self,
+        delta: &ReplicationDelta,
+        registry: &ReplicationRegistry,
+        map: &mut EntityIdMap,
+    ) -> Result<(), error::Replication> {
+        crate::replication::apply(self, delta, registry, map)
+    }
+}
+
+impl core::fmt::Debug for AllStorages {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut debug_struct = f.debug_struct("AllStorages");
+
+        let shards = self.storages.read_all();
+
+        debug_struct.field(
+            "storage_count",
+            &shards.iter().map(|shard| shard.len()).sum::<usize>(),
+        );
+        debug_struct.field(
+            "storages",
+            &shards
+                .iter()
+                .flat_map(|shard| shard.values())
+                .collect::<Vec<_>>(),
+        );
+
+        debug_struct.finish()
+    }
+}
+
+impl core::fmt::Debug for AllStoragesMemoryUsage<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut borrowed_storages = 0;
+
+        let mut debug_struct = f.debug_list();
+
+        let shards = self.0.storages.read_all();
+
+        debug_struct.entries(shards.iter().flat_map(|shard| shard.values()).filter_map(
+            |storage| match unsafe { &*(storage.0) }.borrow() {
+                Ok(storage) => storage.memory_usage(),
+                Err(_) => {
+                    borrowed_storages += 1;
+                    None
+                }
+            },
+        ));
 
         if borrowed_storages != 0 {
             debug_struct.entry(&format_args!(
@@ -0,0 +1,60 @@
+use super::AllStorages;
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::get::Get;
+use crate::hierarchy::{Children, Parent};
+use crate::views::View;
+use alloc::vec::Vec;
+
+pub(super) type RelatedFn = fn(&AllStorages, EntityId) -> Vec<EntityId>;
+pub(super) type OrphanFn = fn(&mut AllStorages, EntityId);
+
+/// Component linking the entity owning it to other entities, like [`Children`].
+///
+/// What happens to the related entities when the owner is deleted is set with [`AllStorages::set_deletion_policy`].
+pub trait Relation: Component + Send + Sync {
+    /// Returns the entities related to the owner of this component.
+    fn related(&self) -> &[EntityId];
+    /// Called on each related entity left alive by [`DeletionPolicy::Orphan`], after its owner is deleted.
+    #[allow(unused_variables)]
+    fn orphan(all_storages: &mut AllStorages, related: EntityId) {}
+}
+
+impl Relation for Children {
+    fn related(&self) -> &[EntityId] {
+        self
+    }
+    fn orphan(all_storages: &mut AllStorages, related: EntityId) {
+        all_storages.remove::<(Parent,)>(related);
+    }
+}
+
+/// What [`AllStorages::delete_entity`] does to the entities related to the deleted one.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DeletionPolicy {
+    /// Deletes the related entities too, following their own relations.
+    Cascade,
+    /// Keeps the related entities alive, [`Relation::orphan`] is called on them.
+    Orphan,
+    /// Refuses to delete an entity with related entities.
+    Forbid,
+}
+
+/// Returns the entities `R` relates to `entity`.
+pub(super) fn related<R: Relation>(all_storages: &AllStorages, entity: EntityId) -> Vec<EntityId> {
+    all_storages
+        .borrow::<View<'_, R>>()
+        .ok()
+        .and_then(|relations| {
+            (&relations)
+                .get(entity)
+                .ok()
+                .map(|relation| relation.related().to_vec())
+        })
+        .unwrap_or_default()
+}
+
+/// Calls `R::orphan`.
+pub(super) fn orphan<R: Relation>(all_storages: &mut AllStorages, related: EntityId) {
+    R::orphan(all_storages, related);
+}
@@ -0,0 +1,71 @@
+use crate::public_transport::{ReadGuard, RwLock};
+use crate::storage::{SBox, StorageId};
+use crate::ShipHashMap;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Number of shards, has to be a power of two.
+const SHARD_COUNT: usize = 16;
+
+/// Storages of an `AllStorages`, split in shards each behind its own lock.
+///
+/// Looking up storages in different shards doesn't contend on the same lock.\
+/// A single shard is locked at a time, except by [`StorageMap::read_all`] which locks them in order.
+pub(crate) struct StorageMap {
+    shards: Box<[RwLock<ShipHashMap<StorageId, SBox>>]>,
+}
+
+impl StorageMap {
+    /// Creates a `StorageMap`, `new_lock` is called once per shard.
+    pub(crate) fn new(
+        mut new_lock: impl FnMut(ShipHashMap<StorageId, SBox>) -> RwLock<ShipHashMap<StorageId, SBox>>,
+    ) -> StorageMap {
+        StorageMap {
+            shards: (0..SHARD_COUNT)
+                .map(|_| new_lock(ShipHashMap::default()))
+                .collect(),
+        }
+    }
+    /// Returns the lock of the shard `storage_id` belongs to.
+    #[inline]
+    pub(crate) fn shard(&self, storage_id: &StorageId) -> &RwLock<ShipHashMap<StorageId, SBox>> {
+        &self.shards[shard_index(storage_id)]
+    }
+    /// Returns the shard `storage_id` belongs to.
+    #[inline]
+    pub(crate) fn shard_mut(
+        &mut self,
+        storage_id: &StorageId,
+    ) -> &mut ShipHashMap<StorageId, SBox> {
+        self.shards[shard_index(storage_id)].get_mut()
+    }
+    /// Returns `true` if there is a storage at `storage_id`.
+    pub(crate) fn contains(&self, storage_id: &StorageId) -> bool {
+        self.shard(storage_id).read().contains_key(storage_id)
+    }
+    /// Locks all shards.
+    pub(crate) fn read_all(&self) -> Vec<ReadGuard<'_, ShipHashMap<StorageId, SBox>>> {
+        self.shards.iter().map(RwLock::read).collect()
+    }
+    /// Returns an iterator over all storages.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&StorageId, &mut SBox)> {
+        self.shards
+            .iter_mut()
+            .flat_map(|shard| shard.get_mut().iter_mut())
+    }
+    /// Returns an iterator over all storages.
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut SBox> {
+        self.iter_mut().map(|(_, storage)| storage)
+    }
+}
+
+/// `TypeId`s are already hashes and custom ids are often sequential, their low bits are spread enough.
+#[inline]
+fn shard_index(storage_id: &StorageId) -> usize {
+    let bits = match storage_id {
+        StorageId::TypeId(type_id) => type_id.0 as usize,
+        StorageId::Custom(id) => *id as usize,
+    };
+
+    bits & (SHARD_COUNT - 1)
+}
@@ -0,0 +1,17 @@
+use super::AllStorages;
+use crate::component::Component;
+use crate::entity_id::EntityId;
+
+pub(super) type TransferFn = fn(&mut AllStorages, EntityId, &mut AllStorages, EntityId);
+
+/// Moves `entity`'s `T` from `from` to `new_entity` in `to`.
+pub(super) fn transfer_component<T: Component + Send + Sync>(
+    from: &mut AllStorages,
+    entity: EntityId,
+    to: &mut AllStorages,
+    new_entity: EntityId,
+) {
+    if let Some(component) = from.remove::<T>(entity) {
+        to.add_component(new_entity, component);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use alloc::format;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use arrow_array::{Array, ArrayRef, RecordBatch, UInt64Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+/// Name of the column holding the `EntityId`s in record batches created from a storage.
+pub const ARROW_ID_COLUMN: &str = "id";
+
+/// Components that can be converted to and from Apache Arrow columns.
+///
+/// ### Example
+/// ```
+/// use arrow_array::{Array, ArrayRef, Float32Array};
+/// use arrow_schema::{ArrowError, DataType, Field};
+/// use shipyard::{ArrowComponent, Component};
+/// use std::sync::Arc;
+///
+/// #[derive(Component)]
+/// struct Pos {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// impl ArrowComponent for Pos {
+///     fn fields() -> Vec<Field> {
+///         vec![
+///             Field::new("x", DataType::Float32, false),
+///             Field::new("y", DataType::Float32, false),
+///         ]
+///     }
+///     fn to_columns(components: &[Self]) -> Vec<ArrayRef> {
+///         vec![
+///             Arc::new(Float32Array::from_iter_values(components.iter().map(|pos| pos.x))),
+///             Arc::new(Float32Array::from_iter_values(components.iter().map(|pos| pos.y))),
+///         ]
+///     }
+///     fn from_columns(columns: &[ArrayRef]) -> Result<Vec<Self>, ArrowError> {
+///         let column = |i: usize| {
+///             columns
+///                 .get(i)
+///                 .and_then(|column| column.as_any().downcast_ref::<Float32Array>())
+///                 .ok_or_else(|| ArrowError::SchemaError("expected two f32 columns".to_string()))
+///         };
+///
+///         Ok(column(0)?
+///             .values()
+///             .iter()
+///             .zip(column(1)?.values().iter())
+///             .map(|(&x, &y)| Pos { x, y })
+///             .collect())
+///     }
+/// }
+/// ```
+pub trait ArrowComponent: Component + Sized {
+    /// Describes the columns returned by [`ArrowComponent::to_columns`].
+    fn fields() -> Vec<Field>;
+    /// Converts `components` to columns, one array per field, in the same order as [`ArrowComponent::fields`].
+    fn to_columns(components: &[Self]) -> Vec<ArrayRef>;
+    /// Rebuilds the components from `columns`, in the same order as [`ArrowComponent::fields`] and without the id column.
+    fn from_columns(columns: &[ArrayRef]) -> Result<Vec<Self>, ArrowError>;
+}
+
+pub(crate) fn to_record_batch<T: ArrowComponent>(
+    ids: &[EntityId],
+    components: &[T],
+) -> Result<RecordBatch, ArrowError> {
+    let mut fields = Vec::with_capacity(1 + T::fields().len());
+    fields.push(Field::new(ARROW_ID_COLUMN, DataType::UInt64, false));
+    fields.extend(T::fields());
+
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(fields.len());
+    columns.push(Arc::new(UInt64Array::from_iter_values(
+        ids.iter().map(|id| id.inner()),
+    )));
+    columns.extend(T::to_columns(components));
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
+
+pub(crate) fn from_record_batch<T: ArrowComponent>(
+    batch: &RecordBatch,
+) -> Result<impl Iterator<Item = (EntityId, T)>, ArrowError> {
+    let ids = batch
+        .column_by_name(ARROW_ID_COLUMN)
+        .and_then(|column| column.as_any().downcast_ref::<UInt64Array>())
+        .filter(|column| column.null_count() == 0)
+        .ok_or_else(|| {
+            ArrowError::SchemaError(format!(
+                "Record batch has no non-nullable UInt64 `{}` column.",
+                ARROW_ID_COLUMN
+            ))
+        })?;
+
+    let ids = ids
+        .values()
+        .iter()
+        .map(|&inner| {
+            EntityId::from_inner(inner).ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!("{} is not a valid EntityId.", inner))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let schema = batch.schema();
+    let columns: Vec<ArrayRef> = schema
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .filter(|(field, _)| field.name() != ARROW_ID_COLUMN)
+        .map(|(_, column)| column.clone())
+        .collect();
+
+    let components = T::from_columns(&columns)?;
+
+    if components.len() != ids.len() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "{} components were decoded for {} ids.",
+            components.len(),
+            ids.len()
+        )));
+    }
+
+    Ok(ids.into_iter().zip(components))
+}
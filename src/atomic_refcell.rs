@@ -112,6 +112,11 @@ impl<T> AtomicRefCell<T> {
 }
 
 impl<T: ?Sized> AtomicRefCell<T> {
+    /// Returns the threads and systems currently borrowing the value.
+    #[cfg(feature = "borrow_debug")]
+    pub(crate) fn holders(&self) -> alloc::vec::Vec<crate::borrow_debug::BorrowHolder> {
+        self.borrow_state.holders()
+    }
     /// Immutably borrows the wrapped value, returning an error if the value is currently mutably
     /// borrowed.
     ///
@@ -206,6 +211,16 @@ impl<T: ?Sized> AtomicRefCell<T> {
             Err(err) => Err(err),
         }
     }
+    /// Returns the value if it can be accessed from any thread, without borrowing.
+    #[inline]
+    pub(crate) fn get_mut_send_sync(&mut self) -> Option<&'_ mut T> {
+        #[cfg(feature = "thread_local")]
+        if self.send.is_some() || !self.is_sync {
+            return None;
+        }
+
+        Some(self.inner.get_mut())
+    }
     #[inline]
     #[track_caller]
     pub(crate) fn get_mut(&mut self) -> &'_ mut T {
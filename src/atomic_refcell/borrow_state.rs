@@ -1,26 +1,50 @@
+#[cfg(feature = "borrow_debug")]
+use crate::borrow_debug::{BorrowHolder, Holders};
 use crate::error;
+#[cfg(feature = "borrow_debug")]
+use alloc::vec::Vec;
 use core::sync::atomic::{AtomicUsize, Ordering};
 
 const HIGH_BIT: usize = !(usize::MAX >> 1);
 const MAX_FAILED_BORROWS: usize = HIGH_BIT + (HIGH_BIT >> 1);
 
-pub(super) struct BorrowState(AtomicUsize);
+pub(super) struct BorrowState(AtomicUsize, #[cfg(feature = "borrow_debug")] Holders);
 
 /// Unlocks a shared borrow on drop.
+///
+/// Frozen borrows don't have any state, they're used when nothing can modify the storage.
 #[must_use]
-pub struct SharedBorrow<'a>(&'a BorrowState);
+pub struct SharedBorrow<'a>(Option<&'a BorrowState>);
+
+impl SharedBorrow<'_> {
+    /// Returns a borrow that doesn't lock anything.
+    ///
+    /// The caller has to make sure nothing can borrow the storage exclusively while it's alive.
+    #[inline]
+    pub(crate) fn frozen() -> Self {
+        SharedBorrow(None)
+    }
+}
 
 impl Drop for SharedBorrow<'_> {
     #[inline]
     fn drop(&mut self) {
-        (self.0).0.fetch_sub(1, Ordering::Release);
+        if let Some(state) = self.0 {
+            #[cfg(feature = "borrow_debug")]
+            state.1.remove(false);
+
+            state.0.fetch_sub(1, Ordering::Release);
+        }
     }
 }
 
 impl Clone for SharedBorrow<'_> {
     #[inline]
     fn clone(&self) -> Self {
-        self.0.read_reborrow()
+        match self.0 {
+            Some(state) => state.read_reborrow(),
+            None => SharedBorrow(None),
+        }
     }
 }
 
@@ -37,6 +61,9 @@ impl ExclusiveBorrow<'_> {
 impl Drop for ExclusiveBorrow<'_> {
     #[inline]
     fn drop(&mut self) {
+        #[cfg(feature = "borrow_debug")]
+        (self.0).1.remove(true);
+
         (self.0).0.store(0, Ordering::Release);
     }
 }
@@ -44,7 +71,25 @@ impl Drop for ExclusiveBorrow<'_> {
 impl BorrowState {
     #[inline]
     pub(super) fn new() -> Self {
-        BorrowState(AtomicUsize::new(0))
+        BorrowState(
+            AtomicUsize::new(0),
+            #[cfg(feature = "borrow_debug")]
+            Holders::new(),
+        )
+    }
+
+    /// Returns the threads and systems currently borrowing.
+    #[cfg(feature = "borrow_debug")]
+    pub(super) fn holders(&self) -> Vec<BorrowHolder> {
+        self.1.get()
+    }
+
+    #[inline]
+    fn shared(&self) -> SharedBorrow<'_> {
+        #[cfg(feature = "borrow_debug")]
+        self.1.push(false);
+
+        SharedBorrow(Some(self))
     }
 
     #[inline]
@@ -55,7 +100,7 @@ impl BorrowState {
 
             Err(error::Borrow::Unique)
         } else {
-            Ok(SharedBorrow(self))
+            Ok(self.shared())
         }
     }
 
@@ -71,7 +116,7 @@ impl BorrowState {
         };
 
         if old == 0 {
-            Ok(SharedBorrow(self))
+            Ok(self.shared())
         } else if old & HIGH_BIT == 0 {
             Err(error::Borrow::Shared)
         } else {
@@ -85,7 +130,7 @@ impl BorrowState {
 
         self.check_overflow(new);
 
-        SharedBorrow(self)
+        self.shared()
     }
 
     #[inline]
@@ -99,6 +144,9 @@ impl BorrowState {
         };
 
         if old == 0 {
+            #[cfg(feature = "borrow_debug")]
+            self.1.push(true);
+
             Ok(ExclusiveBorrow(self))
         } else if old & HIGH_BIT == 0 {
             Err(error::Borrow::Shared)
@@ -142,6 +190,6 @@ mod tests {
 
         let read = write.shared_reborrow();
 
-        assert_eq!(HIGH_BIT + 1, (read.0).0.load(Ordering::Relaxed));
+        assert_eq!(HIGH_BIT + 1, read.0.unwrap().0.load(Ordering::Relaxed));
     }
 }
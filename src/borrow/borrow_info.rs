@@ -6,6 +6,8 @@ use super::non_send_sync::NonSendSync;
 use super::non_sync::NonSync;
 use super::Mutability;
 use crate::all_storages::{AllStorages, CustomStorageAccess};
+#[cfg(feature = "std")]
+use crate::budget::Budget;
 use crate::component::{Component, Unique};
 use crate::entities::Entities;
 use crate::error;
@@ -16,8 +18,8 @@ use crate::system::Nothing;
 use crate::tracking::Tracking;
 use crate::unique::UniqueStorage;
 use crate::views::{
-    AllStoragesView, AllStoragesViewMut, EntitiesView, EntitiesViewMut, UniqueView, UniqueViewMut,
-    View, ViewMut,
+    AllStoragesView, AllStoragesViewMut, AllStoragesViewOf, EntitiesView, EntitiesViewMut,
+    UniqueView, UniqueViewMut, View, ViewMut,
 };
 use alloc::vec::Vec;
 use core::any::type_name;
@@ -69,6 +71,30 @@ pub unsafe trait BorrowInfo {
     );
 }
 
+/// Checks that everything `V` borrows is covered by `access`.
+pub(crate) fn check_access<V: BorrowInfo>(access: &[TypeInfo]) -> Result<(), error::GetStorage> {
+    let mut requested = Vec::new();
+    V::borrow_info(&mut requested);
+
+    for info in requested {
+        let is_allowed = access.iter().any(|granted| {
+            granted.storage_id == info.storage_id
+                && (granted.mutability == Mutability::Exclusive
+                    || info.mutability == Mutability::Shared)
+        });
+
+        if !is_allowed {
+            return Err(error::GetStorage::NotAllowed {
+                name: info.name,
+                id: info.storage_id,
+                mutability: info.mutability,
+            });
+        }
+    }
+
+    Ok(())
+}
+
 // this is needed for downstream crates to impl IntoWorkloadSystem
 unsafe impl BorrowInfo for Nothing {
     fn borrow_info(_: &mut Vec<TypeInfo>) {}
@@ -80,6 +106,12 @@ unsafe impl BorrowInfo for () {
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
 }
 
+#[cfg(feature = "std")]
+unsafe impl BorrowInfo for Budget {
+    fn borrow_info(_: &mut Vec<TypeInfo>) {}
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
+
 unsafe impl<'a> BorrowInfo for AllStoragesView<'a> {
     fn borrow_info(info: &mut Vec<TypeInfo>) {
         info.push(TypeInfo {
@@ -95,6 +127,24 @@ unsafe impl<'a> BorrowInfo for AllStoragesView<'a> {
     fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
 }
 
+// SAFE `AllStoragesViewOf` only gives access to the storages of `V`
+unsafe impl<'a, V: BorrowInfo> BorrowInfo for AllStoragesViewOf<'a, V> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        let len = info.len();
+
+        V::borrow_info(info);
+
+        if info.len() == len {
+            AllStoragesView::borrow_info(info);
+        }
+    }
+    fn enable_tracking(
+        enable_tracking_fn: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>,
+    ) {
+        V::enable_tracking(enable_tracking_fn);
+    }
+}
+
 unsafe impl<'a> BorrowInfo for AllStoragesViewMut<'a> {
     fn borrow_info(info: &mut Vec<TypeInfo>) {
         info.push(TypeInfo {
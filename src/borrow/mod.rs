@@ -7,6 +7,7 @@ mod non_send_sync;
 mod non_sync;
 mod world_borrow;
 
+pub(crate) use borrow_info::check_access;
 pub use borrow_info::BorrowInfo;
 #[cfg(feature = "thread_local")]
 pub use non_send::NonSend;
@@ -21,6 +22,7 @@ use crate::atomic_refcell::{ARef, ARefMut, SharedBorrow};
 use crate::component::{Component, Unique};
 use crate::error;
 use crate::sparse_set::SparseSet;
+use crate::storage::StorageId;
 use crate::system::Nothing;
 use crate::tracking::{Tracking, TrackingTimestamp};
 use crate::unique::UniqueStorage;
@@ -177,6 +179,8 @@ where
         last_run: Option<TrackingTimestamp>,
         current: TrackingTimestamp,
     ) -> Result<Self::View<'a>, error::GetStorage> {
+        all_storages.check_registered::<T>(StorageId::of::<SparseSet<T>>())?;
+
         let view = all_storages.custom_storage_or_insert(SparseSet::new)?;
 
         let (sparse_set, borrow) = unsafe { ARef::destructure(view) };
@@ -201,6 +205,8 @@ where
         last_run: Option<TrackingTimestamp>,
         current: TrackingTimestamp,
     ) -> Result<Self::View<'a>, error::GetStorage> {
+        all_storages.check_registered::<T>(StorageId::of::<NonSend<SparseSet<T>>>())?;
+
         let view = all_storages.custom_storage_or_insert_non_send(|| NonSend(SparseSet::new()))?;
 
         let (sparse_set, borrow) = unsafe { ARef::destructure(view) };
@@ -227,6 +233,8 @@ where
         last_run: Option<TrackingTimestamp>,
         current: TrackingTimestamp,
     ) -> Result<Self::View<'a>, error::GetStorage> {
+        all_storages.check_registered::<T>(StorageId::of::<NonSync<SparseSet<T>>>())?;
+
         let view = all_storages.custom_storage_or_insert_non_sync(|| NonSync(SparseSet::new()))?;
 
         let (sparse_set, borrow) = unsafe { ARef::destructure(view) };
@@ -253,6 +261,8 @@ where
         last_run: Option<TrackingTimestamp>,
         current: TrackingTimestamp,
     ) -> Result<Self::View<'a>, error::GetStorage> {
+        all_storages.check_registered::<T>(StorageId::of::<NonSendSync<SparseSet<T>>>())?;
+
         let view = all_storages
             .custom_storage_or_insert_non_send_sync(|| NonSendSync(SparseSet::new()))?;
 
@@ -279,6 +289,8 @@ where
         last_run: Option<TrackingTimestamp>,
         current: TrackingTimestamp,
     ) -> Result<Self::View<'a>, error::GetStorage> {
+        all_storages.check_registered::<T>(StorageId::of::<SparseSet<T>>())?;
+
         let view = all_storages.custom_storage_or_insert_mut(SparseSet::new)?;
 
         let (sparse_set, borrow) = unsafe { ARefMut::destructure(view) };
@@ -312,6 +324,8 @@ where
         last_run: Option<TrackingTimestamp>,
         current: TrackingTimestamp,
     ) -> Result<Self::View<'a>, error::GetStorage> {
+        all_storages.check_registered::<T>(StorageId::of::<NonSend<SparseSet<T>>>())?;
+
         let view =
             all_storages.custom_storage_or_insert_non_send_mut(|| NonSend(SparseSet::new()))?;
 
@@ -346,6 +360,8 @@ where
         last_run: Option<TrackingTimestamp>,
         current: TrackingTimestamp,
     ) -> Result<Self::View<'a>, error::GetStorage> {
+        all_storages.check_registered::<T>(StorageId::of::<NonSync<SparseSet<T>>>())?;
+
         let view =
             all_storages.custom_storage_or_insert_non_sync_mut(|| NonSync(SparseSet::new()))?;
 
@@ -380,6 +396,8 @@ where
         last_run: Option<TrackingTimestamp>,
         current: TrackingTimestamp,
     ) -> Result<Self::View<'a>, error::GetStorage> {
+        all_storages.check_registered::<T>(StorageId::of::<NonSendSync<SparseSet<T>>>())?;
+
         let view = all_storages
             .custom_storage_or_insert_non_send_sync_mut(|| NonSendSync(SparseSet::new()))?;
 
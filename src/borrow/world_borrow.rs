@@ -1,9 +1,12 @@
 use crate::atomic_refcell::ARef;
 use crate::borrow::Borrow;
+#[cfg(feature = "std")]
+use crate::budget::Budget;
 use crate::error;
 use crate::tracking::TrackingTimestamp;
-use crate::views::{AllStoragesView, AllStoragesViewMut};
+use crate::views::{AllStoragesView, AllStoragesViewMut, AllStoragesViewOf};
 use crate::world::World;
+use core::marker::PhantomData;
 
 /// Allows a type to be borrowed by [`World::borrow`] and [`World::run`].
 pub trait WorldBorrow {
@@ -55,6 +58,36 @@ impl WorldBorrow for AllStoragesView<'_> {
     }
 }
 
+impl<V: Borrow> WorldBorrow for AllStoragesViewOf<'_, V> {
+    type WorldView<'a> = AllStoragesViewOf<'a, V>;
+
+    #[inline]
+    fn world_borrow(
+        world: &World,
+        last_run: Option<TrackingTimestamp>,
+        current: TrackingTimestamp,
+    ) -> Result<Self::WorldView<'_>, error::GetStorage> {
+        Ok(AllStoragesViewOf {
+            all_storages: AllStoragesView::world_borrow(world, last_run, current)?,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl WorldBorrow for Budget {
+    type WorldView<'a> = Budget;
+
+    #[inline]
+    fn world_borrow(
+        _world: &World,
+        _last_run: Option<TrackingTimestamp>,
+        _current: TrackingTimestamp,
+    ) -> Result<Self::WorldView<'_>, error::GetStorage> {
+        Ok(Budget::current())
+    }
+}
+
 impl WorldBorrow for AllStoragesViewMut<'_> {
     type WorldView<'a> = AllStoragesViewMut<'a>;
 
@@ -0,0 +1,152 @@
+use crate::entities::Entities;
+use crate::error;
+use crate::scheduler::Label;
+use crate::storage::StorageId;
+use crate::world::World;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::{Debug, Formatter};
+use std::sync::Mutex;
+use std::thread::ThreadId;
+
+std::thread_local! {
+    static CURRENT_SYSTEM: RefCell<Option<Box<dyn Label>>> = const { RefCell::new(None) };
+}
+
+/// Thread and system holding a storage borrow, recorded with the **borrow_debug** feature.
+#[derive(Clone)]
+pub struct BorrowHolder {
+    /// Thread that borrowed the storage.
+    pub thread: ThreadId,
+    /// Name of the thread, if it has one.
+    pub thread_name: Option<String>,
+    /// System running on this thread when the storage was borrowed.
+    pub system: Option<Box<dyn Label>>,
+    /// `true` for an exclusive borrow.
+    pub exclusive: bool,
+}
+
+impl BorrowHolder {
+    fn current(exclusive: bool) -> BorrowHolder {
+        let thread = std::thread::current();
+
+        BorrowHolder {
+            thread: thread.id(),
+            thread_name: thread.name().map(String::from),
+            system: CURRENT_SYSTEM.with(|system| system.borrow().clone()),
+            exclusive,
+        }
+    }
+}
+
+impl Debug for BorrowHolder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(if self.exclusive {
+            "exclusive borrow"
+        } else {
+            "shared borrow"
+        })?;
+
+        match &self.thread_name {
+            Some(name) => write!(f, " on thread {:?} ({:?})", name, self.thread)?,
+            None => write!(f, " on thread {:?}", self.thread)?,
+        }
+
+        if let Some(system) = &self.system {
+            write!(f, " by system {:?}", system)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Borrows currently held on a storage.
+pub(crate) struct Holders(Mutex<Vec<BorrowHolder>>);
+
+impl Holders {
+    pub(crate) fn new() -> Holders {
+        Holders(Mutex::new(Vec::new()))
+    }
+    pub(crate) fn push(&self, exclusive: bool) {
+        self.lock().push(BorrowHolder::current(exclusive));
+    }
+    /// Removes a borrow made on the current thread, borrows can be sent to other threads so falls back to any borrow.
+    pub(crate) fn remove(&self, exclusive: bool) {
+        let current = std::thread::current().id();
+        let mut holders = self.lock();
+
+        let index = holders
+            .iter()
+            .position(|holder| holder.exclusive == exclusive && holder.thread == current)
+            .or_else(|| {
+                holders
+                    .iter()
+                    .position(|holder| holder.exclusive == exclusive)
+            });
+
+        if let Some(index) = index {
+            holders.swap_remove(index);
+        }
+    }
+    pub(crate) fn get(&self) -> Vec<BorrowHolder> {
+        self.lock().clone()
+    }
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<BorrowHolder>> {
+        // a panic while holding the lock can't leave the list in an invalid state
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Runs `f` with `system` recorded as the current system of this thread.
+pub(crate) fn with_current_system<R>(system: &dyn Label, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Box<dyn Label>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+
+            CURRENT_SYSTEM.with(|system| *system.borrow_mut() = previous);
+        }
+    }
+
+    let _restore =
+        Restore(CURRENT_SYSTEM.with(|current| current.borrow_mut().replace(system.dyn_clone())));
+
+    f()
+}
+
+/// Returns `true` if `error` can go away once other borrows are released.
+pub(crate) fn is_contention(error: &error::GetStorage) -> bool {
+    match error {
+        error::GetStorage::AllStoragesBorrow(borrow)
+        | error::GetStorage::StorageBorrow { borrow, .. }
+        | error::GetStorage::Entities(borrow) => {
+            matches!(borrow, error::Borrow::Unique | error::Borrow::Shared)
+        }
+        _ => false,
+    }
+}
+
+/// Returns the borrows held on the storage `error` is about.
+pub(crate) fn holders(world: &World, error: &error::GetStorage) -> Vec<BorrowHolder> {
+    let storage_id = match error {
+        error::GetStorage::AllStoragesBorrow(_) => return world.all_storages.holders(),
+        error::GetStorage::StorageBorrow { id, .. } => *id,
+        error::GetStorage::Entities(_) => StorageId::of::<Entities>(),
+        _ => return Vec::new(),
+    };
+
+    match world.all_storages.borrow() {
+        Ok(all_storages) => all_storages
+            .storages
+            .shard(&storage_id)
+            .read()
+            .get(&storage_id)
+            // SAFE the storage can't be removed while the shard is locked
+            .map(|storage| unsafe { &*storage.0 }.holders())
+            .unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,168 @@
+use crate::scheduler::{Label, WorkloadSystem};
+use crate::world::World;
+use crate::ShipHashMap;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use core::cell::Cell;
+use core::time::Duration;
+use std::time::Instant;
+
+std::thread_local! {
+    static CURRENT_BUDGET: Cell<Option<(Instant, Duration)>> = const { Cell::new(None) };
+}
+
+/// Time left to a system marked with [`SystemModificator::budget`].
+///
+/// The budget starts when the system does. Systems without budget get an unlimited one.\
+/// The budget is cooperative, the system has to check it and stop its work when it's exhausted.
+/// Runs going over budget are recorded in [`World::budget_report`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{Budget, SystemModificator, Unique, UniqueViewMut, Workload, World};
+/// use std::time::Duration;
+///
+/// #[derive(Unique)]
+/// struct Paths(Vec<u32>);
+///
+/// fn pathfinding(budget: Budget, mut paths: UniqueViewMut<Paths>) {
+///     while !budget.is_exhausted() && paths.0.len() < 10 {
+///         paths.0.push(0);
+///     }
+/// }
+///
+/// let world = World::new();
+/// world.add_unique(Paths(Vec::new()));
+///
+/// Workload::new("")
+///     .with_system(pathfinding.budget(Duration::from_millis(2)))
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// world.run_default_workload().unwrap();
+/// ```
+///
+/// [`SystemModificator::budget`]: crate::SystemModificator::budget
+/// [`World::budget_report`]: crate::World::budget_report
+#[derive(Clone, Copy, Debug)]
+pub struct Budget {
+    start: Instant,
+    limit: Option<Duration>,
+}
+
+impl Budget {
+    /// Returns the budget of the running system.
+    pub(crate) fn current() -> Budget {
+        match CURRENT_BUDGET.with(Cell::get) {
+            Some((start, limit)) => Budget {
+                start,
+                limit: Some(limit),
+            },
+            None => Budget {
+                start: Instant::now(),
+                limit: None,
+            },
+        }
+    }
+    /// Returns the total time given to the system, `None` if it has no budget.
+    pub fn limit(&self) -> Option<Duration> {
+        self.limit
+    }
+    /// Returns the time spent since the system started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+    /// Returns the time left, [`Duration::MAX`] if the system has no budget.
+    pub fn remaining(&self) -> Duration {
+        match self.limit {
+            Some(limit) => limit.saturating_sub(self.elapsed()),
+            None => Duration::MAX,
+        }
+    }
+    /// Returns `true` when there is no time left.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == Duration::ZERO
+    }
+}
+
+/// Runs `f` with a budget of `limit`, returns its result and the time it took.
+pub(crate) fn run_with_budget<R>(limit: Duration, f: impl FnOnce() -> R) -> (R, Duration) {
+    let start = Instant::now();
+    let previous = CURRENT_BUDGET.with(|budget| budget.replace(Some((start, limit))));
+
+    let result = f();
+
+    CURRENT_BUDGET.with(|budget| budget.set(previous));
+
+    (result, start.elapsed())
+}
+
+/// Timing of a system marked with [`SystemModificator::budget`].
+///
+/// [`SystemModificator::budget`]: crate::SystemModificator::budget
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemBudget {
+    /// Time given to the system each run.
+    pub budget: Duration,
+    /// Duration of the last run.
+    pub last_run: Duration,
+    /// Duration of the slowest run.
+    pub worst_run: Duration,
+    /// Number of runs.
+    pub runs: u64,
+    /// Number of runs that took longer than the budget.
+    pub overruns: u64,
+}
+
+/// Timing of all budgeted systems, listed by system name.
+///
+/// Returned by [`World::budget_report`].
+///
+/// [`World::budget_report`]: crate::World::budget_report
+#[derive(Clone, Debug, Default)]
+pub struct BudgetReport(pub ShipHashMap<String, SystemBudget>);
+
+impl BudgetReport {
+    pub(crate) fn record(&mut self, system: &dyn Label, budget: Duration, elapsed: Duration) {
+        let system_budget = self
+            .0
+            .entry(format!("{:?}", system))
+            .or_insert_with(|| SystemBudget {
+                budget,
+                last_run: Duration::ZERO,
+                worst_run: Duration::ZERO,
+                runs: 0,
+                overruns: 0,
+            });
+
+        system_budget.budget = budget;
+        system_budget.last_run = elapsed;
+        system_budget.worst_run = system_budget.worst_run.max(elapsed);
+        system_budget.runs += 1;
+
+        if elapsed > budget {
+            system_budget.overruns += 1;
+        }
+    }
+}
+
+/// Makes `system` run with a [`Budget`] of `budget` and records its timing.
+pub(crate) fn with_budget(mut system: WorkloadSystem, budget: Duration) -> WorkloadSystem {
+    let system_fn = system.system_fn;
+    let name = system.display_name.clone();
+
+    system.system_fn = Box::new(move |world: &World| {
+        let (result, elapsed) = run_with_budget(budget, || (system_fn)(world));
+
+        if let Ok(scheduler) = world.scheduler.borrow() {
+            if let Ok(mut budget_report) = scheduler.budget_report.lock() {
+                budget_report.record(&*name, budget, elapsed);
+            }
+        }
+
+        result
+    });
+
+    system
+}
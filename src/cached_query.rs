@@ -0,0 +1,429 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::atomic_refcell::ARef;
+use crate::component::{Component, Unique};
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::sparse_set::SparseSet;
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::ShipHashMap;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Components a [`CachedQuery`] filters entities on.
+///
+/// Implemented for components and tuples of up to 10 components.
+pub trait QueryComponents {
+    /// Adds the storages of the components to `storage_ids`.
+    fn storage_ids(storage_ids: &mut Vec<StorageId>);
+    /// Adds the entities which gained or lost one of the components within `(last, current]` to `entities`.
+    ///
+    /// Returns `false` if a storage doesn't track insertion, removal and deletion.
+    fn changes(
+        all_storages: &AllStorages,
+        last: TrackingTimestamp,
+        current: TrackingTimestamp,
+        entities: &mut Vec<EntityId>,
+    ) -> Result<bool, error::GetStorage>;
+    /// Adds the entities of the storage with the fewest components to `entities`.
+    fn smallest(
+        all_storages: &AllStorages,
+        entities: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage>;
+}
+
+/// List of the entities having all `With` components and none of the `Without` components.
+///
+/// The list is kept between frames and brought up to date by [`CachedQuery::update`]
+/// using the insertion, removal and deletion tracking of the components.
+/// Only the entities whose components changed are checked, the storages aren't joined again.\
+/// Components that don't track all three are supported but the list is then rebuilt on each update.
+///
+/// Clearing the tracking information before an update hides the changes it contained,
+/// [`CachedQuery::invalidate`] forces the next update to rebuild the list.
+///
+/// ### Example
+/// ```
+/// use shipyard::{AllStoragesView, CachedQuery, Component, World};
+///
+/// #[derive(Component)]
+/// #[track(All)]
+/// struct Layout;
+///
+/// #[derive(Component)]
+/// #[track(All)]
+/// struct Visible;
+///
+/// #[derive(Component)]
+/// #[track(All)]
+/// struct Hidden;
+///
+/// let mut world = World::new();
+/// let mut query = CachedQuery::<(Layout, Visible), Hidden>::new();
+///
+/// let button = world.add_entity((Layout, Visible));
+/// let panel = world.add_entity((Layout, Visible, Hidden));
+///
+/// world.run(|all_storages: AllStoragesView| query.update(&all_storages).unwrap());
+/// assert_eq!(query.entities(), [button]);
+///
+/// world.remove::<Hidden>(panel);
+/// world.delete_entity(button);
+///
+/// world.run(|all_storages: AllStoragesView| query.update(&all_storages).unwrap());
+/// assert_eq!(query.entities(), [panel]);
+/// ```
+pub struct CachedQuery<With, Without = ()> {
+    entities: Vec<EntityId>,
+    /// Position of each entity in `entities`
+    indices: ShipHashMap<EntityId, usize>,
+    last_update: Option<TrackingTimestamp>,
+    phantom: PhantomData<fn() -> (With, Without)>,
+}
+
+impl<With: 'static, Without: 'static> Unique for CachedQuery<With, Without> {}
+
+impl<With: QueryComponents, Without: QueryComponents> Default for CachedQuery<With, Without> {
+    fn default() -> Self {
+        CachedQuery::new()
+    }
+}
+
+impl<With: QueryComponents, Without: QueryComponents> CachedQuery<With, Without> {
+    /// Creates an empty query, the first update fills it.
+    pub fn new() -> CachedQuery<With, Without> {
+        CachedQuery {
+            entities: Vec::new(),
+            indices: ShipHashMap::default(),
+            last_update: None,
+            phantom: PhantomData,
+        }
+    }
+    /// Returns the matching entities, in no particular order.
+    pub fn entities(&self) -> &[EntityId] {
+        &self.entities
+    }
+    /// Returns an iterator over the matching entities, in no particular order.
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, EntityId>> {
+        self.entities.iter().copied()
+    }
+    /// Returns the number of matching entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+    /// Returns `true` if no entity matches.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+    /// Returns `true` if `entity` matched during the last update.
+    pub fn contains(&self, entity: EntityId) -> bool {
+        self.indices.contains_key(&entity)
+    }
+    /// Makes the next update rebuild the list from scratch.
+    pub fn invalidate(&mut self) {
+        self.last_update = None;
+    }
+    /// Checks the entities whose components were inserted, removed or deleted since the last update.
+    ///
+    /// The first update, or any update after [`CachedQuery::invalidate`], rebuilds the list.
+    pub fn update(&mut self, all_storages: &AllStorages) -> Result<(), error::GetStorage> {
+        let current = all_storages.get_current();
+
+        let last = match self.last_update {
+            Some(last) => last,
+            None => return self.rebuild_at(all_storages, current),
+        };
+
+        let mut changes = Vec::new();
+
+        if !With::changes(all_storages, last, current, &mut changes)?
+            || !Without::changes(all_storages, last, current, &mut changes)?
+        {
+            return self.rebuild_at(all_storages, current);
+        }
+
+        if !changes.is_empty() {
+            let filter = Filter::<With, Without>::borrow(all_storages)?;
+
+            for entity in changes {
+                if filter.matches(entity) {
+                    self.insert(entity);
+                } else {
+                    self.remove(entity);
+                }
+            }
+        }
+
+        self.last_update = Some(current);
+
+        Ok(())
+    }
+    /// Rebuilds the list by checking all entities with the rarest `With` component.
+    pub fn rebuild(&mut self, all_storages: &AllStorages) -> Result<(), error::GetStorage> {
+        let current = all_storages.get_current();
+
+        self.rebuild_at(all_storages, current)
+    }
+    fn rebuild_at(
+        &mut self,
+        all_storages: &AllStorages,
+        current: TrackingTimestamp,
+    ) -> Result<(), error::GetStorage> {
+        let mut candidates = Vec::new();
+        With::smallest(all_storages, &mut candidates)?;
+
+        let filter = Filter::<With, Without>::borrow(all_storages)?;
+
+        self.entities.clear();
+        self.indices.clear();
+
+        for entity in candidates {
+            if filter.matches(entity) {
+                self.insert(entity);
+            }
+        }
+
+        self.last_update = Some(current);
+
+        Ok(())
+    }
+    fn insert(&mut self, entity: EntityId) {
+        if !self.indices.contains_key(&entity) {
+            self.indices.insert(entity, self.entities.len());
+            self.entities.push(entity);
+        }
+    }
+    fn remove(&mut self, entity: EntityId) {
+        if let Some(index) = self.indices.remove(&entity) {
+            self.entities.swap_remove(index);
+
+            if let Some(&moved) = self.entities.get(index) {
+                self.indices.insert(moved, index);
+            }
+        }
+    }
+}
+
+impl<With, Without> core::fmt::Debug for CachedQuery<With, Without> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CachedQuery")
+            .field("entities", &self.entities)
+            .finish()
+    }
+}
+
+/// Storages of a [`CachedQuery`], `None` for storages that don't exist.
+struct Filter<'a, With, Without> {
+    with: Vec<Option<ARef<'a, &'a dyn Storage>>>,
+    without: Vec<Option<ARef<'a, &'a dyn Storage>>>,
+    phantom: PhantomData<fn() -> (With, Without)>,
+}
+
+impl<'a, With: QueryComponents, Without: QueryComponents> Filter<'a, With, Without> {
+    fn borrow(all_storages: &'a AllStorages) -> Result<Self, error::GetStorage> {
+        let mut with = Vec::new();
+        With::storage_ids(&mut with);
+        let mut without = Vec::new();
+        Without::storage_ids(&mut without);
+
+        Ok(Filter {
+            with: borrow_storages(all_storages, &with)?,
+            without: borrow_storages(all_storages, &without)?,
+            phantom: PhantomData,
+        })
+    }
+    fn matches(&self, entity: EntityId) -> bool {
+        self.with
+            .iter()
+            .all(|storage| storage_contains(storage, entity))
+            && !self
+                .without
+                .iter()
+                .any(|storage| storage_contains(storage, entity))
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn borrow_storages<'a>(
+    all_storages: &'a AllStorages,
+    storage_ids: &[StorageId],
+) -> Result<Vec<Option<ARef<'a, &'a dyn Storage>>>, error::GetStorage> {
+    storage_ids
+        .iter()
+        .map(
+            |&storage_id| match all_storages.custom_storage_by_id(storage_id) {
+                Ok(storage) => Ok(Some(storage)),
+                Err(error::GetStorage::MissingStorage { .. }) => Ok(None),
+                Err(err) => Err(err),
+            },
+        )
+        .collect()
+}
+
+fn storage_contains(storage: &Option<ARef<'_, &'_ dyn Storage>>, entity: EntityId) -> bool {
+    storage
+        .as_ref()
+        .and_then(|storage| storage.sparse_array())
+        .is_some_and(|sparse| sparse.contains(entity))
+}
+
+fn component_changes<T: Component + Send + Sync>(
+    all_storages: &AllStorages,
+    last: TrackingTimestamp,
+    current: TrackingTimestamp,
+    entities: &mut Vec<EntityId>,
+) -> Result<bool, error::GetStorage> {
+    let sparse_set = match all_storages.custom_storage::<SparseSet<T>>() {
+        Ok(sparse_set) => sparse_set,
+        Err(error::GetStorage::MissingStorage { .. }) => return Ok(true),
+        Err(err) => return Err(err),
+    };
+
+    if !(sparse_set.is_tracking_insertion()
+        && sparse_set.is_tracking_removal()
+        && sparse_set.is_tracking_deletion())
+    {
+        return Ok(false);
+    }
+
+    entities.extend(
+        sparse_set
+            .dense
+            .iter()
+            .zip(&sparse_set.insertion_data)
+            .filter(|(_, timestamp)| timestamp.is_within(last, current))
+            .map(|(&entity, _)| entity),
+    );
+    entities.extend(
+        sparse_set
+            .removal_data
+            .iter()
+            .filter(|(_, timestamp)| timestamp.is_within(last, current))
+            .map(|&(entity, _)| entity),
+    );
+    entities.extend(
+        sparse_set
+            .deletion_data
+            .iter()
+            .filter(|(_, timestamp, _)| timestamp.is_within(last, current))
+            .map(|&(entity, _, _)| entity),
+    );
+
+    Ok(true)
+}
+
+fn component_len<T: Component + Send + Sync>(
+    all_storages: &AllStorages,
+) -> Result<usize, error::GetStorage> {
+    match all_storages.custom_storage::<SparseSet<T>>() {
+        Ok(sparse_set) => Ok(sparse_set.len()),
+        Err(error::GetStorage::MissingStorage { .. }) => Ok(0),
+        Err(err) => Err(err),
+    }
+}
+
+fn component_entities<T: Component + Send + Sync>(
+    all_storages: &AllStorages,
+    entities: &mut Vec<EntityId>,
+) -> Result<(), error::GetStorage> {
+    match all_storages.custom_storage::<SparseSet<T>>() {
+        Ok(sparse_set) => {
+            entities.extend_from_slice(&sparse_set.dense);
+
+            Ok(())
+        }
+        Err(error::GetStorage::MissingStorage { .. }) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+impl QueryComponents for () {
+    fn storage_ids(_storage_ids: &mut Vec<StorageId>) {}
+    fn changes(
+        _all_storages: &AllStorages,
+        _last: TrackingTimestamp,
+        _current: TrackingTimestamp,
+        _entities: &mut Vec<EntityId>,
+    ) -> Result<bool, error::GetStorage> {
+        Ok(true)
+    }
+    fn smallest(
+        _all_storages: &AllStorages,
+        _entities: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage> {
+        Ok(())
+    }
+}
+
+impl<T: Component + Send + Sync> QueryComponents for T {
+    fn storage_ids(storage_ids: &mut Vec<StorageId>) {
+        storage_ids.push(StorageId::of::<SparseSet<T>>());
+    }
+    fn changes(
+        all_storages: &AllStorages,
+        last: TrackingTimestamp,
+        current: TrackingTimestamp,
+        entities: &mut Vec<EntityId>,
+    ) -> Result<bool, error::GetStorage> {
+        component_changes::<T>(all_storages, last, current, entities)
+    }
+    fn smallest(
+        all_storages: &AllStorages,
+        entities: &mut Vec<EntityId>,
+    ) -> Result<(), error::GetStorage> {
+        component_entities::<T>(all_storages, entities)
+    }
+}
+
+macro_rules! impl_query_components {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: Component + Send + Sync,)+> QueryComponents for ($($type,)+) {
+            fn storage_ids(storage_ids: &mut Vec<StorageId>) {
+                $(
+                    storage_ids.push(StorageId::of::<SparseSet<$type>>());
+                )+
+            }
+            fn changes(
+                all_storages: &AllStorages,
+                last: TrackingTimestamp,
+                current: TrackingTimestamp,
+                entities: &mut Vec<EntityId>,
+            ) -> Result<bool, error::GetStorage> {
+                $(
+                    if !component_changes::<$type>(all_storages, last, current, entities)? {
+                        return Ok(false);
+                    }
+                )+
+
+                Ok(true)
+            }
+            fn smallest(
+                all_storages: &AllStorages,
+                entities: &mut Vec<EntityId>,
+            ) -> Result<(), error::GetStorage> {
+                let lens = [$(component_len::<$type>(all_storages)?,)+];
+                let smallest = lens.iter().min().copied().unwrap_or(0);
+
+                $(
+                    if lens[$index] == smallest {
+                        return component_entities::<$type>(all_storages, entities);
+                    }
+                )+
+
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! query_components {
+    ($(($type: ident, $index: tt))*;($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_query_components![$(($type, $index))*];
+        query_components![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))*;) => {
+        impl_query_components![$(($type, $index))*];
+    }
+}
+
+query_components![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
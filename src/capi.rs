@@ -0,0 +1,455 @@
+//! `extern "C"` functions to drive a [`World`] from other languages.
+//!
+//! Components are identified by their [`Component::NAME`] or type name and passed as raw bytes.\
+//! Only components registered in a [`CapiRegistry`] can be added or read, registration happens on the Rust side.\
+//! Panics never unwind into the caller, functions returning a [`ShipyardStatus`] return [`ShipyardStatus::Panicked`].
+//!
+//! ### Example
+//! ```
+//! use shipyard::capi::{self, CWorld, CapiRegistry, ShipyardStatus};
+//! use shipyard::{track, Component, Pod, World};
+//! use std::ffi::CString;
+//!
+//! #[derive(Clone, Copy)]
+//! #[repr(C)]
+//! struct Health(u32);
+//!
+//! impl Component for Health {
+//!     type Tracking = track::Untracked;
+//!     const NAME: Option<&'static str> = Some("Health");
+//! }
+//!
+//! unsafe impl Pod for Health {}
+//!
+//! let mut registry = CapiRegistry::new();
+//! registry.register::<Health>();
+//!
+//! let world = CWorld::new(World::new(), registry).into_raw();
+//!
+//! unsafe {
+//!     let entity = capi::shipyard_spawn(world);
+//!     let name = CString::new("Health").unwrap();
+//!     let mut health = 10u32.to_ne_bytes();
+//!
+//!     assert_eq!(
+//!         capi::shipyard_add_component(world, entity, name.as_ptr(), health.as_ptr(), 4),
+//!         ShipyardStatus::Ok
+//!     );
+//!
+//!     health = [0; 4];
+//!     assert_eq!(
+//!         capi::shipyard_get_component(world, entity, name.as_ptr(), health.as_mut_ptr(), 4),
+//!         ShipyardStatus::Ok
+//!     );
+//!     assert_eq!(u32::from_ne_bytes(health), 10);
+//!
+//!     capi::shipyard_world_free(world);
+//! }
+//! ```
+
+use crate::all_storages::AllStorages;
+use crate::component::{component_name, Component};
+use crate::digest::Pod;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::iter::{IntoIter, IntoWithId};
+use crate::scheduler::Label;
+use crate::views::View;
+use crate::world::World;
+use crate::ShipHashMap;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ffi::{c_char, CStr};
+use core::mem::size_of;
+use core::panic::AssertUnwindSafe;
+
+/// Result of the `extern "C"` functions.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShipyardStatus {
+    #[allow(missing_docs)]
+    Ok = 0,
+    /// A pointer argument is null.
+    NullPointer = 1,
+    /// A name isn't valid UTF-8.
+    InvalidUtf8 = 2,
+    /// No component with this name was registered.
+    UnknownComponent = 3,
+    /// The byte length doesn't match the component's size.
+    WrongSize = 4,
+    /// The entity isn't alive.
+    EntityNotAlive = 5,
+    /// The entity doesn't have this component.
+    MissingComponent = 6,
+    /// No workload with this name was added to the `World`.
+    MissingWorkload = 7,
+    /// A system of the workload failed.
+    WorkloadFailed = 8,
+    /// The `World`'s storages are already borrowed exclusively.
+    BorrowFailed = 9,
+    /// A system or a storage check panicked, the panic was stopped before reaching the caller.
+    Panicked = 10,
+}
+
+/// Runs `f`, a panic returns `on_panic` instead of unwinding into the caller.
+fn catch_panic<R>(on_panic: R, f: impl FnOnce() -> R) -> R {
+    std::panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(on_panic)
+}
+
+pub(crate) struct RawComponent {
+    pub(crate) size: usize,
+    pub(crate) add: unsafe fn(&mut AllStorages, EntityId, *const u8),
+    pub(crate) get: unsafe fn(&AllStorages, EntityId, *mut u8) -> bool,
+    pub(crate) ids: fn(&AllStorages) -> Vec<EntityId>,
+}
+
+/// Components accessible through the `extern "C"` functions.
+#[derive(Default)]
+pub struct CapiRegistry {
+    components: ShipHashMap<&'static str, RawComponent>,
+}
+
+impl CapiRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> CapiRegistry {
+        CapiRegistry::default()
+    }
+    /// Makes `T` accessible by name, its bytes are copied in and out of the `World`.\
+    /// Any bytes of the right length are expected to make a valid `T`.
+    pub fn register<T: Component + Pod + Send + Sync>(&mut self) -> &mut CapiRegistry {
+        self.components.insert(
+            component_name::<T>(),
+            RawComponent {
+                size: size_of::<T>(),
+                add: add_raw::<T>,
+                get: get_raw::<T>,
+                ids: raw_ids::<T>,
+            },
+        );
+
+        self
+    }
+    /// Returns `true` if a component with this name was registered.
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+    /// Returns the names of all registered components.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.components.keys().copied()
+    }
+    pub(crate) fn component(&self, name: &str) -> Result<&RawComponent, ShipyardStatus> {
+        self.components
+            .get(name)
+            .ok_or(ShipyardStatus::UnknownComponent)
+    }
+}
+
+/// SAFETY: `data` has to point to a valid `T`
+unsafe fn add_raw<T: Component + Pod + Send + Sync>(
+    all_storages: &mut AllStorages,
+    entity: EntityId,
+    data: *const u8,
+) {
+    let component = data.cast::<T>().read_unaligned();
+
+    all_storages.add_component(entity, component);
+}
+
+/// SAFETY: `out` has to be valid for `size_of::<T>()` bytes of writes
+unsafe fn get_raw<T: Component + Pod + Send + Sync>(
+    all_storages: &AllStorages,
+    entity: EntityId,
+    out: *mut u8,
+) -> bool {
+    match all_storages.get::<&T>(entity) {
+        Ok(component) => {
+            out.cast::<T>().write_unaligned(**component);
+
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Returns the entities owning a `T`.
+fn raw_ids<T: Component + Pod + Send + Sync>(all_storages: &AllStorages) -> Vec<EntityId> {
+    all_storages
+        .borrow::<View<'_, T>>()
+        .map(|view| (&view).iter().with_id().map(|(entity, _)| entity).collect())
+        .unwrap_or_default()
+}
+
+/// A [`World`] and the components other languages can access, handed out as an opaque pointer.
+pub struct CWorld {
+    world: World,
+    registry: CapiRegistry,
+}
+
+impl CWorld {
+    /// Wraps `world`, `registry` lists the components accessible by name.
+    pub fn new(world: World, registry: CapiRegistry) -> CWorld {
+        CWorld { world, registry }
+    }
+    /// Moves the `CWorld` to the heap, the pointer has to be freed with [`shipyard_world_free`].
+    pub fn into_raw(self) -> *mut CWorld {
+        Box::into_raw(Box::new(self))
+    }
+    /// Returns the inner `World`.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+    /// Returns the inner `World`.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+    /// Returns the components accessible by name.
+    pub fn registry_mut(&mut self) -> &mut CapiRegistry {
+        &mut self.registry
+    }
+    fn component(&self, name: *const c_char) -> Result<&RawComponent, ShipyardStatus> {
+        if name.is_null() {
+            return Err(ShipyardStatus::NullPointer);
+        }
+
+        // SAFETY: the caller guarantees `name` is nul-terminated
+        let name = unsafe { CStr::from_ptr(name) }
+            .to_str()
+            .map_err(|_| ShipyardStatus::InvalidUtf8)?;
+
+        self.registry.component(name)
+    }
+}
+
+/// Creates an empty `World` without registered components.
+#[no_mangle]
+pub extern "C" fn shipyard_world_new() -> *mut CWorld {
+    catch_panic(core::ptr::null_mut(), || {
+        CWorld::new(World::new(), CapiRegistry::new()).into_raw()
+    })
+}
+
+/// Destroys a `World` created by [`shipyard_world_new`] or [`CWorld::into_raw`].
+///
+/// # Safety
+///
+/// `world` has to come from one of these functions and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn shipyard_world_free(world: *mut CWorld) {
+    catch_panic((), || {
+        if !world.is_null() {
+            drop(Box::from_raw(world));
+        }
+    })
+}
+
+/// Adds an entity without component, returns its id.
+///
+/// # Safety
+///
+/// `world` has to be a valid `CWorld` pointer.
+#[no_mangle]
+pub unsafe extern "C" fn shipyard_spawn(world: *mut CWorld) -> u64 {
+    catch_panic(EntityId::dead().inner(), || match world.as_mut() {
+        Some(world) => world.world.add_entity(()).inner(),
+        None => EntityId::dead().inner(),
+    })
+}
+
+/// Deletes an entity and all its components, returns `true` if it was alive.
+///
+/// # Safety
+///
+/// `world` has to be a valid `CWorld` pointer.
+#[no_mangle]
+pub unsafe extern "C" fn shipyard_delete(world: *mut CWorld, entity: u64) -> bool {
+    catch_panic(false, || {
+        match (world.as_mut(), EntityId::from_inner(entity)) {
+            (Some(world), Some(entity)) => world.world.delete_entity(entity),
+            _ => false,
+        }
+    })
+}
+
+/// Returns `true` if `entity` is alive.
+///
+/// # Safety
+///
+/// `world` has to be a valid `CWorld` pointer.
+#[no_mangle]
+pub unsafe extern "C" fn shipyard_is_alive(world: *const CWorld, entity: u64) -> bool {
+    catch_panic(false, || {
+        match (world.as_ref(), EntityId::from_inner(entity)) {
+            (Some(world), Some(entity)) => world.world.is_alive(entity),
+            _ => false,
+        }
+    })
+}
+
+/// Adds the component named `name` to `entity`, replacing the previous one.\
+/// `data` holds the component's bytes, `len` has to be the component's size.
+///
+/// # Safety
+///
+/// - `world` has to be a valid `CWorld` pointer.
+/// - `name` has to be a nul-terminated string.
+/// - `data` has to point to `len` bytes making a valid value of the component.
+#[no_mangle]
+pub unsafe extern "C" fn shipyard_add_component(
+    world: *mut CWorld,
+    entity: u64,
+    name: *const c_char,
+    data: *const u8,
+    len: usize,
+) -> ShipyardStatus {
+    catch_panic(ShipyardStatus::Panicked, || {
+        let Some(world) = world.as_mut() else {
+            return ShipyardStatus::NullPointer;
+        };
+
+        if data.is_null() {
+            return ShipyardStatus::NullPointer;
+        }
+
+        let component = match world.component(name) {
+            Ok(component) => component,
+            Err(status) => return status,
+        };
+
+        if component.size != len {
+            return ShipyardStatus::WrongSize;
+        }
+
+        let Some(entity) =
+            EntityId::from_inner(entity).filter(|&entity| world.world.is_alive(entity))
+        else {
+            return ShipyardStatus::EntityNotAlive;
+        };
+
+        let add = component.add;
+        add(world.world.all_storages.get_mut(), entity, data);
+
+        ShipyardStatus::Ok
+    })
+}
+
+/// Copies the component named `name` of `entity` to `out`, `len` has to be the component's size.
+///
+/// # Safety
+///
+/// - `world` has to be a valid `CWorld` pointer.
+/// - `name` has to be a nul-terminated string.
+/// - `out` has to be valid for `len` bytes of writes.
+#[no_mangle]
+pub unsafe extern "C" fn shipyard_get_component(
+    world: *const CWorld,
+    entity: u64,
+    name: *const c_char,
+    out: *mut u8,
+    len: usize,
+) -> ShipyardStatus {
+    catch_panic(ShipyardStatus::Panicked, || {
+        let Some(world) = world.as_ref() else {
+            return ShipyardStatus::NullPointer;
+        };
+
+        if out.is_null() {
+            return ShipyardStatus::NullPointer;
+        }
+
+        let component = match world.component(name) {
+            Ok(component) => component,
+            Err(status) => return status,
+        };
+
+        if component.size != len {
+            return ShipyardStatus::WrongSize;
+        }
+
+        let Some(entity) =
+            EntityId::from_inner(entity).filter(|&entity| world.world.is_alive(entity))
+        else {
+            return ShipyardStatus::EntityNotAlive;
+        };
+
+        let Ok(all_storages) = world.world.all_storages() else {
+            return ShipyardStatus::BorrowFailed;
+        };
+
+        if (component.get)(&all_storages, entity, out) {
+            ShipyardStatus::Ok
+        } else {
+            ShipyardStatus::MissingComponent
+        }
+    })
+}
+
+/// Runs the workload named `name`.
+///
+/// # Safety
+///
+/// - `world` has to be a valid `CWorld` pointer.
+/// - `name` has to be a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn shipyard_run_workload(
+    world: *const CWorld,
+    name: *const c_char,
+) -> ShipyardStatus {
+    catch_panic(ShipyardStatus::Panicked, || {
+        let Some(world) = world.as_ref() else {
+            return ShipyardStatus::NullPointer;
+        };
+
+        if name.is_null() {
+            return ShipyardStatus::NullPointer;
+        }
+
+        let Ok(name) = CStr::from_ptr(name).to_str() else {
+            return ShipyardStatus::InvalidUtf8;
+        };
+
+        run_workload_by_name(&world.world, name)
+    })
+}
+
+/// Runs the workload whose name is `name`, whatever the label's type.
+pub(crate) fn run_workload_by_name(world: &World, name: &str) -> ShipyardStatus {
+    let Some(label) = workload_by_name(world, name) else {
+        return ShipyardStatus::MissingWorkload;
+    };
+
+    match world.run_workload(label) {
+        Ok(()) => ShipyardStatus::Ok,
+        Err(error::RunWorkload::MissingWorkload) => ShipyardStatus::MissingWorkload,
+        Err(_) => ShipyardStatus::WorkloadFailed,
+    }
+}
+
+/// Returns the label of the workload whose name is `name`, whatever the label's type.
+fn workload_by_name(world: &World, name: &str) -> Option<Box<dyn Label>> {
+    let scheduler = world.scheduler.borrow().ok()?;
+
+    let label = scheduler
+        .workloads
+        .keys()
+        .find(|label| label_name(&***label) == Some(name))
+        .map(|label| label.dyn_clone());
+
+    label
+}
+
+/// Returns the name of string labels.
+fn label_name(label: &dyn Label) -> Option<&str> {
+    let label = label.as_any();
+
+    if let Some(name) = label.downcast_ref::<&'static str>() {
+        Some(name)
+    } else if let Some(name) = label.downcast_ref::<String>() {
+        Some(name)
+    } else {
+        label
+            .downcast_ref::<Cow<'static, str>>()
+            .map(|name| &**name)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::all_storages::AllStorages;
+use crate::component::Unique;
+use crate::entity_id::EntityId;
+use crate::sparse_set::TupleAddComponent;
+use crate::views::{AllStoragesViewMut, UniqueViewMut};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+type Command = Box<dyn FnOnce(&mut AllStorages) + Send + Sync>;
+
+/// Queue of structural changes applied later with exclusive access to the `World`.
+///
+/// Systems push commands through a `UniqueViewMut<CommandBuffer>` instead of borrowing `AllStoragesViewMut`.\
+/// Commands are applied in order at each [`Workload::with_sync_point`], at the end of [`World::run_workload`]
+/// and [`World::run_default_workload`], or with [`World::apply_commands`].\
+/// Commands targeting an entity that isn't alive anymore are skipped.
+///
+/// ### Example
+/// ```
+/// use shipyard::{CommandBuffer, Component, UniqueViewMut, World};
+///
+/// #[derive(Component)]
+/// struct Bullet;
+///
+/// let mut world = World::new();
+/// world.add_unique(CommandBuffer::new());
+///
+/// world.run(|mut commands: UniqueViewMut<CommandBuffer>| {
+///     commands.add_entity((Bullet,));
+/// });
+///
+/// world.apply_commands();
+///
+/// assert_eq!(world.iter::<&Bullet>().iter().count(), 1);
+/// ```
+///
+/// [`Workload::with_sync_point`]: crate::Workload::with_sync_point
+/// [`World::run_workload`]: crate::World::run_workload
+/// [`World::run_default_workload`]: crate::World::run_default_workload
+/// [`World::apply_commands`]: crate::World::apply_commands
+#[derive(Default)]
+pub struct CommandBuffer {
+    commands: Vec<Command>,
+}
+
+impl CommandBuffer {
+    /// Creates an empty buffer.
+    pub fn new() -> CommandBuffer {
+        CommandBuffer::default()
+    }
+    /// Queues `command`.
+    pub fn push<F: FnOnce(&mut AllStorages) + Send + Sync + 'static>(&mut self, command: F) {
+        self.commands.push(Box::new(command));
+    }
+    /// Queues the creation of an entity with `components`.
+    pub fn add_entity<C: TupleAddComponent + Send + Sync + 'static>(&mut self, components: C) {
+        self.push(move |all_storages| {
+            all_storages.add_entity(components);
+        });
+    }
+    /// Queues the addition of `components` to `entity`.
+    pub fn add_component<C: TupleAddComponent + Send + Sync + 'static>(
+        &mut self,
+        entity: EntityId,
+        components: C,
+    ) {
+        self.push(move |all_storages| {
+            if all_storages.is_entity_alive(entity) {
+                all_storages.add_component(entity, components);
+            }
+        });
+    }
+    /// Queues the deletion of `entity`.
+    pub fn delete_entity(&mut self, entity: EntityId) {
+        self.push(move |all_storages| {
+            all_storages.delete_entity(entity);
+        });
+    }
+    /// Queues the deletion of `entity`, recording `reason` in [`DeletionEvents<R>`] if present.
+    ///
+    /// [`DeletionEvents<R>`]: crate::DeletionEvents
+    pub fn delete_entity_with<R: Send + Sync + 'static>(&mut self, entity: EntityId, reason: R) {
+        self.push(move |all_storages| {
+            all_storages.delete_entity_with(entity, reason);
+        });
+    }
+    /// Returns the number of queued commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+    /// Returns `true` if no command is queued.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl Unique for CommandBuffer {}
+
+impl AllStorages {
+    /// Applies the commands queued in the [`CommandBuffer`], if there is one.\
+    /// Commands queued while applying are applied too.
+    pub fn apply_commands(&mut self) {
+        loop {
+            let commands = match self.borrow::<UniqueViewMut<'_, CommandBuffer>>() {
+                Ok(mut buffer) if !buffer.is_empty() => core::mem::take(&mut buffer.commands),
+                _ => return,
+            };
+
+            for command in commands {
+                command(self);
+            }
+        }
+    }
+}
+
+/// System inserted by [`Workload::with_sync_point`].
+///
+/// [`Workload::with_sync_point`]: crate::Workload::with_sync_point
+pub(crate) fn sync_point(mut all_storages: AllStoragesViewMut<'_>) {
+    all_storages.apply_commands();
+}
@@ -1,16 +1,68 @@
+use crate::component_registrar::ComponentRegistrar;
 use crate::tracking::Tracking;
 
 /// Indicates that a `struct` or `enum` can be store in the `World`.
+///
+/// `#[derive(Component)]` accepts a `#[shipyard(...)]` attribute declaring all options of the type in one place:
+/// - `track(Insertion, Modification, Deletion, Removal or All)`, same as `#[track(...)]`
+/// - `name = "..."` sets [`Component::NAME`]
+/// - `inline` sets [`Component::INLINE`]
+/// - `serialize` registers the type in snapshot and replication registries
+/// - `clone` registers the type for [`AllStorages::clone_entity`]
+///
+/// The options are applied by the `register_components` methods, see [`ComponentRegistrar`].
+///
+/// [`AllStorages::clone_entity`]: crate::AllStorages::clone_entity
 #[cfg(feature = "thread_local")]
 pub trait Component: Sized + 'static {
     /// Kind of event to track for this component.
     type Tracking: Tracking;
+    /// Name identifying this component in snapshots, replication deltas and replay streams.\
+    /// Defaults to the type name, which changes when the type is renamed or moved.
+    const NAME: Option<&'static str> = None;
+    /// Stores the first 8 entities of this component's storage inline instead of in heap allocated buckets.\
+    /// Only worth it for components few entities ever have, like a `Player` or `Camera` tag.
+    /// Storages of other components don't pay for it, their lookups are compiled without the inline branch.
+    const INLINE: bool = false;
+    /// Registers this component in the registries it opted into.
+    #[inline]
+    fn register(_registrar: &mut ComponentRegistrar<'_>) {}
 }
 /// Indicates that a `struct` or `enum` can be store in the `World`.
+///
+/// `#[derive(Component)]` accepts a `#[shipyard(...)]` attribute declaring all options of the type in one place:
+/// - `track(Insertion, Modification, Deletion, Removal or All)`, same as `#[track(...)]`
+/// - `name = "..."` sets [`Component::NAME`]
+/// - `inline` sets [`Component::INLINE`]
+/// - `serialize` registers the type in snapshot and replication registries
+/// - `clone` registers the type for [`AllStorages::clone_entity`]
+///
+/// The options are applied by the `register_components` methods, see [`ComponentRegistrar`].
+///
+/// [`AllStorages::clone_entity`]: crate::AllStorages::clone_entity
 #[cfg(not(feature = "thread_local"))]
 pub trait Component: Sized + Send + Sync + 'static {
     /// Kind of event to track for this component.
     type Tracking: Tracking;
+    /// Name identifying this component in snapshots, replication deltas and replay streams.\
+    /// Defaults to the type name, which changes when the type is renamed or moved.
+    const NAME: Option<&'static str> = None;
+    /// Stores the first 8 entities of this component's storage inline instead of in heap allocated buckets.\
+    /// Only worth it for components few entities ever have, like a `Player` or `Camera` tag.
+    /// Storages of other components don't pay for it, their lookups are compiled without the inline branch.
+    const INLINE: bool = false;
+    /// Registers this component in the registries it opted into.
+    #[inline]
+    fn register(_registrar: &mut ComponentRegistrar<'_>) {}
+}
+
+/// Returns [`Component::NAME`] or the type name of `T`.
+#[inline]
+pub(crate) fn component_name<T: Component>() -> &'static str {
+    match T::NAME {
+        Some(name) => name,
+        None => core::any::type_name::<T>(),
+    }
 }
 
 /// Indicates that a `struct` or `enum` can be store a single time in the `World`.
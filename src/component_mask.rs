@@ -0,0 +1,130 @@
+use crate::component::Component;
+use crate::sparse_set::SparseSet;
+use crate::storage::StorageId;
+use alloc::vec::Vec;
+
+/// Set of storages, one bit per storage.
+///
+/// Bits are assigned by an [`AllStorages`] the first time it sees a storage,
+/// masks from different `World`s can't be compared.\
+/// Returned by [`AllStorages::signature`] and [`AllStorages::mask_of`].
+///
+/// [`AllStorages`]: crate::AllStorages
+/// [`AllStorages::signature`]: crate::AllStorages::signature
+/// [`AllStorages::mask_of`]: crate::AllStorages::mask_of
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct ComponentMask {
+    words: Vec<u64>,
+}
+
+impl ComponentMask {
+    /// Creates an empty mask.
+    pub fn new() -> ComponentMask {
+        ComponentMask::default()
+    }
+    /// Sets `bit`.
+    pub fn insert(&mut self, bit: usize) {
+        let word = bit / 64;
+
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        self.words[word] |= 1 << (bit % 64);
+    }
+    /// Unsets `bit`.
+    pub fn remove(&mut self, bit: usize) {
+        if let Some(word) = self.words.get_mut(bit / 64) {
+            *word &= !(1 << (bit % 64));
+        }
+
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+    /// Returns `true` if `bit` is set.
+    pub fn contains(&self, bit: usize) -> bool {
+        self.words
+            .get(bit / 64)
+            .is_some_and(|word| word & (1 << (bit % 64)) != 0)
+    }
+    /// Returns the number of bits set.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+    /// Returns `true` if no bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+    /// Returns `true` if all bits of `other` are set in `self`.
+    pub fn is_superset(&self, other: &ComponentMask) -> bool {
+        other.words.len() <= self.words.len()
+            && self
+                .words
+                .iter()
+                .zip(&other.words)
+                .all(|(word, other)| word & other == *other)
+    }
+    /// Returns `true` if all bits of `self` are set in `other`.
+    pub fn is_subset(&self, other: &ComponentMask) -> bool {
+        other.is_superset(self)
+    }
+    /// Returns an iterator over the bits set.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| index * 64 + bit)
+        })
+    }
+}
+
+/// Trait used as bound for [`AllStorages::mask_of`], [`AllStorages::query_exact`] and [`AllStorages::query_at_least`].
+///
+/// [`AllStorages::mask_of`]: crate::AllStorages::mask_of
+/// [`AllStorages::query_exact`]: crate::AllStorages::query_exact
+/// [`AllStorages::query_at_least`]: crate::AllStorages::query_at_least
+pub trait TupleSignature {
+    /// Adds the storage of each component to `storage_ids`.
+    fn storage_ids(storage_ids: &mut Vec<StorageId>);
+}
+
+impl TupleSignature for () {
+    #[inline]
+    fn storage_ids(_: &mut Vec<StorageId>) {}
+}
+
+impl<T: Component> TupleSignature for T {
+    #[inline]
+    fn storage_ids(storage_ids: &mut Vec<StorageId>) {
+        storage_ids.push(StorageId::of::<SparseSet<T>>());
+    }
+}
+
+macro_rules! impl_signature {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: Component),+> TupleSignature for ($($type,)+) {
+            #[inline]
+            fn storage_ids(storage_ids: &mut Vec<StorageId>) {
+                $(
+                    storage_ids.push(StorageId::of::<SparseSet<$type>>());
+                )+
+            }
+        }
+    }
+}
+
+macro_rules! signature {
+    ($(($type: ident, $index: tt))+; ($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_signature![$(($type, $index))*];
+        signature![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))+;) => {
+        impl_signature![$(($type, $index))*];
+    }
+}
+
+signature![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
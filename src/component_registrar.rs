@@ -0,0 +1,158 @@
+use crate::all_storages::AllStorages;
+use crate::component::Component;
+#[cfg(feature = "replication")]
+use crate::replication::ReplicationRegistry;
+#[cfg(feature = "snapshot")]
+use crate::snapshot::SnapshotRegistry;
+use crate::sparse_set::SparseSet;
+use crate::storage::StorageId;
+#[cfg(feature = "snapshot")]
+use serde::{de::DeserializeOwned, Serialize};
+
+enum Target<'a> {
+    AllStorages(&'a mut AllStorages),
+    #[cfg(feature = "snapshot")]
+    Snapshot(&'a mut SnapshotRegistry),
+    #[cfg(feature = "replication")]
+    Replication(&'a mut ReplicationRegistry),
+}
+
+/// Receives the options a component declared with `#[derive(Component)]`.
+///
+/// Each `register_components` method calls [`Component::register`] with a registrar targeting its registry,
+/// options that don't concern this registry are ignored:
+/// - [`AllStorages::register_components`] and [`World::register_components`] apply `clone`
+/// - `SnapshotRegistry::register_components` and `ReplicationRegistry::register_components` apply `serialize`
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, World};
+///
+/// #[derive(Component, Clone, Debug, PartialEq)]
+/// #[shipyard(clone, name = "health")]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct Sprite(u32);
+///
+/// let mut world = World::new();
+/// world.register_components::<(Health, Sprite)>();
+///
+/// let entity = world.add_entity((Health(10), Sprite(0)));
+/// let clone = world.clone_entity(entity);
+///
+/// assert_eq!(world.get::<&Health>(clone).as_deref(), Ok(&&Health(10)));
+/// assert!(world.get::<&Sprite>(clone).is_err());
+/// ```
+///
+/// [`AllStorages::register_components`]: crate::AllStorages::register_components
+/// [`World::register_components`]: crate::World::register_components
+pub struct ComponentRegistrar<'a> {
+    target: Target<'a>,
+}
+
+impl<'a> ComponentRegistrar<'a> {
+    pub(crate) fn all_storages(all_storages: &'a mut AllStorages) -> ComponentRegistrar<'a> {
+        ComponentRegistrar {
+            target: Target::AllStorages(all_storages),
+        }
+    }
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn snapshot(registry: &'a mut SnapshotRegistry) -> ComponentRegistrar<'a> {
+        ComponentRegistrar {
+            target: Target::Snapshot(registry),
+        }
+    }
+    #[cfg(feature = "replication")]
+    pub(crate) fn replication(registry: &'a mut ReplicationRegistry) -> ComponentRegistrar<'a> {
+        ComponentRegistrar {
+            target: Target::Replication(registry),
+        }
+    }
+    /// Makes `T` part of snapshots and replication deltas.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn serialize<T: Component + Send + Sync + Serialize + DeserializeOwned>(&mut self) {
+        match &mut self.target {
+            Target::Snapshot(registry) => {
+                registry.register::<T>();
+            }
+            #[cfg(feature = "replication")]
+            Target::Replication(registry) => {
+                registry.register::<T>();
+            }
+            Target::AllStorages(_) => {}
+        }
+    }
+    /// Marks `T` as registered, it can be borrowed from strict `AllStorages`.
+    pub(crate) fn declare<T: Component>(&mut self) {
+        match &mut self.target {
+            Target::AllStorages(all_storages) => {
+                all_storages
+                    .registered
+                    .insert(StorageId::of::<SparseSet<T>>());
+            }
+            #[cfg(feature = "snapshot")]
+            Target::Snapshot(_) => {}
+            #[cfg(feature = "replication")]
+            Target::Replication(_) => {}
+        }
+    }
+    /// Makes `T` part of the components [`AllStorages::clone_entity`] copies.
+    ///
+    /// [`AllStorages::clone_entity`]: crate::AllStorages::clone_entity
+    pub fn cloneable<T: Component + Clone + Send + Sync>(&mut self) {
+        match &mut self.target {
+            Target::AllStorages(all_storages) => all_storages.register_clone::<T>(),
+            #[cfg(feature = "snapshot")]
+            Target::Snapshot(_) => {}
+            #[cfg(feature = "replication")]
+            Target::Replication(_) => {}
+        }
+    }
+}
+
+/// Component or tuple of components to register with [`ComponentRegistrar`].
+pub trait TupleRegisterComponent {
+    /// Calls [`Component::register`] for each component.
+    fn register_components(registrar: &mut ComponentRegistrar<'_>);
+}
+
+impl TupleRegisterComponent for () {
+    #[inline]
+    fn register_components(_: &mut ComponentRegistrar<'_>) {}
+}
+
+impl<T: Component> TupleRegisterComponent for T {
+    #[inline]
+    fn register_components(registrar: &mut ComponentRegistrar<'_>) {
+        registrar.declare::<T>();
+        T::register(registrar);
+    }
+}
+
+macro_rules! impl_register_component {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: Component),+> TupleRegisterComponent for ($($type,)+) {
+            #[inline]
+            fn register_components(registrar: &mut ComponentRegistrar<'_>) {
+                $(
+                    registrar.declare::<$type>();
+                    $type::register(registrar);
+                )+
+            }
+        }
+    }
+}
+
+macro_rules! register_component {
+    ($(($type: ident, $index: tt))+; ($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_register_component![$(($type, $index))*];
+        register_component![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))+;) => {
+        impl_register_component![$(($type, $index))*];
+    }
+}
+
+register_component![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
@@ -0,0 +1,105 @@
+use alloc::vec::Vec;
+
+/// Byte level codec applied to whole snapshots and replication deltas.
+///
+/// Set on a [`SnapshotRegistry`] with [`SnapshotRegistry::set_compressor`],
+/// or passed to [`ReplicationDelta::to_compressed_bytes`] and [`ReplicationDelta::from_compressed_bytes`].
+///
+/// ### Example
+/// ```
+/// use shipyard::Compressor;
+///
+/// /// Stores each byte followed by the number of times it's repeated.
+/// struct RunLength;
+///
+/// impl Compressor for RunLength {
+///     fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+///         let mut compressed = Vec::new();
+///
+///         for &byte in bytes {
+///             match compressed.len().checked_sub(2) {
+///                 Some(last) if compressed[last] == byte && compressed[last + 1] < u8::MAX => {
+///                     compressed[last + 1] += 1;
+///                 }
+///                 _ => compressed.extend_from_slice(&[byte, 1]),
+///             }
+///         }
+///
+///         compressed
+///     }
+///     fn decompress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+///         if bytes.len() % 2 != 0 {
+///             return None;
+///         }
+///
+///         Some(
+///             bytes
+///                 .chunks(2)
+///                 .flat_map(|run| core::iter::repeat(run[0]).take(run[1] as usize))
+///                 .collect(),
+///         )
+///     }
+/// }
+///
+/// assert_eq!(RunLength.compress(&[0, 0, 0, 1]), [0, 3, 1, 1]);
+/// assert_eq!(RunLength.decompress(&[0, 3, 1, 1]).unwrap(), [0, 0, 0, 1]);
+/// ```
+///
+/// [`SnapshotRegistry`]: crate::SnapshotRegistry
+/// [`SnapshotRegistry::set_compressor`]: crate::SnapshotRegistry::set_compressor()
+/// [`ReplicationDelta::to_compressed_bytes`]: crate::ReplicationDelta::to_compressed_bytes()
+/// [`ReplicationDelta::from_compressed_bytes`]: crate::ReplicationDelta::from_compressed_bytes()
+pub trait Compressor: Send + Sync {
+    /// Returns the compressed version of `bytes`.
+    fn compress(&self, bytes: &[u8]) -> Vec<u8>;
+    /// Returns the original bytes, `None` if `bytes` weren't compressed by this codec.
+    fn decompress(&self, bytes: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// [LZ4](https://github.com/PSeitz/lz4_flex) compression, fast with a moderate ratio.
+#[cfg(feature = "lz4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lz4")))]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Lz4;
+
+#[cfg(feature = "lz4")]
+impl Compressor for Lz4 {
+    fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+        lz4_flex::compress_prepend_size(bytes)
+    }
+    fn decompress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+        lz4_flex::decompress_size_prepended(bytes).ok()
+    }
+}
+
+/// [Zstandard](https://github.com/gyscos/zstd-rs) compression, slower than LZ4 with a better ratio.
+///
+/// `level` goes from 1 to 22, 3 by default. Higher levels compress more but are slower.
+#[cfg(feature = "zstd")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+#[derive(Clone, Copy, Debug)]
+pub struct Zstd {
+    #[allow(missing_docs)]
+    pub level: i32,
+}
+
+#[cfg(feature = "zstd")]
+impl Default for Zstd {
+    fn default() -> Zstd {
+        Zstd {
+            level: zstd::DEFAULT_COMPRESSION_LEVEL,
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Compressor for Zstd {
+    #[track_caller]
+    fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+        // compressing into a `Vec` only fails on exceptional conditions like a failed allocation
+        zstd::bulk::compress(bytes, self.level).expect("zstd failed to compress")
+    }
+    fn decompress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+        zstd::decode_all(bytes).ok()
+    }
+}
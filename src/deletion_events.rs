@@ -0,0 +1,167 @@
+use crate::all_storages::AllStorages;
+use crate::component::Unique;
+use crate::entity_id::EntityId;
+use crate::views::UniqueViewMut;
+use alloc::boxed::Box;
+use alloc::vec::Drain;
+use alloc::vec::Vec;
+use core::fmt;
+
+type Observer<R> = Box<dyn FnMut(EntityId, &R) + Send + Sync>;
+
+/// Entities deleted with a reason of type `R`, in deletion order.
+///
+/// [`World::delete_entity_with`] and [`AllStorages::delete_entity_with`] record the deletion here when the unique is present.\
+/// Observers are called right after each deletion, the events stay until drained or cleared.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, DeletionEvents, UniqueViewMut, World};
+///
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Reason {
+///     Killed,
+///     Despawned,
+/// }
+///
+/// let mut world = World::new();
+/// world.add_unique(DeletionEvents::<Reason>::new());
+///
+/// let enemy = world.add_entity(Enemy);
+/// world.delete_entity_with(enemy, Reason::Killed);
+///
+/// let mut events = world.borrow::<UniqueViewMut<DeletionEvents<Reason>>>().unwrap();
+/// assert_eq!(events.drain().collect::<Vec<_>>(), [(enemy, Reason::Killed)]);
+/// ```
+///
+/// [`World::delete_entity_with`]: crate::World::delete_entity_with
+pub struct DeletionEvents<R> {
+    events: Vec<(EntityId, R)>,
+    observers: Vec<Observer<R>>,
+}
+
+impl<R: Send + Sync + 'static> Unique for DeletionEvents<R> {}
+
+impl<R> DeletionEvents<R> {
+    /// Creates an empty event list without observers.
+    pub fn new() -> DeletionEvents<R> {
+        DeletionEvents {
+            events: Vec::new(),
+            observers: Vec::new(),
+        }
+    }
+    /// Adds an observer called with each entity deleted with a reason of type `R`, right after its deletion.
+    pub fn observe(&mut self, f: impl FnMut(EntityId, &R) + Send + Sync + 'static) {
+        self.observers.push(Box::new(f));
+    }
+    /// Returns an iterator over the deletions recorded since the last drain or clear.
+    pub fn iter(&self) -> impl Iterator<Item = &(EntityId, R)> + '_ {
+        self.events.iter()
+    }
+    /// Removes and returns all recorded deletions.
+    pub fn drain(&mut self) -> Drain<'_, (EntityId, R)> {
+        self.events.drain(..)
+    }
+    /// Removes all recorded deletions.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+    /// Returns the number of recorded deletions.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+    /// Returns `true` if no deletion is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+    pub(crate) fn record(&mut self, entity: EntityId, reason: R) {
+        for observer in &mut self.observers {
+            (observer)(entity, &reason);
+        }
+
+        self.events.push((entity, reason));
+    }
+}
+
+impl<R> Default for DeletionEvents<R> {
+    fn default() -> Self {
+        DeletionEvents::new()
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for DeletionEvents<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeletionEvents")
+            .field("events", &self.events)
+            .field("observers", &self.observers.len())
+            .finish()
+    }
+}
+
+impl AllStorages {
+    /// Deletes an entity with all its components and records `reason` in [`DeletionEvents<R>`], if present.\
+    /// Returns true if the entity were alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, DeletionEvents, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// struct Killed;
+    ///
+    /// let world = World::new();
+    /// world.add_unique(DeletionEvents::<Killed>::new());
+    ///
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    /// let enemy = all_storages.add_entity(Enemy);
+    ///
+    /// assert!(all_storages.delete_entity_with(enemy, Killed));
+    /// ```
+    pub fn delete_entity_with<R: Send + Sync + 'static>(
+        &mut self,
+        entity: EntityId,
+        reason: R,
+    ) -> bool {
+        if !self.delete_entity(entity) {
+            return false;
+        }
+
+        if let Ok(mut events) = self.borrow::<UniqueViewMut<'_, DeletionEvents<R>>>() {
+            events.record(entity, reason);
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn observers() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut events = DeletionEvents::<u32>::new();
+
+        let observer_count = count.clone();
+        events.observe(move |_, reason| {
+            observer_count.fetch_add(*reason as usize, Ordering::Relaxed);
+        });
+
+        events.record(EntityId::new_from_index_and_gen(0, 0), 2);
+        events.record(EntityId::new_from_index_and_gen(1, 0), 3);
+
+        assert_eq!(count.load(Ordering::Relaxed), 5);
+        assert_eq!(events.len(), 2);
+
+        events.clear();
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::all_storages::AllStorages;
+use crate::component::Component;
+use crate::sparse_set::SparseSet;
+use core::mem::size_of;
+
+/// Types that can be viewed as bytes.
+///
+/// ### Safety
+///
+/// The type must not contain padding, pointers or references.\
+/// All its bytes have to be initialized and be the same on every machine for the same value,
+/// `#[repr(C)]` structs made of `Pod` fields without padding are fine.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($type: ty)*) => {
+        $(unsafe impl Pod for $type {})*
+    };
+}
+
+impl_pod![u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 f32 f64 bool];
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+const SEED: u64 = 0x243f_6a88_85a3_08d3;
+const MULTIPLIER: u64 = 0x9e37_79b9_7f4a_7c15;
+
+#[inline]
+fn mix(hash: u64, word: u64) -> u64 {
+    (hash.rotate_left(5) ^ word).wrapping_mul(MULTIPLIER)
+}
+
+/// Hashes `bytes` 8 at a time, little endian so the result is the same on every platform.
+#[inline]
+fn hash_bytes(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut chunks = bytes.chunks_exact(8);
+
+    for chunk in &mut chunks {
+        let mut word = [0; 8];
+        word.copy_from_slice(chunk);
+
+        hash = mix(hash, u64::from_le_bytes(word));
+    }
+
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        let mut word = [0; 8];
+        word[..remainder.len()].copy_from_slice(remainder);
+
+        hash = mix(hash, u64::from_le_bytes(word));
+    }
+
+    mix(hash, bytes.len() as u64)
+}
+
+/// Returns the digest of all `T` in `all_storages`.
+///
+/// Each entity is hashed alone and the results are summed, the digest doesn't depend on the storage order.
+pub(crate) fn storage_digest<T: Component + Pod>(all_storages: &mut AllStorages) -> u64 {
+    let mut len = 0;
+    let mut sum = 0u64;
+
+    // a missing storage has the same digest as an empty one
+    if let Ok(sparse_set) = all_storages.exclusive_storage_mut::<SparseSet<T>>() {
+        len = sparse_set.dense.len();
+
+        for (entity, component) in sparse_set.dense.iter().zip(&sparse_set.data) {
+            let component: *const T = component;
+            // SAFE Pod types are made of initialized bytes
+            let bytes =
+                unsafe { core::slice::from_raw_parts(component.cast::<u8>(), size_of::<T>()) };
+
+            sum = sum.wrapping_add(hash_bytes(mix(SEED, entity.inner()), bytes));
+        }
+    }
+
+    mix(mix(SEED, len as u64), sum)
+}
+
+/// Combines the digests of multiple storages, in order.
+#[inline]
+pub(crate) fn combine(hash: u64, storage_digest: u64) -> u64 {
+    mix(hash, storage_digest)
+}
+
+/// Initial value passed to [`combine`].
+#[inline]
+pub(crate) fn seed() -> u64 {
+    SEED
+}
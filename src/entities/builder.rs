@@ -0,0 +1,43 @@
+use crate::add_entity::AddEntity;
+use crate::entity_id::EntityId;
+
+/// Assembles a new entity one storage at a time.
+///
+/// The entity is created by [`Entities::build_entity`], even if the builder is dropped without calling [`finish`].
+///
+/// [`Entities::build_entity`]: crate::Entities::build_entity()
+/// [`finish`]: EntityBuilder::finish()
+#[must_use = "call `finish` to get the EntityId"]
+pub struct EntityBuilder {
+    entity: EntityId,
+}
+
+impl EntityBuilder {
+    #[inline]
+    pub(super) fn new(entity: EntityId) -> Self {
+        EntityBuilder { entity }
+    }
+    /// Adds `component` to the entity being built.\
+    /// Multiple components can be added at the same time using a tuple.
+    #[inline]
+    #[track_caller]
+    pub fn with<S: AddEntity>(self, mut storages: S, component: S::Component) -> Self {
+        AddEntity::add_entity(&mut storages, self.entity, component);
+
+        self
+    }
+    /// Adds `component` to the entity being built if it is `Some`.
+    #[inline]
+    #[track_caller]
+    pub fn with_opt<S: AddEntity>(self, storages: S, component: Option<S::Component>) -> Self {
+        match component {
+            Some(component) => self.with(storages, component),
+            None => self,
+        }
+    }
+    /// Returns the [`EntityId`] of the built entity.
+    #[inline]
+    pub fn finish(self) -> EntityId {
+        self.entity
+    }
+}
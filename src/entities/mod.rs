@@ -1,6 +1,12 @@
+mod builder;
 mod iterator;
+mod recycling;
+mod remap;
 
+pub use builder::EntityBuilder;
 pub use iterator::EntitiesIter;
+pub use recycling::{EntityRecycling, GenerationOverflow};
+pub use remap::EntityRemap;
 
 use crate::add_component::AddComponent;
 use crate::add_distinct_component::AddDistinctComponent;
@@ -12,6 +18,7 @@ use crate::reserve::{BulkEntityIter, BulkReserve};
 use crate::storage::Storage;
 use crate::tracking::TrackingTimestamp;
 use alloc::boxed::Box;
+use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 use core::any::type_name;
 use core::iter::repeat_with;
@@ -38,6 +45,20 @@ use core::mem::size_of;
 pub struct Entities {
     pub(crate) data: Vec<EntityId>,
     list: Option<(usize, usize)>,
+    /// Number of ids in `list`.
+    free_len: usize,
+    /// Number of ids in `list` released by `compact`.
+    compacted: usize,
+    recycling: EntityRecycling,
+    overflow: GenerationOverflow,
+    /// Number of calls to `advance_epoch`.
+    epoch: u64,
+    /// Saturated indices and the epoch they can be reused at.
+    quarantine: VecDeque<(usize, u64)>,
+    /// Number of saturated indices never reused.
+    retired: usize,
+    /// Number of ids generated, deleted or spawned.
+    structural_changes: u64,
     on_deletion: Option<Box<dyn FnMut(EntityId) + Send + Sync>>,
 }
 
@@ -47,6 +68,14 @@ impl Entities {
         Entities {
             data: Vec::new(),
             list: None,
+            free_len: 0,
+            compacted: 0,
+            recycling: EntityRecycling::Immediate,
+            overflow: GenerationOverflow::Retire,
+            epoch: 0,
+            quarantine: VecDeque::new(),
+            retired: 0,
+            structural_changes: 0,
             on_deletion: None,
         }
     }
@@ -136,29 +165,40 @@ impl Entities {
         }
     }
     pub(crate) fn generate(&mut self) -> EntityId {
-        if let Some((new, ref mut old)) = self.list {
-            let old_index = *old;
+        let can_recycle = self.can_recycle();
+        self.structural_changes = self.structural_changes.wrapping_add(1);
+
+        match self.list {
+            Some((new, ref mut old)) if can_recycle => {
+                let old_index = *old;
+
+                if new == *old {
+                    self.list = None;
+                } else {
+                    // SAFE old_index is always valid
+                    *old = unsafe { self.data.get_unchecked(old_index).uindex() };
+                }
+
+                self.free_len -= 1;
+                self.compacted = self.compacted.saturating_sub(1);
 
-            if new == *old {
-                self.list = None;
-            } else {
                 // SAFE old_index is always valid
-                *old = unsafe { self.data.get_unchecked(old_index).uindex() };
+                unsafe {
+                    self.data
+                        .get_unchecked_mut(old_index)
+                        .set_index(old_index as u64);
+                    *self.data.get_unchecked(old_index)
+                }
             }
-            // SAFE old_index is always valid
-            unsafe {
-                self.data
-                    .get_unchecked_mut(old_index)
-                    .set_index(old_index as u64);
-                *self.data.get_unchecked(old_index)
+            _ => {
+                let entity_id = EntityId::new(self.data.len() as u64);
+                self.data.push(entity_id);
+                entity_id
             }
-        } else {
-            let entity_id = EntityId::new(self.data.len() as u64);
-            self.data.push(entity_id);
-            entity_id
         }
     }
     pub(crate) fn bulk_generate(&mut self, count: usize) -> &[EntityId] {
+        self.structural_changes = self.structural_changes.wrapping_add(count as u64);
         self.data
             .extend((self.data.len() as u64..(self.data.len() + count) as u64).map(EntityId::new));
 
@@ -175,38 +215,46 @@ impl Entities {
                     .bump_gen()
                     .is_ok()
             } {
-                if let Some((ref mut new, _)) = self.list {
-                    // SAFE new is always in bound
-                    unsafe {
-                        self.data
-                            .get_unchecked_mut(*new)
-                            .set_index(entity_id.index())
-                    };
-                    unsafe {
-                        self.data
-                            .get_unchecked_mut(entity_id.uindex())
-                            .set_index(EntityId::max_index())
-                    };
-                    *new = entity_id.uindex();
-                } else {
-                    unsafe {
-                        self.data
-                            .get_unchecked_mut(entity_id.uindex())
-                            .set_index(EntityId::max_index())
-                    };
-                    self.list = Some((entity_id.uindex(), entity_id.uindex()));
-                }
+                self.push_free(entity_id.uindex());
+            } else {
+                self.retire(entity_id.uindex());
             }
 
             if let Some(on_deletion) = &mut self.on_deletion {
                 (on_deletion)(entity_id)
             }
 
+            self.structural_changes = self.structural_changes.wrapping_add(1);
+
             true
         } else {
             false
         }
     }
+    /// Adds the id at `index` to the end of the free list.
+    fn push_free(&mut self, index: usize) {
+        if let Some((ref mut new, _)) = self.list {
+            // SAFE new is always in bound
+            unsafe { self.data.get_unchecked_mut(*new).set_index(index as u64) };
+            *new = index;
+        } else {
+            self.list = Some((index, index));
+        }
+
+        self.data[index].set_index(EntityId::max_index());
+        self.free_len += 1;
+    }
+    /// Handles an id whose generation can't be bumped, according to the [`GenerationOverflow`] policy.
+    fn retire(&mut self, index: usize) {
+        self.data[index].set_index(EntityId::max_index());
+
+        match self.overflow {
+            GenerationOverflow::Retire => self.retired += 1,
+            GenerationOverflow::Quarantine { epochs } => self
+                .quarantine
+                .push_back((index, self.epoch.saturating_add(epochs))),
+        }
+    }
     /// Stores `component` in a new entity and returns its [`EntityId`].  
     /// Multiple components can be added at the same time using a tuple.
     ///
@@ -242,6 +290,39 @@ impl Entities {
         AddEntity::add_entity(&mut storages, entity_id, component);
         entity_id
     }
+    /// Creates a new entity and returns an [`EntityBuilder`] to attach its components one storage at a time.
+    ///
+    /// ### Example:
+    /// ```
+    /// use shipyard::{Component, EntitiesViewMut, ViewMut, World};
+    ///
+    /// #[derive(Component, Debug, PartialEq, Eq)]
+    /// struct Pos(u32);
+    ///
+    /// #[derive(Component, Debug, PartialEq, Eq)]
+    /// struct Vel(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let (mut entities, mut positions, mut velocities) = world
+    ///     .borrow::<(EntitiesViewMut, ViewMut<Pos>, ViewMut<Vel>)>()
+    ///     .unwrap();
+    ///
+    /// let moving = false;
+    ///
+    /// let entity = entities
+    ///     .build_entity()
+    ///     .with(&mut positions, Pos(0))
+    ///     .with_opt(&mut velocities, moving.then(|| Vel(1)))
+    ///     .finish();
+    ///
+    /// assert_eq!(positions[entity], Pos(0));
+    /// assert!(!velocities.contains(entity));
+    /// ```
+    #[inline]
+    pub fn build_entity(&mut self) -> EntityBuilder {
+        EntityBuilder::new(self.generate())
+    }
     /// Creates multiple new entities and returns an iterator yielding the new [`EntityId`]s.  
     /// Multiple components can be added at the same time using a tuple.
     ///
@@ -284,7 +365,8 @@ impl Entities {
         }
 
         // have to use two loops because of self borrow
-        for (component, id) in iter.zip(repeat_with(|| self.generate())) {
+        // recycled ids would break the contiguity of the new entities
+        for (component, id) in iter.zip(repeat_with(|| self.bulk_generate(1)[0])) {
             AddEntity::add_entity(&mut storages, id, component);
         }
 
@@ -302,6 +384,15 @@ impl Entities {
     /// Does nothing if an entity with a greater generation is already at this index.  
     /// Returns `true` if the entity is successfully spawned.
     pub fn spawn(&mut self, entity: EntityId) -> bool {
+        let spawned = self.spawn_id(entity);
+
+        if spawned {
+            self.structural_changes = self.structural_changes.wrapping_add(1);
+        }
+
+        spawned
+    }
+    fn spawn_id(&mut self, entity: EntityId) -> bool {
         if let Some(&old_entity) = self.data.get(entity.index() as usize) {
             if self.is_alive(old_entity) {
                 if old_entity.gen() <= entity.gen() {
@@ -338,6 +429,8 @@ impl Entities {
                         }
                     }
 
+                    self.free_len = self.free_len.saturating_sub(1);
+                    self.compacted = self.compacted.min(self.free_len);
                     self.data[entity.uindex()] = entity;
 
                     true
@@ -369,6 +462,7 @@ impl Entities {
                 }
 
                 self.data[entity.uindex() - 1].set_index(EntityId::max_index());
+                self.free_len += self.data.len() - old_len - 1;
             }
 
             self.data[entity.uindex()] = entity;
@@ -377,6 +471,163 @@ impl Entities {
         }
     }
 
+    /// Sets when the ids of deleted entities are reused.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, EntityRecycling, World};
+    ///
+    /// let world = World::new();
+    /// let mut entities = world.borrow::<EntitiesViewMut>().unwrap();
+    ///
+    /// entities.set_recycling(EntityRecycling::Manual);
+    ///
+    /// let entity = entities.add_entity((), ());
+    /// entities.delete_unchecked(entity);
+    ///
+    /// assert_ne!(entities.add_entity((), ()).index(), entity.index());
+    ///
+    /// entities.compact();
+    ///
+    /// assert_eq!(entities.add_entity((), ()).index(), entity.index());
+    /// ```
+    pub fn set_recycling(&mut self, recycling: EntityRecycling) {
+        self.recycling = recycling;
+    }
+    /// Returns when the ids of deleted entities are reused.
+    pub fn recycling(&self) -> EntityRecycling {
+        self.recycling
+    }
+    /// Makes the ids of all entities deleted so far reusable, regardless of the [`EntityRecycling`] policy.\
+    /// Does nothing with [`EntityRecycling::Never`].
+    pub fn compact(&mut self) {
+        if self.recycling != EntityRecycling::Never {
+            self.compacted = self.free_len;
+        }
+    }
+    /// Sets what happens to an index once its generation can't be bumped anymore.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, GenerationOverflow, World};
+    ///
+    /// let world = World::new();
+    /// let mut entities = world.borrow::<EntitiesViewMut>().unwrap();
+    ///
+    /// entities.set_generation_overflow(GenerationOverflow::Quarantine { epochs: 60 });
+    ///
+    /// // once per frame
+    /// entities.advance_epoch();
+    /// ```
+    pub fn set_generation_overflow(&mut self, overflow: GenerationOverflow) {
+        self.overflow = overflow;
+    }
+    /// Returns what happens to an index once its generation can't be bumped anymore.
+    pub fn generation_overflow(&self) -> GenerationOverflow {
+        self.overflow
+    }
+    /// Starts a new epoch, indices whose [`GenerationOverflow::Quarantine`] is over are made reusable with generation 0.\
+    /// Returns the number of indices released.
+    pub fn advance_epoch(&mut self) -> usize {
+        self.epoch += 1;
+
+        let mut released = 0;
+        while let Some(&(index, release)) = self.quarantine.front() {
+            if release > self.epoch {
+                break;
+            }
+
+            self.quarantine.pop_front();
+
+            // a snapshot restore could have brought back an older generation
+            if self.data[index].uindex() != index
+                && self.data[index].gen() >= EntityId::max_gen() - 1
+            {
+                self.data[index].copy_gen(EntityId::new(0));
+                self.push_free(index);
+                released += 1;
+            }
+        }
+
+        released
+    }
+    /// Returns the number of calls to [`Entities::advance_epoch`].
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+    /// Returns the number of indices whose generation saturated and that will never be reused.
+    pub fn retired_count(&self) -> usize {
+        self.retired
+    }
+    /// Returns the number of indices whose generation saturated, waiting for their quarantine to end.
+    pub fn quarantined_count(&self) -> usize {
+        self.quarantine.len()
+    }
+    /// Returns the number of ids allocated, alive or not.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    /// Returns `true` if no id was ever allocated.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    /// Returns the number of alive entities.\
+    /// This has to go through all ids.
+    pub fn alive_count(&self) -> usize {
+        self.iter().count()
+    }
+    /// Returns the number of deleted ids waiting to be reused.\
+    /// When they are reused depends on the [`EntityRecycling`] policy.
+    #[inline]
+    pub fn recyclable_count(&self) -> usize {
+        self.free_len
+    }
+    /// Returns an iterator over all allocated ids as `(index, generation, alive)`.\
+    /// For ids that aren't alive, the generation is the one the next entity at this index will have.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, World};
+    ///
+    /// let world = World::new();
+    /// let mut entities = world.borrow::<EntitiesViewMut>().unwrap();
+    ///
+    /// let entity0 = entities.add_entity((), ());
+    /// let _entity1 = entities.add_entity((), ());
+    /// entities.delete_unchecked(entity0);
+    ///
+    /// assert_eq!(entities.len(), 2);
+    /// assert_eq!(entities.alive_count(), 1);
+    /// assert_eq!(entities.recyclable_count(), 1);
+    /// assert_eq!(
+    ///     entities.versions().collect::<Vec<_>>(),
+    ///     [(0, 1, false), (1, 0, true)]
+    /// );
+    /// ```
+    pub fn versions(&self) -> impl Iterator<Item = (u64, u16, bool)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (i as u64, id.gen(), id.uindex() == i))
+    }
+    /// Returns `true` if the next generated id can be taken from the deleted ones.
+    fn can_recycle(&self) -> bool {
+        if self.recycling == EntityRecycling::Never {
+            return false;
+        }
+
+        if self.compacted > 0 {
+            return true;
+        }
+
+        match self.recycling {
+            EntityRecycling::Immediate => self.free_len > 0,
+            EntityRecycling::Delayed { min_free } => self.free_len > min_free,
+            EntityRecycling::Manual | EntityRecycling::Never => false,
+        }
+    }
     /// Sets the on entity deletion callback.
     pub fn on_deletion(&mut self, f: impl FnMut(EntityId) + Send + Sync + 'static) {
         self.on_deletion = Some(Box::new(f));
@@ -386,6 +637,107 @@ impl Entities {
     pub fn take_on_deletion(&mut self) -> Option<Box<dyn FnMut(EntityId) + Send + Sync + 'static>> {
         self.on_deletion.take()
     }
+    /// Moves the alive entities with the highest indices to the free indices below them,
+    /// alive entities then occupy the lowest indices.\
+    /// Returns the new id of each moved entity, nothing moves with [`EntityRecycling::Never`].
+    pub(crate) fn densify(&mut self) -> EntityRemap {
+        let mut remap = EntityRemap::default();
+
+        if self.recycling == EntityRecycling::Never {
+            return remap;
+        }
+
+        let mut free = Vec::with_capacity(self.free_len);
+        if let Some((new, old)) = self.list {
+            let mut index = old;
+
+            loop {
+                free.push(index);
+
+                if index == new {
+                    break;
+                }
+
+                index = self.data[index].uindex();
+            }
+        }
+        free.sort_unstable();
+
+        let alive_len = self.data.len() - free.len();
+        let movers: Vec<usize> = (alive_len..self.data.len())
+            .filter(|&index| self.data[index].uindex() == index)
+            .collect();
+        let holes = free.iter().take_while(|&&index| index < alive_len);
+
+        let mut used = 0;
+        let mut vacated = Vec::new();
+        for (&hole, &mover) in holes.zip(&movers) {
+            let old = self.data[mover];
+            let new = EntityId::new_from_index_and_gen(hole as u64, self.data[hole].gen());
+
+            self.data[hole] = new;
+
+            // the old id can never be alive again, its index is only reused if its generation can be bumped
+            if self.data[mover].bump_gen().is_ok() {
+                vacated.push(mover);
+                self.data[mover].set_index(EntityId::max_index());
+            } else {
+                self.retire(mover);
+            }
+
+            remap.0.insert(old, new);
+            used += 1;
+        }
+
+        let mut free: Vec<usize> = free[used..].iter().copied().chain(vacated).collect();
+        free.sort_unstable();
+
+        for pair in free.windows(2) {
+            self.data[pair[0]].set_index(pair[1] as u64);
+        }
+
+        self.list = match (free.first(), free.last()) {
+            (Some(&first), Some(&last)) => {
+                self.data[last].set_index(EntityId::max_index());
+
+                Some((last, first))
+            }
+            _ => None,
+        };
+        self.free_len = free.len();
+        self.compacted = self.compacted.min(self.free_len);
+
+        remap
+    }
+    /// Saves which ids are alive and the deleted ones waiting to be recycled.
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn save_state(&self) -> EntitiesState {
+        EntitiesState {
+            data: self.data.clone(),
+            list: self.list,
+            free_len: self.free_len,
+            compacted: self.compacted,
+        }
+    }
+    /// Puts back ids saved by [`Entities::save_state`], generations included.\
+    /// Components aren't touched.
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn restore_state(&mut self, state: EntitiesState) {
+        self.data = state.data;
+        self.list = state.list;
+        self.free_len = state.free_len;
+        self.compacted = state.compacted;
+        self.structural_changes = self.structural_changes.wrapping_add(1);
+    }
+}
+
+/// Ids of an [`Entities`] saved by [`Entities::save_state`].
+#[cfg(feature = "snapshot")]
+pub(crate) struct EntitiesState {
+    data: Vec<EntityId>,
+    list: Option<(usize, usize)>,
+    free_len: usize,
+    compacted: usize,
 }
 
 impl Storage for Entities {
@@ -394,8 +746,12 @@ impl Storage for Entities {
             return;
         }
 
+        self.structural_changes = self.structural_changes.wrapping_add(1);
+
         // the first value can be anything but self.data.len() - 1
         // otherwise we would set data[len - 1].index to len - 1 and not delete it
+        let mut free_len = 0;
+        let mut saturated = Vec::new();
         let mut last_alive = if self.data.len() as u64 == EntityId::max_index() {
             0
         } else {
@@ -407,15 +763,22 @@ impl Storage for Entities {
 
             if id.bump_gen().is_ok() {
                 last_alive = i as u64;
+                free_len += 1;
 
                 if let Some(on_deletion) = &mut self.on_deletion {
                     (on_deletion)(id_before_bump)
                 }
+            } else if id.uindex() == i {
+                saturated.push(i);
             }
 
             id.set_index(target);
         }
 
+        for index in saturated {
+            self.retire(index);
+        }
+
         let begin = self
             .data
             .iter()
@@ -428,6 +791,8 @@ impl Storage for Entities {
             .position(|id| id.gen() < EntityId::max_gen())
             .unwrap();
         self.list = Some((self.data.len() - end - 1, begin));
+        self.free_len = free_len;
+        self.compacted = 0;
     }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
         Some(StorageMemoryUsage {
@@ -441,6 +806,12 @@ impl Storage for Entities {
     fn is_empty(&self) -> bool {
         self.data.is_empty()
     }
+    fn len(&self) -> Option<usize> {
+        Some(self.alive_count())
+    }
+    fn structural_changes(&self) -> Option<u64> {
+        Some(self.structural_changes)
+    }
     fn move_component_from(
         &mut self,
         _other_all_storages: &mut crate::AllStorages,
@@ -491,6 +862,37 @@ fn entities() {
     assert_eq!(dead.gen(), 0);
 }
 
+#[test]
+fn generation_overflow() {
+    let mut entities = Entities::new();
+    entities.set_generation_overflow(GenerationOverflow::Quarantine { epochs: 2 });
+
+    let retired = entities.generate();
+    let quarantined = entities.generate();
+    let saturated = EntityId::new_from_index_and_gen(1, EntityId::max_gen() - 1);
+    entities.data[1] = saturated;
+
+    assert!(entities.delete_unchecked(saturated));
+    assert!(!entities.is_alive(saturated));
+    assert_eq!(entities.quarantined_count(), 1);
+    assert_eq!(entities.recyclable_count(), 0);
+
+    entities.set_generation_overflow(GenerationOverflow::Retire);
+    let saturated = EntityId::new_from_index_and_gen(0, EntityId::max_gen() - 1);
+    entities.data[0] = saturated;
+    assert!(entities.delete_unchecked(saturated));
+    assert_eq!(entities.retired_count(), 1);
+
+    assert_eq!(entities.advance_epoch(), 0);
+    assert_eq!(entities.advance_epoch(), 1);
+    assert_eq!(entities.quarantined_count(), 0);
+
+    let reused = entities.generate();
+    assert_eq!(reused.index(), quarantined.index());
+    assert_eq!(reused.gen(), 0);
+    assert_ne!(entities.generate().index(), retired.index());
+}
+
 #[test]
 fn iterator() {
     let mut entities = Entities::new();
@@ -531,3 +933,78 @@ fn iterator() {
 
     assert!(iter.next().is_none());
 }
+
+#[test]
+fn recycling() {
+    let mut entities = Entities::new();
+    entities.set_recycling(EntityRecycling::Delayed { min_free: 2 });
+
+    let ids = [
+        entities.generate(),
+        entities.generate(),
+        entities.generate(),
+    ];
+    for id in ids {
+        entities.delete_unchecked(id);
+    }
+
+    // 3 free ids, one more than `min_free`, reused in deletion order
+    let id = entities.generate();
+    assert_eq!(id.index(), 0);
+    assert_eq!(id.gen(), 1);
+    assert_eq!(entities.generate().index(), 3);
+
+    entities.set_recycling(EntityRecycling::Manual);
+    entities.delete_unchecked(id);
+    assert_eq!(entities.generate().index(), 4);
+
+    entities.compact();
+    assert_eq!(entities.generate().index(), 1);
+    assert_eq!(entities.generate().index(), 2);
+    assert_eq!(entities.generate().index(), 0);
+    assert_eq!(entities.generate().index(), 5);
+
+    entities.clear(TrackingTimestamp::new(0));
+    entities.set_recycling(EntityRecycling::Immediate);
+    assert_eq!(entities.alive_count(), 0);
+    assert_eq!(entities.recyclable_count(), 6);
+    assert!(entities.versions().all(|(_, _, alive)| !alive));
+    assert_eq!(entities.generate().index(), 0);
+}
+
+#[test]
+fn never_recycle() {
+    let mut entities = Entities::new();
+
+    let deleted = entities.generate();
+    entities.delete_unchecked(deleted);
+    entities.compact();
+    entities.set_recycling(EntityRecycling::Never);
+
+    let ids: Vec<_> = (0..4)
+        .map(|i| {
+            let id = entities.generate();
+            if i % 2 == 0 {
+                entities.delete_unchecked(id);
+            }
+            entities.compact();
+            id
+        })
+        .collect();
+
+    assert_eq!(
+        ids,
+        [
+            EntityId::new(1),
+            EntityId::new(2),
+            EntityId::new(3),
+            EntityId::new(4)
+        ]
+    );
+    assert_eq!(entities.recyclable_count(), 3);
+
+    entities.set_recycling(EntityRecycling::Immediate);
+    let recycled = entities.generate();
+    assert_eq!(recycled.index(), 0);
+    assert_eq!(recycled.gen(), 1);
+}
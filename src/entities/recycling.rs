@@ -0,0 +1,52 @@
+/// Controls when the ids of deleted entities are reused.
+///
+/// Deleted ids are reused in the order they were deleted, with their generation bumped.
+///
+/// Set with [`Entities::set_recycling`].
+///
+/// [`Entities::set_recycling`]: crate::Entities::set_recycling()
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum EntityRecycling {
+    /// Deleted ids are reused right away.
+    #[default]
+    Immediate,
+    /// Deleted ids are reused once more than `min_free` ids are waiting to be reused.
+    Delayed {
+        #[allow(missing_docs)]
+        min_free: usize,
+    },
+    /// Deleted ids are only reused after a call to [`Entities::compact`].
+    ///
+    /// [`Entities::compact`]: crate::Entities::compact()
+    Manual,
+    /// Deleted ids are never reused, not even by [`Entities::compact`].
+    ///
+    /// The n-th entity created always gets index n and generation 0, however deletions are interleaved.\
+    /// Useful for golden tests comparing snapshots, unrelated changes to when entities are deleted don't shift ids.
+    ///
+    /// [`Entities::compact`]: crate::Entities::compact()
+    Never,
+}
+
+/// Controls what happens to an index once its generation can't be bumped anymore.
+///
+/// Each deletion bumps the generation of the deleted id, after 65533 deletions at the same index it saturates.
+///
+/// Set with [`Entities::set_generation_overflow`].
+///
+/// [`Entities::set_generation_overflow`]: crate::Entities::set_generation_overflow()
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GenerationOverflow {
+    /// The index is never reused.
+    #[default]
+    Retire,
+    /// The index is reused with generation 0 after `epochs` calls to [`Entities::advance_epoch`].
+    ///
+    /// Ids of this index kept for longer than that could match new entities.
+    ///
+    /// [`Entities::advance_epoch`]: crate::Entities::advance_epoch()
+    Quarantine {
+        #[allow(missing_docs)]
+        epochs: u64,
+    },
+}
@@ -0,0 +1,37 @@
+use crate::entity_id::EntityId;
+use crate::ShipHashMap;
+
+/// New id of each entity moved by [`World::compact_entities`].
+///
+/// Components storing `EntityId`s have to be updated with it, the storages only update their own ids.
+///
+/// [`World::compact_entities`]: crate::World::compact_entities
+#[derive(Clone, Debug, Default)]
+pub struct EntityRemap(pub(crate) ShipHashMap<EntityId, EntityId>);
+
+impl EntityRemap {
+    /// Returns the new id of `entity`, `None` if it didn't move.
+    #[inline]
+    pub fn get(&self, entity: EntityId) -> Option<EntityId> {
+        self.0.get(&entity).copied()
+    }
+    /// Returns the id `entity` has after the compaction, moved or not.
+    #[inline]
+    pub fn map(&self, entity: EntityId) -> EntityId {
+        self.get(entity).unwrap_or(entity)
+    }
+    /// Returns the number of entities moved.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Returns `true` if no entity moved.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Returns an iterator over the `(old, new)` ids of the moved entities.
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, EntityId)> + '_ {
+        self.0.iter().map(|(&old, &new)| (old, new))
+    }
+}
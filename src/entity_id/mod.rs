@@ -1,14 +1,18 @@
 #[cfg(feature = "serde1")]
 mod serde;
+mod weak;
+
+pub use weak::WeakEntity;
 
 use core::num::NonZeroU64;
 
 /// Handle to an entity.
+///
+/// Always 64 bits, with a 48 bits index and a 16 bits generation, whatever the pointer width of the target.\
+/// 32 bits targets like wasm32 can address as many entities as 64 bits ones, memory being the only limit.
 // the id is 64 bits long
-// <- 46 index -> <- 16 gen -> <- 2 meta ->
+// <- 16 gen -> <- 48 index ->
 // a generation of !0 is used as a dead entity
-//
-// inserted and modified component are flagged using metadata
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct EntityId(pub(super) NonZeroU64);
@@ -93,9 +97,10 @@ impl EntityId {
     pub(crate) fn zero() -> Self {
         EntityId(NonZeroU64::new(1).unwrap())
     }
-    /// Returns a dead `EntityId`, it can be used as a null entity.
+    /// Returns a dead `EntityId`, it can be used as a null entity.\
+    /// It is never alive and never has components, components referencing other entities can store it instead of an `Option<EntityId>`.
     #[inline]
-    pub fn dead() -> Self {
+    pub const fn dead() -> Self {
         // SAFE not zero
         EntityId(unsafe { NonZeroU64::new_unchecked(!0) })
     }
@@ -117,8 +122,9 @@ impl EntityId {
     pub(crate) const fn max_gen() -> u16 {
         Self::MAX_GEN
     }
+    /// Returns `true` if this id can never be alive, like the one returned by [`EntityId::dead`].
     #[inline]
-    pub(crate) fn is_dead(&self) -> bool {
+    pub fn is_dead(&self) -> bool {
         (self.0.get() & Self::GEN_MASK) == Self::GEN_MASK
     }
     #[inline]
@@ -182,3 +188,14 @@ fn entity_id() {
     assert_eq!(entity_id.index(), 554);
     assert_eq!(entity_id.gen(), 3);
 }
+
+#[test]
+fn index_width() {
+    // the index doesn't depend on usize
+    let entity_id = EntityId::new_from_index_and_gen(u32::MAX as u64 + 1, 7);
+    assert_eq!(entity_id.index(), u32::MAX as u64 + 1);
+    assert_eq!(entity_id.gen(), 7);
+    assert_eq!(EntityId::max_index(), (1 << 48) - 2);
+    assert_eq!(core::mem::size_of::<EntityId>(), 8);
+    assert_eq!(core::mem::size_of::<Option<EntityId>>(), 8);
+}
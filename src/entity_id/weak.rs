@@ -0,0 +1,111 @@
+use super::EntityId;
+use crate::entities::Entities;
+use alloc::vec::Vec;
+
+/// Handle to an entity that stops resolving once the entity is deleted.
+///
+/// Ids are recycled with a new generation, a [`WeakEntity`] only resolves while the generation it was created with is alive.\
+/// Useful to reference entities from long-lived data without risking to reach a recycled id.
+///
+/// ### Example
+/// ```
+/// use shipyard::{EntitiesViewMut, WeakEntity, World};
+///
+/// let world = World::new();
+/// let mut entities = world.borrow::<EntitiesViewMut>().unwrap();
+///
+/// let entity = entities.add_entity((), ());
+/// let weak = WeakEntity::new(entity);
+///
+/// assert_eq!(weak.get(&entities), Some(entity));
+///
+/// entities.delete_unchecked(entity);
+/// let recycled = entities.add_entity((), ());
+///
+/// assert_eq!(recycled.index(), entity.index());
+/// assert_eq!(weak.get(&entities), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct WeakEntity(EntityId);
+
+impl WeakEntity {
+    /// Makes a new [`WeakEntity`] pointing to `entity`.
+    #[inline]
+    pub const fn new(entity: EntityId) -> Self {
+        WeakEntity(entity)
+    }
+    /// Returns a [`WeakEntity`] that never resolves.
+    #[inline]
+    pub const fn dead() -> Self {
+        WeakEntity(EntityId::dead())
+    }
+    /// Returns the entity if it's still alive.
+    #[inline]
+    pub fn get(&self, entities: &Entities) -> Option<EntityId> {
+        if entities.is_alive(self.0) {
+            Some(self.0)
+        } else {
+            None
+        }
+    }
+    /// Returns `true` if the entity is still alive.
+    #[inline]
+    pub fn is_alive(&self, entities: &Entities) -> bool {
+        entities.is_alive(self.0)
+    }
+    /// Returns the id this handle was created with, whether it's alive or not.
+    #[inline]
+    pub fn id(&self) -> EntityId {
+        self.0
+    }
+}
+
+impl From<EntityId> for WeakEntity {
+    #[inline]
+    fn from(entity: EntityId) -> Self {
+        WeakEntity(entity)
+    }
+}
+
+impl Entities {
+    /// Removes all handles pointing to deleted entities.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{EntitiesViewMut, WeakEntity, World};
+    ///
+    /// let world = World::new();
+    /// let mut entities = world.borrow::<EntitiesViewMut>().unwrap();
+    ///
+    /// let entity0 = entities.add_entity((), ());
+    /// let entity1 = entities.add_entity((), ());
+    /// let mut handles = vec![WeakEntity::new(entity0), WeakEntity::new(entity1)];
+    ///
+    /// entities.delete_unchecked(entity0);
+    /// entities.retain_alive(&mut handles);
+    ///
+    /// assert_eq!(handles, [WeakEntity::new(entity1)]);
+    /// ```
+    #[inline]
+    pub fn retain_alive(&self, handles: &mut Vec<WeakEntity>) {
+        handles.retain(|handle| handle.is_alive(self));
+    }
+    /// Replaces handles pointing to deleted entities by [`WeakEntity::dead`].\
+    /// Returns the number of handles invalidated.
+    ///
+    /// Unlike [`Entities::retain_alive`], positions of the handles are kept.
+    pub fn invalidate_dead(&self, handles: &mut [WeakEntity]) -> usize {
+        let mut count = 0;
+
+        for handle in handles {
+            if !handle.0.is_dead() && !handle.is_alive(self) {
+                *handle = WeakEntity::dead();
+                count += 1;
+            }
+        }
+
+        count
+    }
+}
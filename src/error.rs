@@ -1,11 +1,19 @@
 //! All error types.
+//!
+//! With the **diagnostics** feature, the most common errors also implement `miette::Diagnostic`,
+//! adding an error code and a suggestion to fix them.
 
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+
+use crate::borrow::Mutability;
 use crate::entity_id::EntityId;
 use crate::info::TypeInfo;
 use crate::scheduler::Label;
 use crate::storage::StorageId;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::{Debug, Display, Formatter};
 #[cfg(feature = "std")]
@@ -73,6 +81,37 @@ pub enum GetStorage {
         id: StorageId,
         tracking: &'static str,
     },
+    /// The `World` is strict and the component storage wasn't registered.
+    ///
+    /// See [`World::set_strict`](crate::World::set_strict).
+    UnregisteredStorage {
+        #[allow(missing_docs)]
+        name: &'static str,
+        #[allow(missing_docs)]
+        id: StorageId,
+    },
+    /// A storage of a different type is already stored at this id.\
+    /// Either two `TypeId`s hashed to the same id or the same custom id was used for two storages.
+    StorageIdCollision {
+        #[allow(missing_docs)]
+        name: &'static str,
+        #[allow(missing_docs)]
+        id: StorageId,
+        /// Type of the storage already present.
+        stored: &'static str,
+    },
+    /// The storage isn't part of the access granted to a [`SubWorld`] or declared by an [`AllStoragesViewOf`].
+    ///
+    /// [`SubWorld`]: crate::SubWorld
+    /// [`AllStoragesViewOf`]: crate::AllStoragesViewOf
+    NotAllowed {
+        #[allow(missing_docs)]
+        name: Cow<'static, str>,
+        #[allow(missing_docs)]
+        id: StorageId,
+        /// Access requested.
+        mutability: Mutability,
+    },
     /// Error returned by a custom view.
     #[cfg(feature = "std")]
     Custom(Box<dyn Error + Send + Sync>),
@@ -133,6 +172,40 @@ impl PartialEq for GetStorage {
                     tracking: r_tracking,
                 },
             ) => l_name == r_name && l_id == r_id && l_tracking == r_tracking,
+            (
+                GetStorage::UnregisteredStorage {
+                    name: l_name,
+                    id: l_id,
+                },
+                GetStorage::UnregisteredStorage {
+                    name: r_name,
+                    id: r_id,
+                },
+            ) => l_name == r_name && l_id == r_id,
+            (
+                GetStorage::StorageIdCollision {
+                    name: l_name,
+                    id: l_id,
+                    stored: l_stored,
+                },
+                GetStorage::StorageIdCollision {
+                    name: r_name,
+                    id: r_id,
+                    stored: r_stored,
+                },
+            ) => l_name == r_name && l_id == r_id && l_stored == r_stored,
+            (
+                GetStorage::NotAllowed {
+                    name: l_name,
+                    id: l_id,
+                    mutability: l_mutability,
+                },
+                GetStorage::NotAllowed {
+                    name: r_name,
+                    id: r_id,
+                    mutability: r_mutability,
+                },
+            ) => l_name == r_name && l_id == r_id && l_mutability == r_mutability,
             _ => false,
         }
     }
@@ -189,6 +262,12 @@ impl Debug for GetStorage {
             } else {
                 f.write_fmt(format_args!("{} tracking is not enabled for {:?} storage.", tracking, id))
             }
+            GetStorage::UnregisteredStorage { name, .. } => f.write_fmt(format_args!("{} storage was not registered and the World is strict. You can register it with: world.register_components::<{}>();", name, name)),
+            GetStorage::StorageIdCollision { name, id, stored } => f.write_fmt(format_args!("{} storage cannot be accessed, {} storage is already stored at {:?}.", name, stored, id)),
+            GetStorage::NotAllowed { name, mutability, .. } => match mutability {
+                Mutability::Shared => f.write_fmt(format_args!("{} storage cannot be borrowed, it's not part of the SubWorld or AllStoragesViewOf access.", name)),
+                Mutability::Exclusive => f.write_fmt(format_args!("{} storage cannot be mutably borrowed, it's not part of the SubWorld or AllStoragesViewOf exclusive access.", name)),
+            }
             GetStorage::Custom(err) => {
                 f.write_fmt(format_args!("Storage borrow failed with a custom error, {:?}.", err))
             }
@@ -420,6 +499,39 @@ impl Display for SetDefaultWorkload {
 /// Error returned by [`run_default`] and [`run_workload`].
 /// The error can be a storage error, problem with the scheduler's borrowing, a non existent workload or a custom error.
 ///
+/// Custom errors can be retrieved with [`RunWorkload::downcast`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{IntoWorkload, IntoWorkloadTrySystem, Workload, World};
+///
+/// #[derive(Debug)]
+/// struct GameOver;
+///
+/// impl std::fmt::Display for GameOver {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("game over")
+///     }
+/// }
+///
+/// impl std::error::Error for GameOver {}
+///
+/// fn check_health() -> Result<(), GameOver> {
+///     Err(GameOver)
+/// }
+///
+/// fn main_loop() -> Workload {
+///     (check_health.into_workload_try_system().unwrap(),).into_workload()
+/// }
+///
+/// let mut world = World::new();
+/// world.add_workload(main_loop);
+///
+/// let err = world.run_workload(main_loop).unwrap_err();
+/// assert!(err.system_name().is_some());
+/// assert!(matches!(err.downcast::<GameOver>(), Ok(GameOver)));
+/// ```
+///
 /// [`run_default`]: crate::World#method::run_default()
 /// [`run_workload`]: crate::World#method::run_workload()
 pub enum RunWorkload {
@@ -427,25 +539,107 @@ pub enum RunWorkload {
     Scheduler,
     /// Error while running a system.
     Run((Box<dyn Label>, Run)),
+    /// Multiple systems running in parallel failed, in the order they were added to the workload.
+    Runs(Vec<(Box<dyn Label>, Run)>),
     /// Workload is not present in the world.
     MissingWorkload,
 }
 
 impl RunWorkload {
+    /// Returns `Run` for a single error and `Runs` for multiple errors.
+    ///
+    /// `runs` can't be empty.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn from_runs(mut runs: Vec<(Box<dyn Label>, Run)>) -> RunWorkload {
+        if runs.len() == 1 {
+            RunWorkload::Run(runs.pop().unwrap())
+        } else {
+            RunWorkload::Runs(runs)
+        }
+    }
+    /// Returns the systems that failed and their error.
+    pub fn runs(&self) -> &[(Box<dyn Label>, Run)] {
+        match self {
+            RunWorkload::Run(run) => core::slice::from_ref(run),
+            RunWorkload::Runs(runs) => runs,
+            RunWorkload::Scheduler | RunWorkload::MissingWorkload => &[],
+        }
+    }
+    /// Returns the name of the first system that failed.
+    pub fn system_name(&self) -> Option<&dyn Label> {
+        self.runs().first().map(|(system_name, _)| &**system_name)
+    }
     /// Helper function to get back a custom error.
+    ///
+    /// When multiple systems failed, returns the first custom error.
     #[cfg(feature = "std")]
     pub fn custom_error(self) -> Option<Box<dyn Error + Send + Sync>> {
-        match self {
-            RunWorkload::Run((_, Run::Custom(error))) => Some(error),
-            _ => None,
-        }
+        self.into_custom_error()
     }
     /// Helper function to get back a custom error.
+    ///
+    /// When multiple systems failed, returns the first custom error.
     #[cfg(not(feature = "std"))]
     pub fn custom_error(self) -> Option<Box<dyn core::any::Any + Send>> {
+        self.into_custom_error()
+    }
+    fn into_custom_error(self) -> Option<CustomError> {
+        let runs = match self {
+            RunWorkload::Run(run) => alloc::vec![run],
+            RunWorkload::Runs(runs) => runs,
+            RunWorkload::Scheduler | RunWorkload::MissingWorkload => return None,
+        };
+
+        runs.into_iter().find_map(|(_, run)| match run {
+            Run::Custom(error) => Some(error),
+            Run::GetStorage(_) => None,
+        })
+    }
+    /// Returns the first custom error of type `E`, or `self` if no system returned one.
+    #[cfg(feature = "std")]
+    pub fn downcast<E: Error + 'static>(self) -> Result<E, RunWorkload> {
+        self.take_run(|run| run.downcast_ref::<E>().is_some(), Run::downcast::<E>)
+    }
+    /// Returns the first custom error of type `E`, or `self` if no system returned one.
+    #[cfg(not(feature = "std"))]
+    pub fn downcast<E: core::any::Any>(self) -> Result<E, RunWorkload> {
+        self.take_run(|run| run.downcast_ref::<E>().is_some(), Run::downcast::<E>)
+    }
+    /// Returns a reference to the first custom error of type `E`.
+    #[cfg(feature = "std")]
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.runs().iter().find_map(|(_, run)| run.downcast_ref())
+    }
+    /// Returns a reference to the first custom error of type `E`.
+    #[cfg(not(feature = "std"))]
+    pub fn downcast_ref<E: core::any::Any>(&self) -> Option<&E> {
+        self.runs().iter().find_map(|(_, run)| run.downcast_ref())
+    }
+    /// Converts the first error matching `is_match` with `take`.
+    fn take_run<E>(
+        self,
+        is_match: impl Fn(&Run) -> bool,
+        take: impl FnOnce(Run) -> Result<E, Run>,
+    ) -> Result<E, RunWorkload> {
+        let index = match self.runs().iter().position(|(_, run)| is_match(run)) {
+            Some(index) => index,
+            None => return Err(self),
+        };
+
         match self {
-            RunWorkload::Run((_, Run::Custom(error))) => Some(error),
-            _ => None,
+            RunWorkload::Run((system_name, run)) => {
+                take(run).map_err(|run| RunWorkload::Run((system_name, run)))
+            }
+            RunWorkload::Runs(mut runs) => {
+                let (system_name, run) = runs.remove(index);
+
+                take(run).map_err(|run| {
+                    runs.insert(index, (system_name, run));
+
+                    RunWorkload::Runs(runs)
+                })
+            }
+            RunWorkload::Scheduler | RunWorkload::MissingWorkload => Err(self),
         }
     }
 }
@@ -463,6 +657,15 @@ impl Debug for RunWorkload {
             RunWorkload::Run((system_name, run)) => {
                 f.write_fmt(format_args!("System {:?} failed: {:?}", system_name, run))
             }
+            RunWorkload::Runs(runs) => {
+                f.write_str("Multiple systems failed:")?;
+
+                for (system_name, run) in runs {
+                    f.write_fmt(format_args!("\n- System {:?} failed: {:?}", system_name, run))?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
@@ -473,6 +676,50 @@ impl Display for RunWorkload {
     }
 }
 
+/// Error returned by [`World::set_state`].
+///
+/// [`World::set_state`]: crate::World::set_state
+pub enum SetState {
+    /// The `StateMachine` couldn't be borrowed.
+    StateMachine(GetStorage),
+    /// The state's workload couldn't be made the default workload.
+    SetDefaultWorkload(SetDefaultWorkload),
+    /// The `on_exit` or `on_enter` workload failed.
+    RunWorkload(RunWorkload),
+}
+
+#[cfg(feature = "std")]
+impl Error for SetState {}
+
+impl Debug for SetState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            SetState::StateMachine(err) => f.write_fmt(format_args!(
+                "Could not borrow the state machine: {:?}",
+                err
+            )),
+            SetState::SetDefaultWorkload(err) => f.write_fmt(format_args!(
+                "Could not switch to the state's workload: {:?}",
+                err
+            )),
+            SetState::RunWorkload(err) => {
+                f.write_fmt(format_args!("State transition workload failed: {:?}", err))
+            }
+        }
+    }
+}
+
+impl Display for SetState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+type CustomError = Box<dyn Error + Send + Sync>;
+#[cfg(not(feature = "std"))]
+type CustomError = Box<dyn core::any::Any + Send>;
+
 /// Error returned by [`World::run`] and [`AllStorages::run`].
 /// Can refer to an invalid storage borrow or a custom error.
 ///
@@ -506,6 +753,38 @@ impl Run {
     pub fn from_custom<E: core::any::Any + Send>(error: E) -> Run {
         Run::Custom(Box::new(error))
     }
+    /// Returns the custom error if it's of type `E`, or `self` otherwise.
+    #[cfg(feature = "std")]
+    pub fn downcast<E: Error + 'static>(self) -> Result<E, Run> {
+        match self {
+            Run::Custom(error) => error.downcast().map(|error| *error).map_err(Run::Custom),
+            Run::GetStorage(_) => Err(self),
+        }
+    }
+    /// Returns the custom error if it's of type `E`, or `self` otherwise.
+    #[cfg(not(feature = "std"))]
+    pub fn downcast<E: core::any::Any>(self) -> Result<E, Run> {
+        match self {
+            Run::Custom(error) => error.downcast().map(|error| *error).map_err(Run::Custom),
+            Run::GetStorage(_) => Err(self),
+        }
+    }
+    /// Returns a reference to the custom error if it's of type `E`.
+    #[cfg(feature = "std")]
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        match self {
+            Run::Custom(error) => error.downcast_ref(),
+            Run::GetStorage(_) => None,
+        }
+    }
+    /// Returns a reference to the custom error if it's of type `E`.
+    #[cfg(not(feature = "std"))]
+    pub fn downcast_ref<E: core::any::Any>(&self) -> Option<&E> {
+        match self {
+            Run::Custom(error) => error.downcast_ref(),
+            Run::GetStorage(_) => None,
+        }
+    }
 }
 
 impl PartialEq for Run {
@@ -821,11 +1100,14 @@ impl Display for ImpossibleRequirements {
 /// [`World::get`]: crate::World::get
 /// [`AllStorages::get`]: crate::AllStorages::get
 #[derive(PartialEq)]
+#[non_exhaustive]
 pub enum GetComponent {
     #[allow(missing_docs)]
     StorageBorrow(GetStorage),
     #[allow(missing_docs)]
     MissingComponent(MissingComponent),
+    /// The id can never be alive, like [`EntityId::dead`].
+    DeadEntity(EntityId),
 }
 
 impl From<GetStorage> for GetComponent {
@@ -848,6 +1130,10 @@ impl Debug for GetComponent {
         match self {
             GetComponent::StorageBorrow(err) => f.write_fmt(format_args!("{:?}", err)),
             GetComponent::MissingComponent(err) => f.write_fmt(format_args!("{:?}", err)),
+            GetComponent::DeadEntity(id) => f.write_fmt(format_args!(
+                "{:?} is dead, it can't have any component.",
+                id
+            )),
         }
     }
 }
@@ -857,3 +1143,343 @@ impl Display for GetComponent {
         Debug::fmt(self, f)
     }
 }
+
+/// Error returned by [`World::migrate_storage`] and [`AllStorages::migrate_storage`].
+///
+/// [`World::migrate_storage`]: crate::World::migrate_storage
+/// [`AllStorages::migrate_storage`]: crate::AllStorages::migrate_storage
+#[derive(Clone, PartialEq, Eq)]
+pub enum MigrateStorage {
+    /// No storage holds a component with this name.
+    MissingStorage(&'static str),
+    /// The storage with this name doesn't hold the old component type.
+    WrongType {
+        #[allow(missing_docs)]
+        component: &'static str,
+        /// Name of the storage found.
+        found: Cow<'static, str>,
+    },
+}
+
+#[cfg(feature = "std")]
+impl Error for MigrateStorage {}
+
+impl Debug for MigrateStorage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            MigrateStorage::MissingStorage(name) => {
+                f.write_fmt(format_args!("No storage exists for {}.", name))
+            }
+            MigrateStorage::WrongType { component, found } => f.write_fmt(format_args!(
+                "The storage of {} is a {}, it doesn't hold the component type to migrate from.",
+                component, found
+            )),
+        }
+    }
+}
+
+impl Display for MigrateStorage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
+/// Error returned by [`World::replay`], [`AllStorages::replay`] and [`ReplayStream::from_bytes`].
+///
+/// [`World::replay`]: crate::World::replay
+/// [`AllStorages::replay`]: crate::AllStorages::replay
+/// [`ReplayStream::from_bytes`]: crate::ReplayStream::from_bytes
+#[derive(Clone, PartialEq, Eq)]
+pub enum Replay {
+    /// The bytes don't represent a valid stream.
+    InvalidStream,
+    /// The stream references a component absent from the registry.
+    UnregisteredComponent(Cow<'static, str>),
+    /// The decoder failed to make a component from the recorded data.
+    InvalidComponentData(Cow<'static, str>),
+    /// The stream tries to add a component to an entity that isn't alive.
+    EntityIsNotAlive(EntityId),
+    /// The entity couldn't be spawned with the recorded id, the `World` probably wasn't empty.
+    EntityIdMismatch(EntityId),
+}
+
+#[cfg(feature = "std")]
+impl Error for Replay {}
+
+impl Debug for Replay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Replay::InvalidStream => {
+                f.write_str("The bytes don't represent a valid replay stream.")
+            }
+            Replay::UnregisteredComponent(name) => {
+                f.write_fmt(format_args!("{} is not part of the replay registry.", name))
+            }
+            Replay::InvalidComponentData(name) => f.write_fmt(format_args!(
+                "Recorded data could not be decoded as {}.",
+                name
+            )),
+            Replay::EntityIsNotAlive(entity) => f.write_fmt(format_args!(
+                "Entity {:?} is not alive, components cannot be added to it.",
+                entity
+            )),
+            Replay::EntityIdMismatch(entity) => f.write_fmt(format_args!(
+                "Entity {:?} could not be spawned. Replays have to be played in an empty World.",
+                entity
+            )),
+        }
+    }
+}
+
+impl Display for Replay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
+/// Error returned by [`World::load_snapshot`] and [`AllStorages::load_snapshot`].
+///
+/// [`World::load_snapshot`]: crate::World::load_snapshot
+/// [`AllStorages::load_snapshot`]: crate::AllStorages::load_snapshot
+#[cfg(feature = "snapshot")]
+#[derive(Clone, PartialEq, Eq)]
+pub enum Snapshot {
+    /// The bytes don't represent a valid snapshot.
+    InvalidSnapshot,
+    /// The snapshot contains a component absent from the registry.
+    UnregisteredComponent(Cow<'static, str>),
+    /// The snapshot was saved by a newer version of the `World`.
+    NewerVersion {
+        /// Version of the registry.
+        expected: u32,
+        /// Version of the snapshot.
+        found: u32,
+    },
+    /// The snapshot was saved with a different layout and the registry has no migration for it.
+    LayoutMismatch {
+        #[allow(missing_docs)]
+        component: Cow<'static, str>,
+        /// Layout hash of the registry.
+        expected: u64,
+        /// Layout hash of the snapshot.
+        found: u64,
+    },
+    /// A component could not be deserialized.
+    InvalidComponentData(Cow<'static, str>),
+    /// The entity couldn't be spawned with its saved id, the `World` probably wasn't empty.
+    EntityIdMismatch(EntityId),
+}
+
+#[cfg(all(feature = "snapshot", feature = "std"))]
+impl Error for Snapshot {}
+
+#[cfg(feature = "snapshot")]
+impl Debug for Snapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Snapshot::InvalidSnapshot => f.write_str("The bytes don't represent a valid snapshot."),
+            Snapshot::UnregisteredComponent(name) => {
+                f.write_fmt(format_args!("{} is not part of the snapshot registry.", name))
+            }
+            Snapshot::NewerVersion { expected, found } => f.write_fmt(format_args!(
+                "The snapshot was saved by World version {} but the registry is at version {}.",
+                found, expected
+            )),
+            Snapshot::LayoutMismatch {
+                component,
+                expected,
+                found,
+            } => f.write_fmt(format_args!(
+                "{} was saved with layout {:#x} but the registry expects {:#x} and has no migration for it.",
+                component, found, expected
+            )),
+            Snapshot::InvalidComponentData(name) => f.write_fmt(format_args!(
+                "Saved data could not be deserialized as {}.",
+                name
+            )),
+            Snapshot::EntityIdMismatch(entity) => f.write_fmt(format_args!(
+                "Entity {:?} could not be spawned. Snapshots have to be loaded in an empty World.",
+                entity
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
+/// Error returned by [`World::apply_replication_delta`] and [`ReplicationDelta::from_bytes`].
+///
+/// [`World::apply_replication_delta`]: crate::World::apply_replication_delta
+/// [`ReplicationDelta::from_bytes`]: crate::ReplicationDelta::from_bytes
+#[cfg(feature = "replication")]
+#[derive(Clone, PartialEq, Eq)]
+pub enum Replication {
+    /// The bytes don't represent a valid delta.
+    InvalidDelta,
+    /// The delta contains a component absent from the registry.
+    UnregisteredComponent(Cow<'static, str>),
+    /// A component could not be deserialized.
+    InvalidComponentData(Cow<'static, str>),
+}
+
+#[cfg(all(feature = "replication", feature = "std"))]
+impl Error for Replication {}
+
+#[cfg(feature = "replication")]
+impl Debug for Replication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Replication::InvalidDelta => {
+                f.write_str("The bytes don't represent a valid replication delta.")
+            }
+            Replication::UnregisteredComponent(name) => f.write_fmt(format_args!(
+                "{} is not part of the replication registry.",
+                name
+            )),
+            Replication::InvalidComponentData(name) => f.write_fmt(format_args!(
+                "Replicated data could not be deserialized as {}.",
+                name
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "replication")]
+impl Display for Replication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
+/// Error returned by [`World::borrow_timeout`].
+///
+/// [`World::borrow_timeout`]: crate::World::borrow_timeout
+#[cfg(feature = "borrow_debug")]
+pub struct BorrowTimeout {
+    /// Error of the last borrow attempt.
+    pub error: GetStorage,
+    /// Borrows that were held on the storage when the timeout elapsed.
+    pub holders: Vec<crate::borrow_debug::BorrowHolder>,
+}
+
+#[cfg(feature = "borrow_debug")]
+impl Error for BorrowTimeout {}
+
+#[cfg(feature = "borrow_debug")]
+impl Debug for BorrowTimeout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.write_fmt(format_args!("Timed out: {:?}", self.error))?;
+
+        if self.holders.is_empty() {
+            f.write_str("\nNo borrow is held anymore.")
+        } else {
+            f.write_str("\nHeld by:")?;
+
+            for holder in &self.holders {
+                f.write_fmt(format_args!("\n    - {:?}", holder))?;
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "borrow_debug")]
+impl Display for BorrowTimeout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
+/// Error returned by [`World::add_plugin`] and [`World::add_plugins`].
+///
+/// [`World::add_plugin`]: crate::World::add_plugin
+/// [`World::add_plugins`]: crate::World::add_plugins
+#[derive(Clone, PartialEq, Eq)]
+pub enum AddPlugin {
+    /// A required unique isn't in the `World` and no plugin provides it.
+    MissingUnique {
+        #[allow(missing_docs)]
+        plugin: &'static str,
+        #[allow(missing_docs)]
+        unique: &'static str,
+    },
+    /// A plugin declared a unique it didn't add.
+    NotProvided {
+        #[allow(missing_docs)]
+        plugin: &'static str,
+        #[allow(missing_docs)]
+        unique: &'static str,
+    },
+    /// These plugins each require a unique provided by an other one.
+    Cycle(Vec<&'static str>),
+}
+
+#[cfg(feature = "std")]
+impl Error for AddPlugin {}
+
+impl Debug for AddPlugin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            AddPlugin::MissingUnique { plugin, unique } => f.write_fmt(format_args!(
+                "Plugin {} requires {} unique but it's not in the World and no plugin provides it.",
+                plugin, unique
+            )),
+            AddPlugin::NotProvided { plugin, unique } => f.write_fmt(format_args!(
+                "Plugin {} declared it provides {} unique but didn't add it.",
+                plugin, unique
+            )),
+            AddPlugin::Cycle(plugins) => f.write_fmt(format_args!(
+                "Plugins require each other's uniques: {:?}",
+                plugins
+            )),
+        }
+    }
+}
+
+impl Display for AddPlugin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
+
+/// Error returned by [`SystemRegistry::workload`] and [`SystemRegistry::describe`].
+///
+/// [`SystemRegistry::workload`]: crate::SystemRegistry::workload
+/// [`SystemRegistry::describe`]: crate::SystemRegistry::describe
+#[derive(Clone, PartialEq, Eq)]
+pub enum BuildWorkloadDescriptor {
+    /// No system is registered with this name.
+    UnknownSystem(String),
+    /// This system of the workload isn't registered, the workload can't be described.
+    UnregisteredSystem(String),
+}
+
+#[cfg(feature = "std")]
+impl Error for BuildWorkloadDescriptor {}
+
+impl Debug for BuildWorkloadDescriptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            BuildWorkloadDescriptor::UnknownSystem(name) => f.write_fmt(format_args!(
+                "No system is registered as {} in the SystemRegistry.",
+                name
+            )),
+            BuildWorkloadDescriptor::UnregisteredSystem(name) => f.write_fmt(format_args!(
+                "System {} is not part of the SystemRegistry, the workload can't be described.",
+                name
+            )),
+        }
+    }
+}
+
+impl Display for BuildWorkloadDescriptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        Debug::fmt(self, f)
+    }
+}
@@ -0,0 +1,306 @@
+//! [`miette::Diagnostic`] implementations, giving error codes and suggestions on top of the messages.
+
+use super::{
+    AddWorkload, Borrow, GetComponent, GetStorage, ImpossibleRequirements, MissingComponent, Run,
+    RunWorkload, SetDefaultWorkload,
+};
+use crate::borrow::Mutability;
+use alloc::boxed::Box;
+use alloc::format;
+use core::fmt::Display;
+use miette::Diagnostic;
+
+fn code(code: &'static str) -> Option<Box<dyn Display>> {
+    Some(Box::new(code))
+}
+
+fn help<'a, H: Display + 'a>(help: H) -> Option<Box<dyn Display + 'a>> {
+    Some(Box::new(help))
+}
+
+/// Suggestion shared by all errors caused by a borrow conflict.
+fn borrow_help(borrow: &Borrow, storage: &str) -> Option<Box<dyn Display + 'static>> {
+    match borrow {
+        Borrow::Unique => help(format!(
+            "a view of {storage} is still alive somewhere. \
+            Drop it before borrowing mutably and make sure the same system or `borrow` call doesn't request {storage} twice."
+        )),
+        Borrow::Shared => help(format!(
+            "a mutable view of {storage} is still alive somewhere. \
+            Drop it before borrowing again and make sure the same system or `borrow` call doesn't request {storage} twice."
+        )),
+        Borrow::WrongThread => help(format!(
+            "{storage} holds `!Send` components, it can only be accessed from the thread that created the World. \
+            Workloads run these systems on the calling thread, check for views moved to other threads."
+        )),
+        Borrow::MultipleThreads => help(format!(
+            "{storage} holds `!Sync` components, only one thread can access it at a time. \
+            Avoid sharing its views between threads."
+        )),
+    }
+}
+
+impl Diagnostic for GetStorage {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            GetStorage::AllStoragesBorrow(_) => code("shipyard::get_storage::all_storages_borrow"),
+            GetStorage::StorageBorrow { .. } => code("shipyard::get_storage::storage_borrow"),
+            GetStorage::Entities(_) => code("shipyard::get_storage::entities_borrow"),
+            GetStorage::MissingStorage { .. } => code("shipyard::get_storage::missing_storage"),
+            GetStorage::TrackingNotEnabled { .. } => {
+                code("shipyard::get_storage::tracking_not_enabled")
+            }
+            GetStorage::UnregisteredStorage { .. } => {
+                code("shipyard::get_storage::unregistered_storage")
+            }
+            GetStorage::StorageIdCollision { .. } => {
+                code("shipyard::get_storage::storage_id_collision")
+            }
+            GetStorage::NotAllowed { .. } => code("shipyard::get_storage::not_allowed"),
+            GetStorage::Custom(_) => code("shipyard::get_storage::custom"),
+        }
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            GetStorage::AllStoragesBorrow(_) => help(
+                "`AllStoragesView(Mut)` gives access to every storage, it can't be borrowed alongside other views. \
+                Request it alone or borrow the other storages through it.",
+            ),
+            GetStorage::StorageBorrow { name, id, borrow } => match name {
+                Some(name) => borrow_help(borrow, name),
+                None => borrow_help(borrow, &format!("{:?}", id)),
+            },
+            GetStorage::Entities(borrow) => borrow_help(borrow, "Entities"),
+            GetStorage::MissingStorage { name, id } => {
+                let name = name.map_or_else(|| format!("{:?}", id), |name| name.into());
+
+                help(format!(
+                    "component storages are created on demand but unique storages have to be added first. \
+                    Call `world.add_unique(...)` before borrowing {name}, and check it wasn't removed with `remove_unique`."
+                ))
+            }
+            GetStorage::TrackingNotEnabled { name, tracking, .. } => help(format!(
+                "add `type Tracking = track::{};` to the `Component` impl{}, \
+                or `#[track({})]` when deriving it.",
+                // `tracking` is written "Insertion and Modification"
+                tracking.replace(" and ", "And"),
+                name.map(|name| format!(" of {name}")).unwrap_or_default(),
+                tracking.replace(" and ", ", "),
+            )),
+            GetStorage::UnregisteredStorage { name, .. } => help(format!(
+                "strict worlds don't create component storages on demand. \
+                Call `world.register_components::<...>()` with {name} when building the World, or check the component type isn't a typo."
+            )),
+            GetStorage::StorageIdCollision { .. } => help(
+                "storages with a custom id need an id of their own, `StorageId::from_name` derives one from a unique name. \
+                If both are Rust types, their `TypeId`s collided, give one of them a custom id.",
+            ),
+            GetStorage::NotAllowed {
+                name, mutability, ..
+            } => match mutability {
+                Mutability::Shared => help(format!(
+                    "add `View<...>` or `ViewMut<...>` of {name} to the views passed to `World::sub_world`."
+                )),
+                Mutability::Exclusive => help(format!(
+                    "{name} is only part of the shared access, use `ViewMut<...>` when calling `World::sub_world`."
+                )),
+            },
+            GetStorage::Custom(_) => None,
+        }
+    }
+}
+
+impl Diagnostic for MissingComponent {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        code("shipyard::missing_component")
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        help(format!(
+            "the entity at index {} generation {} doesn't have this component. \
+            It might never have been added, been removed, or the entity might have been deleted{}. \
+            Check with `contains` first when the component is optional.",
+            self.id.index(),
+            self.id.gen(),
+            // a generation of 0 can't come from a reused index
+            if self.id.gen() > 0 {
+                " and its index reused"
+            } else {
+                ""
+            }
+        ))
+    }
+}
+
+impl Diagnostic for GetComponent {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            GetComponent::StorageBorrow(err) => err.code(),
+            GetComponent::MissingComponent(err) => err.code(),
+            GetComponent::DeadEntity(_) => code("shipyard::get_component::dead_entity"),
+        }
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            GetComponent::StorageBorrow(err) => err.help(),
+            GetComponent::MissingComponent(err) => err.help(),
+            GetComponent::DeadEntity(_) => help(
+                "`EntityId::dead` is a placeholder and never points to an entity, \
+                it likely comes from a `Default` value that was never replaced.",
+            ),
+        }
+    }
+}
+
+impl Diagnostic for ImpossibleRequirements {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            ImpossibleRequirements::BeforeAndAfter(..) => {
+                code("shipyard::impossible_requirements::before_and_after")
+            }
+            ImpossibleRequirements::ImpossibleConstraints(..) => {
+                code("shipyard::impossible_requirements::impossible_constraints")
+            }
+        }
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            ImpossibleRequirements::BeforeAndAfter(system, other) => help(format!(
+                "remove either `before_all({other:?})` or `after_all({other:?})` from {system:?}, \
+                possibly inherited from its workload."
+            )),
+            ImpossibleRequirements::ImpossibleConstraints(system, ..) => help(format!(
+                "the `before_all` and `after_all` constraints of {system:?} form a cycle. \
+                Check the systems listed, one of them has to run both before and after it."
+            )),
+        }
+    }
+}
+
+impl Diagnostic for AddWorkload {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            AddWorkload::AlreadyExists => code("shipyard::add_workload::already_exists"),
+            AddWorkload::Borrow => code("shipyard::add_workload::borrow"),
+            AddWorkload::ImpossibleRequirements(err) => err.code(),
+            AddWorkload::MissingInWorkload(..) => {
+                code("shipyard::add_workload::missing_in_workload")
+            }
+            AddWorkload::MissingBefore(..) => code("shipyard::add_workload::missing_before"),
+            AddWorkload::MissingAfter(..) => code("shipyard::add_workload::missing_after"),
+            AddWorkload::TrackingAllStoragesBorrow => {
+                code("shipyard::add_workload::tracking_all_storages_borrow")
+            }
+            AddWorkload::TrackingStorageBorrow { .. } => {
+                code("shipyard::add_workload::tracking_storage_borrow")
+            }
+        }
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            AddWorkload::AlreadyExists => help(
+                "workload names have to be unique, rename one of them with `World::rename_workload`, \
+                or use `Workload::merge` to add systems to an existing workload.",
+            ),
+            AddWorkload::Borrow => help(
+                "workloads can't be added from inside a running workload, add it before calling `run_workload`.",
+            ),
+            AddWorkload::ImpossibleRequirements(err) => err.help(),
+            AddWorkload::MissingInWorkload(system, _) => help(format!(
+                "{system:?} is tagged `require_in_workload`, add the missing systems to the same workload."
+            )),
+            AddWorkload::MissingBefore(system, _) => help(format!(
+                "{system:?} is tagged `require_before`, add the missing systems before it in the workload."
+            )),
+            AddWorkload::MissingAfter(system, _) => help(format!(
+                "{system:?} is tagged `require_after`, add the missing systems after it in the workload."
+            )),
+            AddWorkload::TrackingAllStoragesBorrow => help(
+                "enabling the tracking used by the workload requires AllStorages, \
+                drop any `AllStoragesViewMut` before adding the workload.",
+            ),
+            AddWorkload::TrackingStorageBorrow { name, id, borrow } => match name {
+                Some(name) => borrow_help(borrow, name),
+                None => borrow_help(borrow, &format!("{:?}", id)),
+            },
+        }
+    }
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        match self {
+            AddWorkload::ImpossibleRequirements(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Diagnostic for SetDefaultWorkload {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            SetDefaultWorkload::Borrow => code("shipyard::set_default_workload::borrow"),
+            SetDefaultWorkload::MissingWorkload => {
+                code("shipyard::set_default_workload::missing_workload")
+            }
+        }
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            SetDefaultWorkload::Borrow => {
+                help("the default workload can't be changed from inside a running workload.")
+            }
+            SetDefaultWorkload::MissingWorkload => help(
+                "add the workload with `World::add_workload` before making it the default one.",
+            ),
+        }
+    }
+}
+
+impl Diagnostic for Run {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            Run::GetStorage(err) => err.code(),
+            Run::Custom(_) => code("shipyard::run::custom"),
+        }
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            Run::GetStorage(err) => err.help(),
+            Run::Custom(_) => help(
+                "the system returned an error, it can be retrieved with `downcast` or `downcast_ref`.",
+            ),
+        }
+    }
+}
+
+impl Diagnostic for RunWorkload {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            RunWorkload::Scheduler => code("shipyard::run_workload::scheduler"),
+            RunWorkload::Run((_, err)) => err.code(),
+            RunWorkload::Runs(_) => code("shipyard::run_workload::runs"),
+            RunWorkload::MissingWorkload => code("shipyard::run_workload::missing_workload"),
+        }
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            RunWorkload::Scheduler => help(
+                "workloads can't be run from inside a running workload, \
+                add the systems to the outer workload instead.",
+            ),
+            RunWorkload::Run((_, err)) => err.help(),
+            RunWorkload::Runs(_) => {
+                help("systems of the same batch failed independently, each error is listed below.")
+            }
+            RunWorkload::MissingWorkload => help(
+                "add the workload with `World::add_workload` before running it, \
+                workloads built from a function are named after it.",
+            ),
+        }
+    }
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        match self {
+            RunWorkload::Runs(runs) => Some(Box::new(
+                runs.iter().map(|(_, run)| -> &dyn Diagnostic { run }),
+            )),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,474 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::atomic_refcell::{ARef, ARefMut, ExclusiveBorrow, SharedBorrow};
+use crate::borrow::{Borrow, BorrowInfo, Mutability};
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::iter::{AbstractMut, IntoAbstract};
+use crate::scheduler::TypeInfo;
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::type_id::TypeId;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+/// Adapter over data owned outside of shipyard, like a physics engine's body array or a GPU-mapped buffer.
+///
+/// Once added with [`World::add_external_storage`], the data can be borrowed with [`ExternalView`] and [`ExternalViewMut`].
+/// These views can be iterated alone or in tuples with other views and are scheduled like any other storage.\
+/// The data is never copied into a [`SparseSet`].
+///
+/// `ids()[i]` is the entity owning `data()[i]`, both slices have to be the same length.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, EntityId, ExternalStorage, ExternalViewMut, IntoIter, View, World};
+///
+/// #[derive(Component)]
+/// struct Mass(f32);
+///
+/// struct Bodies {
+///     owners: Vec<EntityId>,
+///     velocities: Vec<[f32; 2]>,
+/// }
+///
+/// impl ExternalStorage for Bodies {
+///     type Item = [f32; 2];
+///
+///     fn ids(&self) -> &[EntityId] {
+///         &self.owners
+///     }
+///     fn data(&self) -> &[[f32; 2]] {
+///         &self.velocities
+///     }
+///     fn data_mut(&mut self) -> &mut [[f32; 2]] {
+///         &mut self.velocities
+///     }
+///     fn index_of(&self, entity: EntityId) -> Option<usize> {
+///         self.owners.iter().position(|&owner| owner == entity)
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let entity = world.add_entity((Mass(2.0),));
+///
+/// world.add_external_storage(Bodies {
+///     owners: vec![entity],
+///     velocities: vec![[4.0, 0.0]],
+/// });
+///
+/// world.run(|mut bodies: ExternalViewMut<Bodies>, masses: View<Mass>| {
+///     for (velocity, mass) in (&mut bodies, &masses).iter() {
+///         velocity[0] /= mass.0;
+///     }
+/// });
+/// ```
+///
+/// [`World::add_external_storage`]: crate::World::add_external_storage
+/// [`SparseSet`]: crate::SparseSet
+pub trait ExternalStorage: Send + Sync + 'static {
+    /// Type of the elements.
+    type Item: 'static;
+
+    /// Returns the entity owning each element.
+    fn ids(&self) -> &[EntityId];
+    /// Returns the elements.
+    fn data(&self) -> &[Self::Item];
+    /// Returns the elements.
+    fn data_mut(&mut self) -> &mut [Self::Item];
+    /// Returns the index of `entity`'s element.
+    fn index_of(&self, entity: EntityId) -> Option<usize>;
+    /// Called when `entity` is deleted from the `World`.
+    #[inline]
+    #[allow(unused_variables)]
+    fn on_delete(&mut self, entity: EntityId) {}
+    /// Called when the `World` is cleared.
+    #[inline]
+    fn on_clear(&mut self) {}
+}
+
+/// Storage holding an [`ExternalStorage`].
+pub(crate) struct External<S>(pub(crate) S);
+
+impl<S: ExternalStorage> Storage for External<S> {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.0.on_delete(entity);
+    }
+    #[inline]
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        self.0.on_clear();
+    }
+    fn name(&self) -> Cow<'static, str> {
+        type_name::<S>().into()
+    }
+    fn is_empty(&self) -> bool {
+        self.0.ids().is_empty()
+    }
+}
+
+/// Shared view over an [`ExternalStorage`].
+pub struct ExternalView<'a, S: ExternalStorage> {
+    storage: &'a S,
+    _all_borrow: Option<SharedBorrow<'a>>,
+    _borrow: SharedBorrow<'a>,
+}
+
+impl<S: ExternalStorage> ExternalView<'_, S> {
+    /// Returns `entity`'s element.
+    pub fn get(&self, entity: EntityId) -> Option<&S::Item> {
+        let index = self.storage.index_of(entity)?;
+
+        self.storage.data().get(index)
+    }
+}
+
+impl<S: ExternalStorage> Deref for ExternalView<'_, S> {
+    type Target = S;
+
+    #[inline]
+    fn deref(&self) -> &S {
+        self.storage
+    }
+}
+
+/// Exclusive view over an [`ExternalStorage`].
+pub struct ExternalViewMut<'a, S: ExternalStorage> {
+    storage: &'a mut S,
+    _all_borrow: Option<SharedBorrow<'a>>,
+    _borrow: ExclusiveBorrow<'a>,
+}
+
+impl<S: ExternalStorage> ExternalViewMut<'_, S> {
+    /// Returns `entity`'s element.
+    pub fn get(&self, entity: EntityId) -> Option<&S::Item> {
+        let index = self.storage.index_of(entity)?;
+
+        self.storage.data().get(index)
+    }
+    /// Returns `entity`'s element.
+    pub fn get_mut(&mut self, entity: EntityId) -> Option<&mut S::Item> {
+        let index = self.storage.index_of(entity)?;
+
+        self.storage.data_mut().get_mut(index)
+    }
+}
+
+impl<S: ExternalStorage> Deref for ExternalViewMut<'_, S> {
+    type Target = S;
+
+    #[inline]
+    fn deref(&self) -> &S {
+        self.storage
+    }
+}
+
+impl<S: ExternalStorage> DerefMut for ExternalViewMut<'_, S> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut S {
+        self.storage
+    }
+}
+
+impl<S: ExternalStorage> Borrow for ExternalView<'_, S> {
+    type View<'a> = ExternalView<'a, S>;
+
+    #[inline]
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        _last_run: Option<TrackingTimestamp>,
+        _current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = all_storages.custom_storage::<External<S>>()?;
+
+        let (storage, borrow) = unsafe { ARef::destructure(view) };
+
+        Ok(ExternalView {
+            storage: &storage.0,
+            _all_borrow: all_borrow,
+            _borrow: borrow,
+        })
+    }
+}
+
+impl<S: ExternalStorage> Borrow for ExternalViewMut<'_, S> {
+    type View<'a> = ExternalViewMut<'a, S>;
+
+    #[inline]
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        _last_run: Option<TrackingTimestamp>,
+        _current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = all_storages.custom_storage_mut::<External<S>>()?;
+
+        let (storage, borrow) = unsafe { ARefMut::destructure(view) };
+
+        Ok(ExternalViewMut {
+            storage: &mut storage.0,
+            _all_borrow: all_borrow,
+            _borrow: borrow,
+        })
+    }
+}
+
+unsafe impl<S: ExternalStorage> BorrowInfo for ExternalView<'_, S> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        info.push(TypeInfo {
+            name: type_name::<S>().into(),
+            mutability: Mutability::Shared,
+            storage_id: StorageId::of::<External<S>>(),
+            thread_safe: true,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
+
+unsafe impl<S: ExternalStorage> BorrowInfo for ExternalViewMut<'_, S> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        info.push(TypeInfo {
+            name: type_name::<S>().into(),
+            mutability: Mutability::Exclusive,
+            storage_id: StorageId::of::<External<S>>(),
+            thread_safe: true,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
+
+/// Iteration window over an [`ExternalStorage`].
+pub struct ExternalWindow<'a, S: ExternalStorage> {
+    storage: &'a S,
+    ids: *const EntityId,
+    data: *const S::Item,
+    len: usize,
+}
+
+unsafe impl<S: ExternalStorage> Send for ExternalWindow<'_, S> where S::Item: Sync {}
+
+impl<S: ExternalStorage> Clone for ExternalWindow<'_, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ExternalWindow {
+            storage: self.storage,
+            ids: self.ids,
+            data: self.data,
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, S: ExternalStorage> ExternalWindow<'a, S> {
+    #[track_caller]
+    fn new(storage: &'a S) -> Self {
+        let ids = storage.ids();
+        let data = storage.data();
+
+        assert_eq!(
+            ids.len(),
+            data.len(),
+            "{}'s ids and data don't have the same length.",
+            type_name::<S>()
+        );
+
+        ExternalWindow {
+            storage,
+            ids: ids.as_ptr(),
+            data: data.as_ptr(),
+            len: ids.len(),
+        }
+    }
+}
+
+/// Exclusive iteration window over an [`ExternalStorage`].
+pub struct ExternalWindowMut<'a, S: ExternalStorage> {
+    storage: *const S,
+    ids: *const EntityId,
+    data: *mut S::Item,
+    len: usize,
+    _phantom: PhantomData<&'a mut S>,
+}
+
+unsafe impl<S: ExternalStorage> Send for ExternalWindowMut<'_, S> where S::Item: Send {}
+
+impl<S: ExternalStorage> Clone for ExternalWindowMut<'_, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ExternalWindowMut {
+            storage: self.storage,
+            ids: self.ids,
+            data: self.data,
+            len: self.len,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, S: ExternalStorage> ExternalWindowMut<'a, S> {
+    #[track_caller]
+    fn new(storage: &'a mut S) -> Self {
+        let data = storage.data_mut();
+        let data_len = data.len();
+        let data = data.as_mut_ptr();
+        let ids = storage.ids();
+
+        assert_eq!(
+            ids.len(),
+            data_len,
+            "{}'s ids and data don't have the same length.",
+            type_name::<S>()
+        );
+
+        ExternalWindowMut {
+            ids: ids.as_ptr(),
+            len: ids.len(),
+            storage,
+            data,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, S: ExternalStorage> AbstractMut for ExternalWindow<'a, S> {
+    type Out = &'a S::Item;
+    type Index = usize;
+
+    #[inline]
+    unsafe fn get_data(&self, index: usize) -> Self::Out {
+        &*self.data.add(index)
+    }
+    #[inline]
+    unsafe fn get_datas(&self, index: Self::Index) -> Self::Out {
+        &*self.data.add(index)
+    }
+    #[inline]
+    fn indices_of(&self, entity_id: EntityId, _: usize, _: u16) -> Option<Self::Index> {
+        self.storage
+            .index_of(entity_id)
+            .filter(|&index| index < self.len)
+    }
+    #[inline]
+    unsafe fn indices_of_unchecked(&self, entity_id: EntityId, _: usize, _: u16) -> Self::Index {
+        self.indices_of(entity_id, 0, 0).unwrap()
+    }
+    #[inline]
+    unsafe fn get_id(&self, index: usize) -> EntityId {
+        *self.ids.add(index)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, S: ExternalStorage> AbstractMut for ExternalWindowMut<'a, S> {
+    type Out = &'a mut S::Item;
+    type Index = usize;
+
+    #[inline]
+    unsafe fn get_data(&self, index: usize) -> Self::Out {
+        &mut *self.data.add(index)
+    }
+    #[inline]
+    unsafe fn get_datas(&self, index: Self::Index) -> Self::Out {
+        &mut *self.data.add(index)
+    }
+    #[inline]
+    fn indices_of(&self, entity_id: EntityId, _: usize, _: u16) -> Option<Self::Index> {
+        // SAFE the storage outlives the window and only `index_of` is called, it doesn't access the data
+        unsafe { &*self.storage }
+            .index_of(entity_id)
+            .filter(|&index| index < self.len)
+    }
+    #[inline]
+    unsafe fn indices_of_unchecked(&self, entity_id: EntityId, _: usize, _: u16) -> Self::Index {
+        self.indices_of(entity_id, 0, 0).unwrap()
+    }
+    #[inline]
+    unsafe fn get_id(&self, index: usize) -> EntityId {
+        *self.ids.add(index)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a: 'b, 'b, S: ExternalStorage> IntoAbstract for &'b ExternalView<'a, S> {
+    type AbsView = ExternalWindow<'b, S>;
+
+    #[inline]
+    fn into_abstract(self) -> Self::AbsView {
+        ExternalWindow::new(self.storage)
+    }
+    #[inline]
+    fn len(&self) -> Option<usize> {
+        Some(self.storage.ids().len())
+    }
+    #[inline]
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<External<S>>()
+    }
+    #[inline]
+    fn inner_type_id(&self) -> TypeId {
+        TypeId::of::<S::Item>()
+    }
+    #[inline]
+    fn dense(&self) -> *const EntityId {
+        self.storage.ids().as_ptr()
+    }
+}
+
+impl<'a: 'b, 'b, S: ExternalStorage> IntoAbstract for &'b ExternalViewMut<'a, S> {
+    type AbsView = ExternalWindow<'b, S>;
+
+    #[inline]
+    fn into_abstract(self) -> Self::AbsView {
+        ExternalWindow::new(&*self.storage)
+    }
+    #[inline]
+    fn len(&self) -> Option<usize> {
+        Some(self.storage.ids().len())
+    }
+    #[inline]
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<External<S>>()
+    }
+    #[inline]
+    fn inner_type_id(&self) -> TypeId {
+        TypeId::of::<S::Item>()
+    }
+    #[inline]
+    fn dense(&self) -> *const EntityId {
+        self.storage.ids().as_ptr()
+    }
+}
+
+impl<'a: 'b, 'b, S: ExternalStorage> IntoAbstract for &'b mut ExternalViewMut<'a, S> {
+    type AbsView = ExternalWindowMut<'b, S>;
+
+    #[inline]
+    fn into_abstract(self) -> Self::AbsView {
+        ExternalWindowMut::new(&mut *self.storage)
+    }
+    #[inline]
+    fn len(&self) -> Option<usize> {
+        Some(self.storage.ids().len())
+    }
+    #[inline]
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<External<S>>()
+    }
+    #[inline]
+    fn inner_type_id(&self) -> TypeId {
+        TypeId::of::<S::Item>()
+    }
+    #[inline]
+    fn dense(&self) -> *const EntityId {
+        self.storage.ids().as_ptr()
+    }
+}
@@ -0,0 +1,185 @@
+use crate::component::{Component, Unique};
+use crate::entity_id::EntityId;
+use crate::sparse_set::SparseSet;
+use crate::storage::StorageId;
+use crate::tracking::{Tracking, TrackingTimestamp};
+use crate::views::{UniqueViewMut, View};
+use crate::ShipHashMap;
+
+/// Structure receiving components copied out of a [`World`](crate::World), usually owned by a renderer.
+///
+/// Implement it for each component type the structure stores, then let [`extract`] keep it up to date.
+pub trait ExtractTarget<T> {
+    /// Called with each component inserted or modified since the last extraction into this structure.
+    fn write(&mut self, entity: EntityId, component: &T);
+    /// Called with each entity that lost its component since the last extraction into this structure.
+    fn remove(&mut self, entity: EntityId);
+    /// Removes all components of type `T`, called before copying all of them when changes can't be tracked.
+    fn clear(&mut self);
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone, S: core::hash::BuildHasher> ExtractTarget<T>
+    for std::collections::HashMap<EntityId, T, S>
+{
+    fn write(&mut self, entity: EntityId, component: &T) {
+        self.insert(entity, component.clone());
+    }
+    fn remove(&mut self, entity: EntityId) {
+        std::collections::HashMap::remove(self, &entity);
+    }
+    fn clear(&mut self) {
+        std::collections::HashMap::clear(self);
+    }
+}
+
+/// Two copies of a user structure, one is read (front) while the other is written by extractions (back).
+///
+/// Each copy remembers when each component type was last extracted to it, only the components inserted, modified, removed or deleted since are copied.\
+/// Components not tracking insertion and modification are copied entirely at each extraction.\
+/// Removals and deletions are only seen if the component tracks them and they are not cleared between two extractions into the same copy.
+///
+/// ### Example
+/// ```
+/// use shipyard::{extract, swap_buffers, Component, DoubleBuffer, EntityId, UniqueView, Workload, World};
+/// use std::collections::HashMap;
+///
+/// #[derive(Component, Clone, Copy, PartialEq, Debug)]
+/// #[track(All)]
+/// struct Position(f32, f32);
+///
+/// type Positions = HashMap<EntityId, Position>;
+///
+/// let mut world = World::new();
+/// world.add_unique(DoubleBuffer::<Positions>::default());
+///
+/// let entity = world.add_entity(Position(0.0, 0.0));
+///
+/// Workload::new("extract")
+///     .with_system(extract::<Position, Positions>)
+///     .with_system(swap_buffers::<Positions>)
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// world.run_workload("extract").unwrap();
+///
+/// let buffers = world.borrow::<UniqueView<DoubleBuffer<Positions>>>().unwrap();
+/// assert_eq!(buffers.front()[&entity], Position(0.0, 0.0));
+/// ```
+#[derive(Debug)]
+pub struct DoubleBuffer<B> {
+    buffers: [B; 2],
+    last_extraction: [ShipHashMap<StorageId, TrackingTimestamp>; 2],
+    front: usize,
+}
+
+impl<B: Send + Sync + 'static> Unique for DoubleBuffer<B> {}
+
+impl<B> DoubleBuffer<B> {
+    /// Creates a double buffer from its two copies, `front` is the one read first.
+    pub fn new(front: B, back: B) -> DoubleBuffer<B> {
+        DoubleBuffer {
+            buffers: [front, back],
+            last_extraction: [ShipHashMap::default(), ShipHashMap::default()],
+            front: 0,
+        }
+    }
+    /// Returns the copy holding the latest extraction.
+    pub fn front(&self) -> &B {
+        &self.buffers[self.front]
+    }
+    /// Returns the copy holding the latest extraction.
+    pub fn front_mut(&mut self) -> &mut B {
+        &mut self.buffers[self.front]
+    }
+    /// Returns the copy the next extraction will write to.
+    pub fn back(&self) -> &B {
+        &self.buffers[1 - self.front]
+    }
+    /// Returns the front copy to read and the back copy to write at the same time.
+    pub fn split_mut(&mut self) -> (&B, &mut B) {
+        let [first, second] = &mut self.buffers;
+
+        if self.front == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+    /// Exchanges the front and back copies.
+    pub fn swap(&mut self) {
+        self.front = 1 - self.front;
+    }
+    /// Copies the changes of `components` since their last extraction into the back copy.\
+    /// Call [`DoubleBuffer::swap`] once all components are extracted to make them readable from the front copy.
+    pub fn extract<T: Component, Track: Tracking>(&mut self, components: &View<'_, T, Track>)
+    where
+        B: ExtractTarget<T>,
+    {
+        let back = 1 - self.front;
+        let current = components.current;
+        let target = &mut self.buffers[back];
+
+        let last = match self.last_extraction[back].insert(StorageId::of::<SparseSet<T>>(), current)
+        {
+            Some(last)
+                if components.is_tracking_insertion() && components.is_tracking_modification() =>
+            {
+                last
+            }
+            _ => {
+                target.clear();
+                for (&entity, component) in components.dense.iter().zip(components.data.iter()) {
+                    target.write(entity, component);
+                }
+
+                return;
+            }
+        };
+
+        for &(entity, timestamp) in &components.removal_data {
+            if timestamp.is_within(last, current) {
+                target.remove(entity);
+            }
+        }
+        for (entity, timestamp, _) in &components.deletion_data {
+            if timestamp.is_within(last, current) {
+                target.remove(*entity);
+            }
+        }
+
+        for (((&entity, component), insertion), modification) in components
+            .dense
+            .iter()
+            .zip(components.data.iter())
+            .zip(&components.insertion_data)
+            .zip(&components.modification_data)
+        {
+            if insertion.is_within(last, current) || modification.is_within(last, current) {
+                target.write(entity, component);
+            }
+        }
+    }
+}
+
+impl<B: Default> Default for DoubleBuffer<B> {
+    fn default() -> Self {
+        DoubleBuffer::new(B::default(), B::default())
+    }
+}
+
+/// System copying the changes of `T` into the back copy of [`DoubleBuffer<B>`], see [`DoubleBuffer::extract`].
+///
+/// Extractions into different buffers only borrow shared storages and run in parallel.\
+/// Add them in their own workload, or at the end of one, followed by [`swap_buffers`] to get a sync point between simulation and rendering.
+pub fn extract<T: Component + Send + Sync, B: ExtractTarget<T> + Send + Sync + 'static>(
+    components: View<'_, T>,
+    mut buffers: UniqueViewMut<'_, DoubleBuffer<B>>,
+) {
+    buffers.extract(&components);
+}
+
+/// System swapping the copies of [`DoubleBuffer<B>`], the latest extraction becomes the front copy.
+pub fn swap_buffers<B: Send + Sync + 'static>(mut buffers: UniqueViewMut<'_, DoubleBuffer<B>>) {
+    buffers.swap();
+}
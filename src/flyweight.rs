@@ -0,0 +1,476 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::atomic_refcell::{ARef, ARefMut, ExclusiveBorrow, SharedBorrow};
+use crate::borrow::{Borrow, BorrowInfo, Mutability};
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::iter::{AbstractMut, IntoAbstract};
+use crate::memory_usage::StorageMemoryUsage;
+use crate::scheduler::TypeInfo;
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::type_id::TypeId;
+use crate::ShipHashMap;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::mem::size_of;
+use core::ops::{Deref, DerefMut};
+
+/// Handle to a value of a [`FlyweightStorage`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FlyweightHandle {
+    index: u32,
+    generation: u32,
+}
+
+struct Slot<T> {
+    value: Option<T>,
+    references: usize,
+    generation: u32,
+}
+
+/// Storage where entities reference shared values instead of owning a copy, like a mesh or a material.
+///
+/// Each entity only stores a [`FlyweightHandle`], a value is dropped when the last entity referencing it is removed.\
+/// Borrowed with [`FlyweightView`] and [`FlyweightViewMut`], iterating them yields `&T`.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, FlyweightView, FlyweightViewMut, IntoIter, View, World};
+///
+/// struct Mesh(Vec<[f32; 3]>);
+///
+/// #[derive(Component)]
+/// struct Visible;
+///
+/// let mut world = World::new();
+/// let entity0 = world.add_entity((Visible,));
+/// let entity1 = world.add_entity((Visible,));
+///
+/// world.run(|mut meshes: FlyweightViewMut<Mesh>| {
+///     let cube = meshes.share(Mesh(vec![[0.0; 3]; 8]));
+///
+///     meshes.insert(entity0, cube);
+///     meshes.insert(entity1, cube);
+///
+///     assert_eq!(meshes.value_count(), 1);
+/// });
+///
+/// world.run(|meshes: FlyweightView<Mesh>, visibles: View<Visible>| {
+///     for (mesh, _) in (&meshes, &visibles).iter() {
+///         assert_eq!(mesh.0.len(), 8);
+///     }
+/// });
+/// ```
+pub struct FlyweightStorage<T> {
+    values: Vec<Slot<T>>,
+    free: Vec<u32>,
+    entities: Vec<EntityId>,
+    handles: Vec<FlyweightHandle>,
+    indices: ShipHashMap<EntityId, usize>,
+}
+
+impl<T> Default for FlyweightStorage<T> {
+    fn default() -> Self {
+        FlyweightStorage {
+            values: Vec::new(),
+            free: Vec::new(),
+            entities: Vec::new(),
+            handles: Vec::new(),
+            indices: ShipHashMap::default(),
+        }
+    }
+}
+
+impl<T> FlyweightStorage<T> {
+    /// Creates an empty storage.
+    pub fn new() -> FlyweightStorage<T> {
+        FlyweightStorage::default()
+    }
+    /// Adds a value entities can reference with [`FlyweightStorage::insert`].\
+    /// The value is kept until an entity references it and the last one is removed, or the storage is cleared.
+    pub fn share(&mut self, value: T) -> FlyweightHandle {
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.values[index as usize];
+                slot.value = Some(value);
+
+                FlyweightHandle {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                let index = self.values.len() as u32;
+                self.values.push(Slot {
+                    value: Some(value),
+                    references: 0,
+                    generation: 0,
+                });
+
+                FlyweightHandle {
+                    index,
+                    generation: 0,
+                }
+            }
+        }
+    }
+    /// Makes `entity` reference the value of `handle`.\
+    /// Returns `false` if `handle`'s value was dropped.
+    pub fn insert(&mut self, entity: EntityId, handle: FlyweightHandle) -> bool {
+        match self.values.get_mut(handle.index as usize) {
+            Some(slot) if slot.generation == handle.generation && slot.value.is_some() => {
+                slot.references += 1;
+            }
+            _ => return false,
+        }
+
+        match self.indices.get(&entity) {
+            Some(&index) => {
+                let previous = core::mem::replace(&mut self.handles[index], handle);
+                self.release(previous);
+            }
+            None => {
+                self.indices.insert(entity, self.entities.len());
+                self.entities.push(entity);
+                self.handles.push(handle);
+            }
+        }
+
+        true
+    }
+    /// Shares `value` and makes `entity` reference it.
+    pub fn insert_value(&mut self, entity: EntityId, value: T) -> FlyweightHandle {
+        let handle = self.share(value);
+        self.insert(entity, handle);
+
+        handle
+    }
+    /// Removes `entity`'s reference, the value is dropped if no other entity references it.\
+    /// Returns `false` if `entity` didn't reference any value.
+    pub fn remove(&mut self, entity: EntityId) -> bool {
+        let index = match self.indices.remove(&entity) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        self.entities.swap_remove(index);
+        let handle = self.handles.swap_remove(index);
+
+        if let Some(&moved) = self.entities.get(index) {
+            self.indices.insert(moved, index);
+        }
+
+        self.release(handle);
+
+        true
+    }
+    fn release(&mut self, handle: FlyweightHandle) {
+        let slot = &mut self.values[handle.index as usize];
+        slot.references -= 1;
+
+        if slot.references == 0 {
+            slot.value = None;
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free.push(handle.index);
+        }
+    }
+    /// Returns the value `entity` references.
+    pub fn get(&self, entity: EntityId) -> Option<&T> {
+        self.value(self.handle(entity)?)
+    }
+    /// Returns the handle of the value `entity` references.
+    pub fn handle(&self, entity: EntityId) -> Option<FlyweightHandle> {
+        self.indices.get(&entity).map(|&index| self.handles[index])
+    }
+    /// Returns the value of `handle`.
+    pub fn value(&self, handle: FlyweightHandle) -> Option<&T> {
+        self.values
+            .get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.value.as_ref())
+    }
+    /// Returns the value of `handle`, modifying it affects all entities referencing it.
+    pub fn value_mut(&mut self, handle: FlyweightHandle) -> Option<&mut T> {
+        self.values
+            .get_mut(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.value.as_mut())
+    }
+    /// Returns the number of entities referencing the value of `handle`.
+    pub fn references(&self, handle: FlyweightHandle) -> usize {
+        self.values
+            .get(handle.index as usize)
+            .filter(|slot| slot.generation == handle.generation)
+            .map_or(0, |slot| slot.references)
+    }
+    /// Returns the entities referencing a value.
+    pub fn ids(&self) -> &[EntityId] {
+        &self.entities
+    }
+    /// Returns the number of entities referencing a value.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+    /// Returns `true` if no entity references a value.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+    /// Returns the number of values stored.
+    pub fn value_count(&self) -> usize {
+        self.values.len() - self.free.len()
+    }
+    /// Removes all references and values.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.free.clear();
+        self.entities.clear();
+        self.handles.clear();
+        self.indices.clear();
+    }
+}
+
+impl<T: Send + Sync + 'static> Storage for FlyweightStorage<T> {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.remove(entity);
+    }
+    #[inline]
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        FlyweightStorage::clear(self);
+    }
+    fn memory_usage(&self) -> Option<StorageMemoryUsage> {
+        let per_entity =
+            size_of::<EntityId>() * 2 + size_of::<FlyweightHandle>() + size_of::<usize>();
+
+        Some(StorageMemoryUsage {
+            storage_name: type_name::<Self>().into(),
+            used_memory_bytes: self.value_count() * size_of::<Slot<T>>()
+                + self.entities.len() * per_entity
+                + size_of::<Self>(),
+            allocated_memory_bytes: self.values.capacity() * size_of::<Slot<T>>()
+                + self.free.capacity() * size_of::<u32>()
+                + self.entities.capacity() * per_entity
+                + size_of::<Self>(),
+            component_count: self.entities.len(),
+        })
+    }
+    fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+    fn len(&self) -> Option<usize> {
+        Some(self.entities.len())
+    }
+}
+
+/// Shared view over a [`FlyweightStorage`].
+pub struct FlyweightView<'a, T> {
+    storage: &'a FlyweightStorage<T>,
+    _all_borrow: Option<SharedBorrow<'a>>,
+    _borrow: SharedBorrow<'a>,
+}
+
+impl<T> Deref for FlyweightView<'_, T> {
+    type Target = FlyweightStorage<T>;
+
+    #[inline]
+    fn deref(&self) -> &FlyweightStorage<T> {
+        self.storage
+    }
+}
+
+/// Exclusive view over a [`FlyweightStorage`].
+pub struct FlyweightViewMut<'a, T> {
+    storage: &'a mut FlyweightStorage<T>,
+    _all_borrow: Option<SharedBorrow<'a>>,
+    _borrow: ExclusiveBorrow<'a>,
+}
+
+impl<T> Deref for FlyweightViewMut<'_, T> {
+    type Target = FlyweightStorage<T>;
+
+    #[inline]
+    fn deref(&self) -> &FlyweightStorage<T> {
+        self.storage
+    }
+}
+
+impl<T> DerefMut for FlyweightViewMut<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut FlyweightStorage<T> {
+        self.storage
+    }
+}
+
+impl<T: Send + Sync + 'static> Borrow for FlyweightView<'_, T> {
+    type View<'a> = FlyweightView<'a, T>;
+
+    #[inline]
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        _last_run: Option<TrackingTimestamp>,
+        _current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = all_storages.custom_storage_or_insert(FlyweightStorage::<T>::new)?;
+
+        let (storage, borrow) = unsafe { ARef::destructure(view) };
+
+        Ok(FlyweightView {
+            storage,
+            _all_borrow: all_borrow,
+            _borrow: borrow,
+        })
+    }
+}
+
+impl<T: Send + Sync + 'static> Borrow for FlyweightViewMut<'_, T> {
+    type View<'a> = FlyweightViewMut<'a, T>;
+
+    #[inline]
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        _last_run: Option<TrackingTimestamp>,
+        _current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = all_storages.custom_storage_or_insert_mut(FlyweightStorage::<T>::new)?;
+
+        let (storage, borrow) = unsafe { ARefMut::destructure(view) };
+
+        Ok(FlyweightViewMut {
+            storage,
+            _all_borrow: all_borrow,
+            _borrow: borrow,
+        })
+    }
+}
+
+unsafe impl<T: Send + Sync + 'static> BorrowInfo for FlyweightView<'_, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        info.push(TypeInfo {
+            name: type_name::<FlyweightStorage<T>>().into(),
+            mutability: Mutability::Shared,
+            storage_id: StorageId::of::<FlyweightStorage<T>>(),
+            thread_safe: true,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
+
+unsafe impl<T: Send + Sync + 'static> BorrowInfo for FlyweightViewMut<'_, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        info.push(TypeInfo {
+            name: type_name::<FlyweightStorage<T>>().into(),
+            mutability: Mutability::Exclusive,
+            storage_id: StorageId::of::<FlyweightStorage<T>>(),
+            thread_safe: true,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
+
+/// Iteration window over a [`FlyweightStorage`].
+pub struct FlyweightWindow<'a, T> {
+    storage: &'a FlyweightStorage<T>,
+}
+
+impl<T> Clone for FlyweightWindow<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        FlyweightWindow {
+            storage: self.storage,
+        }
+    }
+}
+
+impl<'a, T> AbstractMut for FlyweightWindow<'a, T> {
+    type Out = &'a T;
+    type Index = usize;
+
+    #[inline]
+    unsafe fn get_data(&self, index: usize) -> Self::Out {
+        let handle = self.storage.handles.get_unchecked(index);
+
+        // referenced slots always have a value
+        self.storage
+            .values
+            .get_unchecked(handle.index as usize)
+            .value
+            .as_ref()
+            .unwrap_unchecked()
+    }
+    #[inline]
+    unsafe fn get_datas(&self, index: Self::Index) -> Self::Out {
+        self.get_data(index)
+    }
+    #[inline]
+    fn indices_of(&self, entity_id: EntityId, _: usize, _: u16) -> Option<Self::Index> {
+        self.storage.indices.get(&entity_id).copied()
+    }
+    #[inline]
+    unsafe fn indices_of_unchecked(&self, entity_id: EntityId, _: usize, _: u16) -> Self::Index {
+        self.storage.indices[&entity_id]
+    }
+    #[inline]
+    unsafe fn get_id(&self, index: usize) -> EntityId {
+        *self.storage.entities.get_unchecked(index)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        self.storage.entities.len()
+    }
+}
+
+impl<'a: 'b, 'b, T: 'static> IntoAbstract for &'b FlyweightView<'a, T> {
+    type AbsView = FlyweightWindow<'b, T>;
+
+    #[inline]
+    fn into_abstract(self) -> Self::AbsView {
+        FlyweightWindow {
+            storage: self.storage,
+        }
+    }
+    #[inline]
+    fn len(&self) -> Option<usize> {
+        Some(self.storage.len())
+    }
+    #[inline]
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<FlyweightStorage<T>>()
+    }
+    #[inline]
+    fn inner_type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+    #[inline]
+    fn dense(&self) -> *const EntityId {
+        self.storage.entities.as_ptr()
+    }
+}
+
+impl<'a: 'b, 'b, T: 'static> IntoAbstract for &'b FlyweightViewMut<'a, T> {
+    type AbsView = FlyweightWindow<'b, T>;
+
+    #[inline]
+    fn into_abstract(self) -> Self::AbsView {
+        FlyweightWindow {
+            storage: &*self.storage,
+        }
+    }
+    #[inline]
+    fn len(&self) -> Option<usize> {
+        Some(self.storage.len())
+    }
+    #[inline]
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<FlyweightStorage<T>>()
+    }
+    #[inline]
+    fn inner_type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+    #[inline]
+    fn dense(&self) -> *const EntityId {
+        self.storage.entities.as_ptr()
+    }
+}
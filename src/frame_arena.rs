@@ -0,0 +1,254 @@
+use crate::component::Unique;
+use crate::views::UniqueViewMut;
+use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+use core::fmt;
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Alignment of the arena's memory block.
+const CHUNK_ALIGN: usize = 16;
+
+/// Vector allocated in a [`FrameArena`].
+pub type FrameVec<'a, T> = allocator_api2::vec::Vec<T, &'a FrameArena>;
+
+/// Bump allocator for scratch data that doesn't outlive a frame.
+///
+/// Allocating only moves an offset in a single memory block, freeing does nothing.\
+/// When a workload has a system borrowing the arena, the scheduler inserts a system running before all others to reset it.
+/// Allocations that don't fit in the block are served by the global allocator and the block grows on the next reset,
+/// after a few frames everything fits.
+///
+/// Allocations borrow the arena so they can't outlive the view and the reset can't invalidate them.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, FrameArena, IntoIter, UniqueView, View, Workload, World};
+///
+/// #[derive(Component)]
+/// struct Pos(f32);
+///
+/// fn sum(arena: UniqueView<FrameArena>, positions: View<Pos>) {
+///     let mut xs = arena.vec_with_capacity(positions.len());
+///     xs.extend(positions.iter().map(|pos| pos.0));
+///
+///     assert_eq!(xs.iter().sum::<f32>(), 3.0);
+/// }
+///
+/// let mut world = World::new();
+/// world.add_unique(FrameArena::with_capacity(1024));
+/// world.add_entity(Pos(1.0));
+/// world.add_entity(Pos(2.0));
+///
+/// Workload::new("").with_system(sum).add_to_world(&world).unwrap();
+///
+/// world.run_default_workload().unwrap();
+/// ```
+pub struct FrameArena {
+    chunk: NonNull<u8>,
+    capacity: usize,
+    offset: AtomicUsize,
+    overflow: AtomicUsize,
+}
+
+// SAFETY: the block is owned by the arena and the offset is only moved atomically
+unsafe impl Send for FrameArena {}
+// SAFETY: the block is owned by the arena and the offset is only moved atomically
+unsafe impl Sync for FrameArena {}
+
+impl Unique for FrameArena {}
+
+impl FrameArena {
+    /// Creates an empty arena, it'll grow to fit a frame's allocations after the first reset.
+    pub fn new() -> FrameArena {
+        FrameArena::with_capacity(0)
+    }
+    /// Creates an arena able to hold `capacity` bytes before falling back to the global allocator.
+    pub fn with_capacity(capacity: usize) -> FrameArena {
+        FrameArena {
+            chunk: allocate_chunk(capacity),
+            capacity,
+            offset: AtomicUsize::new(0),
+            overflow: AtomicUsize::new(0),
+        }
+    }
+    /// Returns the size of the arena's memory block in bytes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Returns the number of bytes allocated in the block since the last reset, padding included.
+    pub fn allocated(&self) -> usize {
+        self.offset.load(Ordering::Relaxed)
+    }
+    /// Returns the number of bytes served by the global allocator since the last reset.
+    pub fn overflow(&self) -> usize {
+        self.overflow.load(Ordering::Relaxed)
+    }
+    /// Returns a new empty vector allocated in the arena.
+    pub fn vec<T>(&self) -> FrameVec<'_, T> {
+        FrameVec::new_in(self)
+    }
+    /// Returns a new vector allocated in the arena with room for at least `capacity` elements.
+    pub fn vec_with_capacity<T>(&self, capacity: usize) -> FrameVec<'_, T> {
+        FrameVec::with_capacity_in(capacity, self)
+    }
+    /// Frees all allocations at once.\
+    /// If some allocations didn't fit last frame, the block grows to fit them.
+    pub fn reset(&mut self) {
+        let overflow = *self.overflow.get_mut();
+
+        if overflow > 0 {
+            let capacity = (self.capacity + overflow).next_power_of_two();
+
+            free_chunk(self.chunk, self.capacity);
+
+            self.chunk = allocate_chunk(capacity);
+            self.capacity = capacity;
+        }
+
+        *self.offset.get_mut() = 0;
+        *self.overflow.get_mut() = 0;
+    }
+    fn in_chunk(&self, ptr: NonNull<u8>) -> bool {
+        let start = self.chunk.as_ptr() as usize;
+        let ptr = ptr.as_ptr() as usize;
+
+        ptr >= start && ptr < start + self.capacity
+    }
+}
+
+impl Default for FrameArena {
+    fn default() -> Self {
+        FrameArena::new()
+    }
+}
+
+impl Drop for FrameArena {
+    fn drop(&mut self) {
+        free_chunk(self.chunk, self.capacity);
+    }
+}
+
+impl fmt::Debug for FrameArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameArena")
+            .field("capacity", &self.capacity)
+            .field("allocated", &self.allocated())
+            .field("overflow", &self.overflow())
+            .finish()
+    }
+}
+
+// SAFETY: blocks returned by `allocate` never overlap, they stay valid until the arena is reset or dropped
+// which requires all borrows of the arena, and so all allocations, to be gone
+unsafe impl Allocator for FrameArena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() == 0 {
+            return Global.allocate(layout);
+        }
+
+        let start = self.chunk.as_ptr() as usize;
+        let mut offset = self.offset.load(Ordering::Relaxed);
+
+        loop {
+            let Some(aligned) = (start + offset).checked_add(layout.align() - 1) else {
+                break;
+            };
+            let aligned = aligned & !(layout.align() - 1);
+            let end = aligned - start + layout.size();
+
+            if end > self.capacity {
+                break;
+            }
+
+            match self.offset.compare_exchange_weak(
+                offset,
+                end,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    // SAFETY: `aligned - start + layout.size()` is within the block
+                    let ptr = unsafe { self.chunk.as_ptr().add(aligned - start) };
+
+                    return Ok(NonNull::slice_from_raw_parts(
+                        // SAFETY: the block isn't null so neither is any address in it
+                        unsafe { NonNull::new_unchecked(ptr) },
+                        layout.size(),
+                    ));
+                }
+                Err(current) => offset = current,
+            }
+        }
+
+        self.overflow.fetch_add(layout.size(), Ordering::Relaxed);
+
+        Global.allocate(layout)
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 || !self.in_chunk(ptr) {
+            Global.deallocate(ptr, layout);
+        }
+    }
+}
+
+fn allocate_chunk(capacity: usize) -> NonNull<u8> {
+    if capacity == 0 {
+        return NonNull::dangling();
+    }
+
+    let layout = Layout::from_size_align(capacity, CHUNK_ALIGN).unwrap();
+
+    match Global.allocate(layout) {
+        Ok(chunk) => chunk.cast(),
+        Err(_) => alloc::alloc::handle_alloc_error(layout),
+    }
+}
+
+fn free_chunk(chunk: NonNull<u8>, capacity: usize) {
+    if capacity != 0 {
+        // SAFETY: the block was allocated by `allocate_chunk` with the same layout
+        unsafe {
+            Global.deallocate(
+                chunk,
+                Layout::from_size_align(capacity, CHUNK_ALIGN).unwrap(),
+            );
+        }
+    }
+}
+
+/// Resets the [`FrameArena`].\
+/// Inserted by the scheduler at the start of workloads using the arena.
+pub fn reset_frame_arena(mut arena: UniqueViewMut<FrameArena>) {
+    arena.reset();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bump_and_grow() {
+        let mut arena = FrameArena::with_capacity(64);
+
+        {
+            let mut a = arena.vec_with_capacity::<u64>(4);
+            a.extend([1, 2, 3, 4]);
+            let b = arena.vec_with_capacity::<u8>(1);
+
+            assert!(arena.in_chunk(NonNull::new(a.as_ptr() as *mut u8).unwrap()));
+            assert!(arena.in_chunk(NonNull::new(b.as_ptr() as *mut u8).unwrap()));
+            assert_eq!(arena.allocated(), 33);
+
+            let c = arena.vec_with_capacity::<u64>(8);
+            assert!(!arena.in_chunk(NonNull::new(c.as_ptr() as *mut u8).unwrap()));
+            assert_eq!(arena.overflow(), 64);
+            assert_eq!(a, [1, 2, 3, 4]);
+        }
+
+        arena.reset();
+
+        assert_eq!(arena.capacity(), 128);
+        assert_eq!(arena.allocated(), 0);
+        assert_eq!(arena.overflow(), 0);
+    }
+}
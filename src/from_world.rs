@@ -0,0 +1,50 @@
+use crate::world::World;
+
+/// Creates a value from the content of a `World`.
+///
+/// Used by [`World::init_unique`] to build uniques that depend on other uniques or storages.\
+/// All types implementing `Default` implement `FromWorld`.
+///
+/// ### Example
+/// ```
+/// use shipyard::{FromWorld, Unique, UniqueView, World};
+///
+/// #[derive(Unique)]
+/// struct Window {
+///     width: u32,
+/// }
+///
+/// #[derive(Unique)]
+/// struct Renderer {
+///     width: u32,
+/// }
+///
+/// impl FromWorld for Renderer {
+///     fn from_world(world: &World) -> Self {
+///         let window = world.borrow::<UniqueView<Window>>().unwrap();
+///
+///         Renderer {
+///             width: window.width,
+///         }
+///     }
+/// }
+///
+/// let world = World::new();
+/// world.add_unique(Window { width: 640 });
+/// world.init_unique::<Renderer>();
+///
+/// assert_eq!(world.borrow::<UniqueView<Renderer>>().unwrap().width, 640);
+/// ```
+///
+/// [`World::init_unique`]: crate::World::init_unique
+pub trait FromWorld {
+    /// Creates `Self` using data from `world`.
+    fn from_world(world: &World) -> Self;
+}
+
+impl<T: Default> FromWorld for T {
+    #[inline]
+    fn from_world(_: &World) -> T {
+        T::default()
+    }
+}
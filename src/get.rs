@@ -13,7 +13,8 @@ pub trait Get {
     type Out;
     /// Retrieve components of `entity`.
     ///
-    /// Multiple components can be queried at the same time using a tuple.
+    /// Multiple components can be queried at the same time using a tuple.\
+    /// Dead ids, like [`EntityId::dead`], fail without looking up the storage.
     ///
     /// ### Example:
     /// ```
@@ -39,6 +40,13 @@ impl<'a, T: Component> Get for &'a SparseSet<T> {
     type Out = &'a T;
 
     fn get(self, entity: EntityId) -> Result<Self::Out, error::MissingComponent> {
+        if entity.is_dead() {
+            return Err(error::MissingComponent {
+                id: entity,
+                name: type_name::<T>(),
+            });
+        }
+
         self.private_get(entity)
             .ok_or_else(|| error::MissingComponent {
                 id: entity,
@@ -52,6 +60,13 @@ impl<'a, 'b, T: Component, Track: Tracking> Get for &'b View<'a, T, Track> {
 
     #[inline]
     fn get(self, entity: EntityId) -> Result<Self::Out, error::MissingComponent> {
+        if entity.is_dead() {
+            return Err(error::MissingComponent {
+                id: entity,
+                name: type_name::<T>(),
+            });
+        }
+
         (**self)
             .private_get(entity)
             .ok_or_else(|| error::MissingComponent {
@@ -66,6 +81,13 @@ impl<'a, 'b, T: Component, Track: Tracking> Get for &'b ViewMut<'a, T, Track> {
 
     #[inline]
     fn get(self, entity: EntityId) -> Result<Self::Out, error::MissingComponent> {
+        if entity.is_dead() {
+            return Err(error::MissingComponent {
+                id: entity,
+                name: type_name::<T>(),
+            });
+        }
+
         (**self)
             .private_get(entity)
             .ok_or_else(|| error::MissingComponent {
@@ -80,6 +102,13 @@ impl<'a, 'b, T: Component, Track: Tracking> Get for &'b mut ViewMut<'a, T, Track
 
     #[inline]
     fn get(self, entity: EntityId) -> Result<Self::Out, error::MissingComponent> {
+        if entity.is_dead() {
+            return Err(error::MissingComponent {
+                id: entity,
+                name: type_name::<T>(),
+            });
+        }
+
         let index = self
             .index_of(entity)
             .ok_or_else(|| error::MissingComponent {
@@ -0,0 +1,137 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::component::{Component, Unique};
+use crate::entity_id::EntityId;
+use crate::sparse_set::SparseSet;
+use crate::tracking::TrackingTimestamp;
+use crate::views::{AllStoragesView, UniqueViewMut};
+use alloc::vec::Vec;
+use core::fmt;
+
+type ReleaseFn = fn(&AllStorages, TrackingTimestamp, TrackingTimestamp);
+
+/// Unique owning resources referenced by `H` components, like an audio mixer owning the sounds `SoundHandle`s point to.
+///
+/// Registered with [`AllStorages::add_handle_owner`], it is notified of the deleted `H` components by [`release_handles`].\
+/// Removed components are given back to the caller and are not notified.
+pub trait HandleOwner<H>: Unique + Send + Sync {
+    /// Called with all `H` components deleted since the previous run of [`release_handles`], never with an empty slice.
+    fn release(&mut self, released: &[(EntityId, &H)]);
+}
+
+/// Handle owners registered with [`AllStorages::add_handle_owner`] and when they were last notified.
+pub struct HandleOwners {
+    owners: Vec<(ReleaseFn, TrackingTimestamp)>,
+}
+
+impl Unique for HandleOwners {}
+
+impl HandleOwners {
+    /// Returns the number of registered handle owners.
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+    /// Returns `true` if no handle owner is registered.
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+}
+
+impl fmt::Debug for HandleOwners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HandleOwners")
+            .field("owners", &self.owners.len())
+            .finish()
+    }
+}
+
+impl AllStorages {
+    /// Adds `owner` as a unique and registers it to be notified of the deleted `H` components.\
+    /// Enables deletion tracking for `H`.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, EntityId, HandleOwner, Unique, UniqueView, World};
+    ///
+    /// #[derive(Component)]
+    /// struct SoundHandle(usize);
+    ///
+    /// #[derive(Unique, Default)]
+    /// struct Mixer {
+    ///     freed: Vec<usize>,
+    /// }
+    ///
+    /// impl HandleOwner<SoundHandle> for Mixer {
+    ///     fn release(&mut self, released: &[(EntityId, &SoundHandle)]) {
+    ///         self.freed.extend(released.iter().map(|(_, handle)| handle.0));
+    ///     }
+    /// }
+    ///
+    /// let world = World::new();
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    ///
+    /// all_storages.add_handle_owner::<SoundHandle, _>(Mixer::default());
+    ///
+    /// let entity = all_storages.add_entity(SoundHandle(3));
+    /// all_storages.delete_entity(entity);
+    /// all_storages.release_handles();
+    ///
+    /// assert_eq!(all_storages.borrow::<UniqueView<Mixer>>().unwrap().freed, [3]);
+    /// ```
+    pub fn add_handle_owner<H: Component + Send + Sync, O: HandleOwner<H>>(&mut self, owner: O) {
+        self.track_deletion::<H>();
+        self.add_unique(owner);
+
+        let current = self.get_current();
+        let entry = (release::<H, O> as ReleaseFn, current);
+
+        match self.borrow::<UniqueViewMut<'_, HandleOwners>>() {
+            Ok(mut owners) => owners.owners.push(entry),
+            Err(_) => self.add_unique(HandleOwners {
+                owners: alloc::vec![entry],
+            }),
+        }
+    }
+    /// Notifies all handle owners of the handles deleted since their last notification.
+    pub fn release_handles(&self) {
+        let mut owners = match self.borrow::<UniqueViewMut<'_, HandleOwners>>() {
+            Ok(owners) => owners,
+            Err(_) => return,
+        };
+
+        let current = self.get_current();
+        for (release, last) in &mut owners.owners {
+            (release)(self, *last, current);
+            *last = current;
+        }
+    }
+}
+
+/// System notifying handle owners of the deleted handles, see [`AllStorages::release_handles`].
+pub fn release_handles(all_storages: AllStoragesView<'_>) {
+    all_storages.release_handles();
+}
+
+fn release<H: Component + Send + Sync, O: HandleOwner<H>>(
+    all_storages: &AllStorages,
+    last: TrackingTimestamp,
+    current: TrackingTimestamp,
+) {
+    let (handles, mut owner) = match (
+        all_storages.custom_storage::<SparseSet<H>>(),
+        all_storages.borrow::<UniqueViewMut<'_, O>>(),
+    ) {
+        (Ok(handles), Ok(owner)) => (handles, owner),
+        _ => return,
+    };
+
+    let released = handles
+        .deletion_data
+        .iter()
+        .filter(|(_, timestamp, _)| timestamp.is_within(last, current))
+        .map(|(entity, _, handle)| (*entity, handle))
+        .collect::<Vec<_>>();
+
+    if !released.is_empty() {
+        owner.release(&released);
+    }
+}
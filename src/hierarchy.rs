@@ -0,0 +1,321 @@
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::get::Get;
+use crate::track;
+use crate::views::{EntitiesViewMut, ViewMut};
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+/// Links an entity to its parent.
+///
+/// Added and removed by [`Hierarchy`], the entity is listed in its parent's [`Children`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Parent {
+    entity: EntityId,
+}
+
+impl Parent {
+    /// Returns the parent entity.
+    #[inline]
+    pub fn get(&self) -> EntityId {
+        self.entity
+    }
+}
+
+impl Component for Parent {
+    type Tracking = track::Untracked;
+}
+
+/// Lists the children of an entity, in the order they were attached.
+///
+/// Added and removed by [`Hierarchy`], an entity without children doesn't have this component.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub struct Children {
+    entities: Vec<EntityId>,
+}
+
+impl Children {
+    /// Returns the children as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[EntityId] {
+        &self.entities
+    }
+}
+
+impl Deref for Children {
+    type Target = [EntityId];
+
+    #[inline]
+    fn deref(&self) -> &[EntityId] {
+        &self.entities
+    }
+}
+
+impl Component for Children {
+    type Tracking = track::Untracked;
+}
+
+/// Keeps [`Parent`] and [`Children`] in sync when modifying the hierarchy.
+///
+/// Implemented for `(EntitiesViewMut, ViewMut<Parent>, ViewMut<Children>)`.\
+/// To delete an entity and all its descendants use [`World::despawn_recursive`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{Children, EntitiesViewMut, Hierarchy, HierarchyIter, Parent, ViewMut, World};
+///
+/// let world = World::new();
+///
+/// let mut hierarchy = world
+///     .borrow::<(EntitiesViewMut, ViewMut<Parent>, ViewMut<Children>)>()
+///     .unwrap();
+///
+/// let root = hierarchy.0.add_entity((), ());
+/// let child = hierarchy.attach_new(root);
+/// let grand_child = hierarchy.attach_new(child);
+///
+/// assert_eq!((&hierarchy.1, &hierarchy.2).children(root), [child]);
+/// assert!((&hierarchy.1, &hierarchy.2)
+///     .ancestors(grand_child)
+///     .eq([child, root]));
+/// ```
+///
+/// [`World::despawn_recursive`]: crate::World::despawn_recursive
+pub trait Hierarchy {
+    /// Attaches `entity` as the last child of `parent`.\
+    /// If `entity` already had a parent, it is detached from it first.
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` or `parent` is not alive.
+    /// - `parent` is `entity` or one of its descendants.
+    fn attach(&mut self, entity: EntityId, parent: EntityId);
+    /// Creates a new entity and attaches it as the last child of `parent`.
+    ///
+    /// ### Panics
+    ///
+    /// - `parent` is not alive.
+    fn attach_new(&mut self, parent: EntityId) -> EntityId;
+    /// Detaches `entity` from its parent, its own children are kept.\
+    /// Returns the former parent.
+    fn detach(&mut self, entity: EntityId) -> Option<EntityId>;
+}
+
+impl Hierarchy
+    for (
+        EntitiesViewMut<'_>,
+        ViewMut<'_, Parent>,
+        ViewMut<'_, Children>,
+    )
+{
+    #[track_caller]
+    fn attach(&mut self, entity: EntityId, parent: EntityId) {
+        let (entities, parents, children) = self;
+
+        if entity == parent
+            || (&*parents, &*children)
+                .ancestors(parent)
+                .any(|e| e == entity)
+        {
+            panic!(
+                "Entity {:?} can't be attached to itself or one of its descendants.",
+                entity
+            );
+        }
+
+        if !entities.is_alive(parent) {
+            panic!("Entity {:?} has to be alive to get children.", parent);
+        }
+
+        self.detach(entity);
+
+        let (entities, parents, children) = self;
+
+        entities.add_component(entity, &mut *parents, Parent { entity: parent });
+
+        match (&mut *children).get(parent) {
+            Ok(mut siblings) => siblings.entities.push(entity),
+            Err(_) => entities.add_component(
+                parent,
+                &mut *children,
+                Children {
+                    entities: alloc::vec![entity],
+                },
+            ),
+        }
+    }
+    #[track_caller]
+    fn attach_new(&mut self, parent: EntityId) -> EntityId {
+        let entity = self.0.add_entity((), ());
+        self.attach(entity, parent);
+
+        entity
+    }
+    fn detach(&mut self, entity: EntityId) -> Option<EntityId> {
+        let (_, parents, children) = self;
+
+        let parent = parents.remove(entity)?.entity;
+
+        if let Ok(mut siblings) = (&mut *children).get(parent) {
+            siblings.entities.retain(|&sibling| sibling != entity);
+
+            if !siblings.entities.is_empty() {
+                return Some(parent);
+            }
+        }
+
+        children.remove(parent);
+
+        Some(parent)
+    }
+}
+
+/// Traverses a hierarchy built with [`Hierarchy`].
+///
+/// Implemented for `(P, C)` where `P` and `C` give access to [`Parent`] and [`Children`],
+/// like `(&View<Parent>, &View<Children>)`.\
+/// Traversals don't yield the entity they start from.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Children, EntitiesViewMut, Hierarchy, HierarchyIter, Parent, ViewMut, World};
+///
+/// let world = World::new();
+///
+/// let mut hierarchy = world
+///     .borrow::<(EntitiesViewMut, ViewMut<Parent>, ViewMut<Children>)>()
+///     .unwrap();
+///
+/// let root = hierarchy.0.add_entity((), ());
+/// let a = hierarchy.attach_new(root);
+/// let b = hierarchy.attach_new(root);
+/// let a1 = hierarchy.attach_new(a);
+///
+/// let tree = (&hierarchy.1, &hierarchy.2);
+/// assert!(tree.depth_first(root).eq([a, a1, b]));
+/// assert!(tree.breadth_first(root).eq([a, b, a1]));
+/// ```
+pub trait HierarchyIter<'a, P, C> {
+    /// Returns `entity`'s parent.
+    fn parent(&self, entity: EntityId) -> Option<EntityId>;
+    /// Returns `entity`'s children, in the order they were attached.
+    fn children(&self, entity: EntityId) -> &'a [EntityId];
+    /// Returns an iterator over `entity`'s parent, grand parent,... up to the root.
+    fn ancestors(&self, entity: EntityId) -> AncestorsIter<P>;
+    /// Returns an iterator over `entity`'s descendants, each child is followed by its own descendants.
+    fn depth_first(&self, entity: EntityId) -> DepthFirstIter<'a, C>;
+    /// Returns an iterator over `entity`'s descendants, level by level.
+    fn breadth_first(&self, entity: EntityId) -> BreadthFirstIter<C>;
+}
+
+impl<'a, P, C> HierarchyIter<'a, P, C> for (P, C)
+where
+    P: Get<Out = &'a Parent> + Copy,
+    C: Get<Out = &'a Children> + Copy,
+{
+    #[inline]
+    fn parent(&self, entity: EntityId) -> Option<EntityId> {
+        self.0.get(entity).ok().map(Parent::get)
+    }
+    #[inline]
+    fn children(&self, entity: EntityId) -> &'a [EntityId] {
+        self.1.get(entity).map_or(&[], Children::as_slice)
+    }
+    #[inline]
+    fn ancestors(&self, entity: EntityId) -> AncestorsIter<P> {
+        AncestorsIter {
+            parents: self.0,
+            cursor: entity,
+        }
+    }
+    #[inline]
+    fn depth_first(&self, entity: EntityId) -> DepthFirstIter<'a, C> {
+        DepthFirstIter {
+            children: self.1,
+            stack: alloc::vec![self.children(entity).iter()],
+        }
+    }
+    #[inline]
+    fn breadth_first(&self, entity: EntityId) -> BreadthFirstIter<C> {
+        BreadthFirstIter {
+            children: self.1,
+            queue: self.children(entity).iter().copied().collect(),
+        }
+    }
+}
+
+/// Iterator over an entity's ancestors, returned by [`HierarchyIter::ancestors`].
+pub struct AncestorsIter<P> {
+    parents: P,
+    cursor: EntityId,
+}
+
+impl<'a, P> Iterator for AncestorsIter<P>
+where
+    P: Get<Out = &'a Parent> + Copy,
+{
+    type Item = EntityId;
+
+    fn next(&mut self) -> Option<EntityId> {
+        let parent = self.parents.get(self.cursor).ok()?.entity;
+        self.cursor = parent;
+
+        Some(parent)
+    }
+}
+
+/// Depth-first iterator over an entity's descendants, returned by [`HierarchyIter::depth_first`].
+pub struct DepthFirstIter<'a, C> {
+    children: C,
+    stack: Vec<core::slice::Iter<'a, EntityId>>,
+}
+
+impl<'a, C> Iterator for DepthFirstIter<'a, C>
+where
+    C: Get<Out = &'a Children> + Copy,
+{
+    type Item = EntityId;
+
+    fn next(&mut self) -> Option<EntityId> {
+        loop {
+            let siblings = self.stack.last_mut()?;
+
+            match siblings.next() {
+                Some(&entity) => {
+                    if let Ok(children) = self.children.get(entity) {
+                        self.stack.push(children.entities.iter());
+                    }
+
+                    return Some(entity);
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Breadth-first iterator over an entity's descendants, returned by [`HierarchyIter::breadth_first`].
+pub struct BreadthFirstIter<C> {
+    children: C,
+    queue: VecDeque<EntityId>,
+}
+
+impl<'a, C> Iterator for BreadthFirstIter<C>
+where
+    C: Get<Out = &'a Children> + Copy,
+{
+    type Item = EntityId;
+
+    fn next(&mut self) -> Option<EntityId> {
+        let entity = self.queue.pop_front()?;
+
+        if let Ok(children) = self.children.get(entity) {
+            self.queue.extend(children.entities.iter().copied());
+        }
+
+        Some(entity)
+    }
+}
@@ -0,0 +1,377 @@
+//! Keyboard, mouse or gamepad state and the actions bound to it.
+//!
+//! The backend (winit, macroquad,...) feeds its events with [`World::feed_input`],
+//! systems then read [`Input`] for the raw state or drain [`ActionEvents`] for the mapped actions.\
+//! [`clear_input`] has to run once per frame, after all systems reading the input.
+//!
+//! ```
+//! use shipyard::input::{clear_input, ActionEvent, ActionEvents, Input, InputEvent, InputPlugin};
+//! use shipyard::{UniqueView, UniqueViewMut, Workload, World};
+//!
+//! #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+//! enum Key {
+//!     Space,
+//!     Escape,
+//! }
+//!
+//! #[derive(Clone, Copy, PartialEq, Debug)]
+//! enum Action {
+//!     Jump,
+//! }
+//!
+//! let world = World::new();
+//! world
+//!     .add_plugin(InputPlugin::new().bind(Key::Space, Action::Jump))
+//!     .unwrap();
+//!
+//! Workload::new("")
+//!     .with_system(|input: UniqueView<Input<Key>>, mut actions: UniqueViewMut<ActionEvents<Action>>| {
+//!         assert!(input.just_pressed(&Key::Space));
+//!         assert!(!input.pressed(&Key::Escape));
+//!         assert_eq!(actions.drain().collect::<Vec<_>>(), [ActionEvent::Started(Action::Jump)]);
+//!     })
+//!     .with_system(clear_input::<Key>)
+//!     .add_to_world(&world)
+//!     .unwrap();
+//!
+//! world.feed_input(InputEvent::Pressed(Key::Space)).unwrap();
+//! world.run_default_workload().unwrap();
+//! ```
+
+use crate::all_storages::AllStorages;
+use crate::component::Unique;
+use crate::error;
+use crate::plugin::{Plugin, PluginDependencies};
+use crate::views::UniqueViewMut;
+use crate::world::World;
+use crate::{ShipHashMap, ShipHashSet};
+use alloc::vec::{Drain, Vec};
+use core::fmt;
+use core::hash::Hash;
+
+type ActionFn<K> = fn(&AllStorages, &InputEvent<K>);
+
+/// Event sent by the backend.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum InputEvent<K> {
+    /// `K` went down, repeats of an already pressed key are ignored.
+    Pressed(K),
+    /// `K` went up.
+    Released(K),
+}
+
+/// Keys, buttons,... currently pressed and the ones that changed this frame.
+pub struct Input<K> {
+    pressed: ShipHashSet<K>,
+    just_pressed: ShipHashSet<K>,
+    just_released: ShipHashSet<K>,
+    actions: Vec<ActionFn<K>>,
+}
+
+impl<K: Send + Sync + 'static> Unique for Input<K> {}
+
+impl<K: Eq + Hash + Clone> Input<K> {
+    /// Creates an input without any key pressed.
+    pub fn new() -> Input<K> {
+        Input {
+            pressed: ShipHashSet::default(),
+            just_pressed: ShipHashSet::default(),
+            just_released: ShipHashSet::default(),
+            actions: Vec::new(),
+        }
+    }
+    /// Marks `key` as pressed, returns `false` if it already was.
+    pub fn press(&mut self, key: K) -> bool {
+        if self.pressed.insert(key.clone()) {
+            self.just_pressed.insert(key);
+            true
+        } else {
+            false
+        }
+    }
+    /// Marks `key` as released, returns `false` if it wasn't pressed.
+    pub fn release(&mut self, key: K) -> bool {
+        if self.pressed.remove(&key) {
+            self.just_released.insert(key);
+            true
+        } else {
+            false
+        }
+    }
+    /// Returns `true` if `key` is down.
+    pub fn pressed(&self, key: &K) -> bool {
+        self.pressed.contains(key)
+    }
+    /// Returns `true` if `key` went down this frame.
+    pub fn just_pressed(&self, key: &K) -> bool {
+        self.just_pressed.contains(key)
+    }
+    /// Returns `true` if `key` went up this frame.
+    pub fn just_released(&self, key: &K) -> bool {
+        self.just_released.contains(key)
+    }
+    /// Returns an iterator over the keys down.
+    pub fn iter_pressed(&self) -> impl Iterator<Item = &K> + '_ {
+        self.pressed.iter()
+    }
+    /// Returns an iterator over the keys that went down this frame.
+    pub fn iter_just_pressed(&self) -> impl Iterator<Item = &K> + '_ {
+        self.just_pressed.iter()
+    }
+    /// Returns an iterator over the keys that went up this frame.
+    pub fn iter_just_released(&self) -> impl Iterator<Item = &K> + '_ {
+        self.just_released.iter()
+    }
+    /// Starts a new frame, keys stay pressed but are no longer *just* pressed or released.
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+    /// Releases all keys, for example when the window loses focus.
+    pub fn reset(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+        self.just_pressed.clear();
+    }
+    /// Applies `event`, returns `false` if it didn't change the state.
+    pub fn apply(&mut self, event: InputEvent<K>) -> bool {
+        match event {
+            InputEvent::Pressed(key) => self.press(key),
+            InputEvent::Released(key) => self.release(key),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> Default for Input<K> {
+    fn default() -> Self {
+        Input::new()
+    }
+}
+
+impl<K: fmt::Debug> fmt::Debug for Input<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Input")
+            .field("pressed", &self.pressed)
+            .field("just_pressed", &self.just_pressed)
+            .field("just_released", &self.just_released)
+            .finish()
+    }
+}
+
+/// Keys bound to each action `A`.
+pub struct InputMap<K, A> {
+    bindings: ShipHashMap<K, Vec<A>>,
+}
+
+impl<K: Send + Sync + 'static, A: Send + Sync + 'static> Unique for InputMap<K, A> {}
+
+impl<K: Eq + Hash, A: PartialEq> InputMap<K, A> {
+    /// Creates a map without bindings.
+    pub fn new() -> InputMap<K, A> {
+        InputMap {
+            bindings: ShipHashMap::default(),
+        }
+    }
+    /// Binds `key` to `action`, a key can trigger multiple actions and an action multiple keys.
+    pub fn bind(&mut self, key: K, action: A) -> &mut InputMap<K, A> {
+        let actions = self.bindings.entry(key).or_default();
+        if !actions.contains(&action) {
+            actions.push(action);
+        }
+
+        self
+    }
+    /// Removes all actions bound to `key`.
+    pub fn unbind(&mut self, key: &K) {
+        self.bindings.remove(key);
+    }
+    /// Returns the actions bound to `key`.
+    pub fn actions(&self, key: &K) -> &[A] {
+        self.bindings.get(key).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl<K: Eq + Hash, A: PartialEq> Default for InputMap<K, A> {
+    fn default() -> Self {
+        InputMap::new()
+    }
+}
+
+impl<K: fmt::Debug, A: fmt::Debug> fmt::Debug for InputMap<K, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.bindings.iter()).finish()
+    }
+}
+
+/// Change of an action's state.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ActionEvent<A> {
+    /// A key bound to the action was pressed.
+    Started(A),
+    /// A key bound to the action was released.
+    Stopped(A),
+}
+
+/// Actions triggered by the input, in the order the events were fed.
+#[derive(Debug)]
+pub struct ActionEvents<A> {
+    events: Vec<ActionEvent<A>>,
+}
+
+impl<A: Send + Sync + 'static> Unique for ActionEvents<A> {}
+
+impl<A> ActionEvents<A> {
+    /// Creates an empty event list.
+    pub fn new() -> ActionEvents<A> {
+        ActionEvents { events: Vec::new() }
+    }
+    /// Adds an event, to trigger an action without input.
+    pub fn send(&mut self, event: ActionEvent<A>) {
+        self.events.push(event);
+    }
+    /// Returns an iterator over the events not drained yet.
+    pub fn iter(&self) -> impl Iterator<Item = &ActionEvent<A>> + '_ {
+        self.events.iter()
+    }
+    /// Removes and returns all events.
+    pub fn drain(&mut self) -> Drain<'_, ActionEvent<A>> {
+        self.events.drain(..)
+    }
+    /// Removes all events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+    /// Returns the number of events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+    /// Returns `true` if there is no event.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl<A> Default for ActionEvents<A> {
+    fn default() -> Self {
+        ActionEvents::new()
+    }
+}
+
+/// Adds [`Input<K>`], [`InputMap<K, A>`] and [`ActionEvents<A>`] to a [`World`].\
+/// Multiple plugins with the same `K` and different `A` share the same [`Input<K>`].
+pub struct InputPlugin<K, A> {
+    bindings: Vec<(K, A)>,
+}
+
+impl<K, A> InputPlugin<K, A> {
+    /// Creates a plugin without bindings.
+    pub fn new() -> InputPlugin<K, A> {
+        InputPlugin {
+            bindings: Vec::new(),
+        }
+    }
+    /// Binds `key` to `action`.
+    pub fn bind(mut self, key: K, action: A) -> InputPlugin<K, A> {
+        self.bindings.push((key, action));
+        self
+    }
+}
+
+impl<K, A> Default for InputPlugin<K, A> {
+    fn default() -> Self {
+        InputPlugin::new()
+    }
+}
+
+impl<K, A> Plugin for InputPlugin<K, A>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    A: PartialEq + Clone + Send + Sync + 'static,
+{
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies
+            .provides::<InputMap<K, A>>()
+            .provides::<ActionEvents<A>>();
+    }
+    fn build(&self, world: &World) {
+        let all_storages = world.all_storages().unwrap();
+
+        let mut map = InputMap::new();
+        for (key, action) in &self.bindings {
+            map.bind(key.clone(), action.clone());
+        }
+
+        all_storages.add_unique(map);
+        all_storages.add_unique(ActionEvents::<A>::new());
+
+        match all_storages.borrow::<UniqueViewMut<'_, Input<K>>>() {
+            Ok(mut input) => input.actions.push(send_actions::<K, A>),
+            Err(_) => {
+                let mut input = Input::new();
+                input.actions.push(send_actions::<K, A>);
+                all_storages.add_unique(input);
+            }
+        }
+    }
+}
+
+impl AllStorages {
+    /// Applies `event` to [`Input<K>`] and sends the actions bound to the key.
+    pub fn feed_input<K: Eq + Hash + Clone + Send + Sync + 'static>(
+        &self,
+        event: InputEvent<K>,
+    ) -> Result<(), error::GetStorage> {
+        let mut input = self.borrow::<UniqueViewMut<'_, Input<K>>>()?;
+
+        if input.apply(event.clone()) {
+            for send_actions in &input.actions {
+                (send_actions)(self, &event);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl World {
+    /// Applies `event` to [`Input<K>`] and sends the actions bound to the key.
+    pub fn feed_input<K: Eq + Hash + Clone + Send + Sync + 'static>(
+        &self,
+        event: InputEvent<K>,
+    ) -> Result<(), error::GetStorage> {
+        self.all_storages.borrow().unwrap().feed_input(event)
+    }
+}
+
+/// System starting a new input frame, see [`Input::clear`].
+pub fn clear_input<K: Eq + Hash + Clone + Send + Sync + 'static>(
+    mut input: UniqueViewMut<'_, Input<K>>,
+) {
+    input.clear();
+}
+
+fn send_actions<K, A>(all_storages: &AllStorages, event: &InputEvent<K>)
+where
+    K: Eq + Hash + Send + Sync + 'static,
+    A: PartialEq + Clone + Send + Sync + 'static,
+{
+    let (map, mut events) = match (
+        all_storages.borrow::<UniqueViewMut<'_, InputMap<K, A>>>(),
+        all_storages.borrow::<UniqueViewMut<'_, ActionEvents<A>>>(),
+    ) {
+        (Ok(map), Ok(events)) => (map, events),
+        _ => return,
+    };
+
+    let (key, started) = match event {
+        InputEvent::Pressed(key) => (key, true),
+        InputEvent::Released(key) => (key, false),
+    };
+
+    for action in map.actions(key) {
+        events.send(if started {
+            ActionEvent::Started(action.clone())
+        } else {
+            ActionEvent::Stopped(action.clone())
+        });
+    }
+}
@@ -0,0 +1,165 @@
+use crate::add_component::AddComponent;
+use crate::component::{Component, Unique};
+use crate::iter::{IntoIter, IntoWithId};
+use crate::track;
+use crate::views::{UniqueView, View, ViewMut};
+
+/// Types that can be blended between two values.
+pub trait Interpolate {
+    /// Returns the value between `self` and `other` at `t`.\
+    /// `t` is in `[0, 1]`, `0` being `self` and `1` being `other`.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    #[inline]
+    fn interpolate(&self, other: &f32, t: f32) -> f32 {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for f64 {
+    #[inline]
+    fn interpolate(&self, other: &f64, t: f32) -> f64 {
+        self + (other - self) * f64::from(t)
+    }
+}
+
+impl<T: Interpolate, const N: usize> Interpolate for [T; N] {
+    #[inline]
+    fn interpolate(&self, other: &[T; N], t: f32) -> [T; N] {
+        core::array::from_fn(|i| self[i].interpolate(&other[i], t))
+    }
+}
+
+/// Buffer keeping the last two authoritative values of `T` and the time they were received at.
+///
+/// The [`interpolate`] system writes the value of `T` at [`InterpolationTime`] into the `T` component of the same entity.\
+/// Authoritative values usually come from the network and are pushed with [`Interpolated::push`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, Interpolate, Interpolated, InterpolationTime, World};
+///
+/// #[derive(Component, Clone, Debug, PartialEq)]
+/// struct Position([f32; 2]);
+///
+/// impl Interpolate for Position {
+///     fn interpolate(&self, other: &Self, t: f32) -> Self {
+///         Position(self.0.interpolate(&other.0, t))
+///     }
+/// }
+///
+/// let mut world = World::new();
+///
+/// let mut buffer = Interpolated::new();
+/// buffer.push(0.0, Position([0.0, 0.0]));
+/// buffer.push(1.0, Position([10.0, 0.0]));
+///
+/// let entity = world.add_entity((buffer,));
+///
+/// world.add_unique(InterpolationTime(0.5));
+/// world.run(shipyard::interpolate::<Position>);
+///
+/// assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position([5.0, 0.0])));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Interpolated<T> {
+    previous: Option<(f64, T)>,
+    latest: Option<(f64, T)>,
+}
+
+impl<T> Default for Interpolated<T> {
+    fn default() -> Self {
+        Interpolated {
+            previous: None,
+            latest: None,
+        }
+    }
+}
+
+impl<T> Interpolated<T> {
+    /// Creates an empty buffer.
+    pub fn new() -> Interpolated<T> {
+        Interpolated::default()
+    }
+    /// Adds an authoritative `value` received at `time`, the oldest value is dropped.\
+    /// Values older than the latest one arrived out of order and are ignored, returns `false` in this case.
+    pub fn push(&mut self, time: f64, value: T) -> bool {
+        match &self.latest {
+            Some((latest, _)) if time < *latest => false,
+            _ => {
+                self.previous = self.latest.replace((time, value));
+
+                true
+            }
+        }
+    }
+    /// Returns the latest authoritative value and its time.
+    pub fn latest(&self) -> Option<(f64, &T)> {
+        self.latest.as_ref().map(|(time, value)| (*time, value))
+    }
+    /// Returns the authoritative value received before the latest one and its time.
+    pub fn previous(&self) -> Option<(f64, &T)> {
+        self.previous.as_ref().map(|(time, value)| (*time, value))
+    }
+    /// Returns `true` if no value was pushed.
+    pub fn is_empty(&self) -> bool {
+        self.latest.is_none()
+    }
+    /// Removes both values.
+    pub fn clear(&mut self) {
+        self.previous = None;
+        self.latest = None;
+    }
+}
+
+impl<T: Interpolate + Clone> Interpolated<T> {
+    /// Returns the value at `time`.\
+    /// Values are not extrapolated, before the previous value or after the latest one, the closest value is returned.\
+    /// Returns `None` if the buffer is empty.
+    pub fn sample(&self, time: f64) -> Option<T> {
+        let (latest_time, latest) = self.latest.as_ref()?;
+
+        match &self.previous {
+            Some((previous_time, previous)) if time < *latest_time => {
+                if time <= *previous_time {
+                    Some(previous.clone())
+                } else {
+                    let t = (time - previous_time) / (latest_time - previous_time);
+
+                    Some(previous.interpolate(latest, t as f32))
+                }
+            }
+            _ => Some(latest.clone()),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Component for Interpolated<T> {
+    type Tracking = track::Untracked;
+}
+
+/// Time used by the [`interpolate`] system, usually the current time minus the interpolation delay.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct InterpolationTime(pub f64);
+
+impl Unique for InterpolationTime {}
+
+/// System writing the value of each [`Interpolated<T>`] at [`InterpolationTime`] into `T`.\
+/// `T` is added to entities that don't have it yet.
+///
+/// ### Panics
+///
+/// - [`InterpolationTime`] isn't present in the `World`.
+pub fn interpolate<T: Component + Send + Sync + Interpolate + Clone>(
+    time: UniqueView<'_, InterpolationTime>,
+    buffers: View<'_, Interpolated<T>>,
+    mut targets: ViewMut<'_, T>,
+) {
+    for (entity, buffer) in (&buffers).iter().with_id() {
+        if let Some(value) = buffer.sample(time.0) {
+            targets.add_component_unchecked(entity, value);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::vec::Vec;
+
+/// Groups consecutive items sharing the same key.
+pub trait IntoGroupByKey: Iterator + Sized {
+    /// Returns an iterator over runs of consecutive items for which `key` returns the same value.
+    ///
+    /// Only consecutive items are grouped, the storage can be sorted by the key beforehand,
+    /// with [`SparseSet::sort_unstable_by`] for example, to get a single run per key.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, IntoGroupByKey, IntoIter, View, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Sprite {
+    ///     texture: u32,
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity(Sprite { texture: 1 });
+    /// world.add_entity(Sprite { texture: 0 });
+    /// world.add_entity(Sprite { texture: 1 });
+    ///
+    /// world.run(|mut sprites: ViewMut<Sprite>| {
+    ///     sprites.sort_unstable_by(|a, b| a.texture.cmp(&b.texture));
+    /// });
+    ///
+    /// let sprites = world.borrow::<View<Sprite>>().unwrap();
+    /// let batches: Vec<(u32, usize)> = sprites
+    ///     .iter()
+    ///     .group_by_key(|sprite| sprite.texture)
+    ///     .map(|(texture, sprites)| (texture, sprites.len()))
+    ///     .collect();
+    ///
+    /// assert_eq!(batches, [(0, 1), (1, 2)]);
+    /// ```
+    ///
+    /// [`SparseSet::sort_unstable_by`]: crate::SparseSet::sort_unstable_by()
+    fn group_by_key<K: PartialEq, F: FnMut(&Self::Item) -> K>(
+        self,
+        key: F,
+    ) -> GroupByKey<Self, K, F>;
+}
+
+impl<I: Iterator> IntoGroupByKey for I {
+    fn group_by_key<K: PartialEq, F: FnMut(&Self::Item) -> K>(
+        self,
+        key: F,
+    ) -> GroupByKey<Self, K, F> {
+        GroupByKey {
+            iter: self,
+            key,
+            next: None,
+        }
+    }
+}
+
+/// Iterator over runs of consecutive items sharing the same key.
+///
+/// Created by [`IntoGroupByKey::group_by_key`].
+pub struct GroupByKey<I: Iterator, K, F> {
+    iter: I,
+    key: F,
+    /// First item of the next run.
+    next: Option<(K, I::Item)>,
+}
+
+impl<I: Iterator, K: PartialEq, F: FnMut(&I::Item) -> K> Iterator for GroupByKey<I, K, F> {
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, first) = match self.next.take() {
+            Some(next) => next,
+            None => {
+                let item = self.iter.next()?;
+
+                ((self.key)(&item), item)
+            }
+        };
+
+        let mut run = Vec::new();
+        run.push(first);
+
+        for item in self.iter.by_ref() {
+            let item_key = (self.key)(&item);
+
+            if item_key == key {
+                run.push(item);
+            } else {
+                self.next = Some((item_key, item));
+
+                break;
+            }
+        }
+
+        Some((key, run))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.next.is_some() as usize;
+        let (lower, upper) = self.iter.size_hint();
+
+        (
+            (pending + lower).min(1),
+            upper.and_then(|upper| upper.checked_add(pending)),
+        )
+    }
+}
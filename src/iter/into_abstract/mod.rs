@@ -6,7 +6,7 @@ mod or;
 
 use crate::component::Component;
 use crate::entity_id::EntityId;
-use crate::sparse_set::{FullRawWindow, FullRawWindowMut, SparseSet};
+use crate::sparse_set::{EntityBitSet, FullRawWindow, FullRawWindowMut, SparseSet};
 use crate::sparse_set::{SparseArray, BUCKET_SIZE};
 use crate::tracking::Tracking;
 use crate::type_id::TypeId;
@@ -35,6 +35,11 @@ pub trait IntoAbstract {
     fn sparse(&self) -> *const SparseArray<EntityId, BUCKET_SIZE> {
         core::ptr::null()
     }
+    #[inline]
+    #[doc(hidden)]
+    fn bitset(&self) -> Option<&EntityBitSet> {
+        None
+    }
     #[doc(hidden)]
     fn is_tracking(&self) -> bool {
         false
@@ -76,6 +81,14 @@ impl<'a: 'b, 'b, T: Component, Track: Tracking> IntoAbstract for &'b View<'a, T,
     fn dense(&self) -> *const EntityId {
         self.dense.as_ptr()
     }
+    #[inline]
+    fn sparse(&self) -> *const SparseArray<EntityId, BUCKET_SIZE> {
+        &self.sparse
+    }
+    #[inline]
+    fn bitset(&self) -> Option<&EntityBitSet> {
+        (**self).bitset()
+    }
 }
 
 impl<'a: 'b, 'b, T: Component, Track: Tracking> IntoAbstract for &'b ViewMut<'a, T, Track> {
@@ -101,6 +114,14 @@ impl<'a: 'b, 'b, T: Component, Track: Tracking> IntoAbstract for &'b ViewMut<'a,
     fn dense(&self) -> *const EntityId {
         self.dense.as_ptr()
     }
+    #[inline]
+    fn sparse(&self) -> *const SparseArray<EntityId, BUCKET_SIZE> {
+        &self.sparse
+    }
+    #[inline]
+    fn bitset(&self) -> Option<&EntityBitSet> {
+        (**self).bitset()
+    }
 }
 
 impl<'a: 'b, 'b, T: Component, Track> IntoAbstract for &'b mut ViewMut<'a, T, Track> {
@@ -126,4 +147,12 @@ impl<'a: 'b, 'b, T: Component, Track> IntoAbstract for &'b mut ViewMut<'a, T, Tr
     fn dense(&self) -> *const EntityId {
         self.dense.as_ptr()
     }
+    #[inline]
+    fn sparse(&self) -> *const SparseArray<EntityId, BUCKET_SIZE> {
+        &self.sparse
+    }
+    #[inline]
+    fn bitset(&self) -> Option<&EntityBitSet> {
+        (**self).bitset()
+    }
 }
@@ -6,16 +6,63 @@ use super::mixed::Mixed;
 use super::par_iter::ParIter;
 use super::tight::Tight;
 use crate::entity_id::EntityId;
+use crate::sparse_set::{EntityBitSet, SparseArray, BUCKET_SIZE};
 use crate::type_id::TypeId;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::ptr;
+#[cfg(feature = "parallel")]
+use rayon::iter::ParallelIterator;
 
 const ACCESS_FACTOR: usize = 3;
 
+/// Storage of a view: its bitset, sparse array and dense array.
+type BitsetView<'a> = (
+    &'a EntityBitSet,
+    *const SparseArray<EntityId, BUCKET_SIZE>,
+    *const EntityId,
+);
+
+/// Returns the entities present in all storages keeping a bitset.
+///
+/// Returns `None` when less than two storages have a bitset or
+/// when the intersection would cost more than going through the smallest storage.
+fn join_bitsets(views: &[Option<BitsetView<'_>>], smallest: usize) -> Option<Arc<[EntityId]>> {
+    let mut bitsets = Vec::new();
+    let mut lookup = None;
+
+    for &(bitset, sparse, dense) in views.iter().flatten() {
+        bitsets.push(bitset);
+        lookup.get_or_insert((sparse, dense));
+    }
+
+    let cost = bitsets.iter().map(|bitset| bitset.summary_len()).min()?;
+    if bitsets.len() < 2 || cost >= smallest {
+        return None;
+    }
+
+    let (sparse, dense) = lookup?;
+    let mut joined = Vec::new();
+
+    EntityBitSet::intersection(&bitsets, |index| {
+        // SAFE the index is in the bitset so the entity is in the storage
+        unsafe {
+            let sparse_entity = (*sparse)
+                .get(EntityId::new(index as u64))
+                .unwrap_unchecked();
+            joined.push(*dense.add(sparse_entity.uindex()));
+        }
+    });
+
+    Some(joined.into())
+}
+
 /// Trait used to create iterators.  
 ///
 /// `std::iter::IntoIterator` can't be used directly because of conflicting implementation.  
-/// This trait serves as substitute.
+/// This trait serves as substitute.  
+/// Single views also implement `std::iter::IntoIterator`, `for component in &view` works without calling `iter`.
+/// Tuples of views can't, they have to go through this trait.
 pub trait IntoIter {
     #[allow(missing_docs)]
     type IntoIter: Iterator;
@@ -76,6 +123,50 @@ pub trait IntoIter {
     #[cfg(feature = "parallel")]
     #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
     fn par_iter(self) -> Self::IntoParIter;
+    /// Aggregates the components in parallel without collecting them.
+    ///
+    /// Each thread folds its components into an accumulator created with `identity` using `fold_op`,
+    /// the accumulators are then merged with `reduce_op`.\
+    /// Returns `identity()` when there is no component.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, EntitiesViewMut, IntoIter, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Pos(i32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let (mut entities, mut positions) = world.borrow::<(EntitiesViewMut, ViewMut<Pos>)>().unwrap();
+    ///
+    /// entities.add_entity(&mut positions, Pos(-3));
+    /// entities.add_entity(&mut positions, Pos(5));
+    /// entities.add_entity(&mut positions, Pos(1));
+    ///
+    /// let (min, max) = (&positions).par_reduce(
+    ///     || (i32::MAX, i32::MIN),
+    ///     |(min, max), pos| (min.min(pos.0), max.max(pos.0)),
+    ///     |(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)),
+    /// );
+    ///
+    /// assert_eq!((min, max), (-3, 5));
+    /// ```
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    fn par_reduce<T, ID, F, R>(self, identity: ID, fold_op: F, reduce_op: R) -> T
+    where
+        Self: Sized,
+        Self::IntoParIter: ParallelIterator,
+        T: Send,
+        ID: Fn() -> T + Send + Sync,
+        F: Fn(T, <Self::IntoParIter as ParallelIterator>::Item) -> T + Send + Sync,
+        R: Fn(T, T) -> T + Send + Sync,
+    {
+        self.par_iter()
+            .fold(&identity, fold_op)
+            .reduce(&identity, reduce_op)
+    }
 }
 
 impl<T: IntoAbstract> IntoIter for T
@@ -101,6 +192,7 @@ where
 
                 Iter::Mixed(Mixed {
                     rev_next_storage: self.other_dense(),
+                    joined: None,
                     indices: slice.iter(),
                     storage: self.into_abstract(),
                     count: 0,
@@ -149,6 +241,7 @@ where
 
                 Iter::Mixed(Mixed {
                     rev_next_storage: self.0.other_dense(),
+                    joined: None,
                     indices: slice.iter(),
                     storage: (self.0.into_abstract(),),
                     count: 0,
@@ -234,7 +327,24 @@ macro_rules! impl_into_iter {
 
                 let _ = factored_len;
 
-                if smallest == usize::MAX {
+                let bitsets = [
+                    self.$index1.bitset().map(|bitset| (bitset, self.$index1.sparse(), self.$index1.dense())),
+                    $(self.$index.bitset().map(|bitset| (bitset, self.$index.sparse(), self.$index.dense())),)+
+                ];
+
+                if let Some(joined) = join_bitsets(&bitsets, smallest) {
+                    let slice = unsafe { core::slice::from_raw_parts(joined.as_ptr(), joined.len()) };
+
+                    Iter::Mixed(Mixed {
+                        count: 0,
+                        mask: 0,
+                        indices: slice.into_iter(),
+                        last_id: EntityId::dead(),
+                        storage: (self.$index1.into_abstract(), $(self.$index.into_abstract(),)+),
+                        rev_next_storage: Vec::new(),
+                        joined: Some(joined),
+                    })
+                } else if smallest == usize::MAX {
                     Iter::Mixed(Mixed {
                         count: 0,
                         mask,
@@ -242,6 +352,7 @@ macro_rules! impl_into_iter {
                         last_id: EntityId::dead(),
                         storage: (self.$index1.into_abstract(), $(self.$index.into_abstract(),)+),
                         rev_next_storage: Vec::new(),
+                        joined: None,
                     })
                 } else {
                     let slice = unsafe { core::slice::from_raw_parts(smallest_dense, smallest) };
@@ -253,6 +364,7 @@ macro_rules! impl_into_iter {
                         last_id: EntityId::dead(),
                         storage: (self.$index1.into_abstract(), $(self.$index.into_abstract(),)+),
                         rev_next_storage: Vec::new(),
+                        joined: None,
                     })
                 }
             }
@@ -316,6 +428,7 @@ macro_rules! impl_into_iter {
                             last_id: EntityId::dead(),
                             storage: (self.$index1.into_abstract(), $(self.$index.into_abstract(),)+),
                             rev_next_storage: Vec::new(),
+                            joined: None,
                         })
                     } else {
                         let slice = unsafe { core::slice::from_raw_parts(smallest_dense, smallest) };
@@ -327,6 +440,7 @@ macro_rules! impl_into_iter {
                             last_id: EntityId::dead(),
                             storage: (self.$index1.into_abstract(), $(self.$index.into_abstract(),)+),
                             rev_next_storage: Vec::new(),
+                            joined: None,
                         })
                     }
                 } else {
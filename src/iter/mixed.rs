@@ -1,6 +1,7 @@
 use super::abstract_mut::AbstractMut;
 use super::with_id::LastId;
 use crate::entity_id::EntityId;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::slice::Iter;
 #[cfg(feature = "parallel")]
@@ -14,6 +15,8 @@ pub struct Mixed<Storage> {
     pub(crate) mask: u16,
     pub(crate) last_id: EntityId,
     pub(crate) rev_next_storage: Vec<Iter<'static, EntityId>>,
+    /// Entities of a bitset join, `indices` borrows from it
+    pub(crate) joined: Option<Arc<[EntityId]>>,
 }
 
 unsafe impl<Storage: Send> Send for Mixed<Storage> {}
@@ -109,6 +112,7 @@ impl<Storage: AbstractMut + Clone + Send> UnindexedProducer for Mixed<Storage> {
                 mask: self.mask,
                 last_id: self.last_id,
                 rev_next_storage: second_next,
+                joined: self.joined.clone(),
             };
 
             self.indices = first.iter();
@@ -1,11 +1,13 @@
 //! Iterators types and traits.
 
 mod abstract_mut;
+mod group_by;
 mod into_abstract;
 mod into_iter;
 #[allow(clippy::module_inception)]
 mod iter;
 mod mixed;
+mod pairs;
 #[cfg(feature = "parallel")]
 mod par_iter;
 #[cfg(feature = "parallel")]
@@ -16,10 +18,12 @@ mod tight;
 mod with_id;
 
 pub use abstract_mut::AbstractMut;
+pub use group_by::{GroupByKey, IntoGroupByKey};
 pub use into_abstract::IntoAbstract;
 pub use into_iter::IntoIter;
 pub use iter::Iter;
 pub use mixed::Mixed;
+pub use pairs::{IntoIterPairs, PairChunk, PairChunks, Pairs};
 #[cfg(feature = "parallel")]
 pub use par_iter::ParIter;
 #[cfg(feature = "parallel")]
@@ -0,0 +1,199 @@
+use super::into_iter::IntoIter;
+use super::with_id::{IntoWithId, LastId};
+use crate::entity_id::EntityId;
+use alloc::vec::Vec;
+
+/// Creates iterators yielding each unordered pair of components exactly once.
+///
+/// Works with any shared iteration, single views and tuples of views alike.
+pub trait IntoIterPairs {
+    #[allow(missing_docs)]
+    type Item: Clone;
+
+    /// Returns an iterator over all unordered pairs of the iterated components.
+    /// For `n` components, `n * (n - 1) / 2` pairs are yielded. A component is never paired with itself.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, IntoIterPairs, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Pos(f32, f32);
+    ///
+    /// #[derive(Component)]
+    /// struct Radius(f32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity((Pos(0.0, 0.0), Radius(1.0)));
+    /// world.add_entity((Pos(1.0, 0.0), Radius(1.0)));
+    /// world.add_entity((Pos(10.0, 0.0), Radius(1.0)));
+    ///
+    /// let (positions, radii) = world.borrow::<(View<Pos>, View<Radius>)>().unwrap();
+    ///
+    /// let collisions = (&positions, &radii)
+    ///     .iter_pairs()
+    ///     .filter(|((_, (pos_a, radius_a)), (_, (pos_b, radius_b)))| {
+    ///         let distance = ((pos_a.0 - pos_b.0).powi(2) + (pos_a.1 - pos_b.1).powi(2)).sqrt();
+    ///         distance < radius_a.0 + radius_b.0
+    ///     })
+    ///     .count();
+    ///
+    /// assert_eq!(collisions, 1);
+    /// ```
+    fn iter_pairs(self) -> Pairs<Self::Item>;
+}
+
+impl<T: IntoIter> IntoIterPairs for T
+where
+    T::IntoIter: LastId,
+    <T::IntoIter as Iterator>::Item: Clone,
+{
+    type Item = <T::IntoIter as Iterator>::Item;
+
+    fn iter_pairs(self) -> Pairs<Self::Item> {
+        let items: Vec<_> = self.iter().with_id().collect();
+        let end = items.len();
+
+        Pairs {
+            items,
+            first: 0,
+            second: 1,
+            end,
+        }
+    }
+}
+
+/// Iterator over unordered pairs of components.
+///
+/// Created by [`IntoIterPairs::iter_pairs`].
+pub struct Pairs<T> {
+    items: Vec<(EntityId, T)>,
+    first: usize,
+    second: usize,
+    end: usize,
+}
+
+impl<T: Clone> Pairs<T> {
+    /// Splits the pairs in chunks that can be iterated independently, on different threads for example.
+    /// Each chunk contains the pairs whose first component is part of a run of `chunk_size` components.
+    /// The chunks cover all pairs, including the ones already yielded by this iterator.
+    ///
+    /// ### Panics
+    ///
+    /// - `chunk_size` is 0.
+    #[track_caller]
+    pub fn chunks(&self, chunk_size: usize) -> PairChunks<'_, T> {
+        assert!(chunk_size != 0, "chunk_size must be greater than 0.");
+
+        PairChunks {
+            items: &self.items,
+            start: 0,
+            chunk_size,
+        }
+    }
+}
+
+#[inline]
+fn next_pair<T: Clone>(
+    items: &[(EntityId, T)],
+    first: &mut usize,
+    second: &mut usize,
+    end: usize,
+) -> Option<((EntityId, T), (EntityId, T))> {
+    if *second >= items.len() {
+        *first += 1;
+        *second = *first + 1;
+    }
+
+    if *first < end && *second < items.len() {
+        let pair = (items[*first].clone(), items[*second].clone());
+        *second += 1;
+
+        Some(pair)
+    } else {
+        None
+    }
+}
+
+#[inline]
+fn remaining_pairs(len: usize, first: usize, second: usize, end: usize) -> usize {
+    if first >= end || first >= len {
+        return 0;
+    }
+
+    let current = len.saturating_sub(second);
+    let rest: usize = (first + 1..end.min(len)).map(|i| len - i - 1).sum();
+
+    current + rest
+}
+
+impl<T: Clone> Iterator for Pairs<T> {
+    type Item = ((EntityId, T), (EntityId, T));
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        next_pair(&self.items, &mut self.first, &mut self.second, self.end)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = remaining_pairs(self.items.len(), self.first, self.second, self.end);
+
+        (len, Some(len))
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for Pairs<T> {}
+
+/// Iterator over the chunks of a [`Pairs`].
+pub struct PairChunks<'a, T> {
+    items: &'a [(EntityId, T)],
+    start: usize,
+    chunk_size: usize,
+}
+
+impl<'a, T: Clone> Iterator for PairChunks<'a, T> {
+    type Item = PairChunk<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the last component can't be the first of a pair
+        if self.start + 1 < self.items.len() {
+            let first = self.start;
+            self.start += self.chunk_size;
+
+            Some(PairChunk {
+                items: self.items,
+                first,
+                second: first + 1,
+                end: self.start.min(self.items.len()),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Iterator over a subset of unordered pairs of components.
+///
+/// Created by [`Pairs::chunks`].
+pub struct PairChunk<'a, T> {
+    items: &'a [(EntityId, T)],
+    first: usize,
+    second: usize,
+    end: usize,
+}
+
+impl<T: Clone> Iterator for PairChunk<'_, T> {
+    type Item = ((EntityId, T), (EntityId, T));
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        next_pair(self.items, &mut self.first, &mut self.second, self.end)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = remaining_pairs(self.items.len(), self.first, self.second, self.end);
+
+        (len, Some(len))
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for PairChunk<'_, T> {}
@@ -20,6 +20,46 @@ impl<Storage: AbstractMut> From<Iter<Storage>> for ParIter<Storage> {
     }
 }
 
+impl<Storage> ParIter<Storage> {
+    /// Sets the minimum number of components processed by a single thread.
+    ///
+    /// Without it, the batch size is based on the storage length and the number of threads:
+    /// small storages aren't split across threads and large ones are split in up to four batches per thread.\
+    /// Use a small batch size when each component is expensive to process, and a larger one when it's cheap.
+    ///
+    /// ### Panics
+    ///
+    /// - `batch_size` is zero.
+    ///
+    /// ### Example
+    /// ```
+    /// use rayon::prelude::ParallelIterator;
+    /// use shipyard::{Component, EntitiesViewMut, IntoIter, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Path(Vec<u32>);
+    ///
+    /// let world = World::new();
+    ///
+    /// let (mut entities, mut paths) = world.borrow::<(EntitiesViewMut, ViewMut<Path>)>().unwrap();
+    ///
+    /// entities.add_entity(&mut paths, Path(Vec::new()));
+    /// entities.add_entity(&mut paths, Path(Vec::new()));
+    ///
+    /// // path finding is expensive, let each thread take a single entity
+    /// (&mut paths).par_iter().batch_size(1).for_each(|mut path| {
+    ///     path.0.push(0);
+    /// });
+    /// ```
+    #[track_caller]
+    pub fn batch_size(self, batch_size: usize) -> Self {
+        match self {
+            ParIter::Tight(tight) => ParIter::Tight(tight.batch_size(batch_size)),
+            ParIter::Mixed(mixed) => ParIter::Mixed(mixed.batch_size(batch_size)),
+        }
+    }
+}
+
 impl<Storage: AbstractMut> ParallelIterator for ParIter<Storage>
 where
     Storage: Clone + Send,
@@ -46,3 +86,10 @@ where
         }
     }
 }
+
+/// Batch size used when none is specified, enough for four batches per thread but never less than 32.
+pub(super) fn auto_batch_size(len: usize) -> usize {
+    const MIN_AUTO_BATCH_SIZE: usize = 32;
+
+    (len / (rayon::current_num_threads() * 4)).max(MIN_AUTO_BATCH_SIZE)
+}
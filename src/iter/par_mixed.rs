@@ -1,14 +1,38 @@
 use super::abstract_mut::AbstractMut;
 use super::mixed::Mixed;
-use rayon::iter::plumbing::{bridge_unindexed, UnindexedConsumer};
+use super::par_iter::auto_batch_size;
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
 use rayon::iter::ParallelIterator;
 
 #[allow(missing_docs)]
-pub struct ParMixed<Storage>(Mixed<Storage>);
+pub struct ParMixed<Storage> {
+    iter: Mixed<Storage>,
+    batch_size: Option<usize>,
+}
 
 impl<Storage: AbstractMut> From<Mixed<Storage>> for ParMixed<Storage> {
     fn from(iter: Mixed<Storage>) -> Self {
-        ParMixed(iter)
+        ParMixed {
+            iter,
+            batch_size: None,
+        }
+    }
+}
+
+impl<Storage> ParMixed<Storage> {
+    /// Sets the minimum number of entities checked by a single thread, see [`ParIter::batch_size`].
+    ///
+    /// ### Panics
+    ///
+    /// - `batch_size` is zero.
+    ///
+    /// [`ParIter::batch_size`]: crate::iter::ParIter::batch_size
+    #[track_caller]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size != 0, "Batch size has to be at least 1.");
+
+        self.batch_size = Some(batch_size);
+        self
     }
 }
 
@@ -24,6 +48,52 @@ where
     where
         C: UnindexedConsumer<Self::Item>,
     {
-        bridge_unindexed(self.0, consumer)
+        let batch_size = self
+            .batch_size
+            .unwrap_or_else(|| auto_batch_size(self.iter.indices.len()));
+
+        bridge_unindexed(
+            BatchedMixed {
+                mixed: self.iter,
+                batch_size,
+            },
+            consumer,
+        )
+    }
+}
+
+/// [`Mixed`] that doesn't split below `batch_size` entities.
+struct BatchedMixed<Storage> {
+    mixed: Mixed<Storage>,
+    batch_size: usize,
+}
+
+impl<Storage: AbstractMut + Clone + Send> UnindexedProducer for BatchedMixed<Storage> {
+    type Item = <Storage as AbstractMut>::Out;
+
+    #[inline]
+    fn split(self) -> (Self, Option<Self>) {
+        if self.mixed.indices.len() / 2 < self.batch_size && self.mixed.rev_next_storage.is_empty()
+        {
+            return (self, None);
+        }
+
+        let batch_size = self.batch_size;
+        let (first, second) = self.mixed.split();
+
+        (
+            BatchedMixed {
+                mixed: first,
+                batch_size,
+            },
+            second.map(|mixed| BatchedMixed { mixed, batch_size }),
+        )
+    }
+    #[inline]
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        self.mixed.fold_with(folder)
     }
 }
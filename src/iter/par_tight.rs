@@ -1,14 +1,38 @@
 use super::abstract_mut::AbstractMut;
+use super::par_iter::auto_batch_size;
 use super::tight::Tight;
-use rayon::iter::plumbing::{bridge, Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
 use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 
 #[allow(missing_docs)]
-pub struct ParTight<Storage>(Tight<Storage>);
+pub struct ParTight<Storage> {
+    iter: Tight<Storage>,
+    batch_size: Option<usize>,
+}
 
 impl<Storage: AbstractMut> From<Tight<Storage>> for ParTight<Storage> {
     fn from(iter: Tight<Storage>) -> Self {
-        ParTight(iter)
+        ParTight {
+            iter,
+            batch_size: None,
+        }
+    }
+}
+
+impl<Storage> ParTight<Storage> {
+    /// Sets the minimum number of components processed by a single thread, see [`ParIter::batch_size`].
+    ///
+    /// ### Panics
+    ///
+    /// - `batch_size` is zero.
+    ///
+    /// [`ParIter::batch_size`]: crate::iter::ParIter::batch_size
+    #[track_caller]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size != 0, "Batch size has to be at least 1.");
+
+        self.batch_size = Some(batch_size);
+        self
     }
 }
 
@@ -40,7 +64,7 @@ where
 {
     #[inline]
     fn len(&self) -> usize {
-        self.0.len()
+        self.iter.len()
     }
 
     #[inline]
@@ -50,6 +74,48 @@ where
 
     #[inline]
     fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
-        callback.callback(self.0)
+        let batch_size = self
+            .batch_size
+            .unwrap_or_else(|| auto_batch_size(self.iter.len()));
+
+        callback.callback(BatchedTight {
+            tight: self.iter,
+            batch_size,
+        })
+    }
+}
+
+/// [`Tight`] that doesn't split below `batch_size`.
+struct BatchedTight<Storage> {
+    tight: Tight<Storage>,
+    batch_size: usize,
+}
+
+impl<Storage: AbstractMut + Clone + Send> Producer for BatchedTight<Storage> {
+    type Item = <Tight<Storage> as Iterator>::Item;
+    type IntoIter = Tight<Storage>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.tight
+    }
+    #[inline]
+    fn min_len(&self) -> usize {
+        self.batch_size
+    }
+    #[inline]
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (first, second) = self.tight.split_at(index);
+
+        (
+            BatchedTight {
+                tight: first,
+                batch_size: self.batch_size,
+            },
+            BatchedTight {
+                tight: second,
+                batch_size: self.batch_size,
+            },
+        )
     }
 }
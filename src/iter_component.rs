@@ -828,6 +828,7 @@ macro_rules! impl_iter_component {
                             last_id: EntityId::dead(),
                             storage: raw_window,
                             rev_next_storage: Vec::new(),
+                            joined: None,
                         })
                     } else {
                         let slice = unsafe { core::slice::from_raw_parts(smallest_dense, smallest) };
@@ -839,6 +840,7 @@ macro_rules! impl_iter_component {
                             last_id: EntityId::dead(),
                             storage: raw_window,
                             rev_next_storage: Vec::new(),
+                            joined: None,
                         })
                     };
 
@@ -4,12 +4,22 @@
 //!
 //! ## Features
 //!
+//! - **arrow** &mdash; adds conversion of storages to and from [Apache Arrow](https://arrow.apache.org) record batches
+//! - **borrow_debug** &mdash; records which thread and system hold each storage borrow, adds `World::borrow_timeout`
+//! - **capi** &mdash; adds `extern "C"` functions to drive a `World` from other languages
+//! - **diagnostics** &mdash; implements [miette](https://github.com/zkat/miette)'s `Diagnostic` for common errors, with error codes and suggestions
+//! - **input** &mdash; adds keyboard, mouse or gamepad state uniques and action mapping
+//! - **lz4** &mdash; adds LZ4 compression of snapshots and replication deltas, built on **snapshot**
 //! - **parallel** *(default)* &mdash; enables workload threading and add parallel iterators
 //! - **proc** *(default)* &mdash; re-exports macros from `shipyard_proc`, mainly to derive `Component`
+//! - **python** &mdash; adds a [PyO3](https://pyo3.rs) `World` class for Python tooling, built on **capi**
+//! - **replication** &mdash; adds network replication of component changes, built on **snapshot**
 //! - **serde1** &mdash; adds (de)serialization support with [serde](https://github.com/serde-rs/serde)
+//! - **snapshot** &mdash; adds compact binary `World` snapshots checked against a schema, using [bincode](https://github.com/bincode-org/bincode)
 //! - **std** *(default)* &mdash; lets Shipyard use the standard library
 //! - **thread_local** &mdash; adds methods and types required to work with `!Send` and `!Sync` components
 //! - **tracing** &mdash; reports workload and system execution
+//! - **zstd** &mdash; adds Zstandard compression of snapshots and replication deltas, built on **snapshot**
 
 #![warn(elided_lifetimes_in_paths)]
 #![warn(trivial_casts)]
@@ -40,40 +50,92 @@ mod add_component;
 mod add_distinct_component;
 mod add_entity;
 mod all_storages;
+#[cfg(feature = "arrow")]
+mod arrow;
 mod atomic_refcell;
 /// Allows access to helper types needed to implement `Borrow`.
 pub mod borrow;
+#[cfg(feature = "borrow_debug")]
+mod borrow_debug;
+#[cfg(feature = "std")]
+mod budget;
+mod cached_query;
+#[cfg(feature = "capi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capi")))]
+pub mod capi;
+mod command_buffer;
 mod component;
+mod component_mask;
+mod component_registrar;
+#[cfg(feature = "snapshot")]
+mod compression;
 mod contains;
 mod delete;
+mod deletion_events;
+mod digest;
 mod entities;
 mod entity_id;
 pub mod error;
+mod external_storage;
+mod extract;
+mod flyweight;
+mod frame_arena;
+mod from_world;
 mod get;
 mod get_component;
 mod get_unique;
+mod handle_owner;
+mod hierarchy;
+#[cfg(feature = "input")]
+#[cfg_attr(docsrs, doc(cfg(feature = "input")))]
+pub mod input;
+mod interpolation;
 pub mod iter;
 mod iter_component;
+mod lifetime;
 /// Module describing internal memory usage.
 pub mod memory_usage;
+mod metrics;
+mod multi_sparse_set;
 mod r#mut;
 mod not;
 mod or;
+mod plugin;
 mod public_transport;
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+pub mod python;
 mod remove;
+mod replay;
+#[cfg(feature = "replication")]
+mod replication;
 mod reserve;
 mod scheduler;
 mod seal;
+#[cfg(feature = "snapshot")]
+mod snapshot;
 mod sparse_set;
+mod spatial;
+mod stable_id;
+mod state_machine;
 mod storage;
+mod structural_guard;
 mod system;
+/// Helpers to compare a `World` against an expected state in tests.
+#[cfg(feature = "snapshot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+pub mod testing;
+mod time;
 /// Module related to storage tracking, like insertion or modification.
 pub mod track;
 mod tracking;
+mod transform;
 mod type_id;
+mod undo;
 mod unique;
 mod views;
 mod world;
+mod worlds;
 
 #[cfg(feature = "thread_local")]
 #[cfg_attr(docsrs, doc(cfg(feature = "thread_local")))]
@@ -88,53 +150,121 @@ pub use add_component::AddComponent;
 pub use add_distinct_component::AddDistinctComponent;
 pub use add_entity::AddEntity;
 pub use all_storages::{
-    AllStorages, CustomStorageAccess, LockPresent, MissingLock, MissingThreadId, ThreadIdPresent,
-    TupleDeleteAny, TupleRetainStorage,
+    AllStorages, CustomStorageAccess, DeletionPolicy, LockPresent, MissingLock, MissingThreadId,
+    Relation, ThreadIdPresent, TupleDeleteAny, TupleRetainStorage,
 };
+#[cfg(feature = "arrow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+pub use arrow::{ArrowComponent, ARROW_ID_COLUMN};
 pub use atomic_refcell::{ARef, ARefMut};
 #[doc(hidden)]
 pub use atomic_refcell::{ExclusiveBorrow, SharedBorrow};
 #[doc(inline)]
 pub use borrow::{Borrow, BorrowInfo, Mutability, WorldBorrow};
+#[cfg(feature = "borrow_debug")]
+#[cfg_attr(docsrs, doc(cfg(feature = "borrow_debug")))]
+pub use borrow_debug::BorrowHolder;
+#[cfg(feature = "std")]
+pub use budget::{Budget, BudgetReport, SystemBudget};
+pub use cached_query::{CachedQuery, QueryComponents};
+pub use command_buffer::CommandBuffer;
 pub use component::{Component, Unique};
+pub use component_mask::{ComponentMask, TupleSignature};
+pub use component_registrar::{ComponentRegistrar, TupleRegisterComponent};
+#[cfg(feature = "snapshot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+pub use compression::Compressor;
+#[cfg(feature = "lz4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lz4")))]
+pub use compression::Lz4;
+#[cfg(feature = "zstd")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+pub use compression::Zstd;
 pub use contains::Contains;
 pub use delete::Delete;
-pub use entities::Entities;
-pub use entity_id::EntityId;
+pub use deletion_events::DeletionEvents;
+pub use digest::Pod;
+pub use entities::{Entities, EntityBuilder, EntityRecycling, EntityRemap, GenerationOverflow};
+pub use entity_id::{EntityId, WeakEntity};
+pub use external_storage::{
+    ExternalStorage, ExternalView, ExternalViewMut, ExternalWindow, ExternalWindowMut,
+};
+pub use extract::{extract, swap_buffers, DoubleBuffer, ExtractTarget};
+pub use flyweight::{
+    FlyweightHandle, FlyweightStorage, FlyweightView, FlyweightViewMut, FlyweightWindow,
+};
+pub use frame_arena::{reset_frame_arena, FrameArena, FrameVec};
+pub use from_world::FromWorld;
 pub use get::Get;
 pub use get_component::{GetComponent, Ref, RefMut};
 pub use get_unique::GetUnique;
-pub use iter::{IntoIter, IntoWithId};
+pub use handle_owner::{release_handles, HandleOwner, HandleOwners};
+pub use hierarchy::{
+    AncestorsIter, BreadthFirstIter, Children, DepthFirstIter, Hierarchy, HierarchyIter, Parent,
+};
+pub use interpolation::{interpolate, Interpolate, Interpolated, InterpolationTime};
+pub use iter::{IntoGroupByKey, IntoIter, IntoIterPairs, IntoWithId};
 pub use iter_component::{IntoIterRef, IterComponent, IterRef};
+pub use lifetime::{expire_lifetimes, Lifetime};
+pub use metrics::{
+    record_storage_counts, update_diagnostics, DiagnosticSink, Diagnostics, Metric, MetricKind,
+};
+pub use multi_sparse_set::{MultiSparseSet, MultiView, MultiViewMut};
 pub use not::Not;
 pub use or::{OneOfTwo, Or};
-pub use r#mut::Mut;
+pub use plugin::{Plugin, PluginDependencies};
+pub use r#mut::{ModificationFlag, Mut};
 pub use remove::Remove;
+pub use replay::{ReplayCommand, ReplayRegistry, ReplayStream};
+#[cfg(feature = "replication")]
+#[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+pub use replication::{
+    BitReader, BitWriter, Quantizer, ReplicationClient, ReplicationDelta, ReplicationEncoder,
+    ReplicationRegistry,
+};
 pub use reserve::{BulkEntityIter, BulkReserve};
 pub use scheduler::{
-    info, AsLabel, IntoWorkload, IntoWorkloadSystem, IntoWorkloadTrySystem, Label,
-    ScheduledWorkload, SystemModificator, Workload, WorkloadModificator, WorkloadSystem,
+    info, AsLabel, Decision, IntoWorkload, IntoWorkloadSystem, IntoWorkloadTrySystem, Label,
+    ScheduledWorkload, SystemDescriptor, SystemModificator, SystemRegistry, Workload,
+    WorkloadDescriptor, WorkloadModificator, WorkloadSystem,
 };
 #[cfg(feature = "proc")]
-pub use shipyard_proc::{Borrow, BorrowInfo, Component, IntoIter, Label, Unique, WorldBorrow};
+pub use shipyard_proc::{
+    system, Borrow, BorrowInfo, Component, IntoIter, Label, Unique, WorldBorrow,
+};
+#[cfg(feature = "snapshot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+pub use snapshot::{
+    component_layout_hash, layout_hash, EntityIdMap, SnapshotFilter, SnapshotRegistry, WorldDiff,
+};
 pub use sparse_set::{
-    BulkAddEntity, SparseArray, SparseSet, SparseSetDrain, TupleAddComponent, TupleDelete,
-    TupleRemove,
+    BulkAddEntity, EntityBitSet, SparseArray, SparseSet, SparseSetDrain, StorageAllocator,
+    TupleAddComponent, TupleContains, TupleDelete, TupleRemove,
 };
-pub use storage::{Storage, StorageId};
+pub use spatial::{update_spatial_grid, SpatialGrid, SpatialPosition};
+pub use stable_id::{StableId, StableIds};
+pub use state_machine::StateMachine;
+pub use storage::{SizedAny, Storage, StorageHandle, StorageId};
+pub use structural_guard::StructuralChangeGuard;
 #[doc(hidden)]
 pub use system::{AllSystem, Nothing, System};
+#[cfg(feature = "std")]
+pub use time::update_time;
+pub use time::{every_duration, every_n_frames, Time};
 pub use tracking::{
     DeletionTracking, Inserted, InsertedOrModified, InsertionTracking, ModificationTracking,
     Modified, RemovalOrDeletionTracking, RemovalTracking, Tracking, TrackingTimestamp, TupleTrack,
 };
+pub use transform::{propagate_transforms, GlobalTransform, LocalTransform, Transform};
+pub use undo::UndoRegistry;
 pub use unique::UniqueStorage;
 pub use views::{
-    AllStoragesView, AllStoragesViewMut, EntitiesView, EntitiesViewMut, UniqueOrDefaultView,
-    UniqueOrDefaultViewMut, UniqueOrInitView, UniqueOrInitViewMut, UniqueView, UniqueViewMut, View,
-    ViewMut,
+    AllStoragesView, AllStoragesViewMut, AllStoragesViewOf, EntitiesView, EntitiesViewMut,
+    SubViewMut, ThreadLocal, ThreadLocalRefMut, UniqueOrDefaultView, UniqueOrDefaultViewMut,
+    UniqueOrInitView, UniqueOrInitViewMut, UniqueView, UniqueViewMut, View, ViewMut,
 };
-pub use world::{World, WorldBuilder};
+pub use world::{FrozenBorrow, FrozenWorld, SubWorld, World, WorldBuilder};
+pub use worlds::{Shared, Worlds};
 
 #[cfg(not(feature = "std"))]
 type ShipHashMap<K, V> =
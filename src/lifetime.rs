@@ -0,0 +1,98 @@
+use crate::all_storages::AllStorages;
+use crate::component::Component;
+use crate::iter::{IntoIter, IntoWithId};
+use crate::time::Time;
+use crate::track;
+use crate::views::{AllStoragesViewMut, UniqueView, ViewMut};
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// Deletes its entity when it runs out, counted down by [`expire_lifetimes`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{expire_lifetimes, Lifetime, Time, UniqueViewMut, Workload, World};
+/// use std::time::Duration;
+///
+/// let mut world = World::new();
+/// world.add_unique(Time::new(1.0 / 60.0));
+///
+/// let particle = world.add_entity(Lifetime::Frames(2));
+/// let bullet = world.add_entity(Lifetime::Duration(Duration::from_millis(500)));
+///
+/// Workload::new("")
+///     .with_system(|mut time: UniqueViewMut<Time>| time.advance(0.3))
+///     .with_system(expire_lifetimes)
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// world.run_default_workload().unwrap();
+/// assert!(world.is_alive(particle) && world.is_alive(bullet));
+///
+/// world.run_default_workload().unwrap();
+/// assert!(!world.is_alive(particle) && !world.is_alive(bullet));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Lifetime {
+    /// Time left, [`Time::delta`] is subtracted at each run of [`expire_lifetimes`].
+    Duration(Duration),
+    /// Runs of [`expire_lifetimes`] left.
+    Frames(u64),
+}
+
+impl Lifetime {
+    /// Returns `true` when there is no time or frame left.
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        match self {
+            Lifetime::Duration(duration) => duration.is_zero(),
+            Lifetime::Frames(frames) => *frames == 0,
+        }
+    }
+    /// Counts down one frame lasting `delta`, returns `true` if the lifetime expired.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        match self {
+            Lifetime::Duration(duration) => *duration = duration.saturating_sub(delta),
+            Lifetime::Frames(frames) => *frames = frames.saturating_sub(1),
+        }
+
+        self.is_expired()
+    }
+}
+
+impl Component for Lifetime {
+    type Tracking = track::Untracked;
+}
+
+impl AllStorages {
+    /// Counts down all [`Lifetime`]s by one frame of [`Time::delta`] and deletes the entities whose lifetime expired.\
+    /// Without [`Time`], only [`Lifetime::Frames`] count down.\
+    /// Returns the number of entities deleted.
+    pub fn expire_lifetimes(&mut self) -> usize {
+        let delta = self
+            .borrow::<UniqueView<'_, Time>>()
+            .map_or(Duration::ZERO, |time| {
+                Duration::from_secs_f64(time.delta().max(0.0))
+            });
+
+        let expired = match self.borrow::<ViewMut<'_, Lifetime>>() {
+            Ok(mut lifetimes) => (&mut lifetimes)
+                .iter()
+                .with_id()
+                .filter_map(|(entity, lifetime)| lifetime.tick(delta).then_some(entity))
+                .collect::<Vec<_>>(),
+            Err(_) => return 0,
+        };
+
+        for &entity in &expired {
+            self.delete_entity(entity);
+        }
+
+        expired.len()
+    }
+}
+
+/// System deleting the entities whose [`Lifetime`] expired, see [`AllStorages::expire_lifetimes`].
+pub fn expire_lifetimes(mut all_storages: AllStoragesViewMut<'_>) {
+    all_storages.expire_lifetimes();
+}
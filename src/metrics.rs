@@ -0,0 +1,309 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::component::Unique;
+use crate::views::{AllStoragesView, UniqueViewMut};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// How a [`Metric`]'s value carries over frames.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MetricKind {
+    /// Sum of the values recorded during a frame, starts at 0 each frame.
+    Counter,
+    /// Last value recorded, kept until the next one.
+    Gauge,
+}
+
+/// Value recorded by systems and its history over the last frames.
+#[derive(Clone, Debug)]
+pub struct Metric {
+    kind: MetricKind,
+    current: f64,
+    history: VecDeque<f64>,
+}
+
+impl Metric {
+    fn new(kind: MetricKind, capacity: usize) -> Metric {
+        Metric {
+            kind,
+            current: 0.0,
+            history: VecDeque::with_capacity(capacity),
+        }
+    }
+    /// Returns whether this metric is a counter or a gauge.
+    pub fn kind(&self) -> MetricKind {
+        self.kind
+    }
+    /// Returns the value of the frame being recorded.
+    pub fn current(&self) -> f64 {
+        self.current
+    }
+    /// Returns the value of the last completed frame.
+    pub fn last(&self) -> Option<f64> {
+        self.history.back().copied()
+    }
+    /// Returns the average over the history.
+    pub fn average(&self) -> Option<f64> {
+        if self.history.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = self.history.len() as f64;
+
+        Some(self.history.iter().sum::<f64>() / len)
+    }
+    /// Returns the smallest value of the history.
+    pub fn min(&self) -> Option<f64> {
+        self.history.iter().copied().reduce(f64::min)
+    }
+    /// Returns the largest value of the history.
+    pub fn max(&self) -> Option<f64> {
+        self.history.iter().copied().reduce(f64::max)
+    }
+    /// Returns the difference between the newest and oldest values of the history.\
+    /// A count always growing over a long history usually means entities or components are never cleaned up.
+    pub fn growth(&self) -> Option<f64> {
+        Some(self.history.back()? - self.history.front()?)
+    }
+    /// Returns the values of the last completed frames, oldest first.
+    pub fn history(&self) -> impl ExactSizeIterator<Item = f64> + '_ {
+        self.history.iter().copied()
+    }
+}
+
+/// Receives the metrics at the end of each frame, to log or export them.
+///
+/// Implemented for closures taking the metric's name and the metric.
+pub trait DiagnosticSink: Send + Sync {
+    /// Called with each metric, in name order, once the frame is added to its history.
+    fn report(&mut self, name: &str, metric: &Metric);
+    /// Called once all metrics of a frame are reported.
+    fn flush(&mut self) {}
+}
+
+impl<F: FnMut(&str, &Metric) + Send + Sync> DiagnosticSink for F {
+    fn report(&mut self, name: &str, metric: &Metric) {
+        (self)(name, metric)
+    }
+}
+
+/// Named counters and gauges recorded by systems, like entities spawned or draw calls.
+///
+/// [`update_diagnostics`] closes the frame: each metric's value is added to its history and reported to the sinks.
+///
+/// ### Example
+/// ```
+/// use shipyard::{update_diagnostics, Diagnostics, UniqueView, UniqueViewMut, Workload, World};
+///
+/// let world = World::new();
+/// world.add_unique(Diagnostics::new());
+///
+/// Workload::new("")
+///     .with_system(|mut diagnostics: UniqueViewMut<Diagnostics>| {
+///         diagnostics.count("draw_calls", 2);
+///         diagnostics.count("draw_calls", 3);
+///         diagnostics.gauge("zoom", 1.5);
+///     })
+///     .with_system(update_diagnostics)
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// world.run_default_workload().unwrap();
+///
+/// let diagnostics = world.borrow::<UniqueView<Diagnostics>>().unwrap();
+/// assert_eq!(diagnostics.get("draw_calls").unwrap().last(), Some(5.0));
+/// assert_eq!(diagnostics.get("zoom").unwrap().average(), Some(1.5));
+/// ```
+pub struct Diagnostics {
+    metrics: BTreeMap<Cow<'static, str>, Metric>,
+    sinks: Vec<Box<dyn DiagnosticSink>>,
+    history_len: usize,
+}
+
+impl Unique for Diagnostics {}
+
+impl Diagnostics {
+    /// Number of frames [`Diagnostics::new`] keeps.
+    pub const DEFAULT_HISTORY: usize = 120;
+    /// Prefix of the gauges recorded by [`record_storage_counts`].
+    pub const STORAGE_PREFIX: &'static str = "storage/";
+
+    /// Creates an empty `Diagnostics` keeping [`Diagnostics::DEFAULT_HISTORY`] frames.
+    pub fn new() -> Diagnostics {
+        Diagnostics::with_history(Diagnostics::DEFAULT_HISTORY)
+    }
+    /// Creates an empty `Diagnostics` keeping `history_len` frames.
+    pub fn with_history(history_len: usize) -> Diagnostics {
+        Diagnostics {
+            metrics: BTreeMap::new(),
+            sinks: Vec::new(),
+            history_len: history_len.max(1),
+        }
+    }
+    /// Returns the number of frames kept by each metric.
+    pub fn history_len(&self) -> usize {
+        self.history_len
+    }
+    /// Adds `value` to the counter `name` for this frame.
+    ///
+    /// ### Panics
+    ///
+    /// - `name` is a gauge.
+    #[track_caller]
+    pub fn count(&mut self, name: impl Into<Cow<'static, str>>, value: u64) {
+        #[allow(clippy::cast_precision_loss)]
+        let value = value as f64;
+
+        self.metric(name.into(), MetricKind::Counter).current += value;
+    }
+    /// Sets the gauge `name` to `value`.
+    ///
+    /// ### Panics
+    ///
+    /// - `name` is a counter.
+    #[track_caller]
+    pub fn gauge(&mut self, name: impl Into<Cow<'static, str>>, value: f64) {
+        self.metric(name.into(), MetricKind::Gauge).current = value;
+    }
+    /// Returns the metric `name`.
+    pub fn get(&self, name: &str) -> Option<&Metric> {
+        self.metrics.get(name)
+    }
+    /// Returns an iterator over all metrics, in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Metric)> + '_ {
+        self.metrics.iter().map(|(name, metric)| (&**name, metric))
+    }
+    /// Returns an iterator over the storage counts recorded by [`record_storage_counts`], without [`Diagnostics::STORAGE_PREFIX`].
+    pub fn storage_counts(&self) -> impl Iterator<Item = (&str, &Metric)> + '_ {
+        self.iter().filter_map(|(name, metric)| {
+            name.strip_prefix(Diagnostics::STORAGE_PREFIX)
+                .map(|name| (name, metric))
+        })
+    }
+    /// Removes the metric `name` and its history.
+    pub fn remove(&mut self, name: &str) -> Option<Metric> {
+        self.metrics.remove(name)
+    }
+    /// Adds a sink, reported to at the end of each frame.
+    pub fn add_sink(&mut self, sink: impl DiagnosticSink + 'static) {
+        self.sinks.push(Box::new(sink));
+    }
+    /// Adds the current value of each metric to its history, then reports all metrics to the sinks.\
+    /// Counters start the next frame at 0.
+    pub fn end_frame(&mut self) {
+        for metric in self.metrics.values_mut() {
+            if metric.history.len() == self.history_len {
+                metric.history.pop_front();
+            }
+
+            metric.history.push_back(metric.current);
+
+            if metric.kind == MetricKind::Counter {
+                metric.current = 0.0;
+            }
+        }
+
+        for sink in &mut self.sinks {
+            for (name, metric) in &self.metrics {
+                sink.report(name, metric);
+            }
+
+            sink.flush();
+        }
+    }
+    #[track_caller]
+    fn metric(&mut self, name: Cow<'static, str>, kind: MetricKind) -> &mut Metric {
+        let history_len = self.history_len;
+        let metric = self
+            .metrics
+            .entry(name)
+            .or_insert_with(|| Metric::new(kind, history_len));
+
+        assert!(
+            metric.kind == kind,
+            "metric recorded as both a counter and a gauge"
+        );
+
+        metric
+    }
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Diagnostics::new()
+    }
+}
+
+impl fmt::Debug for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Diagnostics")
+            .field("metrics", &self.metrics)
+            .field("sinks", &self.sinks.len())
+            .field("history_len", &self.history_len)
+            .finish()
+    }
+}
+
+impl AllStorages {
+    /// Records the number of entities and components of each storage as gauges of [`Diagnostics`], if present.\
+    /// Gauges are named after the component prefixed by [`Diagnostics::STORAGE_PREFIX`], storages borrowed exclusively are skipped.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{AllStoragesViewMut, Component, Diagnostics, UniqueView, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Bullet;
+    ///
+    /// let world = World::new();
+    /// world.add_unique(Diagnostics::new());
+    ///
+    /// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    /// all_storages.add_entity(Bullet);
+    /// all_storages.add_entity(Bullet);
+    ///
+    /// all_storages.record_storage_counts();
+    ///
+    /// let diagnostics = all_storages.borrow::<UniqueView<Diagnostics>>().unwrap();
+    /// let bullets = diagnostics.storage_counts().find(|(name, _)| name.ends_with("Bullet"));
+    /// assert_eq!(bullets.unwrap().1.current(), 2.0);
+    /// ```
+    pub fn record_storage_counts(&self) {
+        let counts = self
+            .iter_storages()
+            .iter()
+            .filter_map(|storage| {
+                let len = storage.len()?;
+                let name = match storage.component_name() {
+                    Some(name) => Cow::Borrowed(name),
+                    None => storage.name(),
+                };
+
+                Some((format!("{}{}", Diagnostics::STORAGE_PREFIX, name), len))
+            })
+            .collect::<Vec<_>>();
+
+        if let Ok(mut diagnostics) = self.borrow::<UniqueViewMut<'_, Diagnostics>>() {
+            for (name, len) in counts {
+                #[allow(clippy::cast_precision_loss)]
+                let len = len as f64;
+
+                diagnostics.gauge(name, len);
+            }
+        }
+    }
+}
+
+/// System recording the storage counts, see [`AllStorages::record_storage_counts`].
+pub fn record_storage_counts(all_storages: AllStoragesView<'_>) {
+    all_storages.record_storage_counts();
+}
+
+/// System ending the frame of [`Diagnostics`], see [`Diagnostics::end_frame`].
+pub fn update_diagnostics(mut diagnostics: UniqueViewMut<'_, Diagnostics>) {
+    diagnostics.end_frame();
+}
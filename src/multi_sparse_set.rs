@@ -0,0 +1,330 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::atomic_refcell::{ARef, ARefMut, ExclusiveBorrow, SharedBorrow};
+use crate::borrow::{Borrow, BorrowInfo, Mutability};
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::memory_usage::StorageMemoryUsage;
+use crate::scheduler::TypeInfo;
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::ShipHashMap;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::mem::size_of;
+use core::ops::{Deref, DerefMut};
+
+/// Storage allowing multiple `T` on the same entity, like status effects or attached colliders.
+///
+/// An entity's instances are stored contiguously and keep their insertion order.\
+/// Borrowed with [`MultiView`] and [`MultiViewMut`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{MultiView, MultiViewMut, World};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Effect {
+///     Poison,
+///     Slow,
+/// }
+///
+/// let mut world = World::new();
+/// let entity = world.add_entity(());
+///
+/// world.run(|mut effects: MultiViewMut<Effect>| {
+///     effects.insert(entity, Effect::Poison);
+///     effects.insert(entity, Effect::Slow);
+/// });
+///
+/// world.run(|effects: MultiView<Effect>| {
+///     assert_eq!(effects.get_all(entity), [Effect::Poison, Effect::Slow]);
+///     assert_eq!(effects.iter().count(), 2);
+/// });
+/// ```
+pub struct MultiSparseSet<T> {
+    groups: Vec<(EntityId, Vec<T>)>,
+    indices: ShipHashMap<EntityId, usize>,
+    len: usize,
+}
+
+impl<T> Default for MultiSparseSet<T> {
+    fn default() -> Self {
+        MultiSparseSet {
+            groups: Vec::new(),
+            indices: ShipHashMap::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<T> MultiSparseSet<T> {
+    /// Creates an empty storage.
+    pub fn new() -> MultiSparseSet<T> {
+        MultiSparseSet::default()
+    }
+    /// Adds `value` to `entity`'s instances.
+    pub fn insert(&mut self, entity: EntityId, value: T) {
+        match self.indices.get(&entity) {
+            Some(&index) => self.groups[index].1.push(value),
+            None => {
+                self.indices.insert(entity, self.groups.len());
+                self.groups.push((entity, alloc::vec![value]));
+            }
+        }
+
+        self.len += 1;
+    }
+    /// Returns all instances of `entity`, in insertion order.
+    pub fn get_all(&self, entity: EntityId) -> &[T] {
+        match self.indices.get(&entity) {
+            Some(&index) => &self.groups[index].1,
+            None => &[],
+        }
+    }
+    /// Returns all instances of `entity`, in insertion order.
+    pub fn get_all_mut(&mut self, entity: EntityId) -> &mut [T] {
+        match self.indices.get(&entity) {
+            Some(&index) => &mut self.groups[index].1,
+            None => &mut [],
+        }
+    }
+    /// Returns the number of instances of `entity`.
+    pub fn count(&self, entity: EntityId) -> usize {
+        self.get_all(entity).len()
+    }
+    /// Returns `true` if `entity` has at least one instance.
+    pub fn contains(&self, entity: EntityId) -> bool {
+        self.indices.contains_key(&entity)
+    }
+    /// Removes the instance at `index` of `entity`, the following instances are shifted.
+    pub fn remove(&mut self, entity: EntityId, index: usize) -> Option<T> {
+        let group_index = *self.indices.get(&entity)?;
+        let group = &mut self.groups[group_index].1;
+
+        if index >= group.len() {
+            return None;
+        }
+
+        let value = group.remove(index);
+        self.len -= 1;
+
+        if group.is_empty() {
+            self.remove_group(entity);
+        }
+
+        Some(value)
+    }
+    /// Removes all instances of `entity` and returns them.
+    pub fn remove_all(&mut self, entity: EntityId) -> Vec<T> {
+        match self.remove_group(entity) {
+            Some(values) => {
+                self.len -= values.len();
+
+                values
+            }
+            None => Vec::new(),
+        }
+    }
+    /// Keeps only the instances of `entity` for which `f` returns `true`.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, entity: EntityId, f: F) {
+        if let Some(&index) = self.indices.get(&entity) {
+            let group = &mut self.groups[index].1;
+            let before = group.len();
+            group.retain(f);
+            self.len -= before - group.len();
+
+            if group.is_empty() {
+                self.remove_group(entity);
+            }
+        }
+    }
+    fn remove_group(&mut self, entity: EntityId) -> Option<Vec<T>> {
+        let index = self.indices.remove(&entity)?;
+        let (_, values) = self.groups.swap_remove(index);
+
+        if let Some((moved, _)) = self.groups.get(index) {
+            self.indices.insert(*moved, index);
+        }
+
+        Some(values)
+    }
+    /// Returns an iterator over all instances and the entity they belong to.\
+    /// An entity's instances are yielded one after the other.
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, &T)> + '_ {
+        self.groups
+            .iter()
+            .flat_map(|(entity, values)| values.iter().map(move |value| (*entity, value)))
+    }
+    /// Returns an iterator over all instances and the entity they belong to.\
+    /// An entity's instances are yielded one after the other.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EntityId, &mut T)> + '_ {
+        self.groups.iter_mut().flat_map(|(entity, values)| {
+            let entity = *entity;
+            values.iter_mut().map(move |value| (entity, value))
+        })
+    }
+    /// Returns an iterator over the entities with at least one instance.
+    pub fn entities(&self) -> impl Iterator<Item = EntityId> + '_ {
+        self.groups.iter().map(|(entity, _)| *entity)
+    }
+    /// Returns the number of instances.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if there is no instance.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns the number of entities with at least one instance.
+    pub fn entity_count(&self) -> usize {
+        self.groups.len()
+    }
+    /// Removes all instances.
+    pub fn clear(&mut self) {
+        self.groups.clear();
+        self.indices.clear();
+        self.len = 0;
+    }
+}
+
+impl<T: Send + Sync + 'static> Storage for MultiSparseSet<T> {
+    #[inline]
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        self.remove_all(entity);
+    }
+    #[inline]
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        MultiSparseSet::clear(self);
+    }
+    fn memory_usage(&self) -> Option<StorageMemoryUsage> {
+        let per_group = size_of::<(EntityId, Vec<T>)>() + size_of::<(EntityId, usize)>();
+
+        Some(StorageMemoryUsage {
+            storage_name: type_name::<Self>().into(),
+            used_memory_bytes: self.len * size_of::<T>()
+                + self.groups.len() * per_group
+                + size_of::<Self>(),
+            allocated_memory_bytes: self
+                .groups
+                .iter()
+                .map(|(_, values)| values.capacity() * size_of::<T>())
+                .sum::<usize>()
+                + self.groups.capacity() * per_group
+                + size_of::<Self>(),
+            component_count: self.len,
+        })
+    }
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    fn len(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+/// Shared view over a [`MultiSparseSet`].
+pub struct MultiView<'a, T> {
+    storage: &'a MultiSparseSet<T>,
+    _all_borrow: Option<SharedBorrow<'a>>,
+    _borrow: SharedBorrow<'a>,
+}
+
+impl<T> Deref for MultiView<'_, T> {
+    type Target = MultiSparseSet<T>;
+
+    #[inline]
+    fn deref(&self) -> &MultiSparseSet<T> {
+        self.storage
+    }
+}
+
+/// Exclusive view over a [`MultiSparseSet`].
+pub struct MultiViewMut<'a, T> {
+    storage: &'a mut MultiSparseSet<T>,
+    _all_borrow: Option<SharedBorrow<'a>>,
+    _borrow: ExclusiveBorrow<'a>,
+}
+
+impl<T> Deref for MultiViewMut<'_, T> {
+    type Target = MultiSparseSet<T>;
+
+    #[inline]
+    fn deref(&self) -> &MultiSparseSet<T> {
+        self.storage
+    }
+}
+
+impl<T> DerefMut for MultiViewMut<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut MultiSparseSet<T> {
+        self.storage
+    }
+}
+
+impl<T: Send + Sync + 'static> Borrow for MultiView<'_, T> {
+    type View<'a> = MultiView<'a, T>;
+
+    #[inline]
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        _last_run: Option<TrackingTimestamp>,
+        _current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = all_storages.custom_storage_or_insert(MultiSparseSet::<T>::new)?;
+
+        let (storage, borrow) = unsafe { ARef::destructure(view) };
+
+        Ok(MultiView {
+            storage,
+            _all_borrow: all_borrow,
+            _borrow: borrow,
+        })
+    }
+}
+
+impl<T: Send + Sync + 'static> Borrow for MultiViewMut<'_, T> {
+    type View<'a> = MultiViewMut<'a, T>;
+
+    #[inline]
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        _last_run: Option<TrackingTimestamp>,
+        _current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        let view = all_storages.custom_storage_or_insert_mut(MultiSparseSet::<T>::new)?;
+
+        let (storage, borrow) = unsafe { ARefMut::destructure(view) };
+
+        Ok(MultiViewMut {
+            storage,
+            _all_borrow: all_borrow,
+            _borrow: borrow,
+        })
+    }
+}
+
+unsafe impl<T: Send + Sync + 'static> BorrowInfo for MultiView<'_, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        info.push(TypeInfo {
+            name: type_name::<MultiSparseSet<T>>().into(),
+            mutability: Mutability::Shared,
+            storage_id: StorageId::of::<MultiSparseSet<T>>(),
+            thread_safe: true,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
+
+unsafe impl<T: Send + Sync + 'static> BorrowInfo for MultiViewMut<'_, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        info.push(TypeInfo {
+            name: type_name::<MultiSparseSet<T>>().into(),
+            mutability: Mutability::Exclusive,
+            storage_id: StorageId::of::<MultiSparseSet<T>>(),
+            thread_safe: true,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>) {}
+}
@@ -18,6 +18,66 @@ impl<'a, T: ?Sized> Mut<'a, T> {
             data: f(orig.data),
         }
     }
+    /// Returns a mutable reference to the component without flagging it.
+    ///
+    /// This is an associated function that needs to be used as `Mut::bypass_change_detection(...)`.
+    #[inline]
+    pub fn bypass_change_detection(this: &mut Self) -> &mut T {
+        this.data
+    }
+    /// Consumes the [`Mut`] and returns the mutable reference with its full lifetime, the component is flagged.
+    ///
+    /// This is an associated function that needs to be used as `Mut::into_inner(...)`.
+    #[inline]
+    pub fn into_inner(this: Self) -> &'a mut T {
+        if let Some(flag) = this.flag {
+            *flag = this.current;
+        }
+
+        this.data
+    }
+}
+
+impl<T: PartialEq> Mut<'_, T> {
+    /// Replaces the component with `value` only if they're different, only flagging it when changed.\
+    /// Returns `true` if the component was replaced.
+    ///
+    /// This is an associated function that needs to be used as `Mut::set_if_neq(...)`.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{track, Component, Get, Mut, ViewMut, World};
+    ///
+    /// #[derive(Component, PartialEq)]
+    /// #[track(Modification)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity = world.add_entity(U32(0));
+    ///
+    /// let mut u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    /// assert!(!Mut::set_if_neq(&mut (&mut u32s).get(entity).unwrap(), U32(0)));
+    /// assert!(!u32s.is_modified(entity));
+    ///
+    /// assert!(Mut::set_if_neq(&mut (&mut u32s).get(entity).unwrap(), U32(1)));
+    /// assert!(u32s.is_modified(entity));
+    /// ```
+    #[inline]
+    pub fn set_if_neq(this: &mut Self, value: T) -> bool {
+        if *this.data == value {
+            return false;
+        }
+
+        *this.data = value;
+
+        if let Some(flag) = &mut this.flag {
+            **flag = this.current;
+        }
+
+        true
+    }
 }
 
 impl<T: ?Sized> core::ops::Deref for Mut<'_, T> {
@@ -63,3 +123,38 @@ impl<T: ?Sized + core::fmt::Debug> core::fmt::Debug for Mut<'_, T> {
         self.data.fmt(f)
     }
 }
+
+/// Handle to the modification flag of a component.
+///
+/// Returned next to a `&mut T` by [`ViewMut::iter_mut_flagged`], modifying the component
+/// through the reference doesn't flag it, [`ModificationFlag::flag`] has to be called.\
+/// When the storage doesn't track modification, flagging does nothing.
+///
+/// [`ViewMut::iter_mut_flagged`]: crate::ViewMut::iter_mut_flagged()
+pub struct ModificationFlag<'a> {
+    pub(crate) flag: Option<&'a mut TrackingTimestamp>,
+    pub(crate) current: TrackingTimestamp,
+}
+
+impl ModificationFlag<'_> {
+    /// Flags the component as modified.
+    #[inline]
+    pub fn flag(&mut self) {
+        if let Some(flag) = &mut self.flag {
+            **flag = self.current;
+        }
+    }
+    /// Returns `true` if the storage tracks modification.
+    #[inline]
+    pub fn is_tracking(&self) -> bool {
+        self.flag.is_some()
+    }
+}
+
+impl core::fmt::Debug for ModificationFlag<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ModificationFlag")
+            .field("is_tracking", &self.is_tracking())
+            .finish()
+    }
+}
@@ -0,0 +1,107 @@
+use crate::component::Unique;
+use crate::storage::StorageId;
+use crate::unique::UniqueStorage;
+use crate::world::World;
+use alloc::vec::Vec;
+use core::any::type_name;
+
+/// A group of uniques, workloads,... added to a [`World`] in one go.
+///
+/// Plugins declare the uniques they add and the ones they need from other plugins.\
+/// [`World::add_plugin`] and [`World::add_plugins`] check them before building anything,
+/// a missing unique is reported right away instead of at its first borrow.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Plugin, PluginDependencies, Unique, UniqueView, World};
+///
+/// #[derive(Unique)]
+/// struct Gravity(f32);
+///
+/// #[derive(Unique)]
+/// struct Physics {
+///     gravity: f32,
+/// }
+///
+/// struct GravityPlugin;
+///
+/// impl Plugin for GravityPlugin {
+///     fn dependencies(&self, dependencies: &mut PluginDependencies) {
+///         dependencies.provides::<Gravity>();
+///     }
+///     fn build(&self, world: &World) {
+///         world.add_unique(Gravity(-9.81));
+///     }
+/// }
+///
+/// struct PhysicsPlugin;
+///
+/// impl Plugin for PhysicsPlugin {
+///     fn dependencies(&self, dependencies: &mut PluginDependencies) {
+///         dependencies.requires::<Gravity>().provides::<Physics>();
+///     }
+///     fn build(&self, world: &World) {
+///         let gravity = world.borrow::<UniqueView<Gravity>>().unwrap().0;
+///         world.add_unique(Physics { gravity });
+///     }
+/// }
+///
+/// let world = World::new();
+///
+/// // built in dependency order
+/// world
+///     .add_plugins([Box::new(PhysicsPlugin) as Box<dyn Plugin>, Box::new(GravityPlugin)])
+///     .unwrap();
+///
+/// assert_eq!(world.borrow::<UniqueView<Physics>>().unwrap().gravity, -9.81);
+/// ```
+pub trait Plugin {
+    /// Name used in errors.
+    fn name(&self) -> &'static str {
+        type_name::<Self>()
+    }
+    /// Declares the uniques added by [`build`](Plugin::build) and the ones it needs.
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        let _ = dependencies;
+    }
+    /// Adds the plugin to `world`.\
+    /// All required uniques are present.
+    fn build(&self, world: &World);
+}
+
+/// Uniques a [`Plugin`] adds and needs.
+#[derive(Default)]
+pub struct PluginDependencies {
+    pub(crate) provides: Vec<UniqueDependency>,
+    pub(crate) requires: Vec<UniqueDependency>,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct UniqueDependency {
+    pub(crate) name: &'static str,
+    pub(crate) storage_id: StorageId,
+}
+
+impl UniqueDependency {
+    fn of<T: Unique>() -> UniqueDependency {
+        UniqueDependency {
+            name: type_name::<T>(),
+            storage_id: StorageId::of::<UniqueStorage<T>>(),
+        }
+    }
+}
+
+impl PluginDependencies {
+    /// The plugin adds the `T` unique.
+    pub fn provides<T: Unique>(&mut self) -> &mut PluginDependencies {
+        self.provides.push(UniqueDependency::of::<T>());
+
+        self
+    }
+    /// The plugin needs the `T` unique, added by an other plugin or directly to the `World`.
+    pub fn requires<T: Unique>(&mut self) -> &mut PluginDependencies {
+        self.requires.push(UniqueDependency::of::<T>());
+
+        self
+    }
+}
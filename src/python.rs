@@ -0,0 +1,187 @@
+//! [PyO3](https://pyo3.rs) bindings to drive a [`World`] from Python tooling.
+//!
+//! Components are accessed by name as raw bytes, the same way as the [`capi`](crate::capi) functions,
+//! only components registered in a [`CapiRegistry`] are accessible.\
+//! The extension module is built by the crate defining the components:
+//!
+//! ```ignore
+//! use pyo3::prelude::*;
+//! use shipyard::capi::CapiRegistry;
+//! use shipyard::python::PyWorld;
+//! use shipyard::World;
+//!
+//! #[pyfunction]
+//! fn new_world() -> PyWorld {
+//!     let mut registry = CapiRegistry::new();
+//!     registry.register::<Health>();
+//!
+//!     PyWorld::new(World::new(), registry)
+//! }
+//!
+//! #[pymodule]
+//! fn game(py: Python<'_>, m: &PyModule) -> PyResult<()> {
+//!     shipyard::python::register(py, m)?;
+//!     m.add_function(wrap_pyfunction!(new_world, m)?)
+//! }
+//! ```
+//!
+//! ```python
+//! world = game.new_world()
+//! entity = world.spawn()
+//! world.set_component(entity, "Health", (10).to_bytes(4, "little"))
+//! world.run_workload("update")
+//! assert world.query(["Health"]) == [entity]
+//! ```
+
+use crate::capi::{run_workload_by_name, CapiRegistry, RawComponent, ShipyardStatus};
+use crate::entity_id::EntityId;
+use crate::world::World;
+use crate::ShipHashSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// Python `World` class, wraps a [`World`] and the components accessible by name.
+#[pyclass(name = "World", unsendable)]
+pub struct PyWorld {
+    world: World,
+    registry: CapiRegistry,
+}
+
+impl PyWorld {
+    /// Wraps `world`, `registry` lists the components accessible by name.
+    pub fn new(world: World, registry: CapiRegistry) -> PyWorld {
+        PyWorld { world, registry }
+    }
+    /// Returns the inner `World`.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+    /// Returns the inner `World`.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+    fn alive(&self, entity: u64) -> PyResult<EntityId> {
+        EntityId::from_inner(entity)
+            .filter(|&entity| self.world.is_alive(entity))
+            .ok_or_else(|| PyValueError::new_err(format!("entity {} isn't alive", entity)))
+    }
+    fn component(&self, name: &str) -> PyResult<&RawComponent> {
+        self.registry
+            .component(name)
+            .map_err(|_| PyKeyError::new_err(format!("component {:?} isn't registered", name)))
+    }
+}
+
+#[pymethods]
+impl PyWorld {
+    /// Creates an empty `World` without registered components.
+    #[new]
+    fn py_new() -> PyWorld {
+        PyWorld::new(World::new(), CapiRegistry::new())
+    }
+    /// Adds an entity without component, returns its id.
+    fn spawn(&mut self) -> u64 {
+        self.world.add_entity(()).inner()
+    }
+    /// Deletes an entity and all its components, returns `True` if it was alive.
+    fn delete(&mut self, entity: u64) -> bool {
+        EntityId::from_inner(entity).is_some_and(|entity| self.world.delete_entity(entity))
+    }
+    /// Returns `True` if `entity` is alive.
+    fn is_alive(&self, entity: u64) -> bool {
+        EntityId::from_inner(entity).is_some_and(|entity| self.world.is_alive(entity))
+    }
+    /// Returns the names of the accessible components.
+    fn component_names(&self) -> Vec<&'static str> {
+        self.registry.names().collect()
+    }
+    /// Adds the component named `name` to `entity`, replacing the previous one.
+    fn set_component(&mut self, entity: u64, name: &str, data: &[u8]) -> PyResult<()> {
+        let entity = self.alive(entity)?;
+        let component = self.component(name)?;
+
+        if component.size != data.len() {
+            return Err(PyValueError::new_err(format!(
+                "component {:?} is {} bytes, got {}",
+                name,
+                component.size,
+                data.len()
+            )));
+        }
+
+        let add = component.add;
+        // SAFETY: the length was checked and registered components accept any bytes
+        unsafe { add(self.world.all_storages.get_mut(), entity, data.as_ptr()) };
+
+        Ok(())
+    }
+    /// Returns the bytes of the component named `name` of `entity`, `None` if it doesn't have one.
+    fn get_component(&self, py: Python<'_>, entity: u64, name: &str) -> PyResult<Option<PyObject>> {
+        let entity = self.alive(entity)?;
+        let component = self.component(name)?;
+
+        let all_storages = self
+            .world
+            .all_storages()
+            .map_err(|err| PyRuntimeError::new_err(format!("{:?}", err)))?;
+
+        let mut bytes = vec![0; component.size];
+        // SAFETY: `bytes` is the size of the component
+        if unsafe { (component.get)(&all_storages, entity, bytes.as_mut_ptr()) } {
+            Ok(Some(PyBytes::new(py, &bytes).into()))
+        } else {
+            Ok(None)
+        }
+    }
+    /// Returns the entities owning all components listed in `names`.
+    fn query(&self, names: Vec<String>) -> PyResult<Vec<u64>> {
+        let all_storages = self
+            .world
+            .all_storages()
+            .map_err(|err| PyRuntimeError::new_err(format!("{:?}", err)))?;
+
+        let mut entities: Option<Vec<EntityId>> = None;
+        for name in &names {
+            let ids = (self.component(name)?.ids)(&all_storages);
+
+            entities = Some(match entities {
+                Some(mut entities) => {
+                    let ids: ShipHashSet<EntityId> = ids.into_iter().collect();
+                    entities.retain(|entity| ids.contains(entity));
+                    entities
+                }
+                None => ids,
+            });
+        }
+
+        Ok(entities
+            .unwrap_or_default()
+            .into_iter()
+            .map(EntityId::inner)
+            .collect())
+    }
+    /// Runs the workload named `name`.
+    fn run_workload(&self, name: &str) -> PyResult<()> {
+        match run_workload_by_name(&self.world, name) {
+            ShipyardStatus::Ok => Ok(()),
+            ShipyardStatus::MissingWorkload => Err(PyKeyError::new_err(format!(
+                "workload {:?} doesn't exist",
+                name
+            ))),
+            _ => Err(PyRuntimeError::new_err(format!(
+                "workload {:?} failed",
+                name
+            ))),
+        }
+    }
+}
+
+/// Adds the `World` class to `module`.
+pub fn register(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    module.add_class::<PyWorld>()
+}
@@ -70,6 +70,17 @@ macro_rules! impl_remove_component {
                 )+)
             }
         }
+
+        impl<$($storage: Remove),+> Remove for &mut ($($storage,)+) {
+            type Out = ($($storage::Out,)+);
+
+            #[inline]
+            fn remove(&mut self, entity: EntityId) -> Self::Out {
+                ($(
+                    self.$index.remove(entity),
+                )+)
+            }
+        }
     }
 }
 
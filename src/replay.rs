@@ -0,0 +1,402 @@
+use crate::all_storages::AllStorages;
+use crate::component::{component_name, Component};
+use crate::entities::Entities;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::sparse_set::{SparseSet, TupleAddComponent};
+use crate::storage::StorageId;
+use crate::ShipHashMap;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::Any;
+
+const MAGIC: &[u8; 4] = b"SHRP";
+const VERSION: u8 = 1;
+
+/// A single recorded `World` or `AllStorages` operation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReplayCommand {
+    /// An entity was created or spawned with this id.
+    AddEntity(EntityId),
+    /// An entity and all its components were deleted.
+    DeleteEntity(EntityId),
+    /// All components of an entity were deleted.
+    Strip(EntityId),
+    /// A component was added to an entity or replaced.
+    AddComponent {
+        #[allow(missing_docs)]
+        entity: EntityId,
+        /// Name the component was registered with.
+        component: Cow<'static, str>,
+        /// Component serialized by the encoder passed to [`ReplayRegistry::register`].
+        data: Vec<u8>,
+    },
+    /// A component was deleted or removed from an entity.
+    DeleteComponent {
+        #[allow(missing_docs)]
+        entity: EntityId,
+        /// Name the component was registered with.
+        component: Cow<'static, str>,
+    },
+    /// All entities and components were deleted.
+    Clear,
+}
+
+/// Ordered list of [`ReplayCommand`]s recorded from `World` and `AllStorages` operations.
+///
+/// Changes made through views aren't part of it, see [`World::start_recording`] for what is recorded.\
+/// It can be turned into bytes to be saved to a file and played back with [`World::replay`].
+///
+/// [`World::start_recording`]: crate::World::start_recording()
+/// [`World::replay`]: crate::World::replay()
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayStream {
+    commands: Vec<ReplayCommand>,
+}
+
+impl ReplayStream {
+    /// Creates an empty stream.
+    pub fn new() -> ReplayStream {
+        ReplayStream::default()
+    }
+    /// Returns the recorded commands in the order they happened.
+    pub fn commands(&self) -> &[ReplayCommand] {
+        &self.commands
+    }
+    /// Appends a command at the end of the stream.
+    pub fn push(&mut self, command: ReplayCommand) {
+        self.commands.push(command);
+    }
+    /// Returns the number of commands in the stream.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+    /// Returns `true` if the stream doesn't contain any command.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+    /// Encodes the stream in a compact binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+
+        for command in &self.commands {
+            match command {
+                ReplayCommand::AddEntity(entity) => {
+                    bytes.push(0);
+                    bytes.extend_from_slice(&entity.inner().to_le_bytes());
+                }
+                ReplayCommand::DeleteEntity(entity) => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(&entity.inner().to_le_bytes());
+                }
+                ReplayCommand::Strip(entity) => {
+                    bytes.push(2);
+                    bytes.extend_from_slice(&entity.inner().to_le_bytes());
+                }
+                ReplayCommand::AddComponent {
+                    entity,
+                    component,
+                    data,
+                } => {
+                    bytes.push(3);
+                    bytes.extend_from_slice(&entity.inner().to_le_bytes());
+                    write_slice(&mut bytes, component.as_bytes());
+                    write_slice(&mut bytes, data);
+                }
+                ReplayCommand::DeleteComponent { entity, component } => {
+                    bytes.push(4);
+                    bytes.extend_from_slice(&entity.inner().to_le_bytes());
+                    write_slice(&mut bytes, component.as_bytes());
+                }
+                ReplayCommand::Clear => bytes.push(5),
+            }
+        }
+
+        bytes
+    }
+    /// Decodes a stream previously encoded with [`ReplayStream::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<ReplayStream, error::Replay> {
+        let mut reader = Reader(bytes);
+
+        if reader.take(MAGIC.len())? != MAGIC || reader.take(1)? != [VERSION] {
+            return Err(error::Replay::InvalidStream);
+        }
+
+        let mut commands = Vec::new();
+        while !reader.0.is_empty() {
+            let command = match reader.take(1)?[0] {
+                0 => ReplayCommand::AddEntity(reader.entity()?),
+                1 => ReplayCommand::DeleteEntity(reader.entity()?),
+                2 => ReplayCommand::Strip(reader.entity()?),
+                3 => ReplayCommand::AddComponent {
+                    entity: reader.entity()?,
+                    component: reader.name()?,
+                    data: reader.slice()?.into(),
+                },
+                4 => ReplayCommand::DeleteComponent {
+                    entity: reader.entity()?,
+                    component: reader.name()?,
+                },
+                5 => ReplayCommand::Clear,
+                _ => return Err(error::Replay::InvalidStream),
+            };
+
+            commands.push(command);
+        }
+
+        Ok(ReplayStream { commands })
+    }
+    pub(crate) fn play(
+        &self,
+        all_storages: &mut AllStorages,
+        registry: &ReplayRegistry,
+    ) -> Result<(), error::Replay> {
+        for command in &self.commands {
+            match command {
+                ReplayCommand::AddEntity(entity) => {
+                    if !all_storages.spawn(*entity) {
+                        return Err(error::Replay::EntityIdMismatch(*entity));
+                    }
+                }
+                ReplayCommand::DeleteEntity(entity) => {
+                    all_storages.delete_entity(*entity);
+                }
+                ReplayCommand::Strip(entity) => all_storages.strip(*entity),
+                ReplayCommand::AddComponent {
+                    entity,
+                    component,
+                    data,
+                } => {
+                    let codec = registry.codec_by_name(component)?;
+
+                    if !all_storages
+                        .exclusive_storage_mut::<Entities>()
+                        .unwrap()
+                        .is_alive(*entity)
+                    {
+                        return Err(error::Replay::EntityIsNotAlive(*entity));
+                    }
+
+                    if !codec.insert(all_storages, *entity, data) {
+                        return Err(error::Replay::InvalidComponentData(component.clone()));
+                    }
+                }
+                ReplayCommand::DeleteComponent { entity, component } => {
+                    registry
+                        .codec_by_name(component)?
+                        .delete(all_storages, *entity);
+                }
+                ReplayCommand::Clear => all_storages.clear(),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn write_slice(bytes: &mut Vec<u8>, slice: &[u8]) {
+    bytes.extend_from_slice(&(slice.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(slice);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], error::Replay> {
+        if self.0.len() < len {
+            return Err(error::Replay::InvalidStream);
+        }
+
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        Ok(taken)
+    }
+    fn entity(&mut self) -> Result<EntityId, error::Replay> {
+        let mut inner = [0; 8];
+        inner.copy_from_slice(self.take(8)?);
+
+        EntityId::from_inner(u64::from_le_bytes(inner)).ok_or(error::Replay::InvalidStream)
+    }
+    fn slice(&mut self) -> Result<&'a [u8], error::Replay> {
+        let mut len = [0; 4];
+        len.copy_from_slice(self.take(4)?);
+
+        self.take(u32::from_le_bytes(len) as usize)
+    }
+    fn name(&mut self) -> Result<Cow<'static, str>, error::Replay> {
+        let name = core::str::from_utf8(self.slice()?).map_err(|_| error::Replay::InvalidStream)?;
+
+        Ok(Cow::Owned(String::from(name)))
+    }
+}
+
+/// Type erased (de)serialization functions of a component.
+trait ReplayCodec: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn encode(&self, component: &dyn Any) -> Option<Vec<u8>>;
+    fn encode_stored(&self, all_storages: &mut AllStorages, entity: EntityId) -> Option<Vec<u8>>;
+    fn contains(&self, all_storages: &mut AllStorages, entity: EntityId) -> bool;
+    fn insert(&self, all_storages: &mut AllStorages, entity: EntityId, data: &[u8]) -> bool;
+    fn delete(&self, all_storages: &mut AllStorages, entity: EntityId);
+}
+
+struct Codec<T> {
+    encode: fn(&T) -> Vec<u8>,
+    decode: fn(&[u8]) -> Option<T>,
+}
+
+impl<T: Component + Send + Sync> ReplayCodec for Codec<T> {
+    fn name(&self) -> &'static str {
+        component_name::<T>()
+    }
+    fn encode(&self, component: &dyn Any) -> Option<Vec<u8>> {
+        component.downcast_ref::<T>().map(self.encode)
+    }
+    fn encode_stored(&self, all_storages: &mut AllStorages, entity: EntityId) -> Option<Vec<u8>> {
+        all_storages
+            .exclusive_storage_mut::<SparseSet<T>>()
+            .ok()?
+            .private_get(entity)
+            .map(self.encode)
+    }
+    fn contains(&self, all_storages: &mut AllStorages, entity: EntityId) -> bool {
+        all_storages
+            .exclusive_storage_mut::<SparseSet<T>>()
+            .is_ok_and(|sparse_set| sparse_set.contains(entity))
+    }
+    fn insert(&self, all_storages: &mut AllStorages, entity: EntityId, data: &[u8]) -> bool {
+        if let Some(component) = (self.decode)(data) {
+            let current = all_storages.get_current();
+            component.add_component(all_storages, entity, current);
+
+            true
+        } else {
+            false
+        }
+    }
+    fn delete(&self, all_storages: &mut AllStorages, entity: EntityId) {
+        all_storages.delete_component::<(T,)>(entity);
+    }
+}
+
+/// List of components whose values will be part of a [`ReplayStream`].
+///
+/// Components are identified by their [`Component::NAME`] or type name.
+/// The same registry has to be used to record and play back a stream.
+#[derive(Clone, Default)]
+pub struct ReplayRegistry {
+    codecs: ShipHashMap<StorageId, Arc<dyn ReplayCodec>>,
+    names: ShipHashMap<&'static str, StorageId>,
+}
+
+impl ReplayRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> ReplayRegistry {
+        ReplayRegistry::default()
+    }
+    /// Registers `T` with the functions used to turn it into bytes and back.
+    /// `decode` returns `None` when the bytes don't represent a valid `T`.
+    pub fn register<T: Component + Send + Sync>(
+        &mut self,
+        encode: fn(&T) -> Vec<u8>,
+        decode: fn(&[u8]) -> Option<T>,
+    ) -> &mut ReplayRegistry {
+        let storage_id = StorageId::of::<SparseSet<T>>();
+
+        self.codecs
+            .insert(storage_id, Arc::new(Codec { encode, decode }));
+        self.names.insert(component_name::<T>(), storage_id);
+
+        self
+    }
+    /// Returns `true` if `T` was registered.
+    pub fn is_registered<T: Component>(&self) -> bool {
+        self.codecs.contains_key(&StorageId::of::<SparseSet<T>>())
+    }
+    fn codec_by_name(&self, name: &str) -> Result<&dyn ReplayCodec, error::Replay> {
+        self.names
+            .get(name)
+            .and_then(|storage_id| self.codecs.get(storage_id))
+            .map(|codec| &**codec)
+            .ok_or_else(|| error::Replay::UnregisteredComponent(Cow::Owned(String::from(name))))
+    }
+}
+
+/// Recording state of an `AllStorages`.
+pub(crate) struct Recording {
+    registry: ReplayRegistry,
+    stream: ReplayStream,
+}
+
+impl Recording {
+    pub(crate) fn new(registry: ReplayRegistry) -> Recording {
+        Recording {
+            registry,
+            stream: ReplayStream::new(),
+        }
+    }
+    pub(crate) fn into_stream(self) -> ReplayStream {
+        self.stream
+    }
+    pub(crate) fn push(&mut self, command: ReplayCommand) {
+        self.stream.push(command);
+    }
+    /// Records `component` if its type was registered.
+    pub(crate) fn record_component<T: Component>(&mut self, entity: EntityId, component: &T) {
+        if let Some(codec) = self.registry.codecs.get(&StorageId::of::<SparseSet<T>>()) {
+            if let Some(data) = codec.encode(component) {
+                self.stream.push(ReplayCommand::AddComponent {
+                    entity,
+                    component: Cow::Borrowed(codec.name()),
+                    data,
+                });
+            }
+        }
+    }
+    /// Records all registered components `entity` currently owns.
+    pub(crate) fn record_entity(&mut self, all_storages: &mut AllStorages, entity: EntityId) {
+        self.stream.push(ReplayCommand::AddEntity(entity));
+
+        for codec in self.registry.codecs.values() {
+            if let Some(data) = codec.encode_stored(all_storages, entity) {
+                self.stream.push(ReplayCommand::AddComponent {
+                    entity,
+                    component: Cow::Borrowed(codec.name()),
+                    data,
+                });
+            }
+        }
+    }
+    /// Returns which registered components `entity` owns.
+    pub(crate) fn presence(&self, all_storages: &mut AllStorages, entity: EntityId) -> Vec<bool> {
+        self.registry
+            .codecs
+            .values()
+            .map(|codec| codec.contains(all_storages, entity))
+            .collect()
+    }
+    /// Records the deletion of all registered components `entity` owned according to `before` and no longer owns.
+    pub(crate) fn record_deletions(
+        &mut self,
+        all_storages: &mut AllStorages,
+        entity: EntityId,
+        before: &[bool],
+    ) {
+        for (codec, &was_present) in self.registry.codecs.values().zip(before) {
+            if was_present && !codec.contains(all_storages, entity) {
+                self.stream.push(ReplayCommand::DeleteComponent {
+                    entity,
+                    component: Cow::Borrowed(codec.name()),
+                });
+            }
+        }
+    }
+}
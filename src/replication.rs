@@ -0,0 +1,826 @@
+mod encoding;
+
+pub use encoding::{BitReader, BitWriter, Quantizer, ReplicationEncoder};
+
+use crate::all_storages::AllStorages;
+use crate::component::{component_name, Component};
+use crate::component_registrar::{ComponentRegistrar, TupleRegisterComponent};
+use crate::compression::Compressor;
+use crate::entities::Entities;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::snapshot::{Encoding, EntityIdMap};
+use crate::sparse_set::SparseSet;
+use crate::storage::StorageId;
+use crate::tracking::TrackingTimestamp;
+use crate::{ShipHashMap, ShipHashSet};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::Any;
+use encoding::ComponentEncoding;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const MAGIC: &[u8; 4] = b"SHRD";
+const VERSION: u8 = 1;
+
+/// Changes of a replicated storage since a given tick.
+#[derive(Default)]
+struct StorageChanges {
+    /// Entities whose component was inserted since the tick.
+    inserted: Vec<EntityId>,
+    /// Entities whose component was inserted before the tick.
+    existing: Vec<EntityId>,
+    /// Components inserted or modified since the tick.
+    changed: Vec<(EntityId, Vec<u8>)>,
+    /// Entities that lost their component since the tick and didn't get a new one.
+    removed: Vec<EntityId>,
+}
+
+/// Type erased access to a replicated storage.
+trait ReplicationCodec: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn name(&self) -> &'static str;
+    fn enable_tracking(&self, all_storages: &mut AllStorages);
+    /// Only entities passing `is_included` are part of the changes.
+    /// Entities passing `is_full` have their component sent even if it didn't change and no removal.
+    fn collect(
+        &self,
+        all_storages: &mut AllStorages,
+        since: TrackingTimestamp,
+        tick: TrackingTimestamp,
+        is_included: &dyn Fn(EntityId) -> bool,
+        is_full: &dyn Fn(EntityId) -> bool,
+    ) -> StorageChanges;
+    fn holders(&self, all_storages: &mut AllStorages) -> Vec<EntityId>;
+    fn clear_older_than(&self, all_storages: &mut AllStorages, timestamp: TrackingTimestamp);
+    /// Returns `None` if a component couldn't be deserialized.
+    fn decode(&self, entries: &[(EntityId, Vec<u8>)]) -> Option<Box<dyn Any>>;
+    /// Replaces the `EntityId` of each decoded component and the references it contains.
+    fn remap(&self, components: &mut dyn Any, map: &EntityIdMap);
+    fn insert(&self, all_storages: &mut AllStorages, components: Box<dyn Any>);
+    fn delete(&self, all_storages: &mut AllStorages, entity: EntityId);
+}
+
+type EntityVisitor<T> = fn(&mut T, &mut dyn FnMut(&mut EntityId));
+
+struct Codec<T> {
+    encoding: ComponentEncoding<T>,
+    entity_visitor: Option<EntityVisitor<T>>,
+}
+
+impl<T: Component + Send + Sync> ReplicationCodec for Codec<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn name(&self) -> &'static str {
+        component_name::<T>()
+    }
+    fn enable_tracking(&self, all_storages: &mut AllStorages) {
+        let current = all_storages.get_current();
+
+        let sparse_set = all_storages
+            .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<T>>(), SparseSet::<T>::new);
+
+        let was_tracking_insertion = sparse_set.is_tracking_insertion();
+        sparse_set.track_all();
+
+        // components already present are sent to clients like new ones
+        if !was_tracking_insertion {
+            sparse_set.insertion_data.fill(current);
+        }
+    }
+    fn collect(
+        &self,
+        all_storages: &mut AllStorages,
+        since: TrackingTimestamp,
+        tick: TrackingTimestamp,
+        is_included: &dyn Fn(EntityId) -> bool,
+        is_full: &dyn Fn(EntityId) -> bool,
+    ) -> StorageChanges {
+        let mut changes = StorageChanges::default();
+
+        let sparse_set = match all_storages.exclusive_storage_mut::<SparseSet<T>>() {
+            Ok(sparse_set) => sparse_set,
+            Err(_) => return changes,
+        };
+
+        for (index, (&entity, component)) in
+            sparse_set.dense.iter().zip(&sparse_set.data).enumerate()
+        {
+            if !is_included(entity) {
+                continue;
+            }
+
+            let is_inserted = sparse_set
+                .insertion_data
+                .get(index)
+                .is_some_and(|timestamp| timestamp.is_within(since, tick));
+            let is_modified = sparse_set
+                .modification_data
+                .get(index)
+                .is_some_and(|timestamp| timestamp.is_within(since, tick));
+
+            if is_inserted {
+                changes.inserted.push(entity);
+            } else {
+                changes.existing.push(entity);
+            }
+
+            if is_inserted || is_modified || is_full(entity) {
+                if let Some(data) = self.encoding.serialize(component) {
+                    changes.changed.push((entity, data));
+                }
+            }
+        }
+
+        let deleted = sparse_set
+            .deletion_data
+            .iter()
+            .map(|(entity, timestamp, _)| (*entity, *timestamp));
+        let removed = sparse_set.removal_data.iter().copied();
+
+        let mut seen = ShipHashSet::default();
+        for (entity, timestamp) in deleted.chain(removed) {
+            if timestamp.is_within(since, tick)
+                && is_included(entity)
+                && !is_full(entity)
+                && !sparse_set.contains(entity)
+                && seen.insert(entity)
+            {
+                changes.removed.push(entity);
+            }
+        }
+
+        changes
+    }
+    fn holders(&self, all_storages: &mut AllStorages) -> Vec<EntityId> {
+        match all_storages.exclusive_storage_mut::<SparseSet<T>>() {
+            Ok(sparse_set) => sparse_set.dense.clone(),
+            Err(_) => Vec::new(),
+        }
+    }
+    fn clear_older_than(&self, all_storages: &mut AllStorages, timestamp: TrackingTimestamp) {
+        if let Ok(sparse_set) = all_storages.exclusive_storage_mut::<SparseSet<T>>() {
+            sparse_set.clear_all_removed_and_deleted_older_than_timestamp(timestamp);
+        }
+    }
+    fn decode(&self, entries: &[(EntityId, Vec<u8>)]) -> Option<Box<dyn Any>> {
+        let components = entries
+            .iter()
+            .map(|(entity, bytes)| Some((*entity, self.encoding.deserialize(bytes)?)))
+            .collect::<Option<Vec<(EntityId, T)>>>()?;
+
+        Some(Box::new(components))
+    }
+    fn remap(&self, components: &mut dyn Any, map: &EntityIdMap) {
+        if let Some(components) = components.downcast_mut::<Vec<(EntityId, T)>>() {
+            for (entity, component) in components {
+                if let Some(new) = map.get(*entity) {
+                    *entity = new;
+                }
+
+                if let Some(visitor) = self.entity_visitor {
+                    visitor(component, &mut |reference| {
+                        if let Some(new) = map.get(*reference) {
+                            *reference = new;
+                        }
+                    });
+                }
+            }
+        }
+    }
+    fn insert(&self, all_storages: &mut AllStorages, components: Box<dyn Any>) {
+        if let Ok(components) = components.downcast::<Vec<(EntityId, T)>>() {
+            let current = all_storages.get_current();
+
+            all_storages
+                .exclusive_storage_or_insert_mut(
+                    StorageId::of::<SparseSet<T>>(),
+                    SparseSet::<T>::new,
+                )
+                .private_extend_from_iter(*components, current);
+        }
+    }
+    fn delete(&self, all_storages: &mut AllStorages, entity: EntityId) {
+        let current = all_storages.get_current();
+
+        if let Ok(sparse_set) = all_storages.exclusive_storage_mut::<SparseSet<T>>() {
+            sparse_set.dyn_delete(entity, current);
+        }
+    }
+}
+
+/// List of the components sent over the network.
+///
+/// Components are identified by their [`Component::NAME`] or type name, the server and clients have to register the same types.
+#[derive(Clone, Default)]
+pub struct ReplicationRegistry {
+    // ordered by registration, deltas of the same World are identical
+    codecs: Vec<Arc<dyn ReplicationCodec>>,
+    names: ShipHashMap<&'static str, usize>,
+}
+
+impl ReplicationRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> ReplicationRegistry {
+        ReplicationRegistry::default()
+    }
+    /// Registers `T` as replicated.
+    pub fn register<T: Component + Send + Sync + Serialize + DeserializeOwned>(
+        &mut self,
+    ) -> &mut ReplicationRegistry {
+        self.insert_codec::<T>(Codec {
+            encoding: ComponentEncoding::Bytes(Encoding::bincode()),
+            entity_visitor: None,
+        });
+
+        self
+    }
+    /// Registers `T` as replicated, its components are converted by `encoder` instead of going through serde.\
+    /// Registering a component again replaces its encoder.
+    ///
+    /// See [`ReplicationEncoder`].
+    pub fn register_with_encoder<T, E>(&mut self, encoder: E) -> &mut ReplicationRegistry
+    where
+        T: Component + Send + Sync,
+        E: ReplicationEncoder<T>,
+    {
+        self.insert_codec::<T>(Codec {
+            encoding: ComponentEncoding::Custom(Arc::new(encoder)),
+            entity_visitor: None,
+        });
+
+        self
+    }
+    /// Registers `T` as replicated, its components are copied as is instead of going through serde.\
+    /// See [`SnapshotRegistry::register_pod`].
+    ///
+    /// [`SnapshotRegistry::register_pod`]: crate::SnapshotRegistry::register_pod()
+    #[cfg(feature = "bytemuck")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn register_pod<T>(&mut self) -> &mut ReplicationRegistry
+    where
+        T: Component + Send + Sync + Serialize + DeserializeOwned + bytemuck::Pod,
+    {
+        self.insert_codec::<T>(Codec {
+            encoding: ComponentEncoding::Bytes(Encoding::pod()),
+            entity_visitor: None,
+        });
+
+        self
+    }
+    /// Registers the `EntityId`s contained in `T`.\
+    /// When a delta is applied, `visitor` is called on each `T` and has to call the function it receives with each `EntityId` the component contains.
+    /// References to replicated entities are then replaced by their id on the client.
+    ///
+    /// ### Panics
+    ///
+    /// - `T` isn't registered.
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn register_entity_references<T>(
+        &mut self,
+        visitor: fn(&mut T, &mut dyn FnMut(&mut EntityId)),
+    ) -> &mut ReplicationRegistry
+    where
+        T: Component + Send + Sync,
+    {
+        let encoding = self
+            .names
+            .get(component_name::<T>())
+            .and_then(|&index| self.codecs[index].as_any().downcast_ref::<Codec<T>>())
+            .map(|codec| codec.encoding.clone())
+            .unwrap_or_else(|| {
+                panic!(
+                    "{} is not part of the replication registry.",
+                    component_name::<T>()
+                )
+            });
+
+        self.insert_codec::<T>(Codec {
+            encoding,
+            entity_visitor: Some(visitor),
+        });
+
+        self
+    }
+    /// Registers the components of `T` that declared `serialize` with `#[derive(Component)]`.\
+    /// See [`ComponentRegistrar`].
+    ///
+    /// [`ComponentRegistrar`]: crate::ComponentRegistrar
+    pub fn register_components<T: TupleRegisterComponent>(&mut self) -> &mut ReplicationRegistry {
+        T::register_components(&mut ComponentRegistrar::replication(self));
+
+        self
+    }
+    /// Returns `true` if `T` was registered.
+    pub fn is_registered<T: Component>(&self) -> bool {
+        self.names.contains_key(component_name::<T>())
+    }
+    fn insert_codec<T: Component + Send + Sync>(&mut self, codec: Codec<T>) {
+        let name = component_name::<T>();
+
+        match self.names.get(name) {
+            Some(&index) => self.codecs[index] = Arc::new(codec),
+            None => {
+                self.names.insert(name, self.codecs.len());
+                self.codecs.push(Arc::new(codec));
+            }
+        }
+    }
+    fn codec_by_name(&self, name: &str) -> Option<&dyn ReplicationCodec> {
+        self.names.get(name).map(|&index| &*self.codecs[index])
+    }
+}
+
+/// Changes of a replicated component type.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct ComponentDelta {
+    name: Cow<'static, str>,
+    changed: Vec<(EntityId, Vec<u8>)>,
+    removed: Vec<EntityId>,
+}
+
+/// Everything that happened to the replicated components of a `World` since a tick.
+///
+/// Created by [`World::collect_replication_delta`] on the server and applied with [`World::apply_replication_delta`] on clients.
+///
+/// [`World::collect_replication_delta`]: crate::World::collect_replication_delta()
+/// [`World::apply_replication_delta`]: crate::World::apply_replication_delta()
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ReplicationDelta {
+    tick: u64,
+    spawns: Vec<EntityId>,
+    despawns: Vec<EntityId>,
+    components: Vec<ComponentDelta>,
+}
+
+impl ReplicationDelta {
+    /// Returns the tick the delta was collected at.\
+    /// Passing it to the next [`World::collect_replication_delta`] returns the changes that happened after this delta.
+    ///
+    /// [`World::collect_replication_delta`]: crate::World::collect_replication_delta()
+    pub fn tick(&self) -> TrackingTimestamp {
+        TrackingTimestamp::new(self.tick)
+    }
+    /// Returns the server ids of the entities that got their first replicated component.
+    pub fn spawns(&self) -> &[EntityId] {
+        &self.spawns
+    }
+    /// Returns the server ids of the deleted entities.
+    pub fn despawns(&self) -> &[EntityId] {
+        &self.despawns
+    }
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.spawns.is_empty()
+            && self.despawns.is_empty()
+            && self
+                .components
+                .iter()
+                .all(|component| component.changed.is_empty() && component.removed.is_empty())
+    }
+    /// Encodes the delta in a compact binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.tick.to_le_bytes());
+
+        write_entities(&mut bytes, &self.spawns);
+        write_entities(&mut bytes, &self.despawns);
+
+        bytes.extend_from_slice(&(self.components.len() as u32).to_le_bytes());
+        for component in &self.components {
+            write_slice(&mut bytes, component.name.as_bytes());
+
+            bytes.extend_from_slice(&(component.changed.len() as u32).to_le_bytes());
+            for (entity, data) in &component.changed {
+                bytes.extend_from_slice(&entity.inner().to_le_bytes());
+                write_slice(&mut bytes, data);
+            }
+
+            write_entities(&mut bytes, &component.removed);
+        }
+
+        bytes
+    }
+    /// Decodes a delta previously encoded with [`ReplicationDelta::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<ReplicationDelta, error::Replication> {
+        let mut reader = Reader(bytes);
+
+        if reader.take(MAGIC.len())? != MAGIC || reader.take(1)? != [VERSION] {
+            return Err(error::Replication::InvalidDelta);
+        }
+
+        let tick = reader.u64()?;
+        let spawns = reader.entities()?;
+        let despawns = reader.entities()?;
+
+        let mut components = Vec::new();
+        for _ in 0..reader.u32()? {
+            let name = core::str::from_utf8(reader.slice()?)
+                .map_err(|_| error::Replication::InvalidDelta)?;
+
+            let mut changed = Vec::new();
+            for _ in 0..reader.u32()? {
+                changed.push((reader.entity()?, reader.slice()?.to_vec()));
+            }
+
+            components.push(ComponentDelta {
+                name: Cow::Owned(String::from(name)),
+                changed,
+                removed: reader.entities()?,
+            });
+        }
+
+        if !reader.0.is_empty() {
+            return Err(error::Replication::InvalidDelta);
+        }
+
+        Ok(ReplicationDelta {
+            tick,
+            spawns,
+            despawns,
+            components,
+        })
+    }
+    /// Encodes the delta like [`ReplicationDelta::to_bytes`] and compresses the result.
+    pub fn to_compressed_bytes(&self, compressor: &dyn Compressor) -> Vec<u8> {
+        compressor.compress(&self.to_bytes())
+    }
+    /// Decodes a delta previously encoded with [`ReplicationDelta::to_compressed_bytes`] and the same compressor.
+    pub fn from_compressed_bytes(
+        bytes: &[u8],
+        compressor: &dyn Compressor,
+    ) -> Result<ReplicationDelta, error::Replication> {
+        let bytes = compressor
+            .decompress(bytes)
+            .ok_or(error::Replication::InvalidDelta)?;
+
+        ReplicationDelta::from_bytes(&bytes)
+    }
+}
+
+type Interest = Box<dyn Fn(&AllStorages, EntityId) -> bool + Send + Sync>;
+
+/// Replication state of a single client.
+///
+/// Keeps the tick of the last delta sent to the client and the entities it knows about.\
+/// An interest predicate can restrict the entities the client sees, for example based on distance or team.
+/// Entities entering the client's interest are sent with all their replicated components
+/// and entities leaving it are despawned on the client.
+///
+/// Used with [`World::collect_replication_delta_for`].
+///
+/// ### Example
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use shipyard::{Component, ReplicationClient, ReplicationRegistry, View, World};
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Position(f32, f32);
+///
+/// let mut registry = ReplicationRegistry::new();
+/// registry.register::<Position>();
+///
+/// let mut world = World::new();
+/// world.enable_replication(registry);
+/// let near = world.add_entity((Position(1.0, 0.0),));
+/// world.add_entity((Position(100.0, 0.0),));
+///
+/// let mut client = ReplicationClient::new().with_interest(|all_storages, entity| {
+///     let positions = all_storages.borrow::<View<Position>>().unwrap();
+///
+///     positions
+///         .get(entity)
+///         .is_ok_and(|position| position.0 * position.0 + position.1 * position.1 < 10.0 * 10.0)
+/// });
+///
+/// let delta = world.collect_replication_delta_for(&mut client);
+/// assert_eq!(delta.spawns(), &[near]);
+/// ```
+///
+/// [`World::collect_replication_delta_for`]: crate::World::collect_replication_delta_for()
+#[derive(Default)]
+pub struct ReplicationClient {
+    tick: u64,
+    visible: ShipHashSet<EntityId>,
+    interest: Option<Interest>,
+}
+
+impl ReplicationClient {
+    /// Creates a client interested in all replicated entities.
+    pub fn new() -> ReplicationClient {
+        ReplicationClient::default()
+    }
+    /// Restricts the entities sent to this client to the ones `interest` returns `true` for.
+    pub fn with_interest<F: Fn(&AllStorages, EntityId) -> bool + Send + Sync + 'static>(
+        mut self,
+        interest: F,
+    ) -> ReplicationClient {
+        self.set_interest(interest);
+        self
+    }
+    /// Replaces the interest predicate of this client.\
+    /// Entities that are no longer of interest are despawned on the client with the next delta.
+    pub fn set_interest<F: Fn(&AllStorages, EntityId) -> bool + Send + Sync + 'static>(
+        &mut self,
+        interest: F,
+    ) {
+        self.interest = Some(Box::new(interest));
+    }
+    /// Returns the tick of the last delta collected for this client.
+    pub fn tick(&self) -> TrackingTimestamp {
+        TrackingTimestamp::new(self.tick)
+    }
+    /// Returns `true` if the client was sent `entity` and it wasn't despawned since.
+    pub fn is_visible(&self, entity: EntityId) -> bool {
+        self.visible.contains(&entity)
+    }
+    /// Forgets everything sent to this client, the next delta will contain the full state.\
+    /// Use it when a client reconnects with an empty `World`.
+    pub fn reset(&mut self) {
+        self.tick = 0;
+        self.visible.clear();
+    }
+    fn is_interested(&self, all_storages: &AllStorages, entity: EntityId) -> bool {
+        match &self.interest {
+            Some(interest) => interest(all_storages, entity),
+            None => true,
+        }
+    }
+}
+
+impl core::fmt::Debug for ReplicationClient {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReplicationClient")
+            .field("tick", &self.tick)
+            .field("visible", &self.visible.len())
+            .field("has_interest", &self.interest.is_some())
+            .finish()
+    }
+}
+
+/// Enables all tracking on the storages of the registered components.
+pub(crate) fn enable_tracking(all_storages: &mut AllStorages, registry: &ReplicationRegistry) {
+    for codec in &registry.codecs {
+        codec.enable_tracking(all_storages);
+    }
+}
+
+/// Collects the changes of the registered components in (`since`, now].
+pub(crate) fn collect(
+    all_storages: &mut AllStorages,
+    registry: &ReplicationRegistry,
+    since: TrackingTimestamp,
+) -> ReplicationDelta {
+    // modifications happening after this point will have a greater timestamp
+    let tick = all_storages.get_current();
+
+    let mut inserted = Vec::new();
+    let mut existing = ShipHashSet::default();
+    let mut removed = Vec::new();
+    let mut components = Vec::with_capacity(registry.codecs.len());
+
+    for codec in &registry.codecs {
+        let changes = codec.collect(all_storages, since, tick, &|_| true, &|_| false);
+
+        inserted.extend(changes.inserted);
+        existing.extend(changes.existing);
+        removed.extend(changes.removed.iter().copied());
+
+        components.push(ComponentDelta {
+            name: Cow::Borrowed(codec.name()),
+            changed: changes.changed,
+            removed: changes.removed,
+        });
+    }
+
+    let entities = all_storages.exclusive_storage_mut::<Entities>().unwrap();
+
+    let mut spawns = Vec::new();
+    let mut seen = ShipHashSet::default();
+    for entity in inserted {
+        if !existing.contains(&entity) && seen.insert(entity) {
+            spawns.push(entity);
+        }
+    }
+
+    let mut despawns = Vec::new();
+    let mut seen = ShipHashSet::default();
+    for &entity in &removed {
+        if !entities.is_alive(entity) && seen.insert(entity) {
+            despawns.push(entity);
+        }
+    }
+
+    // despawned entities lose all their components, no need to send them one by one
+    for component in &mut components {
+        component.removed.retain(|entity| !seen.contains(entity));
+    }
+
+    ReplicationDelta {
+        tick: tick.get(),
+        spawns,
+        despawns,
+        components,
+    }
+}
+
+/// Collects the changes of the registered components since the last delta of `client`, restricted to its interest.
+pub(crate) fn collect_for(
+    all_storages: &mut AllStorages,
+    registry: &ReplicationRegistry,
+    client: &mut ReplicationClient,
+) -> ReplicationDelta {
+    let tick = all_storages.get_current();
+
+    // alive entities owning a replicated component the client is interested in
+    let mut relevant = Vec::new();
+    let mut seen = ShipHashSet::default();
+    for codec in &registry.codecs {
+        for entity in codec.holders(all_storages) {
+            if seen.insert(entity) {
+                relevant.push(entity);
+            }
+        }
+    }
+
+    let entities = all_storages.exclusive_storage_mut::<Entities>().unwrap();
+    relevant.retain(|&entity| entities.is_alive(entity));
+    relevant.retain(|&entity| client.is_interested(all_storages, entity));
+    let relevant_set: ShipHashSet<EntityId> = relevant.iter().copied().collect();
+
+    let spawns: Vec<EntityId> = relevant
+        .iter()
+        .copied()
+        .filter(|entity| !client.visible.contains(entity))
+        .collect();
+    let spawned: ShipHashSet<EntityId> = spawns.iter().copied().collect();
+
+    let despawns: Vec<EntityId> = client
+        .visible
+        .iter()
+        .copied()
+        .filter(|entity| !relevant_set.contains(entity))
+        .collect();
+
+    let since = TrackingTimestamp::new(client.tick);
+    let components = registry
+        .codecs
+        .iter()
+        .map(|codec| {
+            let changes = codec.collect(
+                all_storages,
+                since,
+                tick,
+                &|entity| relevant_set.contains(&entity),
+                &|entity| spawned.contains(&entity),
+            );
+
+            ComponentDelta {
+                name: Cow::Borrowed(codec.name()),
+                changed: changes.changed,
+                removed: changes.removed,
+            }
+        })
+        .collect();
+
+    client.tick = tick.get();
+    client.visible = relevant_set;
+
+    ReplicationDelta {
+        tick: tick.get(),
+        spawns,
+        despawns,
+        components,
+    }
+}
+
+/// Removes deletion and removal tracking data of the registered components older than `timestamp`.
+pub(crate) fn clear_older_than(
+    all_storages: &mut AllStorages,
+    registry: &ReplicationRegistry,
+    timestamp: TrackingTimestamp,
+) {
+    for codec in &registry.codecs {
+        codec.clear_older_than(all_storages, timestamp);
+    }
+}
+
+/// Applies `delta` to `all_storages`, creating an entity for each server entity absent from `map`.
+pub(crate) fn apply(
+    all_storages: &mut AllStorages,
+    delta: &ReplicationDelta,
+    registry: &ReplicationRegistry,
+    map: &mut EntityIdMap,
+) -> Result<(), error::Replication> {
+    // decode everything first, nothing is modified if the delta is invalid
+    let mut decoded = Vec::with_capacity(delta.components.len());
+    for component in &delta.components {
+        let codec = registry
+            .codec_by_name(&component.name)
+            .ok_or_else(|| error::Replication::UnregisteredComponent(component.name.clone()))?;
+
+        let components =
+            codec
+                .decode(&component.changed)
+                .ok_or(error::Replication::InvalidComponentData(Cow::Borrowed(
+                    codec.name(),
+                )))?;
+
+        decoded.push((codec, components, &component.removed));
+    }
+
+    for &entity in &delta.despawns {
+        if let Some(local) = map.remove(entity) {
+            all_storages.delete_entity(local);
+        }
+    }
+
+    let spawns = delta.spawns.iter().copied();
+    let changed = delta
+        .components
+        .iter()
+        .flat_map(|component| component.changed.iter().map(|(entity, _)| *entity));
+    for entity in spawns.chain(changed) {
+        if map.get(entity).is_none() {
+            let local = all_storages.add_entity(());
+            map.insert(entity, local);
+        }
+    }
+
+    for (codec, mut components, removed) in decoded {
+        for &entity in removed {
+            if let Some(local) = map.get(entity) {
+                codec.delete(all_storages, local);
+            }
+        }
+
+        codec.remap(&mut *components, map);
+        codec.insert(all_storages, components);
+    }
+
+    Ok(())
+}
+
+fn write_slice(bytes: &mut Vec<u8>, slice: &[u8]) {
+    bytes.extend_from_slice(&(slice.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(slice);
+}
+
+fn write_entities(bytes: &mut Vec<u8>, entities: &[EntityId]) {
+    bytes.extend_from_slice(&(entities.len() as u32).to_le_bytes());
+    for entity in entities {
+        bytes.extend_from_slice(&entity.inner().to_le_bytes());
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], error::Replication> {
+        if self.0.len() < len {
+            return Err(error::Replication::InvalidDelta);
+        }
+
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        Ok(taken)
+    }
+    fn u32(&mut self) -> Result<u32, error::Replication> {
+        let mut value = [0; 4];
+        value.copy_from_slice(self.take(4)?);
+
+        Ok(u32::from_le_bytes(value))
+    }
+    fn u64(&mut self) -> Result<u64, error::Replication> {
+        let mut value = [0; 8];
+        value.copy_from_slice(self.take(8)?);
+
+        Ok(u64::from_le_bytes(value))
+    }
+    fn entity(&mut self) -> Result<EntityId, error::Replication> {
+        EntityId::from_inner(self.u64()?).ok_or(error::Replication::InvalidDelta)
+    }
+    fn entities(&mut self) -> Result<Vec<EntityId>, error::Replication> {
+        let mut entities = Vec::new();
+        for _ in 0..self.u32()? {
+            entities.push(self.entity()?);
+        }
+
+        Ok(entities)
+    }
+    fn slice(&mut self) -> Result<&'a [u8], error::Replication> {
+        let len = self.u32()? as usize;
+
+        self.take(len)
+    }
+}
@@ -0,0 +1,313 @@
+use crate::snapshot::Encoding;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+/// Custom conversion of a replicated component to and from bits.
+///
+/// Registered with [`ReplicationRegistry::register_with_encoder`], it replaces serde for this component.\
+/// Fields can be written with fewer bits than their type, using a [`Quantizer`] for floats for example.
+///
+/// ### Example
+/// ```
+/// use shipyard::{
+///     BitReader, BitWriter, Component, Quantizer, ReplicationEncoder, ReplicationRegistry,
+/// };
+///
+/// #[derive(Component)]
+/// struct Transform {
+///     position: [f32; 2],
+///     rotation: f32,
+/// }
+///
+/// struct TransformEncoder {
+///     position: Quantizer,
+///     rotation: Quantizer,
+/// }
+///
+/// impl ReplicationEncoder<Transform> for TransformEncoder {
+///     fn encode(&self, transform: &Transform, writer: &mut BitWriter) {
+///         self.position.write(transform.position[0], writer);
+///         self.position.write(transform.position[1], writer);
+///         self.rotation.write(transform.rotation, writer);
+///     }
+///     fn decode(&self, reader: &mut BitReader<'_>) -> Option<Transform> {
+///         Some(Transform {
+///             position: [self.position.read(reader)?, self.position.read(reader)?],
+///             rotation: self.rotation.read(reader)?,
+///         })
+///     }
+/// }
+///
+/// let encoder = TransformEncoder {
+///     // 1/256 unit precision
+///     position: Quantizer::with_step(-1024.0, 1024.0, 1.0 / 256.0),
+///     rotation: Quantizer::new(-core::f32::consts::PI, core::f32::consts::PI, 12),
+/// };
+/// // 20 + 20 + 12 bits, 7 bytes instead of 12
+/// assert_eq!(encoder.position.bits(), 20);
+///
+/// let mut registry = ReplicationRegistry::new();
+/// registry.register_with_encoder::<Transform, _>(encoder);
+/// ```
+///
+/// [`ReplicationRegistry::register_with_encoder`]: crate::ReplicationRegistry::register_with_encoder()
+pub trait ReplicationEncoder<T>: Send + Sync + 'static {
+    /// Writes `component` to `writer`.
+    fn encode(&self, component: &T, writer: &mut BitWriter);
+    /// Reads back a component written by [`ReplicationEncoder::encode`], `None` if the bits are invalid.
+    fn decode(&self, reader: &mut BitReader<'_>) -> Option<T>;
+}
+
+/// Bit level writer used by [`ReplicationEncoder`].
+///
+/// Bits are packed starting from the least significant bit of each byte.
+#[derive(Clone, Default, Debug)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    /// Number of bits used in the last byte, 0 when it's full.
+    used_bits: u32,
+}
+
+impl BitWriter {
+    /// Creates an empty writer.
+    pub fn new() -> BitWriter {
+        BitWriter::default()
+    }
+    /// Writes the `bits` least significant bits of `value`.
+    ///
+    /// ### Panics
+    ///
+    /// - `bits` is greater than 64.
+    #[track_caller]
+    pub fn write_bits(&mut self, mut value: u64, mut bits: u32) {
+        assert!(bits <= 64, "can't write more than 64 bits at once");
+
+        while bits > 0 {
+            if self.used_bits == 0 {
+                self.bytes.push(0);
+            }
+
+            let free_bits = 8 - self.used_bits;
+            let written = free_bits.min(bits);
+            let mask = (1u64 << written) - 1;
+
+            *self.bytes.last_mut().unwrap() |= ((value & mask) as u8) << self.used_bits;
+
+            self.used_bits = (self.used_bits + written) % 8;
+            value >>= written;
+            bits -= written;
+        }
+    }
+    /// Writes a single bit.
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_bits(value as u64, 1);
+    }
+    /// Writes all 32 bits of `value`.
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_bits(value.to_bits() as u64, 32);
+    }
+    /// Returns the number of bits written.
+    pub fn len(&self) -> usize {
+        match self.used_bits {
+            0 => self.bytes.len() * 8,
+            used_bits => (self.bytes.len() - 1) * 8 + used_bits as usize,
+        }
+    }
+    /// Returns `true` if nothing was written.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+    /// Returns the bytes written, the unused bits of the last byte are zeros.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Bit level reader used by [`ReplicationEncoder`].
+///
+/// Reads bits in the order [`BitWriter`] wrote them.
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    /// Index of the next bit to read.
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Creates a reader starting at the first bit of `bytes`.
+    pub fn new(bytes: &'a [u8]) -> BitReader<'a> {
+        BitReader { bytes, position: 0 }
+    }
+    /// Reads `bits` bits, `None` if there aren't enough left.
+    ///
+    /// ### Panics
+    ///
+    /// - `bits` is greater than 64.
+    #[track_caller]
+    pub fn read_bits(&mut self, bits: u32) -> Option<u64> {
+        assert!(bits <= 64, "can't read more than 64 bits at once");
+
+        if self.remaining() < bits as usize {
+            return None;
+        }
+
+        let mut value = 0;
+        let mut read = 0;
+
+        while read < bits {
+            let byte = self.bytes[self.position / 8];
+            let offset = (self.position % 8) as u32;
+            let taken = (8 - offset).min(bits - read);
+            let mask = (1u64 << taken) - 1;
+
+            value |= ((byte >> offset) as u64 & mask) << read;
+
+            self.position += taken as usize;
+            read += taken;
+        }
+
+        Some(value)
+    }
+    /// Reads a single bit.
+    pub fn read_bool(&mut self) -> Option<bool> {
+        self.read_bits(1).map(|bit| bit == 1)
+    }
+    /// Reads a value written with [`BitWriter::write_f32`].
+    pub fn read_f32(&mut self) -> Option<f32> {
+        self.read_bits(32).map(|bits| f32::from_bits(bits as u32))
+    }
+    /// Returns the number of bits left.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+}
+
+/// Maps floats in a range to integers of a fixed number of bits.
+///
+/// Values outside of the range are clamped, `NaN` becomes the minimum.\
+/// The range bounds are always represented exactly.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Quantizer {
+    min: f32,
+    max: f32,
+    bits: u32,
+}
+
+impl Quantizer {
+    /// Creates a quantizer splitting `min..=max` in `2^bits` values.
+    ///
+    /// ### Panics
+    ///
+    /// - `min` isn't smaller than `max`.
+    /// - `min` or `max` isn't finite.
+    /// - `bits` is 0 or greater than 32.
+    #[track_caller]
+    pub fn new(min: f32, max: f32, bits: u32) -> Quantizer {
+        assert!(
+            min.is_finite() && max.is_finite() && min < max,
+            "the range has to be finite and not empty"
+        );
+        assert!(
+            (1..=32).contains(&bits),
+            "a quantizer has to use 1 to 32 bits"
+        );
+
+        Quantizer { min, max, bits }
+    }
+    /// Creates a quantizer using as few bits as possible for values of `min..=max` to be at most `step` apart.
+    ///
+    /// ### Panics
+    ///
+    /// - `min` isn't smaller than `max`.
+    /// - `min` or `max` isn't finite.
+    /// - `step` needs more than 32 bits for this range.
+    #[track_caller]
+    pub fn with_step(min: f32, max: f32, step: f32) -> Quantizer {
+        let range = max as f64 - min as f64;
+
+        let bits = (1..=32)
+            .find(|&bits| range / levels(bits) <= step as f64)
+            .expect("the step is too small to fit in 32 bits");
+
+        Quantizer::new(min, max, bits)
+    }
+    /// Returns the number of bits used for each value.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+    /// Returns the distance between two consecutive quantized values.
+    pub fn step(&self) -> f32 {
+        ((self.max as f64 - self.min as f64) / levels(self.bits)) as f32
+    }
+    /// Returns the integer closest to `value`.
+    pub fn quantize(&self, value: f32) -> u32 {
+        let levels = levels(self.bits);
+        let normalized = (value as f64 - self.min as f64) / (self.max as f64 - self.min as f64);
+
+        // `NaN` stays `NaN` and `as` turns it into 0
+        (normalized * levels + 0.5).clamp(0.0, levels) as u32
+    }
+    /// Returns the float `quantized` represents.
+    pub fn dequantize(&self, quantized: u32) -> f32 {
+        let levels = levels(self.bits);
+        let normalized = (quantized as f64).min(levels) / levels;
+
+        (self.min as f64 + normalized * (self.max as f64 - self.min as f64)) as f32
+    }
+    /// Writes `value` quantized to `writer`.
+    pub fn write(&self, value: f32, writer: &mut BitWriter) {
+        writer.write_bits(self.quantize(value) as u64, self.bits);
+    }
+    /// Reads a value written with [`Quantizer::write`].
+    pub fn read(&self, reader: &mut BitReader<'_>) -> Option<f32> {
+        reader
+            .read_bits(self.bits)
+            .map(|quantized| self.dequantize(quantized as u32))
+    }
+}
+
+/// Returns the largest integer that fits in `bits` bits.
+fn levels(bits: u32) -> f64 {
+    (u32::MAX >> (32 - bits)) as f64
+}
+
+/// Conversion of a replicated component to and from bytes.
+pub(super) enum ComponentEncoding<T> {
+    Bytes(Encoding<T>),
+    Custom(Arc<dyn ReplicationEncoder<T>>),
+}
+
+impl<T> Clone for ComponentEncoding<T> {
+    fn clone(&self) -> Self {
+        match self {
+            ComponentEncoding::Bytes(encoding) => ComponentEncoding::Bytes(*encoding),
+            ComponentEncoding::Custom(encoder) => ComponentEncoding::Custom(encoder.clone()),
+        }
+    }
+}
+
+impl<T: 'static> ComponentEncoding<T> {
+    pub(super) fn serialize(&self, component: &T) -> Option<Vec<u8>> {
+        match self {
+            ComponentEncoding::Bytes(encoding) => (encoding.serialize)(component),
+            ComponentEncoding::Custom(encoder) => {
+                let mut writer = BitWriter::new();
+                encoder.encode(component, &mut writer);
+
+                Some(writer.into_bytes())
+            }
+        }
+    }
+    pub(super) fn deserialize(&self, bytes: &[u8]) -> Option<T> {
+        match self {
+            ComponentEncoding::Bytes(encoding) => (encoding.deserialize)(bytes),
+            ComponentEncoding::Custom(encoder) => {
+                let mut reader = BitReader::new(bytes);
+                let component = encoder.decode(&mut reader)?;
+
+                // only the padding of the last byte can be left
+                (reader.remaining() < 8).then_some(component)
+            }
+        }
+    }
+}
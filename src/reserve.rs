@@ -2,6 +2,7 @@ use crate::component::Component;
 use crate::entity_id::EntityId;
 use crate::ViewMut;
 use core::iter::{Copied, DoubleEndedIterator, ExactSizeIterator, FusedIterator, Iterator};
+use core::ops::Range;
 use core::slice::Iter;
 
 /// Iterator over newly bulk added entities.
@@ -22,6 +23,29 @@ impl BulkEntityIter<'_> {
     pub fn as_slice(&self) -> &[EntityId] {
         self.slice
     }
+    /// Range of the indices of the newly bulk added entities.\
+    /// Bulk added entities are never recycled, they are contiguous and all have a generation of 0.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity(());
+    /// let new_entities = world.bulk_add_entity((0..3).map(|i| (U32(i),)));
+    ///
+    /// assert_eq!(new_entities.index_range(), 1..4);
+    /// ```
+    pub fn index_range(&self) -> Range<u64> {
+        match (self.slice.first(), self.slice.last()) {
+            (Some(first), Some(last)) => first.index()..last.index() + 1,
+            _ => 0..0,
+        }
+    }
 }
 
 impl<'a> Iterator for BulkEntityIter<'a> {
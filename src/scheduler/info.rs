@@ -59,6 +59,29 @@ pub struct SystemInfo {
     pub after: Vec<String>,
 }
 
+impl SystemInfo {
+    /// Returns the storages this system only reads, like [`View`] or [`UniqueView`].
+    ///
+    /// Systems reading the same storage can be part of the same batch.
+    ///
+    /// [`View`]: crate::View
+    /// [`UniqueView`]: crate::UniqueView
+    pub fn reads(&self) -> impl Iterator<Item = &'_ TypeInfo> {
+        self.borrow
+            .iter()
+            .filter(|type_info| type_info.mutability == Mutability::Shared)
+    }
+    /// Returns the storages this system modifies, like [`ViewMut`] or [`UniqueViewMut`].
+    ///
+    /// [`ViewMut`]: crate::ViewMut
+    /// [`UniqueViewMut`]: crate::UniqueViewMut
+    pub fn writes(&self) -> impl Iterator<Item = &'_ TypeInfo> {
+        self.borrow
+            .iter()
+            .filter(|type_info| type_info.mutability == Mutability::Exclusive)
+    }
+}
+
 impl core::fmt::Debug for SystemInfo {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("SystemInfo")
@@ -220,6 +243,14 @@ impl DedupedLabels {
         self.0.is_empty()
     }
 
+    pub(crate) fn contains(&self, label: &dyn Label) -> bool {
+        self.0.iter().any(|other| **other == *label)
+    }
+
+    pub(crate) fn remove(&mut self, label: &dyn Label) {
+        self.0.retain(|other| **other != *label);
+    }
+
     pub(crate) fn iter(&self) -> RequirementsIter<'_> {
         self.into_iter()
     }
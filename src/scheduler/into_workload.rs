@@ -184,11 +184,13 @@ where
                 tags: vec![name],
                 systems: vec![system],
                 run_if: None,
+                run_if_borrow_constraints: Vec::new(),
                 before_all: DedupedLabels::new(),
                 after_all: DedupedLabels::new(),
                 overwritten_name: false,
                 require_before: DedupedLabels::new(),
                 require_after: DedupedLabels::new(),
+                on_error: None,
                 barriers: Vec::new(),
             }
         }
@@ -226,11 +228,13 @@ macro_rules! impl_into_workload {
                     name,
                     systems: Vec::new(),
                     run_if: None,
+                    run_if_borrow_constraints: Vec::new(),
                     before_all: DedupedLabels::new(),
                     after_all: DedupedLabels::new(),
                     overwritten_name: false,
                     require_before: DedupedLabels::new(),
                     require_after: DedupedLabels::new(),
+                    on_error: None,
                     barriers: Vec::new(),
                 };
 
@@ -256,11 +260,13 @@ macro_rules! impl_into_workload {
                     name,
                     systems: Vec::new(),
                     run_if: None,
+                    run_if_borrow_constraints: Vec::new(),
                     before_all: DedupedLabels::new(),
                     after_all: DedupedLabels::new(),
                     overwritten_name: false,
                     require_before: DedupedLabels::new(),
                     require_after: DedupedLabels::new(),
+                    on_error: None,
                     barriers: Vec::new(),
                 };
 
@@ -24,6 +24,7 @@ where
     fn into_workload_run_if(self) -> Result<RunIf, error::InvalidSystem> {
         Ok(RunIf {
             system_fn: Box::new(move |_: &World| Ok((self)())),
+            borrow_constraints: Vec::new(),
         })
     }
 }
@@ -87,6 +88,7 @@ macro_rules! impl_into_workload_run_if {
                         let last_run = TrackingTimestamp::new(last_run.swap(current.get(), Ordering::Acquire));
                         Ok((&&self)($($type::world_borrow(&world, Some(last_run), current)?),+))
                     }),
+                    borrow_constraints: borrows,
                 })
             }
         }
@@ -107,6 +109,8 @@ into_workload_run_if![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (
 
 pub trait IntoWorkloadRunIf<B> {
     fn into_workload_run_if(self) -> Result<Box<dyn WorkloadRunIfFn>, error::InvalidSystem>;
+    /// Storages borrowed when the condition is evaluated.
+    fn run_if_borrow_info(_info: &mut Vec<TypeInfo>) {}
 }
 
 impl<F> IntoWorkloadRunIf<Nothing> for F
@@ -172,6 +176,12 @@ macro_rules! impl_into_workload_run_if {
                     Ok((&&self)($($type::world_borrow(&world, Some(last_run), current)?),+))
                 }))
             }
+
+            fn run_if_borrow_info(info: &mut Vec<TypeInfo>) {
+                $(
+                    $type::borrow_info(info);
+                )+
+            }
         }
     }
 }
@@ -55,9 +55,11 @@ where
                 name: type_name::<F>().as_label(),
             })],
             run_if: None,
+            run_if_borrow_constraints: Vec::new(),
             require_in_workload: DedupedLabels::new(),
             require_before: DedupedLabels::new(),
             require_after: DedupedLabels::new(),
+            on_error: None,
         })
     }
     fn label(&self) -> Box<dyn Label> {
@@ -170,9 +172,11 @@ macro_rules! impl_into_workload_system {
                         TypeId::of::<Func>()
                     }),
                     run_if: None,
+                    run_if_borrow_constraints: Vec::new(),
                     require_in_workload: DedupedLabels::new(),
                     require_before: DedupedLabels::new(),
                     require_after: DedupedLabels::new(),
+                    on_error: None,
                 })
             }
             fn label(&self) -> Box<dyn Label> {
@@ -69,9 +69,11 @@ where
                 name: system_type_name.as_label(),
             })],
             run_if: None,
+            run_if_borrow_constraints: Vec::new(),
             require_in_workload: DedupedLabels::new(),
             require_before: DedupedLabels::new(),
             require_after: DedupedLabels::new(),
+            on_error: None,
         })
     }
     #[cfg(not(feature = "std"))]
@@ -100,9 +102,11 @@ where
                 name: system_type_name.as_label(),
             })],
             run_if: None,
+            run_if_borrow_constraints: Vec::new(),
             require_in_workload: DedupedLabels::new(),
             require_before: DedupedLabels::new(),
             require_after: DedupedLabels::new(),
+            on_error: None,
         })
     }
 }
@@ -204,9 +208,11 @@ macro_rules! impl_into_workload_try_system {
                         name: type_name::<Func>().as_label(),
                     })],
                     run_if: None,
+                    run_if_borrow_constraints: Vec::new(),
                     require_in_workload: DedupedLabels::new(),
                     require_before: DedupedLabels::new(),
                     require_after: DedupedLabels::new(),
+                    on_error: None,
                 })
             }
             #[cfg(not(feature = "std"))]
@@ -275,9 +281,11 @@ macro_rules! impl_into_workload_try_system {
                         name: type_name::<Func>().as_label(),
                     })],
                     run_if: None,
+                    run_if_borrow_constraints: Vec::new(),
                     require_in_workload: DedupedLabels::new(),
                     require_before: DedupedLabels::new(),
                     require_after: DedupedLabels::new(),
+                    on_error: None,
                 })
             }
         }
@@ -6,6 +6,7 @@ mod into_workload_try_system;
 mod label;
 mod system;
 mod system_modificator;
+mod system_registry;
 mod workload;
 mod workload_modificator;
 
@@ -13,15 +14,18 @@ pub use into_workload::IntoWorkload;
 pub use into_workload_system::IntoWorkloadSystem;
 pub use into_workload_try_system::IntoWorkloadTrySystem;
 pub use label::{AsLabel, Label};
-pub use system::WorkloadSystem;
+pub use system::{Decision, WorkloadSystem};
 pub use system_modificator::SystemModificator;
+pub use system_registry::{SystemDescriptor, SystemRegistry, WorkloadDescriptor};
 pub use workload::{ScheduledWorkload, Workload};
 pub use workload_modificator::WorkloadModificator;
 
 pub(crate) use info::TypeInfo;
 
-use crate::info::WorkloadInfo;
-use crate::scheduler::system::WorkloadRunIfFn;
+#[cfg(feature = "std")]
+use crate::budget::BudgetReport;
+use crate::info::{DedupedLabels, WorkloadInfo};
+use crate::scheduler::system::{ErrorHandler, WorkloadRunIfFn};
 use crate::type_id::TypeId;
 use crate::World;
 use crate::{error, ShipHashMap};
@@ -42,6 +46,24 @@ pub(super) struct Batches {
     pub(super) sequential_run_if:
         Vec<Option<Box<dyn Fn(&World) -> Result<bool, error::Run> + Send + Sync>>>,
     pub(super) run_if: Option<Box<dyn WorkloadRunIfFn>>,
+    /// Index into the list of systems to the handler of their errors
+    pub(super) on_error: ShipHashMap<usize, ErrorHandler>,
+    pub(super) graph: SystemGraph,
+}
+
+/// Dependencies between the systems of a workload
+///
+/// A system can start as soon as all the systems it depends on are done.\
+/// All indices are into `Batches::sequential`.
+#[derive(Default)]
+#[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+pub(super) struct SystemGraph {
+    /// Number of systems each system waits for
+    pub(super) dependency_count: Vec<usize>,
+    /// Systems waiting for each system
+    pub(super) dependents: Vec<Vec<usize>>,
+    /// Systems that have to run on the thread running the workload
+    pub(super) local: Vec<bool>,
 }
 
 #[cfg(test)]
@@ -80,6 +102,11 @@ pub(crate) struct Scheduler {
     pub(crate) workloads: ShipHashMap<Box<dyn Label>, Batches>,
     pub(crate) workloads_info: ShipHashMap<Box<dyn Label>, WorkloadInfo>,
     pub(crate) default: Box<dyn Label>,
+    /// sets disabled with `World::disable_set`
+    pub(crate) disabled_sets: DedupedLabels,
+    /// timing of the systems with a budget
+    #[cfg(feature = "std")]
+    pub(crate) budget_report: std::sync::Mutex<BudgetReport>,
 }
 
 impl Default for Scheduler {
@@ -92,6 +119,9 @@ impl Default for Scheduler {
             workloads: ShipHashMap::with_hasher(BuildHasherDefault::default()),
             workloads_info: ShipHashMap::with_hasher(BuildHasherDefault::default()),
             default: Box::new(""),
+            disabled_sets: DedupedLabels::new(),
+            #[cfg(feature = "std")]
+            budget_report: std::sync::Mutex::new(BudgetReport::default()),
         }
     }
 }
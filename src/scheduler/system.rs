@@ -7,6 +7,7 @@ use crate::scheduler::workload::Workload;
 use crate::type_id::TypeId;
 use crate::world::World;
 use alloc::boxed::Box;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 
 /// Self contained system that may be inserted into a [`Workload`].
@@ -49,6 +50,7 @@ pub struct WorkloadSystem {
     pub generator: Box<dyn Fn(&mut Vec<TypeInfo>) -> TypeId + Send + Sync + 'static>,
     #[allow(missing_docs)]
     pub run_if: Option<Box<dyn Fn(&World) -> Result<bool, error::Run> + Send + Sync + 'static>>,
+    pub(crate) run_if_borrow_constraints: Vec<TypeInfo>,
     #[allow(missing_docs)]
     pub tags: Vec<Box<dyn Label>>,
     #[allow(missing_docs)]
@@ -61,8 +63,50 @@ pub struct WorkloadSystem {
     pub require_before: DedupedLabels,
     #[allow(missing_docs)]
     pub require_after: DedupedLabels,
+    pub(crate) on_error: Option<ErrorHandler>,
 }
 
+impl WorkloadSystem {
+    /// Returns the access information of `run_if`.
+    pub fn run_if_borrow_constraints(&self) -> &[TypeInfo] {
+        &self.run_if_borrow_constraints
+    }
+    /// Returns the handler set with [`WorkloadModificator::on_error`].
+    ///
+    /// [`WorkloadModificator::on_error`]: crate::WorkloadModificator::on_error()
+    #[allow(clippy::type_complexity)]
+    pub fn on_error(
+        &self,
+    ) -> Option<&(dyn Fn(&dyn Label, &error::Run, &World) -> Decision + Send + Sync + 'static)>
+    {
+        self.on_error.as_deref()
+    }
+}
+
+/// What a workload does after one of its systems failed, returned by the handler set with [`WorkloadModificator::on_error`].
+///
+/// [`WorkloadModificator::on_error`]: crate::WorkloadModificator::on_error()
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Decision {
+    /// Ignores the error and runs the remaining systems.
+    Continue,
+    /// Runs the system again.\
+    /// A system is retried at most [`Decision::MAX_RETRIES`] times in a row,
+    /// after that its error stops the workload like [`Decision::Abort`].
+    Retry,
+    /// Stops the workload and returns the error, like without handler.\
+    /// Systems already running in parallel finish but no new system starts.
+    Abort,
+}
+
+impl Decision {
+    /// Number of times a system can be retried in a row before its error stops the workload.
+    pub const MAX_RETRIES: u32 = 16;
+}
+
+pub(crate) type ErrorHandler =
+    Arc<dyn Fn(&dyn Label, &error::Run, &World) -> Decision + Send + Sync + 'static>;
+
 impl Extend<WorkloadSystem> for Workload {
     fn extend<T: IntoIterator<Item = WorkloadSystem>>(&mut self, iter: T) {
         self.systems.extend(iter);
@@ -72,6 +116,7 @@ impl Extend<WorkloadSystem> for Workload {
 #[allow(clippy::type_complexity)]
 pub struct RunIf {
     pub(crate) system_fn: Box<dyn Fn(&World) -> Result<bool, error::Run> + Send + Sync + 'static>,
+    pub(crate) borrow_constraints: Vec<TypeInfo>,
 }
 
 pub trait WorkloadRunIfFn: Send + Sync + 'static {
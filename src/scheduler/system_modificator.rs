@@ -1,4 +1,6 @@
 use crate::borrow::{BorrowInfo, WorldBorrow};
+#[cfg(feature = "std")]
+use crate::budget::with_budget;
 use crate::scheduler::into_workload_run_if::IntoRunIf;
 use crate::scheduler::{IntoWorkloadSystem, WorkloadSystem};
 use crate::storage::StorageId;
@@ -6,6 +8,8 @@ use crate::{error, AllStoragesViewMut, AsLabel, Unique, UniqueStorage};
 use crate::{Component, SparseSet};
 use alloc::boxed::Box;
 use core::ops::Not;
+#[cfg(feature = "std")]
+use core::time::Duration;
 
 /// Modifies a system.
 pub trait SystemModificator<B, R> {
@@ -98,6 +102,13 @@ pub trait SystemModificator<B, R> {
     /// System name used in error and gui built for shipyard.  
     /// Defaults to the system function name.
     fn display_name<T>(self, name: impl AsLabel<T>) -> WorkloadSystem;
+    /// Gives the system `budget` of time each run, it can check how much is left with the [`Budget`] view.\
+    /// Runs going over budget are recorded in [`World::budget_report`].
+    ///
+    /// [`Budget`]: crate::Budget
+    /// [`World::budget_report`]: crate::World::budget_report
+    #[cfg(feature = "std")]
+    fn budget(self, budget: Duration) -> WorkloadSystem;
     /// Adds a tag to this system. Tags can be used to control system ordering when running workloads.
     fn tag<T>(self, tag: impl AsLabel<T>) -> WorkloadSystem;
     /// When building a workload, this system will assert that at least one of the other system is present in the workload.
@@ -126,6 +137,7 @@ where
         let run_if = run_if.into_workload_run_if().unwrap();
 
         system.run_if = Some(run_if.system_fn);
+        system.run_if_borrow_constraints = run_if.borrow_constraints;
 
         system
     }
@@ -161,6 +173,11 @@ where
 
         system
     }
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn budget(self, budget: Duration) -> WorkloadSystem {
+        with_budget(self.into_workload_system().unwrap(), budget)
+    }
     #[track_caller]
     fn tag<T>(self, tag: impl AsLabel<T>) -> WorkloadSystem {
         let mut system = self.into_workload_system().unwrap();
@@ -200,6 +217,8 @@ impl SystemModificator<WorkloadSystem, ()> for WorkloadSystem {
     fn run_if<RunB, Run: IntoRunIf<RunB>>(mut self, run_if: Run) -> WorkloadSystem {
         let run_if = run_if.into_workload_run_if().unwrap();
 
+        self.run_if_borrow_constraints
+            .extend(run_if.borrow_constraints);
         self.run_if = if let Some(prev_run_if) = self.run_if {
             Some(Box::new(move |world| {
                 Ok((prev_run_if)(world)? && (run_if.system_fn)(world)?)
@@ -233,6 +252,10 @@ impl SystemModificator<WorkloadSystem, ()> for WorkloadSystem {
 
         self
     }
+    #[cfg(feature = "std")]
+    fn budget(self, budget: Duration) -> WorkloadSystem {
+        with_budget(self, budget)
+    }
     fn tag<T>(mut self, tag: impl AsLabel<T>) -> WorkloadSystem {
         self.tags.push(tag.as_label());
 
@@ -276,6 +299,7 @@ macro_rules! impl_into_workload_system {
                 let run_if = run_if.into_workload_run_if().unwrap();
 
                 system.run_if = Some(run_if.system_fn);
+                system.run_if_borrow_constraints = run_if.borrow_constraints;
 
                 system
             }
@@ -311,6 +335,11 @@ macro_rules! impl_into_workload_system {
 
                 system
             }
+            #[cfg(feature = "std")]
+            #[track_caller]
+            fn budget(self, budget: Duration) -> WorkloadSystem {
+                with_budget(IntoWorkloadSystem::<($($type,)+), R>::into_workload_system(self).unwrap(), budget)
+            }
             #[track_caller]
             fn tag<T>(self, tag: impl AsLabel<T>) -> WorkloadSystem {
                 let mut system = IntoWorkloadSystem::<($($type,)+), R>::into_workload_system(self).unwrap();
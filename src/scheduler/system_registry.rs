@@ -0,0 +1,229 @@
+use crate::error;
+use crate::info::WorkloadInfo;
+use crate::scheduler::{IntoWorkloadSystem, Workload, WorkloadSystem};
+use crate::type_id::TypeId;
+use crate::ShipHashMap;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+type SystemFn = Box<dyn Fn() -> WorkloadSystem + Send + Sync>;
+
+/// Workload described by the names of its systems, in order.
+///
+/// Built into a [`Workload`] by [`SystemRegistry::workload`], it can be loaded from any format supported by serde
+/// with the `serde1` feature, letting the systems be reordered or disabled without recompiling.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkloadDescriptor {
+    #[allow(missing_docs)]
+    pub name: String,
+    #[allow(missing_docs)]
+    pub systems: Vec<SystemDescriptor>,
+}
+
+/// System of a [`WorkloadDescriptor`], referenced by the name it was registered with.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemDescriptor {
+    #[allow(missing_docs)]
+    pub name: String,
+    /// Disabled systems are not added to the workload.
+    #[cfg_attr(feature = "serde1", serde(default, skip_serializing_if = "is_false"))]
+    pub disabled: bool,
+}
+
+#[cfg(feature = "serde1")]
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+impl SystemDescriptor {
+    /// Creates an enabled `SystemDescriptor`.
+    pub fn new(name: impl Into<String>) -> SystemDescriptor {
+        SystemDescriptor {
+            name: name.into(),
+            disabled: false,
+        }
+    }
+}
+
+/// Systems available to workloads defined from data, by name.
+///
+/// [`SystemRegistry::describe`] gives back the descriptor a workload was built from as long as none of its systems is disabled.\
+/// Describing any other workload only keeps the order of its systems, see [`SystemRegistry::describe`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{
+///     Component, IntoIter, SystemDescriptor, SystemRegistry, View, ViewMut, WorkloadDescriptor, World,
+/// };
+///
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// fn gravity(mut positions: ViewMut<Position>) {
+///     for position in (&mut positions).iter() {
+///         position.0 -= 1.0;
+///     }
+/// }
+///
+/// fn render(_positions: View<Position>) {}
+///
+/// let mut registry = SystemRegistry::new();
+/// registry.register("gravity", gravity).register("render", render);
+///
+/// let descriptor = WorkloadDescriptor {
+///     name: "Update".to_string(),
+///     systems: vec![SystemDescriptor::new("gravity"), SystemDescriptor::new("render")],
+/// };
+///
+/// let world = World::new();
+/// world.add_entity(Position(0.0));
+///
+/// registry.workload(&descriptor).unwrap().add_to_world(&world).unwrap();
+/// world.run_workload("Update").unwrap();
+///
+/// let workloads_info = world.workloads_info();
+/// assert_eq!(registry.describe(&workloads_info.0["Update"]), Ok(descriptor));
+/// ```
+#[derive(Default)]
+pub struct SystemRegistry {
+    systems: ShipHashMap<Cow<'static, str>, (TypeId, SystemFn)>,
+    names: ShipHashMap<TypeId, Cow<'static, str>>,
+}
+
+impl SystemRegistry {
+    /// Creates an empty `SystemRegistry`.
+    pub fn new() -> SystemRegistry {
+        SystemRegistry::default()
+    }
+    /// Registers `system` under `name`, replacing any system previously registered under it.
+    ///
+    /// ### Panics
+    ///
+    /// - `system` is invalid, see [`Workload::with_system`].
+    #[track_caller]
+    pub fn register<B, R, S>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        system: S,
+    ) -> &mut SystemRegistry
+    where
+        S: IntoWorkloadSystem<B, R> + Clone + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let type_id = system.clone().into_workload_system().unwrap().type_id;
+
+        let replaced = self.systems.insert(
+            name.clone(),
+            (
+                type_id,
+                Box::new(move || system.clone().into_workload_system().unwrap()),
+            ),
+        );
+
+        if let Some((replaced, _)) = replaced {
+            if replaced != type_id && self.names.get(&replaced) == Some(&name) {
+                // the replaced system can still be registered under another name
+                match self.systems.iter().find(|(_, (id, _))| *id == replaced) {
+                    Some((other_name, _)) => {
+                        self.names.insert(replaced, other_name.clone());
+                    }
+                    None => {
+                        self.names.remove(&replaced);
+                    }
+                }
+            }
+        }
+
+        self.names.insert(type_id, name);
+
+        self
+    }
+    /// Returns `true` if a system is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.systems.contains_key(name)
+    }
+    /// Returns an iterator over the names of all registered systems, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.systems.keys().map(|name| &**name)
+    }
+    /// Creates the workload described by `descriptor`, with its enabled systems in order.
+    ///
+    /// ### Errors
+    ///
+    /// - A system isn't registered.
+    pub fn workload(
+        &self,
+        descriptor: &WorkloadDescriptor,
+    ) -> Result<Workload, error::BuildWorkloadDescriptor> {
+        let mut workload = Workload::new(descriptor.name.clone());
+
+        for system in &descriptor.systems {
+            if system.disabled {
+                continue;
+            }
+
+            match self.systems.get(&*system.name) {
+                Some((_, system_fn)) => workload = workload.with_system(system_fn()),
+                None => {
+                    return Err(error::BuildWorkloadDescriptor::UnknownSystem(
+                        system.name.clone(),
+                    ))
+                }
+            }
+        }
+
+        Ok(workload)
+    }
+    /// Describes an existing workload with the names its systems were registered with, in execution order.\
+    /// `workload_info` can be found in [`World::workloads_info`].
+    ///
+    /// The description is lossy, it only keeps the names of the systems in the order they run.\
+    /// Ordering constraints like `before_all`/`after_all` are only kept through this order,
+    /// run conditions, tags and everything else set with [`SystemModificator`] or [`WorkloadModificator`] are lost.\
+    /// Building a workload back from the description gives the same systems in the same order, without any of these.
+    ///
+    /// Disabled systems are not part of the workload and are not listed,
+    /// describing a workload built from a descriptor with disabled systems doesn't give back that descriptor.
+    ///
+    /// ### Errors
+    ///
+    /// - A system of the workload isn't registered.
+    ///
+    /// [`World::workloads_info`]: crate::World::workloads_info
+    /// [`SystemModificator`]: crate::SystemModificator
+    /// [`WorkloadModificator`]: crate::WorkloadModificator
+    pub fn describe(
+        &self,
+        workload_info: &WorkloadInfo,
+    ) -> Result<WorkloadDescriptor, error::BuildWorkloadDescriptor> {
+        let systems = workload_info
+            .batch_info
+            .iter()
+            .flat_map(|batch| batch.systems())
+            .map(|system| match self.names.get(&system.type_id) {
+                Some(name) => Ok(SystemDescriptor::new(name.to_string())),
+                None => Err(error::BuildWorkloadDescriptor::UnregisteredSystem(
+                    system.name.clone(),
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(WorkloadDescriptor {
+            name: workload_info.name.clone(),
+            systems,
+        })
+    }
+}
+
+impl fmt::Debug for SystemRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SystemRegistry")
+            .field("systems", &self.systems.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
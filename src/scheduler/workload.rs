@@ -1,12 +1,17 @@
 use crate::all_storages::AllStorages;
 use crate::borrow::Mutability;
+use crate::command_buffer::sync_point;
 use crate::component::{Component, Unique};
+use crate::frame_arena::{reset_frame_arena, FrameArena};
 use crate::scheduler::info::{
     BatchInfo, Conflict, DedupedLabels, SystemId, SystemInfo, TypeInfo, WorkloadInfo,
 };
+use crate::scheduler::into_workload_run_if::IntoWorkloadRunIf;
 use crate::scheduler::label::{SystemLabel, WorkloadLabel};
-use crate::scheduler::system::{ExtractWorkloadRunIf, WorkloadRunIfFn};
-use crate::scheduler::{AsLabel, Batches, IntoWorkloadTrySystem, Label, Scheduler, WorkloadSystem};
+use crate::scheduler::system::{ErrorHandler, ExtractWorkloadRunIf, WorkloadRunIfFn};
+use crate::scheduler::{
+    AsLabel, Batches, IntoWorkloadTrySystem, Label, Scheduler, SystemGraph, WorkloadSystem,
+};
 use crate::storage::StorageId;
 use crate::type_id::TypeId;
 use crate::unique::UniqueStorage;
@@ -21,6 +26,7 @@ use core::any::type_name;
 #[cfg(not(feature = "std"))]
 use core::any::Any;
 use core::hash::BuildHasherDefault;
+use core::sync::atomic::{AtomicU64, Ordering};
 #[cfg(feature = "std")]
 use std::error::Error;
 
@@ -122,17 +128,24 @@ impl World {
 ///
 /// A workload is a collection of systems. They will execute as much in parallel as possible.  
 /// They are evaluated first to last when they can't be parallelized.  
+/// With the `parallel` feature, a system starts as soon as the systems it conflicts with or has to run after are done,
+/// it doesn't wait for the rest of its batch.  
+/// When a system fails, no new system starts and the workload returns the error, with or without the `parallel` feature.
+/// Systems already running in parallel finish and their errors are returned too.  
 /// The default workload will automatically be set to the first workload added.
 pub struct Workload {
     pub(super) name: Box<dyn Label>,
     pub(super) tags: Vec<Box<dyn Label>>,
     pub(super) systems: Vec<WorkloadSystem>,
     pub(super) run_if: Option<Box<dyn WorkloadRunIfFn>>,
+    /// Storages borrowed by `run_if`
+    pub(super) run_if_borrow_constraints: Vec<TypeInfo>,
     pub(super) before_all: DedupedLabels,
     pub(super) after_all: DedupedLabels,
     pub(super) overwritten_name: bool,
     pub(super) require_before: DedupedLabels,
     pub(super) require_after: DedupedLabels,
+    pub(super) on_error: Option<ErrorHandler>,
     pub(super) barriers: Vec<usize>,
 }
 
@@ -185,12 +198,14 @@ impl Workload {
             systems: Vec::new(),
             name: label.clone(),
             run_if: None,
+            run_if_borrow_constraints: Vec::new(),
             tags: vec![label],
             before_all: DedupedLabels::new(),
             after_all: DedupedLabels::new(),
             overwritten_name: true,
             require_before: DedupedLabels::new(),
             require_after: DedupedLabels::new(),
+            on_error: None,
             barriers: Vec::new(),
         }
     }
@@ -230,6 +245,10 @@ impl Workload {
                 })),
             };
 
+            system
+                .run_if_borrow_constraints
+                .extend(self.run_if_borrow_constraints.iter().cloned());
+
             system.tags.extend(self.tags.iter().cloned());
 
             system.before_all.extend(self.before_all.iter().cloned());
@@ -240,9 +259,16 @@ impl Workload {
             system
                 .require_after
                 .extend(self.require_after.iter().cloned());
+
+            // handlers of nested workloads take precedence
+            if system.on_error.is_none() {
+                system.on_error = self.on_error.clone();
+            }
         }
 
         self.run_if = None;
+        self.run_if_borrow_constraints.clear();
+        self.on_error = None;
         self.tags.clear();
         self.before_all.clear();
         self.after_all.clear();
@@ -254,6 +280,59 @@ impl Workload {
     pub fn with_workload(self, other: Workload) -> Workload {
         self.merge(other)
     }
+    /// Adds `set` as a named set of systems, like [`Workload::with_workload`].\
+    /// `set`'s run conditions, tags and ordering constraints apply to all its systems.\
+    /// The whole set can also be toggled between workload runs with [`World::disable_set`] and [`World::enable_set`], using `set`'s name.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Unique, UniqueViewMut, Workload, World};
+    ///
+    /// #[derive(Unique)]
+    /// struct Draws(u32);
+    ///
+    /// fn draw_colliders(mut draws: UniqueViewMut<Draws>) {
+    ///     draws.0 += 1;
+    /// }
+    ///
+    /// fn draw_paths(mut draws: UniqueViewMut<Draws>) {
+    ///     draws.0 += 1;
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(Draws(0));
+    ///
+    /// Workload::new("frame")
+    ///     .with_set(
+    ///         Workload::new("debug")
+    ///             .with_system(draw_colliders)
+    ///             .with_system(draw_paths),
+    ///     )
+    ///     .add_to_world(&world)
+    ///     .unwrap();
+    ///
+    /// world.run_default_workload().unwrap();
+    /// world.disable_set("debug");
+    /// world.run_default_workload().unwrap();
+    ///
+    /// assert_eq!(world.borrow::<UniqueViewMut<Draws>>().unwrap().0, 2);
+    /// ```
+    ///
+    /// [`World::disable_set`]: crate::World::disable_set
+    /// [`World::enable_set`]: crate::World::enable_set
+    pub fn with_set(self, mut set: Workload) -> Workload {
+        let label = set.name.clone();
+        let is_enabled = move |world: &World| Ok(world.is_set_enabled(label.clone()));
+
+        set.run_if = Some(match set.run_if.take() {
+            Some(run_if) => {
+                Box::new(move |world: &World| Ok(is_enabled(world)? && run_if.run(world)?))
+            }
+            None => Box::new(is_enabled),
+        });
+
+        self.merge(set)
+    }
     /// Adds a system to the workload being created.
     ///
     /// ### Example:
@@ -437,6 +516,7 @@ impl Workload {
             workloads,
             workloads_info,
             default,
+            disabled_sets: _,
         } = &mut *world
             .scheduler
             .borrow_mut()
@@ -495,7 +575,7 @@ impl Workload {
             .all_storages
             .borrow()
             .map_err(|_| error::UniquePresence::AllStorages)?;
-        let storages = all_storages.storages.read();
+        let storages = &all_storages.storages;
 
         let unique_name = type_name::<UniqueStorage<ComponentType>>()
             .split_once('<')
@@ -503,7 +583,7 @@ impl Workload {
             .0;
 
         for work_unit in &self.systems {
-            if let Some(value) = check_uniques_in_systems(work_unit, unique_name, &storages) {
+            if let Some(value) = check_uniques_in_systems(work_unit, unique_name, storages) {
                 return value;
             }
         }
@@ -543,20 +623,174 @@ impl Workload {
 
         self
     }
+    /// Applies the [`CommandBuffer`] at this point of the workload.\
+    /// Systems added after the sync point see the changes queued by the systems before it.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{CommandBuffer, Component, UniqueViewMut, View, Workload, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Bullet;
+    ///
+    /// fn spawn(mut commands: UniqueViewMut<CommandBuffer>) {
+    ///     commands.add_entity((Bullet,));
+    /// }
+    ///
+    /// fn check(bullets: View<Bullet>) {
+    ///     assert_eq!(bullets.len(), 1);
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(CommandBuffer::new());
+    ///
+    /// Workload::new("frame")
+    ///     .with_system(spawn)
+    ///     .with_sync_point()
+    ///     .with_system(check)
+    ///     .add_to_world(&world)
+    ///     .unwrap();
+    ///
+    /// world.run_default_workload().unwrap();
+    /// ```
+    ///
+    /// [`CommandBuffer`]: crate::CommandBuffer
+    #[track_caller]
+    pub fn with_sync_point(self) -> Self {
+        self.with_system(sync_point)
+    }
+    /// Runs this workload repeatedly, as long as `condition` returns `true` and at most `max_iterations` times.\
+    /// `condition` is evaluated before each iteration, the workload might not run at all.\
+    /// Useful for physics substeps or constraint relaxation inside a single frame.
+    ///
+    /// The returned workload contains a single system, it doesn't run in parallel with other systems.
+    ///
+    /// ### Panics
+    ///
+    /// - `condition` is an invalid system.
+    /// - This workload can't be built.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Unique, UniqueView, UniqueViewMut, Workload, World};
+    ///
+    /// #[derive(Unique)]
+    /// struct Error(f32);
+    ///
+    /// fn relax(mut error: UniqueViewMut<Error>) {
+    ///     error.0 /= 2.0;
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(Error(1.0));
+    ///
+    /// Workload::new("frame")
+    ///     .with_workload(
+    ///         Workload::new("relaxation")
+    ///             .with_system(relax)
+    ///             .repeat_while(|error: UniqueView<Error>| error.0 > 0.1, 10),
+    ///     )
+    ///     .add_to_world(&world)
+    ///     .unwrap();
+    ///
+    /// world.run_default_workload().unwrap();
+    ///
+    /// assert_eq!(world.borrow::<UniqueView<Error>>().unwrap().0, 0.0625);
+    /// ```
+    #[track_caller]
+    pub fn repeat_while<RunB, Cond: IntoWorkloadRunIf<RunB>>(
+        self,
+        condition: Cond,
+        max_iterations: usize,
+    ) -> Workload {
+        static REPEAT_WHILE_ID: AtomicU64 = AtomicU64::new(0);
+
+        let condition = condition.into_workload_run_if().unwrap();
+
+        let name = self.name.clone();
+        let mut repeated = Workload::new(name.clone());
+        repeated.tags = self.tags.clone();
+        repeated.before_all = self.before_all.clone();
+        repeated.after_all = self.after_all.clone();
+        repeated.require_before = self.require_before.clone();
+        repeated.require_after = self.require_after.clone();
+
+        let (scheduled, _) = self.build().unwrap();
+        let tracking_to_enable = scheduled.tracking_to_enable.clone();
+
+        // each repeated workload is a distinct system
+        let type_id =
+            TypeId(u128::MAX - u128::from(REPEAT_WHILE_ID.fetch_add(1, Ordering::Relaxed)));
+
+        let system_fn = move |world: &World| {
+            for _ in 0..max_iterations {
+                if !condition.run(world)? {
+                    break;
+                }
+
+                scheduled.run_with_world(world).map_err(|err| match err {
+                    error::RunWorkload::Run((_, err)) => err,
+                    // a system can only return a single error
+                    error::RunWorkload::Runs(mut runs) => runs.remove(0).1,
+                    error::RunWorkload::Scheduler | error::RunWorkload::MissingWorkload => {
+                        unreachable!()
+                    }
+                })?;
+            }
+
+            Ok(())
+        };
+
+        let generator = move |constraints: &mut Vec<TypeInfo>| {
+            constraints.push(TypeInfo {
+                name: type_name::<AllStorages>().into(),
+                mutability: Mutability::Exclusive,
+                storage_id: StorageId::of::<AllStorages>(),
+                #[cfg(not(feature = "thread_local"))]
+                thread_safe: true,
+                #[cfg(feature = "thread_local")]
+                thread_safe: false,
+            });
+
+            type_id
+        };
+
+        let mut borrow_constraints = Vec::new();
+        generator(&mut borrow_constraints);
+
+        repeated.systems.push(WorkloadSystem {
+            type_id,
+            display_name: name,
+            system_fn: Box::new(system_fn),
+            borrow_constraints,
+            tracking_to_enable,
+            generator: Box::new(generator),
+            run_if: None,
+            run_if_borrow_constraints: Vec::new(),
+            tags: Vec::new(),
+            before_all: DedupedLabels::new(),
+            after_all: DedupedLabels::new(),
+            require_in_workload: DedupedLabels::new(),
+            require_before: DedupedLabels::new(),
+            require_after: DedupedLabels::new(),
+            on_error: None,
+        });
+
+        repeated
+    }
 }
 
 fn check_uniques_in_systems(
     system: &WorkloadSystem,
     unique_name: &str,
-    storages: &ShipHashMap<StorageId, crate::storage::SBox>,
+    storages: &crate::all_storages::StorageMap,
 ) -> Option<Result<(), error::UniquePresence>> {
     let WorkloadSystem {
         borrow_constraints, ..
     } = system;
 
     for type_info in borrow_constraints {
-        if type_info.name.starts_with(unique_name) && !storages.contains_key(&type_info.storage_id)
-        {
+        if type_info.name.starts_with(unique_name) && !storages.contains(&type_info.storage_id) {
             return Some(Err(error::UniquePresence::Unique(type_info.clone())));
         }
     }
@@ -591,6 +825,8 @@ fn create_workload(
         }
     }
 
+    insert_frame_arena_reset(&mut builder);
+
     let mut collected_systems: Vec<(usize, WorkloadSystem)> =
         Vec::with_capacity(builder.systems.len());
 
@@ -617,6 +853,12 @@ fn create_workload(
 
     batches.run_if = builder.run_if;
 
+    for (system_index, system) in &mut collected_systems {
+        if let Some(on_error) = system.on_error.take().or_else(|| builder.on_error.clone()) {
+            batches.on_error.insert(*system_index, on_error);
+        }
+    }
+
     if collected_systems.len() == 1 {
         let (
             system_index,
@@ -625,10 +867,20 @@ fn create_workload(
                 display_name,
                 borrow_constraints,
                 run_if,
+                run_if_borrow_constraints,
                 ..
             },
         ) = collected_systems.pop().unwrap();
 
+        batches.graph = SystemGraph {
+            dependency_count: vec![0],
+            dependents: vec![Vec::new()],
+            local: vec![borrow_constraints
+                .iter()
+                .chain(&run_if_borrow_constraints)
+                .any(|type_info| !type_info.thread_safe)],
+        };
+
         let mut all_storages = None;
         let mut non_send_sync = None;
 
@@ -714,6 +966,7 @@ fn create_workload(
     let mut collected_before = Vec::new();
     let mut collected_after = Vec::new();
     let mut collected_names = Vec::new();
+    let mut collected_borrows = Vec::new();
 
     for (
         index,
@@ -727,11 +980,20 @@ fn create_workload(
                 require_before,
                 require_after,
                 display_name,
+                borrow_constraints,
+                run_if_borrow_constraints,
                 ..
             },
         ),
     ) in collected_systems.iter_mut().enumerate()
     {
+        collected_borrows.push(
+            borrow_constraints
+                .iter()
+                .chain(&*run_if_borrow_constraints)
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
         memoize_before.insert(index, before_all.clone());
         memoize_after.insert(index, after_all.clone());
         collected_tags.push(core::mem::take(tags));
@@ -1011,9 +1273,113 @@ fn create_workload(
         }
     }
 
+    batches.graph = system_graph(
+        &seq_system_index_map,
+        &collected_borrows,
+        &collected_tags,
+        &memoize_before,
+        &memoize_after,
+    );
+
     Ok(workload_info)
 }
 
+/// Lists the systems each system has to wait for.
+///
+/// A system waits for the systems before it in `sequential` it conflicts with or has to run after.
+/// Makes all systems run after a [`reset_frame_arena`] system when one of them borrows the [`FrameArena`].
+fn insert_frame_arena_reset(builder: &mut Workload) {
+    let frame_arena = StorageId::of::<UniqueStorage<FrameArena>>();
+
+    let uses_arena = builder.systems.iter().any(|system| {
+        system
+            .borrow_constraints
+            .iter()
+            .any(|type_info| type_info.storage_id == frame_arena)
+    });
+
+    if !uses_arena {
+        return;
+    }
+
+    let mut reset = reset_frame_arena.into_workload_system().unwrap();
+
+    if builder
+        .systems
+        .iter()
+        .any(|system| system.type_id == reset.type_id)
+    {
+        return;
+    }
+
+    let tag = "__frame_arena_reset__";
+
+    for system in &mut builder.systems {
+        system.after_all.add(tag);
+    }
+
+    reset.tags.push(Box::new(tag));
+    builder.systems.insert(0, reset);
+}
+
+fn system_graph(
+    seq_system_index_map: &[usize],
+    collected_borrows: &[Vec<TypeInfo>],
+    collected_tags: &[Vec<Box<dyn Label>>],
+    memoize_before: &ShipHashMap<usize, DedupedLabels>,
+    memoize_after: &ShipHashMap<usize, DedupedLabels>,
+) -> SystemGraph {
+    let mut graph = SystemGraph {
+        dependency_count: vec![0; seq_system_index_map.len()],
+        dependents: vec![Vec::new(); seq_system_index_map.len()],
+        local: seq_system_index_map
+            .iter()
+            .map(|&index| {
+                collected_borrows[index]
+                    .iter()
+                    .any(|type_info| !type_info.thread_safe)
+            })
+            .collect(),
+    };
+
+    for (position, &index) in seq_system_index_map.iter().enumerate() {
+        for (other_position, &other_index) in seq_system_index_map[..position].iter().enumerate() {
+            let ordered = memoize_after[&index]
+                .iter()
+                .any(|requirement| collected_tags[other_index].contains(requirement))
+                || memoize_before[&other_index]
+                    .iter()
+                    .any(|requirement| collected_tags[index].contains(requirement));
+
+            if ordered
+                || borrows_conflict(&collected_borrows[index], &collected_borrows[other_index])
+            {
+                graph.dependency_count[position] += 1;
+                graph.dependents[other_position].push(position);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Returns `true` if both systems can't hold their borrows at the same time.
+fn borrows_conflict(
+    borrow_constraints: &[TypeInfo],
+    other_borrow_constraints: &[TypeInfo],
+) -> bool {
+    borrow_constraints.iter().any(|type_info| {
+        other_borrow_constraints.iter().any(|other_type_info| {
+            type_info.storage_id == StorageId::of::<AllStorages>()
+                || other_type_info.storage_id == StorageId::of::<AllStorages>()
+                || (!type_info.thread_safe && !other_type_info.thread_safe)
+                || (type_info.storage_id == other_type_info.storage_id
+                    && (type_info.mutability == Mutability::Exclusive
+                        || other_type_info.mutability == Mutability::Exclusive))
+        })
+    })
+}
+
 #[allow(clippy::needless_range_loop)]
 fn dependencies(
     index: usize,
@@ -1643,6 +2009,7 @@ fn insert_system_in_scheduler(
 mod tests {
     use super::*;
     use crate::component::{Component, Unique};
+    use crate::frame_arena::{reset_frame_arena, FrameArena};
     use crate::{
         AllStoragesViewMut, IntoWorkload, SystemModificator, UniqueView, UniqueViewMut, View,
         WorkloadModificator,
@@ -1692,6 +2059,8 @@ mod tests {
                 sequential: vec![0],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1722,6 +2091,8 @@ mod tests {
                 sequential: vec![0],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1754,6 +2125,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1786,6 +2159,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1818,6 +2193,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1842,6 +2219,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1881,6 +2260,8 @@ mod tests {
                 sequential: vec![0, 1, 2],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1912,6 +2293,8 @@ mod tests {
                 sequential: vec![0],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1935,6 +2318,8 @@ mod tests {
                 sequential: vec![0, 0],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1959,6 +2344,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -1982,6 +2369,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2024,6 +2413,8 @@ mod tests {
                 sequential: vec![0, 0],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2050,6 +2441,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2073,6 +2466,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2096,6 +2491,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2122,6 +2519,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2154,6 +2553,8 @@ mod tests {
                 sequential: vec![0, 1],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2179,6 +2580,8 @@ mod tests {
                 sequential: vec![],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2220,6 +2623,8 @@ mod tests {
                 sequential: vec![0, 1, 2, 3],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             })
         );
         assert_eq!(&scheduler.default, &label);
@@ -2516,6 +2921,8 @@ mod tests {
                 sequential: vec![0, 1, 2],
                 sequential_run_if: Vec::new(),
                 run_if: None,
+                on_error: ShipHashMap::default(),
+                graph: SystemGraph::default(),
             }
         );
     }
@@ -2642,4 +3049,32 @@ mod tests {
             .custom_error()
             .is_some());
     }
+
+    #[test]
+    fn system_graph() {
+        use crate::{View, ViewMut, World};
+
+        fn write_usize(_: ViewMut<'_, Usize>) {}
+        fn read_u32(_: View<'_, U32>) {}
+        fn read_usize(_: View<'_, Usize>) {}
+        fn write_u16(_: ViewMut<'_, U16>) {}
+
+        let world = World::new();
+
+        Workload::new("Systems")
+            .with_system(write_usize)
+            .with_system(read_u32)
+            .with_system(read_usize)
+            .with_system(write_u16.run_if(|_: View<'_, Usize>| true))
+            .add_to_world(&world)
+            .unwrap();
+
+        let scheduler = world.scheduler.borrow_mut().unwrap();
+        let label: Box<dyn Label> = Box::new("Systems");
+        let graph = &scheduler.workloads[&label].graph;
+
+        assert_eq!(graph.dependency_count, vec![0, 0, 1, 1]);
+        assert_eq!(graph.dependents, vec![vec![2, 3], vec![], vec![], vec![]]);
+        assert_eq!(graph.local, vec![false; 4]);
+    }
 }
@@ -1,17 +1,20 @@
 use crate::error;
 use crate::scheduler::into_workload_run_if::IntoWorkloadRunIf;
 use crate::scheduler::label::WorkloadLabel;
+use crate::scheduler::system::Decision;
 use crate::scheduler::workload::Workload;
 use crate::storage::StorageId;
 use crate::type_id::TypeId;
 use crate::AllStoragesViewMut;
 use crate::AsLabel;
 use crate::Component;
+use crate::Label;
 use crate::SparseSet;
 use crate::Unique;
 use crate::UniqueStorage;
 use crate::World;
 use alloc::boxed::Box;
+use alloc::sync::Arc;
 use core::any::type_name;
 use core::ops::Not;
 
@@ -107,6 +110,59 @@ pub trait WorkloadModificator {
     fn rename<T>(self, name: impl AsLabel<T>) -> Workload;
     /// Adds a tag to this workload. Tags can be used to control system ordering when running workloads.
     fn tag<T>(self, tag: impl AsLabel<T>) -> Workload;
+    /// Calls `on_error` when one of the workload's systems fails, without it the first error stops the workload.\
+    /// The handler receives the name of the system and its error, its [`Decision`] says whether to continue, retry the system or stop the workload.
+    ///
+    /// Handlers of nested workloads take precedence over this one for their systems.
+    /// Errors of run conditions always stop the workload.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{
+    ///     Decision, IntoWorkload, IntoWorkloadTrySystem, Unique, UniqueViewMut, Workload,
+    ///     WorkloadModificator, World,
+    /// };
+    ///
+    /// #[derive(Unique)]
+    /// struct Attempts(u32);
+    ///
+    /// #[derive(Debug)]
+    /// struct Busy;
+    ///
+    /// impl std::fmt::Display for Busy {
+    ///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    ///         f.write_str("busy")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for Busy {}
+    ///
+    /// fn connect(mut attempts: UniqueViewMut<Attempts>) -> Result<(), Busy> {
+    ///     attempts.0 += 1;
+    ///
+    ///     if attempts.0 < 3 {
+    ///         Err(Busy)
+    ///     } else {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// fn network() -> Workload {
+    ///     (connect.into_workload_try_system().unwrap(),)
+    ///         .into_workload()
+    ///         .on_error(|_system, _err, _world| Decision::Retry)
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.add_unique(Attempts(0));
+    /// world.add_workload(network);
+    ///
+    /// world.run_workload(network).unwrap();
+    /// assert_eq!(world.borrow::<UniqueViewMut<Attempts>>().unwrap().0, 3);
+    /// ```
+    fn on_error<F>(self, on_error: F) -> Workload
+    where
+        F: Fn(&dyn Label, &error::Run, &World) -> Decision + Send + Sync + 'static;
 }
 
 impl WorkloadModificator for Workload {
@@ -114,6 +170,7 @@ impl WorkloadModificator for Workload {
     fn run_if<RunB, Run: IntoWorkloadRunIf<RunB>>(mut self, run_if: Run) -> Workload {
         let run_if = run_if.into_workload_run_if().unwrap();
 
+        Run::run_if_borrow_info(&mut self.run_if_borrow_constraints);
         self.run_if = if let Some(prev_run_if) = self.run_if.take() {
             Some(Box::new(move |world: &World| {
                 Ok(prev_run_if.run(world)? && run_if.run(world)?)
@@ -148,6 +205,8 @@ impl WorkloadModificator for Workload {
     fn skip_if<RunB, Run: IntoWorkloadRunIf<RunB>>(mut self, should_skip: Run) -> Self {
         let mut should_skip = should_skip.into_workload_run_if().unwrap();
 
+        Run::run_if_borrow_info(&mut self.run_if_borrow_constraints);
+
         should_skip = Box::new(move |world: &World| should_skip.run(world).map(Not::not));
 
         self.run_if = if let Some(prev_run_if) = self.run_if.take() {
@@ -201,6 +260,14 @@ impl WorkloadModificator for Workload {
     fn tag<T>(mut self, tag: impl AsLabel<T>) -> Workload {
         self.tags.push(tag.as_label());
 
+        self
+    }
+    fn on_error<F>(mut self, on_error: F) -> Workload
+    where
+        F: Fn(&dyn Label, &error::Run, &World) -> Decision + Send + Sync + 'static,
+    {
+        self.on_error = Some(Arc::new(on_error));
+
         self
     }
 }
@@ -287,4 +354,20 @@ where
 
         workload.tag(tag)
     }
+    fn on_error<F>(self, on_error: F) -> Workload
+    where
+        F: Fn(&dyn Label, &error::Run, &World) -> Decision + Send + Sync + 'static,
+    {
+        let mut workload = (self)();
+
+        let label = WorkloadLabel {
+            type_id: TypeId::of::<W>(),
+            name: type_name::<W>().as_label(),
+        };
+
+        workload = workload.tag(label.clone());
+        workload.name = Box::new(label);
+
+        workload.on_error(on_error)
+    }
 }
@@ -0,0 +1,1017 @@
+mod diff;
+mod scope;
+
+pub use diff::WorldDiff;
+
+pub(crate) use diff::apply_diff;
+pub(crate) use scope::Checkpoint;
+
+use crate::all_storages::AllStorages;
+use crate::component::{component_name, Component};
+use crate::component_registrar::{ComponentRegistrar, TupleRegisterComponent};
+use crate::compression::Compressor;
+use crate::entities::Entities;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::sparse_set::SparseSet;
+use crate::storage::StorageId;
+use crate::{ShipHashMap, ShipHashSet};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::{type_name, Any};
+use core::mem::{align_of, size_of};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const MAGIC: &[u8; 4] = b"SHSN";
+const VERSION: u8 = 1;
+
+/// Returns the layout hash of `T` for `version`.
+///
+/// The hash is computed from `T`'s type name, size and alignment and `version`.
+/// Bump `version` when the serialized representation of a component changes without its size changing.\
+/// The type name isn't guaranteed to be stable between compiler versions,
+/// components setting [`Component::NAME`] are registered with [`component_layout_hash`] which uses it instead.
+pub fn layout_hash<T: 'static>(version: u32) -> u64 {
+    hash_layout::<T>(type_name::<T>(), version)
+}
+
+/// Returns the layout hash [`SnapshotRegistry`] uses for `T` and `version`.
+///
+/// Same as [`layout_hash`] but uses [`Component::NAME`] when it's set,
+/// renaming or moving the type then doesn't change the hash.
+pub fn component_layout_hash<T: Component>(version: u32) -> u64 {
+    hash_layout::<T>(component_name::<T>(), version)
+}
+
+fn hash_layout<T>(name: &str, version: u32) -> u64 {
+    // FNV-1a, it has to be the same on every platform and every run
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    let bytes = name
+        .as_bytes()
+        .iter()
+        .copied()
+        .chain((size_of::<T>() as u64).to_le_bytes())
+        .chain((align_of::<T>() as u64).to_le_bytes())
+        .chain(version.to_le_bytes());
+
+    for byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+/// Type erased (de)serialization of a storage.
+trait SnapshotCodec: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn name(&self) -> &'static str;
+    fn layout_hash(&self) -> u64;
+    fn encode(
+        &self,
+        all_storages: &mut AllStorages,
+        is_included: &dyn Fn(EntityId) -> bool,
+    ) -> Vec<(EntityId, Vec<u8>)>;
+    /// Returns `None` if a component couldn't be decoded or no migration applies.
+    fn decode(
+        &self,
+        layout_hash: u64,
+        saved_version: u32,
+        entries: &[(EntityId, &[u8])],
+    ) -> Option<Box<dyn Any>>;
+    fn can_decode(&self, layout_hash: u64, saved_version: u32) -> bool;
+    /// Replaces the `EntityId` of each decoded component and the references it contains.
+    fn remap(&self, components: &mut dyn Any, map: &EntityIdMap);
+    fn insert(&self, all_storages: &mut AllStorages, components: Box<dyn Any>);
+    fn delete(&self, all_storages: &mut AllStorages, entities: &[EntityId]);
+}
+
+type EntityVisitor<T> = fn(&mut T, &mut dyn FnMut(&mut EntityId));
+
+/// Conversion of a component to and from bytes.
+pub(crate) struct Encoding<T> {
+    pub(crate) serialize: fn(&T) -> Option<Vec<u8>>,
+    pub(crate) deserialize: fn(&[u8]) -> Option<T>,
+}
+
+impl<T> Clone for Encoding<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Encoding<T> {}
+
+impl<T: Serialize + DeserializeOwned> Encoding<T> {
+    pub(crate) fn bincode() -> Encoding<T> {
+        Encoding {
+            serialize: |component| bincode::serialize(component).ok(),
+            deserialize: |bytes| bincode::deserialize(bytes).ok(),
+        }
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<T: bytemuck::Pod + Serialize + DeserializeOwned> Encoding<T> {
+    /// Copies the component's memory.
+    ///
+    /// bincode writes numbers in little endian, on big endian targets the memory doesn't match
+    /// and components go through bincode to keep the bytes identical on all platforms.
+    pub(crate) fn pod() -> Encoding<T> {
+        if cfg!(target_endian = "little") {
+            Encoding {
+                serialize: |component| Some(bytemuck::bytes_of(component).to_vec()),
+                deserialize: |bytes| bytemuck::try_pod_read_unaligned(bytes).ok(),
+            }
+        } else {
+            Encoding::bincode()
+        }
+    }
+}
+
+type Migration<T> = Arc<dyn Fn(&[u8]) -> Option<T> + Send + Sync>;
+
+struct Codec<T> {
+    layout_hash: u64,
+    encoding: Encoding<T>,
+    layout_migrations: ShipHashMap<u64, Migration<T>>,
+    // sorted by version
+    version_migrations: Vec<(u32, Migration<T>)>,
+    entity_visitor: Option<EntityVisitor<T>>,
+}
+
+impl<T> Codec<T> {
+    /// Returns `Some(None)` if components saved with `layout_hash` in a snapshot of `saved_version` can be deserialized as is,
+    /// `Some(Some(migration))` if they have to be converted and `None` if they can't be loaded.
+    fn migration(&self, layout_hash: u64, saved_version: u32) -> Option<Option<&Migration<T>>> {
+        if let Some((_, migration)) = self
+            .version_migrations
+            .iter()
+            .find(|(version, _)| saved_version <= *version)
+        {
+            Some(Some(migration))
+        } else if layout_hash == self.layout_hash {
+            Some(None)
+        } else {
+            self.layout_migrations.get(&layout_hash).map(Some)
+        }
+    }
+}
+
+impl<T: Component + Send + Sync + Serialize + DeserializeOwned> SnapshotCodec for Codec<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn name(&self) -> &'static str {
+        component_name::<T>()
+    }
+    fn layout_hash(&self) -> u64 {
+        self.layout_hash
+    }
+    fn encode(
+        &self,
+        all_storages: &mut AllStorages,
+        is_included: &dyn Fn(EntityId) -> bool,
+    ) -> Vec<(EntityId, Vec<u8>)> {
+        match all_storages.exclusive_storage_mut::<SparseSet<T>>() {
+            Ok(sparse_set) => sparse_set
+                .dense
+                .iter()
+                .zip(&sparse_set.data)
+                .filter(|(&entity, _)| is_included(entity))
+                .filter_map(|(&entity, component)| {
+                    Some((entity, (self.encoding.serialize)(component)?))
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+    fn decode(
+        &self,
+        layout_hash: u64,
+        saved_version: u32,
+        entries: &[(EntityId, &[u8])],
+    ) -> Option<Box<dyn Any>> {
+        let components = match self.migration(layout_hash, saved_version)? {
+            Some(migration) => entries
+                .iter()
+                .map(|&(entity, bytes)| Some((entity, migration(bytes)?)))
+                .collect::<Option<Vec<(EntityId, T)>>>()?,
+            None => entries
+                .iter()
+                .map(|&(entity, bytes)| Some((entity, (self.encoding.deserialize)(bytes)?)))
+                .collect::<Option<Vec<(EntityId, T)>>>()?,
+        };
+
+        Some(Box::new(components))
+    }
+    fn can_decode(&self, layout_hash: u64, saved_version: u32) -> bool {
+        self.migration(layout_hash, saved_version).is_some()
+    }
+    fn remap(&self, components: &mut dyn Any, map: &EntityIdMap) {
+        if let Some(components) = components.downcast_mut::<Vec<(EntityId, T)>>() {
+            // components of entities that weren't alive when saved have no new id
+            components.retain(|(entity, _)| map.0.contains_key(entity));
+
+            for (entity, component) in components {
+                *entity = map.0[&*entity];
+
+                if let Some(visitor) = self.entity_visitor {
+                    visitor(component, &mut |reference| {
+                        if let Some(&new) = map.0.get(&*reference) {
+                            *reference = new;
+                        }
+                    });
+                }
+            }
+        }
+    }
+    fn insert(&self, all_storages: &mut AllStorages, components: Box<dyn Any>) {
+        if let Ok(components) = components.downcast::<Vec<(EntityId, T)>>() {
+            let current = all_storages.get_current();
+
+            all_storages
+                .exclusive_storage_or_insert_mut(
+                    StorageId::of::<SparseSet<T>>(),
+                    SparseSet::<T>::new,
+                )
+                .private_extend_from_iter(*components, current);
+        }
+    }
+    fn delete(&self, all_storages: &mut AllStorages, entities: &[EntityId]) {
+        let current = all_storages.get_current();
+
+        if let Ok(sparse_set) = all_storages.exclusive_storage_mut::<SparseSet<T>>() {
+            for &entity in entities {
+                sparse_set.dyn_delete(entity, current);
+            }
+        }
+    }
+}
+
+/// List of components saved in a snapshot, with their layout hash.
+///
+/// Components are identified by their [`Component::NAME`] or type name.\
+/// A snapshot can only be loaded if all its components are registered with the same layout hash,
+/// or with a migration from the layout hash they were saved with.
+#[derive(Clone, Default)]
+pub struct SnapshotRegistry {
+    // ordered by registration, snapshots of the same World are identical
+    codecs: Vec<Arc<dyn SnapshotCodec>>,
+    names: ShipHashMap<&'static str, usize>,
+    version: u32,
+    compressor: Option<Arc<dyn Compressor>>,
+}
+
+impl SnapshotRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> SnapshotRegistry {
+        SnapshotRegistry::default()
+    }
+    /// Registers `T` with the layout hash of version 0.
+    pub fn register<T: Component + Send + Sync + Serialize + DeserializeOwned>(
+        &mut self,
+    ) -> &mut SnapshotRegistry {
+        self.register_with_version::<T>(0)
+    }
+    /// Registers `T` with the layout hash of `version`.\
+    /// Registering a component again replaces its layout hash and drops its migrations.
+    pub fn register_with_version<T: Component + Send + Sync + Serialize + DeserializeOwned>(
+        &mut self,
+        version: u32,
+    ) -> &mut SnapshotRegistry {
+        self.insert_codec::<T>(Codec {
+            layout_hash: component_layout_hash::<T>(version),
+            encoding: Encoding::bincode(),
+            layout_migrations: ShipHashMap::default(),
+            version_migrations: Vec::new(),
+            entity_visitor: None,
+        });
+
+        self
+    }
+    /// Registers `T` with the layout hash of version 0, its components are copied as is instead of going through serde.
+    ///
+    /// The bytes are the same as bincode's for `#[repr(C)]` structs made of numbers and arrays deriving `Serialize` and `Deserialize`,
+    /// snapshots stay compatible with components registered with [`SnapshotRegistry::register`].\
+    /// On big endian targets components are serialized with serde to keep the same bytes.
+    ///
+    /// ### Example
+    /// ```
+    /// use bytemuck::{Pod, Zeroable};
+    /// use serde::{Deserialize, Serialize};
+    /// use shipyard::{Component, SnapshotRegistry, World};
+    ///
+    /// #[derive(Component, Serialize, Deserialize, Clone, Copy, Pod, Zeroable, PartialEq, Debug)]
+    /// #[repr(C)]
+    /// struct Particle {
+    ///     position: [f32; 2],
+    ///     velocity: [f32; 2],
+    /// }
+    ///
+    /// let mut registry = SnapshotRegistry::new();
+    /// registry.register_pod::<Particle>();
+    ///
+    /// let mut world = World::new();
+    /// let particle = Particle {
+    ///     position: [0.0, 1.0],
+    ///     velocity: [2.0, 3.0],
+    /// };
+    /// let entity = world.add_entity((particle,));
+    ///
+    /// let snapshot = world.snapshot(&registry);
+    ///
+    /// let mut loaded = World::new();
+    /// loaded.load_snapshot(&snapshot, &registry).unwrap();
+    /// assert_eq!(*loaded.get::<&Particle>(entity).unwrap(), &particle);
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn register_pod<T>(&mut self) -> &mut SnapshotRegistry
+    where
+        T: Component + Send + Sync + Serialize + DeserializeOwned + bytemuck::Pod,
+    {
+        self.insert_codec::<T>(Codec {
+            layout_hash: component_layout_hash::<T>(0),
+            encoding: Encoding::pod(),
+            layout_migrations: ShipHashMap::default(),
+            version_migrations: Vec::new(),
+            entity_visitor: None,
+        });
+
+        self
+    }
+    /// Sets the version of the `World` saved in snapshots, 0 by default.\
+    /// Snapshots saved with an older version go through the migrations registered with [`SnapshotRegistry::register_migration`].
+    pub fn set_version(&mut self, version: u32) -> &mut SnapshotRegistry {
+        self.version = version;
+
+        self
+    }
+    /// Returns the version of the `World` saved in snapshots.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+    /// Compresses snapshots with `compressor` when they're saved and decompresses them when they're loaded.
+    ///
+    /// Snapshots saved without compressor can't be loaded once one is set, and vice versa.
+    pub fn set_compressor<C: Compressor + 'static>(
+        &mut self,
+        compressor: C,
+    ) -> &mut SnapshotRegistry {
+        self.compressor = Some(Arc::new(compressor));
+
+        self
+    }
+    /// Removes the compressor, snapshots are saved and loaded as is.
+    pub fn remove_compressor(&mut self) -> &mut SnapshotRegistry {
+        self.compressor = None;
+
+        self
+    }
+    /// Returns the bytes of a snapshot saved with this registry, decompressed if a compressor is set.
+    ///
+    /// [`WorldDiff::between`] works on decompressed snapshots.
+    ///
+    /// ### Errors
+    ///
+    /// - The compressor couldn't decompress the bytes.
+    pub fn decompress<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, [u8]>, error::Snapshot> {
+        match &self.compressor {
+            Some(compressor) => compressor
+                .decompress(bytes)
+                .map(Cow::Owned)
+                .ok_or(error::Snapshot::InvalidSnapshot),
+            None => Ok(Cow::Borrowed(bytes)),
+        }
+    }
+    /// Allows components of type `T` saved up to `World` version `last_version` to be loaded.\
+    /// Their bytes are deserialized as `Old` and converted with `migrate`.
+    ///
+    /// When multiple migrations apply, the one with the smallest version is used.
+    /// A migration takes precedence over the layout hash check.
+    ///
+    /// ### Panics
+    ///
+    /// - `T` isn't registered.
+    ///
+    /// ### Example
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use shipyard::{Component, SnapshotRegistry, World};
+    ///
+    /// #[derive(Component, Serialize, Deserialize)]
+    /// struct Health(u32);
+    ///
+    /// let mut registry = SnapshotRegistry::new();
+    /// registry.register::<Health>();
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.add_entity((Health(10),));
+    /// let v0_save = world.snapshot(&registry);
+    ///
+    /// // version 1 stores health as a percentage
+    /// #[derive(Serialize, Deserialize)]
+    /// struct OldHealth(u32);
+    ///
+    /// fn v0_to_v1(old: OldHealth) -> Health {
+    ///     Health(old.0 * 10)
+    /// }
+    ///
+    /// registry
+    ///     .set_version(1)
+    ///     .register_migration::<OldHealth, Health>(0, v0_to_v1);
+    ///
+    /// let mut loaded = World::new();
+    /// loaded.load_snapshot(&v0_save, &registry).unwrap();
+    /// assert_eq!(loaded.get::<&Health>(entity).unwrap().0, 100);
+    /// ```
+    #[track_caller]
+    pub fn register_migration<Old, T>(
+        &mut self,
+        last_version: u32,
+        migrate: fn(Old) -> T,
+    ) -> &mut SnapshotRegistry
+    where
+        T: Component + Send + Sync + Serialize + DeserializeOwned,
+        Old: DeserializeOwned + 'static,
+    {
+        let mut codec = self.cloned_codec::<T>();
+
+        let migration: Migration<T> =
+            Arc::new(move |bytes| bincode::deserialize::<Old>(bytes).ok().map(migrate));
+        match codec
+            .version_migrations
+            .binary_search_by_key(&last_version, |(version, _)| *version)
+        {
+            Ok(index) => codec.version_migrations[index].1 = migration,
+            Err(index) => codec
+                .version_migrations
+                .insert(index, (last_version, migration)),
+        }
+
+        self.insert_codec::<T>(codec);
+
+        self
+    }
+    /// Allows components of type `T` saved with `old_layout_hash` to be loaded.\
+    /// Their bytes are deserialized as `Old` and converted with `migrate`.
+    ///
+    /// ### Panics
+    ///
+    /// - `T` isn't registered.
+    #[track_caller]
+    pub fn register_layout_migration<Old, T>(
+        &mut self,
+        old_layout_hash: u64,
+        migrate: fn(Old) -> T,
+    ) -> &mut SnapshotRegistry
+    where
+        T: Component + Send + Sync + Serialize + DeserializeOwned,
+        Old: DeserializeOwned + 'static,
+    {
+        let mut codec = self.cloned_codec::<T>();
+
+        let migration: Migration<T> =
+            Arc::new(move |bytes| bincode::deserialize::<Old>(bytes).ok().map(migrate));
+        codec.layout_migrations.insert(old_layout_hash, migration);
+
+        self.insert_codec::<T>(codec);
+
+        self
+    }
+    /// Registers the `EntityId`s contained in `T`.\
+    /// When a snapshot is loaded with [`World::load_snapshot_remapped`], `visitor` is called on each `T`
+    /// and has to call the function it receives with each `EntityId` the component contains.
+    /// References to entities part of the snapshot are then replaced by their new id.
+    ///
+    /// ### Panics
+    ///
+    /// - `T` isn't registered.
+    ///
+    /// ### Example
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use shipyard::{Component, EntityId, SnapshotRegistry, World};
+    ///
+    /// #[derive(Component, Serialize, Deserialize)]
+    /// struct Parent(EntityId);
+    ///
+    /// let mut registry = SnapshotRegistry::new();
+    /// registry
+    ///     .register::<Parent>()
+    ///     .register_entity_references::<Parent>(|parent, visit| visit(&mut parent.0));
+    ///
+    /// let mut world = World::new();
+    /// let parent = world.add_entity(());
+    /// let child = world.add_entity((Parent(parent),));
+    /// let snapshot = world.snapshot(&registry);
+    ///
+    /// let mut other = World::new();
+    /// other.add_entity(());
+    ///
+    /// let map = other.load_snapshot_remapped(&snapshot, &registry).unwrap();
+    /// let new_child = map.get(child).unwrap();
+    /// assert_eq!(other.get::<&Parent>(new_child).unwrap().0, map.get(parent).unwrap());
+    /// ```
+    ///
+    /// [`World::load_snapshot_remapped`]: crate::World::load_snapshot_remapped()
+    #[track_caller]
+    #[allow(clippy::type_complexity)]
+    pub fn register_entity_references<T>(
+        &mut self,
+        visitor: fn(&mut T, &mut dyn FnMut(&mut EntityId)),
+    ) -> &mut SnapshotRegistry
+    where
+        T: Component + Send + Sync + Serialize + DeserializeOwned,
+    {
+        let mut codec = self.cloned_codec::<T>();
+        codec.entity_visitor = Some(visitor);
+
+        self.insert_codec::<T>(codec);
+
+        self
+    }
+    /// Registers the components of `T` that declared `serialize` with `#[derive(Component)]`.\
+    /// See [`ComponentRegistrar`].
+    ///
+    /// [`ComponentRegistrar`]: crate::ComponentRegistrar
+    pub fn register_components<T: TupleRegisterComponent>(&mut self) -> &mut SnapshotRegistry {
+        T::register_components(&mut ComponentRegistrar::snapshot(self));
+
+        self
+    }
+    /// Returns `true` if `T` was registered.
+    pub fn is_registered<T: Component>(&self) -> bool {
+        self.names.contains_key(component_name::<T>())
+    }
+    #[track_caller]
+    fn cloned_codec<T: Component + Send + Sync + Serialize + DeserializeOwned>(&self) -> Codec<T> {
+        let codec = self
+            .names
+            .get(component_name::<T>())
+            .and_then(|&index| self.codecs[index].as_any().downcast_ref::<Codec<T>>())
+            .unwrap_or_else(|| panic!("{} is not registered.", component_name::<T>()));
+
+        Codec {
+            layout_hash: codec.layout_hash,
+            encoding: codec.encoding,
+            layout_migrations: codec.layout_migrations.clone(),
+            version_migrations: codec.version_migrations.clone(),
+            entity_visitor: codec.entity_visitor,
+        }
+    }
+    fn insert_codec<T: Component + Send + Sync + Serialize + DeserializeOwned>(
+        &mut self,
+        codec: Codec<T>,
+    ) {
+        let codec: Arc<dyn SnapshotCodec> = Arc::new(codec);
+
+        if let Some(&index) = self.names.get(component_name::<T>()) {
+            self.codecs[index] = codec;
+        } else {
+            self.names.insert(component_name::<T>(), self.codecs.len());
+            self.codecs.push(codec);
+        }
+    }
+    fn codec_by_name(&self, name: &str) -> Option<&dyn SnapshotCodec> {
+        self.names.get(name).map(|&index| &*self.codecs[index])
+    }
+}
+
+/// Selects which components and entities are part of a snapshot.
+///
+/// By default everything in the registry is saved.
+///
+/// ### Example
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use shipyard::{Component, SnapshotFilter, SnapshotRegistry, World};
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Pos(f32, f32);
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Sprite(u32);
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Persistent;
+///
+/// let mut registry = SnapshotRegistry::new();
+/// registry.register::<Pos>().register::<Sprite>().register::<Persistent>();
+///
+/// let mut world = World::new();
+/// let saved = world.add_entity((Pos(0.0, 0.0), Sprite(0), Persistent));
+/// let transient = world.add_entity((Pos(1.0, 0.0), Sprite(1)));
+///
+/// let snapshot = world.snapshot_filtered(
+///     &registry,
+///     &SnapshotFilter::new().deny::<Sprite>().with::<Persistent>(),
+/// );
+///
+/// let mut loaded = World::new();
+/// loaded.load_snapshot(&snapshot, &registry).unwrap();
+///
+/// assert!(loaded.get::<&Pos>(saved).is_ok());
+/// assert!(loaded.get::<&Sprite>(saved).is_err());
+/// assert!(!loaded.is_entity_alive(transient));
+/// ```
+#[derive(Default)]
+pub struct SnapshotFilter<'a> {
+    allowed: Option<ShipHashSet<&'static str>>,
+    denied: ShipHashSet<&'static str>,
+    with: Vec<fn(&mut AllStorages, EntityId) -> bool>,
+    without: Vec<fn(&mut AllStorages, EntityId) -> bool>,
+    predicate: Option<Box<dyn Fn(EntityId) -> bool + 'a>>,
+}
+
+impl<'a> SnapshotFilter<'a> {
+    /// Creates a filter keeping everything.
+    pub fn new() -> SnapshotFilter<'a> {
+        SnapshotFilter::default()
+    }
+    /// Saves `T`.\
+    /// Once a component is allowed, only allowed components are saved.
+    pub fn allow<T: Component>(mut self) -> SnapshotFilter<'a> {
+        self.allowed
+            .get_or_insert_with(ShipHashSet::default)
+            .insert(component_name::<T>());
+
+        self
+    }
+    /// Doesn't save `T`, even if it was allowed.
+    pub fn deny<T: Component>(mut self) -> SnapshotFilter<'a> {
+        self.denied.insert(component_name::<T>());
+
+        self
+    }
+    /// Only saves entities with a `T` component.\
+    /// `T` doesn't have to be part of the registry, it can be used as a marker.
+    pub fn with<T: Component>(mut self) -> SnapshotFilter<'a> {
+        self.with.push(has_component::<T>);
+
+        self
+    }
+    /// Only saves entities without a `T` component.
+    pub fn without<T: Component>(mut self) -> SnapshotFilter<'a> {
+        self.without.push(has_component::<T>);
+
+        self
+    }
+    /// Only saves entities for which `predicate` returns `true`.\
+    /// Replaces the previous predicate.
+    pub fn entities<F: Fn(EntityId) -> bool + 'a>(mut self, predicate: F) -> SnapshotFilter<'a> {
+        self.predicate = Some(Box::new(predicate));
+
+        self
+    }
+    /// Returns `true` if `T` will be saved, provided it's part of the registry.
+    pub fn is_component_saved<T: Component>(&self) -> bool {
+        self.is_name_saved(component_name::<T>())
+    }
+    fn is_name_saved(&self, name: &str) -> bool {
+        let is_allowed = match &self.allowed {
+            Some(allowed) => allowed.contains(name),
+            None => true,
+        };
+
+        is_allowed && !self.denied.contains(name)
+    }
+    fn filters_entities(&self) -> bool {
+        !self.with.is_empty() || !self.without.is_empty() || self.predicate.is_some()
+    }
+    fn is_entity_saved(&self, all_storages: &mut AllStorages, entity: EntityId) -> bool {
+        let is_selected = match &self.predicate {
+            Some(predicate) => predicate(entity),
+            None => true,
+        };
+
+        is_selected
+            && self.with.iter().all(|has| has(all_storages, entity))
+            && !self.without.iter().any(|has| has(all_storages, entity))
+    }
+}
+
+fn has_component<T: Component>(all_storages: &mut AllStorages, entity: EntityId) -> bool {
+    all_storages
+        .exclusive_storage_mut::<SparseSet<T>>()
+        .is_ok_and(|sparse_set| sparse_set.contains(entity))
+}
+
+/// Encodes the alive entities and the components in `registry` selected by `filter`.
+pub(crate) fn save(
+    all_storages: &mut AllStorages,
+    registry: &SnapshotRegistry,
+    filter: &SnapshotFilter<'_>,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION);
+    bytes.extend_from_slice(&registry.version.to_le_bytes());
+
+    let codecs: Vec<&dyn SnapshotCodec> = registry
+        .codecs
+        .iter()
+        .map(|codec| &**codec)
+        .filter(|codec| filter.is_name_saved(codec.name()))
+        .collect();
+
+    // schema
+    bytes.extend_from_slice(&(codecs.len() as u32).to_le_bytes());
+    for codec in &codecs {
+        write_slice(&mut bytes, codec.name().as_bytes());
+        bytes.extend_from_slice(&codec.layout_hash().to_le_bytes());
+    }
+
+    let entities = all_storages.exclusive_storage_mut::<Entities>().unwrap();
+    let mut alive: Vec<EntityId> = entities.iter().collect();
+
+    // `None` when all entities are saved
+    let saved: Option<ShipHashSet<EntityId>> = if filter.filters_entities() {
+        alive.retain(|&entity| filter.is_entity_saved(all_storages, entity));
+
+        Some(alive.iter().copied().collect())
+    } else {
+        None
+    };
+
+    bytes.extend_from_slice(&(alive.len() as u32).to_le_bytes());
+    for entity in alive {
+        bytes.extend_from_slice(&entity.inner().to_le_bytes());
+    }
+
+    let is_included = |entity: EntityId| match &saved {
+        Some(saved) => saved.contains(&entity),
+        None => true,
+    };
+
+    for codec in codecs {
+        let components = codec.encode(all_storages, &is_included);
+
+        bytes.extend_from_slice(&(components.len() as u32).to_le_bytes());
+        for (entity, data) in components {
+            bytes.extend_from_slice(&entity.inner().to_le_bytes());
+            write_slice(&mut bytes, &data);
+        }
+    }
+
+    match &registry.compressor {
+        Some(compressor) => compressor.compress(&bytes),
+        None => bytes,
+    }
+}
+
+/// Old to new `EntityId` correspondence of the entities of a snapshot.
+///
+/// Returned by [`World::load_snapshot_remapped`].\
+/// With the `replication` feature, it's also used to map server entities to client entities.
+///
+/// [`World::load_snapshot_remapped`]: crate::World::load_snapshot_remapped()
+#[derive(Clone, Default, Debug)]
+pub struct EntityIdMap(ShipHashMap<EntityId, EntityId>);
+
+impl EntityIdMap {
+    /// Returns the new id of the entity saved as `old`.
+    pub fn get(&self, old: EntityId) -> Option<EntityId> {
+        self.0.get(&old).copied()
+    }
+    /// Returns the number of entities in the map.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Returns `true` if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Returns an iterator over the old and new ids, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, EntityId)> + '_ {
+        self.0.iter().map(|(&old, &new)| (old, new))
+    }
+    #[cfg(feature = "replication")]
+    pub(crate) fn insert(&mut self, old: EntityId, new: EntityId) {
+        self.0.insert(old, new);
+    }
+    #[cfg(feature = "replication")]
+    pub(crate) fn remove(&mut self, old: EntityId) -> Option<EntityId> {
+        self.0.remove(&old)
+    }
+}
+
+type DecodedStorages<'r> = Vec<(&'r dyn SnapshotCodec, Box<dyn Any>)>;
+
+/// Snapshot split in its sections, components are still encoded.
+struct RawSnapshot<'a> {
+    version: u32,
+    alive: Vec<EntityId>,
+    storages: Vec<RawStorage<'a>>,
+}
+
+/// Components of a single storage of a [`RawSnapshot`].
+struct RawStorage<'a> {
+    name: &'a str,
+    layout_hash: u64,
+    components: Vec<(EntityId, &'a [u8])>,
+}
+
+/// Splits a snapshot in its sections without decoding any component.
+fn parse(bytes: &[u8]) -> Result<RawSnapshot<'_>, error::Snapshot> {
+    let mut reader = Reader(bytes);
+
+    if reader.take(MAGIC.len())? != MAGIC || reader.take(1)? != [VERSION] {
+        return Err(error::Snapshot::InvalidSnapshot);
+    }
+
+    let version = reader.u32()?;
+
+    let mut schema = Vec::new();
+    for _ in 0..reader.u32()? {
+        let name =
+            core::str::from_utf8(reader.slice()?).map_err(|_| error::Snapshot::InvalidSnapshot)?;
+        let layout_hash = reader.u64()?;
+
+        schema.push((name, layout_hash));
+    }
+
+    let mut alive = Vec::new();
+    for _ in 0..reader.u32()? {
+        alive.push(reader.entity()?);
+    }
+
+    let mut storages = Vec::with_capacity(schema.len());
+    for (name, layout_hash) in schema {
+        let mut components = Vec::new();
+        for _ in 0..reader.u32()? {
+            components.push((reader.entity()?, reader.slice()?));
+        }
+
+        storages.push(RawStorage {
+            name,
+            layout_hash,
+            components,
+        });
+    }
+
+    if !reader.0.is_empty() {
+        return Err(error::Snapshot::InvalidSnapshot);
+    }
+
+    Ok(RawSnapshot {
+        version,
+        alive,
+        storages,
+    })
+}
+
+/// Returns an error if components saved by `saved_version` can't be loaded with `registry`.
+fn check_version(saved_version: u32, registry: &SnapshotRegistry) -> Result<(), error::Snapshot> {
+    if saved_version > registry.version {
+        Err(error::Snapshot::NewerVersion {
+            expected: registry.version,
+            found: saved_version,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Decodes the components of a storage saved by `saved_version` with the codec registered under `name`.
+fn decode_storage<'r>(
+    registry: &'r SnapshotRegistry,
+    saved_version: u32,
+    name: &str,
+    layout_hash: u64,
+    components: &[(EntityId, &[u8])],
+) -> Result<(&'r dyn SnapshotCodec, Box<dyn Any>), error::Snapshot> {
+    let codec = registry
+        .codec_by_name(name)
+        .ok_or_else(|| error::Snapshot::UnregisteredComponent(Cow::Owned(String::from(name))))?;
+
+    if !codec.can_decode(layout_hash, saved_version) {
+        return Err(error::Snapshot::LayoutMismatch {
+            component: Cow::Borrowed(codec.name()),
+            expected: codec.layout_hash(),
+            found: layout_hash,
+        });
+    }
+
+    let components = codec.decode(layout_hash, saved_version, components).ok_or(
+        error::Snapshot::InvalidComponentData(Cow::Borrowed(codec.name())),
+    )?;
+
+    Ok((codec, components))
+}
+
+/// Decodes a snapshot without modifying the `World`, returns the saved entities and components.
+fn decode<'r>(
+    bytes: &[u8],
+    registry: &'r SnapshotRegistry,
+) -> Result<(Vec<EntityId>, DecodedStorages<'r>), error::Snapshot> {
+    let bytes = registry.decompress(bytes)?;
+    let snapshot = parse(&bytes)?;
+
+    check_version(snapshot.version, registry)?;
+
+    let storages = snapshot
+        .storages
+        .iter()
+        .map(|storage| {
+            decode_storage(
+                registry,
+                snapshot.version,
+                storage.name,
+                storage.layout_hash,
+                &storage.components,
+            )
+        })
+        .collect::<Result<DecodedStorages<'r>, error::Snapshot>>()?;
+
+    Ok((snapshot.alive, storages))
+}
+
+/// Decodes a snapshot and adds its entities and components to `all_storages`, keeping their ids.
+pub(crate) fn load(
+    all_storages: &mut AllStorages,
+    bytes: &[u8],
+    registry: &SnapshotRegistry,
+) -> Result<(), error::Snapshot> {
+    let (alive, storages) = decode(bytes, registry)?;
+
+    for entity in alive {
+        if !all_storages.spawn(entity) {
+            return Err(error::Snapshot::EntityIdMismatch(entity));
+        }
+    }
+
+    for (codec, components) in storages {
+        codec.insert(all_storages, components);
+    }
+
+    Ok(())
+}
+
+/// Decodes a snapshot and adds its entities and components to `all_storages` with new ids.
+pub(crate) fn load_remapped(
+    all_storages: &mut AllStorages,
+    bytes: &[u8],
+    registry: &SnapshotRegistry,
+) -> Result<EntityIdMap, error::Snapshot> {
+    let (alive, storages) = decode(bytes, registry)?;
+
+    let mut map = EntityIdMap::default();
+    for old in alive {
+        map.0.insert(old, all_storages.add_entity(()));
+    }
+
+    for (codec, mut components) in storages {
+        codec.remap(&mut *components, &map);
+        codec.insert(all_storages, components);
+    }
+
+    Ok(map)
+}
+
+fn write_slice(bytes: &mut Vec<u8>, slice: &[u8]) {
+    bytes.extend_from_slice(&(slice.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(slice);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], error::Snapshot> {
+        if self.0.len() < len {
+            return Err(error::Snapshot::InvalidSnapshot);
+        }
+
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        Ok(taken)
+    }
+    fn u32(&mut self) -> Result<u32, error::Snapshot> {
+        let mut value = [0; 4];
+        value.copy_from_slice(self.take(4)?);
+
+        Ok(u32::from_le_bytes(value))
+    }
+    fn u64(&mut self) -> Result<u64, error::Snapshot> {
+        let mut value = [0; 8];
+        value.copy_from_slice(self.take(8)?);
+
+        Ok(u64::from_le_bytes(value))
+    }
+    fn entity(&mut self) -> Result<EntityId, error::Snapshot> {
+        EntityId::from_inner(self.u64()?).ok_or(error::Snapshot::InvalidSnapshot)
+    }
+    fn slice(&mut self) -> Result<&'a [u8], error::Snapshot> {
+        let len = self.u32()? as usize;
+
+        self.take(len)
+    }
+}
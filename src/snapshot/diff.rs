@@ -0,0 +1,313 @@
+use super::{check_version, decode_storage, parse, RawStorage, SnapshotRegistry};
+use crate::all_storages::AllStorages;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::{ShipHashMap, ShipHashSet};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Components of a storage that differ between two snapshots.
+struct StorageDiff {
+    name: String,
+    layout_hash: u64,
+    /// Encoded components that are new or changed.
+    changed: Vec<(EntityId, Vec<u8>)>,
+    /// Entities that lost their component but are still alive.
+    removed: Vec<EntityId>,
+}
+
+/// Differences between two snapshots, created with [`WorldDiff::between`].
+///
+/// Applying it with [`World::apply_diff`] to a `World` in the state of the older snapshot brings it to the state of the newer one.\
+/// Components are compared in their serialized form, the `World` doesn't need to be loaded to compute the diff.
+///
+/// ### Example
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use shipyard::{Component, SnapshotRegistry, World, WorldDiff};
+///
+/// #[derive(Component, Serialize, Deserialize, Debug, PartialEq)]
+/// struct Health(u32);
+///
+/// let mut registry = SnapshotRegistry::new();
+/// registry.register::<Health>();
+///
+/// let mut world = World::new();
+/// let entity = world.add_entity((Health(10),));
+/// let older = world.snapshot(&registry);
+///
+/// let mut copy = World::new();
+/// copy.load_snapshot(&older, &registry).unwrap();
+///
+/// world.get::<&mut Health>(entity).unwrap().0 = 5;
+/// let added = world.add_entity((Health(20),));
+/// let newer = world.snapshot(&registry);
+///
+/// let diff = WorldDiff::between(&older, &newer).unwrap();
+/// assert_eq!(diff.added_entities(), &[added]);
+/// assert_eq!(diff.changed_components().count(), 2);
+///
+/// copy.apply_diff(&diff, &registry).unwrap();
+/// assert_eq!(copy.get::<&Health>(entity).as_deref(), Ok(&&Health(5)));
+/// assert_eq!(copy.get::<&Health>(added).as_deref(), Ok(&&Health(20)));
+/// ```
+///
+/// [`World::apply_diff`]: crate::World::apply_diff()
+pub struct WorldDiff {
+    version: u32,
+    added_entities: Vec<EntityId>,
+    removed_entities: Vec<EntityId>,
+    storages: Vec<StorageDiff>,
+}
+
+impl WorldDiff {
+    /// Computes the differences between two snapshots of the same `World`.
+    ///
+    /// Components whose layout hash changed between the two snapshots are all considered changed.\
+    /// Components absent from `newer`'s schema are considered removed.
+    ///
+    /// Snapshots saved with a compressor have to go through [`SnapshotRegistry::decompress`] first.
+    ///
+    /// ### Errors
+    ///
+    /// - The bytes aren't valid snapshots.
+    pub fn between(older: &[u8], newer: &[u8]) -> Result<WorldDiff, error::Snapshot> {
+        let older = parse(older)?;
+        let newer = parse(newer)?;
+
+        let older_alive: ShipHashSet<EntityId> = older.alive.iter().copied().collect();
+        let newer_alive: ShipHashSet<EntityId> = newer.alive.iter().copied().collect();
+
+        let added_entities = newer
+            .alive
+            .iter()
+            .copied()
+            .filter(|entity| !older_alive.contains(entity))
+            .collect();
+        let removed_entities = older
+            .alive
+            .iter()
+            .copied()
+            .filter(|entity| !newer_alive.contains(entity))
+            .collect();
+
+        let mut storages = Vec::new();
+
+        for new_storage in &newer.storages {
+            let old_storage = older
+                .storages
+                .iter()
+                .find(|storage| storage.name == new_storage.name);
+
+            let storage =
+                diff_storage(new_storage.name, old_storage, Some(new_storage), |entity| {
+                    newer_alive.contains(&entity)
+                });
+
+            storages.extend(storage);
+        }
+
+        for old_storage in &older.storages {
+            if newer
+                .storages
+                .iter()
+                .all(|storage| storage.name != old_storage.name)
+            {
+                let storage = diff_storage(old_storage.name, Some(old_storage), None, |entity| {
+                    newer_alive.contains(&entity)
+                });
+
+                storages.extend(storage);
+            }
+        }
+
+        Ok(WorldDiff {
+            version: newer.version,
+            added_entities,
+            removed_entities,
+            storages,
+        })
+    }
+    /// Returns `true` if both snapshots contain the same entities and components.
+    pub fn is_empty(&self) -> bool {
+        self.added_entities.is_empty()
+            && self.removed_entities.is_empty()
+            && self.storages.is_empty()
+    }
+    /// Returns the entities alive in the newer snapshot but not in the older one.
+    pub fn added_entities(&self) -> &[EntityId] {
+        &self.added_entities
+    }
+    /// Returns the entities alive in the older snapshot but not in the newer one.
+    pub fn removed_entities(&self) -> &[EntityId] {
+        &self.removed_entities
+    }
+    /// Returns the name of the components that were added or modified and the entity they belong to.
+    pub fn changed_components(&self) -> impl Iterator<Item = (&str, EntityId)> + '_ {
+        self.storages.iter().flat_map(|storage| {
+            storage
+                .changed
+                .iter()
+                .map(move |&(entity, _)| (&*storage.name, entity))
+        })
+    }
+    /// Returns the name of the components that were removed from entities still alive and the entity they belonged to.\
+    /// Components of removed entities are not listed.
+    pub fn removed_components(&self) -> impl Iterator<Item = (&str, EntityId)> + '_ {
+        self.storages.iter().flat_map(|storage| {
+            storage
+                .removed
+                .iter()
+                .map(move |&entity| (&*storage.name, entity))
+        })
+    }
+}
+
+impl core::fmt::Debug for WorldDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WorldDiff")
+            .field("added_entities", &self.added_entities)
+            .field("removed_entities", &self.removed_entities)
+            .field(
+                "changed_components",
+                &self.changed_components().collect::<Vec<_>>(),
+            )
+            .field(
+                "removed_components",
+                &self.removed_components().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Compares the components of a storage, returns `None` if they are identical.
+fn diff_storage(
+    name: &str,
+    old_storage: Option<&RawStorage<'_>>,
+    new_storage: Option<&RawStorage<'_>>,
+    is_alive: impl Fn(EntityId) -> bool,
+) -> Option<StorageDiff> {
+    let old_components: ShipHashMap<EntityId, &[u8]> = match old_storage {
+        Some(old_storage) => old_storage.components.iter().copied().collect(),
+        None => ShipHashMap::default(),
+    };
+    let new_components: &[(EntityId, &[u8])] = match new_storage {
+        Some(new_storage) => &new_storage.components,
+        None => &[],
+    };
+
+    // bytes saved with another layout can't be compared
+    let is_comparable = match (old_storage, new_storage) {
+        (Some(old_storage), Some(new_storage)) => {
+            old_storage.layout_hash == new_storage.layout_hash
+        }
+        _ => true,
+    };
+
+    let changed: Vec<(EntityId, Vec<u8>)> = new_components
+        .iter()
+        .filter(|(entity, bytes)| !is_comparable || old_components.get(entity) != Some(bytes))
+        .map(|&(entity, bytes)| (entity, bytes.to_vec()))
+        .collect();
+
+    let new_entities: ShipHashSet<EntityId> =
+        new_components.iter().map(|&(entity, _)| entity).collect();
+    let removed: Vec<EntityId> = old_storage
+        .map(|old_storage| {
+            old_storage
+                .components
+                .iter()
+                .map(|&(entity, _)| entity)
+                .filter(|&entity| is_alive(entity) && !new_entities.contains(&entity))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if changed.is_empty() && removed.is_empty() {
+        return None;
+    }
+
+    Some(StorageDiff {
+        name: String::from(name),
+        layout_hash: new_storage.map_or(0, |new_storage| new_storage.layout_hash),
+        changed,
+        removed,
+    })
+}
+
+/// Applies `diff` to `all_storages`.
+///
+/// All components are decoded before the `World` is modified.
+pub(crate) fn apply_diff(
+    all_storages: &mut AllStorages,
+    diff: &WorldDiff,
+    registry: &SnapshotRegistry,
+) -> Result<(), error::Snapshot> {
+    apply_diff_with(all_storages, diff, registry, |all_storages| {
+        for &entity in &diff.added_entities {
+            if !all_storages.spawn(entity) {
+                return Err(error::Snapshot::EntityIdMismatch(entity));
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Applies `diff` to `all_storages`, `add_entities` makes the added entities alive.
+///
+/// It runs after the removed entities are deleted and before components are inserted.
+pub(crate) fn apply_diff_with(
+    all_storages: &mut AllStorages,
+    diff: &WorldDiff,
+    registry: &SnapshotRegistry,
+    add_entities: impl FnOnce(&mut AllStorages) -> Result<(), error::Snapshot>,
+) -> Result<(), error::Snapshot> {
+    check_version(diff.version, registry)?;
+
+    let mut storages = Vec::with_capacity(diff.storages.len());
+    for storage in &diff.storages {
+        let components: Vec<(EntityId, &[u8])> = storage
+            .changed
+            .iter()
+            .map(|(entity, bytes)| (*entity, &**bytes))
+            .collect();
+
+        let (codec, components) = if components.is_empty() {
+            let codec = registry.codec_by_name(&storage.name).ok_or_else(|| {
+                error::Snapshot::UnregisteredComponent(Cow::Owned(storage.name.clone()))
+            })?;
+
+            (codec, None)
+        } else {
+            let (codec, components) = decode_storage(
+                registry,
+                diff.version,
+                &storage.name,
+                storage.layout_hash,
+                &components,
+            )?;
+
+            (codec, Some(components))
+        };
+
+        storages.push((codec, &storage.removed, components));
+    }
+
+    for &entity in &diff.removed_entities {
+        all_storages.delete_entity(entity);
+    }
+
+    add_entities(all_storages)?;
+
+    for (codec, removed, components) in storages {
+        codec.delete(all_storages, removed);
+
+        if let Some(components) = components {
+            codec.insert(all_storages, components);
+        }
+    }
+
+    Ok(())
+}
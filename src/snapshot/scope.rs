@@ -0,0 +1,76 @@
+use super::diff::apply_diff_with;
+use super::{SnapshotRegistry, WorldDiff};
+use crate::all_storages::{next_all_storages_id, AllStorages};
+use crate::entities::{Entities, EntitiesState};
+use crate::error;
+use crate::storage::StorageId;
+use crate::ShipHashSet;
+use alloc::vec::Vec;
+
+/// State of an `AllStorages` at the start of a [`World::scope`](crate::World::scope).
+pub(crate) struct Checkpoint {
+    snapshot: Vec<u8>,
+    entities: EntitiesState,
+    storages: ShipHashSet<StorageId>,
+}
+
+impl Checkpoint {
+    pub(crate) fn new(all_storages: &mut AllStorages, registry: &SnapshotRegistry) -> Checkpoint {
+        Checkpoint {
+            snapshot: all_storages.snapshot(registry),
+            entities: all_storages
+                .exclusive_storage_mut::<Entities>()
+                .unwrap()
+                .save_state(),
+            storages: all_storages
+                .storages
+                .iter_mut()
+                .map(|(storage_id, _)| *storage_id)
+                .collect(),
+        }
+    }
+    /// Brings `all_storages` back to the checkpoint.
+    ///
+    /// Entities created since are deleted with all their components and deleted ones come back with the same id.
+    /// Components in `registry` get their saved value back, storages created since are removed.
+    pub(crate) fn rollback(
+        self,
+        all_storages: &mut AllStorages,
+        registry: &SnapshotRegistry,
+    ) -> Result<(), error::Snapshot> {
+        let current = all_storages.snapshot(registry);
+        let diff = WorldDiff::between(&current, &self.snapshot)?;
+        let entities = self.entities;
+
+        apply_diff_with(all_storages, &diff, registry, move |all_storages| {
+            // restores generations and the recycling list, spawning the ids wouldn't
+            all_storages
+                .exclusive_storage_mut::<Entities>()
+                .unwrap()
+                .restore_state(entities);
+
+            Ok(())
+        })?;
+
+        let created: Vec<StorageId> = all_storages
+            .storages
+            .iter_mut()
+            .map(|(storage_id, _)| *storage_id)
+            .filter(|storage_id| !self.storages.contains(storage_id))
+            .collect();
+
+        if !created.is_empty() {
+            for storage_id in created {
+                all_storages
+                    .storages
+                    .shard_mut(&storage_id)
+                    .remove(&storage_id);
+            }
+
+            // `StorageHandle`s to the removed storages fall back to a regular lookup
+            all_storages.id = next_all_storages_id();
+        }
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use crate::entity_id::EntityId;
 use crate::sparse_set::SparseSet;
 use crate::storage::StorageId;
 use crate::tracking::TrackingTimestamp;
+use crate::undo::record_insertion;
 #[cfg(doc)]
 use crate::world::World;
 
@@ -32,6 +33,12 @@ impl<T: Send + Sync + Component> TupleAddComponent for T {
         entity: EntityId,
         current: TrackingTimestamp,
     ) {
+        if let Some(recording) = &mut all_storages.recording {
+            recording.record_component(entity, &self);
+        }
+
+        record_insertion::<T>(all_storages, entity);
+
         all_storages
             .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<T>>(), SparseSet::new)
             .insert(entity, self, current)
@@ -49,6 +56,12 @@ impl<T: Send + Sync + Component> TupleAddComponent for Option<T> {
         current: TrackingTimestamp,
     ) {
         if let Some(component) = self {
+            if let Some(recording) = &mut all_storages.recording {
+                recording.record_component(entity, &component);
+            }
+
+            record_insertion::<T>(all_storages, entity);
+
             all_storages
                 .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<T>>(), SparseSet::new)
                 .insert(entity, component, current)
@@ -0,0 +1,93 @@
+use alloc::sync::Arc;
+use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+use core::fmt;
+use core::ptr::NonNull;
+
+/// Vector holding the components of a [`SparseSet`](super::SparseSet).
+pub(crate) type ComponentVec<T> = allocator_api2::vec::Vec<T, StorageAllocator>;
+
+/// Allocator used for the components of a [`SparseSet`].
+///
+/// Any [`allocator_api2`](https://github.com/zakarumych/allocator-api2) `Allocator` is accepted,
+/// on nightly this is the same trait as `core::alloc::Allocator`.\
+/// Only the components use it, entity ids and tracking data are always in the global allocator.
+///
+/// Clones share the same allocator.
+///
+/// [`SparseSet`]: crate::SparseSet
+#[derive(Clone, Default)]
+pub struct StorageAllocator(Option<Arc<dyn Allocator + Send + Sync>>);
+
+impl StorageAllocator {
+    /// Returns the global allocator, used by default.
+    pub fn global() -> StorageAllocator {
+        StorageAllocator(None)
+    }
+    /// Wraps a custom allocator.
+    pub fn new<A: Allocator + Send + Sync + 'static>(allocator: A) -> StorageAllocator {
+        StorageAllocator(Some(Arc::new(allocator)))
+    }
+    /// Returns `true` if this is the global allocator.
+    pub fn is_global(&self) -> bool {
+        self.0.is_none()
+    }
+    fn inner(&self) -> &dyn Allocator {
+        match &self.0 {
+            Some(allocator) => &**allocator,
+            None => &Global,
+        }
+    }
+}
+
+// SAFETY: all calls are forwarded to the same allocator, clones share it
+unsafe impl Allocator for StorageAllocator {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner().allocate(layout)
+    }
+    #[inline]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner().allocate_zeroed(layout)
+    }
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.inner().deallocate(ptr, layout)
+    }
+    #[inline]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner().grow(ptr, old_layout, new_layout)
+    }
+    #[inline]
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner().grow_zeroed(ptr, old_layout, new_layout)
+    }
+    #[inline]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner().shrink(ptr, old_layout, new_layout)
+    }
+}
+
+impl fmt::Debug for StorageAllocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_global() {
+            f.write_str("StorageAllocator(Global)")
+        } else {
+            f.write_str("StorageAllocator(Custom)")
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+
+const BITS: usize = u64::BITS as usize;
+
+/// Two layer bitset of the entity indices present in a [`SparseSet`].
+///
+/// Each bit of `words` flags an entity index, each bit of `summary` flags a non-empty word.\
+/// Intersecting multiple bitsets skips the blocks of 64 entities missing from any of them
+/// and the runs of 4096 entities with no match at all.
+///
+/// [`SparseSet`]: crate::sparse_set::SparseSet
+#[derive(Clone, Debug, Default)]
+pub struct EntityBitSet {
+    words: Vec<u64>,
+    summary: Vec<u64>,
+    len: usize,
+}
+
+impl EntityBitSet {
+    /// Returns an empty bitset.
+    #[inline]
+    pub fn new() -> EntityBitSet {
+        EntityBitSet::default()
+    }
+    /// Returns the number of indices in the bitset.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if the bitset doesn't contain any index.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns `true` if `index` is in the bitset.
+    #[inline]
+    pub fn contains(&self, index: usize) -> bool {
+        self.words
+            .get(index / BITS)
+            .is_some_and(|word| word & (1 << (index % BITS)) != 0)
+    }
+    /// Adds `index` to the bitset.
+    pub(crate) fn insert(&mut self, index: usize) {
+        let word_index = index / BITS;
+        let summary_index = word_index / BITS;
+
+        if word_index >= self.words.len() {
+            self.words.resize(word_index + 1, 0);
+        }
+        if summary_index >= self.summary.len() {
+            self.summary.resize(summary_index + 1, 0);
+        }
+
+        let word = &mut self.words[word_index];
+        let bit = 1 << (index % BITS);
+
+        if *word & bit == 0 {
+            *word |= bit;
+            self.summary[summary_index] |= 1 << (word_index % BITS);
+            self.len += 1;
+        }
+    }
+    /// Removes `index` from the bitset.
+    pub(crate) fn remove(&mut self, index: usize) {
+        let word_index = index / BITS;
+
+        if let Some(word) = self.words.get_mut(word_index) {
+            let bit = 1 << (index % BITS);
+
+            if *word & bit != 0 {
+                *word &= !bit;
+                self.len -= 1;
+
+                if *word == 0 {
+                    self.summary[word_index / BITS] &= !(1 << (word_index % BITS));
+                }
+            }
+        }
+    }
+    /// Removes all indices, keeps the allocated memory.
+    pub(crate) fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+        self.summary.iter_mut().for_each(|word| *word = 0);
+        self.len = 0;
+    }
+    /// Returns the number of summary words, the cost of an intersection involving this bitset.
+    #[inline]
+    pub(crate) fn summary_len(&self) -> usize {
+        self.summary.len()
+    }
+    /// Calls `f` with all indices present in every bitset of `bitsets`, in ascending order.
+    pub(crate) fn intersection(bitsets: &[&EntityBitSet], mut f: impl FnMut(usize)) {
+        let summary_len = bitsets
+            .iter()
+            .map(|bitset| bitset.summary.len())
+            .min()
+            .unwrap_or(0);
+
+        for summary_index in 0..summary_len {
+            let mut summary = bitsets.iter().fold(u64::MAX, |summary, bitset| {
+                summary & bitset.summary[summary_index]
+            });
+
+            while summary != 0 {
+                let word_index = summary_index * BITS + summary.trailing_zeros() as usize;
+                summary &= summary - 1;
+
+                let mut word = bitsets
+                    .iter()
+                    .fold(u64::MAX, |word, bitset| word & bitset.words[word_index]);
+
+                while word != 0 {
+                    f(word_index * BITS + word.trailing_zeros() as usize);
+                    word &= word - 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersection() {
+        let mut a = EntityBitSet::new();
+        let mut b = EntityBitSet::new();
+
+        for index in [0, 3, 64, 5000, 5001] {
+            a.insert(index);
+        }
+        for index in [3, 63, 5001, 10_000] {
+            b.insert(index);
+        }
+
+        a.remove(0);
+        a.remove(64);
+        assert_eq!(a.len(), 3);
+
+        let mut indices = Vec::new();
+        EntityBitSet::intersection(&[&a, &b], |index| indices.push(index));
+        assert_eq!(indices, [3, 5001]);
+
+        b.clear();
+        assert!(b.is_empty());
+        assert!(!b.contains(3));
+
+        indices.clear();
+        EntityBitSet::intersection(&[&a, &b], |index| indices.push(index));
+        assert!(indices.is_empty());
+    }
+}
@@ -41,16 +41,12 @@ impl BulkInsert for () {
     where
         Self: Sized,
     {
-        let iter = iter.into_iter();
-        let len = iter.size_hint().0;
+        let count = iter.into_iter().count();
 
         let entities = all_storages.exclusive_storage_mut::<Entities>().unwrap();
         let entities_len = entities.data.len();
 
-        entities.bulk_generate(len);
-        for _ in iter.skip(len) {
-            entities.generate();
-        }
+        entities.bulk_generate(count);
         BulkEntityIter {
             iter: entities.data[entities_len..].iter().copied(),
             slice: &entities.data[entities_len..],
@@ -80,7 +76,7 @@ impl<T: Send + Sync + Component> BulkInsert for T {
         let new_entities = entities.bulk_generate(new_entities_count);
 
         // add new EntityId to the storage for the components we added above
-        sparse_set.dense.extend_from_slice(new_entities);
+        sparse_set.extend_dense(new_entities);
 
         // add tracking info if needed
         if sparse_set.is_tracking_insertion() {
@@ -97,10 +93,10 @@ impl<T: Send + Sync + Component> BulkInsert for T {
         let SparseSet { sparse, dense, .. } = &mut *sparse_set;
 
         // update sparse to reflect the new state of dense and data
-        sparse.bulk_allocate(dense[old_len], dense[dense.len() - 1]);
+        sparse.bulk_allocate::<T>(dense[old_len], dense[dense.len() - 1]);
         for (i, &entity) in dense[old_len..].iter().enumerate() {
             unsafe {
-                *sparse.get_mut_unchecked(entity) = EntityId::new((old_len + i) as u64);
+                *sparse.get_mut_unchecked::<T>(entity) = EntityId::new((old_len + i) as u64);
             }
         }
 
@@ -131,6 +127,7 @@ macro_rules! impl_bulk_insert {
             fn bulk_insert<Source: IntoIterator<Item = Self>>(all_storages: &mut AllStorages, iter: Source) -> BulkEntityIter<'_> {
                 let iter = iter.into_iter();
                 let size_hint = iter.size_hint().0;
+                let current = all_storages.get_current();
                 let mut entities = all_storages.entities_mut().unwrap();
                 let mut $sparse_set1 = all_storages.custom_storage_or_insert_mut(SparseSet::<$type1>::new).unwrap();
                 $(
@@ -153,43 +150,43 @@ macro_rules! impl_bulk_insert {
                 let new_entities_count = $sparse_set1.data.len() - $sparse_set1.dense.len();
                 let new_entities = entities.bulk_generate(new_entities_count);
 
-                $sparse_set1.dense.extend_from_slice(new_entities);
+                $sparse_set1.extend_dense(new_entities);
                 $(
-                    $sparse_set.dense.extend_from_slice(new_entities);
+                    $sparse_set.extend_dense(new_entities);
                 )*
 
                 if $sparse_set1.is_tracking_insertion() {
-                    $sparse_set1.insertion_data.extend(new_entities.iter().map(|_| TrackingTimestamp::new(0)));
+                    $sparse_set1.insertion_data.extend(new_entities.iter().map(|_| current));
                 }
                 if $sparse_set1.is_tracking_modification() {
-                    $sparse_set1.modification_data.extend(new_entities.iter().map(|_| TrackingTimestamp::new(0)));
+                    $sparse_set1.modification_data.extend(new_entities.iter().map(|_| TrackingTimestamp::origin()));
                 }
                 $(
                     if $sparse_set.is_tracking_insertion() {
-                        $sparse_set.insertion_data.extend(new_entities.iter().map(|_| TrackingTimestamp::new(0)));
+                        $sparse_set.insertion_data.extend(new_entities.iter().map(|_| current));
                     }
                     if $sparse_set.is_tracking_modification() {
-                        $sparse_set.modification_data.extend(new_entities.iter().map(|_| TrackingTimestamp::new(0)));
+                        $sparse_set.modification_data.extend(new_entities.iter().map(|_| TrackingTimestamp::origin()));
                     }
                 )*
 
                 let old_len = $sparse_set1.dense.len() - new_entities_count;
                 let SparseSet { sparse, dense, .. } = &mut *$sparse_set1;
 
-                sparse.bulk_allocate(dense[old_len], dense[dense.len() - 1]);
+                sparse.bulk_allocate::<$type1>(dense[old_len], dense[dense.len() - 1]);
                 for (i, &entity) in dense[old_len..].iter().enumerate() {
                     unsafe {
-                        *sparse.get_mut_unchecked(entity) = EntityId::new((old_len + i) as u64);
+                        *sparse.get_mut_unchecked::<$type1>(entity) = EntityId::new((old_len + i) as u64);
                     }
                 }
                 $(
                     let old_len = $sparse_set.dense.len() - new_entities_count;
                     let SparseSet { sparse, dense, .. } = &mut *$sparse_set;
 
-                    sparse.bulk_allocate(dense[old_len], dense[dense.len() - 1]);
+                    sparse.bulk_allocate::<$type>(dense[old_len], dense[dense.len() - 1]);
                     for (i, &entity) in dense[old_len..].iter().enumerate() {
                         unsafe {
-                            *sparse.get_mut_unchecked(entity) = EntityId::new((old_len + i) as u64);
+                            *sparse.get_mut_unchecked::<$type>(entity) = EntityId::new((old_len + i) as u64);
                         }
                     }
                 )*
@@ -0,0 +1,51 @@
+use crate::all_storages::{AllStorages, CustomStorageAccess};
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::error;
+use crate::sparse_set::SparseSet;
+#[cfg(doc)]
+use crate::world::World;
+
+/// Trait used as bound for [`World::has`] and [`AllStorages::has`].
+pub trait TupleContains {
+    /// See [`World::has`] and [`AllStorages::has`].
+    fn contains(all_storages: &AllStorages, entity: EntityId) -> bool;
+}
+
+impl<T: Send + Sync + Component> TupleContains for T {
+    #[inline]
+    #[track_caller]
+    fn contains(all_storages: &AllStorages, entity: EntityId) -> bool {
+        match all_storages.custom_storage::<SparseSet<T>>() {
+            Ok(sparse_set) => sparse_set.contains(entity),
+            Err(error::GetStorage::MissingStorage { .. }) => false,
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+}
+
+macro_rules! impl_contains_component {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: Send + Sync + Component,)+> TupleContains for ($($type,)+) {
+            #[inline]
+            #[track_caller]
+            fn contains(all_storages: &AllStorages, entity: EntityId) -> bool {
+                $(
+                    $type::contains(all_storages, entity)
+                )&&+
+            }
+        }
+    };
+}
+
+macro_rules! contains_component {
+    ($(($type: ident, $index: tt))*;($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_contains_component![$(($type, $index))*];
+        contains_component![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))*;) => {
+        impl_contains_component![$(($type, $index))*];
+    }
+}
+
+contains_component![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
@@ -3,6 +3,7 @@ use crate::component::Component;
 use crate::entity_id::EntityId;
 use crate::sparse_set::SparseSet;
 use crate::storage::StorageId;
+use crate::undo::record_removal;
 #[cfg(doc)]
 use crate::world::World;
 
@@ -17,6 +18,8 @@ impl<T: Send + Sync + Component> TupleDelete for T {
     fn delete(all_storages: &mut AllStorages, entity: EntityId) -> bool {
         let current = all_storages.get_current();
 
+        record_removal::<T>(all_storages, entity);
+
         all_storages
             .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<T>>(), SparseSet::<T>::new)
             .dyn_delete(entity, current)
@@ -30,9 +33,13 @@ macro_rules! impl_delete_component {
                 let current = all_storages.get_current();
 
                 $(
-                    all_storages
-                        .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<$type>>(), SparseSet::<$type>::new)
-                        .dyn_delete(entity, current)
+                    ({
+                        record_removal::<$type>(all_storages, entity);
+
+                        all_storages
+                            .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<$type>>(), SparseSet::<$type>::new)
+                            .dyn_delete(entity, current)
+                    })
                 )||+
             }
         }
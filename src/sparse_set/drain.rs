@@ -1,6 +1,7 @@
 use crate::entity_id::EntityId;
 use crate::iter::WithId;
-use alloc::vec::Drain;
+use crate::sparse_set::StorageAllocator;
+use allocator_api2::vec::Drain;
 
 /// A draining iterator for [`SparseSet<T>`].
 ///
@@ -8,7 +9,7 @@ use alloc::vec::Drain;
 pub struct SparseSetDrain<'a, T> {
     pub(crate) dense_ptr: *const EntityId,
     pub(crate) dense_len: usize,
-    pub(crate) data: Drain<'a, T>,
+    pub(crate) data: Drain<'a, T, StorageAllocator>,
 }
 
 impl<T> SparseSetDrain<'_, T> {
@@ -25,6 +25,7 @@ impl<T: Component> SparseSet<T> {
             + (self.modification_data.capacity() * size_of::<TrackingTimestamp>())
             + (self.deletion_data.capacity() * size_of::<(EntityId, TrackingTimestamp, T)>())
             + (self.removal_data.capacity() * size_of::<(EntityId, TrackingTimestamp)>())
+            + (self.pool.capacity() * size_of::<T>())
             + size_of::<Self>()
     }
 
@@ -36,6 +37,7 @@ impl<T: Component> SparseSet<T> {
             + (self.modification_data.len() * size_of::<TrackingTimestamp>())
             + (self.deletion_data.len() * size_of::<(EntityId, TrackingTimestamp, T)>())
             + (self.removal_data.len() * size_of::<(EntityId, TrackingTimestamp)>())
+            + (self.pool.len() * size_of::<T>())
             + size_of::<Self>()
     }
 }
@@ -1,5 +1,8 @@
 mod add_component;
+mod allocator;
+mod bitset;
 mod bulk_add_entity;
+mod contains;
 mod delete;
 mod drain;
 mod memory_usage;
@@ -8,19 +11,24 @@ mod sparse_array;
 mod window;
 
 pub use add_component::TupleAddComponent;
+pub use allocator::StorageAllocator;
+pub use bitset::EntityBitSet;
 pub use bulk_add_entity::BulkAddEntity;
+pub use contains::TupleContains;
 pub use delete::TupleDelete;
 pub use drain::SparseSetDrain;
 pub use memory_usage::{SparseSetMemory, SparseSetMemoryUsage};
 pub use remove::TupleRemove;
 pub use sparse_array::SparseArray;
 
+pub(crate) use allocator::ComponentVec;
 pub(crate) use window::{FullRawWindow, FullRawWindowMut};
 
 use crate::all_storages::AllStorages;
 #[cfg(feature = "thread_local")]
 use crate::borrow::{NonSend, NonSendSync, NonSync};
-use crate::component::Component;
+use crate::component::{component_name, Component};
+use crate::entities::EntityRemap;
 use crate::entity_id::EntityId;
 use crate::error;
 use crate::memory_usage::StorageMemoryUsage;
@@ -50,7 +58,7 @@ pub(crate) const BUCKET_SIZE: usize = 256 / size_of::<EntityId>();
 pub struct SparseSet<T: Component> {
     pub(crate) sparse: SparseArray<EntityId, BUCKET_SIZE>,
     pub(crate) dense: Vec<EntityId>,
-    pub(crate) data: Vec<T>,
+    pub(crate) data: ComponentVec<T>,
     pub(crate) last_insert: TrackingTimestamp,
     pub(crate) last_modified: TrackingTimestamp,
     pub(crate) insertion_data: Vec<TrackingTimestamp>,
@@ -61,6 +69,13 @@ pub struct SparseSet<T: Component> {
     pub(crate) is_tracking_modification: bool,
     pub(crate) is_tracking_deletion: bool,
     pub(crate) is_tracking_removal: bool,
+    /// Deleted components kept to reuse their allocations
+    pub(crate) pool: Vec<T>,
+    pub(crate) pool_capacity: usize,
+    /// Entity indices present in the storage, used to speed up joins
+    pub(crate) bitset: Option<EntityBitSet>,
+    /// Number of components added or removed since the storage's creation
+    pub(crate) structural_changes: u64,
     #[allow(clippy::type_complexity)]
     on_insertion: Option<Box<dyn FnMut(EntityId, &T) + Send + Sync>>,
     #[allow(clippy::type_complexity)]
@@ -78,10 +93,23 @@ impl<T: fmt::Debug + Component> fmt::Debug for SparseSet<T> {
 impl<T: Component> SparseSet<T> {
     #[inline]
     pub(crate) fn new() -> Self {
+        SparseSet::new_in(StorageAllocator::global())
+    }
+    /// Returns a new [`SparseSet`] allocating its components with `allocator`.
+    ///
+    /// It can then be added to the `World` with [`World::add_storage_in`] or used as a custom storage.
+    ///
+    /// [`World::add_storage_in`]: crate::World::add_storage_in()
+    #[inline]
+    pub fn new_in(allocator: StorageAllocator) -> Self {
         SparseSet {
-            sparse: SparseArray::new(),
+            sparse: if T::INLINE {
+                SparseArray::new_inline()
+            } else {
+                SparseArray::new()
+            },
             dense: Vec::new(),
-            data: Vec::new(),
+            data: ComponentVec::new_in(allocator),
             last_insert: TrackingTimestamp::new(0),
             last_modified: TrackingTimestamp::new(0),
             insertion_data: Vec::new(),
@@ -92,20 +120,63 @@ impl<T: Component> SparseSet<T> {
             is_tracking_modification: T::Tracking::track_modification(),
             is_tracking_deletion: T::Tracking::track_deletion(),
             is_tracking_removal: T::Tracking::track_removal(),
+            pool: Vec::new(),
+            pool_capacity: 0,
+            bitset: None,
+            structural_changes: 0,
             on_insertion: None,
             on_removal: None,
         }
     }
+    /// Returns a new [`SparseSet`] keeping up to `capacity` deleted components in a pool.
+    ///
+    /// Pooled components can be taken back with [`SparseSet::take_pooled`] to reuse their allocations.
+    ///
+    /// It can then be added to the `World` with [`World::add_pooled_storage`] or used as a custom storage.
+    ///
+    /// [`World::add_pooled_storage`]: crate::World::add_pooled_storage()
+    #[inline]
+    pub fn new_pooled(capacity: usize) -> Self {
+        SparseSet {
+            pool: Vec::with_capacity(capacity),
+            pool_capacity: capacity,
+            ..SparseSet::new()
+        }
+    }
+    /// Returns a new [`SparseSet`] keeping a bitset of the entities it contains.
+    ///
+    /// It can then be added to the `World` with [`World::add_bitset_storage`] or used as a custom storage.
+    ///
+    /// [`World::add_bitset_storage`]: crate::World::add_bitset_storage()
+    #[inline]
+    pub fn new_with_bitset() -> Self {
+        SparseSet {
+            bitset: Some(EntityBitSet::new()),
+            ..SparseSet::new()
+        }
+    }
     /// Returns a new [`SparseSet`] to be used in custom storage.
     #[inline]
     pub fn new_custom_storage() -> Self {
         SparseSet::new()
     }
+    /// Returns `true` if the entities are still stored inline.
+    ///
+    /// Always `false` for components that aren't [`Component::INLINE`].
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.sparse.is_inline()
+    }
     /// Returns a slice of all the components in this storage.
     #[inline]
     pub fn as_slice(&self) -> &[T] {
         &self.data
     }
+    /// Returns the allocator used for the components.
+    #[inline]
+    pub fn allocator(&self) -> &StorageAllocator {
+        self.data.allocator()
+    }
 }
 
 impl<T: Component> SparseSet<T> {
@@ -126,12 +197,118 @@ impl<T: Component> SparseSet<T> {
     }
 }
 
+impl<T: Component> SparseSet<T> {
+    /// Returns the number of components for which `pred` returns `true`.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity(Health(0));
+    /// world.add_entity(Health(10));
+    /// world.add_entity(Health(0));
+    ///
+    /// let healths = world.borrow::<View<Health>>().unwrap();
+    /// assert_eq!(healths.count_where(|health| health.0 == 0), 2);
+    /// ```
+    pub fn count_where<P: FnMut(&T) -> bool>(&self, mut pred: P) -> usize {
+        self.data.iter().filter(|component| pred(component)).count()
+    }
+    /// Returns the component with the minimum value of `key` and the entity owning it.\
+    /// If several components are equally minimum, the first one in the storage is returned.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Distance(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity(Distance(12));
+    /// let closest = world.add_entity(Distance(3));
+    /// world.add_entity(Distance(7));
+    ///
+    /// let distances = world.borrow::<View<Distance>>().unwrap();
+    /// let (entity, distance) = distances.min_by_key(|distance| distance.0).unwrap();
+    ///
+    /// assert_eq!(entity, closest);
+    /// assert_eq!(distance.0, 3);
+    /// ```
+    pub fn min_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut key: F) -> Option<(EntityId, &T)> {
+        self.dense
+            .iter()
+            .copied()
+            .zip(&self.data)
+            .min_by_key(|(_, component)| key(component))
+    }
+    /// Returns the component with the maximum value of `key` and the entity owning it.\
+    /// If several components are equally maximum, the last one in the storage is returned.
+    pub fn max_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut key: F) -> Option<(EntityId, &T)> {
+        self.dense
+            .iter()
+            .copied()
+            .zip(&self.data)
+            .max_by_key(|(_, component)| key(component))
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: Component + Sync> SparseSet<T> {
+    /// Parallel version of [`SparseSet::count_where`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_count_where<P: Fn(&T) -> bool + Send + Sync>(&self, pred: P) -> usize {
+        use rayon::prelude::*;
+
+        self.data
+            .par_iter()
+            .filter(|component| pred(component))
+            .count()
+    }
+    /// Parallel version of [`SparseSet::min_by_key`].\
+    /// If several components are equally minimum, the first one in the storage is returned.
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_min_by_key<K: Ord + Send, F: Fn(&T) -> K + Send + Sync>(
+        &self,
+        key: F,
+    ) -> Option<(EntityId, &T)> {
+        use rayon::prelude::*;
+
+        self.dense
+            .par_iter()
+            .copied()
+            .zip(self.data.par_iter())
+            .min_by_key(|(_, component)| key(component))
+    }
+    /// Parallel version of [`SparseSet::max_by_key`].\
+    /// If several components are equally maximum, the last one in the storage is returned.
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_max_by_key<K: Ord + Send, F: Fn(&T) -> K + Send + Sync>(
+        &self,
+        key: F,
+    ) -> Option<(EntityId, &T)> {
+        use rayon::prelude::*;
+
+        self.dense
+            .par_iter()
+            .copied()
+            .zip(self.data.par_iter())
+            .max_by_key(|(_, component)| key(component))
+    }
+}
+
 impl<T: Component> SparseSet<T> {
     /// Returns the index of `entity`'s component in the `dense` and `data` vectors.  
     /// This index is only valid for this storage and until a modification happens.
     #[inline]
     pub fn index_of(&self, entity: EntityId) -> Option<usize> {
-        self.sparse.get(entity).and_then(|sparse_entity| {
+        self.sparse.get::<T>(entity).and_then(|sparse_entity| {
             if entity.gen() == sparse_entity.gen() {
                 Some(sparse_entity.uindex())
             } else {
@@ -148,13 +325,62 @@ impl<T: Component> SparseSet<T> {
     /// The index is only valid until a modification occurs in the storage.
     #[inline]
     pub unsafe fn index_of_unchecked(&self, entity: EntityId) -> usize {
-        self.sparse.get_unchecked(entity).uindex()
+        self.sparse.get_unchecked::<T>(entity).uindex()
     }
     /// Returns the `EntityId` at a given `index`.
     #[inline]
     pub fn id_at(&self, index: usize) -> Option<EntityId> {
         self.dense.get(index).copied()
     }
+    /// Returns the component at `index` in the `data` vector, without bounds checking.
+    /// Indices can be retrieved with [`index_of`].
+    ///
+    /// # Safety
+    ///
+    /// `index` has to be smaller than the storage's length.
+    /// Doesn't check the component still belongs to the same entity, indices are only valid until a modification occurs in the storage.
+    ///
+    /// [`index_of`]: SparseSet::index_of()
+    #[inline]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        self.data.get_unchecked(index)
+    }
+    /// Returns a pointer to the `dense` vector, a pointer to the `data` vector and their length.
+    /// The component at `data[i]` belongs to the entity at `dense[i]`.
+    ///
+    /// The pointers are valid as long as the storage is borrowed and unmodified.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Speed(f32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity = world.add_entity(Speed(1.0));
+    /// world.add_entity(Speed(2.0));
+    ///
+    /// let speeds = world.borrow::<View<Speed>>().unwrap();
+    /// let (ids, data, len) = speeds.as_raw_parts();
+    ///
+    /// // SAFETY: both pointers come from the same storage and `len` is their length
+    /// let (ids, data) = unsafe {
+    ///     (
+    ///         core::slice::from_raw_parts(ids, len),
+    ///         core::slice::from_raw_parts(data, len),
+    ///     )
+    /// };
+    ///
+    /// let total: f32 = data.iter().map(|speed| speed.0).sum();
+    /// assert_eq!(total, 3.0);
+    /// assert_eq!(ids[speeds.index_of(entity).unwrap()], entity);
+    /// ```
+    #[inline]
+    pub fn as_raw_parts(&self) -> (*const EntityId, *const T, usize) {
+        (self.dense.as_ptr(), self.data.as_ptr(), self.dense.len())
+    }
 
     /// Sets the on insertion callback.
     pub fn on_insertion(&mut self, f: impl FnMut(EntityId, &T) + Send + Sync + 'static) {
@@ -232,10 +458,10 @@ impl<T: Component> SparseSet<T> {
         value: T,
         current: TrackingTimestamp,
     ) -> InsertionResult<T> {
-        self.sparse.allocate_at(entity);
+        self.sparse.allocate_at::<T>(entity);
 
         // at this point there can't be nothing at the sparse index
-        let sparse_entity = unsafe { self.sparse.get_mut_unchecked(entity) };
+        let sparse_entity = unsafe { self.sparse.get_mut_unchecked::<T>(entity) };
 
         let old_component;
 
@@ -254,8 +480,13 @@ impl<T: Component> SparseSet<T> {
                 self.modification_data.push(TrackingTimestamp::origin());
             }
 
+            if let Some(bitset) = &mut self.bitset {
+                bitset.insert(entity.uindex());
+            }
+
             self.dense.push(entity);
             self.data.push(value);
+            self.structural_changes = self.structural_changes.wrapping_add(1);
 
             old_component = InsertionResult::Inserted;
         } else if entity.gen() == sparse_entity.gen() {
@@ -312,6 +543,156 @@ impl<T: Component> SparseSet<T> {
 
         old_component
     }
+    /// Inserts all components from `iter`.\
+    /// When the ids are strictly ascending and none of the entities already has a component in this storage,
+    /// the components are appended in bulk. Otherwise they are inserted one by one.
+    pub(crate) fn private_extend_from_iter<I: IntoIterator<Item = (EntityId, T)>>(
+        &mut self,
+        iter: I,
+        current: TrackingTimestamp,
+    ) {
+        let batch: Vec<(EntityId, T)> = iter.into_iter().collect();
+
+        let (first, last) = match (batch.first(), batch.last()) {
+            (Some(&(first, _)), Some(&(last, _))) => (first, last),
+            _ => return,
+        };
+
+        let is_ascending = batch
+            .windows(2)
+            .all(|window| window[0].0.index() < window[1].0.index());
+        let is_new = |(entity, _): &(EntityId, T)| match self.sparse.get::<T>(*entity) {
+            Some(sparse_entity) => sparse_entity.is_dead(),
+            None => true,
+        };
+
+        if !is_ascending || !batch.iter().all(is_new) {
+            for (entity, component) in batch {
+                let _ = self.insert(entity, component, current);
+            }
+
+            return;
+        }
+
+        if let Some(on_insertion) = &mut self.on_insertion {
+            for (entity, component) in &batch {
+                on_insertion(*entity, component);
+            }
+        }
+
+        let old_len = self.dense.len();
+        let len = batch.len();
+
+        self.sparse.bulk_allocate::<T>(first, last);
+        for (i, &(entity, _)) in batch.iter().enumerate() {
+            unsafe {
+                *self.sparse.get_mut_unchecked::<T>(entity) =
+                    EntityId::new_from_index_and_gen((old_len + i) as u64, entity.gen());
+            }
+        }
+
+        if self.is_tracking_insertion {
+            self.insertion_data.extend(batch.iter().map(|_| current));
+        }
+        if self.is_tracking_modification {
+            self.modification_data
+                .extend(batch.iter().map(|_| TrackingTimestamp::origin()));
+        }
+
+        self.dense.reserve(len);
+        self.data.reserve(len);
+        for (entity, component) in batch {
+            self.dense.push(entity);
+            self.data.push(component);
+        }
+        self.structural_changes = self.structural_changes.wrapping_add(len as u64);
+
+        if let Some(bitset) = &mut self.bitset {
+            for entity in &self.dense[old_len..] {
+                bitset.insert(entity.uindex());
+            }
+        }
+    }
+}
+
+impl<T: Component> SparseSet<T> {
+    /// Makes this storage keep a bitset of the entities it contains.
+    ///
+    /// When at least two storages iterated together have a bitset, the iteration intersects them
+    /// instead of going through the smallest storage and looking up each entity in the others.\
+    /// It pays off for joins of rare components, when few entities have all of them.
+    pub fn enable_bitset(&mut self) -> &mut SparseSet<T> {
+        if self.bitset.is_none() {
+            let mut bitset = EntityBitSet::new();
+
+            for entity in &self.dense {
+                bitset.insert(entity.uindex());
+            }
+
+            self.bitset = Some(bitset);
+        }
+
+        self
+    }
+    /// Drops the bitset of this storage.
+    pub fn disable_bitset(&mut self) {
+        self.bitset = None;
+    }
+    /// Returns the bitset of the entities in this storage, if it keeps one.
+    #[inline]
+    pub fn bitset(&self) -> Option<&EntityBitSet> {
+        self.bitset.as_ref()
+    }
+    /// Adds `entities` to `dense` and to the bitset if this storage keeps one.\
+    /// Their components have to be added to `data` separately.
+    #[inline]
+    pub(crate) fn extend_dense(&mut self, entities: &[EntityId]) {
+        self.dense.extend_from_slice(entities);
+        self.structural_changes = self.structural_changes.wrapping_add(entities.len() as u64);
+
+        if let Some(bitset) = &mut self.bitset {
+            for entity in entities {
+                bitset.insert(entity.uindex());
+            }
+        }
+    }
+}
+
+impl<T: Component> SparseSet<T> {
+    /// Returns how many deleted components this storage keeps at most.
+    #[inline]
+    pub fn pool_capacity(&self) -> usize {
+        self.pool_capacity
+    }
+    /// Sets how many deleted components this storage keeps at most.\
+    /// `0` disables pooling, components are then dropped as soon as they're deleted.
+    pub fn set_pool_capacity(&mut self, capacity: usize) {
+        self.pool_capacity = capacity;
+        self.pool.truncate(capacity);
+    }
+    /// Returns the number of components currently in the pool.
+    #[inline]
+    pub fn pooled_len(&self) -> usize {
+        self.pool.len()
+    }
+    /// Takes a deleted component out of the pool.
+    ///
+    /// The component keeps the value it had when it was deleted, only its allocations are worth reusing.
+    #[inline]
+    pub fn take_pooled(&mut self) -> Option<T> {
+        self.pool.pop()
+    }
+    /// Drops all pooled components.
+    pub fn clear_pool(&mut self) {
+        self.pool.clear();
+    }
+    /// Keeps `component` in the pool if there is room left, drops it otherwise.
+    #[inline]
+    fn recycle(&mut self, component: T) {
+        if self.pool.len() < self.pool_capacity {
+            self.pool.push(component);
+        }
+    }
 }
 
 impl<T: Component> SparseSet<T> {
@@ -321,6 +702,8 @@ impl<T: Component> SparseSet<T> {
         if let Some(component) = self.actual_remove(entity) {
             if self.is_tracking_deletion() {
                 self.deletion_data.push((entity, current, component));
+            } else {
+                self.recycle(component);
             }
 
             true
@@ -343,14 +726,18 @@ impl<T: Component> SparseSet<T> {
 
     #[inline]
     pub(crate) fn actual_remove(&mut self, entity: EntityId) -> Option<T> {
-        let sparse_entity = self.sparse.get(entity)?;
+        let sparse_entity = self.sparse.get::<T>(entity)?;
 
         if entity.gen() >= sparse_entity.gen() {
             unsafe {
-                *self.sparse.get_mut_unchecked(entity) = EntityId::dead();
+                *self.sparse.get_mut_unchecked::<T>(entity) = EntityId::dead();
             }
 
             self.dense.swap_remove(sparse_entity.uindex());
+            self.structural_changes = self.structural_changes.wrapping_add(1);
+            if let Some(bitset) = &mut self.bitset {
+                bitset.remove(entity.uindex());
+            }
             if self.is_tracking_insertion() {
                 self.insertion_data.swap_remove(sparse_entity.uindex());
             }
@@ -400,12 +787,32 @@ impl<T: Component> SparseSet<T> {
     }
     /// Clear all deletion tracking data.
     pub fn clear_all_deleted(&mut self) {
-        self.deletion_data.clear();
+        if self.pool_capacity == 0 {
+            self.deletion_data.clear();
+        } else {
+            let deletion_data = core::mem::take(&mut self.deletion_data);
+
+            for (_, _, component) in deletion_data {
+                self.recycle(component);
+            }
+        }
     }
     /// Clear all deletion tracking data older than some timestamp.
     pub fn clear_all_deleted_older_than_timestamp(&mut self, timestamp: TrackingTimestamp) {
-        self.deletion_data
-            .retain(|(_, t, _)| timestamp.is_older_than(*t));
+        if self.pool_capacity == 0 {
+            self.deletion_data
+                .retain(|(_, t, _)| timestamp.is_older_than(*t));
+        } else {
+            let deletion_data = core::mem::take(&mut self.deletion_data);
+
+            for (entity, t, component) in deletion_data {
+                if timestamp.is_older_than(t) {
+                    self.deletion_data.push((entity, t, component));
+                } else {
+                    self.recycle(component);
+                }
+            }
+        }
     }
     /// Clear all removal tracking data.
     pub fn clear_all_removed(&mut self) {
@@ -425,8 +832,7 @@ impl<T: Component> SparseSet<T> {
         &mut self,
         timestamp: TrackingTimestamp,
     ) {
-        self.deletion_data
-            .retain(|(_, t, _)| timestamp.is_older_than(*t));
+        self.clear_all_deleted_older_than_timestamp(timestamp);
         self.removal_data
             .retain(|(_, t)| timestamp.is_older_than(*t));
     }
@@ -537,7 +943,8 @@ impl<T: Component> SparseSet<T> {
         self.dense.reserve(additional);
         self.data.reserve(additional);
     }
-    /// Sorts the `SparseSet` with a comparator function, but may not preserve the order of equal elements.
+    /// Sorts the `SparseSet` with a comparator function, but may not preserve the order of equal elements.\
+    /// Tracking information moves with the components, sorting doesn't flag them as modified.
     pub fn sort_unstable_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
         let mut transform: Vec<usize> = (0..self.dense.len()).collect();
 
@@ -558,13 +965,138 @@ impl<T: Component> SparseSet<T> {
             }
             self.dense.swap(i, pos);
             self.data.swap(i, pos);
+
+            if self.is_tracking_insertion {
+                self.insertion_data.swap(i, pos);
+            }
+            if self.is_tracking_modification {
+                self.modification_data.swap(i, pos);
+            }
+        }
+
+        for (i, id) in self.dense.iter().enumerate() {
+            unsafe {
+                self.sparse.get_mut_unchecked::<T>(*id).set_index(i as u64);
+            }
+        }
+    }
+
+    /// Sorts the components by entity index and frees the unused memory.
+    ///
+    /// Iterating the storage then goes through entities in the same order as other compacted storages.
+    pub fn compact(&mut self) {
+        let mut transform: Vec<usize> = (0..self.dense.len()).collect();
+
+        // SAFE dense and transform have the same length
+        transform.sort_unstable_by_key(|&i| unsafe { self.dense.get_unchecked(i).index() });
+
+        let mut pos;
+        for i in 0..transform.len() {
+            // SAFE we're in bound
+            pos = unsafe { *transform.get_unchecked(i) };
+            while pos < i {
+                // SAFE we're in bound
+                pos = unsafe { *transform.get_unchecked(pos) };
+            }
+            self.swap_dense(i, pos);
         }
 
         for (i, id) in self.dense.iter().enumerate() {
             unsafe {
-                self.sparse.get_mut_unchecked(*id).set_index(i as u64);
+                self.sparse.get_mut_unchecked::<T>(*id).set_index(i as u64);
             }
         }
+
+        self.sparse.shrink();
+        self.dense.shrink_to_fit();
+        self.data.shrink_to_fit();
+        self.insertion_data.shrink_to_fit();
+        self.modification_data.shrink_to_fit();
+        self.deletion_data.shrink_to_fit();
+        self.removal_data.shrink_to_fit();
+    }
+
+    /// Replaces the ids of the entities in `remap`, tracking information included.
+    pub(crate) fn remap_entities(&mut self, remap: &EntityRemap) {
+        for i in 0..self.dense.len() {
+            // SAFE i is in bound
+            let entity = unsafe { *self.dense.get_unchecked(i) };
+
+            if let Some(new) = remap.get(entity) {
+                self.sparse.remove::<T>(entity);
+                self.sparse.allocate_at::<T>(new);
+
+                // SAFE we just allocated new's entry
+                unsafe {
+                    *self.sparse.get_mut_unchecked::<T>(new) =
+                        EntityId::new_from_index_and_gen(i as u64, new.gen());
+                    *self.dense.get_unchecked_mut(i) = new;
+                }
+
+                if let Some(bitset) = &mut self.bitset {
+                    bitset.remove(entity.uindex());
+                    bitset.insert(new.uindex());
+                }
+            }
+        }
+
+        for (entity, _, _) in &mut self.deletion_data {
+            if let Some(new) = remap.get(*entity) {
+                *entity = new;
+            }
+        }
+        for (entity, _) in &mut self.removal_data {
+            if let Some(new) = remap.get(*entity) {
+                *entity = new;
+            }
+        }
+    }
+
+    /// Moves all components for which `pred` returns `true` before the others.\
+    /// Returns the number of components `pred` returned `true` for.
+    pub(crate) fn private_partition<F: FnMut(EntityId, &T) -> bool>(
+        &mut self,
+        mut pred: F,
+    ) -> usize {
+        let mut split = 0;
+
+        for i in 0..self.dense.len() {
+            // SAFE i is in bound
+            if pred(unsafe { *self.dense.get_unchecked(i) }, unsafe {
+                self.data.get_unchecked(i)
+            }) {
+                if i != split {
+                    self.swap_dense(i, split);
+                }
+
+                split += 1;
+            }
+        }
+
+        split
+    }
+
+    /// Swaps the components at index `a` and `b`, tracking information included.
+    fn swap_dense(&mut self, a: usize, b: usize) {
+        self.dense.swap(a, b);
+        self.data.swap(a, b);
+
+        if self.is_tracking_insertion {
+            self.insertion_data.swap(a, b);
+        }
+        if self.is_tracking_modification {
+            self.modification_data.swap(a, b);
+        }
+
+        // SAFE both ids are present in the storage
+        unsafe {
+            self.sparse
+                .get_mut_unchecked(self.dense[a])
+                .set_index(a as u64);
+            self.sparse
+                .get_mut_unchecked(self.dense[b])
+                .set_index(b as u64);
+        }
     }
 
     /// Applies the given function `f` to the entities `a` and `b`.\
@@ -656,15 +1188,21 @@ impl<T: Component> SparseSet<T> {
     pub(crate) fn private_clear(&mut self, current: TrackingTimestamp) {
         for &id in &self.dense {
             unsafe {
-                *self.sparse.get_mut_unchecked(id) = EntityId::dead();
+                *self.sparse.get_mut_unchecked::<T>(id) = EntityId::dead();
             }
         }
 
         self.insertion_data.clear();
         self.modification_data.clear();
+        if let Some(bitset) = &mut self.bitset {
+            bitset.clear();
+        }
 
         let is_tracking_deletion = self.is_tracking_deletion();
 
+        self.structural_changes = self
+            .structural_changes
+            .wrapping_add(self.dense.len() as u64);
         let dense = self.dense.drain(..);
         let data = self.data.drain(..);
 
@@ -673,6 +1211,9 @@ impl<T: Component> SparseSet<T> {
                 .zip(data)
                 .map(|(entity, component)| (entity, current, component));
             self.deletion_data.extend(iter);
+        } else if self.pool_capacity > self.pool.len() {
+            let room = self.pool_capacity - self.pool.len();
+            self.pool.extend(data.take(room));
         }
     }
 
@@ -686,15 +1227,19 @@ impl<T: Component> SparseSet<T> {
         for id in &self.dense {
             // SAFE ids from sparse_set.dense are always valid
             unsafe {
-                *self.sparse.get_mut_unchecked(*id) = EntityId::dead();
+                *self.sparse.get_mut_unchecked::<T>(*id) = EntityId::dead();
             }
         }
 
         self.insertion_data.clear();
         self.modification_data.clear();
+        if let Some(bitset) = &mut self.bitset {
+            bitset.clear();
+        }
 
         let dense_ptr = self.dense.as_ptr();
         let dense_len = self.dense.len();
+        self.structural_changes = self.structural_changes.wrapping_add(dense_len as u64);
 
         unsafe {
             self.dense.set_len(0);
@@ -748,10 +1293,57 @@ impl<T: Component> SparseSet<T> {
             }
         }
     }
+
+    pub(crate) fn private_for_each_pair_mut<
+        F: FnMut((EntityId, Mut<'_, T>), (EntityId, Mut<'_, T>)),
+    >(
+        &mut self,
+        current: TrackingTimestamp,
+        mut f: F,
+    ) {
+        for second in 1..self.data.len() {
+            let (data_before, data_after) = self.data.split_at_mut(second);
+            let (mut modification_before, modification_after) = if self.is_tracking_modification {
+                let (before, after) = self.modification_data.split_at_mut(second);
+                (Some(before), after.first_mut())
+            } else {
+                (None, None)
+            };
+            let second_data = &mut data_after[0];
+            let mut second_flag = modification_after;
+            let second_id = self.dense[second];
+
+            for (first, first_data) in data_before.iter_mut().enumerate() {
+                let first_flag = modification_before
+                    .as_deref_mut()
+                    .map(|modification| &mut modification[first]);
+
+                f(
+                    (
+                        self.dense[first],
+                        Mut {
+                            flag: first_flag,
+                            current,
+                            data: first_data,
+                        },
+                    ),
+                    (
+                        second_id,
+                        Mut {
+                            flag: second_flag.as_deref_mut(),
+                            current,
+                            data: &mut *second_data,
+                        },
+                    ),
+                );
+            }
+        }
+    }
 }
 
 impl<T: Ord + Component> SparseSet<T> {
-    /// Sorts the `SparseSet`, but may not preserve the order of equal elements.
+    /// Sorts the `SparseSet`, but may not preserve the order of equal elements.\
+    /// Tracking information moves with the components, sorting doesn't flag them as modified.
     pub fn sort_unstable(&mut self) {
         self.sort_unstable_by(Ord::cmp)
     }
@@ -769,12 +1361,27 @@ impl<T: 'static + Component + Send + Sync> Storage for SparseSet<T> {
     fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         Some(&self.sparse)
     }
+    fn component_name(&self) -> Option<&'static str> {
+        Some(component_name::<T>())
+    }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
         Some(self.private_memory_usage())
     }
     fn is_empty(&self) -> bool {
         self.is_empty()
     }
+    fn len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+    fn structural_changes(&self) -> Option<u64> {
+        Some(self.structural_changes)
+    }
+    fn compact(&mut self) {
+        self.compact();
+    }
+    fn remap_entities(&mut self, remap: &EntityRemap) {
+        self.remap_entities(remap);
+    }
     fn clear_all_removed_and_deleted(&mut self) {
         self.deletion_data.clear();
         self.removal_data.clear();
@@ -820,12 +1427,27 @@ impl<T: 'static + Component + Sync> Storage for NonSend<SparseSet<T>> {
     fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         Some(&self.sparse)
     }
+    fn component_name(&self) -> Option<&'static str> {
+        Some(component_name::<T>())
+    }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
         Some(self.private_memory_usage())
     }
     fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+    fn len(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+    fn structural_changes(&self) -> Option<u64> {
+        Some(self.0.structural_changes)
+    }
+    fn compact(&mut self) {
+        self.0.compact();
+    }
+    fn remap_entities(&mut self, remap: &EntityRemap) {
+        self.0.remap_entities(remap);
+    }
     fn clear_all_removed_and_deleted(&mut self) {
         self.deletion_data.clear();
         self.removal_data.clear();
@@ -871,12 +1493,27 @@ impl<T: 'static + Component + Send> Storage for NonSync<SparseSet<T>> {
     fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         Some(&self.sparse)
     }
+    fn component_name(&self) -> Option<&'static str> {
+        Some(component_name::<T>())
+    }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
         Some(self.private_memory_usage())
     }
     fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+    fn len(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+    fn structural_changes(&self) -> Option<u64> {
+        Some(self.0.structural_changes)
+    }
+    fn compact(&mut self) {
+        self.0.compact();
+    }
+    fn remap_entities(&mut self, remap: &EntityRemap) {
+        self.0.remap_entities(remap);
+    }
     fn clear_all_removed_and_deleted(&mut self) {
         self.deletion_data.clear();
         self.removal_data.clear();
@@ -922,12 +1559,27 @@ impl<T: 'static + Component> Storage for NonSendSync<SparseSet<T>> {
     fn sparse_array(&self) -> Option<&SparseArray<EntityId, BUCKET_SIZE>> {
         Some(&self.sparse)
     }
+    fn component_name(&self) -> Option<&'static str> {
+        Some(component_name::<T>())
+    }
     fn memory_usage(&self) -> Option<StorageMemoryUsage> {
         Some(self.private_memory_usage())
     }
     fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+    fn len(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+    fn structural_changes(&self) -> Option<u64> {
+        Some(self.0.structural_changes)
+    }
+    fn compact(&mut self) {
+        self.0.compact();
+    }
+    fn remap_entities(&mut self, remap: &EntityRemap) {
+        self.0.remap_entities(remap);
+    }
     fn clear_all_removed_and_deleted(&mut self) {
         self.deletion_data.clear();
         self.removal_data.clear();
@@ -3,6 +3,7 @@ use crate::component::Component;
 use crate::entity_id::EntityId;
 use crate::sparse_set::SparseSet;
 use crate::storage::StorageId;
+use crate::undo::record_removal;
 #[cfg(doc)]
 use crate::world::World;
 
@@ -21,6 +22,8 @@ impl<T: Send + Sync + Component> TupleRemove for T {
     fn remove(all_storages: &mut AllStorages, entity: EntityId) -> Self::Out {
         let current = all_storages.get_current();
 
+        record_removal::<T>(all_storages, entity);
+
         all_storages
             .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<T>>(), SparseSet::new)
             .dyn_remove(entity, current)
@@ -36,9 +39,13 @@ macro_rules! impl_remove_component {
                 let current = all_storages.get_current();
 
                 ($(
-                    all_storages
-                        .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<$type>>(), SparseSet::new)
-                        .dyn_remove(entity, current),
+                    {
+                        record_removal::<$type>(all_storages, entity);
+
+                        all_storages
+                            .exclusive_storage_or_insert_mut(StorageId::of::<SparseSet<$type>>(), SparseSet::new)
+                            .dyn_remove(entity, current)
+                    },
                 )+)
             }
         }
@@ -1,34 +1,100 @@
+use crate::component::Component;
 use crate::entity_id::EntityId;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use core::hint::unreachable_unchecked;
 use core::mem::size_of;
 
+/// Number of entities an inline [`SparseArray`] can hold before switching to buckets, see [`Component::INLINE`].
+pub(crate) const INLINE_LEN: usize = 8;
+
 /// Internal part of a [`SparseSet`].
 ///
 /// [`SparseSet`]: crate::sparse_set::SparseSet
-pub struct SparseArray<T, const N: usize>(Vec<Option<Box<[T; N]>>>);
+pub struct SparseArray<T, const N: usize> {
+    buckets: Vec<Option<Box<[T; N]>>>,
+    /// Entries of an inline array, `buckets` stays empty until it outgrows them.\
+    /// Only read for [`Component::INLINE`] components, other storages never branch on it.
+    inline: Option<InlineEntries>,
+}
+
+/// `(index, sparse entity)` pairs, a dead sparse entity marks a free entry.
+struct InlineEntries {
+    len: usize,
+    entries: [(u64, EntityId); INLINE_LEN],
+}
+
+impl InlineEntries {
+    /// Makes room for all indices in `start..=end`.\
+    /// Returns `false` when there isn't enough space, some indices might have been allocated.
+    ///
+    /// Allocated entries stay dead until written so the free entries taken are tracked with `slot`.
+    fn allocate(&mut self, start: u64, end: u64) -> bool {
+        let mut slot = 0;
+
+        for index in start..=end {
+            if self.get(index).is_some() {
+                continue;
+            }
+
+            while slot < self.len
+                && (!self.entries[slot].1.is_dead()
+                    || (start..=end).contains(&self.entries[slot].0))
+            {
+                slot += 1;
+            }
+
+            if slot < self.len {
+                self.entries[slot].0 = index;
+                slot += 1;
+            } else if self.len < INLINE_LEN {
+                self.entries[self.len] = (index, EntityId::dead());
+                self.len += 1;
+                slot = self.len;
+            } else {
+                return false;
+            }
+        }
+
+        true
+    }
+    fn get(&self, index: u64) -> Option<&EntityId> {
+        self.entries[..self.len]
+            .iter()
+            .find(|(key, _)| *key == index)
+            .map(|(_, value)| value)
+    }
+    fn get_mut(&mut self, index: u64) -> Option<&mut EntityId> {
+        self.entries[..self.len]
+            .iter_mut()
+            .find(|(key, _)| *key == index)
+            .map(|(_, value)| value)
+    }
+}
 
 impl<T, const N: usize> SparseArray<T, N> {
     #[inline]
     pub(super) fn new() -> Self {
-        SparseArray(Vec::new())
+        SparseArray {
+            buckets: Vec::new(),
+            inline: None,
+        }
     }
     #[inline]
     pub(crate) fn len(&self) -> usize {
-        self.0.len()
+        self.buckets.len()
     }
     #[inline]
     pub(super) fn as_ptr(&self) -> *const Option<Box<[T; N]>> {
-        self.0.as_ptr()
+        self.buckets.as_ptr()
     }
     #[inline]
     pub(super) fn as_mut_ptr(&mut self) -> *mut Option<Box<[T; N]>> {
-        self.0.as_mut_ptr()
+        self.buckets.as_mut_ptr()
     }
     pub(super) fn used_memory(&self) -> usize {
-        self.0.len() * size_of::<Option<Box<T>>>()
-            + self.0.iter().fold(0, |count, array| {
+        self.buckets.len() * size_of::<Option<Box<T>>>()
+            + self.buckets.iter().fold(0, |count, array| {
                 if array.is_some() {
                     count + size_of::<[T; N]>()
                 } else {
@@ -37,8 +103,8 @@ impl<T, const N: usize> SparseArray<T, N> {
             })
     }
     pub(super) fn reserved_memory(&self) -> usize {
-        self.0.capacity() * size_of::<Option<Box<T>>>()
-            + self.0.iter().fold(0, |count, array| {
+        self.buckets.capacity() * size_of::<Option<Box<T>>>()
+            + self.buckets.iter().fold(0, |count, array| {
                 if array.is_some() {
                     count + size_of::<[T; N]>()
                 } else {
@@ -49,54 +115,172 @@ impl<T, const N: usize> SparseArray<T, N> {
 }
 
 impl<const N: usize> SparseArray<EntityId, N> {
+    /// Returns an array storing up to [`INLINE_LEN`] entities without allocating.
     #[inline]
-    #[track_caller]
-    pub(super) fn allocate_at(&mut self, entity: EntityId) {
-        if entity.is_dead() {
-            panic!("Tried to add a component with a dead entity.");
+    pub(super) fn new_inline() -> Self {
+        SparseArray {
+            buckets: Vec::new(),
+            inline: Some(InlineEntries {
+                len: 0,
+                entries: [(0, EntityId::dead()); INLINE_LEN],
+            }),
         }
+    }
+    /// Returns `true` if the entities are still stored inline.
+    #[inline]
+    pub(super) fn is_inline(&self) -> bool {
+        self.inline.is_some()
+    }
+    /// Moves the inline entries to buckets.
+    #[cold]
+    fn spill(&mut self) {
+        if let Some(inline) = self.inline.take() {
+            for &(index, value) in &inline.entries[..inline.len] {
+                if !value.is_dead() {
+                    let entity = EntityId::new(index);
 
-        if entity.bucket() >= self.0.len() {
-            self.0.resize(entity.bucket() + 1, None);
+                    self.allocate_bucket(entity);
+                    // SAFE we just allocated the bucket
+                    unsafe { *self.bucket_entry_mut(entity) = value };
+                }
+            }
+        }
+    }
+    fn allocate_bucket(&mut self, entity: EntityId) {
+        if entity.bucket() >= self.buckets.len() {
+            self.buckets.resize(entity.bucket() + 1, None);
         }
         unsafe {
             // SAFE we just allocated at least entity.bucket()
-            let bucket = self.0.get_unchecked_mut(entity.bucket());
+            let bucket = self.buckets.get_unchecked_mut(entity.bucket());
 
             if bucket.is_none() {
                 *bucket = Some(Box::new([EntityId::dead(); N]));
             }
         }
     }
-    pub(crate) fn bulk_allocate(&mut self, start: EntityId, end: EntityId) {
-        if end.bucket() >= self.0.len() {
-            self.0.resize(end.bucket() + 1, None);
+    #[inline]
+    #[track_caller]
+    pub(super) fn allocate_at<C: Component>(&mut self, entity: EntityId) {
+        if entity.is_dead() {
+            panic!("Tried to add a component with a dead entity.");
+        }
+
+        if C::INLINE {
+            if let Some(inline) = &mut self.inline {
+                if inline.allocate(entity.index(), entity.index()) {
+                    return;
+                }
+
+                self.spill();
+            }
+        }
+
+        self.allocate_bucket(entity);
+    }
+    /// Frees the buckets without any entity and the unused capacity.
+    pub(super) fn shrink(&mut self) {
+        for bucket in &mut self.buckets {
+            if bucket
+                .as_ref()
+                .is_some_and(|entries| entries.iter().all(EntityId::is_dead))
+            {
+                *bucket = None;
+            }
+        }
+
+        while let Some(None) = self.buckets.last() {
+            self.buckets.pop();
+        }
+
+        self.buckets.shrink_to_fit();
+    }
+    /// Marks `entity`'s entry as free.
+    #[inline]
+    pub(super) fn remove<C: Component>(&mut self, entity: EntityId) {
+        if self.get::<C>(entity).is_some() {
+            // SAFE we just checked the entry exists
+            unsafe { *self.get_mut_unchecked::<C>(entity) = EntityId::dead() };
+        }
+    }
+    pub(crate) fn bulk_allocate<C: Component>(&mut self, start: EntityId, end: EntityId) {
+        if C::INLINE {
+            if let Some(inline) = &mut self.inline {
+                if start.index() <= end.index()
+                    && end.index() - start.index() < INLINE_LEN as u64
+                    && inline.allocate(start.index(), end.index())
+                {
+                    return;
+                }
+
+                self.spill();
+            }
+        }
+
+        if end.bucket() >= self.buckets.len() {
+            self.buckets.resize(end.bucket() + 1, None);
         }
         for bucket_index in start.bucket()..end.bucket() + 1 {
-            let bucket = unsafe { self.0.get_unchecked_mut(bucket_index) };
+            let bucket = unsafe { self.buckets.get_unchecked_mut(bucket_index) };
 
             if bucket.is_none() {
                 *bucket = Some(Box::new([EntityId::dead(); N]));
             }
         }
     }
+    /// `C` is the component stored, the inline entries are only looked at if it is [`Component::INLINE`].
     #[inline]
-    pub(crate) fn get(&self, entity: EntityId) -> Option<EntityId> {
-        self.0
-            .get(entity.bucket())?
-            .as_ref()
-            .map(|bucket| unsafe { *bucket.get_unchecked(entity.bucket_index()) })
+    pub(crate) fn get<C: Component>(&self, entity: EntityId) -> Option<EntityId> {
+        if C::INLINE {
+            if let Some(inline) = &self.inline {
+                return inline.get(entity.index()).copied();
+            }
+        }
+
+        self.bucket_entry(entity)
     }
     #[inline]
-    pub(super) unsafe fn get_unchecked(&self, entity: EntityId) -> EntityId {
-        match self.0.get_unchecked(entity.bucket()) {
+    pub(super) unsafe fn get_unchecked<C: Component>(&self, entity: EntityId) -> EntityId {
+        if C::INLINE {
+            if let Some(inline) = &self.inline {
+                return match inline.get(entity.index()) {
+                    Some(value) => *value,
+                    None => unreachable_unchecked(),
+                };
+            }
+        }
+
+        match self.buckets.get_unchecked(entity.bucket()) {
             Some(bucket) => *bucket.get_unchecked(entity.bucket_index()),
             None => unreachable_unchecked(),
         }
     }
     #[inline]
-    pub(crate) unsafe fn get_mut_unchecked(&mut self, entity: EntityId) -> &mut EntityId {
-        match self.0.get_unchecked_mut(entity.bucket()) {
+    pub(crate) unsafe fn get_mut_unchecked<C: Component>(
+        &mut self,
+        entity: EntityId,
+    ) -> &mut EntityId {
+        if C::INLINE {
+            if let Some(inline) = &mut self.inline {
+                return match inline.get_mut(entity.index()) {
+                    Some(value) => value,
+                    None => unreachable_unchecked(),
+                };
+            }
+        }
+
+        self.bucket_entry_mut(entity)
+    }
+    #[inline]
+    fn bucket_entry(&self, entity: EntityId) -> Option<EntityId> {
+        self.buckets
+            .get(entity.bucket())?
+            .as_ref()
+            .map(|bucket| unsafe { *bucket.get_unchecked(entity.bucket_index()) })
+    }
+    #[inline]
+    unsafe fn bucket_entry_mut(&mut self, entity: EntityId) -> &mut EntityId {
+        match self.buckets.get_unchecked_mut(entity.bucket()) {
             Some(bucket) => bucket.get_unchecked_mut(entity.bucket_index()),
             None => unreachable_unchecked(),
         }
@@ -104,7 +288,12 @@ impl<const N: usize> SparseArray<EntityId, N> {
     #[inline]
     #[allow(missing_docs)]
     pub fn contains(&self, entity: EntityId) -> bool {
-        if let Some(sparse_entity) = self.get(entity) {
+        let sparse_entity = match &self.inline {
+            Some(inline) => inline.get(entity.index()).copied(),
+            None => self.bucket_entry(entity),
+        };
+
+        if let Some(sparse_entity) = sparse_entity {
             sparse_entity.gen() == entity.gen()
         } else {
             false
@@ -1,6 +1,7 @@
 use crate::atomic_refcell::{ExclusiveBorrow, SharedBorrow};
 use crate::component::Component;
 use crate::entity_id::EntityId;
+use crate::sparse_set::{SparseArray, SparseSet, BUCKET_SIZE};
 use crate::tracking::{Tracking, TrackingTimestamp};
 use crate::views::{View, ViewMut};
 use alloc::boxed::Box;
@@ -11,6 +12,8 @@ use core::ptr;
 pub struct FullRawWindow<'a, T> {
     sparse: *const *const EntityId,
     sparse_len: usize,
+    /// Only read for [`Component::INLINE`] storages.
+    sparse_array: *const SparseArray<EntityId, BUCKET_SIZE>,
     pub(crate) dense: *const EntityId,
     pub(crate) dense_len: usize,
     pub(crate) data: *const T,
@@ -34,6 +37,7 @@ impl<'w, T: Component> FullRawWindow<'w, T> {
         FullRawWindow {
             sparse,
             sparse_len,
+            sparse_array: &view.sparse,
             dense: view.dense.as_ptr(),
             dense_len: view.dense.len(),
             data: view.data.as_ptr(),
@@ -59,7 +63,7 @@ impl<'w, T: Component> FullRawWindow<'w, T> {
             ..
         } = view;
 
-        let sparse_len = sparse_set.len();
+        let sparse_len = sparse_set.sparse.len();
         let sparse: *const Option<Box<[EntityId; super::BUCKET_SIZE]>> = sparse_set.sparse.as_ptr();
         let sparse = sparse as *const *const EntityId;
 
@@ -67,6 +71,7 @@ impl<'w, T: Component> FullRawWindow<'w, T> {
             FullRawWindow {
                 sparse,
                 sparse_len,
+                sparse_array: &sparse_set.sparse,
                 dense: sparse_set.dense.as_ptr(),
                 dense_len: sparse_set.dense.len(),
                 data: sparse_set.data.as_ptr(),
@@ -90,6 +95,7 @@ impl<'w, T: Component> FullRawWindow<'w, T> {
         FullRawWindow {
             sparse,
             sparse_len,
+            sparse_array: &view.sparse,
             dense: view.dense.as_ptr(),
             dense_len: view.dense.len(),
             data: view.data.as_ptr(),
@@ -135,6 +141,9 @@ impl<'w, T: Component> FullRawWindow<'w, T> {
             } else {
                 None
             }
+        } else if T::INLINE {
+            // SAFE the storage outlives the window and its sparse array isn't modified while borrowed
+            unsafe { (*self.sparse_array).get::<T>(entity) }
         } else {
             None
         }
@@ -147,6 +156,7 @@ impl<T: Component> Clone for FullRawWindow<'_, T> {
         FullRawWindow {
             sparse: self.sparse,
             sparse_len: self.sparse_len,
+            sparse_array: self.sparse_array,
             dense: self.dense,
             dense_len: self.dense_len,
             data: self.data,
@@ -163,6 +173,8 @@ impl<T: Component> Clone for FullRawWindow<'_, T> {
 pub struct FullRawWindowMut<'a, T, Track> {
     sparse: *mut *mut EntityId,
     sparse_len: usize,
+    /// Only read for [`Component::INLINE`] storages.
+    sparse_array: *const SparseArray<EntityId, BUCKET_SIZE>,
     pub(crate) dense: *mut EntityId,
     pub(crate) dense_len: usize,
     pub(crate) data: *mut T,
@@ -180,22 +192,27 @@ unsafe impl<T: Send + Component, Track> Send for FullRawWindowMut<'_, T, Track>
 impl<'w, T: Component, Track> FullRawWindowMut<'w, T, Track> {
     #[inline]
     pub(crate) fn new(view: &mut ViewMut<'_, T, Track>) -> Self {
-        let sparse_len = view.sparse.len();
-        let sparse: *mut Option<Box<[EntityId; super::BUCKET_SIZE]>> = view.sparse.as_mut_ptr();
+        // all pointers have to come from the same reborrow
+        let sparse_set: &mut SparseSet<T> = view.sparse_set;
+
+        let sparse_len = sparse_set.sparse.len();
+        let sparse: *mut Option<Box<[EntityId; super::BUCKET_SIZE]>> =
+            sparse_set.sparse.as_mut_ptr();
         let sparse = sparse as *mut *mut EntityId;
 
         FullRawWindowMut {
             sparse,
             sparse_len,
-            dense: view.dense.as_mut_ptr(),
-            dense_len: view.dense.len(),
-            data: view.data.as_mut_ptr(),
-            insertion_data: view.insertion_data.as_ptr(),
-            modification_data: view.modification_data.as_mut_ptr(),
+            sparse_array: &sparse_set.sparse,
+            dense: sparse_set.dense.as_mut_ptr(),
+            dense_len: sparse_set.dense.len(),
+            data: sparse_set.data.as_mut_ptr(),
+            insertion_data: sparse_set.insertion_data.as_ptr(),
+            modification_data: sparse_set.modification_data.as_mut_ptr(),
             last_insertion: view.last_insertion,
             last_modification: view.last_modification,
             current: view.current,
-            is_tracking_modification: view.is_tracking_modification(),
+            is_tracking_modification: sparse_set.is_tracking_modification(),
             _phantom: PhantomData,
         }
     }
@@ -213,7 +230,7 @@ impl<'w, T: Component, Track> FullRawWindowMut<'w, T, Track> {
             ..
         } = view;
 
-        let sparse_len = sparse_set.len();
+        let sparse_len = sparse_set.sparse.len();
         let sparse: *mut Option<Box<[EntityId; super::BUCKET_SIZE]>> =
             sparse_set.sparse.as_mut_ptr();
         let sparse = sparse as *mut *mut EntityId;
@@ -222,6 +239,7 @@ impl<'w, T: Component, Track> FullRawWindowMut<'w, T, Track> {
             FullRawWindowMut {
                 sparse,
                 sparse_len,
+                sparse_array: &sparse_set.sparse,
                 dense: sparse_set.dense.as_mut_ptr(),
                 dense_len: sparse_set.dense.len(),
                 data: sparse_set.data.as_mut_ptr(),
@@ -271,6 +289,9 @@ impl<'w, T: Component, Track> FullRawWindowMut<'w, T, Track> {
             } else {
                 None
             }
+        } else if T::INLINE {
+            // SAFE the storage outlives the window and its sparse array isn't modified while borrowed
+            unsafe { (*self.sparse_array).get::<T>(entity) }
         } else {
             None
         }
@@ -283,6 +304,7 @@ impl<T: Component, Track> Clone for FullRawWindowMut<'_, T, Track> {
         FullRawWindowMut {
             sparse: self.sparse,
             sparse_len: self.sparse_len,
+            sparse_array: self.sparse_array,
             dense: self.dense,
             dense_len: self.dense_len,
             data: self.data,
@@ -0,0 +1,319 @@
+use crate::component::{Component, Unique};
+use crate::entity_id::EntityId;
+use crate::iter::{IntoIter, IntoWithId};
+use crate::sparse_set::SparseSet;
+use crate::tracking::{InsertionTracking, ModificationTracking};
+use crate::views::{UniqueViewMut, View};
+use crate::ShipHashMap;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Component with a 2D position that can be indexed by a [`SpatialGrid`].
+pub trait SpatialPosition: Component {
+    /// Returns the position as `[x, y]`.
+    fn position(&self) -> [f32; 2];
+}
+
+/// Uniform grid bucketing entities by the position of their `P` component.
+///
+/// The grid is brought up to date by [`SpatialGrid::update`], or the [`update_spatial_grid`] system,
+/// using `P`'s insertion and modification tracking.\
+/// Entities that lost their `P` component are skipped by queries and dropped on the next update.
+///
+/// Distances are computed using the current content of the storage and returned squared.
+///
+/// ### Example
+/// ```
+/// use shipyard::{track, Component, SpatialGrid, SpatialPosition, View, World};
+///
+/// struct Position([f32; 2]);
+///
+/// impl Component for Position {
+///     type Tracking = track::InsertionAndModification;
+/// }
+///
+/// impl SpatialPosition for Position {
+///     fn position(&self) -> [f32; 2] {
+///         self.0
+///     }
+/// }
+///
+/// let mut world = World::new();
+///
+/// let close = world.add_entity(Position([1.0, 0.0]));
+/// let far = world.add_entity(Position([20.0, 0.0]));
+///
+/// let mut grid = SpatialGrid::<Position>::new(10.0);
+/// let positions = world.borrow::<View<Position>>().unwrap();
+/// grid.update(&positions);
+///
+/// let nearest: Vec<_> = grid.nearest([0.0, 0.0], 2, &positions).collect();
+/// assert_eq!(nearest, [(close, 1.0), (far, 400.0)]);
+///
+/// let within: Vec<_> = grid.within_radius([0.0, 0.0], 5.0, &positions).collect();
+/// assert_eq!(within, [(close, 1.0)]);
+/// ```
+pub struct SpatialGrid<P> {
+    cell_size: f32,
+    cells: ShipHashMap<(i32, i32), Vec<EntityId>>,
+    entity_cells: ShipHashMap<EntityId, (i32, i32)>,
+    phantom: PhantomData<fn() -> P>,
+}
+
+impl<P: SpatialPosition> Unique for SpatialGrid<P> {}
+
+impl<P: SpatialPosition> SpatialGrid<P> {
+    /// Creates an empty grid made of square cells of side `cell_size`.
+    ///
+    /// Queries are fastest when the cell size is close to the usual search radius.
+    ///
+    /// ### Panics
+    ///
+    /// - `cell_size` is not strictly positive.
+    #[track_caller]
+    pub fn new(cell_size: f32) -> SpatialGrid<P> {
+        assert!(cell_size > 0.0, "cell_size has to be strictly positive");
+
+        SpatialGrid {
+            cell_size,
+            cells: ShipHashMap::default(),
+            entity_cells: ShipHashMap::default(),
+            phantom: PhantomData,
+        }
+    }
+    /// Returns the side of the cells.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+    /// Returns the number of entities in the grid.
+    pub fn len(&self) -> usize {
+        self.entity_cells.len()
+    }
+    /// Returns `true` if the grid is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entity_cells.is_empty()
+    }
+    /// Moves the entities whose `P` component was inserted or modified to their new cell.
+    ///
+    /// If the grid doesn't contain as many entities as the storage afterwards, it is rebuilt.
+    pub fn update<Track: InsertionTracking + ModificationTracking>(
+        &mut self,
+        positions: &View<'_, P, Track>,
+    ) {
+        for (entity, position) in positions.inserted_or_modified().iter().with_id() {
+            self.insert(entity, position.position());
+        }
+
+        if self.entity_cells.len() != positions.len() {
+            self.rebuild(positions);
+        }
+    }
+    /// Rebuilds the grid from the content of the `P` storage.
+    pub fn rebuild(&mut self, positions: &SparseSet<P>) {
+        self.cells.clear();
+        self.entity_cells.clear();
+
+        for (&entity, position) in positions.dense.iter().zip(&positions.data) {
+            self.insert(entity, position.position());
+        }
+    }
+    /// Adds `entity` to the grid or moves it to the cell containing `position`.
+    fn insert(&mut self, entity: EntityId, position: [f32; 2]) {
+        let cell = self.cell_of(position);
+
+        match self.entity_cells.insert(entity, cell) {
+            Some(old_cell) if old_cell == cell => return,
+            Some(old_cell) => self.remove_from_cell(entity, old_cell),
+            None => {}
+        }
+
+        self.cells.entry(cell).or_default().push(entity);
+    }
+    /// Removes `entity` from the grid, returns `false` if it wasn't in it.
+    pub fn remove(&mut self, entity: EntityId) -> bool {
+        match self.entity_cells.remove(&entity) {
+            Some(cell) => {
+                self.remove_from_cell(entity, cell);
+
+                true
+            }
+            None => false,
+        }
+    }
+    fn remove_from_cell(&mut self, entity: EntityId, cell: (i32, i32)) {
+        if let Some(entities) = self.cells.get_mut(&cell) {
+            if let Some(index) = entities.iter().position(|&other| other == entity) {
+                entities.swap_remove(index);
+            }
+
+            if entities.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+    /// Removes all entities from the grid.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entity_cells.clear();
+    }
+    /// Returns the entities within `radius` of `point` and their squared distance to it, in no particular order.
+    pub fn within_radius<'a>(
+        &'a self,
+        point: [f32; 2],
+        radius: f32,
+        positions: &'a SparseSet<P>,
+    ) -> impl Iterator<Item = (EntityId, f32)> + 'a {
+        let radius_squared = radius * radius;
+        let min = self.cell_of([point[0] - radius, point[1] - radius]);
+        let max = self.cell_of([point[0] + radius, point[1] + radius]);
+
+        let width = (max.0 as i64 - min.0 as i64 + 1) as u64;
+        let height = (max.1 as i64 - min.1 as i64 + 1) as u64;
+        // looking up each cell of the area costs more than going through the occupied ones
+        let scan_all = width.saturating_mul(height) > self.cells.len() as u64;
+
+        let by_coordinates = (!scan_all).then(|| {
+            (min.0..=max.0)
+                .flat_map(move |x| (min.1..=max.1).filter_map(move |y| self.cells.get(&(x, y))))
+        });
+        let by_cells = scan_all.then(|| {
+            self.cells
+                .iter()
+                .filter(move |(&(x, y), _)| {
+                    (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&y)
+                })
+                .map(|(_, entities)| entities)
+        });
+
+        by_coordinates
+            .into_iter()
+            .flatten()
+            .chain(by_cells.into_iter().flatten())
+            .flatten()
+            .filter_map(move |&entity| {
+                let distance = distance_squared(point, positions, entity)?;
+
+                (distance <= radius_squared).then_some((entity, distance))
+            })
+    }
+    /// Returns the `k` entities closest to `point` and their squared distance to it, closest first.
+    pub fn nearest(
+        &self,
+        point: [f32; 2],
+        k: usize,
+        positions: &SparseSet<P>,
+    ) -> impl Iterator<Item = (EntityId, f32)> {
+        let mut found: Vec<(EntityId, f32)> = Vec::new();
+
+        if k > 0 {
+            let center = self.cell_of(point);
+            let mut ring = 0;
+            // entities outside of the rings searched are at least this far
+            let mut reach = 0.0;
+
+            loop {
+                let side = 2 * ring as u64 + 1;
+
+                // going through the occupied cells is cheaper than searching further
+                if side * side > self.cells.len() as u64 {
+                    found.clear();
+                    found.extend(self.cells.values().flatten().filter_map(|&entity| {
+                        Some((entity, distance_squared(point, positions, entity)?))
+                    }));
+
+                    break;
+                }
+
+                for cell in ring_cells(center, ring) {
+                    if let Some(entities) = self.cells.get(&cell) {
+                        found.extend(entities.iter().filter_map(|&entity| {
+                            Some((entity, distance_squared(point, positions, entity)?))
+                        }));
+                    }
+                }
+
+                if found.len() >= k {
+                    found.select_nth_unstable_by(k - 1, |a, b| a.1.total_cmp(&b.1));
+
+                    if found[k - 1].1 <= reach * reach {
+                        break;
+                    }
+                }
+
+                ring += 1;
+                reach += self.cell_size;
+            }
+
+            found.sort_unstable_by(|a, b| a.1.total_cmp(&b.1));
+            found.truncate(k);
+        }
+
+        found.into_iter()
+    }
+    fn cell_of(&self, position: [f32; 2]) -> (i32, i32) {
+        (
+            floor(position[0] / self.cell_size),
+            floor(position[1] / self.cell_size),
+        )
+    }
+}
+
+impl<P> core::fmt::Debug for SpatialGrid<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SpatialGrid")
+            .field("cell_size", &self.cell_size)
+            .field("len", &self.entity_cells.len())
+            .finish()
+    }
+}
+
+/// Updates the [`SpatialGrid`] of `P`.
+///
+/// Modification tracking follows the usual rules, inside a workload changes since the last run are considered.
+pub fn update_spatial_grid<P: SpatialPosition + Send + Sync>(
+    positions: View<'_, P>,
+    mut grid: UniqueViewMut<'_, SpatialGrid<P>>,
+) where
+    P::Tracking: InsertionTracking + ModificationTracking,
+{
+    grid.update(&positions);
+}
+
+fn distance_squared<P: SpatialPosition>(
+    point: [f32; 2],
+    positions: &SparseSet<P>,
+    entity: EntityId,
+) -> Option<f32> {
+    let position = positions.private_get(entity)?.position();
+    let x = position[0] - point[0];
+    let y = position[1] - point[1];
+
+    Some(x * x + y * y)
+}
+
+/// Returns the cells at exactly `ring` cells of `center`.
+fn ring_cells(center: (i32, i32), ring: i32) -> impl Iterator<Item = (i32, i32)> {
+    let (x, y) = center;
+    let horizontal = (x - ring..=x + ring).flat_map(move |cell_x| {
+        let bottom = (cell_x, y - ring);
+        let top = (ring > 0).then_some((cell_x, y + ring));
+
+        core::iter::once(bottom).chain(top)
+    });
+    let vertical = (y - ring + 1..y + ring).flat_map(move |cell_y| {
+        core::iter::once((x - ring, cell_y)).chain(core::iter::once((x + ring, cell_y)))
+    });
+
+    horizontal.chain(vertical)
+}
+
+/// `f32::floor` isn't available without `std`.
+fn floor(value: f32) -> i32 {
+    let truncated = value as i32;
+
+    if value < 0.0 && value % 1.0 != 0.0 {
+        truncated - 1
+    } else {
+        truncated
+    }
+}
@@ -0,0 +1,185 @@
+use crate::component::{Component, Unique};
+use crate::entities::Entities;
+use crate::entity_id::EntityId;
+use crate::sparse_set::SparseSet;
+use crate::views::ViewMut;
+use crate::{track, ShipHashMap};
+
+/// Identity of an entity that outlives its [`EntityId`], like across save games or network sessions.
+///
+/// Generated by [`StableIds`] as a version 4 UUID, or set directly for ids coming from elsewhere.\
+/// Stored as a regular component named `"shipyard::StableId"`, it can be registered in snapshots like any other component.
+///
+/// ### Example
+/// ```
+/// use shipyard::{StableId, World};
+///
+/// let mut world = World::new();
+///
+/// let entity = world.add_entity(());
+/// let stable_id = world.stable_id(entity);
+///
+/// assert_eq!(world.stable_id(entity), stable_id);
+/// assert_eq!(world.entity_of_stable_id(stable_id), Some(entity));
+///
+/// world.delete_entity(entity);
+///
+/// assert_eq!(world.entity_of_stable_id(stable_id), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct StableId(pub u128);
+
+impl Component for StableId {
+    type Tracking = track::Untracked;
+
+    const NAME: Option<&'static str> = Some("shipyard::StableId");
+}
+
+/// Generates [`StableId`]s and maps them back to their entity.
+///
+/// The map is checked against the [`StableId`] storage on each lookup.
+/// Ids added without going through [`StableIds`], like when loading a snapshot, are picked up
+/// the next time a lookup misses and the storage length changed, or after a call to [`StableIds::rebuild`].
+///
+/// Without the `std` feature the generator isn't seeded randomly, [`StableIds::with_seed`] should be used.
+pub struct StableIds {
+    index: ShipHashMap<StableId, EntityId>,
+    state: u64,
+}
+
+impl Unique for StableIds {}
+
+impl Default for StableIds {
+    fn default() -> Self {
+        StableIds::new()
+    }
+}
+
+impl StableIds {
+    /// Creates an empty [`StableIds`], randomly seeded with the `std` feature.
+    pub fn new() -> StableIds {
+        StableIds::with_seed(random_seed())
+    }
+    /// Creates an empty [`StableIds`] generating ids from `seed`.
+    pub fn with_seed(seed: u64) -> StableIds {
+        StableIds {
+            index: ShipHashMap::default(),
+            state: seed,
+        }
+    }
+    /// Generates a new [`StableId`].
+    pub fn generate(&mut self) -> StableId {
+        let value =
+            ((splitmix64(&mut self.state) as u128) << 64) | splitmix64(&mut self.state) as u128;
+
+        // version 4, variant 1
+        let value = (value & !(0xF << 76)) | (0x4 << 76);
+        let value = (value & !(0x3 << 62)) | (0x2 << 62);
+
+        StableId(value)
+    }
+    /// Returns the [`StableId`] of `entity`, generating and adding one if it doesn't have any.
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    #[track_caller]
+    pub fn get_or_insert(
+        &mut self,
+        entity: EntityId,
+        entities: &Entities,
+        stable_ids: &mut ViewMut<'_, StableId>,
+    ) -> StableId {
+        if let Some(&stable_id) = stable_ids.sparse_set.private_get(entity) {
+            return stable_id;
+        }
+
+        let stable_id = self.generate();
+        entities.add_component(entity, &mut *stable_ids, stable_id);
+        self.index.insert(stable_id, entity);
+
+        stable_id
+    }
+    /// Returns the entity with `stable_id`.
+    pub fn entity(
+        &mut self,
+        stable_id: StableId,
+        stable_ids: &SparseSet<StableId>,
+    ) -> Option<EntityId> {
+        if let Some(entity) = self.lookup(stable_id, stable_ids) {
+            return Some(entity);
+        }
+
+        if self.index.len() != stable_ids.len() {
+            self.rebuild(stable_ids);
+
+            return self.lookup(stable_id, stable_ids);
+        }
+
+        None
+    }
+    /// Rebuilds the map from the content of the [`StableId`] storage.
+    pub fn rebuild(&mut self, stable_ids: &SparseSet<StableId>) {
+        self.index.clear();
+        self.index.extend(
+            stable_ids
+                .data
+                .iter()
+                .copied()
+                .zip(stable_ids.dense.iter().copied()),
+        );
+    }
+    /// Looks `stable_id` up, removing the entry if the entity doesn't have this id anymore.
+    fn lookup(
+        &mut self,
+        stable_id: StableId,
+        stable_ids: &SparseSet<StableId>,
+    ) -> Option<EntityId> {
+        let entity = *self.index.get(&stable_id)?;
+
+        if stable_ids.private_get(entity) == Some(&stable_id) {
+            Some(entity)
+        } else {
+            self.index.remove(&stable_id);
+
+            None
+        }
+    }
+}
+
+impl core::fmt::Debug for StableIds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StableIds")
+            .field("len", &self.index.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+fn random_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+
+    if let Ok(time) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        hasher.write_u128(time.as_nanos());
+    }
+
+    hasher.finish()
+}
+
+#[cfg(not(feature = "std"))]
+fn random_seed() -> u64 {
+    0
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    z ^ (z >> 31)
+}
@@ -0,0 +1,125 @@
+use crate::component::Unique;
+use crate::scheduler::{AsLabel, Label};
+use crate::ShipHashMap;
+use alloc::boxed::Box;
+use core::hash::Hash;
+
+/// Associates workloads to the states of a game, like a menu and the gameplay.
+///
+/// Each state can have a main workload, run by [`World::run_default_workload`] while the state is active,
+/// and `on_enter`/`on_exit` workloads run once when switching state with [`World::set_state`].\
+/// All workloads have to be added to the `World` before switching to their state.
+///
+/// ### Example
+/// ```
+/// use shipyard::{StateMachine, Workload, World};
+///
+/// #[derive(PartialEq, Eq, Hash)]
+/// enum GameState {
+///     Menu,
+///     Gameplay,
+/// }
+///
+/// let world = World::new();
+///
+/// Workload::new("menu").with_system(|| {}).add_to_world(&world).unwrap();
+/// Workload::new("gameplay").with_system(|| {}).add_to_world(&world).unwrap();
+/// Workload::new("load_level").with_system(|| {}).add_to_world(&world).unwrap();
+///
+/// world.add_unique(
+///     StateMachine::new()
+///         .with_workload(GameState::Menu, "menu")
+///         .with_workload(GameState::Gameplay, "gameplay")
+///         .with_on_enter(GameState::Gameplay, "load_level"),
+/// );
+///
+/// world.set_state(GameState::Menu).unwrap();
+/// world.run_default_workload().unwrap();
+///
+/// world.set_state(GameState::Gameplay).unwrap();
+/// world.run_default_workload().unwrap();
+/// ```
+///
+/// [`World::run_default_workload`]: crate::World::run_default_workload
+/// [`World::set_state`]: crate::World::set_state
+pub struct StateMachine<S> {
+    current: Option<S>,
+    states: ShipHashMap<S, StateWorkloads>,
+}
+
+#[derive(Default)]
+struct StateWorkloads {
+    workload: Option<Box<dyn Label>>,
+    on_enter: Option<Box<dyn Label>>,
+    on_exit: Option<Box<dyn Label>>,
+}
+
+/// Workloads to run to go from one state to another, returned by [`StateMachine::transition`].
+pub(crate) struct Transition {
+    pub(crate) on_exit: Option<Box<dyn Label>>,
+    pub(crate) workload: Option<Box<dyn Label>>,
+    pub(crate) on_enter: Option<Box<dyn Label>>,
+}
+
+impl<S: Eq + Hash> Default for StateMachine<S> {
+    fn default() -> Self {
+        StateMachine {
+            current: None,
+            states: ShipHashMap::default(),
+        }
+    }
+}
+
+impl<S: Eq + Hash> StateMachine<S> {
+    /// Creates a state machine without any state, the first [`World::set_state`] only runs `on_enter`.
+    ///
+    /// [`World::set_state`]: crate::World::set_state
+    pub fn new() -> StateMachine<S> {
+        StateMachine::default()
+    }
+    /// Makes `workload` the default workload while in `state`.
+    pub fn with_workload<T>(mut self, state: S, workload: impl AsLabel<T>) -> StateMachine<S> {
+        self.states.entry(state).or_default().workload = Some(workload.as_label());
+        self
+    }
+    /// Runs `workload` when switching to `state`.
+    pub fn with_on_enter<T>(mut self, state: S, workload: impl AsLabel<T>) -> StateMachine<S> {
+        self.states.entry(state).or_default().on_enter = Some(workload.as_label());
+        self
+    }
+    /// Runs `workload` when leaving `state`.
+    pub fn with_on_exit<T>(mut self, state: S, workload: impl AsLabel<T>) -> StateMachine<S> {
+        self.states.entry(state).or_default().on_exit = Some(workload.as_label());
+        self
+    }
+    /// Returns the current state, `None` before the first [`World::set_state`].
+    ///
+    /// [`World::set_state`]: crate::World::set_state
+    pub fn state(&self) -> Option<&S> {
+        self.current.as_ref()
+    }
+    /// Returns the workloads involved in switching to `state`.\
+    /// Returns `None` if `state` is already the current state.
+    pub(crate) fn transition(&self, state: &S) -> Option<Transition> {
+        if self.current.as_ref() == Some(state) {
+            return None;
+        }
+
+        let next = self.states.get(state);
+
+        Some(Transition {
+            on_exit: self
+                .current
+                .as_ref()
+                .and_then(|current| self.states.get(current))
+                .and_then(|current| current.on_exit.clone()),
+            workload: next.and_then(|next| next.workload.clone()),
+            on_enter: next.and_then(|next| next.on_enter.clone()),
+        })
+    }
+    pub(crate) fn set_current(&mut self, state: S) {
+        self.current = Some(state);
+    }
+}
+
+impl<S: Eq + Hash + Send + Sync + 'static> Unique for StateMachine<S> {}
@@ -0,0 +1,198 @@
+use crate::all_storages::AllStorages;
+use crate::atomic_refcell::{ARef, ARefMut, AtomicRefCell, SharedBorrow};
+use crate::borrow::Borrow;
+use crate::component::Component;
+use crate::error;
+use crate::sparse_set::SparseSet;
+use crate::storage::{Storage, StorageId};
+use crate::tracking::TrackingTimestamp;
+use crate::views::{View, ViewMut};
+use crate::world::World;
+use core::any::type_name;
+use core::marker::PhantomData;
+
+/// Location of a component storage, borrowable without looking it up in the storage map.
+///
+/// Returned by [`World::storage_handle`] or [`AllStorages::storage_handle`].\
+/// Borrowing a view through a handle skips hashing `TypeId`s, which matters for very small systems or
+/// frequently run [`World::run`] closures.\
+/// A handle is only valid for the `World` that created it, using it with another one falls back to a regular lookup.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, EntitiesViewMut, World};
+///
+/// #[derive(Component)]
+/// struct U32(u32);
+///
+/// let world = World::new();
+/// let u32s_handle = world.storage_handle::<U32>();
+///
+/// {
+///     let mut u32s = u32s_handle.view_mut(&world).unwrap();
+///     let mut entities = world.borrow::<EntitiesViewMut>().unwrap();
+///     entities.add_entity(&mut u32s, U32(0));
+/// }
+///
+/// assert_eq!(u32s_handle.view(&world).unwrap().len(), 1);
+/// ```
+///
+/// [`World::storage_handle`]: crate::World::storage_handle
+/// [`AllStorages::storage_handle`]: crate::AllStorages::storage_handle
+/// [`World::run`]: crate::World::run
+pub struct StorageHandle<T: Component> {
+    all_storages_id: u64,
+    storage: *const AtomicRefCell<dyn Storage>,
+    _phantom: PhantomData<T>,
+}
+
+// SAFE the pointer is only dereferenced when borrowing the `AllStorages` it comes from
+unsafe impl<T: Component + Send + Sync> Send for StorageHandle<T> {}
+unsafe impl<T: Component + Send + Sync> Sync for StorageHandle<T> {}
+
+impl<T: Component> Clone for StorageHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Component> Copy for StorageHandle<T> {}
+
+impl<T: Component> StorageHandle<T> {
+    pub(crate) fn new(
+        all_storages_id: u64,
+        storage: *const AtomicRefCell<dyn Storage>,
+    ) -> StorageHandle<T> {
+        StorageHandle {
+            all_storages_id,
+            storage,
+            _phantom: PhantomData,
+        }
+    }
+    /// Returns the storage if it belongs to `all_storages`.
+    fn get<'a>(&self, all_storages: &'a AllStorages) -> Option<&'a AtomicRefCell<dyn Storage>> {
+        if self.all_storages_id == all_storages.id {
+            // SAFE removing a component storage gives the `AllStorages` a new id
+            // and `all_storages` is borrowed for `'a`
+            Some(unsafe { &*self.storage })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Component + Send + Sync> StorageHandle<T> {
+    /// Borrows the storage, same as `world.borrow::<View<T>>()`.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `T` storage (shared)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `T` storage borrow failed.
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    pub fn view<'w>(&self, world: &'w World) -> Result<View<'w, T>, error::GetStorage> {
+        let current = world.get_current();
+        let all_storages = world
+            .all_storages()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+
+        let (all_storages, all_borrow) = unsafe { ARef::destructure(all_storages) };
+
+        self.borrow(all_storages, Some(all_borrow), None, current)
+    }
+    /// Borrows the storage, same as `world.borrow::<ViewMut<T>>()`.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `T` storage (exclusive)
+    ///
+    /// ### Errors
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `T` storage borrow failed.
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    pub fn view_mut<'w>(&self, world: &'w World) -> Result<ViewMut<'w, T>, error::GetStorage> {
+        let current = world.get_current();
+        let all_storages = world
+            .all_storages()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+
+        let (all_storages, all_borrow) = unsafe { ARef::destructure(all_storages) };
+
+        self.borrow_mut(all_storages, Some(all_borrow), None, current)
+    }
+    fn borrow<'a>(
+        &self,
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        last_run: Option<TrackingTimestamp>,
+        current: TrackingTimestamp,
+    ) -> Result<View<'a, T>, error::GetStorage> {
+        let storage = match self.get(all_storages) {
+            Some(storage) => storage,
+            None => return View::<T>::borrow(all_storages, all_borrow, last_run, current),
+        };
+
+        let storage = storage
+            .borrow()
+            .map_err(|err| error::GetStorage::StorageBorrow {
+                name: Some(type_name::<SparseSet<T>>()),
+                id: StorageId::of::<SparseSet<T>>(),
+                borrow: err,
+            })?;
+        let (sparse_set, borrow) = unsafe {
+            ARef::destructure(ARef::map(storage, |storage| {
+                storage.as_any().downcast_ref::<SparseSet<T>>().unwrap()
+            }))
+        };
+
+        sparse_set.check_tracking::<T::Tracking>()?;
+
+        Ok(View::new(sparse_set, borrow, all_borrow, last_run, current))
+    }
+    fn borrow_mut<'a>(
+        &self,
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        last_run: Option<TrackingTimestamp>,
+        current: TrackingTimestamp,
+    ) -> Result<ViewMut<'a, T>, error::GetStorage> {
+        let storage = match self.get(all_storages) {
+            Some(storage) => storage,
+            None => return ViewMut::<T>::borrow(all_storages, all_borrow, last_run, current),
+        };
+
+        let storage = storage
+            .borrow_mut()
+            .map_err(|err| error::GetStorage::StorageBorrow {
+                name: Some(type_name::<SparseSet<T>>()),
+                id: StorageId::of::<SparseSet<T>>(),
+                borrow: err,
+            })?;
+        let (sparse_set, borrow) = unsafe {
+            ARefMut::destructure(ARefMut::map(storage, |storage| {
+                storage.as_any_mut().downcast_mut::<SparseSet<T>>().unwrap()
+            }))
+        };
+
+        sparse_set.check_tracking::<T::Tracking>()?;
+
+        Ok(ViewMut {
+            last_insertion: last_run.unwrap_or(sparse_set.last_insert),
+            last_modification: last_run.unwrap_or(sparse_set.last_modified),
+            last_removal_or_deletion: last_run.unwrap_or(TrackingTimestamp::origin()),
+            current,
+            sparse_set,
+            borrow,
+            all_borrow,
+            phantom: PhantomData,
+        })
+    }
+}
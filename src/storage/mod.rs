@@ -1,11 +1,14 @@
+mod handle;
 mod sbox;
 mod storage_id;
 
+pub use handle::StorageHandle;
 pub use storage_id::StorageId;
 
-pub(crate) use sbox::SBox;
+pub(crate) use sbox::{cast_mut, cast_ref, SBox};
 
 use crate::all_storages::AllStorages;
+use crate::entities::EntityRemap;
 use crate::entity_id::EntityId;
 use crate::memory_usage::StorageMemoryUsage;
 use crate::sparse_set::SparseArray;
@@ -13,8 +16,11 @@ use crate::tracking::TrackingTimestamp;
 use alloc::borrow::Cow;
 use core::any::Any;
 
+/// Casts any `'static` type to `dyn Any`, it's implemented for all of them.
 pub trait SizedAny {
+    #[allow(missing_docs)]
     fn as_any(&self) -> &dyn Any;
+    #[allow(missing_docs)]
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
@@ -30,6 +36,71 @@ impl<T: 'static> SizedAny for T {
 }
 
 /// Defines common storage operations.
+///
+/// Implementing it allows custom storages to be stored in `AllStorages`, next to [`SparseSet`]s and [`UniqueStorage`]s.\
+/// The hooks are called by `AllStorages` when an entity is deleted, the `World` is cleared or memory usage is requested.\
+/// Custom storages are accessed with [`CustomStorageAccess`], custom views can be built on top by implementing [`Borrow`] and [`BorrowInfo`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{
+///     AllStoragesViewMut, CustomStorageAccess, EntityId, Storage, StorageId, TrackingTimestamp,
+///     World,
+/// };
+///
+/// /// Components stored contiguously, without sparse array.
+/// struct DenseTable<T> {
+///     entities: Vec<EntityId>,
+///     data: Vec<T>,
+/// }
+///
+/// impl<T: 'static> Storage for DenseTable<T> {
+///     fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+///         if let Some(index) = self.entities.iter().position(|&e| e == entity) {
+///             self.entities.swap_remove(index);
+///             self.data.swap_remove(index);
+///         }
+///     }
+///     fn clear(&mut self, _current: TrackingTimestamp) {
+///         self.entities.clear();
+///         self.data.clear();
+///     }
+///     fn is_empty(&self) -> bool {
+///         self.entities.is_empty()
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world
+///     .add_custom_storage(
+///         StorageId::of::<DenseTable<u32>>(),
+///         DenseTable::<u32> {
+///             entities: Vec::new(),
+///             data: Vec::new(),
+///         },
+///     )
+///     .unwrap();
+///
+/// let entity = world.add_entity(());
+///
+/// {
+///     let all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+///     let mut table = all_storages.custom_storage_mut::<DenseTable<u32>>().unwrap();
+///     table.entities.push(entity);
+///     table.data.push(0);
+/// }
+///
+/// world.delete_entity(entity);
+///
+/// let all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+/// assert!(all_storages.custom_storage::<DenseTable<u32>>().unwrap().is_empty());
+/// ```
+///
+/// [`SparseSet`]: crate::sparse_set::SparseSet
+/// [`UniqueStorage`]: crate::UniqueStorage
+/// [`CustomStorageAccess`]: crate::CustomStorageAccess
+/// [`Borrow`]: crate::Borrow
+/// [`BorrowInfo`]: crate::BorrowInfo
 pub trait Storage: SizedAny {
     /// Casts to `&dyn Any`.
     fn any(&self) -> &dyn Any {
@@ -55,6 +126,14 @@ pub trait Storage: SizedAny {
     fn name(&self) -> Cow<'static, str> {
         core::any::type_name::<Self>().into()
     }
+    /// Returns the name of the component stored, for storages holding a single component type.
+    ///
+    /// See [`Component::NAME`].
+    ///
+    /// [`Component::NAME`]: crate::Component::NAME
+    fn component_name(&self) -> Option<&'static str> {
+        None
+    }
     /// Returns a [`SparseSet`]'s internal [`SparseArray`].
     ///
     /// [`SparseSet`]: crate::sparse_set::SparseSet
@@ -66,6 +145,26 @@ pub trait Storage: SizedAny {
     fn is_empty(&self) -> bool {
         false
     }
+    /// Returns the number of components or entities in the storage, `None` if it can't be counted.
+    fn len(&self) -> Option<usize> {
+        None
+    }
+    /// Returns how many times entities or components were added to or removed from the storage, `None` if it isn't counted.\
+    /// Only the difference between two calls is meaningful, the count wraps around.
+    fn structural_changes(&self) -> Option<u64> {
+        None
+    }
+    /// Frees unused memory and improves the layout of this storage.
+    ///
+    /// Called by [`World::compact`].
+    ///
+    /// [`World::compact`]: crate::World::compact
+    fn compact(&mut self) {}
+    /// Replaces the ids of the entities moved by [`World::compact_entities`].
+    ///
+    /// [`World::compact_entities`]: crate::World::compact_entities
+    #[allow(unused_variables)]
+    fn remap_entities(&mut self, remap: &EntityRemap) {}
     /// Clear all deletion and removal tracking data.
     fn clear_all_removed_and_deleted(&mut self) {}
     /// Clear all deletion and removal tracking data older than some timestamp.
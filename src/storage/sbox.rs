@@ -1,13 +1,51 @@
 use crate::atomic_refcell::AtomicRefCell;
-use crate::storage::Storage;
+use crate::error;
+use crate::storage::{Storage, StorageId};
 use alloc::boxed::Box;
 #[cfg(feature = "thread_local")]
 use alloc::sync::Arc;
+use core::alloc::Layout;
+use core::any::type_name;
 
 /// Abstract away `T` from `AtomicRefCell<T>` to be able to store
 /// different types in a `HashMap<TypeId, Storage>`.
-/// and box the `AtomicRefCell` so it doesn't move when the `HashMap` reallocates
-pub(crate) struct SBox(pub(crate) *mut AtomicRefCell<dyn Storage>);
+/// and box the `AtomicRefCell` so it doesn't move when the `HashMap` reallocates.
+///
+/// The storage's type is kept alongside, `StorageId`s are hashes and could collide.
+pub(crate) struct SBox(
+    pub(crate) *mut AtomicRefCell<dyn Storage>,
+    pub(crate) StorageType,
+);
+
+/// Concrete type of a storage, checked before casting it back from `dyn Storage`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct StorageType {
+    type_id: core::any::TypeId,
+    name: &'static str,
+    layout: Layout,
+}
+
+impl StorageType {
+    #[inline]
+    fn of<T: 'static>() -> StorageType {
+        StorageType {
+            type_id: core::any::TypeId::of::<T>(),
+            name: type_name::<T>(),
+            layout: Layout::new::<T>(),
+        }
+    }
+    /// Returns `true` if the storage is a `T`.
+    #[inline]
+    fn is<T: 'static>(&self) -> bool {
+        self.type_id == core::any::TypeId::of::<T>()
+    }
+    /// Returns `true` if the storage is a `T` or has the same name and layout,
+    /// the same type can have a different `TypeId` in each dynamic library.
+    #[inline]
+    fn is_like<T: 'static>(&self) -> bool {
+        self.is::<T>() || self.name == type_name::<T>() && self.layout == Layout::new::<T>()
+    }
+}
 
 #[cfg(not(feature = "thread_local"))]
 unsafe impl Send for SBox {}
@@ -26,7 +64,10 @@ impl Drop for SBox {
 impl SBox {
     #[inline]
     pub(crate) fn new<T: Storage + Send + Sync + 'static>(value: T) -> Self {
-        SBox(Box::into_raw(Box::new(AtomicRefCell::new(value))))
+        SBox(
+            Box::into_raw(Box::new(AtomicRefCell::new(value))),
+            StorageType::of::<T>(),
+        )
     }
     #[cfg(feature = "thread_local")]
     #[inline]
@@ -34,14 +75,18 @@ impl SBox {
         value: T,
         thread_id: Arc<dyn Fn() -> u64 + Send + Sync>,
     ) -> Self {
-        SBox(Box::into_raw(Box::new(AtomicRefCell::new_non_send(
-            value, thread_id,
-        ))))
+        SBox(
+            Box::into_raw(Box::new(AtomicRefCell::new_non_send(value, thread_id))),
+            StorageType::of::<T>(),
+        )
     }
     #[cfg(feature = "thread_local")]
     #[inline]
     pub(crate) fn new_non_sync<T: Storage + Send + 'static>(value: T) -> Self {
-        SBox(Box::into_raw(Box::new(AtomicRefCell::new_non_sync(value))))
+        SBox(
+            Box::into_raw(Box::new(AtomicRefCell::new_non_sync(value))),
+            StorageType::of::<T>(),
+        )
     }
     #[cfg(feature = "thread_local")]
     #[inline]
@@ -49,10 +94,59 @@ impl SBox {
         value: T,
         thread_id: Arc<dyn Fn() -> u64 + Send + Sync>,
     ) -> Self {
-        SBox(Box::into_raw(Box::new(AtomicRefCell::new_non_send_sync(
-            value, thread_id,
-        ))))
+        SBox(
+            Box::into_raw(Box::new(AtomicRefCell::new_non_send_sync(value, thread_id))),
+            StorageType::of::<T>(),
+        )
     }
+    /// Returns an error if the storage isn't a `T`.
+    #[inline]
+    pub(crate) fn check<T: 'static>(&self, storage_id: StorageId) -> Result<(), error::GetStorage> {
+        if self.1.is::<T>() {
+            Ok(())
+        } else {
+            Err(self.collision::<T>(storage_id))
+        }
+    }
+    /// Returns an error if the storage isn't a `T` and doesn't have the same name and layout.
+    #[inline]
+    pub(crate) fn check_like<T: 'static>(
+        &self,
+        storage_id: StorageId,
+    ) -> Result<(), error::GetStorage> {
+        if self.1.is_like::<T>() {
+            Ok(())
+        } else {
+            Err(self.collision::<T>(storage_id))
+        }
+    }
+    fn collision<T: 'static>(&self, storage_id: StorageId) -> error::GetStorage {
+        error::GetStorage::StorageIdCollision {
+            name: type_name::<T>(),
+            id: storage_id,
+            stored: self.1.name,
+        }
+    }
+}
+
+/// Casts `storage` to its concrete type.
+///
+/// ### Safety
+///
+/// `storage` has to be a `T`, [`SBox::check_like`] alone doesn't guarantee it.
+#[inline]
+pub(crate) unsafe fn cast_ref<T: 'static>(storage: &dyn Storage) -> &T {
+    &*(storage as *const dyn Storage as *const T)
+}
+
+/// Casts `storage` to its concrete type.
+///
+/// ### Safety
+///
+/// `storage` has to be a `T`, [`SBox::check_like`] alone doesn't guarantee it.
+#[inline]
+pub(crate) unsafe fn cast_mut<T: 'static>(storage: &mut dyn Storage) -> &mut T {
+    &mut *(storage as *mut dyn Storage as *mut T)
 }
 
 impl core::fmt::Debug for SBox {
@@ -1,5 +1,7 @@
 use crate::type_id::TypeId;
 use core::cmp::Ordering;
+use core::hash::Hasher;
+use siphasher::sip::SipHasher;
 
 /// Id of a storage, can be a `TypeId` or `u64`.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -16,6 +18,36 @@ impl StorageId {
     pub fn of<T: 'static>() -> Self {
         TypeId::of::<T>().into()
     }
+    /// Returns a custom `StorageId` derived from `name`, identical in every build and every binary.
+    ///
+    /// A type can have a different `TypeId` in each dynamic library, storages shared between them
+    /// can use this id and be accessed with [`AllStorages::foreign_storage`].
+    ///
+    /// [`AllStorages::foreign_storage`]: crate::AllStorages::foreign_storage
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, CustomStorageAccess, SparseSet, StorageId, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Score(u32);
+    ///
+    /// let world = World::new();
+    /// let storage_id = StorageId::from_name("my_game::Score");
+    ///
+    /// world
+    ///     .add_custom_storage(storage_id, SparseSet::<Score>::new_custom_storage())
+    ///     .unwrap();
+    ///
+    /// let all_storages = world.all_storages().unwrap();
+    /// assert!(all_storages.custom_storage_by_id(storage_id).is_ok());
+    /// ```
+    pub fn from_name(name: &str) -> Self {
+        let mut hasher = SipHasher::new();
+        hasher.write(name.as_bytes());
+
+        StorageId::Custom(hasher.finish())
+    }
 }
 
 impl From<TypeId> for StorageId {
@@ -0,0 +1,138 @@
+use crate::all_storages::AllStorages;
+use crate::storage::StorageId;
+use crate::world::World;
+use crate::ShipHashMap;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+/// Panics when dropped if entities or components were added or removed since its creation.\
+/// Only checks in debug builds, does nothing in release.
+///
+/// Storages borrowed exclusively when the guard is created or dropped are not checked.
+///
+/// ### Example
+/// ```
+/// use shipyard::{AllStoragesViewMut, Component, IntoIter, View, World};
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let world = World::new();
+/// let mut all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+/// all_storages.add_entity(Health(10));
+///
+/// let guard = all_storages.structural_change_guard();
+/// let total = all_storages
+///     .borrow::<View<Health>>()
+///     .unwrap()
+///     .iter()
+///     .map(|health| health.0)
+///     .sum::<u32>();
+/// drop(guard);
+///
+/// assert_eq!(total, 10);
+/// ```
+pub struct StructuralChangeGuard<'a> {
+    all_storages: &'a AllStorages,
+    changes: ShipHashMap<StorageId, u64>,
+}
+
+impl AllStorages {
+    /// Returns a guard panicking when dropped if entities or components were added or removed in the meantime.\
+    /// See [`StructuralChangeGuard`].
+    pub fn structural_change_guard(&self) -> StructuralChangeGuard<'_> {
+        let changes = if cfg!(debug_assertions) {
+            structural_changes(self)
+                .into_iter()
+                .map(|(storage_id, _, changes)| (storage_id, changes))
+                .collect()
+        } else {
+            ShipHashMap::default()
+        };
+
+        StructuralChangeGuard {
+            all_storages: self,
+            changes,
+        }
+    }
+}
+
+impl World {
+    /// Runs `f` and panics if entities or components were added or removed during its execution.\
+    /// Only checks in debug builds, see [`StructuralChangeGuard`].
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, IntoIter, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.add_entity(Health(10));
+    ///
+    /// world.assert_no_structural_changes(|| {
+    ///     world.run(|mut healths: ViewMut<Health>| {
+    ///         for health in (&mut healths).iter() {
+    ///             health.0 += 1;
+    ///         }
+    ///     });
+    /// });
+    /// ```
+    #[track_caller]
+    pub fn assert_no_structural_changes<R>(&self, f: impl FnOnce() -> R) -> R {
+        let all_storages = self.all_storages.borrow().unwrap();
+        let guard = all_storages.structural_change_guard();
+
+        let result = f();
+
+        drop(guard);
+
+        result
+    }
+}
+
+impl Drop for StructuralChangeGuard<'_> {
+    fn drop(&mut self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+
+        let changed = structural_changes(self.all_storages)
+            .into_iter()
+            .filter(|(storage_id, _, changes)| {
+                self.changes
+                    .get(storage_id)
+                    .is_some_and(|before| before != changes)
+            })
+            .map(|(_, name, _)| name)
+            .collect::<Vec<_>>();
+
+        assert!(
+            changed.is_empty(),
+            "entities or components were added or removed while guarded: {:?}",
+            changed
+        );
+    }
+}
+
+/// Returns the structural change count of each storage counting them and not borrowed exclusively.
+fn structural_changes(all_storages: &AllStorages) -> Vec<(StorageId, Cow<'static, str>, u64)> {
+    all_storages
+        .storages
+        .read_all()
+        .iter()
+        .flat_map(|shard| shard.iter())
+        .filter_map(|(storage_id, storage)| {
+            let storage = unsafe { &*storage.0 }.borrow().ok()?;
+            let changes = storage.structural_changes()?;
+
+            Some((*storage_id, storage.name(), changes))
+        })
+        .collect()
+}
@@ -0,0 +1,232 @@
+use crate::error;
+use crate::snapshot::{SnapshotRegistry, WorldDiff};
+use crate::world::World;
+use crate::{EntityId, ShipHashSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use std::io;
+use std::path::Path;
+
+/// Expected entities and components of a `World`, compared by [`assert_world_matches!`].
+///
+/// Only the components in the registry are compared, in their serialized form.
+///
+/// ### Example
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use shipyard::testing::WorldState;
+/// use shipyard::{assert_world_matches, Component, SnapshotRegistry, World};
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Health(u32);
+///
+/// let mut registry = SnapshotRegistry::new();
+/// registry.register::<Health>();
+///
+/// let mut expected = World::new();
+/// expected.add_entity((Health(5),));
+/// let expected = WorldState::of(&mut expected, &registry);
+///
+/// let mut world = World::new();
+/// let entity = world.add_entity((Health(10),));
+/// world.get::<&mut Health>(entity).unwrap().0 -= 5;
+///
+/// assert_world_matches!(world, expected);
+/// ```
+pub struct WorldState<'r> {
+    snapshot: Vec<u8>,
+    registry: &'r SnapshotRegistry,
+}
+
+impl<'r> WorldState<'r> {
+    /// Saves the current state of `world`.
+    pub fn of(world: &mut World, registry: &'r SnapshotRegistry) -> WorldState<'r> {
+        WorldState {
+            snapshot: world.snapshot(registry),
+            registry,
+        }
+    }
+    /// Uses a snapshot saved by [`World::snapshot`] or [`WorldState::as_bytes`] as expected state.
+    pub fn from_bytes(snapshot: Vec<u8>, registry: &'r SnapshotRegistry) -> WorldState<'r> {
+        WorldState { snapshot, registry }
+    }
+    /// Reads a golden file written by [`WorldState::write_file`].
+    ///
+    /// ### Errors
+    ///
+    /// - The file couldn't be read.
+    pub fn from_file(
+        path: impl AsRef<Path>,
+        registry: &'r SnapshotRegistry,
+    ) -> io::Result<WorldState<'r>> {
+        Ok(WorldState::from_bytes(std::fs::read(path)?, registry))
+    }
+    /// Writes this state as a golden file, it can be read back with [`WorldState::from_file`].
+    ///
+    /// ### Errors
+    ///
+    /// - The file couldn't be written.
+    pub fn write_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, &self.snapshot)
+    }
+    /// Returns the saved snapshot.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.snapshot
+    }
+    /// Compares `world` against this state.\
+    /// Returns `None` if they match.
+    ///
+    /// ### Errors
+    ///
+    /// - The expected snapshot isn't valid.
+    pub fn mismatch(&self, world: &mut World) -> Result<Option<WorldMismatch>, error::Snapshot> {
+        let actual = world.snapshot(self.registry);
+
+        let to_expected = WorldDiff::between(&actual, &self.snapshot)?;
+        if to_expected.is_empty() {
+            return Ok(None);
+        }
+
+        let from_expected = WorldDiff::between(&self.snapshot, &actual)?;
+        let missing: ShipHashSet<(&str, EntityId)> = from_expected.removed_components().collect();
+        // components of entities reported whole aren't listed
+        let reported: ShipHashSet<EntityId> = to_expected
+            .added_entities()
+            .iter()
+            .chain(to_expected.removed_entities())
+            .copied()
+            .collect();
+
+        let mut mismatch = WorldMismatch {
+            missing_entities: to_expected.added_entities().to_vec(),
+            unexpected_entities: to_expected.removed_entities().to_vec(),
+            components: Vec::new(),
+        };
+
+        for (name, entity) in to_expected.changed_components() {
+            if reported.contains(&entity) {
+                continue;
+            }
+
+            let kind = if missing.contains(&(name, entity)) {
+                ComponentMismatch::Missing
+            } else {
+                ComponentMismatch::Differs
+            };
+
+            mismatch.components.push((entity, String::from(name), kind));
+        }
+        for (name, entity) in to_expected.removed_components() {
+            if reported.contains(&entity) {
+                continue;
+            }
+
+            mismatch
+                .components
+                .push((entity, String::from(name), ComponentMismatch::Unexpected));
+        }
+
+        mismatch
+            .components
+            .sort_unstable_by(|(entity0, name0, _), (entity1, name1, _)| {
+                (entity0.index(), name0).cmp(&(entity1.index(), name1))
+            });
+
+        Ok(Some(mismatch))
+    }
+}
+
+/// Differences between a `World` and a [`WorldState`], displayed one per line.
+#[derive(Debug)]
+pub struct WorldMismatch {
+    missing_entities: Vec<EntityId>,
+    unexpected_entities: Vec<EntityId>,
+    components: Vec<(EntityId, String, ComponentMismatch)>,
+}
+
+#[derive(Debug)]
+enum ComponentMismatch {
+    Missing,
+    Unexpected,
+    Differs,
+}
+
+impl WorldMismatch {
+    /// Returns the entities of the expected state that aren't alive in the `World`.
+    pub fn missing_entities(&self) -> &[EntityId] {
+        &self.missing_entities
+    }
+    /// Returns the entities alive in the `World` that aren't part of the expected state.
+    pub fn unexpected_entities(&self) -> &[EntityId] {
+        &self.unexpected_entities
+    }
+}
+
+impl fmt::Display for WorldMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entity in &self.missing_entities {
+            writeln!(f, "- {:?} is missing", entity)?;
+        }
+        for entity in &self.unexpected_entities {
+            writeln!(f, "+ {:?} is unexpected", entity)?;
+        }
+        for (entity, name, kind) in &self.components {
+            match kind {
+                ComponentMismatch::Missing => writeln!(f, "- {:?} is missing {}", entity, name)?,
+                ComponentMismatch::Unexpected => {
+                    writeln!(f, "+ {:?} has an unexpected {}", entity, name)?
+                }
+                ComponentMismatch::Differs => {
+                    writeln!(f, "~ {:?} has a different {}", entity, name)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Panics with a readable diff if `world` doesn't match `expected`.\
+/// Prefer the [`assert_world_matches!`] macro.
+#[track_caller]
+pub fn assert_world_matches(world: &mut World, expected: &WorldState<'_>) {
+    match expected.mismatch(world) {
+        Ok(None) => {}
+        Ok(Some(mismatch)) => panic!("World doesn't match the expected state:\n{}", mismatch),
+        Err(err) => panic!("Couldn't compare the World: {:?}", err),
+    }
+}
+
+/// Asserts that a `World` matches a [`WorldState`], printing the entities and components that differ.
+///
+/// `$world` is borrowed mutably to take a snapshot.
+///
+/// ### Example
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use shipyard::testing::WorldState;
+/// use shipyard::{assert_world_matches, Component, SnapshotRegistry, World};
+///
+/// #[derive(Component, Serialize, Deserialize)]
+/// struct Health(u32);
+///
+/// let mut registry = SnapshotRegistry::new();
+/// registry.register::<Health>();
+///
+/// let mut world = World::new();
+/// world.add_entity((Health(10),));
+///
+/// // golden file, written once with `expected.write_file`
+/// let expected = WorldState::of(&mut world, &registry);
+///
+/// assert_world_matches!(world, expected);
+/// ```
+#[macro_export]
+macro_rules! assert_world_matches {
+    ($world: expr, $expected: expr $(,)?) => {
+        $crate::testing::assert_world_matches(&mut $world, &$expected)
+    };
+}
+
+pub use crate::assert_world_matches;
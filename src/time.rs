@@ -0,0 +1,256 @@
+use crate::component::Unique;
+use crate::views::UniqueView;
+#[cfg(feature = "std")]
+use crate::views::UniqueViewMut;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Frame and fixed step timing, in seconds.
+///
+/// [`Time::advance`] starts a new frame, with the **std** feature [`update_time`] does it with the real elapsed time.\
+/// Each frame's delta is added to an accumulator, spent in steps of [`Time::fixed_step`] by [`World::run_fixed_workload`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{Time, UniqueViewMut, World};
+///
+/// let mut world = World::new();
+/// world.add_unique(Time::new(0.25));
+///
+/// world.run(|mut time: UniqueViewMut<Time>| {
+///     time.advance(0.6);
+///
+///     assert_eq!(time.frame_count(), 1);
+///     assert!(time.consume_fixed_step());
+///     assert!(time.consume_fixed_step());
+///     assert!(!time.consume_fixed_step());
+///     assert_eq!(time.fixed_step_count(), 2);
+/// });
+/// ```
+///
+/// [`World::run_fixed_workload`]: crate::World::run_fixed_workload
+#[derive(Clone, Debug)]
+pub struct Time {
+    delta: f64,
+    elapsed: f64,
+    frame_count: u64,
+    fixed_step: f64,
+    accumulator: f64,
+    fixed_step_count: u64,
+    max_fixed_steps: u32,
+    frame_fixed_steps: u32,
+    #[cfg(feature = "std")]
+    last_update: Option<std::time::Instant>,
+}
+
+impl Time {
+    /// Creates a `Time` spending its accumulator in steps of `fixed_step` seconds.\
+    /// At most 8 fixed steps are run per frame, see [`Time::set_max_fixed_steps`].
+    ///
+    /// ### Panics
+    ///
+    /// - `fixed_step` isn't strictly positive.
+    #[track_caller]
+    pub fn new(fixed_step: f64) -> Time {
+        assert!(fixed_step > 0.0, "Fixed step has to be strictly positive.");
+
+        Time {
+            delta: 0.0,
+            elapsed: 0.0,
+            frame_count: 0,
+            fixed_step,
+            accumulator: 0.0,
+            fixed_step_count: 0,
+            max_fixed_steps: 8,
+            frame_fixed_steps: 0,
+            #[cfg(feature = "std")]
+            last_update: None,
+        }
+    }
+    /// Starts a new frame that lasted `delta` seconds.
+    pub fn advance(&mut self, delta: f64) {
+        self.delta = delta;
+        self.elapsed += delta;
+        self.frame_count += 1;
+        self.accumulator += delta;
+        self.frame_fixed_steps = 0;
+    }
+    /// Starts a new frame using the real time elapsed since the last call.\
+    /// The first call starts a frame of zero seconds.
+    #[cfg(feature = "std")]
+    pub fn update(&mut self) {
+        let now = std::time::Instant::now();
+        let delta = self
+            .last_update
+            .map_or(0.0, |last_update| (now - last_update).as_secs_f64());
+
+        self.last_update = Some(now);
+        self.advance(delta);
+    }
+    /// Spends one fixed step from the accumulator, returns `false` if there isn't enough time accumulated.\
+    /// When the maximum number of fixed steps for this frame is reached, the accumulator is emptied instead.
+    pub fn consume_fixed_step(&mut self) -> bool {
+        if self.accumulator < self.fixed_step {
+            return false;
+        }
+
+        if self.frame_fixed_steps >= self.max_fixed_steps {
+            self.accumulator %= self.fixed_step;
+
+            return false;
+        }
+
+        self.accumulator -= self.fixed_step;
+        self.fixed_step_count += 1;
+        self.frame_fixed_steps += 1;
+
+        true
+    }
+    /// Returns the duration of the current frame.
+    pub fn delta(&self) -> f64 {
+        self.delta
+    }
+    /// Returns the sum of all frame durations.
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+    /// Returns the number of frames started.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+    /// Returns the duration of a fixed step.
+    pub fn fixed_step(&self) -> f64 {
+        self.fixed_step
+    }
+    /// Sets the duration of a fixed step.
+    ///
+    /// ### Panics
+    ///
+    /// - `fixed_step` isn't strictly positive.
+    #[track_caller]
+    pub fn set_fixed_step(&mut self, fixed_step: f64) {
+        assert!(fixed_step > 0.0, "Fixed step has to be strictly positive.");
+
+        self.fixed_step = fixed_step;
+    }
+    /// Returns the time accumulated but not spent in fixed steps yet.
+    pub fn accumulator(&self) -> f64 {
+        self.accumulator
+    }
+    /// Returns how far the accumulator is into the next fixed step, in `[0, 1)`.\
+    /// Useful to interpolate between the last two fixed steps when rendering.
+    pub fn fixed_alpha(&self) -> f64 {
+        self.accumulator / self.fixed_step
+    }
+    /// Returns the number of fixed steps spent.
+    pub fn fixed_step_count(&self) -> u64 {
+        self.fixed_step_count
+    }
+    /// Sets the maximum number of fixed steps run in a single frame.\
+    /// Prevents a slow frame to make the next one even slower.
+    pub fn set_max_fixed_steps(&mut self, max_fixed_steps: u32) {
+        self.max_fixed_steps = max_fixed_steps;
+    }
+}
+
+impl Unique for Time {}
+
+/// System starting a new frame of [`Time`] using the real time elapsed since its last run.
+///
+/// ### Panics
+///
+/// - [`Time`] isn't present in the `World`.
+#[cfg(feature = "std")]
+pub fn update_time(mut time: UniqueViewMut<'_, Time>) {
+    time.update();
+}
+
+/// Run condition returning `true` once every `n` runs, starting with the `n`th.
+///
+/// The counter is kept inside the condition, shared by its clones.
+///
+/// ### Panics
+///
+/// - `n` is zero.
+///
+/// ### Example
+/// ```
+/// use shipyard::{every_n_frames, SystemModificator, Unique, UniqueViewMut, Workload, World};
+///
+/// #[derive(Unique)]
+/// struct Saves(u32);
+///
+/// fn autosave(mut saves: UniqueViewMut<Saves>) {
+///     saves.0 += 1;
+/// }
+///
+/// let world = World::new();
+/// world.add_unique(Saves(0));
+///
+/// Workload::new("frame")
+///     .with_system(autosave.run_if(every_n_frames(3)))
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// for _ in 0..7 {
+///     world.run_default_workload().unwrap();
+/// }
+///
+/// assert_eq!(world.borrow::<UniqueViewMut<Saves>>().unwrap().0, 2);
+/// ```
+#[track_caller]
+pub fn every_n_frames(n: u64) -> impl Fn() -> bool + Clone + Send + Sync + 'static {
+    assert!(n != 0, "Run condition can't trigger every 0 frames.");
+
+    let frames = Arc::new(AtomicU64::new(0));
+
+    move || {
+        if frames.fetch_add(1, Ordering::Relaxed) + 1 >= n {
+            frames.store(0, Ordering::Relaxed);
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Run condition returning `true` when at least `seconds` of [`Time::elapsed`] passed since it last did.\
+/// The first time is after `seconds`.
+///
+/// The last trigger time is kept inside the condition, shared by its clones.
+///
+/// ### Errors
+///
+/// - [`Time`] isn't present in the `World`.
+///
+/// ### Example
+/// ```
+/// use shipyard::{every_duration, Time, UniqueViewMut, WorkloadModificator, Workload, World};
+///
+/// let world = World::new();
+/// world.add_unique(Time::new(1.0 / 60.0));
+///
+/// Workload::new("slow_ai")
+///     .with_system(|| {})
+///     .run_if(every_duration(0.5))
+///     .add_to_world(&world)
+///     .unwrap();
+/// ```
+pub fn every_duration(
+    seconds: f64,
+) -> impl Fn(UniqueView<'_, Time>) -> bool + Clone + Send + Sync + 'static {
+    let last = Arc::new(AtomicU64::new(0.0f64.to_bits()));
+
+    move |time: UniqueView<'_, Time>| {
+        let elapsed = time.elapsed();
+
+        if elapsed - f64::from_bits(last.load(Ordering::Relaxed)) >= seconds {
+            last.store(elapsed.to_bits(), Ordering::Relaxed);
+
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::get::Get;
+use crate::hierarchy::{Children, Parent};
+use crate::iter::{IntoIter, IntoWithId};
+use crate::track;
+use crate::views::{EntitiesView, View, ViewMut};
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+/// Transformation that can be propagated down a hierarchy by [`propagate_transforms`].
+///
+/// Implement it for your matrix, isometry,... type.
+pub trait Transform: Clone + Send + Sync + 'static {
+    /// Returns `child`'s transformation combined with its parent's, `self`.
+    fn compose(&self, child: &Self) -> Self;
+}
+
+/// Transformation of an entity relative to its [`Parent`].
+///
+/// Entities without a parent, or whose parent doesn't have a `LocalTransform`, are roots:
+/// their [`GlobalTransform`] is equal to their `LocalTransform`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct LocalTransform<T>(pub T);
+
+impl<T: Transform> Component for LocalTransform<T> {
+    type Tracking = track::InsertionAndModification;
+}
+
+/// Transformation of an entity in world space.
+///
+/// Added, updated and removed by [`propagate_transforms`], it can't be modified manually.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GlobalTransform<T> {
+    value: T,
+    parent: Option<EntityId>,
+}
+
+impl<T> GlobalTransform<T> {
+    /// Returns the world space transformation.
+    #[inline]
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Deref for GlobalTransform<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Transform> Component for GlobalTransform<T> {
+    type Tracking = track::InsertionAndModification;
+}
+
+/// Updates the [`GlobalTransform`] of all entities with a [`LocalTransform`].
+///
+/// Only entities whose `LocalTransform` was inserted or modified, whose parent changed or
+/// whose parent's `GlobalTransform` was updated are recomputed.\
+/// Entities that lost their `LocalTransform` also lose their `GlobalTransform`.
+///
+/// Modification tracking follows the usual rules, inside a workload changes since the last run are considered.
+/// Outside of workloads [`ViewMut::clear_all_inserted_and_modified`] has to be called on `LocalTransform`.
+///
+/// ### Example
+/// ```
+/// use shipyard::{
+///     propagate_transforms, Children, EntitiesViewMut, GlobalTransform, Hierarchy, LocalTransform,
+///     Parent, Transform, View, ViewMut, Workload, World,
+/// };
+///
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// struct Offset(i32);
+///
+/// impl Transform for Offset {
+///     fn compose(&self, child: &Offset) -> Offset {
+///         Offset(self.0 + child.0)
+///     }
+/// }
+///
+/// let world = World::new();
+///
+/// Workload::new("transforms")
+///     .with_system(propagate_transforms::<Offset>)
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// let child = world.run(
+///     |mut hierarchy: (EntitiesViewMut, ViewMut<Parent>, ViewMut<Children>),
+///      mut locals: ViewMut<LocalTransform<Offset>>| {
+///         let root = hierarchy.0.add_entity(&mut locals, LocalTransform(Offset(10)));
+///         let child = hierarchy.attach_new(root);
+///         hierarchy.0.add_component(child, &mut locals, LocalTransform(Offset(1)));
+///
+///         child
+///     },
+/// );
+///
+/// world.run_workload("transforms").unwrap();
+///
+/// world.run(|globals: View<GlobalTransform<Offset>>| {
+///     assert_eq!(*globals[child], Offset(11));
+/// });
+/// ```
+///
+/// [`ViewMut::clear_all_inserted_and_modified`]: crate::ViewMut::clear_all_inserted_and_modified
+pub fn propagate_transforms<T: Transform>(
+    entities: EntitiesView<'_>,
+    locals: View<'_, LocalTransform<T>>,
+    parents: View<'_, Parent>,
+    children: View<'_, Children>,
+    mut globals: ViewMut<'_, GlobalTransform<T>>,
+) {
+    let transform_parent = |entity: EntityId| {
+        (&parents)
+            .get(entity)
+            .ok()
+            .map(Parent::get)
+            .filter(|&parent| locals.contains(parent))
+    };
+
+    let mut stack: Vec<(EntityId, bool)> = (&locals)
+        .iter()
+        .with_id()
+        .filter(|&(entity, _)| transform_parent(entity).is_none())
+        .map(|(entity, _)| (entity, false))
+        .collect();
+
+    while let Some((entity, parent_updated)) = stack.pop() {
+        let parent = transform_parent(entity);
+
+        let outdated = parent_updated
+            || locals.is_inserted_or_modified(entity)
+            || (&globals)
+                .get(entity)
+                .map_or(true, |global| global.parent != parent);
+
+        if outdated {
+            let local = &locals[entity].0;
+            let value = match parent {
+                Some(parent) => globals[parent].value.compose(local),
+                None => local.clone(),
+            };
+
+            match (&mut globals).get(entity) {
+                Ok(mut global) => {
+                    global.value = value;
+                    global.parent = parent;
+                }
+                Err(_) => {
+                    entities.add_component(entity, &mut globals, GlobalTransform { value, parent })
+                }
+            }
+        }
+
+        if let Ok(entity_children) = (&children).get(entity) {
+            stack.extend(
+                entity_children
+                    .iter()
+                    .filter(|&&child| locals.contains(child))
+                    .map(|&child| (child, outdated)),
+            );
+        }
+    }
+
+    let stale: Vec<EntityId> = (&globals)
+        .iter()
+        .with_id()
+        .filter(|&(entity, _)| !locals.contains(entity))
+        .map(|(entity, _)| entity)
+        .collect();
+
+    for entity in stale {
+        globals.remove(entity);
+    }
+}
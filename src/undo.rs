@@ -0,0 +1,376 @@
+use crate::all_storages::AllStorages;
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::sparse_set::SparseSet;
+use crate::storage::StorageId;
+use crate::ShipHashMap;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::marker::PhantomData;
+
+type BoxedComponent = Box<dyn Any + Send + Sync>;
+
+/// Type erased access to a registered component.
+trait UndoCodec: Send + Sync {
+    fn get(&self, all_storages: &mut AllStorages, entity: EntityId) -> Option<BoxedComponent>;
+    fn insert(&self, all_storages: &mut AllStorages, entity: EntityId, component: BoxedComponent);
+    fn delete(&self, all_storages: &mut AllStorages, entity: EntityId);
+}
+
+struct Codec<T>(PhantomData<fn() -> T>);
+
+impl<T: Component + Clone + Send + Sync> UndoCodec for Codec<T> {
+    fn get(&self, all_storages: &mut AllStorages, entity: EntityId) -> Option<BoxedComponent> {
+        all_storages
+            .exclusive_storage_mut::<SparseSet<T>>()
+            .ok()?
+            .private_get(entity)
+            .map(|component| -> BoxedComponent { Box::new(component.clone()) })
+    }
+    fn insert(&self, all_storages: &mut AllStorages, entity: EntityId, component: BoxedComponent) {
+        if let Ok(component) = component.downcast::<T>() {
+            if all_storages.is_entity_alive(entity) {
+                all_storages.add_component(entity, *component);
+            }
+        }
+    }
+    fn delete(&self, all_storages: &mut AllStorages, entity: EntityId) {
+        all_storages.delete_component::<T>(entity);
+    }
+}
+
+/// List of components whose values are restored by [`AllStorages::undo`] and [`AllStorages::redo`].
+///
+/// Components are cloned when they're replaced, deleted or removed.\
+/// Entities created or deleted are always part of the history, but only registered components are restored with them.
+///
+/// [`AllStorages::undo`]: crate::AllStorages::undo()
+/// [`AllStorages::redo`]: crate::AllStorages::redo()
+#[derive(Clone, Default)]
+pub struct UndoRegistry {
+    codecs: ShipHashMap<StorageId, Arc<dyn UndoCodec>>,
+}
+
+impl UndoRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> UndoRegistry {
+        UndoRegistry::default()
+    }
+    /// Registers `T`, its values will be restored when undoing or redoing.
+    pub fn register<T: Component + Clone + Send + Sync>(&mut self) -> &mut UndoRegistry {
+        self.codecs.insert(
+            StorageId::of::<SparseSet<T>>(),
+            Arc::new(Codec::<T>(PhantomData)),
+        );
+
+        self
+    }
+    /// Returns `true` if `T` was registered.
+    pub fn is_registered<T: Component>(&self) -> bool {
+        self.codecs.contains_key(&StorageId::of::<SparseSet<T>>())
+    }
+}
+
+/// Operation recorded by the journal, with what's needed to revert it.
+enum UndoOp {
+    AddEntity(EntityId),
+    DeleteEntity {
+        entity: EntityId,
+        components: Vec<(StorageId, BoxedComponent)>,
+    },
+    /// A component was added, replaced, deleted or removed, `old` is its previous value.
+    SetComponent {
+        entity: EntityId,
+        storage_id: StorageId,
+        old: Option<BoxedComponent>,
+    },
+}
+
+/// Operations undone or redone together.
+struct UndoStep {
+    name: Option<Cow<'static, str>>,
+    ops: Vec<UndoOp>,
+}
+
+/// Undo history of an `AllStorages`.
+pub(crate) struct UndoJournal {
+    registry: UndoRegistry,
+    /// Operations not yet part of a step.
+    pending: Vec<UndoOp>,
+    pending_name: Option<Cow<'static, str>>,
+    /// Number of transactions in progress.
+    depth: u32,
+    /// Set while a step is reverted, its operations are captured in `pending` to build the opposite step.
+    is_reverting: bool,
+    undo_steps: Vec<UndoStep>,
+    redo_steps: Vec<UndoStep>,
+}
+
+impl UndoJournal {
+    pub(crate) fn new(registry: UndoRegistry) -> UndoJournal {
+        UndoJournal {
+            registry,
+            pending: Vec::new(),
+            pending_name: None,
+            depth: 0,
+            is_reverting: false,
+            undo_steps: Vec::new(),
+            redo_steps: Vec::new(),
+        }
+    }
+    fn push(&mut self, op: UndoOp) {
+        if !self.is_reverting {
+            self.redo_steps.clear();
+        }
+
+        self.pending.push(op);
+    }
+    pub(crate) fn push_add_entity(&mut self, entity: EntityId) {
+        self.push(UndoOp::AddEntity(entity));
+    }
+    /// Turns pending operations into a step.
+    fn commit(&mut self) {
+        let name = self.pending_name.take();
+
+        if !self.pending.is_empty() {
+            let ops = core::mem::take(&mut self.pending);
+
+            self.undo_steps.push(UndoStep { name, ops });
+        }
+    }
+    pub(crate) fn begin_transaction(&mut self, name: Cow<'static, str>) {
+        if self.depth == 0 {
+            self.commit();
+            self.pending_name = Some(name);
+        }
+
+        self.depth += 1;
+    }
+    pub(crate) fn end_transaction(&mut self) {
+        if self.depth > 0 {
+            self.depth -= 1;
+
+            if self.depth == 0 {
+                self.commit();
+            }
+        }
+    }
+    pub(crate) fn can_undo(&self) -> bool {
+        self.depth == 0 && (!self.pending.is_empty() || !self.undo_steps.is_empty())
+    }
+    pub(crate) fn can_redo(&self) -> bool {
+        self.depth == 0 && self.pending.is_empty() && !self.redo_steps.is_empty()
+    }
+    pub(crate) fn undo_names(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        let pending = if self.pending.is_empty() {
+            None
+        } else {
+            Some(self.pending_name.as_deref())
+        };
+
+        pending.into_iter().chain(
+            self.undo_steps
+                .iter()
+                .rev()
+                .map(|step| step.name.as_deref()),
+        )
+    }
+    pub(crate) fn redo_names(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.redo_steps
+            .iter()
+            .rev()
+            .map(|step| step.name.as_deref())
+    }
+    /// Drops all history.
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+        self.undo_steps.clear();
+        self.redo_steps.clear();
+    }
+    /// Returns a clone of all registered components `entity` owns.
+    fn components(
+        &self,
+        all_storages: &mut AllStorages,
+        entity: EntityId,
+    ) -> Vec<(StorageId, BoxedComponent)> {
+        self.registry
+            .codecs
+            .iter()
+            .filter_map(|(&storage_id, codec)| Some((storage_id, codec.get(all_storages, entity)?)))
+            .collect()
+    }
+}
+
+/// Returns a clone of all registered components `entity` owns, `None` if undo isn't enabled.
+pub(crate) fn entity_components(
+    all_storages: &mut AllStorages,
+    entity: EntityId,
+) -> Option<Vec<(StorageId, BoxedComponent)>> {
+    let journal = all_storages.undo.take()?;
+    let components = journal.components(all_storages, entity);
+    all_storages.undo = Some(journal);
+
+    Some(components)
+}
+
+/// Records the deletion of `entity`, `components` are the registered components it owned.
+pub(crate) fn record_entity_deletion(
+    all_storages: &mut AllStorages,
+    entity: EntityId,
+    components: Vec<(StorageId, BoxedComponent)>,
+) {
+    if let Some(journal) = &mut all_storages.undo {
+        journal.push(UndoOp::DeleteEntity { entity, components });
+    }
+}
+
+/// Records the deletion of `components` without deleting the entity.
+pub(crate) fn record_strip(
+    all_storages: &mut AllStorages,
+    entity: EntityId,
+    components: Vec<(StorageId, BoxedComponent)>,
+) {
+    if let Some(journal) = &mut all_storages.undo {
+        for (storage_id, old) in components {
+            journal.push(UndoOp::SetComponent {
+                entity,
+                storage_id,
+                old: Some(old),
+            });
+        }
+    }
+}
+
+/// Records the current value of `entity`'s `T` before it's added or replaced.
+#[inline]
+pub(crate) fn record_insertion<T: Component>(all_storages: &mut AllStorages, entity: EntityId) {
+    if all_storages.undo.is_some() {
+        record_component::<T>(all_storages, entity, true);
+    }
+}
+
+/// Records the current value of `entity`'s `T` before it's deleted or removed.
+#[inline]
+pub(crate) fn record_removal<T: Component>(all_storages: &mut AllStorages, entity: EntityId) {
+    if all_storages.undo.is_some() {
+        record_component::<T>(all_storages, entity, false);
+    }
+}
+
+fn record_component<T: Component>(
+    all_storages: &mut AllStorages,
+    entity: EntityId,
+    is_insertion: bool,
+) {
+    let storage_id = StorageId::of::<SparseSet<T>>();
+
+    if let Some(mut journal) = all_storages.undo.take() {
+        if let Some(codec) = journal.registry.codecs.get(&storage_id) {
+            let old = codec.get(all_storages, entity);
+
+            if is_insertion || old.is_some() {
+                journal.push(UndoOp::SetComponent {
+                    entity,
+                    storage_id,
+                    old,
+                });
+            }
+        }
+
+        all_storages.undo = Some(journal);
+    }
+}
+
+/// Reverts the last step, returns `false` if there was nothing to undo.
+pub(crate) fn undo(all_storages: &mut AllStorages) -> bool {
+    let step = match &mut all_storages.undo {
+        Some(journal) if journal.depth == 0 => {
+            journal.commit();
+
+            match journal.undo_steps.pop() {
+                Some(step) => step,
+                None => return false,
+            }
+        }
+        _ => return false,
+    };
+
+    let redo_step = revert(all_storages, step);
+    if let Some(journal) = &mut all_storages.undo {
+        journal.redo_steps.push(redo_step);
+    }
+
+    true
+}
+
+/// Reapplies the last undone step, returns `false` if there was nothing to redo.
+pub(crate) fn redo(all_storages: &mut AllStorages) -> bool {
+    let step = match &mut all_storages.undo {
+        Some(journal) if journal.can_redo() => journal.redo_steps.pop().unwrap(),
+        _ => return false,
+    };
+
+    let undo_step = revert(all_storages, step);
+    if let Some(journal) = &mut all_storages.undo {
+        journal.undo_steps.push(undo_step);
+    }
+
+    true
+}
+
+/// Applies the inverse of `step`'s operations in reverse order and returns the step reverting them.
+fn revert(all_storages: &mut AllStorages, step: UndoStep) -> UndoStep {
+    if let Some(journal) = &mut all_storages.undo {
+        journal.is_reverting = true;
+    }
+
+    for op in step.ops.into_iter().rev() {
+        match op {
+            UndoOp::AddEntity(entity) => {
+                all_storages.delete_entity(entity);
+            }
+            UndoOp::DeleteEntity { entity, components } => {
+                if all_storages.spawn(entity) {
+                    for (storage_id, component) in components {
+                        if let Some(codec) = codec(all_storages, storage_id) {
+                            codec.insert(all_storages, entity, component);
+                        }
+                    }
+                }
+            }
+            UndoOp::SetComponent {
+                entity,
+                storage_id,
+                old,
+            } => {
+                if let Some(codec) = codec(all_storages, storage_id) {
+                    match old {
+                        Some(component) => codec.insert(all_storages, entity, component),
+                        None => codec.delete(all_storages, entity),
+                    }
+                }
+            }
+        }
+    }
+
+    let journal = all_storages.undo.as_mut().unwrap();
+    journal.is_reverting = false;
+
+    UndoStep {
+        name: step.name,
+        ops: core::mem::take(&mut journal.pending),
+    }
+}
+
+fn codec(all_storages: &AllStorages, storage_id: StorageId) -> Option<Arc<dyn UndoCodec>> {
+    all_storages
+        .undo
+        .as_ref()?
+        .registry
+        .codecs
+        .get(&storage_id)
+        .cloned()
+}
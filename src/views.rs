@@ -1,5 +1,7 @@
 mod all_storages;
 mod entities;
+mod sub_view_mut;
+mod thread_local;
 mod unique_or_default;
 mod unique_or_default_mut;
 mod unique_or_init;
@@ -9,8 +11,10 @@ mod unique_view_mut;
 mod view;
 mod view_mut;
 
-pub use all_storages::{AllStoragesView, AllStoragesViewMut};
+pub use all_storages::{AllStoragesView, AllStoragesViewMut, AllStoragesViewOf};
 pub use entities::{EntitiesView, EntitiesViewMut};
+pub use sub_view_mut::SubViewMut;
+pub use thread_local::{ThreadLocal, ThreadLocalRefMut};
 pub use unique_or_default::UniqueOrDefaultView;
 pub use unique_or_default_mut::UniqueOrDefaultViewMut;
 pub use unique_or_init::UniqueOrInitView;
@@ -1,5 +1,9 @@
 use crate::all_storages::AllStorages;
 use crate::atomic_refcell::{ARef, ARefMut};
+use crate::borrow::{check_access, Borrow, BorrowInfo};
+use crate::error;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 use core::ops::{Deref, DerefMut};
 
 /// Shared view over `AllStorages`.
@@ -28,6 +32,101 @@ impl AsRef<AllStorages> for AllStoragesView<'_> {
     }
 }
 
+/// Shared view over `AllStorages` only borrowing the storages of `V`.
+///
+/// Workloads schedule systems using [`AllStoragesView`] or [`AllStoragesViewMut`] on their own,
+/// the whole batch waits for them.\
+/// A system using `AllStoragesViewOf` instead only conflicts with systems borrowing `V`'s storages,
+/// it can run in parallel with the others.
+///
+/// The storages are borrowed when the system asks for them, with [`declared`] or [`borrow`].\
+/// Only the storages listed in `V` can be accessed, the rest of the `AllStorages` isn't reachable.\
+/// Without any storage in `V`, the system is scheduled like with [`AllStoragesView`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{
+///     AllStoragesViewOf, Component, EntitiesViewMut, IntoIter, IntoWithId, View, ViewMut, Workload,
+///     World,
+/// };
+///
+/// #[derive(Component)]
+/// struct Dead;
+///
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// fn clean_up(all_storages: AllStoragesViewOf<(EntitiesViewMut, ViewMut<Dead>)>) {
+///     let (mut entities, mut dead) = all_storages.declared().unwrap();
+///
+///     let dead_entities: Vec<_> = dead.iter().ids().collect();
+///
+///     for entity in dead_entities {
+///         dead.delete(entity);
+///         entities.delete_unchecked(entity);
+///     }
+/// }
+///
+/// fn render(positions: View<Position>) {
+///     for _ in positions.iter() {}
+/// }
+///
+/// let world = World::new();
+///
+/// Workload::new("")
+///     .with_system(clean_up)
+///     .with_system(render)
+///     .add_to_world(&world)
+///     .unwrap();
+///
+/// // both systems are in the same batch
+/// assert_eq!(world.workloads_info().0[""].batch_info.len(), 1);
+/// ```
+///
+/// [`declared`]: AllStoragesViewOf::declared
+/// [`borrow`]: AllStoragesViewOf::borrow
+pub struct AllStoragesViewOf<'a, V> {
+    pub(crate) all_storages: AllStoragesView<'a>,
+    pub(crate) _phantom: PhantomData<fn() -> V>,
+}
+
+impl<V: Borrow + BorrowInfo> AllStoragesViewOf<'_, V> {
+    /// Borrows the storages listed in `V`.
+    ///
+    /// ### Errors
+    ///
+    /// - Storage borrow failed.
+    #[inline]
+    pub fn declared(&self) -> Result<V::View<'_>, error::GetStorage> {
+        self.all_storages.borrow::<V>()
+    }
+    /// Borrows part of the storages listed in `V`.
+    ///
+    /// ### Errors
+    ///
+    /// - A storage isn't listed in `V` or is borrowed mutably while `V` only borrows it shared.
+    /// - Storage borrow failed.
+    #[inline]
+    pub fn borrow<B: Borrow + BorrowInfo>(&self) -> Result<B::View<'_>, error::GetStorage> {
+        let mut declared = Vec::new();
+        V::borrow_info(&mut declared);
+
+        check_access::<B>(&declared)?;
+
+        self.all_storages.borrow::<B>()
+    }
+}
+
+impl<V> Clone for AllStoragesViewOf<'_, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        AllStoragesViewOf {
+            all_storages: self.all_storages.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
 /// Exclusive view over `AllStorages`.
 pub struct AllStoragesViewMut<'a>(pub(crate) ARefMut<'a, &'a mut AllStorages>);
 
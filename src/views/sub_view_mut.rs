@@ -0,0 +1,192 @@
+use crate::component::Component;
+use crate::entity_id::EntityId;
+use crate::r#mut::{ModificationFlag, Mut};
+use crate::sparse_set::{SparseArray, SparseSet, BUCKET_SIZE};
+use crate::tracking::TrackingTimestamp;
+use core::fmt;
+
+/// Exclusive access to a contiguous part of a component storage.
+///
+/// Two `SubViewMut` created from the same storage never contain the same component,
+/// they can be used from different threads at the same time.\
+/// Created by [`ViewMut::split_at_mut`] and [`ViewMut::partition`].
+///
+/// [`ViewMut::split_at_mut`]: crate::ViewMut::split_at_mut()
+/// [`ViewMut::partition`]: crate::ViewMut::partition()
+pub struct SubViewMut<'a, T: Component> {
+    sparse: &'a SparseArray<EntityId, BUCKET_SIZE>,
+    dense: &'a [EntityId],
+    data: &'a mut [T],
+    // empty when the storage doesn't track modification
+    modification_data: &'a mut [TrackingTimestamp],
+    offset: usize,
+    current: TrackingTimestamp,
+}
+
+impl<'a, T: Component> SubViewMut<'a, T> {
+    pub(crate) fn new(sparse_set: &'a mut SparseSet<T>, current: TrackingTimestamp) -> Self {
+        let SparseSet {
+            sparse,
+            dense,
+            data,
+            modification_data,
+            is_tracking_modification,
+            ..
+        } = sparse_set;
+
+        SubViewMut {
+            sparse,
+            dense,
+            data,
+            modification_data: if *is_tracking_modification {
+                modification_data
+            } else {
+                &mut []
+            },
+            offset: 0,
+            current,
+        }
+    }
+    /// Returns the number of components in this part of the storage.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    /// Returns `true` if this part of the storage contains no component.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    /// Returns the `EntityId`s of the components in this part, in the same order as [`SubViewMut::iter`].
+    #[inline]
+    pub fn ids(&self) -> &[EntityId] {
+        self.dense
+    }
+    #[inline]
+    fn local_index(&self, entity: EntityId) -> Option<usize> {
+        let index = self.sparse.get::<T>(entity)?.uindex();
+
+        index
+            .checked_sub(self.offset)
+            .filter(|&index| index < self.dense.len() && self.dense[index] == entity)
+    }
+    /// Returns `true` if `entity` owns a component in this part of the storage.
+    #[inline]
+    pub fn contains(&self, entity: EntityId) -> bool {
+        self.local_index(entity).is_some()
+    }
+    /// Returns a reference to `entity`'s component if it's part of this sub-view.
+    #[inline]
+    pub fn get(&self, entity: EntityId) -> Option<&T> {
+        self.local_index(entity).map(|index| &self.data[index])
+    }
+    /// Returns a mutable reference to `entity`'s component if it's part of this sub-view.
+    #[inline]
+    pub fn get_mut(&mut self, entity: EntityId) -> Option<Mut<'_, T>> {
+        let index = self.local_index(entity)?;
+
+        Some(Mut {
+            flag: self.modification_data.get_mut(index),
+            current: self.current,
+            data: &mut self.data[index],
+        })
+    }
+    /// Returns an iterator over the components of this part.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+    /// Returns an iterator over the components of this part, allowing modification.\
+    /// Modification tracking works as it does with `ViewMut`.
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = Mut<'_, T>> + '_ {
+        let current = self.current;
+        let mut flags = self.modification_data.iter_mut();
+
+        self.data.iter_mut().map(move |data| Mut {
+            flag: flags.next(),
+            current,
+            data,
+        })
+    }
+    /// Returns an iterator over the components of this part yielding a `&mut T` and the component's [`ModificationFlag`].\
+    /// See [`ViewMut::iter_mut_flagged`].
+    ///
+    /// [`ViewMut::iter_mut_flagged`]: crate::ViewMut::iter_mut_flagged()
+    #[inline]
+    pub fn iter_mut_flagged(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = (&mut T, ModificationFlag<'_>)> + '_ {
+        let current = self.current;
+        let mut flags = self.modification_data.iter_mut();
+
+        self.data.iter_mut().map(move |data| {
+            (
+                data,
+                ModificationFlag {
+                    flag: flags.next(),
+                    current,
+                },
+            )
+        })
+    }
+    pub(crate) fn into_iter_mut_flagged(
+        self,
+    ) -> impl ExactSizeIterator<Item = (&'a mut T, ModificationFlag<'a>)> {
+        let current = self.current;
+        let mut flags = self.modification_data.iter_mut();
+
+        self.data.iter_mut().map(move |data| {
+            (
+                data,
+                ModificationFlag {
+                    flag: flags.next(),
+                    current,
+                },
+            )
+        })
+    }
+    /// Divides this sub-view in two at `mid`.\
+    /// The first one contains the components in `[0, mid)` and the second one the components in `[mid, len)`.
+    ///
+    /// ### Panics
+    ///
+    /// - `mid > len`.
+    #[track_caller]
+    pub fn split_at_mut(self, mid: usize) -> (SubViewMut<'a, T>, SubViewMut<'a, T>) {
+        let (dense_left, dense_right) = self.dense.split_at(mid);
+        let (data_left, data_right) = self.data.split_at_mut(mid);
+        let (modification_left, modification_right) = if self.modification_data.is_empty() {
+            (&mut [][..], &mut [][..])
+        } else {
+            self.modification_data.split_at_mut(mid)
+        };
+
+        (
+            SubViewMut {
+                sparse: self.sparse,
+                dense: dense_left,
+                data: data_left,
+                modification_data: modification_left,
+                offset: self.offset,
+                current: self.current,
+            },
+            SubViewMut {
+                sparse: self.sparse,
+                dense: dense_right,
+                data: data_right,
+                modification_data: modification_right,
+                offset: self.offset + mid,
+                current: self.current,
+            },
+        )
+    }
+}
+
+impl<T: fmt::Debug + Component> fmt::Debug for SubViewMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.dense.iter().zip(self.data.iter()))
+            .finish()
+    }
+}
@@ -0,0 +1,199 @@
+use crate::all_storages::AllStorages;
+use crate::atomic_refcell::ARef;
+use crate::borrow::{Borrow, WorldBorrow};
+use crate::component::Unique;
+use crate::info::TypeInfo;
+use crate::tracking::TrackingTimestamp;
+use crate::views::UniqueViewMut;
+use crate::world::World;
+use crate::{error, BorrowInfo};
+use alloc::vec::Vec;
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Per thread instances of `T`, stored in the `World` as a unique.
+pub(crate) struct ThreadLocalStorage<T> {
+    slots: Vec<Slot<T>>,
+}
+
+struct Slot<T> {
+    borrowed: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFE each `Slot` is only accessed by one thread at a time, guarded by `borrowed`
+unsafe impl<T: Send> Sync for ThreadLocalStorage<T> {}
+
+impl<T: Default + Send + 'static> Unique for ThreadLocalStorage<T> {}
+
+impl<T: Default> ThreadLocalStorage<T> {
+    fn new() -> ThreadLocalStorage<T> {
+        ThreadLocalStorage { slots: Vec::new() }
+    }
+    /// Makes sure there is one slot per thread of the current thread pool, plus one for other threads.
+    fn reserve_slots(&mut self) {
+        #[cfg(feature = "parallel")]
+        let slot_count = rayon::current_num_threads() + 1;
+        #[cfg(not(feature = "parallel"))]
+        let slot_count = 1;
+
+        while self.slots.len() < slot_count {
+            self.slots.push(Slot {
+                borrowed: AtomicBool::new(false),
+                value: UnsafeCell::new(T::default()),
+            });
+        }
+    }
+}
+
+/// Gives each thread its own instance of `T`.
+///
+/// Parallel systems can accumulate results without locks, each thread of the pool gets its instance with [`ThreadLocal::get`].\
+/// Once the parallel work is done, all instances can be merged using [`ThreadLocal::iter_mut`] or [`ThreadLocal::drain`].\
+/// The instances are stored in the `World` and kept between runs, they're created with `T::default()` on first borrow.
+///
+/// Borrowing a `ThreadLocal` is exclusive, two systems using the same `ThreadLocal<T>` can't run in parallel.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, EntitiesViewMut, IntoIter, ThreadLocal, View, ViewMut, World};
+///
+/// #[derive(Component)]
+/// struct Sprite(u32);
+///
+/// let world = World::new();
+///
+/// world.run(|mut entities: EntitiesViewMut, mut sprites: ViewMut<Sprite>| {
+///     entities.add_entity(&mut sprites, Sprite(0));
+///     entities.add_entity(&mut sprites, Sprite(1));
+/// });
+///
+/// world.run(|sprites: View<Sprite>, mut draw_commands: ThreadLocal<Vec<u32>>| {
+///     sprites.iter().for_each(|sprite| draw_commands.get().push(sprite.0));
+///
+///     let mut all_commands: Vec<u32> = draw_commands.drain().flatten().collect();
+///     all_commands.sort();
+///
+///     assert_eq!(all_commands, vec![0, 1]);
+/// });
+/// ```
+pub struct ThreadLocal<'v, T: Default + Send + 'static>(UniqueViewMut<'v, ThreadLocalStorage<T>>);
+
+impl<'v, T: Default + Send + 'static> ThreadLocal<'v, T> {
+    /// Returns the current thread's instance.
+    ///
+    /// ### Panics
+    ///
+    /// - The current thread's instance is already borrowed.
+    #[track_caller]
+    pub fn get(&self) -> ThreadLocalRefMut<'_, T> {
+        let slots = &self.0.as_ref().slots;
+        let other_threads = slots.len() - 1;
+
+        #[cfg(feature = "parallel")]
+        let index = rayon::current_thread_index()
+            .filter(|&index| index < other_threads)
+            .unwrap_or(other_threads);
+        #[cfg(not(feature = "parallel"))]
+        let index = other_threads;
+
+        let slot = &slots[index];
+
+        assert!(
+            !slot.borrowed.swap(true, Ordering::Acquire),
+            "ThreadLocal<{}> is already borrowed on this thread.",
+            core::any::type_name::<T>()
+        );
+
+        ThreadLocalRefMut { slot }
+    }
+    /// Returns an iterator over all threads' instances.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.0
+            .as_mut()
+            .slots
+            .iter_mut()
+            .map(|slot| slot.value.get_mut())
+    }
+    /// Takes all threads' instances, leaving `T::default()` in their place.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.iter_mut().map(core::mem::take)
+    }
+}
+
+impl<'v, T: Default + Send + 'static> WorldBorrow for ThreadLocal<'v, T> {
+    type WorldView<'a> = ThreadLocal<'a, T>;
+
+    fn world_borrow(
+        world: &World,
+        last_run: Option<TrackingTimestamp>,
+        current: TrackingTimestamp,
+    ) -> Result<Self::WorldView<'_>, error::GetStorage> {
+        let all_storages = world
+            .all_storages()
+            .map_err(error::GetStorage::AllStoragesBorrow)?;
+
+        match all_storages.borrow::<UniqueViewMut<'_, ThreadLocalStorage<T>>>() {
+            Ok(_) => {}
+            Err(error::GetStorage::MissingStorage { .. }) => {
+                all_storages.add_unique(ThreadLocalStorage::<T>::new())
+            }
+            Err(err) => return Err(err),
+        };
+
+        let (all_storages, all_borrow) = unsafe { ARef::destructure(all_storages) };
+
+        let mut view = UniqueViewMut::<ThreadLocalStorage<T>>::borrow(
+            all_storages,
+            Some(all_borrow),
+            last_run,
+            current,
+        )?;
+        view.as_mut().reserve_slots();
+
+        Ok(ThreadLocal(view))
+    }
+}
+
+unsafe impl<'v, T: Default + Send + 'static> BorrowInfo for ThreadLocal<'v, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        UniqueViewMut::<ThreadLocalStorage<T>>::borrow_info(info);
+    }
+
+    fn enable_tracking(
+        enable_tracking_fn: &mut Vec<fn(&AllStorages) -> Result<(), error::GetStorage>>,
+    ) {
+        UniqueViewMut::<ThreadLocalStorage<T>>::enable_tracking(enable_tracking_fn);
+    }
+}
+
+/// Exclusive reference to the current thread's instance of a [`ThreadLocal`].
+pub struct ThreadLocalRefMut<'a, T> {
+    slot: &'a Slot<T>,
+}
+
+impl<T> Deref for ThreadLocalRefMut<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFE `borrowed` is set, no one else can access the value
+        unsafe { &*self.slot.value.get() }
+    }
+}
+
+impl<T> DerefMut for ThreadLocalRefMut<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFE `borrowed` is set, no one else can access the value
+        unsafe { &mut *self.slot.value.get() }
+    }
+}
+
+impl<T> Drop for ThreadLocalRefMut<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.slot.borrowed.store(false, Ordering::Release);
+    }
+}
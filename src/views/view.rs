@@ -1,9 +1,12 @@
 use crate::all_storages::AllStorages;
+#[cfg(feature = "arrow")]
+use crate::arrow::ArrowComponent;
 use crate::atomic_refcell::{ARef, SharedBorrow};
 use crate::component::Component;
 use crate::entity_id::EntityId;
 use crate::get::Get;
-use crate::sparse_set::SparseSet;
+use crate::iter::{IntoIter, Iter};
+use crate::sparse_set::{FullRawWindow, SparseSet};
 use crate::storage::StorageId;
 use crate::track;
 use crate::tracking::{
@@ -342,6 +345,19 @@ where
     }
 }
 
+#[cfg(feature = "arrow")]
+impl<T: ArrowComponent, Track: Tracking> View<'_, T, Track> {
+    /// Converts this storage to an Apache Arrow record batch.\
+    /// The first column, named [`ARROW_ID_COLUMN`], contains the `EntityId`s as `u64`,
+    /// the following ones are the columns returned by [`ArrowComponent::to_columns`].
+    ///
+    /// [`ARROW_ID_COLUMN`]: crate::ARROW_ID_COLUMN
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn to_record_batch(&self) -> Result<arrow_array::RecordBatch, arrow_schema::ArrowError> {
+        crate::arrow::to_record_batch(&self.dense, &self.data)
+    }
+}
+
 impl<'a, T: Component, Track: Tracking> Deref for View<'a, T, Track> {
     type Target = SparseSet<T>;
 
@@ -388,3 +404,39 @@ impl<T: Component, Track: Tracking> core::ops::Index<EntityId> for View<'_, T, T
         self.get(entity).unwrap()
     }
 }
+
+impl<'a, 'v, T: Component, Track: Tracking> IntoIterator for &'a View<'v, T, Track> {
+    type Item = &'a T;
+    type IntoIter = Iter<FullRawWindow<'a, T>>;
+
+    /// Same as [`IntoIter::iter`], allows `for component in &view`.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, IntoWithId, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity((U32(0),));
+    /// world.add_entity((U32(1),));
+    ///
+    /// world.run(|u32s: View<U32>| {
+    ///     let mut sum = 0;
+    ///     for u32 in &u32s {
+    ///         sum += u32.0;
+    ///     }
+    ///     assert_eq!(sum, 1);
+    ///
+    ///     for (id, u32) in (&u32s).into_iter().with_id() {
+    ///         assert_eq!(u32s[id].0, u32.0);
+    ///     }
+    /// });
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::iter(self)
+    }
+}
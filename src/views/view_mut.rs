@@ -1,16 +1,20 @@
 use crate::all_storages::AllStorages;
+#[cfg(feature = "arrow")]
+use crate::arrow::ArrowComponent;
 use crate::atomic_refcell::{ARef, ARefMut, ExclusiveBorrow, SharedBorrow};
 use crate::component::Component;
 use crate::entity_id::EntityId;
 use crate::get::Get;
-use crate::r#mut::Mut;
-use crate::sparse_set::{SparseSet, SparseSetDrain};
+use crate::iter::{AbstractMut, IntoIter, Iter};
+use crate::r#mut::{ModificationFlag, Mut};
+use crate::sparse_set::{FullRawWindow, FullRawWindowMut, SparseSet, SparseSetDrain};
 use crate::storage::StorageId;
 use crate::track;
 use crate::tracking::{
     DeletionTracking, Inserted, InsertedOrModified, InsertionTracking, ModificationTracking,
     Modified, RemovalOrDeletionTracking, RemovalTracking, Tracking,
 };
+use crate::views::sub_view_mut::SubViewMut;
 use crate::views::view::View;
 use crate::{error, TrackingTimestamp};
 use core::fmt;
@@ -188,16 +192,136 @@ impl<'a, T: Component, Track> ViewMut<'a, T, Track>
 where
     Track: Tracking,
 {
-    /// Deletes all components in this storage.
+    /// Deletes all components in this storage.\
+    /// Each component is recorded in deletion tracking.
     pub fn clear(&mut self) {
         self.sparse_set.private_clear(self.current);
     }
-    /// Creates a draining iterator that empties the storage and yields the removed items.
+    /// Removes `entity`'s component from this storage and returns it.\
+    /// The removal is recorded in removal tracking, the component is given back instead of being kept for deletion tracking.
+    ///
+    /// Use [`ViewMut::delete`] to drop the component.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{track, Component, ViewMut, World};
+    ///
+    /// #[derive(Component, Debug, PartialEq, Eq)]
+    /// #[track(Removal)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity = world.add_entity(U32(0));
+    ///
+    /// let mut u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    /// assert_eq!(u32s.remove(entity), Some(U32(0)));
+    /// assert!(u32s.is_removed(entity));
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, entity: EntityId) -> Option<T> {
+        let current = self.current;
+        self.sparse_set.dyn_remove(entity, current)
+    }
+    /// Deletes `entity`'s component from this storage, returns `true` if the entity had one.\
+    /// The deletion is recorded in deletion tracking, which keeps the component until tracking is cleared.
+    ///
+    /// Use [`ViewMut::remove`] to get the component back.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{track, Component, ViewMut, World};
+    ///
+    /// #[derive(Component, Debug, PartialEq, Eq)]
+    /// #[track(Deletion)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity = world.add_entity(U32(0));
+    ///
+    /// let mut u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    /// assert!(u32s.delete(entity));
+    /// assert_eq!(u32s.deleted().next(), Some((entity, &U32(0))));
+    /// ```
+    #[inline]
+    pub fn delete(&mut self, entity: EntityId) -> bool {
+        let current = self.current;
+        self.sparse_set.dyn_delete(entity, current)
+    }
+    /// Adds `component` to `entity` only if it doesn't already have a component in this storage.\
+    /// An existing component is left untouched and isn't flagged modified.
+    /// This function does not check `entity` is alive.
+    ///
+    /// Returns `true` if the component was added.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, ViewMut, World};
+    ///
+    /// #[derive(Component, Debug, PartialEq, Eq)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity = world.add_entity(());
+    ///
+    /// let mut u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    /// assert!(u32s.add_component_if_missing(entity, U32(0)));
+    /// assert!(!u32s.add_component_if_missing(entity, U32(1)));
+    /// assert_eq!(u32s[entity], U32(0));
+    /// ```
+    #[inline]
+    pub fn add_component_if_missing(&mut self, entity: EntityId, component: T) -> bool {
+        if self.sparse_set.contains(entity) {
+            return false;
+        }
+
+        self.sparse_set
+            .insert(entity, component, self.current)
+            .was_inserted()
+    }
+    /// Adds all components from `iter` to their entity.\
+    /// Components already present are replaced, like [`AddComponent::add_component_unchecked`] does.  
+    /// When the ids are strictly ascending and none of the entities already has a component in this storage,
+    /// the components are appended in bulk, which is a lot faster than adding them one by one.
+    ///
+    /// Components appended in bulk are all flagged inserted with a single timestamp, the others are tracked like individual insertions.\
+    /// This function does not check the entities are alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, EntitiesViewMut, ViewMut, World};
+    ///
+    /// #[derive(Component, Debug, PartialEq, Eq)]
+    /// struct U32(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let (mut entities, mut u32s) = world.borrow::<(EntitiesViewMut, ViewMut<U32>)>().unwrap();
+    /// let ids: Vec<_> = (0..10).map(|_| entities.add_entity((), ())).collect();
+    ///
+    /// u32s.extend_from_iter(ids.iter().map(|&id| (id, U32(id.index() as u32))));
+    ///
+    /// assert_eq!(u32s.len(), 10);
+    /// assert_eq!(u32s[ids[3]], U32(3));
+    /// ```
+    ///
+    /// [`AddComponent::add_component_unchecked`]: crate::AddComponent::add_component_unchecked()
+    pub fn extend_from_iter<I: IntoIterator<Item = (EntityId, T)>>(&mut self, iter: I) {
+        self.sparse_set.private_extend_from_iter(iter, self.current);
+    }
+    /// Creates a draining iterator that empties the storage and yields the removed items.\
+    /// All components are recorded in removal tracking, even if the iterator is dropped early.
     pub fn drain(&mut self) -> SparseSetDrain<'_, T> {
         self.sparse_set.private_drain(self.current)
     }
     /// Applies the given function `f` to the entities `a` and `b`.\
     /// The two entities shouldn't point to the same component.  
+    /// Only `a`'s component is flagged modified.
     ///
     /// ### Panics
     ///
@@ -209,6 +333,7 @@ where
     }
     /// Applies the given function `f` to the entities `a` and `b`.\
     /// The two entities shouldn't point to the same component.  
+    /// Both components are flagged modified.
     ///
     /// ### Panics
     ///
@@ -224,15 +349,223 @@ where
         self.sparse_set.private_apply_mut(a, b, f, self.current)
     }
 
-    /// Deletes all components for which `f(id, &component)` returns `false`.
+    /// Deletes all components for which `f(id, &component)` returns `false`.\
+    /// Deleted components are recorded in deletion tracking.
     pub fn retain<F: FnMut(EntityId, &T) -> bool>(&mut self, f: F) {
         self.sparse_set.private_retain(self.current, f);
     }
 
-    /// Deletes all components for which `f(id, Mut<component>)` returns `false`.
+    /// Deletes all components for which `f(id, Mut<component>)` returns `false`.\
+    /// Deleted components are recorded in deletion tracking, kept components are flagged modified only if they're accessed mutably.
     pub fn retain_mut<F: FnMut(EntityId, Mut<'_, T>) -> bool>(&mut self, f: F) {
         self.sparse_set.private_retain_mut(self.current, f);
     }
+
+    /// Calls `f` on each unordered pair of components exactly once, with mutable access to both.\
+    /// A component is never paired with itself.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Pos(f32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity((Pos(0.0),));
+    /// world.add_entity((Pos(0.5),));
+    /// world.add_entity((Pos(10.0),));
+    ///
+    /// let mut positions = world.borrow::<ViewMut<Pos>>().unwrap();
+    ///
+    /// // push overlapping entities apart
+    /// positions.for_each_pair_mut(|(_, mut a), (_, mut b)| {
+    ///     if (a.0 - b.0).abs() < 1.0 {
+    ///         a.0 -= 1.0;
+    ///         b.0 += 1.0;
+    ///     }
+    /// });
+    /// ```
+    pub fn for_each_pair_mut<F: FnMut((EntityId, Mut<'_, T>), (EntityId, Mut<'_, T>))>(
+        &mut self,
+        f: F,
+    ) {
+        self.sparse_set.private_for_each_pair_mut(self.current, f);
+    }
+    /// Returns an iterator over the components yielding a `&mut T` and the component's [`ModificationFlag`].\
+    /// Modifying a component through the reference doesn't flag it, [`ModificationFlag::flag`] has to be called.
+    /// This avoids [`Mut`]'s check on each mutable access in hot loops.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{track, Component, ViewMut, World};
+    ///
+    /// struct U32(u32);
+    /// impl Component for U32 {
+    ///     type Tracking = track::Modification;
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity0 = world.add_entity((U32(0),));
+    /// let entity1 = world.add_entity((U32(1),));
+    ///
+    /// world.run(|mut u32s: ViewMut<U32>| {
+    ///     for (u32, mut flag) in u32s.iter_mut_flagged() {
+    ///         if u32.0 % 2 == 1 {
+    ///             u32.0 *= 10;
+    ///             flag.flag();
+    ///         }
+    ///     }
+    ///
+    ///     assert!(!u32s.is_modified(entity0));
+    ///     assert!(u32s.is_modified(entity1));
+    /// });
+    /// ```
+    pub fn iter_mut_flagged(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = (&mut T, ModificationFlag<'_>)> + '_ {
+        SubViewMut::new(self.sparse_set, self.current).into_iter_mut_flagged()
+    }
+    /// Returns the component at `index` in the `data` vector, without bounds checking.
+    /// Indices can be retrieved with [`index_of`].
+    ///
+    /// The component is flagged when modified, like with [`get`].
+    ///
+    /// # Safety
+    ///
+    /// `index` has to be smaller than the storage's length.
+    /// Doesn't check the component still belongs to the same entity, indices are only valid until a modification occurs in the storage.
+    ///
+    /// [`index_of`]: SparseSet::index_of()
+    /// [`get`]: crate::Get::get()
+    #[inline]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> Mut<'_, T> {
+        let SparseSet {
+            data,
+            modification_data,
+            is_tracking_modification,
+            ..
+        } = &mut *self.sparse_set;
+
+        Mut {
+            flag: is_tracking_modification.then(|| modification_data.get_unchecked_mut(index)),
+            current: self.current,
+            data: data.get_unchecked_mut(index),
+        }
+    }
+    /// Returns a pointer to the `dense` vector, a mutable pointer to the `data` vector and their length.
+    /// The component at `data[i]` belongs to the entity at `dense[i]`.
+    ///
+    /// The pointers are valid as long as this view is alive and the storage isn't modified through it.\
+    /// Writes through the pointer are not flagged, use [`get_unchecked_mut`] or [`iter_mut_flagged`] when tracking modification.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Speed(f32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity(Speed(1.0));
+    /// world.add_entity(Speed(2.0));
+    ///
+    /// let mut speeds = world.borrow::<ViewMut<Speed>>().unwrap();
+    /// let (_, data, len) = speeds.as_raw_parts_mut();
+    ///
+    /// // SAFETY: `data` points to `len` components and no other reference to them exists
+    /// let data = unsafe { core::slice::from_raw_parts_mut(data, len) };
+    /// for speed in data {
+    ///     speed.0 *= 2.0;
+    /// }
+    ///
+    /// assert_eq!(speeds.as_slice()[1].0, 4.0);
+    /// ```
+    ///
+    /// [`get_unchecked_mut`]: ViewMut::get_unchecked_mut()
+    /// [`iter_mut_flagged`]: ViewMut::iter_mut_flagged()
+    #[inline]
+    pub fn as_raw_parts_mut(&mut self) -> (*const EntityId, *mut T, usize) {
+        (
+            self.sparse_set.dense.as_ptr(),
+            self.sparse_set.data.as_mut_ptr(),
+            self.sparse_set.dense.len(),
+        )
+    }
+    /// Divides the storage in two disjoint parts at `mid`, following the storage's order.\
+    /// The first one contains the components in `[0, mid)` and the second one the components in `[mid, len)`.\
+    /// Both parts can be iterated and modified independently, from different threads for example.
+    ///
+    /// ### Panics
+    ///
+    /// - `mid > len`.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.bulk_add_entity((0..10).map(|i| (U32(i),)));
+    ///
+    /// let mut u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    /// let (mut left, mut right) = u32s.split_at_mut(5);
+    /// for mut u32 in left.iter_mut() {
+    ///     u32.0 += 100;
+    /// }
+    /// for mut u32 in right.iter_mut() {
+    ///     u32.0 += 200;
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn split_at_mut(&mut self, mid: usize) -> (SubViewMut<'_, T>, SubViewMut<'_, T>) {
+        assert!(
+            mid <= self.sparse_set.len(),
+            "Cannot split a storage of length {} at {}.",
+            self.sparse_set.len(),
+            mid
+        );
+
+        SubViewMut::new(self.sparse_set, self.current).split_at_mut(mid)
+    }
+    /// Reorders the storage to place the components for which `pred` returns `true` first
+    /// then divides it in two disjoint parts.\
+    /// The first part contains the components `pred` returned `true` for and the second one the rest.\
+    /// Both parts can be iterated and modified independently, from different threads for example.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.bulk_add_entity((0..10).map(|i| (U32(i),)));
+    ///
+    /// let mut u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    /// let (even, odd) = u32s.partition(|_, u32| u32.0 % 2 == 0);
+    ///
+    /// assert!(even.iter().all(|u32| u32.0 % 2 == 0));
+    /// assert!(odd.iter().all(|u32| u32.0 % 2 == 1));
+    /// ```
+    pub fn partition<F: FnMut(EntityId, &T) -> bool>(
+        &mut self,
+        pred: F,
+    ) -> (SubViewMut<'_, T>, SubViewMut<'_, T>) {
+        let mid = self.sparse_set.private_partition(pred);
+
+        SubViewMut::new(self.sparse_set, self.current).split_at_mut(mid)
+    }
 }
 
 impl<'v, Track, T: Component + Default> ViewMut<'v, T, Track>
@@ -471,6 +804,43 @@ where
     }
 }
 
+#[cfg(feature = "arrow")]
+impl<T: ArrowComponent, Track> ViewMut<'_, T, Track>
+where
+    Track: Tracking,
+{
+    /// Converts this storage to an Apache Arrow record batch.\
+    /// See [`View::to_record_batch`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn to_record_batch(&self) -> Result<arrow_array::RecordBatch, arrow_schema::ArrowError> {
+        crate::arrow::to_record_batch(&self.dense, &self.data)
+    }
+    /// Adds the components contained in `batch` to their entity.\
+    /// `batch` needs an [`ARROW_ID_COLUMN`] column with the `EntityId`s,
+    /// the other columns are passed to [`ArrowComponent::from_columns`].
+    /// The components are added with [`ViewMut::extend_from_iter`], batches created by [`View::to_record_batch`] use the fast path.
+    ///
+    /// This function does not check the entities are alive.
+    ///
+    /// ### Errors
+    ///
+    /// - The id column is missing, has the wrong type or contains an invalid `EntityId`.
+    /// - [`ArrowComponent::from_columns`] failed or returned a different number of components than ids.
+    ///
+    /// [`ARROW_ID_COLUMN`]: crate::ARROW_ID_COLUMN
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn extend_from_record_batch(
+        &mut self,
+        batch: &arrow_array::RecordBatch,
+    ) -> Result<(), arrow_schema::ArrowError> {
+        let components = crate::arrow::from_record_batch::<T>(batch)?;
+
+        self.extend_from_iter(components);
+
+        Ok(())
+    }
+}
+
 impl<T: Component, Track> Deref for ViewMut<'_, T, Track> {
     type Target = SparseSet<T>;
 
@@ -549,3 +919,50 @@ impl<'a, T: Component, Track> core::ops::IndexMut<EntityId> for ViewMut<'a, T, T
         unsafe { data.get_unchecked_mut(index) }
     }
 }
+
+impl<'a, 'v, T: Component, Track: Tracking> IntoIterator for &'a ViewMut<'v, T, Track> {
+    type Item = &'a T;
+    type IntoIter = Iter<FullRawWindow<'a, T>>;
+
+    /// Same as [`IntoIter::iter`], allows `for component in &view`.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::iter(self)
+    }
+}
+
+impl<'a, 'v, T: Component, Track> IntoIterator for &'a mut ViewMut<'v, T, Track>
+where
+    FullRawWindowMut<'a, T, Track>: AbstractMut,
+    <FullRawWindowMut<'a, T, Track> as AbstractMut>::Index: From<usize> + Clone,
+{
+    type Item = <FullRawWindowMut<'a, T, Track> as AbstractMut>::Out;
+    type IntoIter = Iter<FullRawWindowMut<'a, T, Track>>;
+
+    /// Same as [`IntoIter::iter`], allows `for component in &mut view`.\
+    /// Modification tracking works as it does with [`IntoIter::iter`].
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity = world.add_entity((U32(0),));
+    ///
+    /// world.run(|mut u32s: ViewMut<U32>| {
+    ///     for mut u32 in &mut u32s {
+    ///         u32.0 += 1;
+    ///     }
+    ///
+    ///     assert_eq!(u32s[entity].0, 1);
+    /// });
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::iter(self)
+    }
+}
@@ -1,32 +1,64 @@
 mod builder;
+mod frozen_world;
 mod run_batches;
+mod sub_world;
 
 pub use builder::WorldBuilder;
+pub use frozen_world::{FrozenBorrow, FrozenWorld};
+pub use sub_world::SubWorld;
 
-use crate::all_storages::{AllStorages, CustomStorageAccess, TupleDeleteAny, TupleRetainStorage};
+use crate::all_storages::{
+    AllStorages, CustomStorageAccess, DeletionPolicy, Relation, TupleDeleteAny, TupleRetainStorage,
+};
 use crate::atomic_refcell::{ARef, ARefMut, AtomicRefCell};
-use crate::borrow::WorldBorrow;
+use crate::borrow::{BorrowInfo, WorldBorrow};
+#[cfg(feature = "std")]
+use crate::budget::BudgetReport;
 use crate::component::{Component, Unique};
-use crate::entities::Entities;
+use crate::component_mask::{ComponentMask, TupleSignature};
+use crate::component_registrar::TupleRegisterComponent;
+use crate::digest::Pod;
+use crate::entities::{Entities, EntityRemap};
 use crate::entity_id::EntityId;
 use crate::error;
+use crate::external_storage::ExternalStorage;
+use crate::from_world::FromWorld;
 use crate::get_component::GetComponent;
 use crate::get_unique::GetUnique;
+use crate::handle_owner::HandleOwner;
 use crate::info::WorkloadsInfo;
 use crate::iter_component::{IntoIterRef, IterComponent};
 use crate::memory_usage::WorldMemoryUsage;
+use crate::plugin::{Plugin, PluginDependencies};
 use crate::r#mut::Mut;
+use crate::replay::{ReplayRegistry, ReplayStream};
+#[cfg(feature = "replication")]
+use crate::replication::{ReplicationClient, ReplicationDelta, ReplicationRegistry};
 use crate::reserve::BulkEntityIter;
 use crate::scheduler::Label;
 use crate::scheduler::{AsLabel, Batches, Scheduler};
-use crate::sparse_set::{BulkAddEntity, TupleAddComponent, TupleDelete, TupleRemove};
-use crate::storage::{Storage, StorageId};
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{Checkpoint, EntityIdMap, SnapshotFilter, SnapshotRegistry, WorldDiff};
+use crate::sparse_set::{
+    BulkAddEntity, StorageAllocator, TupleAddComponent, TupleContains, TupleDelete, TupleRemove,
+};
+use crate::stable_id::{StableId, StableIds};
+use crate::state_machine::StateMachine;
+use crate::storage::{Storage, StorageHandle, StorageId};
 use crate::system::System;
+use crate::time::Time;
 use crate::tracking::{TrackingTimestamp, TupleTrack};
-use crate::views::EntitiesViewMut;
+use crate::undo::UndoRegistry;
+use crate::unique::UniqueStorage;
+use crate::views::{EntitiesView, EntitiesViewMut, UniqueView, UniqueViewMut, View, ViewMut};
+use crate::ShipHashSet;
+use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::format;
 use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::hash::Hash;
 use core::sync::atomic::AtomicU64;
 
 /// `World` contains all data this library will manipulate.
@@ -230,6 +262,60 @@ impl World {
             .unwrap()
             .add_unique_non_send_sync(component);
     }
+    /// Adds a new unique storage built with [`FromWorld`], unique storages store a single value.\
+    /// Does nothing if the storage already exists.
+    ///
+    /// `T::from_world` is called without any borrow held, it can borrow any storage.\
+    /// Initializing uniques in dependency order lets each one use the previous ones.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - Any storage `T::from_world` borrows
+    ///
+    /// ### Panics
+    ///
+    /// - [`AllStorages`] borrow failed.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Unique, UniqueView, World};
+    ///
+    /// #[derive(Unique, Default)]
+    /// struct Score(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// world.init_unique::<Score>();
+    ///
+    /// assert_eq!(world.borrow::<UniqueView<Score>>().unwrap().0, 0);
+    /// ```
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    /// [`FromWorld`]: crate::FromWorld
+    #[track_caller]
+    pub fn init_unique<T: Send + Sync + Unique + FromWorld>(&self) {
+        let storage_id = StorageId::of::<UniqueStorage<T>>();
+
+        if self
+            .all_storages
+            .borrow()
+            .unwrap()
+            .storages
+            .contains(&storage_id)
+        {
+            return;
+        }
+
+        let unique = T::from_world(self);
+
+        let all_storages = self.all_storages.borrow().unwrap();
+        // `from_world` could have added it
+        if !all_storages.storages.contains(&storage_id) {
+            all_storages.add_unique(unique);
+        }
+    }
     /// Removes a unique storage.
     ///
     /// ### Borrows
@@ -376,6 +462,64 @@ let (entities, mut usizes) = world
 
         V::world_borrow(self, None, current)
     }
+    /// Borrows the requested storages, retrying while they're borrowed elsewhere.
+    ///
+    /// Gives up once `timeout` elapsed and reports which threads and systems hold the storage.\
+    /// Errors other than borrow conflicts are returned right away.\
+    /// This is meant to debug deadlocks, regular code should use [`World::borrow`].
+    ///
+    /// ### Borrows
+    ///
+    /// Same as [`World::borrow`].
+    ///
+    /// ### Errors
+    ///
+    /// - Same as [`World::borrow`], with the borrows held when the timeout elapsed.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, View, ViewMut, World};
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Component, Debug)]
+    /// struct U32(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let _u32s = world.borrow::<ViewMut<U32>>().unwrap();
+    ///
+    /// let err = world
+    ///     .borrow_timeout::<View<U32>>(Duration::from_millis(10))
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(err.holders.len(), 1);
+    /// assert!(err.holders[0].exclusive);
+    /// ```
+    #[cfg(feature = "borrow_debug")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "borrow_debug")))]
+    pub fn borrow_timeout<V: WorldBorrow>(
+        &self,
+        timeout: core::time::Duration,
+    ) -> Result<V::WorldView<'_>, error::BorrowTimeout> {
+        let start = std::time::Instant::now();
+
+        loop {
+            let error = match V::world_borrow(self, None, self.get_current()) {
+                Ok(view) => return Ok(view),
+                Err(error) => error,
+            };
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout || !crate::borrow_debug::is_contention(&error) {
+                return Err(error::BorrowTimeout {
+                    holders: crate::borrow_debug::holders(self, &error),
+                    error,
+                });
+            }
+
+            std::thread::sleep((timeout - elapsed).min(core::time::Duration::from_millis(1)));
+        }
+    }
     #[doc = "Borrows the requested storages, runs the function and evaluates to the function's return value.
 Data can be passed to the function, this always has to be a single type but you can use a tuple if needed.
 
@@ -624,6 +768,50 @@ let i = world.run(sys1);
             .map_err(error::Run::GetStorage)
             .unwrap()
     }
+    /// Creates a [`SubWorld`] only able to borrow what `V` borrows.\
+    /// `V` is usually a tuple of views, exclusive access also grants shared access.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, EntitiesView, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let world = World::new();
+    /// let sub_world = world.sub_world::<(EntitiesView, View<Health>)>();
+    ///
+    /// assert!(sub_world.is_allowed::<View<Health>>());
+    /// ```
+    pub fn sub_world<V: BorrowInfo>(&self) -> SubWorld<'_> {
+        SubWorld::new::<V>(self)
+    }
+    /// Freezes the `World` for a read-only phase, like rendering or AI planning.\
+    /// The [`FrozenWorld`] hands out shared views without borrow bookkeeping and can be shared between threads.
+    ///
+    /// The `World` is usable again once the `FrozenWorld` is dropped.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, EntitiesView, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.add_entity(Health(10));
+    ///
+    /// let frozen = world.freeze();
+    /// let (entities, healths) = frozen.borrow::<(EntitiesView, View<Health>)>().unwrap();
+    ///
+    /// assert!(entities.is_alive(entity));
+    /// assert_eq!(healths[entity].0, 10);
+    /// ```
+    pub fn freeze(&mut self) -> FrozenWorld<'_> {
+        let current = self.get_current();
+
+        FrozenWorld::new(self.all_storages.get_mut(), current)
+    }
     /// Modifies the current default workload to `name`.
     ///
     /// ### Borrows
@@ -643,6 +831,279 @@ let i = world.run(sys1);
             .map_err(|_| error::SetDefaultWorkload::Borrow)?
             .set_default(name.as_label())
     }
+    /// Switches the [`StateMachine<S>`] to `state`.\
+    /// The current state's `on_exit` workload runs, then `state`'s workload becomes the default workload and its `on_enter` workload runs.\
+    /// Does nothing if `state` is already the current state.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`StateMachine<S>`] (exclusive), released while workloads run
+    /// - Scheduler (exclusive)
+    /// - Systems' borrow as they are executed
+    ///
+    /// ### Errors
+    ///
+    /// - [`StateMachine<S>`] borrow failed.
+    /// - Scheduler borrow failed.
+    /// - A workload did not exist.
+    /// - Storage borrow failed.
+    /// - User error returned by system.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{StateMachine, UniqueView, Workload, World};
+    ///
+    /// #[derive(PartialEq, Eq, Hash, Debug)]
+    /// enum GameState {
+    ///     Menu,
+    ///     Gameplay,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// Workload::new("gameplay").with_system(|| {}).add_to_world(&world).unwrap();
+    ///
+    /// world.add_unique(StateMachine::new().with_workload(GameState::Gameplay, "gameplay"));
+    ///
+    /// world.set_state(GameState::Gameplay).unwrap();
+    ///
+    /// world.run(|states: UniqueView<StateMachine<GameState>>| {
+    ///     assert_eq!(states.state(), Some(&GameState::Gameplay));
+    /// });
+    /// ```
+    pub fn set_state<S: Eq + Hash + Send + Sync + 'static>(
+        &self,
+        state: S,
+    ) -> Result<(), error::SetState> {
+        let transition = match self
+            .borrow::<UniqueView<'_, StateMachine<S>>>()
+            .map_err(error::SetState::StateMachine)?
+            .transition(&state)
+        {
+            Some(transition) => transition,
+            None => return Ok(()),
+        };
+
+        if let Some(on_exit) = transition.on_exit {
+            self.run_workload(on_exit)
+                .map_err(error::SetState::RunWorkload)?;
+        }
+
+        self.borrow::<UniqueViewMut<'_, StateMachine<S>>>()
+            .map_err(error::SetState::StateMachine)?
+            .set_current(state);
+
+        if let Some(workload) = transition.workload {
+            self.set_default_workload(workload)
+                .map_err(error::SetState::SetDefaultWorkload)?;
+        }
+
+        if let Some(on_enter) = transition.on_enter {
+            self.run_workload(on_enter)
+                .map_err(error::SetState::RunWorkload)?;
+        }
+
+        Ok(())
+    }
+    /// Checks `plugin`'s required uniques are present then builds it.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared), released while the plugin builds
+    ///
+    /// ### Errors
+    ///
+    /// - A required unique is not present, nothing is built.
+    /// - The plugin didn't add a unique it declared.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{error, Plugin, PluginDependencies, Unique, World};
+    ///
+    /// #[derive(Unique)]
+    /// struct Gravity(f32);
+    ///
+    /// struct Physics;
+    ///
+    /// impl Plugin for Physics {
+    ///     fn dependencies(&self, dependencies: &mut PluginDependencies) {
+    ///         dependencies.requires::<Gravity>();
+    ///     }
+    ///     fn build(&self, _world: &World) {}
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// assert!(matches!(
+    ///     world.add_plugin(Physics),
+    ///     Err(error::AddPlugin::MissingUnique { .. })
+    /// ));
+    ///
+    /// world.add_unique(Gravity(-9.81));
+    /// world.add_plugin(Physics).unwrap();
+    /// ```
+    pub fn add_plugin<P: Plugin>(&self, plugin: P) -> Result<(), error::AddPlugin> {
+        self.build_plugins(vec![&plugin])
+    }
+    /// Orders `plugins` so each one is built after the plugins providing the uniques it requires,
+    /// then builds them.\
+    /// Plugins that don't depend on each other are built in the order they're passed.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared), released while the plugins build
+    ///
+    /// ### Errors
+    ///
+    /// - A required unique is not present and no plugin provides it, nothing is built.
+    /// - Plugins require each other's uniques, nothing is built.
+    /// - A plugin didn't add a unique it declared, the following plugins aren't built.
+    pub fn add_plugins<I: IntoIterator<Item = Box<dyn Plugin>>>(
+        &self,
+        plugins: I,
+    ) -> Result<(), error::AddPlugin> {
+        let plugins: Vec<Box<dyn Plugin>> = plugins.into_iter().collect();
+
+        self.build_plugins(plugins.iter().map(|plugin| &**plugin).collect())
+    }
+    fn build_plugins(&self, plugins: Vec<&dyn Plugin>) -> Result<(), error::AddPlugin> {
+        let mut pending: Vec<(&dyn Plugin, PluginDependencies)> = plugins
+            .into_iter()
+            .map(|plugin| {
+                let mut dependencies = PluginDependencies::default();
+                plugin.dependencies(&mut dependencies);
+
+                (plugin, dependencies)
+            })
+            .collect();
+
+        let mut ordered = Vec::with_capacity(pending.len());
+
+        {
+            let all_storages = self.all_storages.borrow().unwrap();
+            let mut provided = ShipHashSet::default();
+
+            while !pending.is_empty() {
+                let is_available = |storage_id: &StorageId, provided: &ShipHashSet<StorageId>| {
+                    provided.contains(storage_id) || all_storages.storages.contains(storage_id)
+                };
+
+                let ready = pending.iter().position(|(_, dependencies)| {
+                    dependencies
+                        .requires
+                        .iter()
+                        .all(|unique| is_available(&unique.storage_id, &provided))
+                });
+
+                match ready {
+                    Some(index) => {
+                        let (plugin, dependencies) = pending.remove(index);
+
+                        provided
+                            .extend(dependencies.provides.iter().map(|unique| unique.storage_id));
+                        ordered.push((plugin, dependencies));
+                    }
+                    None => {
+                        let provided_later: ShipHashSet<StorageId> = pending
+                            .iter()
+                            .flat_map(|(_, dependencies)| &dependencies.provides)
+                            .map(|unique| unique.storage_id)
+                            .collect();
+
+                        for (plugin, dependencies) in &pending {
+                            for unique in &dependencies.requires {
+                                if !is_available(&unique.storage_id, &provided)
+                                    && !provided_later.contains(&unique.storage_id)
+                                {
+                                    return Err(error::AddPlugin::MissingUnique {
+                                        plugin: plugin.name(),
+                                        unique: unique.name,
+                                    });
+                                }
+                            }
+                        }
+
+                        return Err(error::AddPlugin::Cycle(
+                            pending.iter().map(|(plugin, _)| plugin.name()).collect(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        for (plugin, dependencies) in ordered {
+            plugin.build(self);
+
+            let all_storages = self.all_storages.borrow().unwrap();
+            if let Some(unique) = dependencies
+                .provides
+                .iter()
+                .find(|unique| !all_storages.storages.contains(&unique.storage_id))
+            {
+                return Err(error::AddPlugin::NotProvided {
+                    plugin: plugin.name(),
+                    unique: unique.name,
+                });
+            }
+        }
+
+        Ok(())
+    }
+    /// Stops all systems of the `set` set from running, until [`World::enable_set`] is called.\
+    /// Sets are added to workloads with [`Workload::with_set`].
+    ///
+    /// ### Borrows
+    ///
+    /// - Scheduler (exclusive)
+    ///
+    /// ### Panics
+    ///
+    /// - Scheduler borrow failed.
+    ///
+    /// [`Workload::with_set`]: crate::Workload::with_set
+    #[track_caller]
+    pub fn disable_set<T>(&self, set: impl AsLabel<T>) {
+        self.scheduler
+            .borrow_mut()
+            .unwrap()
+            .disabled_sets
+            .add(set.as_label());
+    }
+    /// Lets the systems of the `set` set run again after [`World::disable_set`].
+    ///
+    /// ### Borrows
+    ///
+    /// - Scheduler (exclusive)
+    ///
+    /// ### Panics
+    ///
+    /// - Scheduler borrow failed.
+    #[track_caller]
+    pub fn enable_set<T>(&self, set: impl AsLabel<T>) {
+        self.scheduler
+            .borrow_mut()
+            .unwrap()
+            .disabled_sets
+            .remove(&*set.as_label());
+    }
+    /// Returns `false` if the `set` set was disabled with [`World::disable_set`].
+    ///
+    /// ### Borrows
+    ///
+    /// - Scheduler (shared)
+    ///
+    /// ### Panics
+    ///
+    /// - Scheduler borrow failed.
+    #[track_caller]
+    pub fn is_set_enabled<T>(&self, set: impl AsLabel<T>) -> bool {
+        !self
+            .scheduler
+            .borrow()
+            .unwrap()
+            .disabled_sets
+            .contains(&*set.as_label())
+    }
     /// Changes the name of a workload if it exists.
     ///
     /// ### Borrows
@@ -689,7 +1150,62 @@ let i = world.run(sys1);
             &scheduler.system_names,
             batches,
             &*label,
-        )
+        )?;
+
+        self.apply_pending_commands();
+
+        Ok(())
+    }
+    /// Runs the `label` workload once per fixed step available in [`Time`].\
+    /// Returns the number of times the workload ran.
+    ///
+    /// ### Borrows
+    ///
+    /// - Scheduler (shared)
+    /// - [`Time`] (exclusive), released while the workload runs
+    ///
+    /// ### Panics
+    ///
+    /// - [`Time`] isn't present in the `World`.
+    ///
+    /// ### Errors
+    ///
+    /// - Scheduler borrow failed.
+    /// - Workload did not exist.
+    /// - Storage borrow failed.
+    /// - User error returned by system.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Time, UniqueViewMut, Workload, World};
+    ///
+    /// let world = World::new();
+    /// world.add_unique(Time::new(1.0 / 60.0));
+    ///
+    /// Workload::new("physics")
+    ///     .with_system(|| {})
+    ///     .add_to_world(&world)
+    ///     .unwrap();
+    ///
+    /// world.run(|mut time: UniqueViewMut<Time>| time.advance(2.5 / 60.0));
+    ///
+    /// assert_eq!(world.run_fixed_workload("physics").unwrap(), 2);
+    /// ```
+    #[track_caller]
+    pub fn run_fixed_workload<T>(&self, label: impl AsLabel<T>) -> Result<u32, error::RunWorkload> {
+        let label = label.as_label();
+        let mut steps = 0;
+
+        while self
+            .borrow::<UniqueViewMut<'_, Time>>()
+            .unwrap()
+            .consume_fixed_step()
+        {
+            self.run_workload(label.clone())?;
+            steps += 1;
+        }
+
+        Ok(steps)
     }
     /// Returns `true` if the world contains the `name` workload.
     ///
@@ -769,10 +1285,18 @@ let i = world.run(sys1);
                 &scheduler.system_names,
                 scheduler.default_workload(),
                 &scheduler.default,
-            )?
+            )?;
+
+            self.apply_pending_commands();
         }
         Ok(())
     }
+    /// Applies the `CommandBuffer` after a workload, when the `World` isn't borrowed.
+    fn apply_pending_commands(&self) {
+        if let Ok(mut all_storages) = self.all_storages.borrow_mut() {
+            all_storages.apply_commands();
+        }
+    }
     /// Returns a `Ref<&AllStorages>`, used to implement custom storages.
     /// To borrow `AllStorages` you should use `borrow` or `run` with `AllStoragesViewMut`.
     ///
@@ -791,36 +1315,160 @@ let i = world.run(sys1);
     pub fn all_storages_mut(&self) -> Result<ARefMut<'_, &'_ mut AllStorages>, error::Borrow> {
         self.all_storages.borrow_mut()
     }
-    /// Inserts a custom storage to the `World`.
+    /// Inserts a custom storage to the `World`.\
+    /// Does nothing if a storage of the same type already exists at `storage_id`.\
+    /// Storages shared with other dynamic libraries should use [`StorageId::from_name`], their `TypeId` can differ.
     ///
     /// ### Errors
     ///
     /// - `AllStorages` is already borrowed exclusively.
+    /// - A storage of a different type already exists at `storage_id`.
     pub fn add_custom_storage<S: 'static + Storage + Send + Sync>(
         &self,
         storage_id: StorageId,
         storage: S,
-    ) -> Result<(), error::Borrow> {
-        let _ = self
-            .all_storages
-            .borrow()?
-            .custom_storage_or_insert_by_id(storage_id, || storage);
-
-        Ok(())
-    }
-
-    /// Increments the current tracking cycle and returns the previous value.
-    #[inline]
-    pub(crate) fn get_current(&self) -> TrackingTimestamp {
-        TrackingTimestamp::new(
-            self.counter
-                .fetch_add(1, core::sync::atomic::Ordering::Acquire),
-        )
+    ) -> Result<(), error::GetStorage> {
+        self.all_storages
+            .borrow()
+            .map_err(error::GetStorage::AllStoragesBorrow)?
+            .add_custom_storage(storage_id, storage)
     }
-
-    /// Returns a timestamp used to clear tracking information.
-    pub fn get_tracking_timestamp(&self) -> TrackingTimestamp {
-        TrackingTimestamp::new(self.counter.load(core::sync::atomic::Ordering::Acquire))
+    /// Replaces `T`'s storage with an empty one allocating its components with `allocator`.\
+    /// The components of the previous storage are dropped without being tracked as deleted, entities stay alive.
+    ///
+    /// Dropping the storage, by calling this method again with [`StorageAllocator::global`] for example,
+    /// gives all its memory back to `allocator`. Transient data can be allocated in an arena this way and freed at once.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, StorageAllocator, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Tile(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// // any `allocator_api2` allocator, like an arena
+    /// world.add_storage_in::<Tile>(StorageAllocator::new(allocator_api2::alloc::Global));
+    /// world.add_entity(Tile(0));
+    ///
+    /// // unloading the level
+    /// world.add_storage_in::<Tile>(StorageAllocator::global());
+    ///
+    /// assert!(world.borrow::<View<Tile>>().unwrap().is_empty());
+    /// ```
+    pub fn add_storage_in<T: Component + Send + Sync>(&mut self, allocator: StorageAllocator) {
+        self.all_storages.get_mut().add_storage_in::<T>(allocator);
+    }
+    /// Replaces `T`'s storage with an empty one keeping up to `capacity` deleted components in a pool.\
+    /// The components of the previous storage are dropped without being tracked as deleted, entities stay alive.
+    ///
+    /// Instead of being dropped, deleted components wait in the pool until [`SparseSet::take_pooled`] hands them back.
+    /// Components owning heap memory can then be reused without going through the allocator,
+    /// useful for games spawning and despawning many entities each frame.\
+    /// Components tracked as deleted join the pool when the deletion tracking data is cleared.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, EntitiesViewMut, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Trail(Vec<[f32; 2]>);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_pooled_storage::<Trail>(64);
+    ///
+    /// let bullet = world.add_entity(Trail(Vec::with_capacity(32)));
+    /// world.delete_entity(bullet);
+    ///
+    /// world.run(|mut entities: EntitiesViewMut, mut trails: ViewMut<Trail>| {
+    ///     let mut trail = trails
+    ///         .take_pooled()
+    ///         .unwrap_or_else(|| Trail(Vec::with_capacity(32)));
+    ///     trail.0.clear();
+    ///
+    ///     assert!(trail.0.capacity() >= 32);
+    ///
+    ///     entities.add_entity(&mut trails, trail);
+    /// });
+    /// ```
+    ///
+    /// [`SparseSet::take_pooled`]: crate::SparseSet::take_pooled()
+    pub fn add_pooled_storage<T: Component + Send + Sync>(&mut self, capacity: usize) {
+        self.all_storages
+            .get_mut()
+            .add_pooled_storage::<T>(capacity);
+    }
+    /// Replaces `T`'s storage with an empty one keeping a bitset of the entities it contains.\
+    /// The components of the previous storage are dropped without being tracked as deleted, entities stay alive.
+    ///
+    /// Iterating multiple storages with a bitset intersects them, skipping whole blocks of entities missing from one of them.
+    /// Worth it for joins of components only a few entities have, out of many.\
+    /// [`SparseSet::enable_bitset`] adds a bitset to an existing storage.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, IntoIter, IntoWithId, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Boss;
+    ///
+    /// #[derive(Component)]
+    /// struct Enraged;
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_bitset_storage::<Boss>();
+    /// world.add_bitset_storage::<Enraged>();
+    ///
+    /// world.bulk_add_entity((0..1000).map(|_| Boss));
+    /// let boss = world.add_entity((Boss, Enraged));
+    ///
+    /// world.run(|bosses: View<Boss>, enraged: View<Enraged>| {
+    ///     assert_eq!((&bosses, &enraged).iter().ids().collect::<Vec<_>>(), [boss]);
+    /// });
+    /// ```
+    ///
+    /// [`SparseSet::enable_bitset`]: crate::SparseSet::enable_bitset()
+    pub fn add_bitset_storage<T: Component + Send + Sync>(&mut self) {
+        self.all_storages.get_mut().add_bitset_storage::<T>();
+    }
+
+    /// Adds an [`ExternalStorage`], it can then be borrowed with [`ExternalView`] and [`ExternalViewMut`].\
+    /// Replaces the previous `S` if there was one.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `S` storage (exclusive)
+    ///
+    /// ### Panics
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - `S` storage borrow failed.
+    ///
+    /// [`ExternalView`]: crate::ExternalView
+    /// [`ExternalViewMut`]: crate::ExternalViewMut
+    #[track_caller]
+    pub fn add_external_storage<S: ExternalStorage>(&self, storage: S) {
+        self.all_storages
+            .borrow()
+            .unwrap()
+            .add_external_storage(storage);
+    }
+    /// Increments the current tracking cycle and returns the previous value.
+    #[inline]
+    pub(crate) fn get_current(&self) -> TrackingTimestamp {
+        TrackingTimestamp::new(
+            self.counter
+                .fetch_add(1, core::sync::atomic::Ordering::Acquire),
+        )
+    }
+
+    /// Returns a timestamp used to clear tracking information.
+    pub fn get_tracking_timestamp(&self) -> TrackingTimestamp {
+        TrackingTimestamp::new(self.counter.load(core::sync::atomic::Ordering::Acquire))
     }
 }
 
@@ -870,6 +1518,51 @@ impl World {
     pub fn bulk_add_entity<T: BulkAddEntity>(&mut self, source: T) -> BulkEntityIter<'_> {
         self.all_storages.get_mut().bulk_add_entity(source)
     }
+    /// Creates `count` new entities, their components are created in parallel by calling `f` with each entity's position in the batch.\
+    /// `f` must always return a tuple, even for a single component.
+    ///
+    /// Only the creation of the components is parallel, they are then added to the storages in bulk.\
+    /// Uses the local thread pool if there is one.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Particle {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let new_entities = world.par_bulk_add_entity(1000, |i| {
+    ///     (Particle {
+    ///         x: i as f32,
+    ///         y: 0.0,
+    ///     },)
+    /// });
+    /// assert_eq!(new_entities.index_range(), 0..1000);
+    /// ```
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn par_bulk_add_entity<B, F>(&mut self, count: usize, f: F) -> BulkEntityIter<'_>
+    where
+        B: Send,
+        F: Fn(usize) -> B + Send + Sync,
+        Vec<B>: BulkAddEntity,
+    {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let components: Vec<B> = if let Some(thread_pool) = &self.thread_pool {
+            thread_pool.install(|| (0..count).into_par_iter().map(f).collect())
+        } else {
+            (0..count).into_par_iter().map(f).collect()
+        };
+
+        self.all_storages.get_mut().bulk_add_entity(components)
+    }
     /// Adds components to an existing entity.
     /// If the entity already owned a component it will be replaced.
     /// `component` must always be a tuple, even for a single component.
@@ -975,6 +1668,44 @@ impl World {
     pub fn delete_entity(&mut self, entity: EntityId) -> bool {
         self.all_storages.get_mut().delete_entity(entity)
     }
+    /// Deletes an entity with all its components and records `reason` in [`DeletionEvents<R>`], if present.\
+    /// Returns true if the entity were alive.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, DeletionEvents, UniqueView, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Reason {
+    ///     Killed,
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.add_unique(DeletionEvents::<Reason>::new());
+    ///
+    /// let entity = world.add_entity(Enemy);
+    ///
+    /// assert!(world.delete_entity_with(entity, Reason::Killed));
+    /// assert_eq!(
+    ///     world.borrow::<UniqueView<DeletionEvents<Reason>>>().unwrap().len(),
+    ///     1
+    /// );
+    /// ```
+    ///
+    /// [`DeletionEvents<R>`]: crate::DeletionEvents
+    pub fn delete_entity_with<R: Send + Sync + 'static>(
+        &mut self,
+        entity: EntityId,
+        reason: R,
+    ) -> bool {
+        self.all_storages
+            .get_mut()
+            .delete_entity_with(entity, reason)
+    }
     /// Deletes all components of an entity without deleting the entity.
     ///
     /// ### Example
@@ -1079,6 +1810,71 @@ impl World {
     pub fn clear(&mut self) {
         self.all_storages.get_mut().clear();
     }
+    /// Frees the unused memory of all storages and sorts their components by entity index.
+    ///
+    /// Storages that grew and shrank keep their memory until compacted, long running worlds accumulate it.
+    /// Sorting makes iterations over multiple storages go through memory in the same order.\
+    /// Ids don't change, see [`World::compact_entities`] to also move entities to the lowest indices.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entities: Vec<_> = world.bulk_add_entity((0..100).map(Health)).collect();
+    /// for &entity in &entities[1..] {
+    ///     world.delete_entity(entity);
+    /// }
+    ///
+    /// world.compact();
+    ///
+    /// assert_eq!(world.borrow::<View<Health>>().unwrap()[entities[0]].0, 0);
+    /// ```
+    pub fn compact(&mut self) {
+        self.all_storages.get_mut().compact();
+    }
+    /// Moves the alive entities to the lowest free indices then calls [`World::compact`].
+    ///
+    /// Moved entities get a new [`EntityId`], returned in the [`EntityRemap`].
+    /// Storages update their own ids but `EntityId`s stored inside components have to be updated with the remap.\
+    /// The undo history is dropped when entities move.
+    /// Nothing moves with [`EntityRecycling::Never`].
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, EntityId, IntoIter, View, ViewMut, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Target(EntityId);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let first = world.add_entity(());
+    /// let last = world.add_entity(());
+    /// let hunter = world.add_entity(Target(last));
+    /// world.delete_entity(first);
+    ///
+    /// let remap = world.compact_entities();
+    ///
+    /// let new_hunter = remap.map(hunter);
+    /// world.run(|mut targets: ViewMut<Target>| {
+    ///     for target in (&mut targets).iter() {
+    ///         target.0 = remap.map(target.0);
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(new_hunter.index(), 0);
+    /// assert_eq!(world.borrow::<View<Target>>().unwrap()[new_hunter].0, last);
+    /// ```
+    ///
+    /// [`EntityRecycling::Never`]: crate::EntityRecycling::Never
+    pub fn compact_entities(&mut self) -> EntityRemap {
+        self.all_storages.get_mut().compact_entities()
+    }
     /// Clear all deletion and removal tracking data.
     pub fn clear_all_removed_and_deleted(&mut self) {
         self.all_storages.get_mut().clear_all_removed_and_deleted()
@@ -1146,6 +1942,51 @@ impl World {
                 .collect(),
         )
     }
+    /// Returns the timing of the systems with a budget, including how many times they went over it.
+    ///
+    /// ### Borrows
+    ///
+    /// - Scheduler (shared)
+    ///
+    /// ### Panics
+    ///
+    /// - Scheduler borrow failed.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Budget, SystemModificator, Workload, World};
+    /// use std::time::Duration;
+    ///
+    /// fn pathfinding(budget: Budget) {
+    ///     std::thread::sleep(budget.remaining() + Duration::from_millis(1));
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// Workload::new("")
+    ///     .with_system(pathfinding.budget(Duration::from_millis(1)))
+    ///     .add_to_world(&world)
+    ///     .unwrap();
+    ///
+    /// world.run_default_workload().unwrap();
+    ///
+    /// let report = world.budget_report();
+    /// let pathfinding = report.0.values().next().unwrap();
+    /// assert_eq!(pathfinding.runs, 1);
+    /// assert_eq!(pathfinding.overruns, 1);
+    /// ```
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn budget_report(&self) -> BudgetReport {
+        let scheduler = self.scheduler.borrow().unwrap();
+        let budget_report = scheduler
+            .budget_report
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+
+        budget_report
+    }
 
     /// Enable insertion tracking for the given components.
     pub fn track_insertion<T: TupleTrack>(&mut self) -> &mut World {
@@ -1212,6 +2053,7 @@ You can use:
 - [AllStorages] borrow failed.
 - Storage borrow failed.
 - Entity does not have the component.
+- Entity is dead, like [`EntityId::dead`](crate::EntityId::dead).
 
 ### Example
 ```
@@ -1243,6 +2085,10 @@ assert!(*j == &U32(1));
         &self,
         entity: EntityId,
     ) -> Result<T::Out<'_>, error::GetComponent> {
+        if entity.is_dead() {
+            return Err(error::GetComponent::DeadEntity(entity));
+        }
+
         let (all_storages, all_borrow) = unsafe {
             ARef::destructure(
                 self.all_storages
@@ -1433,6 +2279,115 @@ for (i, j) in &mut iter {
             .unwrap()
             .is_alive(entity)
     }
+    /// Returns true if entity matches a living entity.\
+    /// Unlike [`World::is_entity_alive`], only borrows the `World` immutably.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - [`Entities`] (shared)
+    ///
+    /// ### Panics
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - [`Entities`] borrow failed.
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    /// [`Entities`]: crate::Entities
+    #[track_caller]
+    pub fn is_alive(&self, entity: EntityId) -> bool {
+        self.borrow::<EntitiesView<'_>>().unwrap().is_alive(entity)
+    }
+    /// Returns `true` if `entity` has all components of `C`.\
+    /// Missing storages are considered empty.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - `C` storages (shared)
+    ///
+    /// ### Panics
+    ///
+    /// - [`AllStorages`] borrow failed.
+    /// - Storage borrow failed.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct U32(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct USIZE(usize);
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let entity = world.add_entity(U32(0));
+    ///
+    /// assert!(world.has::<U32>(entity));
+    /// assert!(!world.has::<(U32, USIZE)>(entity));
+    /// ```
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    #[track_caller]
+    pub fn has<C: TupleContains>(&self, entity: EntityId) -> bool {
+        self.all_storages.borrow().unwrap().has::<C>(entity)
+    }
+    /// Returns the [`StableId`] of `entity`, generating and adding one if it doesn't have any.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - [`Entities`] (shared)
+    /// - [`StableIds`] unique (exclusive)
+    /// - [`StableId`] storage (exclusive)
+    ///
+    /// ### Panics
+    ///
+    /// - Storage borrow failed.
+    /// - `entity` is not alive.
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    /// [`Entities`]: crate::Entities
+    #[track_caller]
+    pub fn stable_id(&self, entity: EntityId) -> StableId {
+        self.init_unique::<StableIds>();
+
+        let (entities, mut index, mut stable_ids) = self
+            .borrow::<(
+                EntitiesView<'_>,
+                UniqueViewMut<'_, StableIds>,
+                ViewMut<'_, StableId>,
+            )>()
+            .unwrap();
+
+        index.get_or_insert(entity, &entities, &mut stable_ids)
+    }
+    /// Returns the entity with `stable_id`.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    /// - [`StableIds`] unique (exclusive)
+    /// - [`StableId`] storage (shared)
+    ///
+    /// ### Panics
+    ///
+    /// - Storage borrow failed.
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    #[track_caller]
+    pub fn entity_of_stable_id(&self, stable_id: StableId) -> Option<EntityId> {
+        self.init_unique::<StableIds>();
+
+        let (mut index, stable_ids) = self
+            .borrow::<(UniqueViewMut<'_, StableIds>, View<'_, StableId>)>()
+            .unwrap();
+
+        index.entity(stable_id, &stable_ids)
+    }
 
     /// Moves an entity from a `World` to another.
     ///
@@ -1489,6 +2444,678 @@ for (i, j) in &mut iter {
             .get_mut()
             .move_components(other_all_storages, from, to);
     }
+    /// Starts recording `World` and `AllStorages` operations and the value of the components in `registry`.
+    /// Only operations going through `World` or `AllStorages` are recorded: adding and deleting entities,
+    /// adding, deleting and removing components, `strip`, `clear` and `spawn`.
+    /// Components not part of `registry` are ignored.
+    ///
+    /// Changes made through views are not recorded:
+    /// - [`EntitiesViewMut::add_entity`] and [`ViewMut::add_component`]
+    /// - deleting or removing components from a [`ViewMut`]
+    /// - modifying components through [`ViewMut`], [`Mut`] or `&mut`
+    ///
+    /// Replaying a stream recorded from a `World` modified by systems won't reproduce these changes.
+    ///
+    /// If a recording was in progress, it's discarded.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use shipyard::{Component, ReplayRegistry, ReplayStream, World};
+    ///
+    /// #[derive(Component, Debug, PartialEq, Eq)]
+    /// struct U32(u32);
+    ///
+    /// let mut registry = ReplayRegistry::new();
+    /// registry.register::<U32>(
+    ///     |u32| u32.0.to_le_bytes().to_vec(),
+    ///     |bytes| Some(U32(u32::from_le_bytes(bytes.try_into().ok()?))),
+    /// );
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.start_recording(registry.clone());
+    /// let entity = world.add_entity((U32(0),));
+    /// world.add_component(entity, (U32(1),));
+    /// let bytes = world.stop_recording().unwrap().to_bytes();
+    ///
+    /// let mut replayed = World::new();
+    /// replayed
+    ///     .replay(&ReplayStream::from_bytes(&bytes).unwrap(), &registry)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(replayed.get::<&U32>(entity).as_deref(), Ok(&&U32(1)));
+    /// ```
+    ///
+    /// [`EntitiesViewMut::add_entity`]: crate::EntitiesViewMut::add_entity
+    /// [`ViewMut::add_component`]: crate::AddComponent::add_component
+    /// [`ViewMut`]: crate::ViewMut
+    /// [`Mut`]: crate::Mut
+    pub fn start_recording(&mut self, registry: ReplayRegistry) {
+        self.all_storages.get_mut().start_recording(registry);
+    }
+    /// Stops the recording and returns everything recorded since [`World::start_recording`].
+    /// Returns `None` if no recording was in progress.
+    pub fn stop_recording(&mut self) -> Option<ReplayStream> {
+        self.all_storages.get_mut().stop_recording()
+    }
+    /// Returns `true` if a recording is in progress.
+    pub fn is_recording(&mut self) -> bool {
+        self.all_storages.get_mut().is_recording()
+    }
+    /// Starts recording the history [`World::undo`] and [`World::redo`] walk through.\
+    /// See [`AllStorages::enable_undo`].
+    ///
+    /// [`AllStorages::enable_undo`]: crate::AllStorages::enable_undo
+    pub fn enable_undo(&mut self, registry: UndoRegistry) {
+        self.all_storages.get_mut().enable_undo(registry);
+    }
+    /// Stops recording undo history and discards it.
+    pub fn disable_undo(&mut self) {
+        self.all_storages.get_mut().disable_undo();
+    }
+    /// Returns `true` if undo history is recorded.
+    pub fn is_undo_enabled(&mut self) -> bool {
+        self.all_storages.get_mut().is_undo_enabled()
+    }
+    /// Groups all operations `f` does in a single undo step named `name`.\
+    /// Nested transactions are part of the outermost one.
+    ///
+    /// Does nothing more than calling `f` if undo isn't enabled.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, UndoRegistry, World};
+    ///
+    /// #[derive(Component, Clone, Debug, PartialEq)]
+    /// struct Name(&'static str);
+    ///
+    /// let mut world = World::new();
+    /// world.enable_undo(UndoRegistry::new().register::<Name>().clone());
+    ///
+    /// let entity = world.add_entity(Name("cube"));
+    ///
+    /// world.transaction("rename", |world| {
+    ///     world.add_component(entity, Name("box"));
+    /// });
+    ///
+    /// assert!(world.undo());
+    /// assert_eq!(world.get::<&Name>(entity).as_deref(), Ok(&&Name("cube")));
+    /// ```
+    pub fn transaction<R>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        f: impl FnOnce(&mut World) -> R,
+    ) -> R {
+        self.begin_transaction(name);
+        let result = f(self);
+        self.end_transaction();
+
+        result
+    }
+    /// Starts a transaction lasting until [`World::end_transaction`].\
+    /// See [`AllStorages::begin_transaction`].
+    ///
+    /// [`AllStorages::begin_transaction`]: crate::AllStorages::begin_transaction
+    pub fn begin_transaction(&mut self, name: impl Into<Cow<'static, str>>) {
+        self.all_storages.get_mut().begin_transaction(name);
+    }
+    /// Ends a transaction started with [`World::begin_transaction`].
+    pub fn end_transaction(&mut self) {
+        self.all_storages.get_mut().end_transaction();
+    }
+    /// Reverts the last undo step.\
+    /// See [`AllStorages::undo`].
+    ///
+    /// [`AllStorages::undo`]: crate::AllStorages::undo
+    pub fn undo(&mut self) -> bool {
+        self.all_storages.get_mut().undo()
+    }
+    /// Reapplies the last step reverted by [`World::undo`].\
+    /// See [`AllStorages::redo`].
+    ///
+    /// [`AllStorages::redo`]: crate::AllStorages::redo
+    pub fn redo(&mut self) -> bool {
+        self.all_storages.get_mut().redo()
+    }
+    /// Returns `true` if [`World::undo`] would revert a step.
+    pub fn can_undo(&mut self) -> bool {
+        self.all_storages.get_mut().can_undo()
+    }
+    /// Returns `true` if [`World::redo`] would reapply a step.
+    pub fn can_redo(&mut self) -> bool {
+        self.all_storages.get_mut().can_redo()
+    }
+    /// Makes `T` part of the types [`World::tick_digest`] can hash.
+    pub fn register_digest<T: Component + Pod>(&mut self) {
+        self.all_storages.get_mut().register_digest::<T>();
+    }
+    /// Returns a hash of the entity ids and components of `types`.\
+    /// See [`AllStorages::tick_digest`].
+    ///
+    /// ### Panics
+    ///
+    /// - A type wasn't registered with [`World::register_digest`].
+    ///
+    /// ### Example
+    /// ```
+    /// use core::any::TypeId;
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component, Clone, Copy)]
+    /// struct Health(u32);
+    ///
+    /// unsafe impl shipyard::Pod for Health {}
+    ///
+    /// let mut world = World::new();
+    /// world.register_digest::<Health>();
+    /// world.add_entity((Health(10),));
+    ///
+    /// let mut peer = World::new();
+    /// peer.register_digest::<Health>();
+    /// peer.add_entity((Health(10),));
+    ///
+    /// let types = [TypeId::of::<Health>()];
+    /// assert_eq!(world.tick_digest(&types), peer.tick_digest(&types));
+    /// ```
+    #[track_caller]
+    pub fn tick_digest(&mut self, types: &[core::any::TypeId]) -> u64 {
+        self.all_storages.get_mut().tick_digest(types)
+    }
+    /// Makes `T` part of the components [`World::transfer`] moves to the other `World`.
+    pub fn register_transfer<T: Component + Send + Sync>(&mut self) {
+        self.all_storages.get_mut().register_transfer::<T>();
+    }
+    /// Moves `entity` to `other`, returns its id in `other`.\
+    /// See [`AllStorages::transfer`].
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Mesh(u32);
+    ///
+    /// let mut loading = World::new();
+    /// let mut live = World::new();
+    ///
+    /// loading.register_transfer::<Mesh>();
+    ///
+    /// let entity = loading.add_entity((Mesh(0),));
+    /// let new_entity = loading.transfer(entity, &mut live);
+    ///
+    /// assert_eq!(live.get::<&Mesh>(new_entity).as_deref(), Ok(&&Mesh(0)));
+    /// ```
+    #[track_caller]
+    pub fn transfer(&mut self, entity: EntityId, other: &mut World) -> EntityId {
+        self.all_storages.get_mut().transfer(entity, other)
+    }
+    /// Sets what [`World::delete_entity`] does to the entities related through `R`.\
+    /// See [`AllStorages::set_deletion_policy`].
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Children, DeletionPolicy, World};
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.set_deletion_policy::<Children>(DeletionPolicy::Forbid);
+    /// ```
+    pub fn set_deletion_policy<R: Relation>(&mut self, policy: DeletionPolicy) {
+        self.all_storages.get_mut().set_deletion_policy::<R>(policy);
+    }
+    /// Removes `R`'s deletion policy, returns the previous one.
+    pub fn remove_deletion_policy<R: Relation>(&mut self) -> Option<DeletionPolicy> {
+        self.all_storages.get_mut().remove_deletion_policy::<R>()
+    }
+    /// Adds `owner` as a unique and registers it to be notified of the deleted `H` components.\
+    /// See [`AllStorages::add_handle_owner`].
+    pub fn add_handle_owner<H: Component + Send + Sync, O: HandleOwner<H>>(&mut self, owner: O) {
+        self.all_storages.get_mut().add_handle_owner::<H, O>(owner);
+    }
+    /// Makes `T` part of the components [`World::clone_entity`] copies.
+    pub fn register_clone<T: Component + Clone + Send + Sync>(&mut self) {
+        self.all_storages.get_mut().register_clone::<T>();
+    }
+    /// Adds a new entity with a clone of `entity`'s components, returns the new entity.\
+    /// See [`AllStorages::clone_entity`].
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    #[track_caller]
+    pub fn clone_entity(&mut self, entity: EntityId) -> EntityId {
+        self.all_storages.get_mut().clone_entity(entity)
+    }
+    /// Makes `T` addable by [`World::add_default_component`], using `T::default()`.
+    pub fn register_default<T: Component + Default + Send + Sync>(&mut self) {
+        self.all_storages.get_mut().register_default::<T>();
+    }
+    /// Adds the default value of the component identified by `type_id` to `entity`.\
+    /// See [`AllStorages::add_default_component`].
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    ///
+    /// [`AllStorages::add_default_component`]: crate::AllStorages::add_default_component
+    #[track_caller]
+    pub fn add_default_component(&mut self, entity: EntityId, type_id: core::any::TypeId) -> bool {
+        self.all_storages
+            .get_mut()
+            .add_default_component(entity, type_id)
+    }
+    /// Adds the default value of the component named `name` to `entity`.\
+    /// See [`AllStorages::add_default_component_by_name`].
+    ///
+    /// ### Panics
+    ///
+    /// - `entity` is not alive.
+    ///
+    /// [`AllStorages::add_default_component_by_name`]: crate::AllStorages::add_default_component_by_name
+    #[track_caller]
+    pub fn add_default_component_by_name(&mut self, entity: EntityId, name: &str) -> bool {
+        self.all_storages
+            .get_mut()
+            .add_default_component_by_name(entity, name)
+    }
+    /// Replaces the storage of the component named like `New` by a storage of `New`, converting each component with `convert`.\
+    /// See [`AllStorages::migrate_storage`].
+    ///
+    /// [`AllStorages::migrate_storage`]: crate::AllStorages::migrate_storage
+    pub fn migrate_storage<Old, New>(
+        &mut self,
+        convert: impl FnMut(Old) -> New,
+    ) -> Result<usize, error::MigrateStorage>
+    where
+        Old: Component + Send + Sync,
+        New: Component + Send + Sync,
+    {
+        self.all_storages.get_mut().migrate_storage(convert)
+    }
+    /// Applies the options `T` declared with `#[derive(Component)]` that concern the `World`.\
+    /// See [`ComponentRegistrar`].
+    ///
+    /// [`ComponentRegistrar`]: crate::ComponentRegistrar
+    pub fn register_components<T: TupleRegisterComponent>(&mut self) {
+        self.all_storages.get_mut().register_components::<T>();
+    }
+    /// When `strict`, borrowing a view of a component without storage errors instead of creating the storage.\
+    /// See [`AllStorages::set_strict`].
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, View, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Position;
+    ///
+    /// #[derive(Component)]
+    /// struct Pos;
+    ///
+    /// let mut world = World::new();
+    /// world.set_strict(true);
+    /// world.register_components::<Position>();
+    ///
+    /// assert!(world.borrow::<View<Position>>().is_ok());
+    /// assert!(world.borrow::<View<Pos>>().is_err());
+    /// ```
+    pub fn set_strict(&mut self, strict: bool) {
+        self.all_storages.get_mut().set_strict(strict);
+    }
+    /// Returns `true` if borrowing a view of an unregistered component errors, see [`World::set_strict`].
+    pub fn is_strict(&self) -> bool {
+        self.all_storages.borrow().unwrap().is_strict()
+    }
+    /// Returns the mask of the storages `entity` has a component in.\
+    /// See [`AllStorages::signature`].
+    pub fn signature(&mut self, entity: EntityId) -> ComponentMask {
+        self.all_storages.get_mut().signature(entity)
+    }
+    /// Returns the mask of `T`'s storages.
+    pub fn mask_of<T: TupleSignature>(&mut self) -> ComponentMask {
+        self.all_storages.get_mut().mask_of::<T>()
+    }
+    /// Returns the entities with exactly the components in `T`, no more, no less.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, World};
+    ///
+    /// #[derive(Component)]
+    /// struct Position;
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity;
+    ///
+    /// let mut world = World::new();
+    ///
+    /// world.add_entity((Position,));
+    /// let entity = world.add_entity((Position, Velocity));
+    ///
+    /// assert_eq!(world.query_exact::<(Position, Velocity)>(), [entity]);
+    /// assert_eq!(world.query_at_least::<Position>().len(), 2);
+    /// ```
+    pub fn query_exact<T: TupleSignature>(&mut self) -> Vec<EntityId> {
+        self.all_storages.get_mut().query_exact::<T>()
+    }
+    /// Returns the entities with at least the components in `T`.
+    pub fn query_at_least<T: TupleSignature>(&mut self) -> Vec<EntityId> {
+        self.all_storages.get_mut().query_at_least::<T>()
+    }
+    /// Deletes `entity` and all its descendants, `entity` is detached from its parent.\
+    /// Returns `false` if `entity` wasn't alive.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Children, EntitiesViewMut, Hierarchy, Parent, ViewMut, World};
+    ///
+    /// let mut world = World::new();
+    ///
+    /// let (root, child) = world.run(
+    ///     |mut hierarchy: (EntitiesViewMut, ViewMut<Parent>, ViewMut<Children>)| {
+    ///         let root = hierarchy.0.add_entity((), ());
+    ///         let child = hierarchy.attach_new(root);
+    ///
+    ///         (root, child)
+    ///     },
+    /// );
+    ///
+    /// assert!(world.despawn_recursive(root));
+    /// assert!(!world.is_entity_alive(child));
+    /// ```
+    pub fn despawn_recursive(&mut self, entity: EntityId) -> bool {
+        self.all_storages.get_mut().despawn_recursive(entity)
+    }
+    /// Applies the commands queued in the [`CommandBuffer`], if there is one.
+    ///
+    /// [`CommandBuffer`]: crate::CommandBuffer
+    pub fn apply_commands(&mut self) {
+        self.all_storages.get_mut().apply_commands();
+    }
+    /// Returns a [`StorageHandle`] to `T`'s storage, creating the storage if it doesn't exist.\
+    /// The handle can then borrow views without looking the storage up.
+    ///
+    /// ### Borrows
+    ///
+    /// - [`AllStorages`] (shared)
+    ///
+    /// ### Panics
+    ///
+    /// - [`AllStorages`] borrow failed.
+    ///
+    /// [`AllStorages`]: crate::AllStorages
+    #[track_caller]
+    pub fn storage_handle<T: Component + Send + Sync>(&self) -> StorageHandle<T> {
+        self.all_storages.borrow().unwrap().storage_handle::<T>()
+    }
+    /// Plays back `stream` in this `World`.
+    /// `registry` has to know all components present in the stream.
+    /// The stream should be played in an empty `World` to get the same `EntityId`s as when it was recorded.
+    ///
+    /// ### Errors
+    ///
+    /// - A component isn't part of `registry`.
+    /// - A component's data couldn't be decoded.
+    /// - An entity couldn't be spawned with its recorded id.
+    /// - A component is added to an entity that isn't alive.
+    pub fn replay(
+        &mut self,
+        stream: &ReplayStream,
+        registry: &ReplayRegistry,
+    ) -> Result<(), error::Replay> {
+        self.all_storages.get_mut().replay(stream, registry)
+    }
+    /// Saves all alive entities and the components in `registry` in a compact binary format.\
+    /// See [`AllStorages::snapshot`].
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn snapshot(&mut self, registry: &SnapshotRegistry) -> Vec<u8> {
+        self.all_storages.get_mut().snapshot(registry)
+    }
+    /// Saves the alive entities and the components in `registry` selected by `filter`.\
+    /// Components of entities that aren't saved are skipped.
+    /// See [`SnapshotFilter`].
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn snapshot_filtered(
+        &mut self,
+        registry: &SnapshotRegistry,
+        filter: &SnapshotFilter<'_>,
+    ) -> Vec<u8> {
+        self.all_storages
+            .get_mut()
+            .snapshot_filtered(registry, filter)
+    }
+    /// Loads a snapshot created by [`World::snapshot`].\
+    /// It should be loaded in an empty `World` to get the same `EntityId`s as when it was saved.
+    /// See [`AllStorages::load_snapshot`].
+    ///
+    /// ### Errors
+    ///
+    /// - The bytes aren't a valid snapshot.
+    /// - A component isn't part of `registry`.
+    /// - A component's layout hash doesn't match and there is no migration for it.
+    /// - A component couldn't be deserialized.
+    /// - An entity couldn't be spawned with its saved id.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn load_snapshot(
+        &mut self,
+        bytes: &[u8],
+        registry: &SnapshotRegistry,
+    ) -> Result<(), error::Snapshot> {
+        self.all_storages.get_mut().load_snapshot(bytes, registry)
+    }
+    /// Loads a snapshot created by [`World::snapshot`], giving a new id to all its entities.\
+    /// Unlike [`World::load_snapshot`], the `World` doesn't have to be empty.
+    /// Returns the new id of each saved entity.
+    /// See [`AllStorages::load_snapshot_remapped`].
+    ///
+    /// ### Errors
+    ///
+    /// - The bytes aren't a valid snapshot.
+    /// - A component isn't part of `registry`.
+    /// - A component's layout hash doesn't match and there is no migration for it.
+    /// - A component couldn't be deserialized.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn load_snapshot_remapped(
+        &mut self,
+        bytes: &[u8],
+        registry: &SnapshotRegistry,
+    ) -> Result<EntityIdMap, error::Snapshot> {
+        self.all_storages
+            .get_mut()
+            .load_snapshot_remapped(bytes, registry)
+    }
+    /// Applies the differences between two snapshots computed by [`WorldDiff::between`].\
+    /// The `World` is expected to be in the state of the older snapshot.
+    ///
+    /// See [`AllStorages::apply_diff`].
+    ///
+    /// ### Errors
+    ///
+    /// - The newer snapshot was saved by a newer version of the registry.
+    /// - A component isn't part of `registry`.
+    /// - A component's layout hash doesn't match and there is no migration for it.
+    /// - A component couldn't be deserialized.
+    /// - An added entity couldn't be spawned with its id.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn apply_diff(
+        &mut self,
+        diff: &WorldDiff,
+        registry: &SnapshotRegistry,
+    ) -> Result<(), error::Snapshot> {
+        self.all_storages.get_mut().apply_diff(diff, registry)
+    }
+    /// Runs `f` then rolls back the structural changes it made, integration tests can share an expensive to build `World` this way.
+    ///
+    /// - entities created in the scope are deleted with all their components
+    /// - deleted entities come back with the same `EntityId`
+    /// - components in `registry` get their value from before the scope back
+    /// - storages created in the scope are removed, uniques included
+    ///
+    /// Components not in `registry` of entities alive before the scope and uniques that already existed are left as is.\
+    /// If `f` panics, the `World` is rolled back before the panic resumes.
+    ///
+    /// ### Errors
+    ///
+    /// - A component in `registry` couldn't be deserialized.
+    ///
+    /// ### Example
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use shipyard::{Component, SnapshotRegistry, View, World};
+    ///
+    /// #[derive(Component, Serialize, Deserialize, PartialEq, Eq, Debug)]
+    /// struct Health(u32);
+    ///
+    /// let mut registry = SnapshotRegistry::new();
+    /// registry.register::<Health>();
+    ///
+    /// let mut world = World::new();
+    /// let player = world.add_entity((Health(10),));
+    ///
+    /// world
+    ///     .scope(&registry, |world| {
+    ///         world.add_entity((Health(5),));
+    ///         world.get::<&mut Health>(player).unwrap().0 = 0;
+    ///     })
+    ///     .unwrap();
+    ///
+    /// world.run(|healths: View<Health>| {
+    ///     assert_eq!(healths.len(), 1);
+    ///     assert_eq!(healths[player], Health(10));
+    /// });
+    /// ```
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn scope<R>(
+        &mut self,
+        registry: &SnapshotRegistry,
+        f: impl FnOnce(&mut World) -> R,
+    ) -> Result<R, error::Snapshot> {
+        let checkpoint = Checkpoint::new(self.all_storages.get_mut(), registry);
+
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| f(self)));
+
+        let rollback = checkpoint.rollback(self.all_storages.get_mut(), registry);
+
+        match result {
+            Ok(result) => rollback.map(|_| result),
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    }
+    /// Makes the components in `registry` replicated.\
+    /// See [`AllStorages::enable_replication`].
+    ///
+    /// ### Example
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use shipyard::{Component, EntityIdMap, ReplicationRegistry, TrackingTimestamp, World};
+    ///
+    /// #[derive(Component, Serialize, Deserialize, PartialEq, Eq, Debug)]
+    /// struct Health(u32);
+    ///
+    /// let mut registry = ReplicationRegistry::new();
+    /// registry.register::<Health>();
+    ///
+    /// let mut server = World::new();
+    /// server.enable_replication(registry.clone());
+    /// let entity = server.add_entity((Health(10),));
+    ///
+    /// let delta = server.collect_replication_delta(TrackingTimestamp::origin());
+    /// let bytes = delta.to_bytes();
+    ///
+    /// let mut client = World::new();
+    /// let mut map = EntityIdMap::default();
+    /// let delta = shipyard::ReplicationDelta::from_bytes(&bytes).unwrap();
+    /// client.apply_replication_delta(&delta, &registry, &mut map).unwrap();
+    ///
+    /// let local = map.get(entity).unwrap();
+    /// assert_eq!(client.get::<&Health>(local).as_deref(), Ok(&&Health(10)));
+    ///
+    /// server.get::<&mut Health>(entity).unwrap().0 = 5;
+    /// let delta = server.collect_replication_delta(delta.tick());
+    /// client.apply_replication_delta(&delta, &registry, &mut map).unwrap();
+    ///
+    /// assert_eq!(client.get::<&Health>(local).as_deref(), Ok(&&Health(5)));
+    /// ```
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    pub fn enable_replication(&mut self, registry: ReplicationRegistry) {
+        self.all_storages.get_mut().enable_replication(registry);
+    }
+    /// Returns the changes of the replicated components since `since_tick`.\
+    /// See [`AllStorages::collect_replication_delta`].
+    ///
+    /// ### Panics
+    ///
+    /// - Replication isn't enabled.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    #[track_caller]
+    pub fn collect_replication_delta(&mut self, since_tick: TrackingTimestamp) -> ReplicationDelta {
+        self.all_storages
+            .get_mut()
+            .collect_replication_delta(since_tick)
+    }
+    /// Returns the changes of the replicated components `client` should see since the last delta collected for it.\
+    /// See [`AllStorages::collect_replication_delta_for`].
+    ///
+    /// ### Panics
+    ///
+    /// - Replication isn't enabled.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    #[track_caller]
+    pub fn collect_replication_delta_for(
+        &mut self,
+        client: &mut ReplicationClient,
+    ) -> ReplicationDelta {
+        self.all_storages
+            .get_mut()
+            .collect_replication_delta_for(client)
+    }
+    /// Removes the deletion and removal tracking of the replicated components older than `tick`.\
+    /// See [`AllStorages::clear_replication_older_than`].
+    ///
+    /// ### Panics
+    ///
+    /// - Replication isn't enabled.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    #[track_caller]
+    pub fn clear_replication_older_than(&mut self, tick: TrackingTimestamp) {
+        self.all_storages
+            .get_mut()
+            .clear_replication_older_than(tick);
+    }
+    /// Applies a delta created by [`World::collect_replication_delta`].\
+    /// See [`AllStorages::apply_replication_delta`].
+    ///
+    /// ### Errors
+    ///
+    /// - A component isn't part of `registry`.
+    /// - A component couldn't be deserialized.
+    #[cfg(feature = "replication")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replication")))]
+    pub fn apply_replication_delta(
+        &mut self,
+        delta: &ReplicationDelta,
+        registry: &ReplicationRegistry,
+        map: &mut EntityIdMap,
+    ) -> Result<(), error::Replication> {
+        self.all_storages
+            .get_mut()
+            .apply_replication_delta(delta, registry, map)
+    }
 }
 
 impl core::fmt::Debug for World {
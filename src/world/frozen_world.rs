@@ -0,0 +1,184 @@
+use crate::all_storages::AllStorages;
+use crate::atomic_refcell::SharedBorrow;
+use crate::component::{Component, Unique};
+use crate::entities::Entities;
+use crate::error;
+use crate::sparse_set::SparseSet;
+use crate::storage::{SBox, Storage, StorageId};
+use crate::tracking::{Tracking, TrackingTimestamp};
+use crate::unique::UniqueStorage;
+use crate::views::{EntitiesView, UniqueView, View};
+use crate::ShipHashMap;
+use core::any::type_name;
+
+/// Read-only access to a [`World`](crate::World).
+///
+/// The `World` is borrowed exclusively while it's frozen, no entity or storage can be added or removed.\
+/// In exchange views are handed out without any borrow bookkeeping, any number of threads can borrow the same storages.\
+/// Created by [`World::freeze`](crate::World::freeze).
+///
+/// Only storages that existed when the `World` was frozen can be borrowed.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Component, IntoIter, View, World};
+///
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// let mut world = World::new();
+/// world.add_entity(Position(1.0));
+///
+/// let frozen = world.freeze();
+///
+/// std::thread::scope(|scope| {
+///     for _ in 0..2 {
+///         scope.spawn(|| {
+///             let positions = frozen.borrow::<View<Position>>().unwrap();
+///
+///             assert_eq!(positions.iter().map(|pos| pos.0).sum::<f32>(), 1.0);
+///         });
+///     }
+/// });
+/// ```
+pub struct FrozenWorld<'w> {
+    storages: ShipHashMap<StorageId, (&'w SBox, &'w dyn Storage)>,
+    current: TrackingTimestamp,
+}
+
+// SAFE only storages accessible from any thread are kept and they can't be borrowed exclusively
+unsafe impl Send for FrozenWorld<'_> {}
+unsafe impl Sync for FrozenWorld<'_> {}
+
+impl<'w> FrozenWorld<'w> {
+    pub(crate) fn new(all_storages: &'w mut AllStorages, current: TrackingTimestamp) -> Self {
+        let storages = all_storages
+            .storages
+            .iter_mut()
+            .filter_map(|(storage_id, storage)| {
+                let storage: &'w SBox = storage;
+                // SAFE the box lives as long as the `AllStorages`
+                let inner: &'w mut dyn Storage = unsafe { &mut *storage.0 }.get_mut_send_sync()?;
+
+                Some((*storage_id, (storage, &*inner)))
+            })
+            .collect();
+
+        FrozenWorld { storages, current }
+    }
+    /// Borrows the requested storages without locking them, see [`World::borrow`](crate::World::borrow).\
+    /// `V` can be a [`View`], [`UniqueView`], [`EntitiesView`] or a tuple of them.
+    ///
+    /// ### Errors
+    ///
+    /// - Storage didn't exist when the `World` was frozen.
+    /// - An other storage type is stored at the same [`StorageId`].
+    /// - The view's tracking doesn't match the component's.
+    pub fn borrow<V: FrozenBorrow>(&self) -> Result<V::View<'w>, error::GetStorage> {
+        V::frozen_borrow(self)
+    }
+    fn storage<S: 'static>(&self) -> Result<&'w S, error::GetStorage> {
+        let storage_id = StorageId::of::<S>();
+
+        let (sbox, storage) = match self.storages.get(&storage_id) {
+            Some(storage) => *storage,
+            None => {
+                return Err(error::GetStorage::MissingStorage {
+                    name: Some(type_name::<S>()),
+                    id: storage_id,
+                })
+            }
+        };
+
+        sbox.check::<S>(storage_id)?;
+
+        Ok(storage.as_any().downcast_ref().unwrap())
+    }
+}
+
+/// Views that can be borrowed from a [`FrozenWorld`].
+pub trait FrozenBorrow {
+    #[allow(missing_docs)]
+    type View<'a>;
+
+    /// Builds the view, the storages are only read.
+    fn frozen_borrow<'a>(world: &FrozenWorld<'a>) -> Result<Self::View<'a>, error::GetStorage>;
+}
+
+impl FrozenBorrow for EntitiesView<'_> {
+    type View<'a> = EntitiesView<'a>;
+
+    #[inline]
+    fn frozen_borrow<'a>(world: &FrozenWorld<'a>) -> Result<Self::View<'a>, error::GetStorage> {
+        Ok(EntitiesView {
+            entities: world.storage::<Entities>()?,
+            borrow: None,
+            all_borrow: None,
+        })
+    }
+}
+
+impl<T: Send + Sync + Component, Track> FrozenBorrow for View<'_, T, Track>
+where
+    Track: Tracking,
+{
+    type View<'a> = View<'a, T, Track>;
+
+    #[inline]
+    fn frozen_borrow<'a>(world: &FrozenWorld<'a>) -> Result<Self::View<'a>, error::GetStorage> {
+        let sparse_set = world.storage::<SparseSet<T>>()?;
+
+        sparse_set.check_tracking::<Track>()?;
+
+        Ok(View::new(
+            sparse_set,
+            SharedBorrow::frozen(),
+            None,
+            None,
+            world.current,
+        ))
+    }
+}
+
+impl<T: Send + Sync + Unique> FrozenBorrow for UniqueView<'_, T> {
+    type View<'a> = UniqueView<'a, T>;
+
+    #[inline]
+    fn frozen_borrow<'a>(world: &FrozenWorld<'a>) -> Result<Self::View<'a>, error::GetStorage> {
+        let unique = world.storage::<UniqueStorage<T>>()?;
+
+        Ok(UniqueView {
+            unique,
+            borrow: None,
+            all_borrow: None,
+            last_insertion: unique.last_insert,
+            last_modification: unique.last_modification,
+            current: world.current,
+        })
+    }
+}
+
+macro_rules! impl_frozen_borrow {
+    ($(($type: ident, $index: tt))+) => {
+        impl<$($type: FrozenBorrow),+> FrozenBorrow for ($($type,)+) {
+            type View<'a> = ($($type::View<'a>,)+);
+
+            #[inline]
+            fn frozen_borrow<'a>(world: &FrozenWorld<'a>) -> Result<Self::View<'a>, error::GetStorage> {
+                Ok(($($type::frozen_borrow(world)?,)+))
+            }
+        }
+    }
+}
+
+macro_rules! frozen_borrow {
+    ($(($type: ident, $index: tt))*;($type1: ident, $index1: tt) $(($queue_type: ident, $queue_index: tt))*) => {
+        impl_frozen_borrow![$(($type, $index))*];
+        frozen_borrow![$(($type, $index))* ($type1, $index1); $(($queue_type, $queue_index))*];
+    };
+    ($(($type: ident, $index: tt))*;) => {
+        impl_frozen_borrow![$(($type, $index))*];
+    }
+}
+
+frozen_borrow![(A, 0); (B, 1) (C, 2) (D, 3) (E, 4) (F, 5) (G, 6) (H, 7) (I, 8) (J, 9)];
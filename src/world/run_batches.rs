@@ -1,7 +1,15 @@
 use crate::error;
-use crate::scheduler::{Batches, Label};
+use crate::scheduler::{Batches, Decision, Label};
 use crate::world::World;
 use alloc::boxed::Box;
+#[cfg(feature = "parallel")]
+use alloc::vec::Vec;
+#[cfg(feature = "parallel")]
+use core::cell::UnsafeCell;
+#[cfg(feature = "parallel")]
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(feature = "parallel")]
+use std::sync::{Condvar, Mutex};
 
 impl World {
     #[cfg(feature = "parallel")]
@@ -18,106 +26,72 @@ impl World {
         #[cfg(feature = "tracing")]
         let _parent_span = parent_span.enter();
 
-        let run_batch = || -> Result<(), error::RunWorkload> {
-            for (batch, batches_run_if) in batches.parallel.iter().zip(&batches.parallel_run_if) {
-                let mut result = Ok(());
-                let run_if = (
-                    if let Some(run_if_index) = batches_run_if.0 {
-                        if let Some(run_if) = &batches.sequential_run_if[run_if_index] {
-                            (run_if)(self).map_err(|err| {
-                                error::RunWorkload::Run((
-                                    system_names[batch.0.unwrap()].clone(),
-                                    err,
-                                ))
-                            })?
-                        } else {
-                            true
-                        }
-                    } else {
-                        true
-                    },
-                    batches_run_if
-                        .1
-                        .iter()
-                        .map(|run_if_index| {
-                            if let Some(run_if) = &batches.sequential_run_if[*run_if_index] {
-                                (run_if)(self).map_err(|err| {
-                                    error::RunWorkload::Run((
-                                        system_names[batches.sequential[*run_if_index]].clone(),
-                                        err,
-                                    ))
-                                })
-                            } else {
-                                Ok(true)
-                            }
-                        })
-                        .collect::<Result<alloc::vec::Vec<_>, error::RunWorkload>>()?,
-                );
-
-                let mut start = 0;
-                let single_system = batch.0.filter(|_| run_if.0).or_else(|| {
-                    let system = batch.1.first().copied().filter(|_| run_if.1[0]);
-
-                    if system.is_some() {
-                        start = 1;
-                    }
-
-                    system
-                });
-
-                rayon::in_place_scope(|scope| {
-                    // This check exists to avoid spawning a parallel job when possible.
-                    // On wasm it causes a "condvar wait not supported" error.
-                    if start < batch.1.len() {
-                        scope.spawn(|_| {
-                            use rayon::prelude::*;
-
-                            result = batch.1[start..]
-                                .par_iter()
-                                .zip(&run_if.1[start..])
-                                .try_for_each(|(&index, should_run)| {
-                                    if !should_run {
-                                        return Ok(());
-                                    }
-
-                                    #[cfg(feature = "tracing")]
-                                    {
-                                        self.run_single_system(
-                                            systems,
-                                            system_names,
-                                            &parent_span,
-                                            index,
-                                        )
-                                    }
-                                    #[cfg(not(feature = "tracing"))]
-                                    {
-                                        self.run_single_system(systems, system_names, index)
-                                    }
-                                });
-                        });
-                    }
+        let graph = GraphRun {
+            world: self,
+            systems,
+            system_names,
+            batches,
+            #[cfg(feature = "tracing")]
+            parent_span: &parent_span,
+            dependency_count: batches
+                .graph
+                .dependency_count
+                .iter()
+                .map(|&count| AtomicUsize::new(count))
+                .collect(),
+            local_ready: Mutex::new(Vec::new()),
+            local_wakeup: Condvar::new(),
+            aborted: AtomicBool::new(false),
+            done: AtomicUsize::new(0),
+            errors: batches
+                .sequential
+                .iter()
+                .map(|_| ErrorSlot(UnsafeCell::new(None)))
+                .collect(),
+        };
 
-                    if let Some(index) = single_system {
-                        #[cfg(feature = "tracing")]
-                        self.run_single_system(systems, system_names, &parent_span, index)?;
-                        #[cfg(not(feature = "tracing"))]
-                        self.run_single_system(systems, system_names, index)?;
+        let run_graph = || {
+            rayon::in_place_scope(|scope| {
+                for (position, &count) in batches.graph.dependency_count.iter().enumerate() {
+                    if count == 0 {
+                        graph.start(scope, position);
                     }
+                }
 
-                    Ok(())
-                })?;
-
-                result?;
-            }
+                // systems borrowing `!Send` or `!Sync` storages run on this thread
+                // the others are picked up by rayon's workers as soon as they are ready
+                if batches.graph.local.contains(&true) {
+                    loop {
+                        // when this thread is a rayon worker, the jobs it spawned are in its own queue
+                        // they have to run before blocking or a single threaded pool would never progress
+                        while matches!(rayon::yield_now(), Some(rayon::Yield::Executed)) {}
 
-            Ok(())
+                        match graph.next_local() {
+                            Some(position) => graph.run(scope, position),
+                            None => break,
+                        }
+                    }
+                }
+            });
         };
 
         if let Some(thread_pool) = &self.thread_pool {
-            thread_pool.scope(|_| run_batch())
+            thread_pool.in_place_scope(|_| run_graph());
         } else {
             // Use non local ThreadPool
-            run_batch()
+            run_graph();
+        }
+
+        let errors: Vec<_> = graph
+            .errors
+            .into_iter()
+            .filter_map(|error| error.0.into_inner())
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(error::RunWorkload::from_runs(errors))
         }
     }
 
@@ -152,11 +126,13 @@ impl World {
 
                 #[cfg(feature = "tracing")]
                 {
-                    self.run_single_system(systems, system_names, &parent_span, index)
+                    self.run_single_system(systems, system_names, batches, &parent_span, index)
+                        .map_err(error::RunWorkload::Run)
                 }
                 #[cfg(not(feature = "tracing"))]
                 {
-                    self.run_single_system(systems, system_names, index)
+                    self.run_single_system(systems, system_names, batches, index)
+                        .map_err(error::RunWorkload::Run)
                 }
             })
     }
@@ -166,16 +142,158 @@ impl World {
         &self,
         systems: &[Box<dyn Fn(&World) -> Result<(), error::Run> + Send + Sync>],
         system_names: &[Box<dyn Label>],
+        batches: &Batches,
         #[cfg(feature = "tracing")] parent_span: &tracing::Span,
         index: usize,
-    ) -> Result<(), error::RunWorkload> {
+    ) -> Result<(), (Box<dyn Label>, error::Run)> {
         #[cfg(feature = "tracing")]
         let system_span =
             tracing::info_span!(parent: parent_span.clone(), "system", name = ?system_names[index]);
         #[cfg(feature = "tracing")]
         let _system_span = system_span.enter();
 
-        (systems[index])(self)
-            .map_err(|err| error::RunWorkload::Run((system_names[index].clone(), err)))
+        let mut retries = 0;
+
+        loop {
+            #[cfg(feature = "borrow_debug")]
+            let result = crate::borrow_debug::with_current_system(&*system_names[index], || {
+                (systems[index])(self)
+            });
+            #[cfg(not(feature = "borrow_debug"))]
+            let result = (systems[index])(self);
+
+            let err = match result {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+
+            let decision = match batches.on_error.get(&index) {
+                Some(on_error) => (on_error)(&*system_names[index], &err, self),
+                None => Decision::Abort,
+            };
+
+            match decision {
+                Decision::Continue => return Ok(()),
+                Decision::Retry if retries < Decision::MAX_RETRIES => retries += 1,
+                Decision::Retry | Decision::Abort => {
+                    return Err((system_names[index].clone(), err))
+                }
+            }
+        }
+    }
+}
+
+/// State of a workload run in parallel.
+///
+/// Each system starts as soon as the systems it depends on are done.
+#[cfg(feature = "parallel")]
+#[allow(clippy::type_complexity)]
+struct GraphRun<'a> {
+    world: &'a World,
+    systems: &'a [Box<dyn Fn(&World) -> Result<(), error::Run> + Send + Sync + 'static>],
+    system_names: &'a [Box<dyn Label>],
+    batches: &'a Batches,
+    #[cfg(feature = "tracing")]
+    parent_span: &'a tracing::Span,
+    /// Number of systems each system is still waiting for
+    dependency_count: Vec<AtomicUsize>,
+    /// Systems that have to run on the thread running the workload and are ready to run
+    local_ready: Mutex<Vec<usize>>,
+    /// Wakes up the thread running the workload when a local system is ready or all systems are done
+    local_wakeup: Condvar,
+    /// A system failed, systems not started yet won't run
+    aborted: AtomicBool,
+    /// Number of systems done, skipped systems included
+    done: AtomicUsize,
+    errors: Vec<ErrorSlot>,
+}
+
+/// Error of a single system.
+#[cfg(feature = "parallel")]
+struct ErrorSlot(UnsafeCell<Option<(Box<dyn Label>, error::Run)>>);
+
+// SAFE a slot is only written by the job running its system and only read once all jobs are done
+#[cfg(feature = "parallel")]
+unsafe impl Sync for ErrorSlot {}
+
+#[cfg(feature = "parallel")]
+impl GraphRun<'_> {
+    fn start<'s>(&'s self, scope: &rayon::Scope<'s>, position: usize) {
+        if self.batches.graph.local[position] {
+            self.local_ready.lock().unwrap().push(position);
+            self.local_wakeup.notify_one();
+        } else {
+            scope.spawn(move |scope| self.run(scope, position));
+        }
+    }
+
+    /// Blocks until a local system is ready, returns `None` once all systems are done.
+    fn next_local(&self) -> Option<usize> {
+        let mut local_ready = self.local_ready.lock().unwrap();
+
+        loop {
+            if let Some(position) = local_ready.pop() {
+                return Some(position);
+            }
+
+            if self.done.load(Ordering::Acquire) == self.batches.sequential.len() {
+                return None;
+            }
+
+            local_ready = self.local_wakeup.wait(local_ready).unwrap();
+        }
+    }
+
+    fn run<'s>(&'s self, scope: &rayon::Scope<'s>, position: usize) {
+        // like the sequential executor, a failure stops the workload
+        // systems already running finish but no new system starts
+        if !self.aborted.load(Ordering::Acquire) {
+            if let Err(error) = self.run_system(position) {
+                self.aborted.store(true, Ordering::Release);
+
+                // SAFE this job is the only one accessing this slot
+                unsafe {
+                    *self.errors[position].0.get() = Some(error);
+                }
+            }
+        }
+
+        for &dependent in &self.batches.graph.dependents[position] {
+            if self.dependency_count[dependent].fetch_sub(1, Ordering::AcqRel) == 1 {
+                self.start(scope, dependent);
+            }
+        }
+
+        if self.done.fetch_add(1, Ordering::AcqRel) + 1 == self.batches.sequential.len() {
+            // taking the lock makes sure the thread running the workload is either waiting or will see `done`
+            let _local_ready = self.local_ready.lock().unwrap();
+            self.local_wakeup.notify_one();
+        }
+    }
+
+    fn run_system(&self, position: usize) -> Result<(), (Box<dyn Label>, error::Run)> {
+        let index = self.batches.sequential[position];
+
+        if let Some(run_if) = &self.batches.sequential_run_if[position] {
+            if !(run_if)(self.world).map_err(|err| (self.system_names[index].clone(), err))? {
+                return Ok(());
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            self.world.run_single_system(
+                self.systems,
+                self.system_names,
+                self.batches,
+                self.parent_span,
+                index,
+            )
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            self.world
+                .run_single_system(self.systems, self.system_names, self.batches, index)
+        }
     }
 }
@@ -0,0 +1,112 @@
+use crate::borrow::{check_access, BorrowInfo, WorldBorrow};
+use crate::error;
+use crate::scheduler::TypeInfo;
+use crate::system::System;
+use crate::world::World;
+use alloc::vec::Vec;
+
+/// Restricted access to a [`World`].
+///
+/// Only the storages declared when the `SubWorld` was created can be borrowed,
+/// it can be given to code that shouldn't access anything else, like plugins.\
+/// Created by [`World::sub_world`].
+///
+/// ### Example
+/// ```
+/// use shipyard::{AllStoragesViewMut, Component, IntoIter, View, ViewMut, World};
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct Score(u32);
+///
+/// let world = World::new();
+/// let sub_world = world.sub_world::<(ViewMut<Health>, View<Score>)>();
+///
+/// sub_world.run(|mut healths: ViewMut<Health>, _: View<Score>| {
+///     for health in (&mut healths).iter() {
+///         health.0 += 1;
+///     }
+/// });
+///
+/// assert!(sub_world.borrow::<ViewMut<Score>>().is_err());
+/// assert!(sub_world.borrow::<AllStoragesViewMut>().is_err());
+/// ```
+pub struct SubWorld<'w> {
+    world: &'w World,
+    access: Vec<TypeInfo>,
+}
+
+impl<'w> SubWorld<'w> {
+    pub(crate) fn new<V: BorrowInfo>(world: &'w World) -> SubWorld<'w> {
+        let mut access = Vec::new();
+        V::borrow_info(&mut access);
+
+        SubWorld { world, access }
+    }
+    /// Returns the storages this `SubWorld` can borrow.
+    pub fn access(&self) -> &[TypeInfo] {
+        &self.access
+    }
+    /// Returns `true` if everything `V` borrows is part of this `SubWorld`'s access.
+    pub fn is_allowed<V: BorrowInfo>(&self) -> bool {
+        self.check::<V>().is_ok()
+    }
+    /// Borrows the requested storages, see [`World::borrow`].
+    ///
+    /// ### Errors
+    ///
+    /// - A storage isn't part of this `SubWorld`'s access or is borrowed mutably with only shared access.
+    /// - The storage borrow failed.
+    pub fn borrow<V: WorldBorrow + BorrowInfo>(
+        &self,
+    ) -> Result<V::WorldView<'w>, error::GetStorage> {
+        self.check::<V>()?;
+
+        self.world.borrow::<V>()
+    }
+    /// Borrows the requested storages and runs the function, see [`World::run`].
+    ///
+    /// ### Panics
+    ///
+    /// - A storage isn't part of this `SubWorld`'s access or is borrowed mutably with only shared access.
+    /// - The storage borrow failed.
+    #[track_caller]
+    pub fn run<B: BorrowInfo, S: System<(), B>>(&self, system: S) -> S::Return {
+        if let Err(err) = self.check::<B>() {
+            panic!("{:?}", error::Run::GetStorage(err));
+        }
+
+        self.world.run(system)
+    }
+    /// Borrows the requested storages and runs the function with `data`, see [`World::run_with_data`].
+    ///
+    /// ### Panics
+    ///
+    /// - A storage isn't part of this `SubWorld`'s access or is borrowed mutably with only shared access.
+    /// - The storage borrow failed.
+    #[track_caller]
+    pub fn run_with_data<Data, B: BorrowInfo, S: System<(Data,), B>>(
+        &self,
+        system: S,
+        data: Data,
+    ) -> S::Return {
+        if let Err(err) = self.check::<B>() {
+            panic!("{:?}", error::Run::GetStorage(err));
+        }
+
+        self.world.run_with_data(system, data)
+    }
+    fn check<V: BorrowInfo>(&self) -> Result<(), error::GetStorage> {
+        check_access::<V>(&self.access)
+    }
+}
+
+impl core::fmt::Debug for SubWorld<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.access.iter().map(|info| (&info.name, info.mutability)))
+            .finish()
+    }
+}
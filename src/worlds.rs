@@ -0,0 +1,317 @@
+use crate::component::Unique;
+use crate::error;
+use crate::scheduler::AsLabel;
+use crate::world::World;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+/// Unique shared between multiple [`World`]s of a [`Worlds`].
+///
+/// It's accessed with `UniqueView<Shared<T>>`, `T` needs interior mutability to be modified.
+pub struct Shared<T>(Arc<T>);
+
+impl<T> Shared<T> {
+    /// Returns the `Arc` shared by all `World`s.
+    pub fn arc(&self) -> &Arc<T> {
+        &self.0
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared(self.0.clone())
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Send + Sync + 'static> Unique for Shared<T> {}
+
+type Sharer = Box<dyn Fn(&World) + Send + Sync>;
+
+/// Collection of named [`World`]s, like a main, UI and loading screen `World`.
+///
+/// Uniques added with [`Worlds::share_unique`] are present in all `World`s as [`Shared<T>`],
+/// including `World`s inserted later.
+///
+/// ### Example
+/// ```
+/// use shipyard::{Shared, UniqueView, World, Worlds};
+///
+/// struct Assets(Vec<&'static str>);
+///
+/// let mut worlds = Worlds::new();
+/// worlds.insert("main", World::new());
+/// worlds.share_unique(Assets(vec!["player.png"]));
+/// worlds.insert("ui", World::new());
+///
+/// worlds["ui"].run(|assets: UniqueView<Shared<Assets>>| {
+///     assert_eq!(assets.0, ["player.png"]);
+/// });
+/// ```
+#[derive(Default)]
+pub struct Worlds {
+    worlds: Vec<(Cow<'static, str>, World)>,
+    sharers: Vec<Sharer>,
+    #[cfg(feature = "parallel")]
+    thread_pool: Option<rayon::ThreadPool>,
+}
+
+impl Worlds {
+    /// Creates an empty collection.
+    pub fn new() -> Worlds {
+        Worlds::default()
+    }
+    /// Creates an empty collection running its `World`s on `thread_pool`, see [`Worlds::run_workload_all`].
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn with_thread_pool(thread_pool: rayon::ThreadPool) -> Worlds {
+        Worlds {
+            thread_pool: Some(thread_pool),
+            ..Worlds::default()
+        }
+    }
+    /// Removes the [`ThreadPool`](rayon::ThreadPool), the `World`s then run on rayon's global one.
+    #[cfg(feature = "parallel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+    pub fn remove_thread_pool(&mut self) -> Option<rayon::ThreadPool> {
+        self.thread_pool.take()
+    }
+    /// Adds `world` with `name`, it receives all shared uniques.\
+    /// Returns the `World` previously stored with this name.
+    pub fn insert(&mut self, name: impl Into<Cow<'static, str>>, world: World) -> Option<World> {
+        let name = name.into();
+
+        for sharer in &self.sharers {
+            sharer(&world);
+        }
+
+        match self.index(&name) {
+            Some(index) => Some(core::mem::replace(&mut self.worlds[index].1, world)),
+            None => {
+                self.worlds.push((name, world));
+
+                None
+            }
+        }
+    }
+    /// Removes the `World` named `name` and returns it.\
+    /// Shared uniques stay in the `World`.
+    pub fn remove(&mut self, name: &str) -> Option<World> {
+        let index = self.index(name)?;
+
+        Some(self.worlds.remove(index).1)
+    }
+    /// Returns the `World` named `name`.
+    pub fn get(&self, name: &str) -> Option<&World> {
+        self.index(name).map(|index| &self.worlds[index].1)
+    }
+    /// Returns the `World` named `name`.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut World> {
+        self.index(name).map(move |index| &mut self.worlds[index].1)
+    }
+    /// Returns `true` if a `World` is named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.index(name).is_some()
+    }
+    /// Returns an iterator over all `World`s and their name, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &World)> + '_ {
+        self.worlds.iter().map(|(name, world)| (&**name, world))
+    }
+    /// Returns the number of `World`s.
+    pub fn len(&self) -> usize {
+        self.worlds.len()
+    }
+    /// Returns `true` if there is no `World`.
+    pub fn is_empty(&self) -> bool {
+        self.worlds.is_empty()
+    }
+    /// Adds `unique` to all `World`s as a [`Shared<T>`], all of them point to the same value.\
+    /// If `T` was already shared, it's replaced.
+    pub fn share_unique<T: Send + Sync + 'static>(&mut self, unique: T) -> Arc<T> {
+        let unique = Arc::new(unique);
+        let shared = Shared(unique.clone());
+
+        for (_, world) in &self.worlds {
+            world.add_unique(shared.clone());
+        }
+
+        self.sharers
+            .push(Box::new(move |world| world.add_unique(shared.clone())));
+
+        unique
+    }
+    /// Runs the workload `label` of the `World` named `world`.
+    ///
+    /// ### Panics
+    ///
+    /// - No `World` is named `world`.
+    ///
+    /// ### Errors
+    ///
+    /// - See [`World::run_workload`].
+    #[track_caller]
+    pub fn run_workload<T>(
+        &self,
+        world: &str,
+        label: impl AsLabel<T>,
+    ) -> Result<(), error::RunWorkload> {
+        self[world].run_workload(label)
+    }
+    /// Runs the default workload of the `World` named `world`.
+    ///
+    /// ### Panics
+    ///
+    /// - No `World` is named `world`.
+    ///
+    /// ### Errors
+    ///
+    /// - See [`World::run_default_workload`].
+    #[track_caller]
+    pub fn run_default_workload(&self, world: &str) -> Result<(), error::RunWorkload> {
+        self[world].run_default_workload()
+    }
+    /// Runs the workload `label` of every `World` at the same time, like multiple match instances on a server.\
+    /// Returns each `World`'s result, in insertion order.
+    ///
+    /// The `World`s are isolated from each other, a workload can only borrow its own `World`'s storages.
+    /// The only state they have in common are [`Shared`] uniques.\
+    /// An error in one `World` doesn't stop the others.
+    /// With the `parallel` feature a panic doesn't either, it's resumed once all `World`s are done.
+    ///
+    /// With the `parallel` feature, the `World`s run on the thread pool given to [`Worlds::with_thread_pool`]
+    /// or rayon's global one. Their workloads share this pool, unless their `World` has a local thread pool.\
+    /// Without it, the `World`s run one after the other.
+    ///
+    /// ### Example
+    /// ```
+    /// use shipyard::{Component, IntoIter, ViewMut, Workload, World, Worlds};
+    ///
+    /// #[derive(Component)]
+    /// struct Score(u32);
+    ///
+    /// fn add_point(mut scores: ViewMut<Score>) {
+    ///     for mut score in (&mut scores).iter() {
+    ///         score.0 += 1;
+    ///     }
+    /// }
+    ///
+    /// let mut worlds = Worlds::new();
+    /// for name in ["match 0", "match 1"] {
+    ///     let mut world = World::new();
+    ///     Workload::new("tick").with_system(add_point).add_to_world(&world).unwrap();
+    ///     world.add_entity(Score(0));
+    ///
+    ///     worlds.insert(name, world);
+    /// }
+    ///
+    /// for (name, result) in worlds.run_workload_all("tick") {
+    ///     assert!(result.is_ok(), "{} failed", name);
+    /// }
+    /// ```
+    pub fn run_workload_all<T>(
+        &self,
+        label: impl AsLabel<T>,
+    ) -> Vec<(&str, Result<(), error::RunWorkload>)> {
+        let label = label.as_label();
+
+        self.run_all(|world| world.run_workload(label.clone()))
+    }
+    /// Runs the default workload of every `World` at the same time.\
+    /// Returns each `World`'s result, in insertion order.
+    ///
+    /// See [`Worlds::run_workload_all`] for the isolation guarantees.
+    pub fn run_default_workload_all(&self) -> Vec<(&str, Result<(), error::RunWorkload>)> {
+        self.run_all(World::run_default_workload)
+    }
+    #[cfg(feature = "parallel")]
+    fn run_all<F>(&self, run: F) -> Vec<(&str, Result<(), error::RunWorkload>)>
+    where
+        F: Fn(&World) -> Result<(), error::RunWorkload> + Sync,
+    {
+        let mut results: Vec<Option<Result<(), error::RunWorkload>>> =
+            self.worlds.iter().map(|_| None).collect();
+
+        let run = &run;
+        let slots = results.iter_mut();
+        let run_worlds = move || {
+            rayon::scope(|scope| {
+                // each World is given to a single job
+                for (result, (_, world)) in slots.zip(&self.worlds) {
+                    scope.spawn(move |_| *result = Some(run(world)));
+                }
+            })
+        };
+
+        match &self.thread_pool {
+            Some(thread_pool) => thread_pool.install(run_worlds),
+            None => run_worlds(),
+        }
+
+        self.worlds
+            .iter()
+            .zip(results)
+            .map(|((name, _), result)| (&**name, result.unwrap()))
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    fn run_all<F>(&self, run: F) -> Vec<(&str, Result<(), error::RunWorkload>)>
+    where
+        F: Fn(&World) -> Result<(), error::RunWorkload> + Sync,
+    {
+        self.worlds
+            .iter()
+            .map(|(name, world)| (&**name, run(world)))
+            .collect()
+    }
+    fn index(&self, name: &str) -> Option<usize> {
+        self.worlds
+            .iter()
+            .position(|(world_name, _)| world_name == name)
+    }
+}
+
+impl core::ops::Index<&str> for Worlds {
+    type Output = World;
+
+    #[track_caller]
+    fn index(&self, name: &str) -> &World {
+        match self.get(name) {
+            Some(world) => world,
+            None => panic!("No World named {:?}.", name),
+        }
+    }
+}
+
+impl core::ops::IndexMut<&str> for Worlds {
+    #[track_caller]
+    fn index_mut(&mut self, name: &str) -> &mut World {
+        match self.get_mut(name) {
+            Some(world) => world,
+            None => panic!("No World named {:?}.", name),
+        }
+    }
+}
+
+impl core::fmt::Debug for Worlds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.worlds.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
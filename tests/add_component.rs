@@ -100,6 +100,22 @@ fn no_pack_unchecked() {
     assert_eq!((&usizes, &u32s).get(entity1).unwrap(), (&USIZE(2), &U32(3)));
 }
 
+#[test]
+fn tuple_of_views() {
+    let mut world = World::new();
+    let entity = world.add_entity(());
+
+    let (entities, mut views) = world
+        .borrow::<(EntitiesView, (ViewMut<USIZE>, ViewMut<U32>))>()
+        .unwrap();
+
+    entities.add_component(entity, &mut views, (USIZE(0), U32(1)));
+    assert_eq!(
+        (&views.0, &views.1).get(entity).unwrap(),
+        (&USIZE(0), &U32(1))
+    );
+}
+
 #[test]
 fn workload_add() {
     let mut world = World::new();
@@ -162,3 +178,25 @@ fn move_between_worlds() {
     assert_eq!(*world2.get::<&USIZE>(entity1).unwrap(), &USIZE(1));
     assert_eq!(*world2.get::<&U32>(entity1).unwrap(), &U32(2));
 }
+
+#[test]
+fn if_missing() {
+    let mut world = World::new();
+    world.track_modification::<USIZE>();
+
+    let entity = world.add_entity(());
+
+    let mut usizes = world
+        .borrow::<ViewMut<USIZE, track::Modification>>()
+        .unwrap();
+
+    assert!(usizes.add_component_if_missing(entity, USIZE(0)));
+    assert!(!usizes.add_component_if_missing(entity, USIZE(1)));
+    assert_eq!(usizes[entity], USIZE(0));
+    assert!(!usizes.is_modified(entity));
+
+    assert!(!usizes.add_distinct_component_unchecked(entity, USIZE(0)));
+    assert!(!usizes.is_modified(entity));
+    assert!(usizes.add_distinct_component_unchecked(entity, USIZE(1)));
+    assert!(usizes.is_modified(entity));
+}
@@ -171,3 +171,35 @@ fn workload() {
     world.run_default_workload().unwrap();
     world.run_default_workload().unwrap();
 }
+
+#[test]
+fn builder() {
+    #[derive(Debug, PartialEq, Eq)]
+    struct USIZE(usize);
+    impl Component for USIZE {
+        type Tracking = track::Insertion;
+    }
+
+    let world = World::new();
+    let (mut entities, mut usizes, mut u32s) = world
+        .borrow::<(EntitiesViewMut, ViewMut<USIZE>, ViewMut<U32>)>()
+        .unwrap();
+
+    let entity0 = entities
+        .build_entity()
+        .with(&mut usizes, USIZE(0))
+        .with_opt(&mut u32s, None)
+        .finish();
+    let entity1 = entities
+        .build_entity()
+        .with_opt(&mut usizes, Some(USIZE(1)))
+        .with(&mut u32s, U32(1))
+        .finish();
+    let entity2 = entities.build_entity().finish();
+
+    assert!(entities.is_alive(entity2));
+    assert_eq!(usizes[entity0], USIZE(0));
+    assert!(!u32s.contains(entity0));
+    assert_eq!((&usizes, &u32s).get(entity1), Ok((&USIZE(1), &U32(1))));
+    assert!(usizes.is_inserted(entity0) && usizes.is_inserted(entity1));
+}
@@ -0,0 +1,50 @@
+use allocator_api2::alloc::{AllocError, Allocator, Global, Layout};
+use core::ptr::NonNull;
+use shipyard::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct U64(u64);
+
+/// Counts the bytes currently allocated.
+#[derive(Clone, Default)]
+struct Counting(Arc<AtomicUsize>);
+
+unsafe impl Allocator for Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.fetch_add(layout.size(), Ordering::Relaxed);
+
+        Global.allocate(layout)
+    }
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.fetch_sub(layout.size(), Ordering::Relaxed);
+
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn add_storage_in() {
+    let counting = Counting::default();
+    let mut world = World::new();
+
+    world.add_storage_in::<U64>(StorageAllocator::new(counting.clone()));
+
+    let entities: Vec<_> = (0..10).map(|i| world.add_entity(U64(i))).collect();
+
+    assert!(counting.0.load(Ordering::Relaxed) >= 10 * core::mem::size_of::<U64>());
+    assert!(!world.borrow::<View<U64>>().unwrap().allocator().is_global());
+
+    world.delete_entity(entities[3]);
+    world.run(|u64s: View<U64>| {
+        assert_eq!(u64s.len(), 9);
+        assert_eq!(u64s.get(entities[9]), Ok(&U64(9)));
+    });
+
+    world.add_storage_in::<U64>(StorageAllocator::global());
+
+    assert_eq!(counting.0.load(Ordering::Relaxed), 0);
+    assert!(world.borrow::<View<U64>>().unwrap().is_empty());
+    assert!(world.is_alive(entities[0]));
+}
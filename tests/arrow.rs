@@ -0,0 +1,78 @@
+#![cfg(feature = "arrow")]
+
+use arrow_array::{Array, ArrayRef, UInt32Array};
+use arrow_schema::{ArrowError, DataType, Field};
+use shipyard::*;
+use std::sync::Arc;
+
+#[derive(PartialEq, Eq, Debug)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+
+impl ArrowComponent for U32 {
+    fn fields() -> Vec<Field> {
+        vec![Field::new("u32", DataType::UInt32, false)]
+    }
+    fn to_columns(components: &[Self]) -> Vec<ArrayRef> {
+        vec![Arc::new(UInt32Array::from_iter_values(
+            components.iter().map(|u32| u32.0),
+        ))]
+    }
+    fn from_columns(columns: &[ArrayRef]) -> Result<Vec<Self>, ArrowError> {
+        let column = columns
+            .first()
+            .and_then(|column| column.as_any().downcast_ref::<UInt32Array>())
+            .ok_or_else(|| ArrowError::SchemaError("expected a u32 column".to_string()))?;
+
+        Ok(column.values().iter().map(|&u32| U32(u32)).collect())
+    }
+}
+
+#[test]
+fn round_trip() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((U32(0),));
+    let entity1 = world.add_entity((U32(1),));
+
+    let batch = world
+        .borrow::<View<U32>>()
+        .unwrap()
+        .to_record_batch()
+        .unwrap();
+
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(batch.schema().field(0).name(), ARROW_ID_COLUMN);
+
+    let mut other = World::new();
+    other
+        .borrow::<ViewMut<U32>>()
+        .unwrap()
+        .extend_from_record_batch(&batch)
+        .unwrap();
+
+    let u32s = other.borrow::<View<U32>>().unwrap();
+    assert_eq!(u32s[entity0], U32(0));
+    assert_eq!(u32s[entity1], U32(1));
+}
+
+#[test]
+fn missing_id_column() {
+    let batch = arrow_array::RecordBatch::try_from_iter([(
+        "u32",
+        Arc::new(UInt32Array::from(vec![0])) as ArrayRef,
+    )])
+    .unwrap();
+
+    let world = World::new();
+
+    assert!(matches!(
+        world
+            .borrow::<ViewMut<U32>>()
+            .unwrap()
+            .extend_from_record_batch(&batch),
+        Err(ArrowError::SchemaError(_))
+    ));
+}
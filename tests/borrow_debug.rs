@@ -0,0 +1,109 @@
+#![cfg(feature = "borrow_debug")]
+
+use core::sync::atomic::{AtomicU8, Ordering};
+use shipyard::*;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+impl Unique for U32 {}
+
+#[test]
+fn borrow_timeout() {
+    let mut world = World::new();
+
+    world.add_entity(U32(0));
+
+    let u32s = world.borrow_timeout::<View<U32>>(Duration::from_millis(10));
+    assert_eq!(u32s.unwrap().iter().map(|x| x.0).collect::<Vec<_>>(), [0]);
+
+    {
+        let _u32s = world.borrow::<View<U32>>().unwrap();
+        let _u32s_2 = world.borrow::<View<U32>>().unwrap();
+
+        let err = world
+            .borrow_timeout::<ViewMut<U32>>(Duration::from_millis(10))
+            .unwrap_err();
+
+        assert!(matches!(err.error, error::GetStorage::StorageBorrow { .. }));
+        assert_eq!(err.holders.len(), 2);
+        assert!(err
+            .holders
+            .iter()
+            .all(|holder| !holder.exclusive && holder.thread == std::thread::current().id()));
+    }
+
+    let err = world
+        .borrow_timeout::<UniqueView<U32>>(Duration::from_secs(10))
+        .unwrap_err();
+    assert!(matches!(
+        err.error,
+        error::GetStorage::MissingStorage { .. }
+    ));
+    assert!(err.holders.is_empty());
+
+    assert!(world
+        .borrow_timeout::<ViewMut<U32>>(Duration::from_millis(10))
+        .is_ok());
+}
+
+#[test]
+fn released_before_timeout() {
+    let world = World::new();
+
+    std::thread::scope(|scope| {
+        let u32s = world.borrow::<ViewMut<U32>>().unwrap();
+
+        let handle = scope.spawn(|| {
+            world
+                .borrow_timeout::<View<U32>>(Duration::from_secs(10))
+                .is_ok()
+        });
+
+        std::thread::sleep(Duration::from_millis(5));
+        drop(u32s);
+
+        assert!(handle.join().unwrap());
+    });
+}
+
+static STATE: AtomicU8 = AtomicU8::new(0);
+
+fn hold(_: ViewMut<U32>) {
+    STATE.store(1, Ordering::Release);
+
+    while STATE.load(Ordering::Acquire) != 2 {
+        std::thread::yield_now();
+    }
+}
+
+#[test]
+fn holder_system() {
+    let world = World::new();
+
+    world.add_workload(|| hold);
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| world.run_default_workload().unwrap());
+
+        while STATE.load(Ordering::Acquire) != 1 {
+            std::thread::yield_now();
+        }
+
+        let err = world
+            .borrow_timeout::<View<U32>>(Duration::from_millis(10))
+            .unwrap_err();
+
+        STATE.store(2, Ordering::Release);
+
+        assert_eq!(err.holders.len(), 1);
+        let holder = &err.holders[0];
+        assert!(holder.exclusive);
+        assert_ne!(holder.thread, std::thread::current().id());
+        assert!(format!("{:?}", holder.system.as_ref().unwrap()).contains("hold"));
+        assert!(format!("{:?}", err).contains("hold"));
+    });
+}
@@ -0,0 +1,59 @@
+use shipyard::*;
+
+#[derive(Component, Debug)]
+#[track(All)]
+struct Layout;
+
+#[derive(Component, Debug)]
+#[track(All)]
+struct Hidden;
+
+#[derive(Component, Debug)]
+struct Untracked;
+
+#[test]
+fn incremental() {
+    let mut world = World::new();
+    let mut query = CachedQuery::<Layout, Hidden>::new();
+
+    let e0 = world.add_entity(Layout);
+    let e1 = world.add_entity((Layout, Hidden));
+
+    world.run(|all_storages: AllStoragesView| query.update(&all_storages).unwrap());
+    assert_eq!(query.entities(), [e0]);
+
+    let e2 = world.add_entity(Layout);
+    world.add_component(e0, Hidden);
+    world.remove::<Hidden>(e1);
+
+    world.run(|all_storages: AllStoragesView| query.update(&all_storages).unwrap());
+    let mut entities = query.entities().to_vec();
+    entities.sort_by_key(|entity| entity.index());
+    assert_eq!(entities, [e1, e2]);
+    assert!(!query.contains(e0));
+
+    world.delete_entity(e1);
+    world.delete_component::<Layout>(e2);
+
+    world.run(|all_storages: AllStoragesView| query.update(&all_storages).unwrap());
+    assert!(query.is_empty());
+}
+
+#[test]
+fn untracked() {
+    let mut world = World::new();
+    let mut query = CachedQuery::<(Layout, Untracked)>::new();
+
+    world.run(|all_storages: AllStoragesView| query.update(&all_storages).unwrap());
+    assert!(query.is_empty());
+
+    let entity = world.add_entity((Layout, Untracked));
+
+    world.run(|all_storages: AllStoragesView| query.update(&all_storages).unwrap());
+    assert_eq!(query.entities(), [entity]);
+
+    world.delete_component::<Untracked>(entity);
+
+    world.run(|all_storages: AllStoragesView| query.update(&all_storages).unwrap());
+    assert!(query.is_empty());
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "capi")]
+
+use shipyard::capi::*;
+use shipyard::*;
+use std::ffi::CString;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Pos([f32; 2]);
+
+impl Component for Pos {
+    type Tracking = track::Untracked;
+    const NAME: Option<&'static str> = Some("Pos");
+}
+
+unsafe impl Pod for Pos {}
+
+fn shift(mut positions: ViewMut<Pos>) {
+    for pos in (&mut positions).iter() {
+        pos.0[0] += 1.0;
+    }
+}
+
+#[test]
+fn drive_world() {
+    let world = World::new();
+    Workload::new("update")
+        .with_system(shift)
+        .add_to_world(&world)
+        .unwrap();
+    Workload::new(String::from("owned"))
+        .with_system(shift)
+        .add_to_world(&world)
+        .unwrap();
+
+    let mut registry = CapiRegistry::new();
+    registry.register::<Pos>();
+
+    let world = CWorld::new(world, registry).into_raw();
+    let pos = CString::new("Pos").unwrap();
+    let unknown = CString::new("Vel").unwrap();
+    let update = CString::new("update").unwrap();
+    let owned = CString::new("owned").unwrap();
+    let missing = CString::new("render").unwrap();
+
+    unsafe {
+        let entity = shipyard_spawn(world);
+        assert!(shipyard_is_alive(world, entity));
+
+        let bytes: Vec<u8> = [1.0f32, 2.0].iter().flat_map(|f| f.to_ne_bytes()).collect();
+
+        assert_eq!(
+            shipyard_add_component(world, entity, unknown.as_ptr(), bytes.as_ptr(), 8),
+            ShipyardStatus::UnknownComponent
+        );
+        assert_eq!(
+            shipyard_add_component(world, entity, pos.as_ptr(), bytes.as_ptr(), 4),
+            ShipyardStatus::WrongSize
+        );
+        assert_eq!(
+            shipyard_add_component(world, entity, pos.as_ptr(), bytes.as_ptr(), 8),
+            ShipyardStatus::Ok
+        );
+
+        assert_eq!(
+            shipyard_run_workload(world, update.as_ptr()),
+            ShipyardStatus::Ok
+        );
+        assert_eq!(
+            shipyard_run_workload(world, owned.as_ptr()),
+            ShipyardStatus::Ok
+        );
+        assert_eq!(
+            shipyard_run_workload(world, missing.as_ptr()),
+            ShipyardStatus::MissingWorkload
+        );
+
+        let mut out = Pos([0.0; 2]);
+        assert_eq!(
+            shipyard_get_component(
+                world,
+                entity,
+                pos.as_ptr(),
+                (&mut out as *mut Pos).cast(),
+                8
+            ),
+            ShipyardStatus::Ok
+        );
+        assert_eq!(out, Pos([3.0, 2.0]));
+
+        let all_storages = (*world).world().borrow::<AllStoragesViewMut>().unwrap();
+        assert_eq!(
+            shipyard_get_component(
+                world,
+                entity,
+                pos.as_ptr(),
+                (&mut out as *mut Pos).cast(),
+                8
+            ),
+            ShipyardStatus::BorrowFailed
+        );
+        drop(all_storages);
+
+        assert!(shipyard_delete(world, entity));
+        assert_eq!(
+            shipyard_add_component(world, entity, pos.as_ptr(), bytes.as_ptr(), 8),
+            ShipyardStatus::EntityNotAlive
+        );
+
+        shipyard_world_free(world);
+    }
+}
+
+#[test]
+fn panic() {
+    fn explode(_: View<Pos>) {
+        panic!("explode");
+    }
+
+    let world = World::new();
+    Workload::new("explode")
+        .with_system(explode)
+        .add_to_world(&world)
+        .unwrap();
+
+    let mut registry = CapiRegistry::new();
+    registry.register::<Pos>();
+
+    let world = CWorld::new(world, registry).into_raw();
+    let pos = CString::new("Pos").unwrap();
+    let explode = CString::new("explode").unwrap();
+
+    unsafe {
+        let entity = shipyard_spawn(world);
+
+        assert_eq!(
+            shipyard_run_workload(world, explode.as_ptr()),
+            ShipyardStatus::Panicked
+        );
+
+        // the world is still usable
+        let bytes: Vec<u8> = [1.0f32, 2.0].iter().flat_map(|f| f.to_ne_bytes()).collect();
+        assert_eq!(
+            shipyard_add_component(world, entity, pos.as_ptr(), bytes.as_ptr(), 8),
+            ShipyardStatus::Ok
+        );
+
+        shipyard_world_free(world);
+    }
+}
@@ -0,0 +1,101 @@
+use shipyard::*;
+
+struct Bullet;
+impl Component for Bullet {
+    type Tracking = track::Untracked;
+}
+
+struct Health(u32);
+impl Component for Health {
+    type Tracking = track::Untracked;
+}
+
+fn spawn(mut commands: UniqueViewMut<CommandBuffer>) {
+    commands.add_entity((Bullet,));
+}
+
+#[test]
+fn sync_point() {
+    fn before(bullets: View<Bullet>) {
+        assert_eq!(bullets.len(), 0);
+    }
+    fn after(bullets: View<Bullet>) {
+        assert_eq!(bullets.len(), 1);
+    }
+
+    let world = World::new();
+    world.add_unique(CommandBuffer::new());
+
+    Workload::new("frame")
+        .with_system(spawn)
+        .with_system(before)
+        .with_sync_point()
+        .with_system(after)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+}
+
+#[test]
+fn applied_at_workload_end() {
+    let world = World::new();
+    world.add_unique(CommandBuffer::new());
+
+    Workload::new("frame")
+        .with_system(spawn)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_workload("frame").unwrap();
+    world.run_default_workload().unwrap();
+
+    world.run(
+        |bullets: View<Bullet>, commands: UniqueView<CommandBuffer>| {
+            assert_eq!(bullets.len(), 2);
+            assert!(commands.is_empty());
+        },
+    );
+}
+
+#[test]
+fn commands_in_order() {
+    let mut world = World::new();
+    world.add_unique(CommandBuffer::new());
+
+    let entity = world.add_entity(());
+
+    world.run(|mut commands: UniqueViewMut<CommandBuffer>| {
+        commands.add_component(entity, (Health(10),));
+        commands.push(move |all_storages: &mut AllStorages| {
+            all_storages.run(|mut healths: ViewMut<Health>| healths[entity].0 -= 3);
+        });
+        commands.delete_entity(entity);
+        // skipped, the entity is dead by then
+        commands.add_component(entity, (Bullet,));
+
+        assert_eq!(commands.len(), 4);
+    });
+
+    world.apply_commands();
+
+    assert!(!world.is_entity_alive(entity));
+    world.run(|bullets: View<Bullet>, healths: View<Health>| {
+        assert!(bullets.is_empty());
+        assert!(healths.is_empty());
+    });
+}
+
+#[test]
+fn without_buffer() {
+    let mut world = World::new();
+
+    Workload::new("frame")
+        .with_system(|| {})
+        .with_sync_point()
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+    world.apply_commands();
+}
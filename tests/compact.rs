@@ -0,0 +1,71 @@
+use shipyard::*;
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct U32(u32);
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct USIZE(usize);
+
+#[test]
+fn sort_and_shrink() {
+    let mut world = World::new();
+
+    let entities: Vec<_> = (0..10)
+        .map(|i| world.add_entity((U32(i), USIZE(i as usize))))
+        .collect();
+
+    world.run(|mut u32s: ViewMut<U32>| u32s.sort_unstable_by(|a, b| b.0.cmp(&a.0)));
+    for &entity in &entities[5..] {
+        world.delete_entity(entity);
+    }
+
+    world.compact();
+
+    world.run(|u32s: View<U32>, usizes: View<USIZE>| {
+        assert_eq!(u32s.as_slice(), [U32(0), U32(1), U32(2), U32(3), U32(4)]);
+        assert_eq!(u32s.len(), 5);
+
+        for (i, &entity) in entities[..5].iter().enumerate() {
+            assert_eq!(u32s[entity], U32(i as u32));
+            assert_eq!(usizes[entity], USIZE(i));
+        }
+    });
+}
+
+#[test]
+fn compact_entities() {
+    let mut world = World::new();
+
+    let entities: Vec<_> = (0..6).map(|i| world.add_entity(U32(i))).collect();
+    world.delete_entity(entities[1]);
+    world.delete_entity(entities[3]);
+
+    let remap = world.compact_entities();
+
+    assert_eq!(remap.len(), 2);
+    assert_eq!(remap.get(entities[0]), None);
+    assert_eq!(remap.map(entities[4]).index(), 1);
+    assert_eq!(remap.map(entities[5]).index(), 3);
+
+    assert!(!world.is_alive(entities[4]));
+    assert!(!world.is_alive(entities[5]));
+    assert!(!world.is_alive(entities[1]));
+
+    world.run(|entities_view: EntitiesView, u32s: View<U32>| {
+        assert_eq!(entities_view.alive_count(), 4);
+        assert_eq!(u32s.len(), 4);
+
+        for (i, &entity) in entities.iter().enumerate() {
+            if i != 1 && i != 3 {
+                let entity = remap.map(entity);
+
+                assert!(entities_view.is_alive(entity));
+                assert_eq!(u32s[entity], U32(i as u32));
+            }
+        }
+    });
+
+    let new = world.add_entity(U32(10));
+    assert_eq!(new.index(), 4);
+    assert_ne!(new, entities[4]);
+}
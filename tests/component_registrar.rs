@@ -0,0 +1,128 @@
+use shipyard::*;
+
+#[derive(Component, Clone, Debug, PartialEq)]
+#[shipyard(clone, track(Insertion, Modification), name = "position")]
+struct Position(f32, f32);
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Velocity(f32, f32);
+
+#[derive(Component, Clone, Debug, PartialEq)]
+#[shipyard(clone)]
+struct Target(EntityId);
+
+#[test]
+fn options() {
+    fn tracking<T: Component<Tracking = track::InsertionAndModification>>() {}
+
+    tracking::<Position>();
+
+    assert_eq!(Position::NAME, Some("position"));
+    assert_eq!(Velocity::NAME, None);
+}
+
+#[test]
+fn clone_entity() {
+    let mut world = World::new();
+    world.register_components::<(Position, Velocity, Target)>();
+
+    let target = world.add_entity(());
+    let entity = world.add_entity((Position(0.0, 1.0), Velocity(1.0, 0.0), Target(target)));
+
+    let clone = world.clone_entity(entity);
+
+    assert_ne!(clone, entity);
+    assert_eq!(
+        world.get::<&Position>(clone).as_deref(),
+        Ok(&&Position(0.0, 1.0))
+    );
+    assert!(world.get::<&Velocity>(clone).is_err());
+    assert_eq!(world.get::<&Target>(clone).as_deref(), Ok(&&Target(target)));
+    assert_eq!(
+        world.get::<&Position>(entity).as_deref(),
+        Ok(&&Position(0.0, 1.0))
+    );
+
+    world.register_clone::<Velocity>();
+    let clone = world.clone_entity(entity);
+    assert_eq!(
+        world.get::<&Velocity>(clone).as_deref(),
+        Ok(&&Velocity(1.0, 0.0))
+    );
+}
+
+#[test]
+#[should_panic(expected = "has to be alive to clone it")]
+fn clone_dead_entity() {
+    let mut world = World::new();
+
+    let entity = world.add_entity(());
+    world.delete_entity(entity);
+
+    world.clone_entity(entity);
+}
+
+#[test]
+fn replay_stable_name() {
+    fn encode(position: &Position) -> Vec<u8> {
+        [position.0.to_le_bytes(), position.1.to_le_bytes()].concat()
+    }
+    fn decode(bytes: &[u8]) -> Option<Position> {
+        Some(Position(
+            f32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?),
+            f32::from_le_bytes(bytes.get(4..8)?.try_into().ok()?),
+        ))
+    }
+
+    let mut registry = ReplayRegistry::new();
+    registry.register::<Position>(encode, decode);
+
+    let mut world = World::new();
+    world.start_recording(registry.clone());
+    world.add_entity((Position(2.0, 3.0),));
+    let stream = world.stop_recording().unwrap();
+
+    let bytes = stream.to_bytes();
+    assert!(bytes.windows(8).any(|window| window == b"position"));
+
+    let mut other = World::new();
+    other
+        .replay(&ReplayStream::from_bytes(&bytes).unwrap(), &registry)
+        .unwrap();
+    assert_eq!(other.iter::<&Position>().iter().count(), 1);
+}
+
+#[test]
+fn default_component() {
+    #[derive(Component, Default, Debug, PartialEq)]
+    #[shipyard(name = "health")]
+    struct Health(u32);
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    struct Mana(u32);
+
+    let mut world = World::new();
+    world.register_default::<Health>();
+
+    let entity = world.add_entity(());
+
+    assert!(world.add_default_component_by_name(entity, "health"));
+    assert!(!world.add_default_component(entity, core::any::TypeId::of::<Mana>()));
+    assert_eq!(world.get::<&Health>(entity).as_deref(), Ok(&&Health(0)));
+    assert!(world.get::<&Mana>(entity).is_err());
+
+    world.register_default::<Mana>();
+    assert!(world.add_default_component(entity, core::any::TypeId::of::<Mana>()));
+    assert_eq!(world.get::<&Mana>(entity).as_deref(), Ok(&&Mana(0)));
+
+    let mut names = world
+        .borrow::<AllStoragesView>()
+        .unwrap()
+        .default_components()
+        .map(|(_, name)| name)
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    let mut expected = vec!["health", core::any::type_name::<Mana>()];
+    expected.sort_unstable();
+    assert_eq!(names, expected);
+}
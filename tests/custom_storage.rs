@@ -0,0 +1,237 @@
+use core::ops::{Deref, DerefMut};
+use shipyard::info::TypeInfo;
+use shipyard::*;
+
+struct DenseTable<T> {
+    entities: Vec<EntityId>,
+    data: Vec<T>,
+}
+
+impl<T> DenseTable<T> {
+    fn new() -> Self {
+        DenseTable {
+            entities: Vec::new(),
+            data: Vec::new(),
+        }
+    }
+    fn insert(&mut self, entity: EntityId, value: T) {
+        self.entities.push(entity);
+        self.data.push(value);
+    }
+}
+
+impl<T: 'static> Storage for DenseTable<T> {
+    fn delete(&mut self, entity: EntityId, _current: TrackingTimestamp) {
+        if let Some(index) = self.entities.iter().position(|&e| e == entity) {
+            self.entities.swap_remove(index);
+            self.data.swap_remove(index);
+        }
+    }
+    fn clear(&mut self, _current: TrackingTimestamp) {
+        self.entities.clear();
+        self.data.clear();
+    }
+    fn memory_usage(&self) -> Option<memory_usage::StorageMemoryUsage> {
+        Some(memory_usage::StorageMemoryUsage {
+            storage_name: self.name(),
+            used_memory_bytes: self.data.len() * core::mem::size_of::<T>(),
+            allocated_memory_bytes: self.data.capacity() * core::mem::size_of::<T>(),
+            component_count: self.data.len(),
+        })
+    }
+    fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+struct TableViewMut<'v, T: 'static> {
+    table: ARefMut<'v, &'v mut DenseTable<T>>,
+    _all_borrow: Option<SharedBorrow<'v>>,
+}
+
+impl<T> Deref for TableViewMut<'_, T> {
+    type Target = DenseTable<T>;
+
+    fn deref(&self) -> &DenseTable<T> {
+        &self.table
+    }
+}
+
+impl<T> DerefMut for TableViewMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut DenseTable<T> {
+        &mut self.table
+    }
+}
+
+impl<T: 'static + Send + Sync> Borrow for TableViewMut<'_, T> {
+    type View<'a> = TableViewMut<'a, T>;
+
+    fn borrow<'a>(
+        all_storages: &'a AllStorages,
+        all_borrow: Option<SharedBorrow<'a>>,
+        _last_run: Option<TrackingTimestamp>,
+        _current: TrackingTimestamp,
+    ) -> Result<Self::View<'a>, error::GetStorage> {
+        Ok(TableViewMut {
+            table: all_storages.custom_storage_or_insert_mut(DenseTable::<T>::new)?,
+            _all_borrow: all_borrow,
+        })
+    }
+}
+
+// SAFE: The only storage borrowed is recorded.
+unsafe impl<T: 'static + Send + Sync> BorrowInfo for TableViewMut<'_, T> {
+    fn borrow_info(info: &mut Vec<TypeInfo>) {
+        info.push(TypeInfo {
+            name: core::any::type_name::<DenseTable<T>>().into(),
+            mutability: Mutability::Exclusive,
+            storage_id: StorageId::of::<DenseTable<T>>(),
+            thread_safe: true,
+        });
+    }
+    fn enable_tracking(_: &mut Vec<for<'a> fn(&'a AllStorages) -> Result<(), error::GetStorage>>) {}
+}
+
+#[test]
+fn custom_view() {
+    let world = World::new();
+
+    let entity = world.run(|mut entities: EntitiesViewMut| entities.add_entity((), ()));
+
+    world.run(|mut table: TableViewMut<u32>| table.insert(entity, 1));
+    world.run(|mut table: TableViewMut<u32>| {
+        assert_eq!(table.entities, [entity]);
+        table.data[0] += 1;
+    });
+
+    assert_eq!(
+        world.borrow::<TableViewMut<u32>>().unwrap().data.as_slice(),
+        [2]
+    );
+    assert!(world
+        .borrow::<(TableViewMut<u32>, TableViewMut<u32>)>()
+        .is_err());
+}
+
+#[test]
+fn workload() {
+    fn push(mut table: TableViewMut<u32>) {
+        table.insert(EntityId::dead(), 0);
+    }
+
+    let world = World::new();
+
+    Workload::new("push")
+        .with_system(push)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+    world.run_default_workload().unwrap();
+
+    assert_eq!(world.borrow::<TableViewMut<u32>>().unwrap().data.len(), 2);
+    assert_eq!(
+        world.workloads_info().0["push"].batch_info[0]
+            .systems()
+            .next()
+            .unwrap()
+            .borrow[0]
+            .storage_id,
+        StorageId::of::<DenseTable<u32>>()
+    );
+}
+
+#[test]
+fn hooks() {
+    let mut world = World::new();
+
+    let mut table = DenseTable::new();
+    let entity1 = world.add_entity(());
+    let entity2 = world.add_entity(());
+    table.insert(entity1, 1u32);
+    table.insert(entity2, 2u32);
+
+    world
+        .borrow::<AllStoragesViewMut>()
+        .unwrap()
+        .add_custom_storage(StorageId::of::<DenseTable<u32>>(), table)
+        .unwrap();
+
+    world.delete_entity(entity1);
+    assert_eq!(
+        world.borrow::<TableViewMut<u32>>().unwrap().entities,
+        [entity2]
+    );
+
+    assert!(format!("{:?}", world.memory_usage()).contains("DenseTable<u32>"));
+
+    world.clear();
+    assert!(world.borrow::<TableViewMut<u32>>().unwrap().is_empty());
+}
+
+#[test]
+fn many_storages() {
+    let world = World::new();
+
+    world.run(|all_storages: AllStoragesViewMut| {
+        for id in 0..100u64 {
+            let mut table = DenseTable::new();
+            table.insert(EntityId::dead(), id);
+
+            all_storages
+                .add_custom_storage(StorageId::Custom(id), table)
+                .unwrap();
+        }
+
+        // one of them exclusively borrowed doesn't prevent borrowing the others
+        let first = all_storages
+            .custom_storage_mut_by_id(StorageId::Custom(0))
+            .unwrap();
+
+        for id in 1..100u64 {
+            let storage = all_storages
+                .custom_storage_by_id(StorageId::Custom(id))
+                .unwrap();
+
+            assert_eq!(storage.memory_usage().unwrap().component_count, 1);
+        }
+
+        // the entity storage and the custom storages, except the borrowed one
+        assert_eq!(all_storages.iter_storages().len(), 100);
+
+        drop(first);
+    });
+}
+
+#[test]
+fn id_collision() {
+    let world = World::new();
+    let storage_id = StorageId::from_name("tables");
+
+    world
+        .add_custom_storage(storage_id, DenseTable::<u32>::new())
+        .unwrap();
+
+    let all_storages = world.all_storages().unwrap();
+
+    assert!(all_storages
+        .custom_storage_or_insert_by_id(storage_id, DenseTable::<u32>::new)
+        .is_ok());
+    assert_eq!(
+        all_storages
+            .custom_storage_or_insert_by_id(storage_id, DenseTable::<u64>::new)
+            .err(),
+        Some(error::GetStorage::StorageIdCollision {
+            name: core::any::type_name::<DenseTable<u64>>(),
+            id: storage_id,
+            stored: core::any::type_name::<DenseTable<u32>>(),
+        })
+    );
+    assert!(matches!(
+        world.add_custom_storage(storage_id, DenseTable::<u64>::new()),
+        Err(error::GetStorage::StorageIdCollision { .. })
+    ));
+
+    let table = unsafe { all_storages.foreign_storage::<DenseTable<u32>>(storage_id) };
+    assert!(table.is_ok());
+}
@@ -111,3 +111,34 @@ fn on_deletion() {
         EntityId::new_from_index_and_gen(0, 0).inner()
     );
 }
+
+#[test]
+fn weak_entity() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(());
+    let entity1 = world.add_entity(());
+    let mut handles = [
+        WeakEntity::new(entity0),
+        WeakEntity::new(entity1),
+        WeakEntity::dead(),
+    ];
+
+    world.delete_entity(entity0);
+    let recycled = world.add_entity(());
+    assert_eq!(recycled.index(), entity0.index());
+
+    let entities = world.borrow::<EntitiesView>().unwrap();
+
+    assert_eq!(handles[0].get(&entities), None);
+    assert_eq!(handles[1].get(&entities), Some(entity1));
+    assert_eq!(entities.invalidate_dead(&mut handles), 1);
+    assert_eq!(
+        handles,
+        [
+            WeakEntity::dead(),
+            WeakEntity::new(entity1),
+            WeakEntity::dead()
+        ]
+    );
+}
@@ -0,0 +1,54 @@
+use shipyard::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+#[derive(Component)]
+struct Enemy(u32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Reason {
+    Killed,
+    Despawned,
+}
+
+#[test]
+fn reasons() {
+    let mut world = World::new();
+    world.add_unique(DeletionEvents::<Reason>::new());
+    world.add_unique(CommandBuffer::new());
+
+    let score = Arc::new(AtomicU32::new(0));
+    let observer_score = score.clone();
+    world.run(|mut events: UniqueViewMut<DeletionEvents<Reason>>| {
+        events.observe(move |_, reason| {
+            if *reason == Reason::Killed {
+                observer_score.fetch_add(10, Ordering::Relaxed);
+            }
+        });
+    });
+
+    let a = world.add_entity(Enemy(0));
+    let b = world.add_entity(Enemy(1));
+    let c = world.add_entity(Enemy(2));
+
+    assert!(world.delete_entity_with(a, Reason::Killed));
+    assert!(!world.delete_entity_with(a, Reason::Killed));
+    // other reason types aren't recorded
+    assert!(world.delete_entity_with(b, "untracked"));
+
+    world.run(|mut commands: UniqueViewMut<CommandBuffer>| {
+        commands.delete_entity_with(c, Reason::Despawned);
+    });
+    world.apply_commands();
+
+    assert!(!world.is_alive(c));
+    assert_eq!(score.load(Ordering::Relaxed), 10);
+
+    world.run(|mut events: UniqueViewMut<DeletionEvents<Reason>>| {
+        assert_eq!(
+            events.drain().collect::<Vec<_>>(),
+            [(a, Reason::Killed), (c, Reason::Despawned)]
+        );
+        assert!(events.is_empty());
+    });
+}
@@ -90,3 +90,116 @@ fn into_iter_rename() {
         }
     });
 }
+
+#[test]
+fn system_attribute() {
+    #[derive(Component, Debug, PartialEq)]
+    #[track(Modification)]
+    struct Position(f32);
+    #[derive(Component)]
+    struct Velocity(f32);
+    #[derive(Unique)]
+    struct Dt(f32);
+    #[derive(Unique, Default)]
+    struct Count(u32);
+
+    #[shipyard::system]
+    fn movement(pos: &mut Position, vel: &Velocity, dt: &Unique<Dt>, count: &mut Unique<Count>) {
+        pos.0 += vel.0 * dt.0;
+        count.0 += 1;
+    }
+
+    #[shipyard::system]
+    fn ids(id: EntityId, pos: &Position, count: &Unique<Count>) {
+        assert_ne!(id, EntityId::dead());
+        assert!(pos.0 >= 0.0);
+        assert_eq!(count.0, 2);
+    }
+
+    #[shipyard::system]
+    fn check(count: &Unique<Count>) -> Result<(), &'static str> {
+        if count.0 == 2 {
+            Ok(())
+        } else {
+            Err("movement should have run for two entities")
+        }
+    }
+
+    let mut world = World::new();
+    world.add_unique(Dt(0.5));
+    world.add_unique(Count::default());
+    world.add_entity((Position(0.0), Velocity(2.0)));
+    world.add_entity((Position(1.0), Velocity(2.0)));
+    world.add_entity((Position(1.0),));
+
+    Workload::new("movement")
+        .with_system(movement)
+        .with_system(ids)
+        .with_try_system(check)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_workload("movement").unwrap();
+
+    let mut positions = world
+        .iter::<&Position>()
+        .iter()
+        .map(|pos| pos.0)
+        .collect::<Vec<_>>();
+    positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(positions, [1.0, 1.0, 2.0]);
+}
+
+#[test]
+fn custom_view_batching() {
+    #[derive(Unique)]
+    struct Gravity(f32);
+    #[derive(Unique)]
+    struct Score(u32);
+
+    #[derive(Borrow, BorrowInfo)]
+    struct Settings<'v> {
+        gravity: UniqueView<'v, Gravity>,
+        score: UniqueView<'v, Score>,
+    }
+
+    #[derive(Borrow, BorrowInfo)]
+    struct Scoring<'v> {
+        _gravity: UniqueView<'v, Gravity>,
+        score: UniqueViewMut<'v, Score>,
+    }
+
+    fn read_settings(settings: Settings) {
+        assert!(settings.gravity.0 < 0.0);
+        assert!(settings.score.0 < 10);
+    }
+    fn read_gravity(_: UniqueView<Gravity>) {}
+    fn score(mut scoring: Scoring) {
+        scoring.score.0 += 1;
+    }
+
+    let world = World::new();
+    world.add_unique(Gravity(-9.81));
+    world.add_unique(Score(0));
+
+    Workload::new("")
+        .with_system(read_settings)
+        .with_system(read_gravity)
+        .with_system(score)
+        .add_to_world(&world)
+        .unwrap();
+
+    let info = &world.workloads_info().0[""];
+    assert_eq!(info.batch_info.len(), 2);
+    assert_eq!(info.batch_info[0].systems().count(), 2);
+
+    let score_info = info.batch_info[1].systems().next().unwrap();
+    assert_eq!(score_info.reads().count(), 1);
+    assert_eq!(
+        score_info.writes().next().unwrap().storage_id,
+        StorageId::of::<UniqueStorage<Score>>()
+    );
+
+    world.run_workload("").unwrap();
+    assert_eq!(world.borrow::<UniqueView<Score>>().unwrap().0, 1);
+}
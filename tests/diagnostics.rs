@@ -0,0 +1,89 @@
+#![cfg(feature = "diagnostics")]
+
+use miette::Diagnostic;
+use shipyard::*;
+
+#[derive(Component, Debug)]
+struct U32;
+
+#[derive(Unique, Debug)]
+struct Score;
+
+#[test]
+fn get_storage() {
+    let world = World::new();
+
+    let _u32s = world.borrow::<View<U32>>().unwrap();
+    let err = world.borrow::<ViewMut<U32>>().unwrap_err();
+    assert_eq!(
+        err.code().unwrap().to_string(),
+        "shipyard::get_storage::storage_borrow"
+    );
+    assert!(err.help().unwrap().to_string().contains("U32"));
+
+    let err = world.borrow::<UniqueView<Score>>().unwrap_err();
+    assert_eq!(
+        err.code().unwrap().to_string(),
+        "shipyard::get_storage::missing_storage"
+    );
+    assert!(err.help().unwrap().to_string().contains("add_unique"));
+
+    let err = world
+        .borrow::<View<U32, track::InsertionAndModification>>()
+        .unwrap_err();
+    assert!(err
+        .help()
+        .unwrap()
+        .to_string()
+        .contains("track::InsertionAndModification"));
+}
+
+#[test]
+fn missing_component() {
+    let mut world = World::new();
+
+    let entity = world.add_entity(U32);
+    world.delete_entity(entity);
+    let entity = world.add_entity(());
+
+    let u32s = world.borrow::<View<U32>>().unwrap();
+    let err = u32s.get(entity).unwrap_err();
+
+    assert_eq!(
+        err.code().unwrap().to_string(),
+        "shipyard::missing_component"
+    );
+    let help = err.help().unwrap().to_string();
+    assert!(help.contains(&format!(
+        "index {} generation {}",
+        entity.index(),
+        entity.gen()
+    )));
+    assert!(help.contains("reused"));
+}
+
+#[test]
+fn scheduling() {
+    fn sys1() {}
+    fn sys2() {}
+
+    let world = World::new();
+
+    let err = Workload::new("")
+        .with_system(sys1.before_all(sys2).after_all(sys2))
+        .with_system(sys2)
+        .add_to_world(&world)
+        .unwrap_err();
+    assert_eq!(
+        err.code().unwrap().to_string(),
+        "shipyard::impossible_requirements::before_and_after"
+    );
+    assert!(err.diagnostic_source().is_some());
+
+    let err = world.run_workload("missing").unwrap_err();
+    assert_eq!(
+        err.code().unwrap().to_string(),
+        "shipyard::run_workload::missing_workload"
+    );
+    assert!(err.help().unwrap().to_string().contains("add_workload"));
+}
@@ -0,0 +1,61 @@
+use core::any::TypeId;
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+unsafe impl Pod for U32 {}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct U8(u8);
+impl Component for U8 {
+    type Tracking = track::Untracked;
+}
+unsafe impl Pod for U8 {}
+
+#[test]
+fn tick_digest() {
+    let types = [TypeId::of::<U32>(), TypeId::of::<U8>()];
+
+    let mut world = World::new();
+    world.register_digest::<U32>();
+    world.register_digest::<U8>();
+    let empty = world.tick_digest(&types);
+
+    let entity0 = world.add_entity((U32(0), U8(0)));
+    let entity1 = world.add_entity((U32(1),));
+    let digest = world.tick_digest(&types);
+    assert_ne!(digest, empty);
+
+    // same state reached in a different order
+    let mut peer = World::new();
+    peer.register_digest::<U32>();
+    peer.register_digest::<U8>();
+    peer.add_entity(());
+    peer.add_entity((U32(1),));
+    peer.add_component(entity0, (U8(0), U32(0)));
+    assert_eq!(peer.tick_digest(&types), digest);
+
+    peer.get::<&mut U32>(entity1).unwrap().0 = 2;
+    assert_ne!(peer.tick_digest(&types), digest);
+
+    peer.get::<&mut U32>(entity1).unwrap().0 = 1;
+    assert_eq!(peer.tick_digest(&types), digest);
+    assert_ne!(
+        peer.tick_digest(&[TypeId::of::<U8>(), TypeId::of::<U32>()]),
+        digest
+    );
+
+    world.delete_entity(entity1);
+    assert_ne!(world.tick_digest(&types), digest);
+}
+
+#[test]
+#[should_panic(expected = "is not registered for digests")]
+fn unregistered_digest() {
+    let mut world = World::new();
+
+    world.tick_digest(&[TypeId::of::<U32>()]);
+}
@@ -0,0 +1,49 @@
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Insertion;
+}
+
+#[test]
+fn sorted_batch() {
+    let world = World::new();
+
+    let (mut entities, mut u32s) = world.borrow::<(EntitiesViewMut, ViewMut<U32>)>().unwrap();
+
+    let ids: Vec<_> = (0..100).map(|_| entities.add_entity((), ())).collect();
+
+    u32s.add_component_unchecked(ids[0], U32(1000));
+    u32s.extend_from_iter(ids[1..].iter().map(|&id| (id, U32(id.index() as u32))));
+
+    assert_eq!(u32s.len(), 100);
+    assert_eq!(u32s[ids[0]], U32(1000));
+    for &id in &ids[1..] {
+        assert_eq!(u32s[id], U32(id.index() as u32));
+        assert!(u32s.is_inserted(id));
+    }
+}
+
+#[test]
+fn unsorted_batch() {
+    let world = World::new();
+
+    let (mut entities, mut u32s) = world.borrow::<(EntitiesViewMut, ViewMut<U32>)>().unwrap();
+
+    let ids: Vec<_> = (0..10).map(|_| entities.add_entity((), ())).collect();
+
+    u32s.add_component_unchecked(ids[5], U32(1000));
+    u32s.extend_from_iter(ids.iter().rev().map(|&id| (id, U32(id.index() as u32))));
+
+    assert_eq!(u32s.len(), 10);
+    for &id in &ids {
+        assert_eq!(u32s[id], U32(id.index() as u32));
+    }
+    assert_eq!(u32s.iter().next(), Some(&U32(5)));
+
+    // ascending but overriding an existing component
+    u32s.extend_from_iter(ids.iter().map(|&id| (id, U32(0))));
+    assert_eq!(u32s.len(), 10);
+    assert!(u32s.iter().all(|u32| *u32 == U32(0)));
+}
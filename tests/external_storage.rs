@@ -0,0 +1,183 @@
+use shipyard::*;
+
+struct Velocity(f32);
+impl Component for Velocity {
+    type Tracking = track::Untracked;
+}
+
+#[derive(Default)]
+struct Bodies {
+    owners: Vec<EntityId>,
+    positions: Vec<f32>,
+}
+
+impl Bodies {
+    fn push(&mut self, entity: EntityId, position: f32) {
+        self.owners.push(entity);
+        self.positions.push(position);
+    }
+}
+
+impl ExternalStorage for Bodies {
+    type Item = f32;
+
+    fn ids(&self) -> &[EntityId] {
+        &self.owners
+    }
+    fn data(&self) -> &[f32] {
+        &self.positions
+    }
+    fn data_mut(&mut self) -> &mut [f32] {
+        &mut self.positions
+    }
+    fn index_of(&self, entity: EntityId) -> Option<usize> {
+        self.owners.iter().position(|&owner| owner == entity)
+    }
+    fn on_delete(&mut self, entity: EntityId) {
+        if let Some(index) = self.index_of(entity) {
+            self.owners.swap_remove(index);
+            self.positions.swap_remove(index);
+        }
+    }
+    fn on_clear(&mut self) {
+        self.owners.clear();
+        self.positions.clear();
+    }
+}
+
+#[test]
+fn iteration() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((Velocity(1.0),));
+    let entity1 = world.add_entity(());
+    let entity2 = world.add_entity((Velocity(2.0),));
+
+    let mut bodies = Bodies::default();
+    bodies.push(entity1, 10.0);
+    bodies.push(entity2, 20.0);
+    bodies.push(entity0, 0.0);
+    world.add_external_storage(bodies);
+
+    world.run(
+        |mut bodies: ExternalViewMut<Bodies>, velocities: View<Velocity>| {
+            for (position, velocity) in (&mut bodies, &velocities).iter() {
+                *position += velocity.0;
+            }
+        },
+    );
+
+    world.run(|bodies: ExternalView<Bodies>| {
+        assert_eq!(bodies.get(entity0), Some(&1.0));
+        assert_eq!(bodies.get(entity1), Some(&10.0));
+        assert_eq!(bodies.get(entity2), Some(&22.0));
+
+        let mut all = bodies.iter().with_id().collect::<Vec<_>>();
+        all.sort_by(|a, b| a.1.total_cmp(b.1));
+        assert_eq!(all, [(entity0, &1.0), (entity1, &10.0), (entity2, &22.0)]);
+    });
+
+    world.run(
+        |bodies: ExternalView<Bodies>, mut velocities: ViewMut<Velocity>| {
+            for (position, velocity) in (&bodies, &mut velocities).iter() {
+                velocity.0 = *position;
+            }
+        },
+    );
+
+    assert_eq!(world.get::<&Velocity>(entity2).unwrap().0, 22.0);
+}
+
+#[test]
+fn hooks() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(());
+    let entity1 = world.add_entity(());
+
+    let mut bodies = Bodies::default();
+    bodies.push(entity0, 0.0);
+    bodies.push(entity1, 1.0);
+    world.add_external_storage(bodies);
+
+    world.delete_entity(entity0);
+    assert_eq!(
+        world.borrow::<ExternalView<Bodies>>().unwrap().ids(),
+        [entity1]
+    );
+
+    world.clear();
+    assert!(world
+        .borrow::<ExternalView<Bodies>>()
+        .unwrap()
+        .ids()
+        .is_empty());
+}
+
+#[test]
+fn missing() {
+    let world = World::new();
+
+    assert!(matches!(
+        world.borrow::<ExternalView<Bodies>>(),
+        Err(error::GetStorage::MissingStorage { .. })
+    ));
+}
+
+#[test]
+fn scheduling() {
+    fn read(_: ExternalView<Bodies>) {}
+    fn write(_: ExternalViewMut<Bodies>) {}
+
+    let world = World::new();
+    world.add_external_storage(Bodies::default());
+
+    Workload::new("physics")
+        .with_system(read)
+        .with_system(read)
+        .with_system(write)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+
+    let info = &world.workloads_info().0["physics"];
+    assert_eq!(info.batch_info.len(), 2);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn par_iter() {
+    use rayon::prelude::ParallelIterator;
+
+    let mut world = World::new();
+
+    let mut bodies = Bodies::default();
+    for i in 0..100 {
+        let entity = world.add_entity((Velocity(1.0),));
+        bodies.push(entity, i as f32);
+    }
+    world.add_external_storage(bodies);
+
+    world.run(
+        |mut bodies: ExternalViewMut<Bodies>, velocities: View<Velocity>| {
+            (&mut bodies, &velocities)
+                .par_iter()
+                .for_each(|(position, velocity)| *position += velocity.0);
+        },
+    );
+
+    world.run(|bodies: ExternalView<Bodies>| {
+        assert_eq!(bodies.data().iter().sum::<f32>(), 5050.0);
+    });
+}
+
+#[test]
+#[should_panic]
+fn replace_borrowed() {
+    let world = World::new();
+    world.add_external_storage(Bodies::default());
+
+    let _bodies = world.borrow::<ExternalView<Bodies>>().unwrap();
+    world.add_external_storage(Bodies::default());
+}
@@ -0,0 +1,54 @@
+use shipyard::*;
+use std::collections::HashMap;
+
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+#[track(All)]
+struct Position(f32);
+
+#[derive(Default)]
+struct Render {
+    positions: HashMap<EntityId, Position>,
+    writes: usize,
+}
+
+impl ExtractTarget<Position> for Render {
+    fn write(&mut self, entity: EntityId, component: &Position) {
+        self.positions.insert(entity, *component);
+        self.writes += 1;
+    }
+    fn remove(&mut self, entity: EntityId) {
+        self.positions.remove(&entity);
+    }
+    fn clear(&mut self) {
+        self.positions.clear();
+    }
+}
+
+#[test]
+fn changes_only() {
+    let mut world = World::new();
+    world.add_unique(DoubleBuffer::<Render>::default());
+
+    let e0 = world.add_entity(Position(0.0));
+    let e1 = world.add_entity(Position(0.0));
+
+    Workload::new("extract")
+        .with_system(extract::<Position, Render>)
+        .with_system(swap_buffers::<Render>)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_workload("extract").unwrap();
+    world.run_workload("extract").unwrap();
+
+    world.run(|mut positions: ViewMut<Position>| positions[e0].0 = 1.0);
+    world.delete_entity(e1);
+
+    world.run_workload("extract").unwrap();
+
+    let buffers = world.borrow::<UniqueView<DoubleBuffer<Render>>>().unwrap();
+    assert_eq!(buffers.front().positions.len(), 1);
+    assert_eq!(buffers.front().positions[&e0], Position(1.0));
+    assert_eq!(buffers.front().writes, 3);
+    assert_eq!(buffers.back().positions.len(), 2);
+}
@@ -0,0 +1,103 @@
+use shipyard::*;
+
+#[derive(Debug, PartialEq)]
+struct Material(&'static str);
+
+struct Visible;
+impl Component for Visible {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn shared_value() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((Visible,));
+    let entity1 = world.add_entity(());
+    let entity2 = world.add_entity((Visible,));
+
+    let stone = world.run(|mut materials: FlyweightViewMut<Material>| {
+        let stone = materials.share(Material("stone"));
+
+        assert!(materials.insert(entity0, stone));
+        assert!(materials.insert(entity1, stone));
+        materials.insert_value(entity2, Material("wood"));
+
+        assert_eq!(materials.references(stone), 2);
+        assert_eq!(materials.value_count(), 2);
+
+        stone
+    });
+
+    world.run(|mut materials: FlyweightViewMut<Material>| {
+        materials.value_mut(stone).unwrap().0 = "granite";
+    });
+
+    world.run(
+        |materials: FlyweightView<Material>, visibles: View<Visible>| {
+            assert_eq!(materials.get(entity1), Some(&Material("granite")));
+
+            let mut visible = (&materials, &visibles)
+                .iter()
+                .with_id()
+                .map(|(id, (material, _))| (id, material.0))
+                .collect::<Vec<_>>();
+            visible.sort();
+
+            assert_eq!(visible, [(entity0, "granite"), (entity2, "wood")]);
+            assert_eq!(materials.iter().count(), 3);
+        },
+    );
+}
+
+#[test]
+fn value_dropped_with_last_reference() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(());
+    let entity1 = world.add_entity(());
+
+    let stone = world.run(|mut materials: FlyweightViewMut<Material>| {
+        let stone = materials.share(Material("stone"));
+        materials.insert(entity0, stone);
+        materials.insert(entity1, stone);
+
+        stone
+    });
+
+    world.delete_entity(entity0);
+    world.run(|materials: FlyweightView<Material>| {
+        assert_eq!(materials.value(stone), Some(&Material("stone")));
+        assert_eq!(materials.references(stone), 1);
+    });
+
+    world.run(|mut materials: FlyweightViewMut<Material>| {
+        assert!(materials.remove(entity1));
+        assert!(!materials.remove(entity1));
+
+        assert_eq!(materials.value(stone), None);
+        assert_eq!(materials.value_count(), 0);
+
+        let wood = materials.share(Material("wood"));
+        assert_ne!(wood, stone);
+        assert!(!materials.insert(entity1, stone));
+        assert!(materials.is_empty());
+    });
+}
+
+#[test]
+fn replace_reference() {
+    let world = World::new();
+    let entity = world
+        .borrow::<EntitiesViewMut>()
+        .unwrap()
+        .add_entity((), ());
+
+    let mut materials = world.borrow::<FlyweightViewMut<Material>>().unwrap();
+    let stone = materials.insert_value(entity, Material("stone"));
+    let wood = materials.insert_value(entity, Material("wood"));
+
+    assert_eq!(materials.value(stone), None);
+    assert_eq!(materials.handle(entity), Some(wood));
+    assert_eq!(materials.len(), 1);
+}
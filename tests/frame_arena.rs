@@ -0,0 +1,39 @@
+use shipyard::*;
+
+fn scratch(arena: UniqueView<FrameArena>) {
+    assert_eq!(arena.allocated(), 0);
+
+    let mut values = arena.vec();
+    values.extend(0..32u32);
+
+    assert_eq!(values.iter().sum::<u32>(), 496);
+}
+
+fn more_scratch(arena: UniqueView<FrameArena>) {
+    let values: FrameVec<'_, u64> = arena.vec_with_capacity(16);
+
+    assert!(values.capacity() >= 16);
+}
+
+#[test]
+fn reset_each_run() {
+    let world = World::new();
+    world.add_unique(FrameArena::new());
+
+    Workload::new("")
+        .with_system(scratch)
+        .with_system(more_scratch)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+
+    let capacity = world.borrow::<UniqueView<FrameArena>>().unwrap().capacity();
+    assert!(capacity >= 128 + 128);
+
+    world.run_default_workload().unwrap();
+
+    let arena = world.borrow::<UniqueView<FrameArena>>().unwrap();
+    assert_eq!(arena.capacity(), capacity);
+    assert_eq!(arena.overflow(), 0);
+}
@@ -0,0 +1,115 @@
+use shipyard::*;
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct U32(u32);
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct USIZE(usize);
+
+#[derive(Unique, PartialEq, Eq, Debug)]
+struct Frame(u64);
+
+#[test]
+fn borrow() {
+    let mut world = World::new();
+
+    world.add_unique(Frame(3));
+    let entities: Vec<_> = (0..100)
+        .map(|i| world.add_entity((U32(i), USIZE(i as usize))))
+        .collect();
+
+    let frozen = world.freeze();
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let (entities_view, u32s, usizes, frame) = frozen
+                    .borrow::<(EntitiesView, View<U32>, View<USIZE>, UniqueView<Frame>)>()
+                    .unwrap();
+
+                assert_eq!(frame.0, 3);
+                assert!(entities
+                    .iter()
+                    .all(|&entity| entities_view.is_alive(entity)));
+                assert_eq!(
+                    (&u32s, &usizes)
+                        .iter()
+                        .map(|(u32_, usize_)| u32_.0 as usize + usize_.0)
+                        .sum::<usize>(),
+                    99 * 100
+                );
+            });
+        }
+    });
+
+    // views can outlive each other freely
+    let u32s = frozen.borrow::<View<U32>>().unwrap();
+    let u32s_again = frozen.borrow::<View<U32>>().unwrap();
+    drop(u32s);
+    assert_eq!(u32s_again[entities[5]], U32(5));
+}
+
+#[test]
+fn errors() {
+    let mut world = World::new();
+
+    world.add_entity(U32(0));
+
+    let frozen = world.freeze();
+
+    assert_eq!(
+        frozen.borrow::<View<USIZE>>().err(),
+        Some(error::GetStorage::MissingStorage {
+            name: Some(core::any::type_name::<SparseSet<USIZE>>()),
+            id: StorageId::of::<SparseSet<USIZE>>(),
+        })
+    );
+    assert!(frozen.borrow::<UniqueView<Frame>>().is_err());
+    assert!(matches!(
+        frozen.borrow::<View<U32, track::Insertion>>(),
+        Err(error::GetStorage::TrackingNotEnabled { .. })
+    ));
+}
+
+#[test]
+fn unfreeze() {
+    let mut world = World::new();
+
+    let entity = world.add_entity(U32(0));
+
+    assert_eq!(
+        world.freeze().borrow::<View<U32>>().unwrap()[entity],
+        U32(0)
+    );
+
+    world.run(|mut u32s: ViewMut<U32>| u32s[entity].0 = 1);
+
+    let frozen = world.freeze();
+    assert_eq!(frozen.borrow::<View<U32>>().unwrap()[entity], U32(1));
+    drop(frozen);
+
+    // the storages aren't left borrowed
+    world.borrow::<ViewMut<U32>>().unwrap();
+}
+
+#[test]
+fn id_collision() {
+    struct NotASparseSet;
+    impl Storage for NotASparseSet {}
+
+    let mut world = World::new();
+    world
+        .add_custom_storage(StorageId::of::<SparseSet<U32>>(), NotASparseSet)
+        .unwrap();
+
+    let frozen = world.freeze();
+
+    assert_eq!(
+        frozen.borrow::<View<U32>>().err(),
+        Some(error::GetStorage::StorageIdCollision {
+            name: core::any::type_name::<SparseSet<U32>>(),
+            id: StorageId::of::<SparseSet<U32>>(),
+            stored: core::any::type_name::<NotASparseSet>(),
+        })
+    );
+}
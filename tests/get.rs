@@ -139,3 +139,119 @@ fn old_id() {
         assert!(u32s.get(entity1).is_err());
     });
 }
+
+#[test]
+fn has() {
+    struct Life;
+    impl Component for Life {
+        type Tracking = track::Untracked;
+    }
+
+    struct Energy;
+    impl Component for Energy {
+        type Tracking = track::Untracked;
+    }
+
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(Life);
+    let entity1 = world.add_entity((Life, Energy));
+
+    assert!(world.is_alive(entity0));
+    assert!(world.has::<Life>(entity0));
+    assert!(!world.has::<Energy>(entity0));
+    assert!(!world.has::<(Life, Energy)>(entity0));
+    assert!(world.has::<(Life, Energy)>(entity1));
+
+    {
+        let (lives, energies) = world.borrow::<(View<Life>, View<Energy>)>().unwrap();
+        assert!((&lives, &energies).contains(entity1));
+        assert!(!(&lives, &energies).contains(entity0));
+        // shared borrows don't conflict
+        assert!(world.has::<Life>(entity0));
+    }
+
+    world.delete_entity(entity0);
+
+    assert!(!world.is_alive(entity0));
+    assert!(!world.has::<Life>(entity0));
+}
+
+#[test]
+fn dead_entity() {
+    #[derive(Debug)]
+    struct Target(EntityId);
+    impl Component for Target {
+        type Tracking = track::Untracked;
+    }
+
+    let mut world = World::new();
+
+    let entity = world.add_entity(Target(EntityId::dead()));
+    let target = world.get::<&Target>(entity).unwrap().0;
+
+    assert!(target.is_dead());
+    assert!(!entity.is_dead());
+    assert!(!world.is_alive(target));
+    assert!(matches!(
+        world.get::<&Target>(target),
+        Err(error::GetComponent::DeadEntity(id)) if id == target
+    ));
+    assert!(world.borrow::<View<Target>>().unwrap().get(target).is_err());
+}
+
+#[test]
+fn dead_entity_view() {
+    #[derive(Debug, PartialEq)]
+    struct Target(EntityId);
+    impl Component for Target {
+        type Tracking = track::Untracked;
+    }
+
+    let mut world = World::new();
+    world.add_entity(Target(EntityId::dead()));
+
+    let missing = error::MissingComponent {
+        id: EntityId::dead(),
+        name: core::any::type_name::<Target>(),
+    };
+
+    world.run(|mut targets: ViewMut<Target>| {
+        assert_eq!(targets.get(EntityId::dead()).err(), Some(missing));
+        assert_eq!((&targets).get(EntityId::dead()).err(), Some(missing));
+        assert_eq!((&mut targets).get(EntityId::dead()).err(), Some(missing));
+    });
+    world.run(|targets: View<Target>| {
+        assert_eq!(targets.get(EntityId::dead()).err(), Some(missing));
+        assert_eq!((&targets,).get(EntityId::dead()).err(), Some(missing));
+    });
+}
+
+#[test]
+fn unchecked() {
+    #[derive(PartialEq, Eq, Debug)]
+    struct U32(u32);
+    impl Component for U32 {
+        type Tracking = track::Modification;
+    }
+
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(U32(0));
+    let entity1 = world.add_entity(U32(1));
+
+    world.run(|mut u32s: ViewMut<U32>| {
+        let index = u32s.index_of(entity1).unwrap();
+        assert_eq!(unsafe { u32s.get_unchecked(index) }, &U32(1));
+
+        unsafe { u32s.get_unchecked_mut(index) }.0 += 10;
+
+        assert!(!u32s.is_modified(entity0));
+        assert!(u32s.is_modified(entity1));
+
+        let (ids, data, len) = u32s.as_raw_parts();
+        assert_eq!(len, 2);
+        assert_eq!(unsafe { *ids.add(index) }, entity1);
+        assert_eq!(unsafe { &*data.add(index) }, &U32(11));
+    });
+}
@@ -0,0 +1,46 @@
+use shipyard::*;
+
+#[derive(Component)]
+struct TextureHandle(u32);
+
+#[derive(Unique, Default)]
+struct Textures {
+    batches: Vec<Vec<(EntityId, u32)>>,
+}
+
+impl HandleOwner<TextureHandle> for Textures {
+    fn release(&mut self, released: &[(EntityId, &TextureHandle)]) {
+        self.batches.push(
+            released
+                .iter()
+                .map(|(entity, handle)| (*entity, handle.0))
+                .collect(),
+        );
+    }
+}
+
+#[test]
+fn batched_release() {
+    let mut world = World::new();
+    world.add_handle_owner::<TextureHandle, _>(Textures::default());
+
+    Workload::new("")
+        .with_system(release_handles)
+        .add_to_world(&world)
+        .unwrap();
+
+    let e0 = world.add_entity(TextureHandle(0));
+    let e1 = world.add_entity(TextureHandle(1));
+    world.add_entity(TextureHandle(2));
+
+    world.delete_entity(e0);
+    world.delete_entity(e1);
+    world.run_default_workload().unwrap();
+
+    // nothing deleted, no notification
+    world.run_default_workload().unwrap();
+
+    world.run(|textures: UniqueView<Textures>| {
+        assert_eq!(textures.batches, [vec![(e0, 0), (e1, 1)]]);
+    });
+}
@@ -0,0 +1,159 @@
+use shipyard::*;
+
+struct Name;
+impl Component for Name {
+    type Tracking = track::Untracked;
+}
+
+type HierarchyViewMut<'v> = (
+    EntitiesViewMut<'v>,
+    ViewMut<'v, Parent>,
+    ViewMut<'v, Children>,
+);
+
+#[test]
+fn attach_detach() {
+    let world = World::new();
+    let mut hierarchy = world.borrow::<HierarchyViewMut>().unwrap();
+
+    let root = hierarchy.0.add_entity((), ());
+    let a = hierarchy.attach_new(root);
+    let b = hierarchy.attach_new(root);
+    let c = hierarchy.attach_new(root);
+
+    assert_eq!((&hierarchy.1, &hierarchy.2).children(root), [a, b, c]);
+    assert_eq!((&hierarchy.1, &hierarchy.2).parent(b), Some(root));
+
+    assert_eq!(hierarchy.detach(b), Some(root));
+    assert_eq!(hierarchy.detach(b), None);
+    assert_eq!((&hierarchy.1, &hierarchy.2).children(root), [a, c]);
+    assert_eq!((&hierarchy.1, &hierarchy.2).parent(b), None);
+
+    hierarchy.detach(a);
+    hierarchy.detach(c);
+    assert!(hierarchy.2.get(root).is_err());
+}
+
+#[test]
+fn reparent() {
+    let world = World::new();
+    let mut hierarchy = world.borrow::<HierarchyViewMut>().unwrap();
+
+    let root1 = hierarchy.0.add_entity((), ());
+    let root2 = hierarchy.0.add_entity((), ());
+    let a = hierarchy.attach_new(root1);
+    let a1 = hierarchy.attach_new(a);
+
+    hierarchy.attach(a, root2);
+
+    assert!(hierarchy.2.get(root1).is_err());
+    assert_eq!((&hierarchy.1, &hierarchy.2).children(root2), [a]);
+    assert!((&hierarchy.1, &hierarchy.2).ancestors(a1).eq([a, root2]));
+
+    // attaching to the same parent moves the entity last
+    let b = hierarchy.attach_new(root2);
+    hierarchy.attach(a, root2);
+    assert_eq!((&hierarchy.1, &hierarchy.2).children(root2), [b, a]);
+}
+
+#[test]
+#[should_panic(expected = "can't be attached to itself or one of its descendants")]
+fn attach_to_descendant() {
+    let world = World::new();
+    let mut hierarchy = world.borrow::<HierarchyViewMut>().unwrap();
+
+    let root = hierarchy.0.add_entity((), ());
+    let a = hierarchy.attach_new(root);
+    let a1 = hierarchy.attach_new(a);
+
+    hierarchy.attach(root, a1);
+}
+
+#[test]
+fn traversal() {
+    let world = World::new();
+    let mut hierarchy = world.borrow::<HierarchyViewMut>().unwrap();
+
+    let root = hierarchy.0.add_entity((), ());
+    let a = hierarchy.attach_new(root);
+    let b = hierarchy.attach_new(root);
+    let a1 = hierarchy.attach_new(a);
+    let a2 = hierarchy.attach_new(a);
+    let b1 = hierarchy.attach_new(b);
+    let a11 = hierarchy.attach_new(a1);
+
+    let tree = (&hierarchy.1, &hierarchy.2);
+    assert!(tree.depth_first(root).eq([a, a1, a11, a2, b, b1]));
+    assert!(tree.breadth_first(root).eq([a, b, a1, a2, b1, a11]));
+    assert!(tree.depth_first(b1).eq([]));
+    assert!(tree.ancestors(root).eq([]));
+}
+
+#[test]
+fn despawn_recursive() {
+    let mut world = World::new();
+
+    let (root, a, a1, b) = world.run(
+        |mut hierarchy: HierarchyViewMut, mut names: ViewMut<Name>| {
+            let root = hierarchy.0.add_entity((), ());
+            let a = hierarchy.attach_new(root);
+            let a1 = hierarchy.attach_new(a);
+            let b = hierarchy.attach_new(root);
+
+            hierarchy.0.add_component(a1, &mut names, Name);
+
+            (root, a, a1, b)
+        },
+    );
+
+    assert!(world.despawn_recursive(a));
+    assert!(!world.despawn_recursive(a));
+
+    assert!(world.is_entity_alive(root));
+    assert!(world.is_entity_alive(b));
+    assert!(!world.is_entity_alive(a));
+    assert!(!world.is_entity_alive(a1));
+
+    world.run(
+        |parents: View<Parent>, children: View<Children>, names: View<Name>| {
+            assert_eq!((&parents, &children).children(root), [b]);
+            assert!(parents.get(a1).is_err());
+            assert_eq!(names.iter().count(), 0);
+        },
+    );
+}
+
+#[test]
+fn deletion_policy() {
+    let mut world = World::new();
+
+    let (root, a, a1, b) = world.run(|mut hierarchy: HierarchyViewMut| {
+        let root = hierarchy.0.add_entity((), ());
+        let a = hierarchy.attach_new(root);
+        let a1 = hierarchy.attach_new(a);
+        let b = hierarchy.attach_new(root);
+
+        (root, a, a1, b)
+    });
+
+    world.set_deletion_policy::<Children>(DeletionPolicy::Forbid);
+    assert!(!world.delete_entity(root));
+    assert!(world.is_alive(root));
+
+    world.set_deletion_policy::<Children>(DeletionPolicy::Orphan);
+    assert!(world.delete_entity(a));
+    assert!(world.is_alive(a1));
+    world.run(|parents: View<Parent>| {
+        assert!(parents.get(a1).is_err());
+    });
+
+    world.set_deletion_policy::<Children>(DeletionPolicy::Cascade);
+    assert!(world.delete_entity(root));
+    assert!(!world.is_alive(b));
+    assert!(world.is_alive(a1));
+
+    assert_eq!(
+        world.remove_deletion_policy::<Children>(),
+        Some(DeletionPolicy::Cascade)
+    );
+}
@@ -0,0 +1,112 @@
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+    const INLINE: bool = true;
+}
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct USIZE(usize);
+
+#[test]
+fn spill() {
+    let mut world = World::new();
+
+    let entities: Vec<_> = (0..8).map(|i| world.add_entity(U32(i))).collect();
+
+    world.run(|u32s: View<U32>, usizes: View<USIZE>| {
+        assert!(u32s.is_inline());
+        assert!(!usizes.is_inline());
+
+        for (i, &entity) in entities.iter().enumerate() {
+            assert_eq!(u32s.get(entity), Ok(&U32(i as u32)));
+        }
+    });
+
+    world.delete_entity(entities[2]);
+    let reused = world.add_entity(U32(20));
+
+    assert!(world.borrow::<View<U32>>().unwrap().is_inline());
+    assert_eq!(
+        world.borrow::<View<U32>>().unwrap().get(entities[2]),
+        Err(error::MissingComponent {
+            id: entities[2],
+            name: core::any::type_name::<U32>(),
+        })
+    );
+
+    let ninth = world.add_entity(U32(8));
+
+    world.run(|u32s: View<U32>| {
+        assert!(!u32s.is_inline());
+        assert_eq!(u32s.len(), 9);
+        assert_eq!(u32s.get(reused), Ok(&U32(20)));
+        assert_eq!(u32s.get(ninth), Ok(&U32(8)));
+        assert_eq!(u32s.get(entities[7]), Ok(&U32(7)));
+        assert!(!u32s.contains(entities[2]));
+    });
+}
+
+#[test]
+fn remove_and_iter() {
+    let mut world = World::new();
+
+    let e0 = world.add_entity((U32(0), USIZE(0)));
+    let e1 = world.add_entity(USIZE(1));
+    let e2 = world.add_entity((U32(2), USIZE(2)));
+
+    world.add_component(e1, U32(1));
+    assert_eq!(world.remove::<U32>(e0), Some(U32(0)));
+
+    world.run(|mut u32s: ViewMut<U32>, usizes: View<USIZE>| {
+        let iter: Vec<_> = (&u32s, &usizes).iter().with_id().collect();
+        assert_eq!(
+            iter,
+            [(e1, (&U32(1), &USIZE(1))), (e2, (&U32(2), &USIZE(2)))]
+        );
+
+        for (u32_, usize_) in (&mut u32s, &usizes).iter() {
+            u32_.0 += usize_.0 as u32;
+        }
+
+        assert_eq!(u32s[e1], U32(2));
+        assert_eq!(u32s[e2], U32(4));
+        assert!(u32s.is_inline());
+    });
+}
+
+#[test]
+fn bulk_add() {
+    let mut world = World::new();
+
+    let entities: Vec<_> = world.bulk_add_entity((0..4).map(U32)).collect();
+    assert!(world.borrow::<View<U32>>().unwrap().is_inline());
+
+    let more: Vec<_> = world.bulk_add_entity((4..10).map(U32)).collect();
+
+    world.run(|u32s: View<U32>| {
+        assert!(!u32s.is_inline());
+
+        for (i, &entity) in entities.iter().chain(&more).enumerate() {
+            assert_eq!(u32s.get(entity), Ok(&U32(i as u32)));
+        }
+    });
+}
+
+#[test]
+fn derive() {
+    #[derive(Component)]
+    #[shipyard(inline)]
+    struct Inline(u32);
+
+    let mut world = World::new();
+    let entity = world.add_entity(Inline(0));
+
+    assert!(<Inline as Component>::INLINE);
+    world.run(|inlines: View<Inline>| {
+        assert!(inlines.is_inline());
+        assert_eq!(inlines.get(entity).map(|inline| inline.0), Ok(0));
+    });
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "input")]
+
+use shipyard::input::*;
+use shipyard::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Key {
+    A,
+    D,
+    Space,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Move {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Jump {
+    Jump,
+}
+
+#[test]
+fn actions() {
+    let world = World::new();
+    world
+        .add_plugin(
+            InputPlugin::new()
+                .bind(Key::A, Move::Left)
+                .bind(Key::D, Move::Right),
+        )
+        .unwrap();
+    world
+        .add_plugin(InputPlugin::new().bind(Key::Space, Jump::Jump))
+        .unwrap();
+
+    world.feed_input(InputEvent::Pressed(Key::A)).unwrap();
+    world.feed_input(InputEvent::Pressed(Key::A)).unwrap();
+    world.feed_input(InputEvent::Pressed(Key::Space)).unwrap();
+    world.feed_input(InputEvent::Released(Key::A)).unwrap();
+
+    world.run(
+        |mut input: UniqueViewMut<Input<Key>>,
+         mut moves: UniqueViewMut<ActionEvents<Move>>,
+         mut jumps: UniqueViewMut<ActionEvents<Jump>>| {
+            assert!(input.just_pressed(&Key::A));
+            assert!(input.just_released(&Key::A));
+            assert!(!input.pressed(&Key::A));
+            assert!(input.pressed(&Key::Space));
+
+            assert_eq!(
+                moves.drain().collect::<Vec<_>>(),
+                [
+                    ActionEvent::Started(Move::Left),
+                    ActionEvent::Stopped(Move::Left)
+                ]
+            );
+            assert_eq!(
+                jumps.drain().collect::<Vec<_>>(),
+                [ActionEvent::Started(Jump::Jump)]
+            );
+
+            input.clear();
+            assert!(!input.just_pressed(&Key::Space));
+            assert!(input.pressed(&Key::Space));
+        },
+    );
+}
+
+#[test]
+fn missing_input() {
+    let world = World::new();
+
+    assert!(world.feed_input(InputEvent::Pressed(Key::D)).is_err());
+}
@@ -0,0 +1,67 @@
+use shipyard::*;
+
+#[derive(PartialEq, Debug, Clone)]
+struct Position([f32; 2]);
+impl Component for Position {
+    type Tracking = track::Untracked;
+}
+impl Interpolate for Position {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Position(self.0.interpolate(&other.0, t))
+    }
+}
+
+#[test]
+fn sample() {
+    let mut buffer = Interpolated::new();
+    assert_eq!(buffer.sample(0.0), None);
+
+    assert!(buffer.push(1.0, 10.0f32));
+    assert_eq!(buffer.sample(0.0), Some(10.0));
+
+    assert!(buffer.push(2.0, 20.0));
+    assert!(!buffer.push(1.5, 0.0));
+    assert_eq!(buffer.previous(), Some((1.0, &10.0)));
+    assert_eq!(buffer.latest(), Some((2.0, &20.0)));
+
+    assert_eq!(buffer.sample(0.5), Some(10.0));
+    assert_eq!(buffer.sample(1.25), Some(12.5));
+    assert_eq!(buffer.sample(3.0), Some(20.0));
+
+    buffer.clear();
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn system() {
+    let mut world = World::new();
+
+    let mut buffer = Interpolated::new();
+    buffer.push(0.0, Position([0.0, 0.0]));
+    buffer.push(1.0, Position([10.0, -10.0]));
+    let entity = world.add_entity((buffer,));
+    let empty = world.add_entity((Interpolated::<Position>::new(),));
+
+    world.add_unique(InterpolationTime(0.5));
+    world.run(interpolate::<Position>);
+
+    assert_eq!(
+        world.get::<&Position>(entity).as_deref(),
+        Ok(&&Position([5.0, -5.0]))
+    );
+    assert!(world.get::<&Position>(empty).is_err());
+
+    world.run(|mut buffers: ViewMut<Interpolated<Position>>| {
+        (&mut buffers)
+            .get(entity)
+            .unwrap()
+            .push(2.0, Position([20.0, -20.0]));
+    });
+    world.run(|mut time: UniqueViewMut<InterpolationTime>| time.0 = 1.5);
+    world.run(interpolate::<Position>);
+
+    assert_eq!(
+        world.get::<&Position>(entity).as_deref(),
+        Ok(&&Position([15.0, -15.0]))
+    );
+}
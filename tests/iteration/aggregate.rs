@@ -0,0 +1,75 @@
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug)]
+struct Distance(u32);
+impl Component for Distance {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn aggregate() {
+    let mut world = World::new();
+
+    let empty = world.borrow::<View<Distance>>().unwrap();
+    assert_eq!(empty.count_where(|_| true), 0);
+    assert_eq!(empty.min_by_key(|distance| distance.0), None);
+    assert_eq!(empty.max_by_key(|distance| distance.0), None);
+    drop(empty);
+
+    world.add_entity(Distance(5));
+    let entity1 = world.add_entity(Distance(2));
+    let entity2 = world.add_entity(Distance(9));
+    let entity3 = world.add_entity(Distance(2));
+    let entity4 = world.add_entity(Distance(9));
+
+    let mut distances = world.borrow::<ViewMut<Distance>>().unwrap();
+
+    assert_eq!(distances.count_where(|distance| distance.0 > 4), 3);
+    assert_eq!(
+        distances.min_by_key(|distance| distance.0),
+        Some((entity1, &Distance(2)))
+    );
+    assert_eq!(
+        distances.max_by_key(|distance| distance.0),
+        Some((entity4, &Distance(9)))
+    );
+
+    distances.delete(entity4);
+    distances.delete(entity1);
+
+    assert_eq!(distances.count_where(|distance| distance.0 > 4), 2);
+    assert_eq!(
+        distances.min_by_key(|distance| distance.0),
+        Some((entity3, &Distance(2)))
+    );
+    assert_eq!(
+        distances.max_by_key(|distance| distance.0),
+        Some((entity2, &Distance(9)))
+    );
+    assert_eq!(
+        distances.min_by_key(|distance| core::cmp::Reverse(distance.0)),
+        Some((entity2, &Distance(9)))
+    );
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn par_aggregate() {
+    let mut world = World::new();
+
+    let entities = (0..1000u32)
+        .map(|i| world.add_entity(Distance(i % 100)))
+        .collect::<Vec<_>>();
+
+    let distances = world.borrow::<View<Distance>>().unwrap();
+
+    assert_eq!(distances.par_count_where(|distance| distance.0 == 0), 10);
+    assert_eq!(
+        distances.par_min_by_key(|distance| distance.0),
+        Some((entities[0], &Distance(0)))
+    );
+    assert_eq!(
+        distances.par_max_by_key(|distance| distance.0),
+        Some((entities[999], &Distance(99)))
+    );
+}
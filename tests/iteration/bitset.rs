@@ -0,0 +1,77 @@
+use shipyard::*;
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct U32(u32);
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct USIZE(usize);
+
+#[test]
+fn join() {
+    let mut world = World::new();
+
+    world.add_bitset_storage::<U32>();
+    world.add_bitset_storage::<USIZE>();
+
+    let u32_only: Vec<_> = world.bulk_add_entity((0..5000).map(U32)).collect();
+    let both: Vec<_> = (0..3)
+        .map(|i| world.add_entity((U32(i), USIZE(i as usize))))
+        .collect();
+    world.bulk_add_entity((0..100).map(USIZE));
+
+    world.delete_entity(both[1]);
+    world.delete_entity(u32_only[0]);
+    world.add_component(u32_only[4999], USIZE(10));
+
+    world.run(|mut u32s: ViewMut<U32>, usizes: View<USIZE>| {
+        assert_eq!(u32s.bitset().unwrap().len(), 5001);
+
+        let mut iter: Vec<_> = (&u32s, &usizes).iter().with_id().collect();
+        iter.sort_by_key(|(id, _)| id.index());
+
+        assert_eq!(
+            iter,
+            [
+                (u32_only[4999], (&U32(4999), &USIZE(10))),
+                (both[0], (&U32(0), &USIZE(0))),
+                (both[2], (&U32(2), &USIZE(2))),
+            ]
+        );
+
+        for (u32_, usize_) in (&mut u32s, &usizes).iter() {
+            u32_.0 += usize_.0 as u32;
+        }
+
+        assert_eq!(u32s[u32_only[4999]], U32(5009));
+        assert_eq!(u32s[both[2]], U32(4));
+    });
+
+    world.borrow::<ViewMut<U32>>().unwrap().clear();
+
+    world.run(|u32s: View<U32>, usizes: View<USIZE>| {
+        assert!(u32s.bitset().unwrap().is_empty());
+        assert_eq!((&u32s, &usizes).iter().count(), 0);
+    });
+}
+
+#[test]
+fn enable() {
+    let mut world = World::new();
+
+    let entities: Vec<_> = (0..10)
+        .map(|i| world.add_entity((U32(i), USIZE(i as usize))))
+        .collect();
+
+    world.run(|mut u32s: ViewMut<U32>, mut usizes: ViewMut<USIZE>| {
+        assert!(u32s.bitset().is_none());
+
+        u32s.enable_bitset();
+        usizes.enable_bitset();
+
+        assert!(u32s.bitset().unwrap().contains(entities[3].uindex()));
+        assert_eq!((&u32s, &usizes).iter().count(), 10);
+
+        u32s.disable_bitset();
+        assert!(u32s.bitset().is_none());
+    });
+}
@@ -0,0 +1,74 @@
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct Texture(u32);
+impl Component for Texture {
+    type Tracking = track::Untracked;
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct Layer(u32);
+impl Component for Layer {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn group_by_key() {
+    let world = World::new();
+
+    let (mut entities, mut textures, mut layers) = world
+        .borrow::<(EntitiesViewMut, ViewMut<Texture>, ViewMut<Layer>)>()
+        .unwrap();
+
+    let entity0 = entities.add_entity((&mut textures, &mut layers), (Texture(1), Layer(0)));
+    let entity1 = entities.add_entity((&mut textures, &mut layers), (Texture(0), Layer(0)));
+    let entity2 = entities.add_entity((&mut textures, &mut layers), (Texture(1), Layer(1)));
+    entities.add_entity(&mut textures, Texture(1));
+
+    // runs are only made of consecutive items
+    assert_eq!(
+        textures
+            .iter()
+            .group_by_key(|texture| texture.0)
+            .map(|(key, run)| (key, run.len()))
+            .collect::<Vec<_>>(),
+        [(1, 1), (0, 1), (1, 2)]
+    );
+
+    assert_eq!(
+        textures
+            .iter()
+            .group_by_key(|texture| texture.0)
+            .size_hint(),
+        (1, Some(4))
+    );
+
+    textures.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        textures
+            .iter()
+            .group_by_key(|texture| texture.0)
+            .map(|(key, run)| (key, run.len()))
+            .collect::<Vec<_>>(),
+        [(0, 1), (1, 3)]
+    );
+
+    let mut groups = (&textures, &layers)
+        .iter()
+        .with_id()
+        .group_by_key(|(_, (_, layer))| **layer);
+
+    let (key, run) = groups.next().unwrap();
+    let mut ids = run.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+    ids.sort_unstable();
+    assert_eq!(key, Layer(0));
+    assert_eq!(ids, [entity0, entity1]);
+
+    assert_eq!(
+        groups.next(),
+        Some((Layer(1), vec![(entity2, (&Texture(1), &Layer(1)))]))
+    );
+
+    assert_eq!(groups.next(), None);
+}
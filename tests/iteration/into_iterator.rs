@@ -0,0 +1,47 @@
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Modification;
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct USIZE(usize);
+impl Component for USIZE {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn for_loops() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((U32(0), USIZE(0)));
+    let entity1 = world.add_entity((U32(1), USIZE(1)));
+
+    let (mut u32s, mut usizes) = world.borrow::<(ViewMut<U32>, ViewMut<USIZE>)>().unwrap();
+
+    for mut u32 in &mut u32s {
+        if u32.0 == 1 {
+            u32.0 = 10;
+        }
+    }
+    for usize in &mut usizes {
+        usize.0 += 1;
+    }
+
+    assert!(!u32s.is_modified(entity0));
+    assert!(u32s.is_modified(entity1));
+
+    let mut iter = (&u32s).into_iter().with_id();
+    assert_eq!(iter.next(), Some((entity0, &U32(0))));
+    assert_eq!(iter.next(), Some((entity1, &U32(10))));
+    assert_eq!(iter.next(), None);
+    drop(iter);
+
+    let usizes = usizes.as_view();
+    assert_eq!(
+        (&usizes).into_iter().collect::<Vec<_>>(),
+        vec![&USIZE(1), &USIZE(2)]
+    );
+}
@@ -1,2 +1,7 @@
+mod aggregate;
+mod bitset;
+mod group_by;
+mod into_iterator;
 mod non_packed;
+mod pairs;
 mod update;
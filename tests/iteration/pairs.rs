@@ -0,0 +1,95 @@
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Modification;
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct I16(i16);
+impl Component for I16 {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn iter_pairs() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((U32(0), I16(10)));
+    world.add_entity((U32(1),));
+    let entity2 = world.add_entity((U32(2), I16(12)));
+    let entity3 = world.add_entity((U32(3), I16(13)));
+
+    let (u32s, i16s) = world.borrow::<(View<U32>, View<I16>)>().unwrap();
+
+    let mut pairs = (&u32s).iter_pairs();
+    assert_eq!(pairs.len(), 6);
+    assert_eq!(
+        pairs.next(),
+        Some((
+            (entity0, &U32(0)),
+            (EntityId::new_from_index_and_gen(1, 0), &U32(1))
+        ))
+    );
+    assert_eq!(pairs.len(), 5);
+    assert_eq!(pairs.count(), 5);
+
+    let pairs = (&u32s, &i16s)
+        .iter_pairs()
+        .map(|((id_a, _), (id_b, _))| (id_a, id_b))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pairs,
+        vec![(entity0, entity2), (entity0, entity3), (entity2, entity3)]
+    );
+}
+
+#[test]
+fn pair_chunks() {
+    let mut world = World::new();
+
+    world.bulk_add_entity((0..10).map(|i| (U32(i),)));
+
+    let u32s = world.borrow::<View<U32>>().unwrap();
+    let pairs = (&u32s).iter_pairs();
+
+    let mut chunked = pairs
+        .chunks(3)
+        .flat_map(|chunk| chunk.map(|((_, a), (_, b))| (a.0, b.0)))
+        .collect::<Vec<_>>();
+    let mut all = pairs.map(|((_, a), (_, b))| (a.0, b.0)).collect::<Vec<_>>();
+
+    chunked.sort_unstable();
+    all.sort_unstable();
+
+    assert_eq!(all.len(), 45);
+    assert_eq!(chunked, all);
+}
+
+#[test]
+fn for_each_pair_mut() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((U32(0),));
+    let entity1 = world.add_entity((U32(1),));
+    let entity2 = world.add_entity((U32(2),));
+
+    world.run(|mut u32s: ViewMut<U32>| {
+        let mut count = 0;
+
+        u32s.for_each_pair_mut(|(id_a, mut a), (_, b)| {
+            count += 1;
+
+            if id_a == entity0 {
+                a.0 += b.0;
+            }
+        });
+
+        assert_eq!(count, 3);
+        assert_eq!(u32s[entity0], U32(3));
+        assert!(u32s.is_modified(entity0));
+        assert!(!u32s.is_modified(entity1));
+        assert!(!u32s.is_modified(entity2));
+    });
+}
@@ -47,3 +47,76 @@ fn filter() {
 
     assert_eq!(mod_vec, vec![&U32(2), &U32(4), &U32(6)]);
 }
+
+#[test]
+fn par_reduce() {
+    let world = World::new();
+
+    let empty = world
+        .run(|u32s: View<U32>| u32s.par_reduce(|| 0, |sum, x| sum + x.0, |sum1, sum2| sum1 + sum2));
+    assert_eq!(empty, 0);
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<U32>| {
+        for i in 0..1000 {
+            entities.add_entity(&mut u32s, U32(i));
+        }
+    });
+
+    let (sum, max) = world.run(|u32s: View<U32>| {
+        u32s.par_reduce(
+            || (0, 0),
+            |(sum, max), x| (sum + x.0, max.max(x.0)),
+            |(sum1, max1), (sum2, max2)| (sum1 + sum2, max1.max(max2)),
+        )
+    });
+    assert_eq!(sum, 499_500);
+    assert_eq!(max, 999);
+}
+
+#[test]
+fn batch_size() {
+    #[derive(PartialEq, Eq, Debug, Clone, Copy)]
+    struct USIZE(usize);
+    impl Component for USIZE {
+        type Tracking = track::Untracked;
+    }
+
+    let world = World::new();
+
+    world.run(
+        |mut entities: EntitiesViewMut, mut u32s: ViewMut<U32>, mut usizes: ViewMut<USIZE>| {
+            for i in 0..100 {
+                entities.add_entity(&mut u32s, U32(i));
+            }
+            for i in 0..100 {
+                entities.add_entity((&mut u32s, &mut usizes), (U32(i), USIZE(i as usize)));
+            }
+        },
+    );
+
+    world.run(|u32s: View<U32>, usizes: View<USIZE>| {
+        let threads = std::sync::Mutex::new(Vec::new());
+        u32s.par_iter()
+            .batch_size(usize::MAX)
+            .for_each(|_| threads.lock().unwrap().push(rayon::current_thread_index()));
+
+        let mut threads = threads.into_inner().unwrap();
+        assert_eq!(threads.len(), 200);
+        threads.dedup();
+        assert_eq!(threads.len(), 1);
+
+        let threads = std::sync::Mutex::new(Vec::new());
+        (&u32s, &usizes)
+            .par_iter()
+            .batch_size(usize::MAX)
+            .for_each(|_| threads.lock().unwrap().push(rayon::current_thread_index()));
+
+        let mut threads = threads.into_inner().unwrap();
+        assert_eq!(threads.len(), 100);
+        threads.dedup();
+        assert_eq!(threads.len(), 1);
+
+        let sum: u32 = u32s.par_iter().batch_size(1).map(|x| x.0).sum();
+        assert_eq!(sum, 9900);
+    });
+}
@@ -0,0 +1,37 @@
+use core::time::Duration;
+use shipyard::*;
+
+#[derive(Component)]
+struct Particle;
+
+#[test]
+fn expire() {
+    let mut world = World::new();
+
+    let short = world.add_entity((Particle, Lifetime::Frames(1)));
+    let long = world.add_entity((Particle, Lifetime::Frames(3)));
+    let timed = world.add_entity((Particle, Lifetime::Duration(Duration::from_secs(1))));
+    let forever = world.add_entity(Particle);
+
+    world.run(|mut all_storages: AllStoragesViewMut| {
+        assert_eq!(all_storages.expire_lifetimes(), 1);
+    });
+
+    assert!(!world.is_alive(short));
+    assert!(world.is_alive(long));
+    // no Time, durations don't count down
+    assert!(world.is_alive(timed));
+
+    world.add_unique(Time::new(0.1));
+    world.run(|mut time: UniqueViewMut<Time>| time.advance(1.5));
+    world.run(expire_lifetimes);
+
+    assert!(world.is_alive(long));
+    assert!(!world.is_alive(timed));
+    assert!(world.is_alive(forever));
+
+    world.run(|lifetimes: View<Lifetime>| {
+        assert_eq!(lifetimes[long], Lifetime::Frames(1));
+        assert_eq!(lifetimes.len(), 1);
+    });
+}
@@ -0,0 +1,107 @@
+use shipyard::*;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn rolling_history() {
+    let mut diagnostics = Diagnostics::with_history(3);
+
+    for spawned in [1, 2, 3, 6] {
+        diagnostics.count("spawned", spawned);
+        diagnostics.end_frame();
+    }
+
+    let spawned = diagnostics.get("spawned").unwrap();
+    assert_eq!(spawned.kind(), MetricKind::Counter);
+    assert_eq!(spawned.history().collect::<Vec<_>>(), [2.0, 3.0, 6.0]);
+    assert_eq!(spawned.average(), Some(11.0 / 3.0));
+    assert_eq!(spawned.min(), Some(2.0));
+    assert_eq!(spawned.max(), Some(6.0));
+    assert_eq!(spawned.current(), 0.0);
+
+    diagnostics.gauge("memory", 10.0);
+    diagnostics.end_frame();
+    diagnostics.end_frame();
+
+    let memory = diagnostics.get("memory").unwrap();
+    assert_eq!(memory.history().collect::<Vec<_>>(), [10.0, 10.0]);
+}
+
+#[test]
+fn sinks() {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+
+    let world = World::new();
+    let mut diagnostics = Diagnostics::new();
+    let sink_reports = reports.clone();
+    diagnostics.add_sink(move |name: &str, metric: &Metric| {
+        sink_reports
+            .lock()
+            .unwrap()
+            .push((name.to_string(), metric.last()));
+    });
+    world.add_unique(diagnostics);
+
+    Workload::new("")
+        .with_system(|mut diagnostics: UniqueViewMut<Diagnostics>| {
+            diagnostics.gauge("fps", 60.0);
+            diagnostics.count("draw_calls", 4);
+        })
+        .with_system(update_diagnostics)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+
+    assert_eq!(
+        *reports.lock().unwrap(),
+        [
+            ("draw_calls".to_string(), Some(4.0)),
+            ("fps".to_string(), Some(60.0))
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn counter_and_gauge() {
+    let mut diagnostics = Diagnostics::new();
+
+    diagnostics.count("calls", 1);
+    diagnostics.gauge("calls", 1.0);
+}
+
+#[test]
+fn storage_counts() {
+    #[derive(Component)]
+    struct ToDelete;
+
+    let world = World::new();
+    world.add_unique(Diagnostics::with_history(4));
+
+    Workload::new("")
+        .with_system(
+            |mut entities: EntitiesViewMut, mut to_delete: ViewMut<ToDelete>| {
+                entities.add_entity(&mut to_delete, ToDelete);
+            },
+        )
+        .with_system(record_storage_counts)
+        .with_system(update_diagnostics)
+        .add_to_world(&world)
+        .unwrap();
+
+    for _ in 0..6 {
+        world.run_default_workload().unwrap();
+    }
+
+    let diagnostics = world.borrow::<UniqueView<Diagnostics>>().unwrap();
+    let (_, to_delete) = diagnostics
+        .storage_counts()
+        .find(|(name, _)| name.ends_with("ToDelete"))
+        .unwrap();
+
+    assert_eq!(
+        to_delete.history().collect::<Vec<_>>(),
+        [3.0, 4.0, 5.0, 6.0]
+    );
+    assert_eq!(to_delete.growth(), Some(3.0));
+}
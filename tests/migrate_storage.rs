@@ -0,0 +1,86 @@
+use shipyard::*;
+
+#[derive(Component)]
+#[shipyard(name = "health")]
+struct OldHealth(u32);
+
+#[derive(Component, Debug, PartialEq)]
+#[shipyard(name = "health")]
+struct Health {
+    current: u32,
+    max: u32,
+}
+
+#[derive(Component)]
+#[shipyard(name = "health")]
+struct Unrelated;
+
+#[test]
+fn migrate() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(OldHealth(10));
+    world.add_entity(());
+    let entity2 = world.add_entity(OldHealth(20));
+
+    assert_eq!(
+        world.migrate_storage(|OldHealth(current)| Health { current, max: 100 }),
+        Ok(2)
+    );
+
+    world.run(|old_healths: View<OldHealth>, healths: View<Health>| {
+        assert!(old_healths.is_empty());
+        assert_eq!(healths.len(), 2);
+        assert_eq!(
+            healths.get(entity0),
+            Ok(&Health {
+                current: 10,
+                max: 100
+            })
+        );
+        assert_eq!(
+            healths.get(entity2),
+            Ok(&Health {
+                current: 20,
+                max: 100
+            })
+        );
+    });
+}
+
+#[test]
+fn errors() {
+    let mut world = World::new();
+
+    assert_eq!(
+        world.migrate_storage(|OldHealth(current)| Health { current, max: 100 }),
+        Err(error::MigrateStorage::MissingStorage("health"))
+    );
+
+    world.add_entity(OldHealth(10));
+
+    assert!(matches!(
+        world.migrate_storage(|_: Unrelated| Health { current: 0, max: 0 }),
+        Err(error::MigrateStorage::WrongType {
+            component: "health",
+            ..
+        })
+    ));
+
+    assert_eq!(world.iter::<&OldHealth>().into_iter().count(), 1);
+}
+
+#[test]
+fn storage_handle() {
+    let mut world = World::new();
+    let handle = world.storage_handle::<OldHealth>();
+
+    world.add_entity(OldHealth(10));
+
+    assert_eq!(
+        world.migrate_storage(|OldHealth(current)| Health { current, max: 100 }),
+        Ok(1)
+    );
+
+    assert!(handle.view(&world).unwrap().is_empty());
+}
@@ -0,0 +1,103 @@
+use shipyard::*;
+
+#[derive(Debug, PartialEq)]
+enum Effect {
+    Poison(u32),
+    Slow,
+    Burn,
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut world = World::new();
+    let entity0 = world.add_entity(());
+    let entity1 = world.add_entity(());
+
+    let mut effects = world.borrow::<MultiViewMut<Effect>>().unwrap();
+    effects.insert(entity0, Effect::Poison(1));
+    effects.insert(entity1, Effect::Slow);
+    effects.insert(entity0, Effect::Burn);
+    effects.insert(entity0, Effect::Poison(2));
+
+    assert_eq!(effects.len(), 4);
+    assert_eq!(effects.entity_count(), 2);
+    assert_eq!(
+        effects.get_all(entity0),
+        [Effect::Poison(1), Effect::Burn, Effect::Poison(2)]
+    );
+
+    if let Effect::Poison(damage) = &mut effects.get_all_mut(entity0)[0] {
+        *damage = 3;
+    }
+
+    assert_eq!(effects.remove(entity0, 1), Some(Effect::Burn));
+    assert_eq!(effects.remove(entity0, 5), None);
+    assert_eq!(
+        effects.get_all(entity0),
+        [Effect::Poison(3), Effect::Poison(2)]
+    );
+
+    effects.retain(entity0, |effect| *effect != Effect::Poison(2));
+    assert_eq!(effects.count(entity0), 1);
+
+    assert_eq!(effects.remove_all(entity0), [Effect::Poison(3)]);
+    assert!(!effects.contains(entity0));
+    assert_eq!(effects.get_all(entity1), [Effect::Slow]);
+    assert_eq!(effects.len(), 1);
+}
+
+#[test]
+fn iteration() {
+    let mut world = World::new();
+    let entity0 = world.add_entity(());
+    let entity1 = world.add_entity(());
+
+    world.run(|mut effects: MultiViewMut<Effect>| {
+        effects.insert(entity0, Effect::Poison(1));
+        effects.insert(entity1, Effect::Poison(10));
+        effects.insert(entity0, Effect::Poison(2));
+
+        for (_, effect) in effects.iter_mut() {
+            if let Effect::Poison(damage) = effect {
+                *damage *= 2;
+            }
+        }
+    });
+
+    world.run(|effects: MultiView<Effect>| {
+        let mut all = effects
+            .iter()
+            .map(|(entity, effect)| match effect {
+                Effect::Poison(damage) => (entity, *damage),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        all.sort();
+
+        assert_eq!(all, [(entity0, 2), (entity0, 4), (entity1, 20)]);
+    });
+}
+
+#[test]
+fn delete_entity() {
+    let mut world = World::new();
+    let entity0 = world.add_entity(());
+    let entity1 = world.add_entity(());
+
+    world.run(|mut effects: MultiViewMut<Effect>| {
+        effects.insert(entity0, Effect::Slow);
+        effects.insert(entity0, Effect::Burn);
+        effects.insert(entity1, Effect::Slow);
+    });
+
+    world.delete_entity(entity0);
+
+    world.run(|effects: MultiView<Effect>| {
+        assert!(effects.get_all(entity0).is_empty());
+        assert_eq!(effects.entities().collect::<Vec<_>>(), [entity1]);
+        assert_eq!(effects.len(), 1);
+    });
+
+    world.clear();
+    assert!(world.borrow::<MultiView<Effect>>().unwrap().is_empty());
+}
@@ -0,0 +1,138 @@
+use shipyard::*;
+
+#[derive(Unique)]
+struct Gravity(i32);
+
+#[derive(Unique)]
+struct Physics(i32);
+
+#[derive(Unique)]
+struct Render;
+
+struct GravityPlugin;
+
+impl Plugin for GravityPlugin {
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.provides::<Gravity>();
+    }
+    fn build(&self, world: &World) {
+        world.add_unique(Gravity(-10));
+    }
+}
+
+struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.requires::<Gravity>().provides::<Physics>();
+    }
+    fn build(&self, world: &World) {
+        let gravity = world.borrow::<UniqueView<Gravity>>().unwrap().0;
+        world.add_unique(Physics(gravity));
+    }
+}
+
+struct RenderPlugin;
+
+impl Plugin for RenderPlugin {
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.requires::<Physics>().provides::<Render>();
+    }
+    fn build(&self, world: &World) {
+        world.add_unique(Render);
+    }
+}
+
+#[test]
+fn order() {
+    let world = World::new();
+
+    world
+        .add_plugins([
+            Box::new(RenderPlugin) as Box<dyn Plugin>,
+            Box::new(PhysicsPlugin),
+            Box::new(GravityPlugin),
+        ])
+        .unwrap();
+
+    assert_eq!(world.borrow::<UniqueView<Physics>>().unwrap().0, -10);
+    assert!(world.borrow::<UniqueView<Render>>().is_ok());
+}
+
+#[test]
+fn missing_unique() {
+    let world = World::new();
+
+    assert_eq!(
+        world.add_plugins([
+            Box::new(RenderPlugin) as Box<dyn Plugin>,
+            Box::new(PhysicsPlugin)
+        ]),
+        Err(error::AddPlugin::MissingUnique {
+            plugin: core::any::type_name::<PhysicsPlugin>(),
+            unique: core::any::type_name::<Gravity>(),
+        })
+    );
+    // nothing was built
+    assert!(world.borrow::<UniqueView<Render>>().is_err());
+
+    world.add_unique(Gravity(-1));
+    world.add_plugin(PhysicsPlugin).unwrap();
+    assert_eq!(world.borrow::<UniqueView<Physics>>().unwrap().0, -1);
+}
+
+#[test]
+fn not_provided() {
+    struct Liar;
+
+    impl Plugin for Liar {
+        fn name(&self) -> &'static str {
+            "Liar"
+        }
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.provides::<Gravity>();
+        }
+        fn build(&self, _: &World) {}
+    }
+
+    let world = World::new();
+
+    assert_eq!(
+        world.add_plugins([Box::new(Liar) as Box<dyn Plugin>, Box::new(PhysicsPlugin)]),
+        Err(error::AddPlugin::NotProvided {
+            plugin: "Liar",
+            unique: core::any::type_name::<Gravity>(),
+        })
+    );
+    assert!(world.borrow::<UniqueView<Physics>>().is_err());
+}
+
+#[test]
+fn cycle() {
+    struct Chicken;
+    struct Egg;
+
+    impl Plugin for Chicken {
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.requires::<Gravity>().provides::<Physics>();
+        }
+        fn build(&self, _: &World) {}
+    }
+
+    impl Plugin for Egg {
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.requires::<Physics>().provides::<Gravity>();
+        }
+        fn build(&self, _: &World) {}
+    }
+
+    let world = World::new();
+
+    assert_eq!(
+        world.add_plugins([Box::new(Chicken) as Box<dyn Plugin>, Box::new(Egg)]),
+        Err(error::AddPlugin::Cycle(vec![
+            core::any::type_name::<Chicken>(),
+            core::any::type_name::<Egg>()
+        ]))
+    );
+}
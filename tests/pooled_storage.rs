@@ -0,0 +1,86 @@
+use shipyard::*;
+
+#[derive(Component, PartialEq, Eq, Debug)]
+struct Buffer(Vec<u32>);
+
+#[test]
+fn reuse() {
+    let mut world = World::new();
+
+    world.add_pooled_storage::<Buffer>(2);
+
+    let entities: Vec<_> = (0..3)
+        .map(|i| world.add_entity(Buffer(Vec::with_capacity(16 + i))))
+        .collect();
+
+    for &entity in &entities {
+        world.delete_entity(entity);
+    }
+
+    world.run(|mut buffers: ViewMut<Buffer>| {
+        assert!(buffers.is_empty());
+        assert_eq!(buffers.pool_capacity(), 2);
+        assert_eq!(buffers.pooled_len(), 2);
+
+        let buffer = buffers.take_pooled().unwrap();
+        assert!(buffer.0.capacity() >= 17);
+        assert_eq!(buffers.pooled_len(), 1);
+
+        buffers.set_pool_capacity(0);
+        assert_eq!(buffers.take_pooled(), None);
+    });
+}
+
+#[test]
+fn tracked_deletion() {
+    let mut world = World::new();
+
+    world.add_pooled_storage::<Buffer>(4);
+    world.borrow::<ViewMut<Buffer>>().unwrap().track_deletion();
+
+    let entity = world.add_entity(Buffer(vec![1, 2, 3]));
+    world.delete_entity(entity);
+
+    world.run(|mut buffers: ViewMut<Buffer>| {
+        assert_eq!(buffers.pooled_len(), 0);
+
+        buffers.clear_all_deleted();
+
+        assert_eq!(buffers.take_pooled(), Some(Buffer(vec![1, 2, 3])));
+    });
+}
+
+#[test]
+fn tracked_deletion_older_than_timestamp() {
+    let mut world = World::new();
+
+    world.add_pooled_storage::<Buffer>(4);
+    world.borrow::<ViewMut<Buffer>>().unwrap().track_deletion();
+
+    let entity = world.add_entity(Buffer(vec![1, 2, 3]));
+    world.delete_entity(entity);
+
+    let time = world.get_tracking_timestamp();
+
+    let entity = world.add_entity(Buffer(vec![4]));
+    world.delete_entity(entity);
+
+    world.run(|mut buffers: ViewMut<Buffer>| {
+        buffers.clear_all_deleted_older_than_timestamp(time);
+
+        assert_eq!(buffers.take_pooled(), Some(Buffer(vec![1, 2, 3])));
+        assert_eq!(buffers.take_pooled(), None);
+    });
+}
+
+#[test]
+fn clear() {
+    let mut world = World::new();
+
+    world.add_pooled_storage::<Buffer>(2);
+
+    world.bulk_add_entity((0..5).map(|i| Buffer(vec![i])));
+    world.borrow::<ViewMut<Buffer>>().unwrap().clear();
+
+    assert_eq!(world.borrow::<View<Buffer>>().unwrap().pooled_len(), 2);
+}
@@ -167,3 +167,55 @@ fn track_reset_with_timestamp() {
     let usizes = world.borrow::<View<USIZE, track::All>>().unwrap();
     assert_eq!(usizes.removed().collect::<Vec<_>>(), vec![]);
 }
+
+#[test]
+fn tuple_of_views() {
+    #[derive(PartialEq, Eq, Debug)]
+    struct USIZE(usize);
+    impl Component for USIZE {
+        type Tracking = track::Untracked;
+    }
+
+    let mut world = World::new();
+    let entity = world.add_entity((USIZE(0), U32(1)));
+
+    let mut views = world.borrow::<(ViewMut<USIZE>, ViewMut<U32>)>().unwrap();
+
+    fn remove_both<R: Remove>(mut storages: R, entity: EntityId) -> R::Out {
+        storages.remove(entity)
+    }
+
+    assert_eq!(
+        remove_both(&mut views, entity),
+        (Some(USIZE(0)), Some(U32(1)))
+    );
+    assert!(views.0.is_empty() && views.1.is_empty());
+}
+
+#[test]
+fn remove_and_delete_tracking() {
+    #[derive(PartialEq, Eq, Debug)]
+    struct USIZE(usize);
+    impl Component for USIZE {
+        type Tracking = track::Untracked;
+    }
+
+    let mut world = World::new();
+    world.track_all::<USIZE>();
+
+    let removed = world.add_entity(USIZE(0));
+    let deleted = world.add_entity(USIZE(1));
+
+    let mut usizes = world.borrow::<ViewMut<USIZE, track::All>>().unwrap();
+
+    assert_eq!(usizes.remove(removed), Some(USIZE(0)));
+    assert_eq!(usizes.remove(removed), None);
+    assert!(usizes.delete(deleted));
+    assert!(!usizes.delete(deleted));
+
+    assert_eq!(usizes.removed().collect::<Vec<_>>(), vec![removed]);
+    assert_eq!(
+        usizes.deleted().collect::<Vec<_>>(),
+        vec![(deleted, &USIZE(1))]
+    );
+}
@@ -0,0 +1,116 @@
+use shipyard::error;
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct USIZE(usize);
+impl Component for USIZE {
+    type Tracking = track::Untracked;
+}
+
+fn registry() -> ReplayRegistry {
+    let mut registry = ReplayRegistry::new();
+
+    registry
+        .register::<U32>(
+            |u32| u32.0.to_le_bytes().to_vec(),
+            |bytes| Some(U32(u32::from_le_bytes(bytes.try_into().ok()?))),
+        )
+        .register::<USIZE>(
+            |usize| (usize.0 as u64).to_le_bytes().to_vec(),
+            |bytes| Some(USIZE(u64::from_le_bytes(bytes.try_into().ok()?) as usize)),
+        );
+
+    registry
+}
+
+#[test]
+fn record_and_replay() {
+    let mut world = World::new();
+
+    world.start_recording(registry());
+
+    let entity0 = world.add_entity((U32(0), USIZE(0)));
+    let entity1 = world.add_entity((U32(1),));
+    let entity2 = world.add_entity((USIZE(2),));
+
+    world.add_component(entity1, (USIZE(11),));
+    world.delete_component::<(U32,)>(entity0);
+    assert_eq!(world.remove::<(USIZE,)>(entity2), (Some(USIZE(2)),));
+    world.delete_entity(entity1);
+    let entity3 = world.add_entity((U32(3),));
+
+    let stream = world.stop_recording().unwrap();
+    assert!(!world.is_recording());
+
+    let mut replayed = World::new();
+    replayed.replay(&stream, &registry()).unwrap();
+
+    assert_eq!(replayed.get::<&USIZE>(entity0).as_deref(), Ok(&&USIZE(0)));
+    assert!(replayed.get::<&U32>(entity0).is_err());
+    assert!(!replayed.is_entity_alive(entity1));
+    assert!(replayed.is_entity_alive(entity2));
+    assert!(replayed.get::<&USIZE>(entity2).is_err());
+    assert_eq!(replayed.get::<&U32>(entity3).as_deref(), Ok(&&U32(3)));
+}
+
+#[test]
+fn bytes_round_trip() {
+    let mut world = World::new();
+
+    world.start_recording(registry());
+
+    let entity = world.add_entity((U32(0),));
+    world.bulk_add_entity((0..3).map(|i| (U32(i), USIZE(i as usize))));
+    world.strip(entity);
+    world.clear();
+
+    let stream = world.stop_recording().unwrap();
+    let bytes = stream.to_bytes();
+
+    assert_eq!(ReplayStream::from_bytes(&bytes), Ok(stream.clone()));
+    assert_eq!(stream.commands()[0], ReplayCommand::AddEntity(entity));
+    assert_eq!(stream.commands().last(), Some(&ReplayCommand::Clear));
+    assert_eq!(
+        ReplayStream::from_bytes(&bytes[..bytes.len() - 2]),
+        Err(error::Replay::InvalidStream)
+    );
+}
+
+#[test]
+fn unregistered_component() {
+    let mut world = World::new();
+
+    world.start_recording(registry());
+    world.add_entity((U32(0),));
+    let stream = world.stop_recording().unwrap();
+
+    let mut partial_registry = ReplayRegistry::new();
+    partial_registry.register::<USIZE>(
+        |usize| (usize.0 as u64).to_le_bytes().to_vec(),
+        |bytes| Some(USIZE(u64::from_le_bytes(bytes.try_into().ok()?) as usize)),
+    );
+
+    assert!(matches!(
+        World::new().replay(&stream, &partial_registry),
+        Err(error::Replay::UnregisteredComponent(_))
+    ));
+}
+
+#[test]
+fn views_are_not_recorded() {
+    let mut world = World::new();
+
+    world.start_recording(registry());
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<U32>| {
+        entities.add_entity(&mut u32s, U32(0));
+    });
+
+    assert!(world.stop_recording().unwrap().is_empty());
+}
@@ -0,0 +1,382 @@
+#![cfg(feature = "replication")]
+
+use serde::{Deserialize, Serialize};
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct Parent(EntityId);
+impl Component for Parent {
+    type Tracking = track::Untracked;
+}
+
+fn registry() -> ReplicationRegistry {
+    let mut registry = ReplicationRegistry::new();
+    registry
+        .register::<U32>()
+        .register::<Parent>()
+        .register_entity_references::<Parent>(|parent, visit| visit(&mut parent.0));
+
+    registry
+}
+
+#[test]
+fn spawn_change_despawn() {
+    let registry = registry();
+
+    let mut server = World::new();
+    let before = server.add_entity((U32(0),));
+    server.enable_replication(registry.clone());
+    let parent = server.add_entity((U32(1),));
+    let child = server.add_entity((Parent(parent),));
+
+    let mut client = World::new();
+    client.add_entity(());
+    let mut map = EntityIdMap::default();
+
+    let delta = server.collect_replication_delta(TrackingTimestamp::origin());
+    assert_eq!(delta.spawns(), &[before, parent, child]);
+    assert!(delta.despawns().is_empty());
+
+    let delta = ReplicationDelta::from_bytes(&delta.to_bytes()).unwrap();
+    client
+        .apply_replication_delta(&delta, &registry, &mut map)
+        .unwrap();
+
+    assert_eq!(map.len(), 3);
+    let local_parent = map.get(parent).unwrap();
+    assert_eq!(client.get::<&U32>(local_parent).as_deref(), Ok(&&U32(1)));
+    assert_eq!(
+        client.get::<&Parent>(map.get(child).unwrap()).as_deref(),
+        Ok(&&Parent(local_parent))
+    );
+
+    let tick = delta.tick();
+    assert!(server.collect_replication_delta(tick).is_empty());
+
+    server.get::<&mut U32>(parent).unwrap().0 = 2;
+    server.remove::<U32>(before);
+    server.delete_entity(child);
+
+    let delta = server.collect_replication_delta(tick);
+    assert!(delta.spawns().is_empty());
+    assert_eq!(delta.despawns(), &[child]);
+
+    client
+        .apply_replication_delta(&delta, &registry, &mut map)
+        .unwrap();
+
+    assert_eq!(client.get::<&U32>(local_parent).as_deref(), Ok(&&U32(2)));
+    assert!(client.get::<&U32>(map.get(before).unwrap()).is_err());
+    assert!(map.get(child).is_none());
+    assert_eq!(client.borrow::<View<Parent>>().unwrap().len(), 0);
+
+    server.clear_replication_older_than(server.get_tracking_timestamp());
+    assert!(server.collect_replication_delta(tick).despawns().is_empty());
+}
+
+#[test]
+fn unregistered_component() {
+    let mut server = World::new();
+    server.enable_replication(registry());
+    server.add_entity((U32(0),));
+
+    let delta = server.collect_replication_delta(TrackingTimestamp::origin());
+
+    let mut client = World::new();
+    assert_eq!(
+        client.apply_replication_delta(
+            &delta,
+            &ReplicationRegistry::new(),
+            &mut EntityIdMap::default()
+        ),
+        Err(error::Replication::UnregisteredComponent(
+            core::any::type_name::<U32>().into()
+        ))
+    );
+    assert_eq!(client.borrow::<EntitiesView>().unwrap().iter().count(), 0);
+
+    assert_eq!(
+        ReplicationDelta::from_bytes(&[0, 1, 2]),
+        Err(error::Replication::InvalidDelta)
+    );
+}
+
+#[test]
+fn interest() {
+    #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+    struct Team(u32);
+    impl Component for Team {
+        type Tracking = track::Untracked;
+    }
+
+    let mut registry = registry();
+    registry.register::<Team>();
+
+    let mut server = World::new();
+    server.enable_replication(registry.clone());
+    let ally = server.add_entity((U32(0), Team(0)));
+    let enemy = server.add_entity((U32(1), Team(1)));
+
+    let mut client = ReplicationClient::new().with_interest(|all_storages, entity| {
+        all_storages
+            .borrow::<View<Team>>()
+            .unwrap()
+            .get(entity)
+            .is_ok_and(|team| team.0 == 0)
+    });
+
+    let mut client_world = World::new();
+    let mut map = EntityIdMap::default();
+
+    let delta = server.collect_replication_delta_for(&mut client);
+    assert_eq!(delta.spawns(), &[ally]);
+    client_world
+        .apply_replication_delta(&delta, &registry, &mut map)
+        .unwrap();
+    assert!(client.is_visible(ally));
+    assert!(!client.is_visible(enemy));
+    assert!(map.get(enemy).is_none());
+
+    // the enemy joins the team, it's sent with all its components
+    server.get::<&mut Team>(enemy).unwrap().0 = 0;
+    let delta = server.collect_replication_delta_for(&mut client);
+    assert_eq!(delta.spawns(), &[enemy]);
+    client_world
+        .apply_replication_delta(&delta, &registry, &mut map)
+        .unwrap();
+    assert_eq!(
+        client_world.get::<&U32>(map.get(enemy).unwrap()).as_deref(),
+        Ok(&&U32(1))
+    );
+
+    assert!(server.collect_replication_delta_for(&mut client).is_empty());
+
+    // the ally leaves the team, it's despawned on the client
+    server.get::<&mut Team>(ally).unwrap().0 = 1;
+    let delta = server.collect_replication_delta_for(&mut client);
+    assert_eq!(delta.despawns(), &[ally]);
+    client_world
+        .apply_replication_delta(&delta, &registry, &mut map)
+        .unwrap();
+    assert!(map.get(ally).is_none());
+    assert_eq!(client_world.borrow::<View<U32>>().unwrap().len(), 1);
+
+    client.reset();
+    assert_eq!(
+        server.collect_replication_delta_for(&mut client).spawns(),
+        &[enemy]
+    );
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn pod() {
+    #[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+    #[repr(C)]
+    struct Velocity([f32; 2]);
+    impl Component for Velocity {
+        type Tracking = track::Untracked;
+    }
+    unsafe impl bytemuck::Zeroable for Velocity {}
+    unsafe impl bytemuck::Pod for Velocity {}
+
+    let mut pod_registry = ReplicationRegistry::new();
+    pod_registry.register_pod::<Velocity>();
+
+    let mut serde_server = World::new();
+    let mut serde_registry = ReplicationRegistry::new();
+    serde_registry.register::<Velocity>();
+    serde_server.enable_replication(serde_registry);
+    serde_server.add_entity((Velocity([1.0, -1.0]),));
+
+    let mut server = World::new();
+    server.enable_replication(pod_registry.clone());
+    let entity = server.add_entity((Velocity([1.0, -1.0]),));
+
+    let delta = server.collect_replication_delta(TrackingTimestamp::origin());
+    assert_eq!(
+        delta.to_bytes(),
+        serde_server
+            .collect_replication_delta(TrackingTimestamp::origin())
+            .to_bytes()
+    );
+
+    let mut client = World::new();
+    let mut map = EntityIdMap::default();
+    client
+        .apply_replication_delta(&delta, &pod_registry, &mut map)
+        .unwrap();
+
+    assert_eq!(
+        client.get::<&Velocity>(map.get(entity).unwrap()).as_deref(),
+        Ok(&&Velocity([1.0, -1.0]))
+    );
+}
+
+/// Flips all bits and appends a checksum.
+struct Inverted;
+
+impl Compressor for Inverted {
+    fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+        let checksum = bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+
+        bytes.iter().map(|byte| !byte).chain([checksum]).collect()
+    }
+    fn decompress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+        let (&checksum, bytes) = bytes.split_last()?;
+        let bytes: Vec<u8> = bytes.iter().map(|byte| !byte).collect();
+
+        (bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) == checksum).then_some(bytes)
+    }
+}
+
+#[test]
+fn compressed() {
+    let registry = registry();
+
+    let mut server = World::new();
+    server.enable_replication(registry.clone());
+    let entity = server.add_entity((U32(3),));
+
+    let delta = server.collect_replication_delta(TrackingTimestamp::origin());
+    let bytes = delta.to_compressed_bytes(&Inverted);
+    assert_ne!(bytes, delta.to_bytes());
+    assert_eq!(
+        ReplicationDelta::from_compressed_bytes(&bytes, &Inverted).as_ref(),
+        Ok(&delta)
+    );
+    assert_eq!(
+        ReplicationDelta::from_bytes(&bytes),
+        Err(error::Replication::InvalidDelta)
+    );
+
+    let mut corrupted = bytes.clone();
+    corrupted[0] ^= 1;
+    assert_eq!(
+        ReplicationDelta::from_compressed_bytes(&corrupted, &Inverted),
+        Err(error::Replication::InvalidDelta)
+    );
+
+    let mut client = World::new();
+    let mut map = EntityIdMap::default();
+    client
+        .apply_replication_delta(
+            &ReplicationDelta::from_compressed_bytes(&bytes, &Inverted).unwrap(),
+            &registry,
+            &mut map,
+        )
+        .unwrap();
+    assert_eq!(
+        client.get::<&U32>(map.get(entity).unwrap()).as_deref(),
+        Ok(&&U32(3))
+    );
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Transform {
+    position: [f32; 2],
+    rotation: f32,
+    visible: bool,
+}
+impl Component for Transform {
+    type Tracking = track::Untracked;
+}
+
+struct TransformEncoder {
+    position: Quantizer,
+    rotation: Quantizer,
+}
+
+impl ReplicationEncoder<Transform> for TransformEncoder {
+    fn encode(&self, transform: &Transform, writer: &mut BitWriter) {
+        self.position.write(transform.position[0], writer);
+        self.position.write(transform.position[1], writer);
+        self.rotation.write(transform.rotation, writer);
+        writer.write_bool(transform.visible);
+    }
+    fn decode(&self, reader: &mut BitReader<'_>) -> Option<Transform> {
+        Some(Transform {
+            position: [self.position.read(reader)?, self.position.read(reader)?],
+            rotation: self.rotation.read(reader)?,
+            visible: reader.read_bool()?,
+        })
+    }
+}
+
+#[test]
+fn quantized() {
+    let quantizer = Quantizer::with_step(-1024.0, 1024.0, 1.0 / 256.0);
+    assert_eq!(quantizer.bits(), 20);
+    assert!(quantizer.step() <= 1.0 / 256.0);
+    assert_eq!(quantizer.dequantize(quantizer.quantize(-1024.0)), -1024.0);
+    assert_eq!(quantizer.dequantize(quantizer.quantize(1024.0)), 1024.0);
+    assert_eq!(quantizer.quantize(-5000.0), 0);
+    assert_eq!(quantizer.quantize(f32::NAN), 0);
+    assert_eq!(quantizer.quantize(5000.0), (1 << 20) - 1);
+
+    let mut writer = BitWriter::new();
+    writer.write_bits(0b101, 3);
+    writer.write_bits(u64::MAX, 64);
+    writer.write_f32(1.5);
+    assert_eq!(writer.len(), 99);
+    let bytes = writer.into_bytes();
+    assert_eq!(bytes.len(), 13);
+    let mut reader = BitReader::new(&bytes);
+    assert_eq!(reader.read_bits(3), Some(0b101));
+    assert_eq!(reader.read_bits(64), Some(u64::MAX));
+    assert_eq!(reader.read_f32(), Some(1.5));
+    assert_eq!(reader.remaining(), 5);
+    assert_eq!(reader.read_bits(6), None);
+
+    let encoder = || TransformEncoder {
+        position: quantizer,
+        rotation: Quantizer::new(-std::f32::consts::PI, std::f32::consts::PI, 12),
+    };
+    let mut registry = ReplicationRegistry::new();
+    registry.register_with_encoder::<Transform, _>(encoder());
+    let mut serde_registry = ReplicationRegistry::new();
+    serde_registry.register::<Transform>();
+
+    let transform = Transform {
+        position: [12.3456, -700.001],
+        rotation: 1.0,
+        visible: true,
+    };
+
+    let mut server = World::new();
+    server.enable_replication(registry.clone());
+    let entity = server.add_entity((transform,));
+    let delta = server.collect_replication_delta(TrackingTimestamp::origin());
+
+    let mut serde_server = World::new();
+    serde_server.enable_replication(serde_registry);
+    serde_server.add_entity((Transform {
+        position: [12.3456, -700.001],
+        rotation: 1.0,
+        visible: true,
+    },));
+    let serde_delta = serde_server.collect_replication_delta(TrackingTimestamp::origin());
+    assert!(delta.to_bytes().len() < serde_delta.to_bytes().len());
+
+    let mut client = World::new();
+    let mut map = EntityIdMap::default();
+    client
+        .apply_replication_delta(
+            &ReplicationDelta::from_bytes(&delta.to_bytes()).unwrap(),
+            &registry,
+            &mut map,
+        )
+        .unwrap();
+
+    let received = client.get::<&Transform>(map.get(entity).unwrap()).unwrap();
+    assert!((received.position[0] - 12.3456).abs() <= quantizer.step() / 2.0);
+    assert!((received.position[1] + 700.001).abs() <= quantizer.step() / 2.0);
+    assert!((received.rotation - 1.0).abs() <= encoder().rotation.step() / 2.0);
+    assert!(received.visible);
+}
@@ -0,0 +1,75 @@
+use shipyard::*;
+
+struct Position;
+impl Component for Position {
+    type Tracking = track::Untracked;
+}
+
+struct Velocity;
+impl Component for Velocity {
+    type Tracking = track::Untracked;
+}
+
+struct Player;
+impl Component for Player {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn signature() {
+    let mut world = World::new();
+
+    let entity = world.add_entity((Position, Velocity));
+    let empty = world.add_entity(());
+
+    let signature = world.signature(entity);
+    assert_eq!(signature.len(), 2);
+    assert_eq!(signature, world.mask_of::<(Velocity, Position)>());
+    assert!(signature.is_superset(&world.mask_of::<Velocity>()));
+    assert!(!signature.is_superset(&world.mask_of::<Player>()));
+    assert!(world.signature(empty).is_empty());
+
+    world.remove::<Velocity>(entity);
+    assert_eq!(world.signature(entity), world.mask_of::<Position>());
+
+    world.delete_entity(entity);
+    assert!(world.signature(entity).is_empty());
+}
+
+#[test]
+fn query() {
+    let mut world = World::new();
+
+    let moving = world.add_entity((Position, Velocity));
+    let fixed = world.add_entity((Position,));
+    let player = world.add_entity((Position, Velocity, Player));
+    let empty = world.add_entity(());
+
+    assert_eq!(world.query_exact::<Position>(), [fixed]);
+    assert_eq!(world.query_exact::<(Position, Velocity)>(), [moving]);
+    assert_eq!(world.query_exact::<()>(), [empty]);
+
+    let mut at_least = world.query_at_least::<(Position, Velocity)>();
+    at_least.sort();
+    assert_eq!(at_least, [moving, player]);
+
+    assert_eq!(world.query_at_least::<()>().len(), 4);
+}
+
+#[test]
+fn mask() {
+    let mut mask = ComponentMask::new();
+    mask.insert(3);
+    mask.insert(130);
+
+    assert!(mask.contains(130));
+    assert!(!mask.contains(64));
+    assert_eq!(mask.iter().collect::<Vec<_>>(), [3, 130]);
+
+    let mut other = ComponentMask::new();
+    other.insert(3);
+    assert!(other.is_subset(&mask));
+
+    mask.remove(130);
+    assert_eq!(mask, other);
+}
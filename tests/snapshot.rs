@@ -0,0 +1,600 @@
+#![cfg(feature = "snapshot")]
+
+use serde::{Deserialize, Serialize};
+use shipyard::error;
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+
+#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+struct Name(String);
+impl Component for Name {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn round_trip() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>().register::<Name>();
+
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((U32(0), Name("0".to_string())));
+    let entity1 = world.add_entity((U32(1),));
+    let entity2 = world.add_entity(());
+    world.delete_entity(entity1);
+
+    let snapshot = world.snapshot(&registry);
+
+    let mut loaded = World::new();
+    loaded.load_snapshot(&snapshot, &registry).unwrap();
+
+    assert!(!loaded.is_entity_alive(entity1));
+    assert!(loaded.is_entity_alive(entity2));
+    assert_eq!(loaded.get::<&U32>(entity0).as_deref(), Ok(&&U32(0)));
+    assert_eq!(loaded.get::<&Name>(entity0).unwrap().0, "0".to_string());
+    assert_eq!(loaded.borrow::<View<U32>>().unwrap().len(), 1);
+}
+
+#[test]
+fn layout_mismatch() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>();
+
+    let mut world = World::new();
+    let entity = world.add_entity((U32(1),));
+    let snapshot = world.snapshot(&registry);
+
+    let mut new_registry = SnapshotRegistry::new();
+    new_registry.register_with_version::<U32>(1);
+
+    assert!(matches!(
+        World::new().load_snapshot(&snapshot, &new_registry),
+        Err(error::Snapshot::LayoutMismatch { .. })
+    ));
+
+    new_registry.register_layout_migration::<u32, U32>(layout_hash::<U32>(0), |old| U32(old * 10));
+
+    let mut loaded = World::new();
+    loaded.load_snapshot(&snapshot, &new_registry).unwrap();
+    assert_eq!(loaded.get::<&U32>(entity).as_deref(), Ok(&&U32(10)));
+}
+
+#[test]
+fn unregistered_component() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>();
+
+    let snapshot = World::new().snapshot(&registry);
+
+    assert!(matches!(
+        World::new().load_snapshot(&snapshot, &SnapshotRegistry::new()),
+        Err(error::Snapshot::UnregisteredComponent(_))
+    ));
+    assert_eq!(
+        World::new().load_snapshot(&snapshot[..snapshot.len() - 1], &registry),
+        Err(error::Snapshot::InvalidSnapshot)
+    );
+}
+
+#[test]
+fn version_migration() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>();
+
+    let mut world = World::new();
+    let entity = world.add_entity((U32(1),));
+    let v0_save = world.snapshot(&registry);
+
+    registry.set_version(2).register::<Name>();
+    let v2_save = world.snapshot(&registry);
+
+    assert_eq!(
+        World::new().load_snapshot(&v2_save, SnapshotRegistry::new().register::<U32>()),
+        Err(error::Snapshot::NewerVersion {
+            expected: 0,
+            found: 2
+        })
+    );
+
+    registry.register_migration::<u32, U32>(1, |old| U32(old + 1));
+
+    let mut loaded = World::new();
+    loaded.load_snapshot(&v0_save, &registry).unwrap();
+    assert_eq!(loaded.get::<&U32>(entity).as_deref(), Ok(&&U32(2)));
+
+    // the migration only applies up to version 1
+    let mut loaded = World::new();
+    loaded.load_snapshot(&v2_save, &registry).unwrap();
+    assert_eq!(loaded.get::<&U32>(entity).as_deref(), Ok(&&U32(1)));
+}
+
+#[test]
+fn filtered() {
+    struct Transient;
+    impl Component for Transient {
+        type Tracking = track::Untracked;
+    }
+
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>().register::<Name>();
+
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((U32(0), Name("0".to_string())));
+    let entity1 = world.add_entity((U32(1), Transient));
+    let entity2 = world.add_entity((U32(2),));
+
+    let snapshot = world.snapshot_filtered(
+        &registry,
+        &SnapshotFilter::new()
+            .allow::<U32>()
+            .without::<Transient>()
+            .entities(|entity| entity != entity2),
+    );
+
+    let mut loaded = World::new();
+    loaded.load_snapshot(&snapshot, &registry).unwrap();
+
+    assert!(loaded.is_entity_alive(entity0));
+    assert!(!loaded.is_entity_alive(entity1));
+    assert!(!loaded.is_entity_alive(entity2));
+    assert_eq!(loaded.get::<&U32>(entity0).as_deref(), Ok(&&U32(0)));
+    assert!(loaded.get::<&Name>(entity0).is_err());
+    assert_eq!(loaded.borrow::<View<U32>>().unwrap().len(), 1);
+}
+
+#[test]
+fn remapped() {
+    #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+    struct Parent(EntityId);
+    impl Component for Parent {
+        type Tracking = track::Untracked;
+    }
+
+    let mut registry = SnapshotRegistry::new();
+    registry
+        .register::<U32>()
+        .register::<Parent>()
+        .register_entity_references::<Parent>(|parent, visit| visit(&mut parent.0));
+
+    let mut world = World::new();
+    let outside = world.add_entity(());
+    let parent = world.add_entity((U32(0),));
+    let child = world.add_entity((Parent(parent),));
+    let orphan = world.add_entity((Parent(outside),));
+    world.delete_entity(outside);
+
+    let snapshot = world.snapshot(&registry);
+
+    let map = world.load_snapshot_remapped(&snapshot, &registry).unwrap();
+
+    assert_eq!(map.len(), 3);
+    assert!(map.get(outside).is_none());
+
+    let new_parent = map.get(parent).unwrap();
+    let new_child = map.get(child).unwrap();
+    assert_ne!(new_parent, parent);
+    assert_eq!(world.get::<&U32>(new_parent).as_deref(), Ok(&&U32(0)));
+    assert_eq!(
+        world.get::<&Parent>(new_child).as_deref(),
+        Ok(&&Parent(new_parent))
+    );
+    // references to entities outside the snapshot are kept as is
+    assert_eq!(
+        world.get::<&Parent>(map.get(orphan).unwrap()).as_deref(),
+        Ok(&&Parent(outside))
+    );
+}
+
+#[test]
+fn register_components() {
+    #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+    struct Health(u32);
+    impl Component for Health {
+        type Tracking = track::Untracked;
+        const NAME: Option<&'static str> = Some("health");
+
+        fn register(registrar: &mut ComponentRegistrar<'_>) {
+            registrar.serialize::<Self>();
+        }
+    }
+
+    let mut registry = SnapshotRegistry::new();
+    registry.register_components::<(Health, U32)>();
+
+    assert!(registry.is_registered::<Health>());
+    assert!(!registry.is_registered::<U32>());
+
+    let mut world = World::new();
+    let entity = world.add_entity((Health(10), U32(0)));
+
+    let snapshot = world.snapshot(&registry);
+
+    let mut loaded = World::new();
+    loaded.load_snapshot(&snapshot, &registry).unwrap();
+
+    assert_eq!(loaded.get::<&Health>(entity).as_deref(), Ok(&&Health(10)));
+    assert!(loaded.get::<&U32>(entity).is_err());
+}
+
+#[test]
+fn diff() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>().register::<Name>();
+
+    let mut world = World::new();
+
+    let unchanged = world.add_entity((U32(0), Name("0".to_string())));
+    let modified = world.add_entity((U32(1),));
+    let stripped = world.add_entity((U32(2), Name("2".to_string())));
+    let deleted = world.add_entity((U32(3),));
+
+    let older = world.snapshot(&registry);
+
+    let mut copy = World::new();
+    copy.load_snapshot(&older, &registry).unwrap();
+
+    assert!(WorldDiff::between(&older, &older).unwrap().is_empty());
+
+    world.get::<&mut U32>(modified).unwrap().0 = 10;
+    world.remove::<Name>(stripped);
+    world.delete_entity(deleted);
+    let recycled = world.add_entity((Name("recycled".to_string()),));
+
+    let newer = world.snapshot(&registry);
+    let diff = WorldDiff::between(&older, &newer).unwrap();
+
+    assert_eq!(diff.added_entities(), &[recycled]);
+    assert_eq!(diff.removed_entities(), &[deleted]);
+    assert_eq!(
+        diff.changed_components().collect::<Vec<_>>(),
+        [
+            (core::any::type_name::<U32>(), modified),
+            (core::any::type_name::<Name>(), recycled)
+        ]
+    );
+    assert_eq!(
+        diff.removed_components().collect::<Vec<_>>(),
+        [(core::any::type_name::<Name>(), stripped)]
+    );
+
+    assert!(matches!(
+        copy.apply_diff(&diff, SnapshotRegistry::new().register::<U32>()),
+        Err(error::Snapshot::UnregisteredComponent(_))
+    ));
+
+    copy.apply_diff(&diff, &registry).unwrap();
+
+    assert!(!copy.is_entity_alive(deleted));
+    assert!(copy.is_entity_alive(recycled));
+    assert_eq!(copy.get::<&U32>(unchanged).as_deref(), Ok(&&U32(0)));
+    assert_eq!(copy.get::<&U32>(modified).as_deref(), Ok(&&U32(10)));
+    assert_eq!(copy.get::<&U32>(stripped).as_deref(), Ok(&&U32(2)));
+    assert!(copy.get::<&Name>(stripped).is_err());
+    assert_eq!(copy.get::<&Name>(recycled).unwrap().0, "recycled");
+
+    let copy_snapshot = copy.snapshot(&registry);
+    assert!(WorldDiff::between(&newer, &copy_snapshot)
+        .unwrap()
+        .is_empty());
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn pod() {
+    #[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+    #[repr(C)]
+    struct Particle {
+        position: [f32; 2],
+        lifetime: u32,
+    }
+    impl Component for Particle {
+        type Tracking = track::Untracked;
+    }
+    unsafe impl bytemuck::Zeroable for Particle {}
+    unsafe impl bytemuck::Pod for Particle {}
+
+    let mut pod_registry = SnapshotRegistry::new();
+    pod_registry.register_pod::<Particle>().register::<U32>();
+
+    let mut serde_registry = SnapshotRegistry::new();
+    serde_registry.register::<Particle>().register::<U32>();
+
+    let mut world = World::new();
+
+    let entities = (0..10u32)
+        .map(|i| {
+            world.add_entity((
+                Particle {
+                    position: [i as f32, -(i as f32)],
+                    lifetime: i * 10,
+                },
+                U32(i),
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    let snapshot = world.snapshot(&pod_registry);
+    assert_eq!(snapshot, world.snapshot(&serde_registry));
+
+    let mut loaded = World::new();
+    loaded.load_snapshot(&snapshot, &pod_registry).unwrap();
+
+    assert_eq!(
+        loaded.get::<&Particle>(entities[3]).as_deref(),
+        Ok(&&Particle {
+            position: [3.0, -3.0],
+            lifetime: 30,
+        })
+    );
+    assert_eq!(loaded.borrow::<View<Particle>>().unwrap().len(), 10);
+
+    let mut loaded = World::new();
+    loaded.load_snapshot(&snapshot, &serde_registry).unwrap();
+    assert_eq!(loaded.get::<&U32>(entities[9]).as_deref(), Ok(&&U32(9)));
+}
+
+/// Stores each byte followed by the number of times it's repeated.
+struct RunLength;
+
+impl Compressor for RunLength {
+    fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut compressed = Vec::new();
+
+        for &byte in bytes {
+            match compressed.len().checked_sub(2) {
+                Some(last) if compressed[last] == byte && compressed[last + 1] < u8::MAX => {
+                    compressed[last + 1] += 1;
+                }
+                _ => compressed.extend_from_slice(&[byte, 1]),
+            }
+        }
+
+        compressed
+    }
+    fn decompress(&self, bytes: &[u8]) -> Option<Vec<u8>> {
+        if bytes.len() % 2 != 0 || bytes.chunks(2).any(|run| run[1] == 0) {
+            return None;
+        }
+
+        Some(
+            bytes
+                .chunks(2)
+                .flat_map(|run| std::iter::repeat(run[0]).take(run[1] as usize))
+                .collect(),
+        )
+    }
+}
+
+#[test]
+fn compressed() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>();
+
+    let mut compressed_registry = registry.clone();
+    compressed_registry.set_compressor(RunLength);
+
+    let mut world = World::new();
+    let entities = (0..20)
+        .map(|_| world.add_entity((U32(0),)))
+        .collect::<Vec<_>>();
+
+    let snapshot = world.snapshot(&registry);
+    let compressed = world.snapshot(&compressed_registry);
+    assert!(compressed.len() < snapshot.len());
+    assert_eq!(
+        compressed_registry.decompress(&compressed).as_deref(),
+        Ok(&*snapshot)
+    );
+
+    let mut loaded = World::new();
+    loaded
+        .load_snapshot(&compressed, &compressed_registry)
+        .unwrap();
+    assert_eq!(loaded.get::<&U32>(entities[19]).as_deref(), Ok(&&U32(0)));
+
+    assert_eq!(
+        World::new().load_snapshot(&compressed, &registry),
+        Err(error::Snapshot::InvalidSnapshot)
+    );
+    assert_eq!(
+        World::new().load_snapshot(&snapshot, &compressed_registry),
+        Err(error::Snapshot::InvalidSnapshot)
+    );
+
+    world.get::<&mut U32>(entities[5]).unwrap().0 = 5;
+    let newer = world.snapshot(&compressed_registry);
+    let diff = WorldDiff::between(
+        &compressed_registry.decompress(&compressed).unwrap(),
+        &compressed_registry.decompress(&newer).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        diff.changed_components().collect::<Vec<_>>(),
+        [(core::any::type_name::<U32>(), entities[5])]
+    );
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn lz4() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>().set_compressor(Lz4);
+
+    let mut world = World::new();
+    let entity = world.add_entity((U32(7),));
+
+    let snapshot = world.snapshot(&registry);
+
+    let mut loaded = World::new();
+    loaded.load_snapshot(&snapshot, &registry).unwrap();
+    assert_eq!(loaded.get::<&U32>(entity).as_deref(), Ok(&&U32(7)));
+}
+
+#[test]
+fn scope() {
+    #[derive(Component)]
+    struct Tag;
+
+    #[derive(Unique)]
+    struct Frame;
+
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>();
+
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((U32(0),));
+    let entity1 = world.add_entity((U32(1),));
+    let deleted = world.add_entity(());
+    world.delete_entity(deleted);
+
+    let created = world
+        .scope(&registry, |world| {
+            world.delete_entity(entity0);
+            world.get::<&mut U32>(entity1).unwrap().0 = 10;
+            world.add_component(entity1, Tag);
+            world.add_unique(Frame);
+
+            world.add_entity((U32(2),))
+        })
+        .unwrap();
+
+    assert!(world.is_entity_alive(entity0));
+    assert!(!world.is_entity_alive(created));
+    assert!(!world.is_entity_alive(deleted));
+    assert_eq!(world.get::<&U32>(entity0).as_deref(), Ok(&&U32(0)));
+    assert_eq!(world.get::<&U32>(entity1).as_deref(), Ok(&&U32(1)));
+    assert!(world.borrow::<View<Tag>>().unwrap().is_empty());
+    assert!(world.borrow::<UniqueView<Frame>>().is_err());
+    assert_eq!(world.borrow::<View<U32>>().unwrap().len(), 2);
+
+    // the recycled ids are the same as without the scope
+    assert_eq!(world.add_entity(()), created);
+}
+
+#[test]
+fn scope_storage_handle() {
+    #[derive(Component)]
+    struct Tag;
+
+    let registry = SnapshotRegistry::new();
+
+    let mut world = World::new();
+    world.set_strict(true);
+
+    let handle = world
+        .scope(&registry, |world| {
+            let handle = world.storage_handle::<Tag>();
+            world.add_entity((Tag,));
+
+            handle
+        })
+        .unwrap();
+
+    // the storage was removed by the rollback
+    assert_eq!(
+        handle.view(&world).err(),
+        Some(error::GetStorage::UnregisteredStorage {
+            name: std::any::type_name::<Tag>(),
+            id: StorageId::of::<SparseSet<Tag>>(),
+        })
+    );
+}
+
+#[test]
+fn scope_panic() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>();
+
+    let mut world = World::new();
+    let entity = world.add_entity((U32(0),));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = world.scope(&registry, |world| {
+            world.delete_entity(entity);
+
+            panic!();
+        });
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(world.get::<&U32>(entity).as_deref(), Ok(&&U32(0)));
+}
+
+#[test]
+fn world_matches() {
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>();
+
+    let mut world = World::new();
+    let entity = world.add_entity((U32(0),));
+
+    let expected = testing::WorldState::of(&mut world, &registry);
+
+    let path = std::env::temp_dir().join("shipyard_world_matches.golden");
+    expected.write_file(&path).unwrap();
+    let golden = testing::WorldState::from_file(&path, &registry).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    world.get::<&mut U32>(entity).unwrap().0 = 1;
+    world.get::<&mut U32>(entity).unwrap().0 = 0;
+
+    assert_world_matches!(world, expected);
+    assert_world_matches!(world, golden);
+}
+
+#[test]
+fn world_mismatch() {
+    #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
+    struct Tag(u8);
+    impl Component for Tag {
+        type Tracking = track::Untracked;
+    }
+
+    let mut registry = SnapshotRegistry::new();
+    registry.register::<U32>().register::<Tag>();
+
+    let mut world = World::new();
+    let modified = world.add_entity((U32(0), Tag(0)));
+    let stripped = world.add_entity((U32(1), Tag(1)));
+    let deleted = world.add_entity((U32(2),));
+
+    let expected = testing::WorldState::of(&mut world, &registry);
+
+    world.get::<&mut U32>(modified).unwrap().0 = 10;
+    world.add_component(deleted, Tag(2));
+    world.remove::<Tag>(stripped);
+    world.delete_entity(deleted);
+    let added = world.add_entity((U32(3),));
+
+    let mismatch = expected.mismatch(&mut world).unwrap().unwrap();
+
+    assert_eq!(mismatch.missing_entities(), &[deleted]);
+    assert_eq!(mismatch.unexpected_entities(), &[added]);
+    assert_eq!(
+        mismatch.to_string(),
+        format!(
+            "- {:?} is missing\n+ {:?} is unexpected\n~ {:?} has a different {}\n- {:?} is missing {}\n",
+            deleted,
+            added,
+            modified,
+            core::any::type_name::<U32>(),
+            stripped,
+            core::any::type_name::<Tag>(),
+        )
+    );
+
+    world.delete_entity(added);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        assert_world_matches!(world, expected);
+    }));
+
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.starts_with("World doesn't match the expected state:\n"));
+}
@@ -0,0 +1,119 @@
+use shipyard::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Position([f32; 2]);
+
+impl Component for Position {
+    type Tracking = track::InsertionAndModification;
+}
+
+impl SpatialPosition for Position {
+    fn position(&self) -> [f32; 2] {
+        self.0
+    }
+}
+
+fn brute_force(positions: &View<Position>, point: [f32; 2]) -> Vec<(EntityId, f32)> {
+    let mut all: Vec<_> = positions
+        .iter()
+        .with_id()
+        .map(|(entity, position)| {
+            let x = position.0[0] - point[0];
+            let y = position.0[1] - point[1];
+
+            (entity, x * x + y * y)
+        })
+        .collect();
+
+    all.sort_unstable_by(|a, b| a.1.total_cmp(&b.1));
+
+    all
+}
+
+#[test]
+fn queries() {
+    let mut world = World::new();
+
+    // deterministic spread over several cells, including negative coordinates
+    let mut state = 7u32;
+    let mut next = move || {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (state >> 16) as f32 / 65536.0 * 200.0 - 100.0
+    };
+
+    for _ in 0..200 {
+        world.add_entity(Position([next(), next()]));
+    }
+
+    let mut grid = SpatialGrid::<Position>::new(7.5);
+    let positions = world.borrow::<View<Position>>().unwrap();
+    grid.update(&positions);
+
+    assert_eq!(grid.len(), 200);
+
+    for point in [[0.0, 0.0], [-55.5, 31.0], [99.0, -99.0], [500.0, 500.0]] {
+        let expected = brute_force(&positions, point);
+
+        let nearest: Vec<_> = grid.nearest(point, 5, &positions).collect();
+        assert_eq!(nearest, expected[..5]);
+
+        let mut within: Vec<_> = grid.within_radius(point, 20.0, &positions).collect();
+        within.sort_unstable_by(|a, b| a.1.total_cmp(&b.1));
+        let expected_within: Vec<_> = expected
+            .iter()
+            .copied()
+            .filter(|&(_, distance)| distance <= 400.0)
+            .collect();
+        assert_eq!(within, expected_within);
+    }
+
+    assert_eq!(grid.nearest([0.0, 0.0], 0, &positions).count(), 0);
+    assert_eq!(grid.nearest([0.0, 0.0], 500, &positions).count(), 200);
+    assert_eq!(
+        grid.within_radius([0.0, 0.0], 1000.0, &positions).count(),
+        200
+    );
+}
+
+#[test]
+fn tracking() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(Position([0.0, 0.0]));
+    let entity1 = world.add_entity(Position([50.0, 0.0]));
+
+    world.add_unique(SpatialGrid::<Position>::new(10.0));
+    world.run(update_spatial_grid::<Position>);
+    world.run(|positions: ViewMut<Position>| positions.clear_all_inserted_and_modified());
+
+    world.run(|mut positions: ViewMut<Position>| {
+        (&mut positions).get(entity1).unwrap().0 = [1.0, 1.0];
+    });
+    world.run(update_spatial_grid::<Position>);
+
+    world.run(
+        |grid: UniqueView<SpatialGrid<Position>>, positions: View<Position>| {
+            let nearest: Vec<_> = grid.nearest([0.0, 0.0], 2, &positions).collect();
+            assert_eq!(nearest, [(entity0, 0.0), (entity1, 2.0)]);
+        },
+    );
+
+    world.delete_entity(entity0);
+
+    world.run(
+        |grid: UniqueView<SpatialGrid<Position>>, positions: View<Position>| {
+            // deleted entities are skipped before the grid is updated
+            let within: Vec<_> = grid.within_radius([0.0, 0.0], 5.0, &positions).collect();
+            assert_eq!(within, [(entity1, 2.0)]);
+        },
+    );
+
+    world.run(update_spatial_grid::<Position>);
+    assert_eq!(
+        world
+            .borrow::<UniqueView<SpatialGrid<Position>>>()
+            .unwrap()
+            .len(),
+        1
+    );
+}
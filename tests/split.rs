@@ -0,0 +1,78 @@
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Modification;
+}
+
+#[test]
+fn split_at_mut() {
+    let mut world = World::new();
+
+    let entities = world
+        .bulk_add_entity((0..10).map(|i| (U32(i),)))
+        .collect::<Vec<_>>();
+
+    world.run(|mut u32s: ViewMut<U32>| {
+        let (mut left, mut right) = u32s.split_at_mut(4);
+
+        assert_eq!(left.len(), 4);
+        assert_eq!(right.len(), 6);
+        assert_eq!(left.ids(), &entities[..4]);
+        assert_eq!(right.get(entities[4]), Some(&U32(4)));
+        assert_eq!(right.get(entities[3]), None);
+        assert!(left.contains(entities[3]));
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| left.iter_mut().for_each(|mut u32| u32.0 *= 10));
+            scope.spawn(|| right.get_mut(entities[9]).unwrap().0 = 0);
+        });
+
+        assert_eq!(u32s[entities[3]], U32(30));
+        assert_eq!(u32s[entities[9]], U32(0));
+        assert!(u32s.is_modified(entities[0]));
+        assert!(u32s.is_modified(entities[9]));
+        assert!(!u32s.is_modified(entities[5]));
+
+        let (left, right) = u32s.split_at_mut(0);
+        assert!(left.is_empty());
+        assert_eq!(right.len(), 10);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Cannot split a storage of length 2 at 3.")]
+fn split_out_of_bound() {
+    let mut world = World::new();
+
+    world.add_entity((U32(0),));
+    world.add_entity((U32(1),));
+
+    world.borrow::<ViewMut<U32>>().unwrap().split_at_mut(3);
+}
+
+#[test]
+fn partition() {
+    let mut world = World::new();
+
+    let entities = world
+        .bulk_add_entity((0..10).map(|i| (U32(i),)))
+        .collect::<Vec<_>>();
+
+    let mut u32s = world.borrow::<ViewMut<U32>>().unwrap();
+
+    let (even, odd) = u32s.partition(|_, u32| u32.0 % 2 == 0);
+
+    assert_eq!(even.len(), 5);
+    assert_eq!(odd.len(), 5);
+    assert!(even.iter().all(|u32| u32.0 % 2 == 0));
+    assert!(odd.iter().all(|u32| u32.0 % 2 == 1));
+    for (id, u32) in odd.ids().iter().zip(odd.iter()) {
+        assert_eq!(odd.get(*id), Some(u32));
+    }
+
+    for (i, &entity) in entities.iter().enumerate() {
+        assert_eq!(u32s[entity], U32(i as u32));
+    }
+}
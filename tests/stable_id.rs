@@ -0,0 +1,56 @@
+use shipyard::*;
+
+#[test]
+fn generate() {
+    let mut stable_ids = StableIds::with_seed(0);
+
+    let id0 = stable_ids.generate();
+    let id1 = stable_ids.generate();
+
+    assert_ne!(id0, id1);
+    // version 4, variant 1
+    assert_eq!((id0.0 >> 76) & 0xF, 4);
+    assert_eq!((id0.0 >> 62) & 0x3, 2);
+
+    assert_eq!(StableIds::with_seed(0).generate(), id0);
+}
+
+#[test]
+fn lookup() {
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(());
+    let entity1 = world.add_entity(());
+
+    let stable_id0 = world.stable_id(entity0);
+    let stable_id1 = world.stable_id(entity1);
+
+    assert_ne!(stable_id0, stable_id1);
+    assert_eq!(world.stable_id(entity0), stable_id0);
+    assert_eq!(world.entity_of_stable_id(stable_id1), Some(entity1));
+
+    world.delete_entity(entity0);
+    assert_eq!(world.entity_of_stable_id(stable_id0), None);
+
+    // ids added directly, like when loading a save, are found
+    let loaded = StableId(42);
+    let entity2 = world.add_entity(loaded);
+    assert_eq!(world.entity_of_stable_id(loaded), Some(entity2));
+
+    // moving an id to another entity
+    world.remove::<(StableId,)>(entity2);
+    let entity3 = world.add_entity(loaded);
+    assert_eq!(world.entity_of_stable_id(loaded), Some(entity3));
+    assert_eq!(world.entity_of_stable_id(stable_id1), Some(entity1));
+}
+
+#[test]
+#[should_panic]
+fn dead_entity() {
+    let mut world = World::new();
+
+    let entity = world.add_entity(());
+    world.delete_entity(entity);
+
+    world.stable_id(entity);
+}
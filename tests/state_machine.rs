@@ -0,0 +1,96 @@
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+enum GameState {
+    Menu,
+    Gameplay,
+}
+
+#[derive(Default)]
+struct Log(Vec<&'static str>);
+impl Unique for Log {}
+
+macro_rules! logging_workload {
+    ($world: ident, $name: literal) => {
+        Workload::new($name)
+            .with_system(|mut log: UniqueViewMut<Log>| log.0.push($name))
+            .add_to_world(&$world)
+            .unwrap();
+    };
+}
+
+fn world() -> World {
+    let world = World::new();
+    world.add_unique(Log::default());
+
+    logging_workload!(world, "menu");
+    logging_workload!(world, "gameplay");
+    logging_workload!(world, "enter_menu");
+    logging_workload!(world, "exit_menu");
+    logging_workload!(world, "enter_gameplay");
+
+    world.add_unique(
+        StateMachine::new()
+            .with_workload(GameState::Menu, "menu")
+            .with_on_enter(GameState::Menu, "enter_menu")
+            .with_on_exit(GameState::Menu, "exit_menu")
+            .with_workload(GameState::Gameplay, "gameplay")
+            .with_on_enter(GameState::Gameplay, "enter_gameplay"),
+    );
+
+    world
+}
+
+fn take_log(world: &World) -> Vec<&'static str> {
+    world.run(|mut log: UniqueViewMut<Log>| core::mem::take(&mut log.0))
+}
+
+#[test]
+fn transitions() {
+    let world = world();
+
+    world.set_state(GameState::Menu).unwrap();
+    world.run_default_workload().unwrap();
+    assert_eq!(take_log(&world), ["enter_menu", "menu"]);
+
+    // same state is a no-op
+    world.set_state(GameState::Menu).unwrap();
+    assert!(take_log(&world).is_empty());
+
+    world.set_state(GameState::Gameplay).unwrap();
+    world.run_default_workload().unwrap();
+    assert_eq!(
+        take_log(&world),
+        ["exit_menu", "enter_gameplay", "gameplay"]
+    );
+
+    world.run(|states: UniqueView<StateMachine<GameState>>| {
+        assert_eq!(states.state(), Some(&GameState::Gameplay));
+    });
+
+    world.set_state(GameState::Menu).unwrap();
+    assert_eq!(take_log(&world), ["enter_menu"]);
+}
+
+#[test]
+fn missing_state_machine() {
+    let world = World::new();
+
+    assert!(matches!(
+        world.set_state(GameState::Menu),
+        Err(error::SetState::StateMachine(_))
+    ));
+}
+
+#[test]
+fn missing_workload() {
+    let world = World::new();
+    world.add_unique(StateMachine::new().with_workload(GameState::Menu, "menu"));
+
+    assert!(matches!(
+        world.set_state(GameState::Menu),
+        Err(error::SetState::SetDefaultWorkload(
+            error::SetDefaultWorkload::MissingWorkload
+        ))
+    ));
+}
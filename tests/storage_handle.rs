@@ -0,0 +1,75 @@
+use shipyard::*;
+
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn borrow() {
+    let world = World::new();
+    let handle = world.storage_handle::<U32>();
+
+    world.run(|mut entities: EntitiesViewMut| {
+        let mut u32s = handle.view_mut(&world).unwrap();
+        entities.add_entity(&mut u32s, U32(0));
+        entities.add_entity(&mut u32s, U32(1));
+    });
+
+    let u32s = handle.view(&world).unwrap();
+    assert_eq!(u32s.iter().map(|x| x.0).sum::<u32>(), 1);
+    assert_eq!(world.borrow::<View<U32>>().unwrap().len(), 2);
+
+    assert!(matches!(
+        handle.view_mut(&world),
+        Err(error::GetStorage::StorageBorrow { .. })
+    ));
+    drop(u32s);
+
+    let _all_storages = world.borrow::<AllStoragesViewMut>().unwrap();
+    assert!(matches!(
+        handle.view(&world),
+        Err(error::GetStorage::AllStoragesBorrow(_))
+    ));
+}
+
+#[test]
+fn other_world() {
+    let world1 = World::new();
+    let mut world2 = World::new();
+    let handle = world1.storage_handle::<U32>();
+
+    world2.add_entity((U32(0),));
+
+    assert_eq!(handle.view(&world1).unwrap().len(), 0);
+    assert_eq!(handle.view(&world2).unwrap().len(), 1);
+}
+
+struct NotASparseSet;
+impl Storage for NotASparseSet {}
+
+#[test]
+#[should_panic]
+fn other_storage_type() {
+    let world = World::new();
+    world
+        .all_storages()
+        .unwrap()
+        .add_custom_storage(StorageId::of::<SparseSet<U32>>(), NotASparseSet)
+        .unwrap();
+    let handle = world.storage_handle::<U32>();
+
+    let _ = handle.view(&world);
+}
+
+#[test]
+fn replaced_storage() {
+    let mut world = World::new();
+    let handle = world.storage_handle::<U32>();
+
+    world.add_entity((U32(0),));
+    world.add_storage_in::<U32>(StorageAllocator::global());
+    world.add_entity((U32(1),));
+
+    assert_eq!(handle.view(&world).unwrap().len(), 1);
+}
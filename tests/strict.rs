@@ -0,0 +1,34 @@
+use shipyard::*;
+
+#[derive(Component)]
+struct Position(f32);
+
+#[derive(Component)]
+struct Pos(f32);
+
+#[derive(Component)]
+struct Velocity(f32);
+
+#[test]
+fn strict() {
+    let mut world = World::new();
+    world.set_strict(true);
+    world.register_components::<Position>();
+
+    assert!(world.is_strict());
+    assert!(world.borrow::<ViewMut<Position>>().is_ok());
+    assert_eq!(
+        world.borrow::<ViewMut<Pos>>().err(),
+        Some(error::GetStorage::UnregisteredStorage {
+            name: std::any::type_name::<Pos>(),
+            id: StorageId::of::<SparseSet<Pos>>(),
+        })
+    );
+
+    // adding a component creates its storage
+    world.add_entity(Velocity(1.0));
+    assert!(world.borrow::<View<Velocity>>().is_ok());
+
+    world.set_strict(false);
+    assert!(world.borrow::<View<Pos>>().is_ok());
+}
@@ -0,0 +1,50 @@
+use shipyard::*;
+
+#[derive(Component)]
+struct Health(u32);
+
+#[test]
+fn no_change() {
+    let mut world = World::new();
+    let entity = world.add_entity(Health(10));
+
+    let health = world.assert_no_structural_changes(|| {
+        world.run(|mut healths: ViewMut<Health>| {
+            healths[entity].0 -= 1;
+            healths[entity].0
+        })
+    });
+
+    assert_eq!(health, 9);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "entities or components were added or removed while guarded")]
+fn add_and_delete() {
+    let mut world = World::new();
+    let entity = world.add_entity(Health(10));
+
+    world.assert_no_structural_changes(|| {
+        world.run(
+            |mut entities: EntitiesViewMut, mut healths: ViewMut<Health>| {
+                healths.remove(entity);
+                entities.add_entity(&mut healths, Health(10));
+            },
+        );
+    });
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Health")]
+fn remove_component() {
+    let mut world = World::new();
+    let entity = world.add_entity(Health(10));
+    let all_storages = world.all_storages().unwrap();
+
+    let _guard = all_storages.structural_change_guard();
+    all_storages.run(|mut healths: ViewMut<Health>| {
+        healths.remove(entity);
+    });
+}
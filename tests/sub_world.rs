@@ -0,0 +1,88 @@
+use shipyard::error::GetStorage;
+use shipyard::*;
+
+#[derive(PartialEq, Eq, Debug)]
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+
+#[derive(PartialEq, Eq, Debug)]
+struct USIZE(usize);
+impl Component for USIZE {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn restricted_borrow() {
+    let mut world = World::new();
+    world.add_entity((U32(0), USIZE(0)));
+
+    let sub_world = world.sub_world::<(ViewMut<U32>, View<USIZE>)>();
+
+    assert!(sub_world.borrow::<View<U32>>().is_ok());
+    assert!(sub_world.borrow::<ViewMut<U32>>().is_ok());
+    assert!(sub_world.borrow::<(ViewMut<U32>, View<USIZE>)>().is_ok());
+    assert_eq!(
+        sub_world.borrow::<ViewMut<USIZE>>().err(),
+        Some(GetStorage::NotAllowed {
+            name: core::any::type_name::<SparseSet<USIZE>>().into(),
+            id: StorageId::of::<SparseSet<USIZE>>(),
+            mutability: Mutability::Exclusive,
+        })
+    );
+    assert!(sub_world.borrow::<EntitiesView>().is_err());
+    assert!(sub_world.borrow::<AllStoragesView>().is_err());
+    assert!(!sub_world.is_allowed::<AllStoragesViewMut>());
+    assert_eq!(sub_world.access().len(), 2);
+
+    let sum = sub_world.run(|u32s: View<U32>, usizes: View<USIZE>| {
+        (&u32s, &usizes)
+            .iter()
+            .map(|(a, b)| a.0 as usize + b.0)
+            .sum::<usize>()
+    });
+    assert_eq!(sum, 0);
+
+    sub_world.run_with_data(
+        |value: u32, mut u32s: ViewMut<U32>| {
+            for u32 in (&mut u32s).iter() {
+                u32.0 = value;
+            }
+        },
+        5,
+    );
+    assert_eq!(
+        world.borrow::<View<U32>>().unwrap().iter().next(),
+        Some(&U32(5))
+    );
+}
+
+#[test]
+#[should_panic(expected = "not part of the SubWorld")]
+fn run_not_allowed() {
+    let world = World::new();
+    let sub_world = world.sub_world::<View<U32>>();
+
+    sub_world.run(|_: AllStoragesViewMut| {});
+}
+
+#[test]
+fn all_storages_view_of() {
+    let world = World::new();
+    let sub_world = world.sub_world::<View<U32>>();
+
+    let all_storages = sub_world.borrow::<AllStoragesViewOf<View<U32>>>().unwrap();
+
+    assert!(all_storages.declared().is_ok());
+    assert!(all_storages.borrow::<View<U32>>().is_ok());
+    assert_eq!(
+        all_storages.borrow::<ViewMut<U32>>().err(),
+        Some(GetStorage::NotAllowed {
+            name: core::any::type_name::<SparseSet<U32>>().into(),
+            id: StorageId::of::<SparseSet<U32>>(),
+            mutability: Mutability::Exclusive,
+        })
+    );
+    assert!(all_storages.borrow::<View<USIZE>>().is_err());
+}
@@ -0,0 +1,157 @@
+use shipyard::*;
+
+#[derive(Component)]
+struct Position(f32);
+
+fn gravity(mut positions: ViewMut<Position>) {
+    for position in (&mut positions).iter() {
+        position.0 -= 1.0;
+    }
+}
+
+fn wind(mut positions: ViewMut<Position>) {
+    for position in (&mut positions).iter() {
+        position.0 += 0.5;
+    }
+}
+
+fn registry() -> SystemRegistry {
+    let mut registry = SystemRegistry::new();
+    registry.register("gravity", gravity).register("wind", wind);
+
+    registry
+}
+
+#[test]
+fn disabled() {
+    let registry = registry();
+    let descriptor = WorkloadDescriptor {
+        name: "Update".to_string(),
+        systems: vec![
+            SystemDescriptor::new("gravity"),
+            SystemDescriptor {
+                name: "wind".to_string(),
+                disabled: true,
+            },
+        ],
+    };
+
+    let world = World::new();
+    let entity = world.add_entity(Position(0.0));
+
+    registry
+        .workload(&descriptor)
+        .unwrap()
+        .add_to_world(&world)
+        .unwrap();
+    world.run_workload("Update").unwrap();
+
+    assert_eq!(world.get::<&Position>(entity).unwrap().0, -1.0);
+    assert_eq!(
+        registry.describe(&world.workloads_info().0["Update"]),
+        Ok(WorkloadDescriptor {
+            name: "Update".to_string(),
+            systems: vec![SystemDescriptor::new("gravity")],
+        })
+    );
+}
+
+#[test]
+fn unknown_system() {
+    let registry = registry();
+    let descriptor = WorkloadDescriptor {
+        name: "Update".to_string(),
+        systems: vec![SystemDescriptor::new("jump")],
+    };
+
+    assert_eq!(
+        registry.workload(&descriptor).err(),
+        Some(error::BuildWorkloadDescriptor::UnknownSystem(
+            "jump".to_string()
+        ))
+    );
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn json() {
+    let registry = registry();
+    let descriptor: WorkloadDescriptor = serde_json::from_str(
+        r#"{"name":"Update","systems":[{"name":"wind"},{"name":"gravity","disabled":true}]}"#,
+    )
+    .unwrap();
+
+    let world = World::new();
+    registry
+        .workload(&descriptor)
+        .unwrap()
+        .add_to_world(&world)
+        .unwrap();
+
+    let described = registry
+        .describe(&world.workloads_info().0["Update"])
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&described).unwrap(),
+        r#"{"name":"Update","systems":[{"name":"wind"}]}"#
+    );
+}
+
+#[test]
+fn replaced_system() {
+    let mut registry = registry();
+    registry.register("gravity", wind);
+
+    let world = World::new();
+    Workload::new("Update")
+        .with_system(gravity)
+        .add_to_world(&world)
+        .unwrap();
+
+    assert!(matches!(
+        registry.describe(&world.workloads_info().0["Update"]),
+        Err(error::BuildWorkloadDescriptor::UnregisteredSystem(_))
+    ));
+}
+
+#[test]
+fn describe_is_lossy() {
+    let registry = registry();
+
+    let world = World::new();
+    let entity = world.add_entity(Position(0.0));
+    Workload::new("Update")
+        .with_system(gravity.after_all(wind).tag("physics").run_if(|| false))
+        .with_system(wind)
+        .add_to_world(&world)
+        .unwrap();
+    world.run_workload("Update").unwrap();
+    assert_eq!(world.get::<&Position>(entity).unwrap().0, 0.5);
+
+    // only the names in execution order are kept
+    let descriptor = registry
+        .describe(&world.workloads_info().0["Update"])
+        .unwrap();
+    assert_eq!(
+        descriptor,
+        WorkloadDescriptor {
+            name: "Update".to_string(),
+            systems: vec![
+                SystemDescriptor::new("wind"),
+                SystemDescriptor::new("gravity")
+            ],
+        }
+    );
+
+    // the run condition is lost, gravity runs
+    let world = World::new();
+    let entity = world.add_entity(Position(0.0));
+    registry
+        .workload(&descriptor)
+        .unwrap()
+        .add_to_world(&world)
+        .unwrap();
+    world.run_workload("Update").unwrap();
+    assert_eq!(world.get::<&Position>(entity).unwrap().0, -0.5);
+}
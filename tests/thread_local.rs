@@ -0,0 +1,71 @@
+use shipyard::*;
+
+struct U32(u32);
+impl Component for U32 {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn accumulate_and_drain() {
+    let world = World::new();
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<U32>| {
+        for i in 0..10 {
+            entities.add_entity(&mut u32s, U32(i));
+        }
+    });
+
+    fn sum(u32s: View<U32>, sums: ThreadLocal<u32>) {
+        u32s.iter().for_each(|x| *sums.get() += x.0);
+    }
+
+    Workload::new("sum")
+        .with_system(sum)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_workload("sum").unwrap();
+    world.run_workload("sum").unwrap();
+
+    world.run(|mut sums: ThreadLocal<u32>| {
+        assert_eq!(sums.iter_mut().map(|sum| *sum).sum::<u32>(), 90);
+        assert_eq!(sums.drain().sum::<u32>(), 90);
+        assert_eq!(sums.drain().sum::<u32>(), 0);
+    });
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel() {
+    use rayon::prelude::*;
+
+    let world = World::new();
+
+    world.run(|mut entities: EntitiesViewMut, mut u32s: ViewMut<U32>| {
+        for i in 0..1000 {
+            entities.add_entity(&mut u32s, U32(i));
+        }
+    });
+
+    world.run(|u32s: View<U32>, mut evens: ThreadLocal<Vec<u32>>| {
+        u32s.par_iter()
+            .filter(|x| x.0 % 2 == 0)
+            .for_each(|x| evens.get().push(x.0));
+
+        let mut evens: Vec<u32> = evens.drain().flatten().collect();
+        evens.sort_unstable();
+
+        assert_eq!(evens, (0..1000).step_by(2).collect::<Vec<_>>());
+    });
+}
+
+#[test]
+#[should_panic(expected = "ThreadLocal<u32> is already borrowed on this thread.")]
+fn double_borrow() {
+    let world = World::new();
+
+    world.run(|sums: ThreadLocal<u32>| {
+        let _first = sums.get();
+        let _second = sums.get();
+    });
+}
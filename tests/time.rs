@@ -0,0 +1,114 @@
+use shipyard::*;
+
+#[derive(Default)]
+struct Steps(u32);
+impl Unique for Steps {}
+
+fn world() -> World {
+    let world = World::new();
+    world.add_unique(Time::new(0.5));
+    world.add_unique(Steps::default());
+
+    Workload::new("fixed")
+        .with_system(|mut steps: UniqueViewMut<Steps>| steps.0 += 1)
+        .add_to_world(&world)
+        .unwrap();
+
+    world
+}
+
+#[test]
+fn accumulator_carries_over() {
+    let world = world();
+
+    world.run(|mut time: UniqueViewMut<Time>| time.advance(0.75));
+    assert_eq!(world.run_fixed_workload("fixed").unwrap(), 1);
+
+    world.run(|mut time: UniqueViewMut<Time>| time.advance(0.75));
+    assert_eq!(world.run_fixed_workload("fixed").unwrap(), 2);
+
+    world.run(|time: UniqueView<Time>, steps: UniqueView<Steps>| {
+        assert_eq!(steps.0, 3);
+        assert_eq!(time.frame_count(), 2);
+        assert_eq!(time.fixed_step_count(), 3);
+        assert_eq!(time.elapsed(), 1.5);
+        assert_eq!(time.delta(), 0.75);
+        assert_eq!(time.accumulator(), 0.0);
+    });
+}
+
+#[test]
+fn max_fixed_steps() {
+    let world = world();
+
+    world.run(|mut time: UniqueViewMut<Time>| {
+        time.set_max_fixed_steps(2);
+        time.advance(10.25);
+    });
+    assert_eq!(world.run_fixed_workload("fixed").unwrap(), 2);
+
+    world.run(|time: UniqueView<Time>| {
+        assert_eq!(time.accumulator(), 0.25);
+        assert_eq!(time.fixed_alpha(), 0.5);
+    });
+}
+
+#[test]
+fn missing_workload() {
+    let world = world();
+
+    world.run(|mut time: UniqueViewMut<Time>| time.advance(1.0));
+    assert!(world.run_fixed_workload("missing").is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn update_time_system() {
+    let world = world();
+
+    world.run(update_time);
+    world.run(update_time);
+
+    world.run(|time: UniqueView<Time>| {
+        assert_eq!(time.frame_count(), 2);
+        assert!(time.elapsed() >= 0.0);
+    });
+}
+
+#[test]
+fn every_n_frames_condition() {
+    let world = World::new();
+    world.add_unique(Steps::default());
+
+    Workload::new("frame")
+        .with_system((|mut steps: UniqueViewMut<Steps>| steps.0 += 1).run_if(every_n_frames(2)))
+        .add_to_world(&world)
+        .unwrap();
+
+    for _ in 0..5 {
+        world.run_default_workload().unwrap();
+    }
+
+    assert_eq!(world.borrow::<UniqueView<Steps>>().unwrap().0, 2);
+}
+
+#[test]
+fn every_duration_condition() {
+    let world = world();
+
+    Workload::new("slow")
+        .with_system(|mut steps: UniqueViewMut<Steps>| steps.0 += 1)
+        .run_if(every_duration(1.0))
+        .add_to_world(&world)
+        .unwrap();
+
+    let mut runs = Vec::new();
+    for _ in 0..6 {
+        world.run(|mut time: UniqueViewMut<Time>| time.advance(0.4));
+        world.run_workload("slow").unwrap();
+        runs.push(world.borrow::<UniqueView<Steps>>().unwrap().0);
+    }
+
+    // elapsed: 0.4, 0.8, 1.2, 1.6, 2.0, 2.4
+    assert_eq!(runs, [0, 0, 1, 1, 1, 2]);
+}
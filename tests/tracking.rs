@@ -1,4 +1,6 @@
-use shipyard::{error::GetStorage, track, Component, View, ViewMut, World};
+use shipyard::{
+    error::GetStorage, track, Component, Get, IntoIter, IntoWithId, Mut, View, ViewMut, World,
+};
 
 struct Unit;
 impl Component for Unit {
@@ -148,3 +150,119 @@ fn workload_enable_runtime_removal_tracking() {
     assert!(world.borrow::<View<Unit, track::Removal>>().is_ok());
     assert!(world.borrow::<ViewMut<Unit, track::Removal>>().is_ok());
 }
+
+#[test]
+fn iter_mut_flagged() {
+    struct UnitModif(u32);
+    impl Component for UnitModif {
+        type Tracking = track::Modification;
+    }
+
+    let mut world = World::new();
+
+    let entity0 = world.add_entity((Unit, UnitModif(0)));
+    let entity1 = world.add_entity((Unit, UnitModif(1)));
+
+    world.run(|mut units: ViewMut<Unit>, mut modifs: ViewMut<UnitModif>| {
+        assert!(units
+            .iter_mut_flagged()
+            .all(|(_, flag)| !flag.is_tracking()));
+
+        for (modif, mut flag) in modifs.iter_mut_flagged() {
+            modif.0 += 1;
+
+            if modif.0 == 2 {
+                flag.flag();
+            }
+        }
+
+        assert!(!modifs.is_modified(entity0));
+        assert!(modifs.is_modified(entity1));
+        assert_eq!(modifs[entity0].0, 1);
+    });
+}
+
+#[test]
+fn mut_utilities() {
+    #[derive(PartialEq)]
+    struct Pos {
+        x: u32,
+        y: u32,
+    }
+    impl Component for Pos {
+        type Tracking = track::Modification;
+    }
+
+    let mut world = World::new();
+
+    let entity0 = world.add_entity(Pos { x: 0, y: 0 });
+    let entity1 = world.add_entity(Pos { x: 1, y: 1 });
+    let entity2 = world.add_entity(Pos { x: 2, y: 2 });
+
+    let mut positions = world.borrow::<ViewMut<Pos>>().unwrap();
+
+    {
+        let mut pos = (&mut positions).get(entity0).unwrap();
+        Mut::bypass_change_detection(&mut pos).x = 10;
+        assert!(!Mut::set_if_neq(&mut pos, Pos { x: 10, y: 0 }));
+    }
+    assert!(!positions.is_modified(entity0));
+    assert_eq!(positions[entity0].x, 10);
+
+    {
+        let x = Mut::map((&mut positions).get(entity1).unwrap(), |pos| &mut pos.x);
+        assert_eq!(*x, 1);
+    }
+    assert!(!positions.is_modified(entity1));
+
+    let mut y = Mut::map((&mut positions).get(entity1).unwrap(), |pos| &mut pos.y);
+    assert!(Mut::set_if_neq(&mut y, 5));
+    assert!(positions.is_modified(entity1));
+
+    for (id, pos) in (&mut positions).iter().with_id() {
+        if id == entity2 {
+            Mut::into_inner(pos).x = 20;
+        }
+    }
+    assert!(positions.is_modified(entity2));
+    assert_eq!(positions[entity2].x, 20);
+}
+
+#[test]
+fn bulk_operations() {
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+    struct U32(u32);
+    impl Component for U32 {
+        type Tracking = track::All;
+    }
+
+    let mut world = World::new();
+
+    let entities = world
+        .bulk_add_entity((0..4u32).rev().map(|i| (U32(i), Unit)))
+        .collect::<Vec<_>>();
+
+    world.run(|u32s: ViewMut<U32>| {
+        assert_eq!(u32s.inserted().iter().count(), 4);
+        u32s.clear_all_inserted();
+    });
+
+    world.run(|mut u32s: ViewMut<U32>| {
+        *(&mut u32s).get(entities[0]).unwrap() = U32(10);
+
+        u32s.sort_unstable();
+
+        let modified = u32s.modified().iter().with_id().collect::<Vec<_>>();
+        assert_eq!(modified, [(entities[0], &U32(10))]);
+        assert_eq!(u32s.inserted().iter().count(), 0);
+
+        u32s.apply(entities[1], entities[2], |a, b| a.0 += b.0);
+        assert!(u32s.is_modified(entities[1]));
+        assert!(!u32s.is_modified(entities[2]));
+
+        let drained = u32s.drain().count();
+        assert_eq!(drained, 4);
+        assert_eq!(u32s.removed().count(), 4);
+        assert_eq!(u32s.modified().iter().count(), 0);
+    });
+}
@@ -0,0 +1,62 @@
+use shipyard::*;
+
+#[derive(Debug, PartialEq)]
+struct Mesh(u32);
+impl Component for Mesh {
+    type Tracking = track::Untracked;
+}
+
+#[derive(Debug, PartialEq)]
+struct Loading;
+impl Component for Loading {
+    type Tracking = track::Untracked;
+}
+
+#[test]
+fn transfer() {
+    let mut loading = World::new();
+    let mut live = World::new();
+
+    loading.register_transfer::<Mesh>();
+
+    live.add_entity((Mesh(0),));
+    let entity = loading.add_entity((Mesh(1), Loading));
+    let other = loading.add_entity((Mesh(2),));
+
+    let new_entity = loading.transfer(entity, &mut live);
+
+    assert!(!loading.is_entity_alive(entity));
+    assert_eq!(loading.get::<&Mesh>(other).as_deref(), Ok(&&Mesh(2)));
+    assert_eq!(live.get::<&Mesh>(new_entity).as_deref(), Ok(&&Mesh(1)));
+    assert!(live.get::<&Loading>(new_entity).is_err());
+    assert_eq!(live.borrow::<View<Mesh>>().unwrap().len(), 2);
+}
+
+#[test]
+fn transfer_all_storages() {
+    let loading = World::new();
+    let mut live = World::new();
+
+    let mut all_storages = loading.borrow::<AllStoragesViewMut>().unwrap();
+    all_storages.register_transfer::<Mesh>();
+    all_storages.register_transfer::<Loading>();
+
+    let entity = all_storages.add_entity((Loading,));
+    let new_entity = all_storages.transfer(entity, &mut live);
+
+    assert!(!all_storages.is_entity_alive(entity));
+    assert!(live.get::<&Mesh>(new_entity).is_err());
+    assert_eq!(live.get::<&Loading>(new_entity).as_deref(), Ok(&&Loading));
+}
+
+#[test]
+#[should_panic(expected = "has to be alive to move it to another World.")]
+fn dead_entity() {
+    let mut loading = World::new();
+    let mut live = World::new();
+
+    let entity = loading.add_entity(());
+    loading.delete_entity(entity);
+
+    loading.transfer(entity, &mut live);
+}
@@ -0,0 +1,157 @@
+use shipyard::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Offset(i32);
+
+impl Transform for Offset {
+    fn compose(&self, child: &Offset) -> Offset {
+        Offset(self.0 + child.0)
+    }
+}
+
+type HierarchyViewMut<'v> = (
+    EntitiesViewMut<'v>,
+    ViewMut<'v, Parent>,
+    ViewMut<'v, Children>,
+);
+
+fn world() -> World {
+    let world = World::new();
+
+    Workload::new("transforms")
+        .with_system(propagate_transforms::<Offset>)
+        .add_to_world(&world)
+        .unwrap();
+
+    world
+}
+
+fn global(world: &World, entity: EntityId) -> Option<Offset> {
+    world.run(|globals: View<GlobalTransform<Offset>>| {
+        globals.get(entity).ok().map(|global| *global.get())
+    })
+}
+
+#[test]
+fn propagation() {
+    let world = world();
+
+    let (root, a, a1, b) = world.run(
+        |mut hierarchy: HierarchyViewMut, mut locals: ViewMut<LocalTransform<Offset>>| {
+            let root = hierarchy
+                .0
+                .add_entity(&mut locals, LocalTransform(Offset(100)));
+            let a = hierarchy.attach_new(root);
+            let a1 = hierarchy.attach_new(a);
+            let b = hierarchy.attach_new(root);
+
+            hierarchy
+                .0
+                .add_component(a, &mut locals, LocalTransform(Offset(10)));
+            hierarchy
+                .0
+                .add_component(a1, &mut locals, LocalTransform(Offset(1)));
+            hierarchy
+                .0
+                .add_component(b, &mut locals, LocalTransform(Offset(20)));
+
+            (root, a, a1, b)
+        },
+    );
+
+    world.run_workload("transforms").unwrap();
+
+    assert_eq!(global(&world, root), Some(Offset(100)));
+    assert_eq!(global(&world, a), Some(Offset(110)));
+    assert_eq!(global(&world, a1), Some(Offset(111)));
+    assert_eq!(global(&world, b), Some(Offset(120)));
+
+    world.run(|mut locals: ViewMut<LocalTransform<Offset>>| {
+        locals[root].0 = Offset(200);
+    });
+
+    world.run_workload("transforms").unwrap();
+
+    assert_eq!(global(&world, a1), Some(Offset(211)));
+    assert_eq!(global(&world, b), Some(Offset(220)));
+}
+
+#[test]
+fn only_outdated_are_updated() {
+    let world = world();
+
+    let (a, b) = world.run(
+        |mut hierarchy: HierarchyViewMut, mut locals: ViewMut<LocalTransform<Offset>>| {
+            let root = hierarchy
+                .0
+                .add_entity(&mut locals, LocalTransform(Offset(0)));
+            let a = hierarchy.attach_new(root);
+            let b = hierarchy.attach_new(root);
+
+            hierarchy
+                .0
+                .add_component(a, &mut locals, LocalTransform(Offset(1)));
+            hierarchy
+                .0
+                .add_component(b, &mut locals, LocalTransform(Offset(2)));
+
+            (a, b)
+        },
+    );
+
+    world.run_workload("transforms").unwrap();
+
+    world.run(|globals: ViewMut<GlobalTransform<Offset>>| {
+        globals.clear_all_inserted_and_modified();
+    });
+    world.run(|mut locals: ViewMut<LocalTransform<Offset>>| {
+        locals[a].0 = Offset(5);
+    });
+
+    world.run_workload("transforms").unwrap();
+
+    world.run(|globals: View<GlobalTransform<Offset>>| {
+        assert!(globals.is_modified(a));
+        assert!(!globals.is_modified(b));
+        assert_eq!(*globals[a], Offset(5));
+    });
+}
+
+#[test]
+fn reparent_and_remove() {
+    let world = world();
+
+    let (root1, root2, a) = world.run(
+        |mut hierarchy: HierarchyViewMut, mut locals: ViewMut<LocalTransform<Offset>>| {
+            let root1 = hierarchy
+                .0
+                .add_entity(&mut locals, LocalTransform(Offset(100)));
+            let root2 = hierarchy
+                .0
+                .add_entity(&mut locals, LocalTransform(Offset(200)));
+            let a = hierarchy.attach_new(root1);
+
+            hierarchy
+                .0
+                .add_component(a, &mut locals, LocalTransform(Offset(1)));
+
+            (root1, root2, a)
+        },
+    );
+
+    world.run_workload("transforms").unwrap();
+    assert_eq!(global(&world, a), Some(Offset(101)));
+
+    world.run(|mut hierarchy: HierarchyViewMut| hierarchy.attach(a, root2));
+    world.run_workload("transforms").unwrap();
+    assert_eq!(global(&world, a), Some(Offset(201)));
+
+    // without a LocalTransform the parent doesn't contribute
+    world.run(|mut locals: ViewMut<LocalTransform<Offset>>| {
+        locals.remove(root2);
+    });
+    world.run_workload("transforms").unwrap();
+    assert_eq!(global(&world, a), Some(Offset(1)));
+    assert_eq!(global(&world, root2), None);
+    assert_eq!(global(&world, root1), Some(Offset(100)));
+}
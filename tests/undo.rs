@@ -0,0 +1,161 @@
+use shipyard::*;
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Position(u32);
+
+#[derive(Component, Clone, Debug, PartialEq)]
+struct Velocity(u32);
+
+#[derive(Component, Debug, PartialEq)]
+struct Unregistered(u32);
+
+fn world() -> World {
+    let mut world = World::new();
+    world.enable_undo(
+        UndoRegistry::new()
+            .register::<Position>()
+            .register::<Velocity>()
+            .clone(),
+    );
+
+    world
+}
+
+#[test]
+fn entities() {
+    let mut world = world();
+
+    let entity = world.transaction("spawn", |world| {
+        world.add_entity((Position(0), Velocity(1), Unregistered(2)))
+    });
+
+    world.transaction("delete", |world| world.delete_entity(entity));
+    assert!(!world.is_entity_alive(entity));
+
+    assert!(world.undo());
+    assert!(world.is_entity_alive(entity));
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(0)));
+    assert_eq!(world.get::<&Velocity>(entity).as_deref(), Ok(&&Velocity(1)));
+    assert!(world.get::<&Unregistered>(entity).is_err());
+
+    assert!(world.undo());
+    assert!(!world.is_entity_alive(entity));
+    assert!(!world.undo());
+
+    assert!(world.redo());
+    assert!(world.is_entity_alive(entity));
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(0)));
+
+    assert!(world.redo());
+    assert!(!world.is_entity_alive(entity));
+    assert!(!world.redo());
+}
+
+#[test]
+fn components() {
+    let mut world = world();
+
+    let entity = world.add_entity((Position(0), Velocity(0)));
+
+    world.transaction("move", |world| {
+        world.add_component(entity, Position(1));
+        world.add_component(entity, Position(2));
+    });
+    world.transaction("stop", |world| {
+        world.delete_component::<Velocity>(entity);
+    });
+    world.transaction("take", |world| {
+        assert_eq!(world.remove::<Position>(entity), Some(Position(2)));
+    });
+
+    assert!(world.undo());
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(2)));
+
+    assert!(world.undo());
+    assert_eq!(world.get::<&Velocity>(entity).as_deref(), Ok(&&Velocity(0)));
+
+    assert!(world.undo());
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(0)));
+
+    assert!(world.redo());
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(2)));
+
+    world.transaction("strip", |world| world.strip(entity));
+    assert!(world.get::<&Velocity>(entity).is_err());
+
+    assert!(world.undo());
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(2)));
+    assert_eq!(world.get::<&Velocity>(entity).as_deref(), Ok(&&Velocity(0)));
+}
+
+#[test]
+fn transactions() {
+    let mut world = world();
+
+    let entity = world.add_entity(Position(0));
+
+    world.transaction("outer", |world| {
+        world.add_component(entity, Position(1));
+
+        world.transaction("inner", |world| {
+            world.add_component(entity, Position(2));
+        });
+    });
+
+    world.begin_transaction("drag");
+    world.add_component(entity, Position(3));
+    assert!(!world.can_undo());
+    assert!(!world.undo());
+    world.add_component(entity, Position(4));
+    world.end_transaction();
+
+    world.run(|all_storages: AllStoragesView| {
+        assert_eq!(
+            all_storages.undo_names().collect::<Vec<_>>(),
+            [Some("drag"), Some("outer"), None]
+        );
+    });
+
+    assert!(world.undo());
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(2)));
+
+    assert!(world.undo());
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(0)));
+
+    world.run(|all_storages: AllStoragesView| {
+        assert_eq!(
+            all_storages.redo_names().collect::<Vec<_>>(),
+            [Some("outer"), Some("drag")]
+        );
+    });
+
+    // a new operation discards what could be redone
+    world.add_component(entity, Position(5));
+    assert!(!world.can_redo());
+    assert!(!world.redo());
+
+    assert!(world.undo());
+    assert_eq!(world.get::<&Position>(entity).as_deref(), Ok(&&Position(0)));
+}
+
+#[test]
+fn disabled() {
+    let mut world = World::new();
+
+    let entity = world.transaction("spawn", |world| world.add_entity(Position(0)));
+
+    assert!(!world.is_undo_enabled());
+    assert!(!world.undo());
+    assert!(world.is_entity_alive(entity));
+
+    let mut world = self::world();
+    world.add_entity(Position(0));
+    world.clear();
+
+    assert!(!world.can_undo());
+
+    world.add_entity(Position(0));
+    world.disable_undo();
+
+    assert!(!world.can_undo());
+}
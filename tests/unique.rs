@@ -211,3 +211,27 @@ fn unique_or_init_mut() {
 
     world.run(|u: UniqueOrInitViewMut<USIZE>| assert_eq!(**u.get().unwrap(), USIZE(11)));
 }
+
+#[test]
+fn init_unique() {
+    struct Double(usize);
+    impl Unique for Double {}
+    impl FromWorld for Double {
+        fn from_world(world: &World) -> Self {
+            Double(world.borrow::<UniqueView<USIZE>>().unwrap().0 * 2)
+        }
+    }
+
+    let world = World::new();
+
+    world.init_unique::<USIZE>();
+    world.run(|mut x: UniqueViewMut<USIZE>| x.0 = 21);
+    world.init_unique::<Double>();
+
+    assert_eq!(world.borrow::<UniqueView<Double>>().unwrap().0, 42);
+
+    // existing uniques are not replaced
+    world.init_unique::<USIZE>();
+
+    assert_eq!(*world.borrow::<UniqueView<USIZE>>().unwrap(), USIZE(21));
+}
@@ -249,3 +249,455 @@ fn skip_first() {
 
     world.run_default_workload().unwrap();
 }
+
+#[test]
+fn repeat_while() {
+    let world = World::new();
+    world.add_unique(U32(0));
+
+    Workload::new("frame")
+        .with_system(|mut u32: UniqueViewMut<U32>| u32.0 *= 10)
+        .with_workload(
+            Workload::new("substeps")
+                .with_system(|mut u32: UniqueViewMut<U32>| u32.0 += 1)
+                .repeat_while(|u32: UniqueView<U32>| u32.0 % 10 < 3, 100),
+        )
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 3);
+
+    world.run_default_workload().unwrap();
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 33);
+}
+
+#[test]
+fn repeat_while_max_iterations() {
+    let world = World::new();
+    world.add_unique(U32(0));
+
+    Workload::new("substeps")
+        .with_system(|mut u32: UniqueViewMut<U32>| u32.0 += 1)
+        .repeat_while(|| true, 4)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 4);
+}
+
+#[test]
+fn repeat_while_error() {
+    let world = World::new();
+
+    Workload::new("substeps")
+        .with_system(|_: UniqueView<U32>| {})
+        .repeat_while(|| true, 4)
+        .add_to_world(&world)
+        .unwrap();
+
+    assert!(matches!(
+        world.run_default_workload(),
+        Err(error::RunWorkload::Run(_))
+    ));
+}
+
+#[test]
+fn system_set() {
+    fn sys1(mut u32: UniqueViewMut<U32>) {
+        u32.0 += 1;
+    }
+    fn sys2(mut u32: UniqueViewMut<U32>) {
+        u32.0 += 10;
+    }
+    fn sys3(mut u32: UniqueViewMut<U32>) {
+        u32.0 += 100;
+    }
+
+    let world = World::new();
+    world.add_unique(U32(0));
+
+    Workload::new("frame")
+        .with_system(sys1)
+        .with_set(Workload::new("debug").with_system(sys2).with_system(sys3))
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 111);
+
+    world.disable_set("debug");
+    assert!(!world.is_set_enabled("debug"));
+    world.run_default_workload().unwrap();
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 112);
+
+    world.enable_set("debug");
+    world.run_default_workload().unwrap();
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 223);
+}
+
+#[test]
+fn system_set_run_if() {
+    fn sys1(mut u32: UniqueViewMut<U32>) {
+        u32.0 += 1;
+    }
+
+    let world = World::new();
+    world.add_unique(U32(0));
+
+    Workload::new("frame")
+        .with_set(Workload::new("set").with_system(sys1).run_if(|| false))
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_default_workload().unwrap();
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 0);
+}
+
+#[derive(Debug, PartialEq)]
+struct GameOver(u32);
+
+impl std::fmt::Display for GameOver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl std::error::Error for GameOver {}
+
+#[test]
+fn downcast_error() {
+    fn type_name_of<F: 'static>(_: F) -> &'static str {
+        type_name::<F>()
+    }
+
+    fn game_over() -> Result<(), GameOver> {
+        Err(GameOver(3))
+    }
+
+    let world = World::new();
+
+    world.add_workload(|| (game_over.into_workload_try_system().unwrap(),).into_workload());
+
+    let err = world.run_default_workload().unwrap_err();
+    assert!(err
+        .system_name()
+        .unwrap()
+        .dyn_eq(&*type_name_of(game_over).as_label()));
+    assert_eq!(err.runs().len(), 1);
+    assert_eq!(err.downcast_ref::<GameOver>(), Some(&GameOver(3)));
+
+    let err = err.downcast::<std::fmt::Error>().unwrap_err();
+    assert_eq!(err.downcast::<GameOver>().unwrap(), GameOver(3));
+
+    world.add_workload(|| Workload::new("missing storage").with_system(|_: UniqueView<U32>| {}));
+    let err = world.run_workload("missing storage").unwrap_err();
+    assert!(matches!(
+        err.downcast::<GameOver>(),
+        Err(error::RunWorkload::Run(_))
+    ));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn multiple_errors() {
+    fn first() -> Result<(), GameOver> {
+        Err(GameOver(1))
+    }
+    fn second() -> Result<(), GameOver> {
+        Err(GameOver(2))
+    }
+
+    let world = World::new();
+
+    world.add_workload(|| {
+        (
+            first.into_workload_try_system().unwrap(),
+            second.into_workload_try_system().unwrap(),
+        )
+            .into_workload()
+    });
+
+    let err = world.run_default_workload().unwrap_err();
+    assert_eq!(err.runs().len(), 2);
+    assert!(matches!(err, error::RunWorkload::Runs(_)));
+    assert_eq!(err.downcast_ref::<GameOver>(), Some(&GameOver(1)));
+    assert_eq!(err.downcast::<GameOver>().unwrap(), GameOver(1));
+}
+
+#[test]
+fn on_error() {
+    fn type_name_of<F: 'static>(_: F) -> &'static str {
+        type_name::<F>()
+    }
+
+    fn fail(mut u32: UniqueViewMut<U32>) -> Result<(), GameOver> {
+        u32.0 += 1;
+
+        Err(GameOver(u32.0))
+    }
+    fn after(mut usize: UniqueViewMut<USIZE>) {
+        usize.0 += 1;
+    }
+
+    let world = World::new();
+    world.add_unique(U32(0));
+    world.add_unique(USIZE(0));
+
+    Workload::new("continue")
+        .with_try_system(fail)
+        .with_system(after)
+        .on_error(|system_name, err, _| {
+            assert!(system_name.dyn_eq(&*type_name_of(fail).as_label()));
+            assert!(err.downcast_ref::<GameOver>().is_some());
+
+            Decision::Continue
+        })
+        .add_to_world(&world)
+        .unwrap();
+    world.run_workload("continue").unwrap();
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 1);
+
+    Workload::new("retry")
+        .with_try_system(fail)
+        .with_system(after)
+        .on_error(|_, err, _| match err.downcast_ref::<GameOver>() {
+            Some(GameOver(count)) if *count < 5 => Decision::Retry,
+            _ => Decision::Abort,
+        })
+        .add_to_world(&world)
+        .unwrap();
+    let err = world.run_workload("retry").unwrap_err();
+    assert_eq!(err.downcast::<GameOver>().unwrap(), GameOver(5));
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 1);
+
+    // the nested handler takes precedence
+    Workload::new("nested")
+        .with_workload(
+            Workload::new("inner")
+                .with_try_system(fail)
+                .on_error(|_, _, _| Decision::Continue),
+        )
+        .with_system(after)
+        .on_error(|_, _, _| Decision::Abort)
+        .add_to_world(&world)
+        .unwrap();
+    world.run_workload("nested").unwrap();
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 6);
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 2);
+}
+
+#[test]
+fn on_error_retry_limit() {
+    fn fail(mut u32: UniqueViewMut<U32>) -> Result<(), GameOver> {
+        u32.0 += 1;
+
+        Err(GameOver(u32.0))
+    }
+
+    let world = World::new();
+    world.add_unique(U32(0));
+
+    Workload::new("")
+        .with_try_system(fail)
+        .on_error(|_, _, _| Decision::Retry)
+        .add_to_world(&world)
+        .unwrap();
+
+    let err = world.run_workload("").unwrap_err();
+    assert_eq!(
+        err.downcast::<GameOver>().unwrap(),
+        GameOver(Decision::MAX_RETRIES + 1)
+    );
+}
+
+#[test]
+fn unique_access() {
+    fn read(_: UniqueView<U32>) {}
+    fn read_both(_: UniqueView<U32>, _: UniqueView<USIZE>) {}
+    fn write(mut usize: UniqueViewMut<USIZE>, u32: UniqueView<U32>) {
+        usize.0 = u32.0 as usize;
+    }
+
+    let world = World::new();
+    world.add_unique(U32(1));
+    world.add_unique(USIZE(0));
+
+    Workload::new("")
+        .with_system(read)
+        .with_system(read_both)
+        .with_system(write)
+        .add_to_world(&world)
+        .unwrap();
+
+    let info = &world.workloads_info().0[""];
+    assert_eq!(info.batch_info.len(), 2);
+    assert_eq!(info.batch_info[0].systems().count(), 2);
+    assert_eq!(
+        info.batch_info[0]
+            .systems()
+            .flat_map(|system| system.reads())
+            .count(),
+        3
+    );
+    assert_eq!(
+        info.batch_info[0]
+            .systems()
+            .flat_map(|system| system.writes())
+            .count(),
+        0
+    );
+
+    let write_info = info.batch_info[1].systems().next().unwrap();
+    assert_eq!(
+        write_info
+            .reads()
+            .map(|type_info| type_info.storage_id)
+            .collect::<Vec<_>>(),
+        [StorageId::of::<UniqueStorage<U32>>()]
+    );
+    assert_eq!(
+        write_info
+            .writes()
+            .map(|type_info| type_info.storage_id)
+            .collect::<Vec<_>>(),
+        [StorageId::of::<UniqueStorage<USIZE>>()]
+    );
+
+    world.run_workload("").unwrap();
+    assert_eq!(world.borrow::<UniqueView<USIZE>>().unwrap().0, 1);
+}
+
+#[test]
+fn all_storages_view_of() {
+    struct Dead;
+    impl Component for Dead {
+        type Tracking = track::Untracked;
+    }
+
+    fn clean_up(all_storages: AllStoragesViewOf<(EntitiesViewMut, ViewMut<Dead>)>) {
+        assert!(all_storages.borrow::<UniqueView<U32>>().is_err());
+
+        let (mut entities, mut dead) = all_storages.declared().unwrap();
+        let dead_entities: Vec<_> = dead.iter().ids().collect();
+
+        for entity in dead_entities {
+            dead.delete(entity);
+            entities.delete_unchecked(entity);
+        }
+    }
+    fn read(_: UniqueView<U32>) {}
+    fn exclusive(_: AllStoragesViewMut) {}
+    fn spawn(mut entities: EntitiesViewMut) {
+        entities.add_entity((), ());
+    }
+
+    let mut world = World::new();
+    world.add_unique(U32(0));
+    let entity = world.add_entity(Dead);
+
+    Workload::new("")
+        .with_system(clean_up)
+        .with_system(read)
+        .with_system(exclusive)
+        .with_system(spawn)
+        .add_to_world(&world)
+        .unwrap();
+
+    let info = &world.workloads_info().0[""];
+    assert_eq!(info.batch_info.len(), 3);
+    assert_eq!(info.batch_info[0].systems().count(), 2);
+    assert_eq!(
+        info.batch_info[1]
+            .systems()
+            .next()
+            .unwrap()
+            .writes()
+            .count(),
+        1
+    );
+    assert!(matches!(
+        &info.batch_info[2].systems().next().unwrap().conflict,
+        Some(info::Conflict::Borrow { other_type_info, .. })
+            if other_type_info.storage_id == StorageId::of::<AllStorages>()
+    ));
+
+    world.run_workload("").unwrap();
+    assert!(!world.is_entity_alive(entity));
+
+    // without declared storages it's scheduled on its own
+    Workload::new("undeclared")
+        .with_system(|_: AllStoragesViewOf<()>| {})
+        .with_system(read)
+        .add_to_world(&world)
+        .unwrap();
+    assert_eq!(world.workloads_info().0["undeclared"].batch_info.len(), 2);
+}
+
+#[test]
+fn error_stops_workload() {
+    fn fail(_: UniqueViewMut<U32>) -> Result<(), GameOver> {
+        Err(GameOver(0))
+    }
+    fn dependent(mut u32: UniqueViewMut<U32>) {
+        u32.0 += 1;
+    }
+    fn independent(mut usize: UniqueViewMut<USIZE>) {
+        usize.0 += 1;
+    }
+
+    let world = World::new();
+    world.add_unique(U32(0));
+    world.add_unique(USIZE(0));
+
+    Workload::new("")
+        .with_try_system(fail)
+        .with_system(dependent)
+        .with_system(independent)
+        .add_to_world(&world)
+        .unwrap();
+
+    let err = world.run_workload("").unwrap_err();
+    assert_eq!(err.runs().len(), 1);
+    // `dependent` waits for `fail` so it can't have started
+    // `independent` may have started alongside `fail`
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 0);
+}
+
+#[test]
+fn budget() {
+    use std::time::Duration;
+
+    fn budgeted(budget: Budget, mut u32: UniqueViewMut<U32>) {
+        assert_eq!(budget.limit(), Some(Duration::from_secs(60)));
+        assert!(!budget.is_exhausted());
+
+        u32.0 += 1;
+    }
+    fn unlimited(budget: Budget) {
+        assert_eq!(budget.limit(), None);
+        assert_eq!(budget.remaining(), Duration::MAX);
+    }
+
+    let world = World::new();
+    world.add_unique(U32(0));
+
+    Workload::new("")
+        .with_system(budgeted.budget(Duration::from_secs(60)))
+        .with_system(unlimited)
+        .add_to_world(&world)
+        .unwrap();
+
+    world.run_workload("").unwrap();
+    world.run_workload("").unwrap();
+
+    let report = world.budget_report();
+    assert_eq!(report.0.len(), 1);
+
+    let budgeted = report.0.values().next().unwrap();
+    assert_eq!(budgeted.budget, Duration::from_secs(60));
+    assert_eq!(budgeted.runs, 2);
+    assert_eq!(budgeted.overruns, 0);
+    assert_eq!(world.borrow::<UniqueView<U32>>().unwrap().0, 2);
+}
@@ -172,3 +172,44 @@ fn bulk() {
     assert_eq!(u32s.len(), 10);
     assert_eq!(usizes.len(), 10);
 }
+
+#[test]
+fn bulk_index_range() {
+    let mut world = World::new();
+
+    let entity = world.add_entity(());
+    world.delete_entity(entity);
+
+    let new_entities = world.bulk_add_entity((0..3).map(|i| (U32(i),)));
+    assert_eq!(new_entities.index_range(), 1..4);
+    assert!(new_entities
+        .as_slice()
+        .iter()
+        .all(|entity| entity.gen() == 0));
+
+    // the deleted entity is not recycled
+    let new_entities = world.bulk_add_entity((0..2).map(|_| ()));
+    assert_eq!(new_entities.index_range(), 4..6);
+
+    assert_eq!(
+        world.bulk_add_entity((0..0).map(|_| ())).index_range(),
+        0..0
+    );
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn par_bulk() {
+    let mut world = World::new();
+
+    let entities = world
+        .par_bulk_add_entity(100, |i| (U32(i as u32),))
+        .collect::<Vec<_>>();
+
+    assert_eq!(entities.len(), 100);
+
+    let u32s = world.borrow::<View<U32>>().unwrap();
+    for (i, entity) in entities.into_iter().enumerate() {
+        assert_eq!(u32s[entity], U32(i as u32));
+    }
+}
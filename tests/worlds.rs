@@ -0,0 +1,118 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+use shipyard::*;
+
+struct Counter(AtomicU32);
+
+fn increment(counter: UniqueView<Shared<Counter>>) {
+    counter.0.fetch_add(1, Ordering::Relaxed);
+}
+
+#[test]
+fn shared_unique() {
+    let mut worlds = Worlds::new();
+    worlds.insert("main", World::new());
+    let counter = worlds.share_unique(Counter(AtomicU32::new(0)));
+    worlds.insert("ui", World::new());
+
+    worlds["main"].run(increment);
+    worlds["ui"].run(increment);
+
+    assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn run_workload() {
+    let mut worlds = Worlds::new();
+    worlds.insert("main", World::new());
+    worlds.insert("ui", World::new());
+    let counter = worlds.share_unique(Counter(AtomicU32::new(0)));
+
+    Workload::new("tick")
+        .with_system(increment)
+        .add_to_world(&worlds["ui"])
+        .unwrap();
+
+    worlds.run_default_workload("ui").unwrap();
+    worlds.run_workload("ui", "tick").unwrap();
+    assert!(worlds.run_workload("main", "tick").is_err());
+
+    assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn run_workload_all() {
+    #[derive(Component)]
+    struct Score(u32);
+
+    fn add_point(mut scores: ViewMut<Score>) {
+        for score in (&mut scores).iter() {
+            score.0 += 1;
+        }
+    }
+
+    let mut worlds = Worlds::new();
+    for name in ["match 0", "match 1", "lobby"] {
+        worlds.insert(name, World::new());
+    }
+    let counter = worlds.share_unique(Counter(AtomicU32::new(0)));
+
+    for name in ["match 0", "match 1"] {
+        let world = &mut worlds[name];
+
+        Workload::new("tick")
+            .with_system(add_point)
+            .with_system(increment)
+            .add_to_world(world)
+            .unwrap();
+        world.add_entity(Score(0));
+    }
+
+    let results = worlds.run_workload_all("tick");
+
+    assert_eq!(
+        results.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+        ["match 0", "match 1", "lobby"]
+    );
+    assert!(results[0].1.is_ok());
+    assert!(results[1].1.is_ok());
+    assert!(matches!(
+        results[2].1,
+        Err(error::RunWorkload::MissingWorkload)
+    ));
+    assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+
+    worlds.run_default_workload_all();
+
+    for name in ["match 0", "match 1"] {
+        worlds[name].run(|scores: View<Score>| {
+            assert_eq!(scores.iter().map(|score| score.0).collect::<Vec<_>>(), [2]);
+        });
+    }
+}
+
+#[test]
+fn insert_remove() {
+    let mut worlds = Worlds::new();
+
+    assert!(worlds.insert("main", World::new()).is_none());
+    assert!(worlds.insert("main", World::new()).is_some());
+    worlds.insert("ui", World::new());
+
+    assert_eq!(worlds.len(), 2);
+    assert_eq!(
+        worlds.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["main", "ui"]
+    );
+
+    assert!(worlds.remove("main").is_some());
+    assert!(!worlds.contains("main"));
+    assert!(worlds.get("main").is_none());
+}
+
+#[test]
+#[should_panic(expected = "No World named \"missing\".")]
+fn missing_world() {
+    let worlds = Worlds::new();
+
+    let _ = worlds.run_default_workload("missing");
+}